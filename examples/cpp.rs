@@ -0,0 +1,500 @@
+// Copyright (c) ZeroC, Inc.
+
+//! An example backend, which generates C++ headers for the data types defined in Slice2 files.
+//!
+//! A header is generated for each source file, containing a `struct` for each Slice struct, an `enum class` for each
+//! enum, and a type alias for each type alias and custom type (custom types are mapped with `[cpp::type("...")]`).
+//! Definitions are generated in dependency order, so each type is declared before it's used.
+//!
+//! Structs don't encode themselves. Instead they provide `visit` functions, which pass each of their fields, in
+//! encoding order, to a visitor. Encoders and decoders are implemented as visitors, so they can be shared by every
+//! generated struct.
+//!
+//! This backend only supports data types: interfaces, and enums with fields, aren't generated, and neither are Slice1
+//! files.
+//!
+//! Usage: `cargo run --example cpp -- <SLICE_FILES> --output-dir <DIRECTORY>`
+
+use clap::Parser;
+use slicec::backend::{Backend, BackendOptions, IdentifierRules};
+use slicec::compilation_state::CompilationState;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::attributes::Unparsed;
+use slicec::grammar::*;
+use slicec::rendering::render_doc_comment;
+use slicec::slice_options::SliceOptions;
+use slicec::utils::casing::ReservedWords;
+use slicec::utils::code_gen_util::get_bit_sequence_size;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::exit;
+
+/// The directive of the attribute that custom types are mapped to C++ types with.
+const TYPE_ATTRIBUTE: &str = "cpp::type";
+
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char8_t",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "concept",
+    "const",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "const_cast",
+    "continue",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+pub fn main() {
+    let options = SliceOptions::parse_from(slicec::response_files::args());
+    slicec::panic_handler::install(&options);
+    slicec::logging::install(&options);
+    let backend = CppBackend::new();
+    let state = slicec::compile_with_backends(&options, &[&backend]);
+    exit(state.emit_diagnostics(&options).exit_code.into());
+}
+
+struct CppBackend {
+    reserved_words: ReservedWords,
+}
+
+impl CppBackend {
+    fn new() -> Self {
+        // Identifiers which are C++ keywords are escaped with a trailing underscore.
+        let reserved_words = ReservedWords::new(CPP_KEYWORDS, |identifier| format!("{identifier}_"));
+        CppBackend { reserved_words }
+    }
+
+    fn escape(&self, identifier: &str) -> String {
+        self.reserved_words.escape(identifier)
+    }
+}
+
+impl Backend for CppBackend {
+    fn name(&self) -> &str {
+        "cpp"
+    }
+
+    fn identifier_rules(&self) -> Option<IdentifierRules<'_>> {
+        Some(IdentifierRules {
+            reserved_words: Some(&self.reserved_words),
+            ..IdentifierRules::default()
+        })
+    }
+
+    fn custom_type_attribute(&self) -> Option<&str> {
+        Some(TYPE_ATTRIBUTE)
+    }
+
+    fn generate(&self, state: &mut CompilationState, options: &BackendOptions) {
+        let output_dir = Path::new(options.slice_options.output_dir.as_deref().unwrap_or("."));
+        let definitions = state.ast.definitions_in_dependency_order();
+
+        let files = state.files.iter().filter(|file| file.is_source);
+        let files = files.filter(|file| file.compilation_mode() == CompilationMode::Slice2);
+        let headers = files.map(|file| {
+            let definitions = definitions
+                .iter()
+                .filter(|definition| definition.span().file == file.relative_path);
+            let mut generator = HeaderGenerator::new(self, &file.relative_path);
+            definitions.for_each(|definition| generator.generate_definition(*definition));
+            (output_dir.join(header_name(&file.relative_path)), generator.finish())
+        });
+        let headers = headers.collect::<Vec<_>>();
+
+        for (path, header) in headers {
+            if let Err(error) = std::fs::write(&path, header) {
+                let path = path.display().to_string();
+                let error = Error::IO {
+                    action: "write",
+                    path,
+                    error,
+                };
+                Diagnostic::new(error).push_into(&mut state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Returns the name of the header generated for the provided Slice file. Ex: `Foo/Bar.slice` becomes `Bar.hpp`.
+fn header_name(relative_path: &str) -> String {
+    let stem = Path::new(relative_path).file_stem().unwrap_or_default();
+    format!("{}.hpp", stem.to_string_lossy())
+}
+
+/// Generates the header for a single Slice file.
+struct HeaderGenerator<'a> {
+    backend: &'a CppBackend,
+    relative_path: &'a str,
+
+    /// The headers which the generated code uses, like `<vector>` or `"Other.hpp"`.
+    includes: BTreeSet<String>,
+
+    /// The namespace that the generated code is currently in (ex: `Foo::Bar`), if it's in one.
+    namespace: Option<String>,
+
+    code: String,
+}
+
+impl<'a> HeaderGenerator<'a> {
+    fn new(backend: &'a CppBackend, relative_path: &'a str) -> Self {
+        HeaderGenerator {
+            backend,
+            relative_path,
+            includes: BTreeSet::new(),
+            namespace: None,
+            code: String::new(),
+        }
+    }
+
+    /// Returns the header, with its includes and any namespace that's still open.
+    fn finish(mut self) -> String {
+        self.close_namespace();
+
+        let mut header = format!(
+            "// Generated from '{}' by the example C++ backend.\n\n",
+            self.relative_path
+        );
+        header += "#pragma once\n\n";
+        for include in &self.includes {
+            writeln!(header, "#include {include}").unwrap();
+        }
+        if !self.includes.is_empty() {
+            header += "\n";
+        }
+        header + &self.code
+    }
+
+    fn generate_definition(&mut self, definition: &dyn Entity) {
+        match definition.concrete_entity() {
+            // Generic structs are generated through their instantiations, which are ordinary structs.
+            Entities::Struct(struct_def) if !struct_def.is_generic() => {
+                self.open_namespace(definition);
+                self.generate_struct(struct_def);
+            }
+            Entities::Enum(enum_def) if enum_def.underlying.is_some() => {
+                self.open_namespace(definition);
+                self.generate_enum(enum_def);
+            }
+            Entities::TypeAlias(type_alias) => {
+                self.open_namespace(definition);
+                self.generate_comment(type_alias.comment(), "");
+                let underlying = self.cpp_type(&type_alias.underlying);
+                let identifier = self.backend.escape(type_alias.identifier());
+                writeln!(self.code, "using {identifier} = {underlying};\n").unwrap();
+            }
+            Entities::CustomType(custom_type) => {
+                // Custom types are guaranteed to have a mapping, since we provide a custom type attribute.
+                let attribute = custom_type
+                    .attributes()
+                    .into_iter()
+                    .find(|a| a.kind.directive() == TYPE_ATTRIBUTE);
+                let mapped_type = attribute.and_then(|a| a.downcast::<Unparsed>()?.args.first().cloned());
+                if let Some(mapped_type) = mapped_type {
+                    self.open_namespace(definition);
+                    self.generate_comment(custom_type.comment(), "");
+                    let identifier = self.backend.escape(custom_type.identifier());
+                    writeln!(self.code, "using {identifier} = {mapped_type};\n").unwrap();
+                }
+            }
+            Entities::Constant(constant) => {
+                if let Ok(value) = i64::try_from(constant.value()) {
+                    self.open_namespace(definition);
+                    self.includes.insert("<cstdint>".to_owned());
+                    self.generate_comment(constant.comment(), "");
+                    let identifier = self.backend.escape(constant.identifier());
+                    writeln!(self.code, "inline constexpr std::int64_t {identifier} = {value};\n").unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn generate_struct(&mut self, struct_def: &Struct) {
+        let identifier = self.backend.escape(struct_def.identifier());
+        let fields = struct_def.fields();
+
+        self.generate_comment(struct_def.comment(), "");
+        writeln!(self.code, "struct {identifier}\n{{").unwrap();
+        for field in &fields {
+            self.generate_comment(field.comment(), "    ");
+            let field_type = self.cpp_type(&field.data_type);
+            writeln!(
+                self.code,
+                "    {field_type} {};",
+                self.backend.escape(field.identifier())
+            )
+            .unwrap();
+        }
+        if !fields.is_empty() {
+            self.code += "\n";
+        }
+
+        // Encoders and decoders need to know how the struct is encoded, in addition to its fields.
+        self.includes.insert("<cstddef>".to_owned());
+        let bit_sequence_size = get_bit_sequence_size(&fields, Encoding::Slice2);
+        writeln!(
+            self.code,
+            "    static constexpr bool is_compact = {};",
+            struct_def.is_compact
+        )
+        .unwrap();
+        writeln!(
+            self.code,
+            "    static constexpr std::size_t bit_sequence_size = {bit_sequence_size};"
+        )
+        .unwrap();
+
+        // Untagged fields are encoded in the order they're declared, followed by the tagged fields in order of their tags.
+        let (mut encoding_order, mut tagged_fields): (Vec<&Field>, Vec<&Field>) =
+            fields.iter().partition(|field| !field.is_tagged());
+        tagged_fields.sort_by_key(|field| field.tag());
+        encoding_order.extend(tagged_fields);
+
+        // We generate a const overload for encoding, and a non-const overload for decoding.
+        for qualifier in ["", " const"] {
+            self.code +=
+                "\n    /// Calls the visitor with each of this struct's fields, in the order they're encoded.\n";
+            self.code += "    template <typename Visitor>\n";
+            writeln!(self.code, "    void visit(Visitor& visitor){qualifier}\n    {{").unwrap();
+            for field in &encoding_order {
+                let field_identifier = self.backend.escape(field.identifier());
+                match field.tag() {
+                    Some(tag) => writeln!(
+                        self.code,
+                        "        visitor.tagged_field({tag}, \"{}\", {field_identifier});",
+                        field.identifier(),
+                    ),
+                    None => writeln!(
+                        self.code,
+                        "        visitor.field(\"{}\", {field_identifier});",
+                        field.identifier(),
+                    ),
+                }
+                .unwrap();
+            }
+            self.code += "    }\n";
+        }
+        self.code += "};\n\n";
+    }
+
+    fn generate_enum(&mut self, enum_def: &Enum) {
+        let identifier = self.backend.escape(enum_def.identifier());
+        let underlying = self.cpp_primitive(enum_def.underlying.as_ref().unwrap().definition());
+
+        self.generate_comment(enum_def.comment(), "");
+        writeln!(self.code, "enum class {identifier} : {underlying}\n{{").unwrap();
+        for enumerator in enum_def.enumerators() {
+            self.generate_comment(enumerator.comment(), "    ");
+            let enumerator_identifier = self.backend.escape(enumerator.identifier());
+            writeln!(self.code, "    {enumerator_identifier} = {},", enumerator.value()).unwrap();
+        }
+        self.code += "};\n\n";
+    }
+
+    fn generate_comment(&mut self, comment: Option<&DocComment>, indent: &str) {
+        let Some(comment) = comment else { return };
+        for line in render_doc_comment(comment).lines() {
+            let line = format!("{indent}/// {line}");
+            writeln!(self.code, "{}", line.trim_end()).unwrap();
+        }
+    }
+
+    /// Opens the namespace of the provided definition, closing the current namespace if it's different.
+    fn open_namespace(&mut self, definition: &dyn Entity) {
+        let namespace = self.cpp_namespace(definition);
+        if self.namespace.as_ref() == Some(&namespace) {
+            return;
+        }
+
+        self.close_namespace();
+        writeln!(self.code, "namespace {namespace}\n{{\n").unwrap();
+        self.namespace = Some(namespace);
+    }
+
+    fn close_namespace(&mut self) {
+        if let Some(namespace) = self.namespace.take() {
+            writeln!(self.code, "}} // namespace {namespace}\n").unwrap();
+        }
+    }
+
+    /// Returns the C++ namespace which corresponds to the module the provided definition is defined in.
+    fn cpp_namespace(&self, definition: &dyn Entity) -> String {
+        let segments = definition
+            .module_scope()
+            .split("::")
+            .map(|segment| self.backend.escape(segment));
+        segments.collect::<Vec<_>>().join("::")
+    }
+
+    /// Returns the fully scoped C++ identifier of the provided definition, and includes its header if it's defined in
+    /// a different file.
+    fn cpp_identifier(&mut self, definition: &dyn Entity) -> String {
+        let file = &definition.span().file;
+        if file != self.relative_path {
+            self.includes.insert(format!("\"{}\"", header_name(file)));
+        }
+        let namespace = self.cpp_namespace(definition);
+        format!("::{namespace}::{}", self.backend.escape(definition.identifier()))
+    }
+
+    /// Returns the C++ type that the provided type reference is mapped to, and includes any headers it requires.
+    fn cpp_type(&mut self, type_ref: &TypeRef) -> String {
+        let mapped_type = match type_ref.type_aliases().first() {
+            Some(type_alias) => self.cpp_identifier(*type_alias),
+            None => match type_ref.concrete_type() {
+                Types::Struct(struct_def) => self.cpp_identifier(struct_def),
+                Types::Enum(enum_def) => self.cpp_identifier(enum_def),
+                Types::CustomType(custom_type) => self.cpp_identifier(custom_type),
+                Types::Class(class_def) => self.cpp_identifier(class_def),
+                Types::ResultType(result_type) => {
+                    self.includes.insert("<variant>".to_owned());
+                    let success_type = self.cpp_type(&result_type.success_type);
+                    let failure_type = self.cpp_type(&result_type.failure_type);
+                    format!("std::variant<{success_type}, {failure_type}>")
+                }
+                Types::Sequence(sequence) => {
+                    let element_type = self.cpp_type(&sequence.element_type);
+                    // Sequences with a fixed size are mapped to arrays, which don't need to allocate.
+                    match type_ref.size_bounds() {
+                        Some(size) if size.is_fixed() => {
+                            self.includes.insert("<array>".to_owned());
+                            format!("std::array<{element_type}, {}>", size.max)
+                        }
+                        _ => {
+                            self.includes.insert("<vector>".to_owned());
+                            format!("std::vector<{element_type}>")
+                        }
+                    }
+                }
+                Types::Dictionary(dictionary) => {
+                    self.includes.insert("<map>".to_owned());
+                    let key_type = self.cpp_type(&dictionary.key_type);
+                    let value_type = self.cpp_type(&dictionary.value_type);
+                    format!("std::map<{key_type}, {value_type}>")
+                }
+                Types::Primitive(primitive) => self.cpp_primitive(primitive),
+            },
+        };
+
+        // Boxed types can form cycles, so they're held by pointer, which can also represent a missing value.
+        if type_ref.is_boxed() {
+            self.includes.insert("<memory>".to_owned());
+            format!("std::shared_ptr<{mapped_type}>")
+        } else if type_ref.is_optional {
+            self.includes.insert("<optional>".to_owned());
+            format!("std::optional<{mapped_type}>")
+        } else {
+            mapped_type
+        }
+    }
+
+    fn cpp_primitive(&mut self, primitive: &Primitive) -> String {
+        let (mapped_type, includes): (_, &[&str]) = match primitive {
+            Primitive::Bool => ("bool", &[]),
+            Primitive::Int8 => ("std::int8_t", &["<cstdint>"]),
+            Primitive::UInt8 => ("std::uint8_t", &["<cstdint>"]),
+            Primitive::Int16 => ("std::int16_t", &["<cstdint>"]),
+            Primitive::UInt16 => ("std::uint16_t", &["<cstdint>"]),
+            Primitive::Int32 | Primitive::VarInt32 => ("std::int32_t", &["<cstdint>"]),
+            Primitive::UInt32 | Primitive::VarUInt32 => ("std::uint32_t", &["<cstdint>"]),
+            Primitive::Int64 | Primitive::VarInt62 => ("std::int64_t", &["<cstdint>"]),
+            Primitive::UInt64 | Primitive::VarUInt62 => ("std::uint64_t", &["<cstdint>"]),
+            Primitive::Float32 => ("float", &[]),
+            Primitive::Float64 => ("double", &[]),
+            Primitive::String => ("std::string", &["<string>"]),
+            Primitive::Uuid => ("std::array<std::uint8_t, 16>", &["<array>", "<cstdint>"]),
+            Primitive::Timestamp => ("std::chrono::sys_time<std::chrono::microseconds>", &["<chrono>"]),
+            Primitive::Duration => ("std::chrono::microseconds", &["<chrono>"]),
+            // Classes can only be used in Slice1 files, which this backend doesn't generate.
+            Primitive::AnyClass => unreachable!("AnyClass can only be used in Slice1 mode"),
+        };
+
+        self.includes
+            .extend(includes.iter().map(|include| (*include).to_owned()));
+        mapped_type.to_owned()
+    }
+}
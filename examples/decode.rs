@@ -0,0 +1,36 @@
+// Copyright (c) ZeroC, Inc.
+
+use clap::{Parser, Subcommand};
+use slicec::codec::decode_payload;
+use slicec::slice_options::DecodeOptions;
+use std::process::exit;
+
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Decode a hex-encoded payload against a Slice type or operation, and print the decoded value.
+    Decode(DecodeOptions),
+}
+
+pub fn main() {
+    let Command::Decode(options) = Cli::parse_from(slicec::response_files::args()).command;
+    slicec::panic_handler::install(&options.slice_options);
+    slicec::logging::install(&options.slice_options);
+    let state = slicec::compile_from_options(&options.slice_options, |_| {}, |_| {});
+
+    if !state.diagnostics.has_errors() {
+        match decode_payload(&options, &state) {
+            Ok(value) => println!("{value}"),
+            Err(error) => {
+                eprintln!("error: failed to decode '{}': {error}", options.type_name);
+                exit(1);
+            }
+        }
+    }
+    exit(state.emit_diagnostics(&options.slice_options).exit_code.into());
+}
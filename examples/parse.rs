@@ -5,7 +5,9 @@ use slicec::slice_options::SliceOptions;
 use std::process::exit;
 
 pub fn main() {
-    let options = SliceOptions::parse();
+    let options = SliceOptions::parse_from(slicec::response_files::args());
+    slicec::panic_handler::install(&options);
+    slicec::logging::install(&options);
     let state = slicec::compile_from_options(&options, |_| {}, |_| {});
-    exit(i32::from(state.emit_diagnostics(&options)));
+    exit(state.emit_diagnostics(&options).exit_code.into());
 }
@@ -0,0 +1,539 @@
+// Copyright (c) ZeroC, Inc.
+
+//! An example backend, which generates Python dataclasses and enums for the data types defined in Slice2 files.
+//!
+//! Each Slice module is generated as a Python package, mirroring the module's nesting: the definitions in module
+//! `Demo::Shapes` are generated into `demo/shapes/__init__.py`, from every source file which contributes to the module.
+//! Each struct is generated as a dataclass with type hints, each enum as an `IntEnum` (or `IntFlag`), and each type
+//! alias as a `TypeAlias`. Custom types are mapped with `[python::type("...")]`.
+//!
+//! Any definition, field, or enumerator can be renamed with `[python::name("...")]`. Otherwise, fields are converted
+//! to snake case, enumerators and constants to upper snake case, and packages to snake case.
+//!
+//! Structs have `encode` and `decode` functions, which encode their fields with the `Encoder` and `Decoder` of a
+//! separate runtime package. Its name is set with `-X python:runtime=<PACKAGE>`, and defaults to `slice_runtime`.
+//!
+//! This backend only supports data types: interfaces, and enums with fields, aren't generated, and neither are Slice1
+//! files.
+//!
+//! Usage: `cargo run --example python -- <SLICE_FILES> --output-dir <DIRECTORY>`
+
+use clap::Parser;
+use slicec::backend::{Backend, BackendOptions};
+use slicec::compilation_state::CompilationState;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::attributes::Unparsed;
+use slicec::grammar::*;
+use slicec::rendering::render_doc_comment;
+use slicec::slice_options::SliceOptions;
+use slicec::utils::attribute_parsing_util::check_that_exactly_one_argument_was_provided;
+use slicec::utils::casing::{to_snake_case, ReservedWords};
+use slicec::utils::code_gen_util::{get_bit_sequence_size, is_element_bit_sequence_encodable};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+/// The directive of the attribute that Slice identifiers are renamed with.
+const NAME_ATTRIBUTE: &str = "python::name";
+
+/// The directive of the attribute that custom types are mapped to Python types with.
+const TYPE_ATTRIBUTE: &str = "python::type";
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+pub fn main() {
+    let options = SliceOptions::parse_from(slicec::response_files::args());
+    slicec::panic_handler::install(&options);
+    slicec::logging::install(&options);
+    let backend = PythonBackend::new();
+    let state = slicec::compile_with_backends(&options, &[&backend]);
+    exit(state.emit_diagnostics(&options).exit_code.into());
+}
+
+struct PythonBackend {
+    reserved_words: ReservedWords,
+}
+
+impl PythonBackend {
+    fn new() -> Self {
+        // Identifiers which are Python keywords are escaped with a trailing underscore, as recommended by PEP 8.
+        let reserved_words = ReservedWords::new(PYTHON_KEYWORDS, |identifier| format!("{identifier}_"));
+        PythonBackend { reserved_words }
+    }
+
+    /// Returns the Python identifier of the provided entity: its `python::name` if it has one, or its Slice identifier
+    /// converted by `convert` otherwise. Either way, identifiers which are Python keywords are escaped.
+    fn identifier_of(&self, entity: &dyn Entity, convert: fn(&str) -> String) -> String {
+        let identifier = match name_override_of(entity) {
+            Some(name) => name.to_owned(),
+            None => convert(entity.identifier()),
+        };
+        self.reserved_words.escape(&identifier)
+    }
+
+    /// Returns the name of the Python package that the provided entity's module is generated as. Ex: `demo.shapes`.
+    fn package_of(&self, entity: &dyn Entity) -> String {
+        let segments = entity.module_scope().split("::");
+        let segments = segments.map(|segment| self.reserved_words.escape(&to_snake_case(segment)));
+        segments.collect::<Vec<_>>().join(".")
+    }
+}
+
+impl Backend for PythonBackend {
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn custom_type_attribute(&self) -> Option<&str> {
+        Some(TYPE_ATTRIBUTE)
+    }
+
+    /// Reports any `python::name` attributes which don't have exactly one argument, or whose argument isn't a valid
+    /// Python identifier.
+    fn validate(&self, state: &mut CompilationState, _: &BackendOptions) {
+        let entities = state
+            .ast
+            .as_slice()
+            .iter()
+            .filter_map(|node| <&dyn Entity>::try_from(node).ok());
+        for entity in entities {
+            for attribute in entity.attributes() {
+                let Some(unparsed) = attribute.downcast::<Unparsed>() else { continue };
+                if unparsed.directive != NAME_ATTRIBUTE {
+                    continue;
+                }
+
+                let diagnostics = &mut state.diagnostics;
+                check_that_exactly_one_argument_was_provided(
+                    &unparsed.args,
+                    NAME_ATTRIBUTE,
+                    &attribute.span,
+                    diagnostics,
+                );
+                if let Some(name) = unparsed.args.first().filter(|name| !is_python_identifier(name)) {
+                    Diagnostic::new(Error::ArgumentNotSupported {
+                        argument: name.clone(),
+                        directive: NAME_ATTRIBUTE.to_owned(),
+                    })
+                    .set_span(&unparsed.arg_spans[0])
+                    .add_note("names must be valid Python identifiers", None)
+                    .push_into(diagnostics);
+                }
+            }
+        }
+    }
+
+    fn generate(&self, state: &mut CompilationState, options: &BackendOptions) {
+        let output_dir = Path::new(options.slice_options.output_dir.as_deref().unwrap_or("."));
+        let runtime = options.get("runtime").unwrap_or("slice_runtime");
+
+        // Definitions are grouped by the package they're generated into, and kept in dependency order within it.
+        let source_files = state.files.iter().filter(|file| file.is_source);
+        let source_files = source_files.filter(|file| file.compilation_mode() == CompilationMode::Slice2);
+        let source_files = source_files.map(|file| file.relative_path.as_str()).collect::<Vec<_>>();
+        let mut packages: BTreeMap<String, Vec<&dyn Entity>> = BTreeMap::new();
+        for definition in state.ast.definitions_in_dependency_order() {
+            if source_files.contains(&definition.span().file.as_str()) {
+                packages
+                    .entry(self.package_of(definition))
+                    .or_default()
+                    .push(definition);
+            }
+        }
+
+        let mut outputs = BTreeMap::new();
+        for (package, definitions) in packages {
+            let mut generator = PackageGenerator::new(self, &package, runtime);
+            definitions
+                .iter()
+                .for_each(|definition| generator.generate_definition(*definition));
+
+            // Every package enclosing a generated package also needs an `__init__.py`, even if it's empty.
+            let mut path = output_dir.to_owned();
+            for segment in package.split('.') {
+                path.push(segment);
+                outputs.entry(path.join("__init__.py")).or_insert_with(String::new);
+            }
+            outputs.insert(path.join("__init__.py"), generator.finish());
+        }
+
+        for (path, contents) in outputs {
+            if let Err(error) = write_file(&path, &contents) {
+                let path = path.display().to_string();
+                let error = Error::IO {
+                    action: "write",
+                    path,
+                    error,
+                };
+                Diagnostic::new(error).push_into(&mut state.diagnostics);
+            }
+        }
+    }
+}
+
+fn write_file(path: &PathBuf, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// Returns the argument of the provided entity's `python::name` attribute, if it has one.
+fn name_override_of(entity: &dyn Entity) -> Option<&str> {
+    let attribute = entity
+        .attributes()
+        .into_iter()
+        .find(|a| a.kind.directive() == NAME_ATTRIBUTE)?;
+    attribute.downcast::<Unparsed>()?.args.first().map(String::as_str)
+}
+
+fn is_python_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_correctly = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_');
+    starts_correctly && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn to_upper_snake_case(identifier: &str) -> String {
+    to_snake_case(identifier).to_uppercase()
+}
+
+/// Generates the `__init__.py` of a single package.
+struct PackageGenerator<'a> {
+    backend: &'a PythonBackend,
+    package: &'a str,
+    runtime: &'a str,
+
+    /// The modules which the generated code uses, like `enum` or `demo.shapes`.
+    imports: BTreeSet<String>,
+
+    code: String,
+}
+
+impl<'a> PackageGenerator<'a> {
+    fn new(backend: &'a PythonBackend, package: &'a str, runtime: &'a str) -> Self {
+        let mut imports = BTreeSet::new();
+        imports.insert(runtime.to_owned());
+        PackageGenerator {
+            backend,
+            package,
+            runtime,
+            imports,
+            code: String::new(),
+        }
+    }
+
+    /// Returns the contents of the package, with its imports.
+    fn finish(self) -> String {
+        let mut contents = "# Generated by the example Python backend.\n\n".to_owned();
+        // Postponing the evaluation of annotations lets types refer to types which are defined after them.
+        contents += "from __future__ import annotations\n\n";
+        for import in &self.imports {
+            writeln!(contents, "import {import}").unwrap();
+        }
+        contents + &self.code
+    }
+
+    fn generate_definition(&mut self, definition: &dyn Entity) {
+        match definition.concrete_entity() {
+            // Generic structs are generated through their instantiations, which are ordinary structs.
+            Entities::Struct(struct_def) if !struct_def.is_generic() => self.generate_struct(struct_def),
+            Entities::Enum(enum_def) if enum_def.underlying.is_some() => self.generate_enum(enum_def),
+            Entities::TypeAlias(type_alias) => {
+                self.imports.insert("typing".to_owned());
+                let identifier = self.backend.identifier_of(type_alias, str::to_owned);
+                let underlying = self.type_hint(&type_alias.underlying);
+                writeln!(self.code, "\n\n{identifier}: typing.TypeAlias = \"{underlying}\"").unwrap();
+                self.generate_docstring(type_alias.comment(), "");
+            }
+            Entities::Constant(constant) => {
+                self.imports.insert("typing".to_owned());
+                let identifier = self.backend.identifier_of(constant, to_upper_snake_case);
+                writeln!(self.code, "\n\n{identifier}: typing.Final = {}", constant.value()).unwrap();
+                self.generate_docstring(constant.comment(), "");
+            }
+            _ => {}
+        }
+    }
+
+    fn generate_struct(&mut self, struct_def: &Struct) {
+        self.imports.insert("dataclasses".to_owned());
+        let identifier = self.backend.identifier_of(struct_def, str::to_owned);
+        let fields = struct_def.fields();
+        let runtime = self.runtime;
+
+        writeln!(self.code, "\n\n@dataclasses.dataclass\nclass {identifier}:").unwrap();
+        self.generate_docstring(struct_def.comment(), "    ");
+        for field in &fields {
+            let field_identifier = self.backend.identifier_of(*field, to_snake_case);
+            let type_hint = self.type_hint(&field.data_type);
+            writeln!(self.code, "    {field_identifier}: {type_hint}").unwrap();
+        }
+
+        // Untagged fields are encoded in the order they're declared, followed by the tagged fields in order of their tags.
+        // Untagged optional fields are preceded by a bit sequence, which records which of them are set.
+        let (untagged_fields, mut tagged_fields): (Vec<&Field>, Vec<&Field>) =
+            fields.iter().partition(|field| !field.is_tagged());
+        tagged_fields.sort_by_key(|field| field.tag());
+        let bit_sequence_size = get_bit_sequence_size(&fields, Encoding::Slice2);
+
+        writeln!(self.code, "\n    def encode(self, encoder: {runtime}.Encoder) -> None:").unwrap();
+        if bit_sequence_size > 0 {
+            writeln!(
+                self.code,
+                "        bit_sequence = encoder.encode_bit_sequence({bit_sequence_size})"
+            )
+            .unwrap();
+        }
+        for field in &untagged_fields {
+            let value = format!("self.{}", self.backend.identifier_of(*field, to_snake_case));
+            let encode = self.encode_expression(&field.data_type, &value);
+            match field.data_type.is_optional {
+                true => writeln!(
+                    self.code,
+                    "        if bit_sequence.write({value} is not None):\n            {encode}"
+                ),
+                false => writeln!(self.code, "        {encode}"),
+            }
+            .unwrap();
+        }
+        for field in &tagged_fields {
+            let value = format!("self.{}", self.backend.identifier_of(*field, to_snake_case));
+            let encode = self.encode_expression(&field.data_type, "v");
+            let tag = field.tag().unwrap();
+            writeln!(
+                self.code,
+                "        encoder.encode_tagged({tag}, {value}, lambda encoder, v: {encode})"
+            )
+            .unwrap();
+        }
+        if !struct_def.is_compact {
+            self.code += "        encoder.encode_tag_end_marker()\n";
+        }
+        if fields.is_empty() && struct_def.is_compact {
+            self.code += "        pass\n";
+        }
+
+        // Keyword arguments are evaluated in order, so the fields are decoded in the same order they're encoded in.
+        writeln!(
+            self.code,
+            "\n    @classmethod\n    def decode(cls, decoder: {runtime}.Decoder) -> {identifier}:"
+        )
+        .unwrap();
+        if bit_sequence_size > 0 {
+            writeln!(
+                self.code,
+                "        bit_sequence = decoder.decode_bit_sequence({bit_sequence_size})"
+            )
+            .unwrap();
+        }
+        self.code += "        value = cls(\n";
+        for field in &untagged_fields {
+            let field_identifier = self.backend.identifier_of(*field, to_snake_case);
+            let decode = self.decode_expression(&field.data_type);
+            match field.data_type.is_optional {
+                true => writeln!(
+                    self.code,
+                    "            {field_identifier}={decode} if bit_sequence.read() else None,"
+                ),
+                false => writeln!(self.code, "            {field_identifier}={decode},"),
+            }
+            .unwrap();
+        }
+        for field in &tagged_fields {
+            let field_identifier = self.backend.identifier_of(*field, to_snake_case);
+            let decode = self.decode_expression(&field.data_type);
+            let tag = field.tag().unwrap();
+            writeln!(
+                self.code,
+                "            {field_identifier}=decoder.decode_tagged({tag}, lambda decoder: {decode}),"
+            )
+            .unwrap();
+        }
+        self.code += "        )\n";
+        if !struct_def.is_compact {
+            self.code += "        decoder.skip_tagged_fields()\n";
+        }
+        self.code += "        return value\n";
+    }
+
+    fn generate_enum(&mut self, enum_def: &Enum) {
+        self.imports.insert("enum".to_owned());
+        let identifier = self.backend.identifier_of(enum_def, str::to_owned);
+        let base = if enum_def.is_flags() { "IntFlag" } else { "IntEnum" };
+
+        writeln!(self.code, "\n\nclass {identifier}(enum.{base}):").unwrap();
+        self.generate_docstring(enum_def.comment(), "    ");
+        for enumerator in enum_def.enumerators() {
+            let enumerator_identifier = self.backend.identifier_of(enumerator, to_upper_snake_case);
+            writeln!(self.code, "    {enumerator_identifier} = {}", enumerator.value()).unwrap();
+        }
+    }
+
+    fn generate_docstring(&mut self, comment: Option<&DocComment>, indent: &str) {
+        let Some(comment) = comment else { return };
+        let comment = render_doc_comment(comment).replace("\"\"\"", "\\\"\\\"\\\"");
+        let lines = comment
+            .lines()
+            .map(|line| format!("{indent}{line}").trim_end().to_owned());
+        let lines = lines.collect::<Vec<_>>();
+        writeln!(self.code, "{indent}\"\"\"{}\"\"\"", lines.join("\n").trim_start()).unwrap();
+    }
+
+    /// Returns the Python identifier of the provided definition, qualified by its package if it's in another package.
+    fn qualified_identifier(&mut self, definition: &dyn Entity) -> String {
+        let identifier = self.backend.identifier_of(definition, str::to_owned);
+        let package = self.backend.package_of(definition);
+        if package == self.package {
+            return identifier;
+        }
+
+        // Packages are imported whole, and only their contents are qualified, so packages can depend on each other.
+        self.imports.insert(package.clone());
+        format!("{package}.{identifier}")
+    }
+
+    /// Returns the Python type (or type alias) that the provided custom type is mapped to, importing its module.
+    fn custom_type(&mut self, custom_type: &CustomType) -> String {
+        // Custom types are guaranteed to have a mapping, since we provide a custom type attribute.
+        let attribute = custom_type
+            .attributes()
+            .into_iter()
+            .find(|a| a.kind.directive() == TYPE_ATTRIBUTE);
+        let mapped_type = attribute.and_then(|a| a.downcast::<Unparsed>()?.args.first().cloned());
+        let mapped_type = mapped_type.unwrap_or_else(|| "typing.Any".to_owned());
+        if let Some((module, _)) = mapped_type.rsplit_once('.') {
+            self.imports.insert(module.to_owned());
+        }
+        mapped_type
+    }
+
+    /// Returns the type hint of the provided type reference, importing any modules it uses.
+    fn type_hint(&mut self, type_ref: &TypeRef) -> String {
+        let type_hint = match type_ref.type_aliases().first() {
+            Some(type_alias) => self.qualified_identifier(*type_alias),
+            None => match type_ref.concrete_type() {
+                Types::Struct(struct_def) => self.qualified_identifier(struct_def),
+                Types::Enum(enum_def) => self.qualified_identifier(enum_def),
+                Types::Class(class_def) => self.qualified_identifier(class_def),
+                Types::CustomType(custom_type) => self.custom_type(custom_type),
+                Types::ResultType(result_type) => {
+                    let success_type = self.type_hint(&result_type.success_type);
+                    let failure_type = self.type_hint(&result_type.failure_type);
+                    format!("{}.Result[{success_type}, {failure_type}]", self.runtime)
+                }
+                Types::Sequence(sequence) => format!("list[{}]", self.type_hint(&sequence.element_type)),
+                Types::Dictionary(dictionary) => {
+                    let key_type = self.type_hint(&dictionary.key_type);
+                    let value_type = self.type_hint(&dictionary.value_type);
+                    format!("dict[{key_type}, {value_type}]")
+                }
+                Types::Primitive(primitive) => self.primitive_type_hint(primitive),
+            },
+        };
+
+        match type_ref.is_optional {
+            true => format!("{type_hint} | None"),
+            false => type_hint,
+        }
+    }
+
+    fn primitive_type_hint(&mut self, primitive: &Primitive) -> String {
+        let (type_hint, import) = match primitive {
+            Primitive::Bool => ("bool", None),
+            Primitive::Float32 | Primitive::Float64 => ("float", None),
+            Primitive::String => ("str", None),
+            Primitive::Uuid => ("uuid.UUID", Some("uuid")),
+            Primitive::Timestamp => ("datetime.datetime", Some("datetime")),
+            Primitive::Duration => ("datetime.timedelta", Some("datetime")),
+            // Classes can only be used in Slice1 files, which this backend doesn't generate.
+            Primitive::AnyClass => unreachable!("AnyClass can only be used in Slice1 mode"),
+            _ => ("int", None),
+        };
+
+        if let Some(import) = import {
+            self.imports.insert(import.to_owned());
+        }
+        type_hint.to_owned()
+    }
+
+    /// Returns an expression which encodes the provided (non-optional) value with `encoder`.
+    fn encode_expression(&mut self, type_ref: &TypeRef, value: &str) -> String {
+        match type_ref.concrete_type() {
+            Types::Struct(_) | Types::Class(_) | Types::CustomType(_) => format!("{value}.encode(encoder)"),
+            Types::Enum(enum_def) => {
+                let underlying = enum_def.underlying.as_ref().unwrap().definition().kind();
+                format!("encoder.encode_{underlying}({value}.value)")
+            }
+            Types::ResultType(result_type) => format!(
+                "encoder.encode_result({value}, lambda encoder, v: {}, lambda encoder, v: {})",
+                self.encode_expression(&result_type.success_type, "v"),
+                self.encode_expression(&result_type.failure_type, "v"),
+            ),
+            Types::Sequence(sequence) => {
+                let element_type = &sequence.element_type;
+                let function = match is_element_bit_sequence_encodable(element_type, Encoding::Slice2) {
+                    true => "encode_sequence_with_optional_elements",
+                    false => "encode_sequence",
+                };
+                let encode = self.encode_expression(element_type, "v");
+                format!("encoder.{function}({value}, lambda encoder, v: {encode})")
+            }
+            Types::Dictionary(dictionary) => {
+                let value_type = &dictionary.value_type;
+                let function = match is_element_bit_sequence_encodable(value_type, Encoding::Slice2) {
+                    true => "encode_dictionary_with_optional_values",
+                    false => "encode_dictionary",
+                };
+                let encode_key = self.encode_expression(&dictionary.key_type, "k");
+                let encode_value = self.encode_expression(value_type, "v");
+                format!(
+                    "encoder.{function}({value}, lambda encoder, k: {encode_key}, lambda encoder, v: {encode_value})"
+                )
+            }
+            Types::Primitive(primitive) => format!("encoder.encode_{}({value})", primitive.kind()),
+        }
+    }
+
+    /// Returns an expression which decodes a (non-optional) value of the provided type with `decoder`.
+    fn decode_expression(&mut self, type_ref: &TypeRef) -> String {
+        match type_ref.concrete_type() {
+            Types::Struct(struct_def) => format!("{}.decode(decoder)", self.qualified_identifier(struct_def)),
+            Types::Class(class_def) => format!("{}.decode(decoder)", self.qualified_identifier(class_def)),
+            Types::CustomType(custom_type) => format!("{}.decode(decoder)", self.custom_type(custom_type)),
+            Types::Enum(enum_def) => {
+                let underlying = enum_def.underlying.as_ref().unwrap().definition().kind();
+                format!("{}(decoder.decode_{underlying}())", self.qualified_identifier(enum_def))
+            }
+            Types::ResultType(result_type) => format!(
+                "decoder.decode_result(lambda decoder: {}, lambda decoder: {})",
+                self.decode_expression(&result_type.success_type),
+                self.decode_expression(&result_type.failure_type),
+            ),
+            Types::Sequence(sequence) => {
+                let element_type = &sequence.element_type;
+                let function = match is_element_bit_sequence_encodable(element_type, Encoding::Slice2) {
+                    true => "decode_sequence_with_optional_elements",
+                    false => "decode_sequence",
+                };
+                let decode = self.decode_expression(element_type);
+                format!("decoder.{function}(lambda decoder: {decode})")
+            }
+            Types::Dictionary(dictionary) => {
+                let value_type = &dictionary.value_type;
+                let function = match is_element_bit_sequence_encodable(value_type, Encoding::Slice2) {
+                    true => "decode_dictionary_with_optional_values",
+                    false => "decode_dictionary",
+                };
+                let decode_key = self.decode_expression(&dictionary.key_type);
+                let decode_value = self.decode_expression(value_type);
+                format!("decoder.{function}(lambda decoder: {decode_key}, lambda decoder: {decode_value})")
+            }
+            Types::Primitive(primitive) => format!("decoder.decode_{}()", primitive.kind()),
+        }
+    }
+}
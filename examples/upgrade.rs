@@ -0,0 +1,50 @@
+// Copyright (c) ZeroC, Inc.
+
+use clap::{Parser, Subcommand};
+use slicec::slice_options::UpgradeOptions;
+use slicec::upgrade::upgrade_files;
+use std::process::exit;
+
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Rewrite Slice files written with older versions of the Slice syntax to use the current syntax.
+    Upgrade(UpgradeOptions),
+}
+
+pub fn main() {
+    let Command::Upgrade(options) = Cli::parse_from(slicec::response_files::args()).command;
+    let upgrades = match upgrade_files(&options) {
+        Ok(upgrades) => upgrades,
+        Err(error) => {
+            eprintln!("error: failed to upgrade files: {error}");
+            exit(1);
+        }
+    };
+
+    let mut has_issues = false;
+    for (path, upgrade) in upgrades {
+        for change in &upgrade.changes {
+            eprintln!(
+                "{path}:{}:{}: {}",
+                change.location.row, change.location.col, change.description
+            );
+        }
+        for issue in &upgrade.issues {
+            eprintln!(
+                "{path}:{}:{}: warning: {}",
+                issue.location.row, issue.location.col, issue.description
+            );
+            has_issues = true;
+        }
+        if !options.in_place {
+            print!("{}", upgrade.text);
+        }
+    }
+    exit(i32::from(has_issues));
+}
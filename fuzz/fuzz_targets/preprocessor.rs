@@ -0,0 +1,9 @@
+// Copyright (c) ZeroC, Inc.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = slicec::fuzzing::preprocess(input);
+});
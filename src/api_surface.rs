@@ -0,0 +1,244 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module distills Slice files down to their public API surface, for publishing an API to its consumers without
+//! sharing the definitions that are only used by its implementation.
+//!
+//! A file's API surface is a copy of its text with every internal definition (see [Visibility]) removed, along with
+//! the doc comment and attributes written before it. Everything else is kept exactly as written, including the file's
+//! mode, attributes, and using directives. Optionally, comments can be removed as well.
+//!
+//! Surfaces are computed from the text of files, so they can be written back out as Slice files. This is done by the
+//! [ApiSurfaceBackend], which compilers can register like any other [Backend].
+
+use crate::backend::{Backend, BackendOptions};
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::{Diagnostic, Error};
+use crate::grammar::*;
+use crate::lossless::{tokenize, SyntaxKind, SyntaxToken};
+use crate::slice_file::{Location, SliceFile};
+use std::path::{Component, Path};
+use std::{fs, io};
+
+/// Returns the public API surface of the provided file: its text without any of its internal definitions.
+/// If `strip_comments` is true, comments (including doc comments) are removed too. Runs of blank lines left behind by
+/// removed text are collapsed into a single blank line.
+///
+/// If the file doesn't contain any public definitions, this returns `None`, since it has nothing to publish.
+pub fn public_api_surface(slice_file: &SliceFile, strip_comments: bool) -> Option<String> {
+    let definitions = written_definitions(slice_file);
+    if !definitions
+        .iter()
+        .any(|definition| definition.visibility() == Visibility::Public)
+    {
+        return None;
+    }
+
+    let tokens = tokenize(&slice_file.raw_text, &slice_file.relative_path).collect::<Vec<_>>();
+    let starts = definitions
+        .iter()
+        .map(|definition| start_of_definition(*definition, &tokens))
+        .collect::<Vec<_>>();
+
+    // Each definition extends until the next one starts, minus any trivia, doc comments, or preprocessor directives
+    // between them. Only the internal definitions are removed; the text between definitions is always kept.
+    let mut text = String::new();
+    let mut position = 0;
+    for (i, definition) in definitions.iter().enumerate() {
+        let next_start = starts.get(i + 1).copied().unwrap_or(tokens.len());
+        let end = end_of_definition(starts[i], next_start, &tokens);
+        if definition.visibility() == Visibility::Internal {
+            tokens[position..starts[i]]
+                .iter()
+                .for_each(|token| text.push_str(&token.text));
+            position = end;
+        }
+    }
+    tokens[position..].iter().for_each(|token| text.push_str(&token.text));
+
+    if strip_comments {
+        text = remove_comments(&text);
+    }
+    Some(collapse_blank_lines(&text))
+}
+
+/// A backend which writes the public API surface of each source file to the output directory. Files are written with
+/// the same relative paths as their source files, and files without any public definitions aren't written.
+///
+/// It accepts a single option: `-X api:strip-comments`, which removes comments from the written files.
+#[derive(Debug, Default)]
+pub struct ApiSurfaceBackend;
+
+impl ApiSurfaceBackend {
+    /// The name this backend is selected with: `--target api`.
+    pub const NAME: &'static str = "api";
+}
+
+impl Backend for ApiSurfaceBackend {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn generate(&self, state: &mut CompilationState, options: &BackendOptions) {
+        let strip_comments = options.contains("strip-comments");
+        let output_dir = Path::new(options.slice_options.output_dir.as_deref().unwrap_or("."));
+
+        for slice_file in state.files.iter().filter(|file| file.is_source) {
+            let Some(surface) = public_api_surface(slice_file, strip_comments) else { continue };
+
+            let path = output_dir.join(output_path(&slice_file.relative_path));
+            if let Err(error) = write_surface(&path, &slice_file.relative_path, &surface) {
+                let error = Error::IO {
+                    action: "write",
+                    path: path.display().to_string(),
+                    error,
+                };
+                Diagnostic::new(error).push_into(&mut state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Returns the definitions that were written in the provided file, in the order they were written.
+///
+/// Files also contain definitions that weren't written in them: the structs synthesized for tuples (whose spans are
+/// inside the definition using the tuple), and the instantiations of generic structs (which share the generic struct's
+/// span). These are skipped, since they're removed or kept along with the definitions they came from.
+fn written_definitions(slice_file: &SliceFile) -> Vec<&dyn Entity> {
+    let mut definitions = slice_file
+        .contents
+        .iter()
+        .map(Definition::borrow)
+        .filter(|definition| !definition.is_synthesized())
+        .collect::<Vec<_>>();
+    definitions.sort_by_key(|definition| definition.span().start);
+    definitions.dedup_by_key(|definition| definition.span().start);
+    definitions
+}
+
+/// Returns the index of the first token of the provided definition, including any attributes and doc comment that
+/// were written before it.
+fn start_of_definition(definition: &dyn Entity, tokens: &[SyntaxToken]) -> usize {
+    let attribute_starts = definition
+        .attributes()
+        .into_iter()
+        .map(|attribute| attribute.span.start);
+    let start = attribute_starts.fold(definition.span().start, Location::min);
+    let mut index = tokens
+        .iter()
+        .position(|token| token.span.start >= start)
+        .unwrap_or(tokens.len());
+
+    // The spans of attributes don't include the brackets around them.
+    if index > 0 && tokens[index - 1].text == "[" {
+        index -= 1;
+    }
+
+    // Doc comments are directly above the definition they document, so we stop at the first blank line.
+    while index > 0 {
+        let token = &tokens[index - 1];
+        let is_line_break = token.kind == SyntaxKind::Whitespace && token.text.matches('\n').count() <= 1;
+        match token.kind == SyntaxKind::DocComment || is_line_break {
+            true => index -= 1,
+            false => break,
+        }
+    }
+    while tokens
+        .get(index)
+        .is_some_and(|token| token.kind == SyntaxKind::Whitespace)
+    {
+        index += 1;
+    }
+    index
+}
+
+/// Returns the index after the last token of the definition which starts at `start`, given the start of the next one.
+fn end_of_definition(start: usize, next_start: usize, tokens: &[SyntaxToken]) -> usize {
+    let mut end = next_start;
+    while end > start {
+        let kind = tokens[end - 1].kind;
+        match kind.is_trivia() || matches!(kind, SyntaxKind::DocComment | SyntaxKind::PreprocessorDirective) {
+            true => end -= 1,
+            false => break,
+        }
+    }
+    end
+}
+
+/// Removes all the comments from the provided text, along with any lines that only contained comments.
+fn remove_comments(text: &str) -> String {
+    // Each line is stored along with whether any comments were removed from it.
+    let mut lines = vec![(String::new(), false)];
+    for token in tokenize(text, "") {
+        match token.kind {
+            SyntaxKind::LineComment | SyntaxKind::BlockComment | SyntaxKind::DocComment => {
+                lines.last_mut().unwrap().1 = true;
+            }
+            _ => {
+                for (i, part) in token.text.split('\n').enumerate() {
+                    if i > 0 {
+                        lines.push((String::new(), false));
+                    }
+                    lines.last_mut().unwrap().0.push_str(part);
+                }
+            }
+        }
+    }
+
+    let lines = lines.into_iter().filter_map(|(line, had_comments)| match had_comments {
+        true if line.trim().is_empty() => None,
+        true => Some(line.trim_end().to_owned()),
+        false => Some(line),
+    });
+    lines.collect::<Vec<_>>().join("\n")
+}
+
+/// Collapses any runs of blank lines into a single blank line, and removes any blank lines from the start and end of
+/// the text. The returned text always ends with a single newline.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut result = String::new();
+    let mut previous_was_blank = true;
+    for line in text.lines() {
+        let is_blank = line.trim().is_empty();
+        if !(is_blank && previous_was_blank) {
+            result.push_str(if is_blank { "" } else { line });
+            result.push('\n');
+        }
+        previous_was_blank = is_blank;
+    }
+    while result.ends_with("\n\n") {
+        result.pop();
+    }
+    result
+}
+
+/// Returns the path (relative to the output directory) that a source file's API surface is written to.
+/// This is the file's relative path, unless it's absolute or outside the working directory, in which case it's only
+/// the file's name.
+fn output_path(relative_path: &str) -> &Path {
+    let path = Path::new(relative_path);
+    let is_nested = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    match is_nested {
+        true => path,
+        false => Path::new(path.file_name().unwrap_or(path.as_os_str())),
+    }
+}
+
+/// Writes a file's API surface to `path`, creating any missing directories. The file is never written over its own
+/// source file, since that would destroy it; an error is returned instead.
+fn write_surface(path: &Path, source_path: &str, surface: &str) -> io::Result<()> {
+    let is_source_file = match (path.canonicalize(), Path::new(source_path).canonicalize()) {
+        (Ok(path), Ok(source_path)) => path == source_path,
+        _ => false,
+    };
+    if is_source_file {
+        let message = "the API surface of a file cannot be written over the file itself";
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, surface)
+}
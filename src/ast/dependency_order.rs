@@ -0,0 +1,251 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::grammar::*;
+use std::collections::{BTreeSet, HashMap};
+
+/// Sorts the provided definitions so that each definition comes after the definitions it depends on.
+/// See [Ast::definitions_in_dependency_order](super::Ast::definitions_in_dependency_order).
+pub(super) fn sort(definitions: Vec<&dyn Entity>) -> Vec<&dyn Entity> {
+    // Map each definition to its position in the list, so dependencies can be looked up by address.
+    let indexes: HashMap<*const (), usize> = definitions
+        .iter()
+        .enumerate()
+        .map(|(index, definition)| (address_of(*definition), index))
+        .collect();
+
+    // For each definition, count how many other definitions it depends on, and record which definitions depend on it.
+    let mut dependency_counts = vec![0; definitions.len()];
+    let mut dependents = vec![Vec::new(); definitions.len()];
+    let mut all_dependencies = Vec::with_capacity(definitions.len());
+    for (index, definition) in definitions.iter().enumerate() {
+        let mut dependencies = dependencies_of(*definition)
+            .into_iter()
+            .filter_map(|dependency| indexes.get(&address_of(dependency)).copied())
+            .filter(|dependency| *dependency != index)
+            .collect::<Vec<_>>();
+        dependencies.sort_unstable();
+        dependencies.dedup();
+
+        dependency_counts[index] = dependencies.len();
+        for &dependency in &dependencies {
+            dependents[dependency].push(index);
+        }
+        all_dependencies.push(dependencies);
+    }
+    let components = strongly_connected_components(&all_dependencies);
+
+    // Ties are broken by identifier (and then by position), so the order doesn't depend on the order files were parsed.
+    let key_of = |index: usize| (definitions[index].parser_scoped_identifier(), index);
+    let mut ready = (0..definitions.len())
+        .filter(|index| dependency_counts[*index] == 0)
+        .map(key_of)
+        .collect::<BTreeSet<_>>();
+
+    let mut is_sorted = vec![false; definitions.len()];
+    let mut sorted = Vec::with_capacity(definitions.len());
+
+    // The component of the cycle currently being broken. Every definition in it is sorted before any other definition,
+    // so definitions which depend on the cycle come after all of it.
+    let mut current_cycle = None;
+    while sorted.len() < definitions.len() {
+        let first_unsorted_in = |component: usize, is_sorted: &[bool]| {
+            (0..definitions.len())
+                .filter(|index| !is_sorted[*index] && components[*index] == component)
+                .map(key_of)
+                .min()
+        };
+        if current_cycle.is_some_and(|component| first_unsorted_in(component, &is_sorted).is_none()) {
+            current_cycle = None;
+        }
+
+        let index = if let Some(component) = current_cycle {
+            // If none of the cycle's remaining definitions are ready, it contains another cycle, which we break too.
+            let next = ready.iter().find(|(_, index)| components[*index] == component).cloned();
+            match next {
+                Some(entry) => ready.take(&entry).unwrap().1,
+                None => first_unsorted_in(component, &is_sorted).unwrap().1,
+            }
+        } else if let Some((_, index)) = ready.pop_first() {
+            index
+        } else {
+            // If no definitions are ready, the remaining definitions are either part of a cycle, or depend on one.
+            // We break a cycle whose other dependencies have all been sorted, at whichever of its definitions comes first.
+            let is_blocked = |index: usize| {
+                all_dependencies[index]
+                    .iter()
+                    .any(|&dependency| !is_sorted[dependency] && components[dependency] != components[index])
+            };
+            let blocked_components = (0..definitions.len())
+                .filter(|index| !is_sorted[*index] && is_blocked(*index))
+                .map(|index| components[index])
+                .collect::<BTreeSet<_>>();
+            let index = (0..definitions.len())
+                .filter(|index| !is_sorted[*index] && !blocked_components.contains(&components[*index]))
+                .map(key_of)
+                .min()
+                .unwrap()
+                .1;
+            current_cycle = Some(components[index]);
+            index
+        };
+
+        is_sorted[index] = true;
+        sorted.push(definitions[index]);
+        for &dependent in &dependents[index] {
+            dependency_counts[dependent] -= 1;
+            if dependency_counts[dependent] == 0 && !is_sorted[dependent] {
+                ready.insert(key_of(dependent));
+            }
+        }
+    }
+    sorted
+}
+
+/// Returns the strongly connected component that each node of the provided graph belongs to, as computed by Tarjan's
+/// algorithm. Nodes are in the same component if they (transitively) depend on each other, so each cycle of definitions
+/// is contained in a single component. `dependencies` holds the nodes that each node depends on.
+fn strongly_connected_components(dependencies: &[Vec<usize>]) -> Vec<usize> {
+    struct Tarjan<'a> {
+        dependencies: &'a [Vec<usize>],
+        indexes: Vec<Option<usize>>,
+        low_links: Vec<usize>,
+        stack: Vec<usize>,
+        is_on_stack: Vec<bool>,
+        components: Vec<usize>,
+        next_index: usize,
+        next_component: usize,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.indexes[node] = Some(self.next_index);
+            self.low_links[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.is_on_stack[node] = true;
+
+            for &dependency in &self.dependencies[node] {
+                match self.indexes[dependency] {
+                    None => {
+                        self.visit(dependency);
+                        self.low_links[node] = self.low_links[node].min(self.low_links[dependency]);
+                    }
+                    Some(index) if self.is_on_stack[dependency] => {
+                        self.low_links[node] = self.low_links[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            // If this node is the root of a component, pop the component's nodes off the stack.
+            if Some(self.low_links[node]) == self.indexes[node] {
+                while let Some(member) = self.stack.pop() {
+                    self.is_on_stack[member] = false;
+                    self.components[member] = self.next_component;
+                    if member == node {
+                        break;
+                    }
+                }
+                self.next_component += 1;
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        dependencies,
+        indexes: vec![None; dependencies.len()],
+        low_links: vec![0; dependencies.len()],
+        stack: Vec::new(),
+        is_on_stack: vec![false; dependencies.len()],
+        components: vec![0; dependencies.len()],
+        next_index: 0,
+        next_component: 0,
+    };
+    for node in 0..dependencies.len() {
+        if tarjan.indexes[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components
+}
+
+/// Returns the definitions that the provided definition references, either directly or through its members.
+fn dependencies_of(definition: &dyn Entity) -> Vec<&dyn Entity> {
+    let mut dependencies = Vec::new();
+    match definition.concrete_entity() {
+        Entities::Struct(struct_def) => {
+            for field in struct_def.fields() {
+                push_type_dependencies(&field.data_type, &mut dependencies);
+            }
+        }
+        Entities::Class(class_def) => {
+            if let Some(base) = class_def.base_class() {
+                dependencies.push(base);
+            }
+            for field in class_def.fields() {
+                push_type_dependencies(&field.data_type, &mut dependencies);
+            }
+        }
+        Entities::Exception(exception_def) => {
+            if let Some(base) = exception_def.base_exception() {
+                dependencies.push(base);
+            }
+            for field in exception_def.fields() {
+                push_type_dependencies(&field.data_type, &mut dependencies);
+            }
+        }
+        Entities::Interface(interface_def) => {
+            for base in interface_def.base_interfaces() {
+                dependencies.push(base);
+            }
+            for operation in interface_def.operations() {
+                for member in operation.parameters_and_return_members() {
+                    push_type_dependencies(&member.data_type, &mut dependencies);
+                }
+                for exception in &operation.exception_specification {
+                    dependencies.push(exception.definition());
+                }
+            }
+        }
+        Entities::Enum(enum_def) => {
+            for enumerator in enum_def.enumerators() {
+                for field in enumerator.fields() {
+                    push_type_dependencies(&field.data_type, &mut dependencies);
+                }
+            }
+        }
+        Entities::TypeAlias(type_alias) => push_type_dependencies(&type_alias.underlying, &mut dependencies),
+        _ => {}
+    }
+    dependencies
+}
+
+/// Pushes the definitions that the provided type reference uses. Types referenced through a type alias only depend on
+/// that alias, since the alias itself depends on the type.
+fn push_type_dependencies<'a>(type_ref: &'a TypeRef, dependencies: &mut Vec<&'a dyn Entity>) {
+    if let Some(type_alias) = type_ref.type_aliases().first() {
+        dependencies.push(*type_alias);
+        return;
+    }
+
+    match type_ref.concrete_type() {
+        Types::Struct(struct_def) => dependencies.push(struct_def),
+        Types::Class(class_def) => dependencies.push(class_def),
+        Types::Enum(enum_def) => dependencies.push(enum_def),
+        Types::CustomType(custom_type) => dependencies.push(custom_type),
+        Types::ResultType(result_type) => {
+            push_type_dependencies(&result_type.success_type, dependencies);
+            push_type_dependencies(&result_type.failure_type, dependencies);
+        }
+        Types::Sequence(sequence) => push_type_dependencies(&sequence.element_type, dependencies),
+        Types::Dictionary(dictionary) => {
+            push_type_dependencies(&dictionary.key_type, dependencies);
+            push_type_dependencies(&dictionary.value_type, dependencies);
+        }
+        Types::Primitive(_) => {}
+    }
+}
+
+fn address_of(entity: &dyn Entity) -> *const () {
+    entity as *const dyn Entity as *const ()
+}
@@ -2,11 +2,14 @@
 
 //! TODO write a doc comment for the module.
 
+mod dependency_order;
 pub mod node;
 
-use self::node::Node;
-use crate::grammar::{Element, NamedSymbol, Primitive};
+use self::node::{Node, NodeId};
+use crate::grammar::*;
+use crate::slice_file::{Location, Span};
 use crate::utils::ptr_util::{OwnedPtr, WeakPtr};
+use crate::well_known_types;
 use std::collections::HashMap;
 
 /// The AST (Abstract Syntax Tree) is the heart of the compiler, containing all the slice elements defined and used by
@@ -15,6 +18,8 @@ use std::collections::HashMap;
 /// The AST is primarily for centralizing ownership of Slice elements, but also features lookup functions for finding
 /// nodes (see [`find_node`](Ast::find_node) and [`find_node_with_scope`](Ast::find_node_with_scope)) and their
 /// elements (see [`find_element`](Ast::find_element) and [`find_element_with_scope`](Ast::find_element_with_scope)).
+/// Every node also has a [`NodeId`], which can be used to look it up without an identifier (see
+/// [`get_by_id`](Ast::get_by_id)).
 ///
 /// In practice, there is a single instance of the AST per compilation, which is [created](Ast::create) during
 /// initialization and lives as long as the program does, making the AST effectively `'static`.
@@ -36,6 +41,21 @@ pub struct Ast {
     /// Each element's fully scoped identifier (without a leading '::') is used for its key, and the value stored is
     /// the element's index in this AST (specifically in the [`elements`](Ast::elements) vector).
     lookup_table: HashMap<String, usize>,
+
+    /// An index of every Slice definition stored in this AST. This includes every element with an identifier except
+    /// for modules (which are re-opened, not redefined) and primitives (which can't be defined in Slice).
+    ///
+    /// Keys are fully scoped identifiers (like in the [`lookup_table`](Ast::lookup_table)), but the value stored is
+    /// the index of _every_ element defined with that identifier, in the order they were added to this AST. So unlike
+    /// the lookup table, redefinitions don't overwrite each other.
+    definition_index: HashMap<String, Vec<usize>>,
+
+    /// A lookup table mapping the address of every Slice element stored in this AST to its index in this AST, so the
+    /// [ID](NodeId) of an element can be found from a reference to it (see [`id_of`](Ast::id_of)).
+    ///
+    /// Elements are heap allocated, and never moved or removed once they're added to this AST, so their addresses are
+    /// stable for as long as this AST exists.
+    id_table: HashMap<usize, usize>,
 }
 
 impl Ast {
@@ -45,11 +65,13 @@ impl Ast {
     /// ```
     /// # use slicec::ast::Ast;
     /// let ast = Ast::create();
-    /// assert_eq!(ast.as_slice().len(), 17); // Only the 17 primitives are defined.
+    /// assert_eq!(ast.as_slice().len(), 20); // Only the 20 primitives are defined.
     /// ```
     pub fn create() -> Ast {
         // Primitive types are built in to the compiler. Since they aren't defined in Slice, we 'define' them here,
         // when the AST is created, to ensure they're always available.
+        // Note that `uuid`, `timestamp`, and `duration` aren't keywords; they're only resolved through this table, so
+        // they can still be used as identifiers (ex: `timestamp: int64`), like primitive aliases can.
 
         let elements = vec![
             Node::Primitive(OwnedPtr::new(Primitive::Bool)),
@@ -68,6 +90,9 @@ impl Ast {
             Node::Primitive(OwnedPtr::new(Primitive::Float32)),
             Node::Primitive(OwnedPtr::new(Primitive::Float64)),
             Node::Primitive(OwnedPtr::new(Primitive::String)),
+            Node::Primitive(OwnedPtr::new(Primitive::Uuid)),
+            Node::Primitive(OwnedPtr::new(Primitive::Timestamp)),
+            Node::Primitive(OwnedPtr::new(Primitive::Duration)),
             Node::Primitive(OwnedPtr::new(Primitive::AnyClass)),
         ];
 
@@ -88,10 +113,179 @@ impl Ast {
             ("float32".to_owned(), 13),
             ("float64".to_owned(), 14),
             ("string".to_owned(), 15),
-            ("AnyClass".to_owned(), 16),
+            ("uuid".to_owned(), 16),
+            ("timestamp".to_owned(), 17),
+            ("duration".to_owned(), 18),
+            ("AnyClass".to_owned(), 19),
         ]);
 
-        Ast { elements, lookup_table }
+        let id_table = elements
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (element_address(<&dyn Element>::from(node)), i))
+            .collect();
+
+        Ast {
+            elements,
+            lookup_table,
+            definition_index: HashMap::new(),
+            id_table,
+        }
+    }
+
+    /// Registers an additional identifier which the provided primitive type can be referenced by (ex: `f32` for
+    /// `float32`). Aliases are looked up like any other global identifier, so definitions in a module can shadow them.
+    ///
+    /// Aliases must be registered before any Slice files are parsed. If the alias is already in use (by a primitive type,
+    /// a built-in type, or another alias), the alias isn't registered and this returns `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// # use slicec::grammar::*;
+    /// let mut ast = Ast::create();
+    /// assert!(ast.add_primitive_alias("f32", &Primitive::Float32));
+    /// assert!(!ast.add_primitive_alias("uuid", &Primitive::Float32));
+    ///
+    /// let float32_def = ast.find_element::<Primitive>("f32");
+    /// assert_eq!(float32_def.unwrap(), &Primitive::Float32);
+    /// ```
+    pub fn add_primitive_alias(&mut self, alias: impl Into<String>, primitive: &Primitive) -> bool {
+        let alias = alias.into();
+        if self.lookup_table.contains_key(&alias) {
+            return false;
+        }
+
+        // Primitives are always in the lookup table under their Slice keywords.
+        let index = self.lookup_table[primitive.kind()];
+        self.lookup_table.insert(alias, index);
+        true
+    }
+
+    /// Registers the [well-known types](crate::well_known_types) as custom types in the `WellKnownTypes` module, each
+    /// with an attribute for every one of its [default mappings](well_known_types::WellKnownType::mappings).
+    ///
+    /// Like primitives, well-known types aren't defined in any Slice file. Their spans all point to the start of
+    /// [`FILE_NAME`](well_known_types::FILE_NAME) instead, which doesn't correspond to an actual file.
+    ///
+    /// The well-known types must be registered before any Slice files are parsed. If any of them are already in use,
+    /// nothing is registered and this returns `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// # use slicec::grammar::*;
+    /// let mut ast = Ast::create();
+    /// assert!(ast.add_well_known_types());
+    ///
+    /// let uri_def = ast.find_element::<CustomType>("WellKnownTypes::Uri").unwrap();
+    /// assert_eq!(uri_def.attributes()[0].kind.directive(), "cs::type");
+    /// ```
+    pub fn add_well_known_types(&mut self) -> bool {
+        let module_name = well_known_types::MODULE_NAME;
+        let scoped_identifier = |identifier: &str| format!("{module_name}::{identifier}");
+        let is_in_use = |identifier: &str| self.lookup_table.contains_key(&scoped_identifier(identifier));
+        if well_known_types::WELL_KNOWN_TYPES
+            .iter()
+            .any(|t| is_in_use(t.identifier))
+        {
+            return false;
+        }
+
+        let span = Span::new(Location::default(), Location::default(), well_known_types::FILE_NAME);
+        let identifier = |value: &str| Identifier {
+            value: value.to_owned(),
+            span: span.clone(),
+        };
+
+        let module_ptr = OwnedPtr::new(Module {
+            identifier: identifier(module_name),
+            attributes: Vec::new(),
+            span: span.clone(),
+        });
+        let scope = Scope {
+            parser_scope: module_name.to_owned(),
+            module: Some(module_ptr.downgrade()),
+        };
+        self.add_named_element(module_ptr);
+
+        for well_known_type in well_known_types::WELL_KNOWN_TYPES {
+            let overview = Message {
+                value: vec![MessageComponent::Text(well_known_type.comment.to_owned())],
+                span: span.clone(),
+                raw_text: well_known_type.comment.to_owned(),
+            };
+            let comment = DocComment {
+                overview: Some(overview),
+                params: Vec::new(),
+                returns: Vec::new(),
+                throws: Vec::new(),
+                see: Vec::new(),
+                locale: None,
+                localizations: Vec::new(),
+                span: span.clone(),
+                raw_text: well_known_type.comment.to_owned(),
+            };
+            self.add_named_element(OwnedPtr::new(CustomType {
+                identifier: identifier(well_known_type.identifier),
+                scope: scope.clone(),
+                attributes: Vec::new(),
+                comment: Some(comment),
+                span: span.clone(),
+                supported_encodings: None, // Patched by the encoding patcher.
+            }));
+
+            for (directive, argument) in well_known_type.mappings {
+                self.add_well_known_type_mapping(well_known_type.identifier, *directive, *argument);
+            }
+        }
+        true
+    }
+
+    /// Maps a [well-known type](crate::well_known_types) to a type in some language, by adding an attribute with the
+    /// provided directive and argument to it (ex: `cs::type` and `System.Uri` adds `[cs::type("System.Uri")]`).
+    /// This lets backends map the well-known types for their own languages.
+    ///
+    /// Mappings must be added after the well-known types are [registered](Ast::add_well_known_types), and before the
+    /// AST is validated. If there is no well-known type with the provided (unscoped) identifier, nothing is added and
+    /// this returns `false`.
+    pub fn add_well_known_type_mapping(
+        &mut self,
+        identifier: &str,
+        directive: impl Into<String>,
+        argument: impl Into<String>,
+    ) -> bool {
+        let scoped_identifier = format!("{}::{identifier}", well_known_types::MODULE_NAME);
+        let Some(&index) = self.lookup_table.get(&scoped_identifier) else {
+            return false;
+        };
+        let span = match &self.elements[index] {
+            Node::CustomType(custom_type_ptr) if custom_type_ptr.borrow().span.file == well_known_types::FILE_NAME => {
+                custom_type_ptr.borrow().span.clone()
+            }
+            _ => return false,
+        };
+
+        let argument = argument.into();
+        let literal = StringLiteral {
+            value: argument.clone(),
+            lexeme: format!("\"{}\"", argument.replace('\\', "\\\\").replace('"', "\\\"")),
+            span: span.clone(),
+        };
+        let attribute = Attribute::new(
+            directive.into(),
+            vec![argument],
+            vec![span.clone()],
+            vec![Some(Literal::String(literal))],
+            span,
+        );
+        let attribute_ptr = self.add_element(OwnedPtr::new(attribute));
+
+        let Node::CustomType(custom_type_ptr) = &mut self.elements[index] else {
+            unreachable!("the node was already checked to be a custom type");
+        };
+        unsafe { custom_type_ptr.borrow_mut().attributes.push(attribute_ptr) };
+        true
     }
 
     /// Returns a reference to the AST [node](Node) with the provided identifier, if one exists.
@@ -198,6 +392,45 @@ impl Ast {
         self.find_node(identifier)
     }
 
+    /// Returns references to every AST [node](Node) that the provided identifier could refer to from within the
+    /// provided scope, in the order they're checked by [`find_node_with_scope`](Ast::find_node_with_scope).
+    ///
+    /// So the first node (if there is one) is the node that `find_node_with_scope` would return, and any others are
+    /// nodes in more outward scopes which it shadows. If the identifier begins with '::' it is treated as globally
+    /// scoped, and at most one node is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// let ast = Ast::create();
+    ///
+    /// // Primitives are defined at global scope, so they can be referenced from any scope.
+    /// assert_eq!(ast.find_nodes_with_scope("bool", "foo::bar").len(), 1);
+    ///
+    /// // If no element exists with the specified identifier, an empty vector is returned.
+    /// assert!(ast.find_nodes_with_scope("hello", "foo::bar").is_empty());
+    /// ```
+    pub fn find_nodes_with_scope<'a>(&'a self, identifier: &str, scope: &str) -> Vec<&'a Node> {
+        // If the identifier is globally scoped (starts with '::'), find the node without scoping.
+        if let Some(unprefixed_identifier) = identifier.strip_prefix("::") {
+            return self.find_node(unprefixed_identifier).into_iter().collect();
+        }
+
+        // Check for the identifier in every enclosing scope, starting with the innermost, and ending at global scope.
+        let mut scopes = scope.split("::").collect::<Vec<_>>();
+        let mut nodes = Vec::new();
+        while !scopes.is_empty() {
+            let candidate = scopes.join("::") + "::" + identifier;
+            if let Some(i) = self.lookup_table.get(&candidate) {
+                nodes.push(&self.elements[*i]);
+            }
+            scopes.pop();
+        }
+        nodes.extend(self.find_node(identifier));
+        nodes
+    }
+
     /// Returns a reference to a Slice element with the provided identifier and specified type, if one exists.
     /// The identifier must be fully qualified, since this performs no scope resolution, but cannot begin with '::'.
     ///
@@ -288,6 +521,143 @@ impl Ast {
         self.find_node_with_scope(identifier, scope).and_then(|x| x.try_into())
     }
 
+    /// Returns every entity that was defined with the provided identifier, in the order they were added to this AST.
+    /// The identifier must be fully qualified, since this performs no scope resolution, but may optionally begin
+    /// with '::'.
+    ///
+    /// In a valid compilation, identifiers are only defined once (except for parameters and return members, which
+    /// share an AST scope), so this returns at most one entity. But unlike [`find_node`](Ast::find_node), which only
+    /// returns the last definition, this returns all of them, even if they're in different files. Modules aren't
+    /// definitions, so they're never returned; they can be re-opened any number of times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// let ast = Ast::create();
+    ///
+    /// // Primitives are built in to the compiler, so they don't have definitions.
+    /// assert!(ast.find_definitions("int32").is_empty());
+    /// ```
+    pub fn find_definitions(&self, identifier: &str) -> Vec<&dyn Entity> {
+        let identifier = identifier.strip_prefix("::").unwrap_or(identifier);
+        let Some(indexes) = self.definition_index.get(identifier) else { return Vec::new() };
+
+        // Every element in the definition index is an entity, so this filter is only a formality.
+        let nodes = indexes.iter().map(|i| &self.elements[*i]);
+        nodes.filter_map(|node| node.try_into().ok()).collect()
+    }
+
+    /// Returns all the entities that are defined directly in the provided scope, in the order they were defined.
+    /// Entities defined in nested scopes (like the operations in an interface) aren't included.
+    ///
+    /// The scope must be fully qualified, but may optionally begin with '::'.
+    /// For example, `Foo::Bar` returns the definitions in the `Foo::Bar` module, and `Foo::I` returns the operations
+    /// of the `I` interface in the `Foo` module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// let ast = Ast::create();
+    ///
+    /// // Primitives aren't entities, so an AST containing only primitives has no entities in any scope.
+    /// assert!(ast.entities_in_scope("Foo").is_empty());
+    /// ```
+    pub fn entities_in_scope(&self, scope: &str) -> Vec<&dyn Entity> {
+        let scope = scope.strip_prefix("::").unwrap_or(scope);
+        self.elements
+            .iter()
+            .filter_map(|node| <&dyn Entity>::try_from(node).ok())
+            .filter(|entity| entity.parser_scope() == scope)
+            .collect()
+    }
+
+    /// Returns every definition in this AST (structs, classes, exceptions, interfaces, enums, custom types, type aliases,
+    /// and constants), ordered so that each definition comes after the definitions it depends on. This lets backends
+    /// for languages which require types to be declared before they're used (like C and C++) generate definitions by
+    /// iterating over this list, instead of computing the order themselves.
+    ///
+    /// A definition depends on the types used by its fields, operations, and underlying type, and on its bases.
+    /// Definitions with no dependency between them are ordered by their fully scoped identifiers, so the order is stable,
+    /// regardless of the order files were compiled in. Definitions can form cycles, which have no valid order: classes
+    /// can reference each other, and so can structs, through optional or `[boxed]` fields. Each cycle is broken at its
+    /// definition with the first identifier, but only after everything the cycle depends on, so definitions which only
+    /// depend on a cycle still come after it. Backends must forward-declare these definitions to handle cycles.
+    ///
+    /// This can only be called after the AST has been patched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// let ast = Ast::create();
+    ///
+    /// // Primitives are built in to the compiler, so they don't have definitions.
+    /// assert!(ast.definitions_in_dependency_order().is_empty());
+    /// ```
+    pub fn definitions_in_dependency_order(&self) -> Vec<&dyn Entity> {
+        let definitions = self
+            .elements
+            .iter()
+            .filter_map(|node| <&dyn Entity>::try_from(node).ok());
+        let definitions = definitions.filter(|entity| {
+            matches!(
+                entity.concrete_entity(),
+                Entities::Struct(_)
+                    | Entities::Class(_)
+                    | Entities::Exception(_)
+                    | Entities::Interface(_)
+                    | Entities::Enum(_)
+                    | Entities::CustomType(_)
+                    | Entities::TypeAlias(_)
+                    | Entities::Constant(_)
+            )
+        });
+        dependency_order::sort(definitions.collect())
+    }
+
+    /// Returns a reference to the AST [node](Node) with the provided [ID](NodeId), if one exists.
+    ///
+    /// IDs are only meaningful for the AST they were obtained from (see [NodeId]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// # use slicec::grammar::*;
+    /// let ast = Ast::create();
+    ///
+    /// // Look up a node by its ID.
+    /// let int32_def = ast.find_element::<Primitive>("int32").unwrap();
+    /// let int32_id = ast.id_of(int32_def).unwrap();
+    /// let int32_node = ast.get_by_id(int32_id).unwrap();
+    /// assert_eq!(<&Primitive>::try_from(int32_node).unwrap(), &Primitive::Int32);
+    /// ```
+    pub fn get_by_id(&self, id: NodeId) -> Option<&Node> {
+        self.elements.get(id.index())
+    }
+
+    /// Returns the [ID](NodeId) of the provided Slice element, or `None` if the element isn't stored in this AST.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slicec::ast::Ast;
+    /// # use slicec::grammar::*;
+    /// let ast = Ast::create();
+    ///
+    /// // Elements stored in the AST have IDs.
+    /// let bool_def = ast.find_element::<Primitive>("bool").unwrap();
+    /// assert!(ast.id_of(bool_def).is_some());
+    ///
+    /// // Other elements don't.
+    /// assert!(ast.id_of(&Primitive::Bool).is_none());
+    /// ```
+    pub fn id_of<T: Element + ?Sized>(&self, element: &T) -> Option<NodeId> {
+        self.id_table.get(&element_address(element)).copied().map(NodeId::new)
+    }
+
     /// Returns an immutable slice of all the [nodes](Node) contained in this AST.
     ///
     /// # Examples
@@ -326,6 +696,8 @@ impl Ast {
         OwnedPtr<T>: Into<Node>,
     {
         let weak_ptr = element.downgrade();
+        self.id_table
+            .insert(element_address(element.borrow()), self.elements.len());
         // Convert the element into a [Node] and add it to this AST.
         self.elements.push(element.into());
         weak_ptr
@@ -333,17 +705,106 @@ impl Ast {
 
     /// Moves a Slice element into this AST, and returns a [WeakPtr] to it, after adding an entry for the element into
     /// this AST's [lookup table](Ast::lookup_table), allowing it to be retrieved by identifier.
+    /// If the element isn't a module, it's also added to this AST's [definition index](Ast::definition_index).
     pub(crate) fn add_named_element<T: NamedSymbol>(&mut self, element: OwnedPtr<T>) -> WeakPtr<T>
     where
         OwnedPtr<T>: Into<Node>,
     {
-        // Add an entry to this AST's lookup table for the element.
         let scoped_identifier = element.borrow().parser_scoped_identifier();
-        self.lookup_table.insert(scoped_identifier, self.elements.len());
+        let index = self.elements.len();
 
         // Add the element to this AST.
-        self.add_element(element)
+        let weak_ptr = self.add_element(element);
+
+        // Add entries to this AST's lookup table and definition index for the element.
+        if !matches!(self.elements[index], Node::Module(_)) {
+            let definitions = self.definition_index.entry(scoped_identifier.clone()).or_default();
+            definitions.push(index);
+        }
+        self.lookup_table.insert(scoped_identifier, index);
+
+        weak_ptr
+    }
+
+    /// Renames any synthesized (tuple) structs whose names are already used by another definition, by appending a
+    /// numeric suffix to them (ex: if `PointPos` is taken, it's renamed to `PointPos2`).
+    ///
+    /// Tuple structs are named after the member they're declared on, but since definitions can be split across files,
+    /// whether these names are unique can only be checked once every file has been parsed.
+    pub(crate) unsafe fn rename_colliding_synthesized_structs(&mut self) {
+        for index in 0..self.elements.len() {
+            let Node::Struct(struct_ptr) = &self.elements[index] else {
+                continue;
+            };
+            let struct_def = struct_ptr.borrow();
+            let old_identifier = struct_def.parser_scoped_identifier();
+            if !struct_def.is_synthesized || self.definition_index[&old_identifier].len() < 2 {
+                continue;
+            }
+
+            let scoped_name = |name: &str| match struct_def.scope.parser_scope.as_str() {
+                "" => name.to_owned(),
+                scope => format!("{scope}::{name}"),
+            };
+            let mut suffix = 2;
+            while self
+                .lookup_table
+                .contains_key(&scoped_name(&format!("{}{suffix}", struct_def.identifier.value)))
+            {
+                suffix += 1;
+            }
+            let new_name = format!("{}{suffix}", struct_def.identifier.value);
+            let new_identifier = scoped_name(&new_name);
+            let field_indices = struct_def
+                .fields
+                .iter()
+                .map(|field| self.id_table[&element_address(field.borrow())]);
+            let field_indices = field_indices.collect::<Vec<_>>();
+
+            // Move the struct, and each of its fields, to their new identifiers.
+            self.move_definition(index, &old_identifier, new_identifier.clone());
+            if let Node::Struct(struct_ptr) = &mut self.elements[index] {
+                struct_ptr.borrow_mut().identifier.value = new_name;
+            }
+            for field_index in field_indices {
+                let Node::Field(field_ptr) = &mut self.elements[field_index] else {
+                    unreachable!("struct fields are always fields");
+                };
+                let old_field_identifier = field_ptr.borrow().parser_scoped_identifier();
+                field_ptr.borrow_mut().scope.parser_scope = new_identifier.clone();
+                let new_field_identifier = format!("{new_identifier}::{}", field_ptr.borrow().identifier.value);
+                self.move_definition(field_index, &old_field_identifier, new_field_identifier);
+            }
+        }
     }
+
+    /// Moves the entries for the element at `index` in this AST's [lookup table](Ast::lookup_table) and
+    /// [definition index](Ast::definition_index) from `old_identifier` to `new_identifier`. If another definition has
+    /// the old identifier, the lookup table's entry for it is restored to point to that definition.
+    fn move_definition(&mut self, index: usize, old_identifier: &str, new_identifier: String) {
+        let definitions = self.definition_index.get_mut(old_identifier).unwrap();
+        definitions.retain(|&i| i != index);
+        if self.lookup_table.get(old_identifier) == Some(&index) {
+            match definitions.last() {
+                Some(&other_index) => self.lookup_table.insert(old_identifier.to_owned(), other_index),
+                None => self.lookup_table.remove(old_identifier),
+            };
+        }
+        if definitions.is_empty() {
+            self.definition_index.remove(old_identifier);
+        }
+
+        self.definition_index
+            .entry(new_identifier.clone())
+            .or_default()
+            .push(index);
+        self.lookup_table.insert(new_identifier, index);
+    }
+}
+
+/// Returns the address of the provided element, which is used as its key in the AST's [ID table](Ast::id_table).
+fn element_address<T: ?Sized>(element: &T) -> usize {
+    element as *const T as *const () as usize
 }
 
 impl Default for Ast {
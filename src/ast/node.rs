@@ -9,6 +9,37 @@ use crate::utils::ptr_util::{OwnedPtr, WeakPtr};
 use convert_case::ccase;
 use std::fmt;
 
+/// A small, copyable identifier for a [node](Node) in an [Ast](super::Ast), which can be used to refer to the node
+/// cheaply (in caches, diffs, or serialized analyses) instead of by its scoped identifier. Unlike identifiers, every
+/// node has an ID, including anonymous types and attributes.
+///
+/// IDs are stable for the lifetime of an AST, and since elements are added to the AST in the order they're parsed,
+/// compiling the same files with the same options always assigns the same IDs. IDs from one AST are meaningless in
+/// another, unless both were created from the same input.
+///
+/// Nodes are looked up by ID with [`get_by_id`](super::Ast::get_by_id), and an element's ID can be found with
+/// [`id_of`](super::Ast::id_of).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Creates an ID from its integer representation, as returned by [`index`](NodeId::index).
+    pub fn new(index: usize) -> Self {
+        NodeId(index)
+    }
+
+    /// Returns the integer representation of this ID, which is the index of its node in the AST.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // Helper macro for generating `TryFrom` conversion functions to unwrap `Node`s to concrete types, when the type of
 // element the Node is holding is known.
 macro_rules! generate_try_from_node_impl {
@@ -83,7 +114,7 @@ macro_rules! generate_node_enum {
 // generate the `Node` enum with variants for every type allowed to be in the AST.
 generate_node_enum! {
     Module, Struct, Class, Exception, Field, Interface, Operation, Parameter, Enum,
-    Enumerator, CustomType, TypeAlias, ResultType, Sequence, Dictionary, Primitive, Attribute
+    Enumerator, CustomType, TypeAlias, Constant, ResultType, Sequence, Dictionary, Primitive, Attribute
 }
 
 impl<'a> TryFrom<&'a Node> for WeakPtr<dyn Type> {
@@ -161,6 +192,7 @@ impl<'a> TryFrom<&'a Node> for &'a dyn NamedSymbol {
             Node::Enumerator(enumerator_ptr) => Ok(enumerator_ptr.borrow()),
             Node::CustomType(custom_type_ptr) => Ok(custom_type_ptr.borrow()),
             Node::TypeAlias(type_alias_ptr) => Ok(type_alias_ptr.borrow()),
+            Node::Constant(constant_ptr) => Ok(constant_ptr.borrow()),
             _ => Err(LookupError::TypeMismatch {
                 expected: "named symbol".to_owned(),
                 actual: ccase!(lower, node.to_string()),
@@ -190,6 +222,7 @@ impl<'a> TryFrom<&'a Node> for WeakPtr<dyn Entity> {
             Node::Enumerator(enumerator_ptr) => Ok(downgrade_as!(enumerator_ptr, dyn Entity)),
             Node::CustomType(custom_type_ptr) => Ok(downgrade_as!(custom_type_ptr, dyn Entity)),
             Node::TypeAlias(type_alias_ptr) => Ok(downgrade_as!(type_alias_ptr, dyn Entity)),
+            Node::Constant(constant_ptr) => Ok(downgrade_as!(constant_ptr, dyn Entity)),
             _ => Err(LookupError::TypeMismatch {
                 expected: "entity".to_owned(),
                 actual: ccase!(lower, node.to_string()),
@@ -219,6 +252,7 @@ impl<'a> TryFrom<&'a Node> for &'a dyn Entity {
             Node::Enumerator(enumerator_ptr) => Ok(enumerator_ptr.borrow()),
             Node::CustomType(custom_type_ptr) => Ok(custom_type_ptr.borrow()),
             Node::TypeAlias(type_alias_ptr) => Ok(type_alias_ptr.borrow()),
+            Node::Constant(constant_ptr) => Ok(constant_ptr.borrow()),
             _ => Err(LookupError::TypeMismatch {
                 expected: "entity".to_owned(),
                 actual: ccase!(lower, node.to_string()),
@@ -255,6 +289,7 @@ impl_into_node_for!(Enum);
 impl_into_node_for!(Enumerator);
 impl_into_node_for!(CustomType);
 impl_into_node_for!(TypeAlias);
+impl_into_node_for!(Constant);
 impl_into_node_for!(ResultType);
 impl_into_node_for!(Sequence);
 impl_into_node_for!(Dictionary);
@@ -0,0 +1,223 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module allows a single compiler invocation to run multiple code generation backends.
+//!
+//! Each [Backend] is registered with the driver, which parses and validates the Slice files once, and then runs every
+//! selected backend over the same [CompilationState]. Users select backends with `--target`, and pass options to a
+//! specific backend with `-X <BACKEND>:<KEY>=<VALUE>`; see [SliceOptions].
+
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::{Diagnostic, Error};
+use crate::panic_handler;
+use crate::slice_options::SliceOptions;
+use crate::utils::casing::{convert_case, Case, ReservedWords};
+use crate::validators::{check_for_generated_identifier_collisions, check_for_missing_custom_type_mappings};
+use std::time::Instant;
+
+/// A code generation backend, like the C# code generator, or a documentation generator.
+pub trait Backend {
+    /// Returns the name of this backend. Users select it with `--target <NAME>`, and namespace its options with it.
+    fn name(&self) -> &str;
+
+    /// Validates the AST for this backend's language mapping, reporting any problems to `state.diagnostics`.
+    /// This is only called if no errors were reported during parsing and common validation.
+    fn validate(&self, _state: &mut CompilationState, _options: &BackendOptions) {}
+
+    /// Returns how this backend maps Slice identifiers to identifiers in its generated code.
+    /// If this returns `Some`, the compiler reports any identifiers which would collide in the generated code,
+    /// before calling [Backend::validate]. By default this returns `None`, and no such check is performed.
+    fn identifier_rules(&self) -> Option<IdentifierRules<'_>> {
+        None
+    }
+
+    /// Returns the directive of the attribute this backend maps custom types with (ex: `cs::type`).
+    /// If this returns `Some`, the compiler reports any custom types in the source files which don't have this
+    /// attribute, before calling [Backend::validate]. By default this returns `None`, and no such check is performed.
+    fn custom_type_attribute(&self) -> Option<&str> {
+        None
+    }
+
+    /// Generates code for the source files in `state`, reporting any problems to `state.diagnostics`.
+    /// This is only called if no errors were reported by any of the selected backends, and `--dry-run` wasn't set.
+    fn generate(&self, state: &mut CompilationState, options: &BackendOptions);
+}
+
+/// The options that were passed to a specific backend, along with the options shared by all backends.
+#[derive(Debug)]
+pub struct BackendOptions<'a> {
+    /// The options shared by all backends.
+    pub slice_options: &'a SliceOptions,
+
+    /// The key-value pairs passed to this backend, in the order they were specified.
+    /// Options specified without a value (`-X <BACKEND>:<KEY>`) have an empty value.
+    values: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> BackendOptions<'a> {
+    /// Collects the options that were passed to the specified backend.
+    pub fn new(backend_name: &str, slice_options: &'a SliceOptions) -> Self {
+        let values = slice_options
+            .backend_options
+            .iter()
+            .filter_map(|option| split_backend_option(option))
+            .filter(|(backend, _, _)| *backend == backend_name)
+            .map(|(_, key, value)| (key, value))
+            .collect();
+
+        BackendOptions { slice_options, values }
+    }
+
+    /// Returns the value of the last occurrence of the specified key, or `None` if it wasn't passed to this backend.
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns the values of every occurrence of the specified key, in the order they were specified.
+    pub fn get_all(&self, key: &str) -> Vec<&'a str> {
+        self.values
+            .iter()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+            .collect()
+    }
+
+    /// Returns true if the specified key was passed to this backend, with or without a value.
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.iter().any(|(k, _)| *k == key)
+    }
+}
+
+/// Describes how a backend maps Slice identifiers to identifiers in its generated code.
+///
+/// Two identifiers in the same scope collide if they map to the same generated identifier, or, for case-insensitive
+/// languages, if their generated identifiers only differ in case. For example, the fields `foo` and `Foo` collide in a
+/// case-insensitive language, and `my_field` and `myField` collide if the backend converts identifiers to Pascal case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdentifierRules<'a> {
+    /// Whether identifiers are compared without regard to case.
+    pub case_insensitive: bool,
+
+    /// The case that identifiers are converted to, or `None` if they're used as-is.
+    pub case: Option<Case>,
+
+    /// The reserved words that identifiers are escaped against, after any case conversion.
+    pub reserved_words: Option<&'a ReservedWords>,
+}
+
+impl IdentifierRules<'_> {
+    /// Returns the identifier that the backend generates for the provided Slice identifier.
+    pub fn generated_identifier(&self, identifier: &str) -> String {
+        let converted = match self.case {
+            Some(case) => convert_case(identifier, case),
+            None => identifier.to_owned(),
+        };
+        match self.reserved_words {
+            Some(reserved_words) => reserved_words.escape(&converted),
+            None => converted,
+        }
+    }
+}
+
+/// Runs the backends selected by `options.targets` over an already-compiled `state`.
+/// If no targets were specified, every backend is run.
+///
+/// First each backend validates the AST (including checking for identifier collisions if the backend provides
+/// [IdentifierRules], and for unmapped custom types if it provides a
+/// [custom type attribute](Backend::custom_type_attribute)), and then, if no errors were reported, each backend
+/// generates its code.
+/// A backend reporting an error while generating code doesn't stop the remaining backends from generating theirs.
+/// An error is reported for each target or backend option that doesn't name one of the provided backends.
+///
+/// If `--check` or `--validate-references` was specified, this function is a no-op; compilation stops after the AST has
+/// been validated.
+pub fn run_backends(state: &mut CompilationState, options: &SliceOptions, backends: &[&dyn Backend]) {
+    if options.check || options.validate_references {
+        return;
+    }
+
+    // Report any targets or backend options that don't correspond to a registered backend.
+    let backend_option_names = options.backend_options.iter().filter_map(|o| split_backend_option(o));
+    let requested_names = options
+        .targets
+        .iter()
+        .map(String::as_str)
+        .chain(backend_option_names.map(|(backend, _, _)| backend));
+    let mut reported_names = Vec::new();
+    for name in requested_names {
+        if !backends.iter().any(|b| b.name() == name) && !reported_names.contains(&name) {
+            let error = Error::UnknownBackend { name: name.to_owned() };
+            Diagnostic::new(error).push_into(&mut state.diagnostics);
+            reported_names.push(name);
+        }
+    }
+
+    let selected_backends = backends
+        .iter()
+        .filter(|backend| options.targets.is_empty() || options.targets.iter().any(|t| t == backend.name()))
+        .map(|backend| (*backend, BackendOptions::new(backend.name(), options)))
+        .collect::<Vec<_>>();
+
+    // Backends only run if no errors have been reported, so they can safely assume the AST is valid.
+    // Every backend validates the AST, even if an earlier backend reported errors, so users see all their errors at once.
+    if state.diagnostics.has_errors() {
+        return;
+    }
+
+    // Custom types are checked for all the backends at once, so each type is reported once, with every missing mapping.
+    let mappings = selected_backends.iter().filter_map(|(backend, _)| {
+        let directive = backend.custom_type_attribute()?;
+        Some((backend.name(), directive))
+    });
+    let mappings = mappings.collect::<Vec<_>>();
+    check_for_missing_custom_type_mappings(&state.files, &mappings, &mut state.diagnostics);
+
+    for (backend, backend_options) in &selected_backends {
+        let start = Instant::now();
+        let _span = tracing::info_span!("validate", backend = backend.name()).entered();
+        panic_handler::set_phase(format!("validating ({})", backend.name()));
+        if let Some(rules) = backend.identifier_rules() {
+            check_for_generated_identifier_collisions(&state.ast, backend.name(), &rules, &mut state.diagnostics);
+        }
+        backend.validate(state, backend_options);
+        state
+            .timings
+            .record_phase(format!("validate ({})", backend.name()), start.elapsed());
+    }
+
+    // Code is only generated if none of the backends reported errors.
+    if options.dry_run || state.diagnostics.has_errors() {
+        return;
+    }
+    for (backend, backend_options) in &selected_backends {
+        let start = Instant::now();
+        let _span = tracing::info_span!("generate", backend = backend.name()).entered();
+        panic_handler::set_phase(format!("generating code ({})", backend.name()));
+        backend.generate(state, backend_options);
+        state
+            .timings
+            .record_phase(format!("generate ({})", backend.name()), start.elapsed());
+    }
+}
+
+/// Splits a backend option of the form `<BACKEND>:<KEY>[=<VALUE>]` into its components.
+/// Returns `None` if the option isn't of this form.
+fn split_backend_option(option: &str) -> Option<(&str, &str, &str)> {
+    let (backend, key_and_value) = option.split_once(':')?;
+    let (key, value) = key_and_value.split_once('=').unwrap_or((key_and_value, ""));
+    match backend.is_empty() || key.is_empty() {
+        true => None,
+        false => Some((backend, key, value)),
+    }
+}
+
+/// Validates that a backend option is of the form `<BACKEND>:<KEY>[=<VALUE>]`. Used by clap to parse `-X` options.
+pub(crate) fn parse_backend_option(option: &str) -> Result<String, String> {
+    match split_backend_option(option) {
+        Some(_) => Ok(option.to_owned()),
+        None => Err("backend options must be of the form '<BACKEND>:<KEY>' or '<BACKEND>:<KEY>=<VALUE>'".to_owned()),
+    }
+}
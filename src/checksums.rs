@@ -0,0 +1,94 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module computes stable checksums for Slice definitions, similar to Ice's Slice checksums.
+//!
+//! A definition's checksum only depends on its structure: its kind, identifier, members, types, and tags.
+//! Comments, attributes, whitespace, and formatting don't affect it, nor do the names of any type aliases it uses.
+//! Runtimes can compare the checksums of the definitions that a client and server were generated from,
+//! to verify that they're compatible.
+
+use crate::ast::node::Node;
+use crate::ast::Ast;
+use crate::grammar::*;
+use crate::rendering::{render_members, render_signature_with, RenderOptions};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// The checksums of every type and interface in an AST, keyed by their fully scoped identifiers.
+///
+/// Type aliases and constants aren't included, since they only exist at compile time.
+#[derive(Debug, Default, Serialize)]
+pub struct ChecksumManifest {
+    pub checksums: BTreeMap<String, String>,
+}
+
+impl ChecksumManifest {
+    /// Returns this manifest as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize checksum manifest")
+    }
+}
+
+/// Computes the checksum of the provided entity, formatted as a string of 64 lowercase hexadecimal digits.
+///
+/// This is equivalent to calling [Entity::checksum] on the entity.
+pub fn compute_checksum(entity: &(impl Entity + ?Sized)) -> String {
+    let mut hash_engine = Sha256::new();
+    hash_engine.update(canonical_form(entity));
+    format!("{:x}", hash_engine.finalize())
+}
+
+/// Computes the checksum of every struct, class, exception, interface, enum, and custom type in the provided AST.
+pub fn generate_checksum_manifest(ast: &Ast) -> ChecksumManifest {
+    let mut manifest = ChecksumManifest::default();
+    for node in ast.as_slice() {
+        let entity: &dyn Entity = match node {
+            Node::Struct(ptr) => ptr.borrow(),
+            Node::Class(ptr) => ptr.borrow(),
+            Node::Exception(ptr) => ptr.borrow(),
+            Node::Interface(ptr) => ptr.borrow(),
+            Node::Enum(ptr) => ptr.borrow(),
+            Node::CustomType(ptr) => ptr.borrow(),
+            _ => continue,
+        };
+        let checksum = compute_checksum(entity);
+        manifest.checksums.insert(entity.parser_scoped_identifier(), checksum);
+    }
+    manifest
+}
+
+/// The options used to render the signatures which canonical forms are built from.
+const CANONICAL_OPTIONS: RenderOptions = RenderOptions {
+    include_attributes: false,
+    expand_type_aliases: true,
+};
+
+/// Returns a canonical string representation of the provided entity, which checksums are computed from.
+/// Any two definitions with the same structure will have the same canonical form, regardless of how they were written.
+///
+/// An entity's canonical form is its [signature](crate::rendering::render_signature) (without attributes, and with
+/// type aliases replaced by their underlying types), followed by the canonical forms of any members it contains.
+fn canonical_form(entity: &(impl Entity + ?Sized)) -> String {
+    let signature = render_signature_with(entity.concrete_entity().as_entity(), CANONICAL_OPTIONS);
+    match entity.concrete_entity() {
+        Entities::Struct(struct_def) => format!("{signature} {}", members(struct_def.fields())),
+        Entities::Class(class_def) => format!("{signature} {}", members(class_def.fields())),
+        Entities::Exception(exception_def) => format!("{signature} {}", members(exception_def.fields())),
+        Entities::Interface(interface_def) => {
+            let operations = interface_def.operations().into_iter().map(canonical_form);
+            format!("{signature} {{ {} }}", operations.collect::<Vec<_>>().join("; "))
+        }
+        // The encoding of an operation affects how its parameters and return members are encoded on the wire.
+        Entities::Operation(operation) => format!("{} {signature}", operation.encoding),
+        Entities::Enum(enum_def) => {
+            let enumerators = enum_def.enumerators().into_iter().map(canonical_form);
+            format!("{signature} {{ {} }}", enumerators.collect::<Vec<_>>().join(", "))
+        }
+        _ => signature,
+    }
+}
+
+fn members<T: Member>(members: Vec<&T>) -> String {
+    render_members(members, CANONICAL_OPTIONS)
+}
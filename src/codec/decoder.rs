@@ -0,0 +1,604 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::value::{Fields, Value};
+use super::*;
+use crate::ast::node::Node;
+use crate::ast::Ast;
+use crate::grammar::*;
+use crate::utils::code_gen_util::is_element_bit_sequence_encodable;
+
+/// Decodes [Value]s from a buffer, using the rules of a single Slice encoding.
+#[derive(Debug)]
+pub(super) struct Decoder<'a> {
+    encoding: Encoding,
+    bytes: &'a [u8],
+    position: usize,
+
+    /// The AST that class and exception instances are resolved against.
+    ast: &'a Ast,
+
+    /// The Slice1 type IDs that have been decoded so far. Type IDs encoded as indexes refer to this list.
+    type_ids: Vec<String>,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8], encoding: Encoding, ast: &'a Ast) -> Self {
+        Decoder {
+            encoding,
+            bytes,
+            position: 0,
+            ast,
+            type_ids: Vec::new(),
+        }
+    }
+
+    /// Returns an error if there are any bytes left in the buffer that haven't been decoded.
+    pub fn finish(&self) -> Result<(), CodecError> {
+        match self.bytes.len() - self.position {
+            0 => Ok(()),
+            remaining => Err(CodecError::InvalidData(format!(
+                "{remaining} bytes remained in the buffer after decoding finished"
+            ))),
+        }
+    }
+
+    /// Decodes a (non-null) value of the provided type.
+    pub fn decode_type(&mut self, data_type: &dyn Type) -> Result<Value, CodecError> {
+        match data_type.concrete_type() {
+            Types::Struct(struct_def) => self.decode_struct(struct_def),
+            Types::Class(_) => self.decode_class_reference(),
+            Types::Enum(enum_def) => self.decode_enum(enum_def),
+            Types::CustomType(custom_type) => Err(CodecError::unsupported_custom_type(custom_type)),
+            Types::ResultType(result_type) => self.decode_result(result_type),
+            Types::Sequence(sequence) => self.decode_sequence(sequence),
+            Types::Dictionary(dictionary) => self.decode_dictionary(dictionary),
+            Types::Primitive(primitive) => self.decode_primitive(primitive),
+        }
+    }
+
+    fn decode_primitive(&mut self, primitive: &Primitive) -> Result<Value, CodecError> {
+        let value = match primitive {
+            Primitive::Bool => match self.read_u8()? {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                other => return Err(CodecError::InvalidData(format!("{other} is not a valid bool"))),
+            },
+            Primitive::Float32 => Value::Float32(f32::from_le_bytes(self.read_array()?)),
+            Primitive::Float64 => Value::Float64(f64::from_le_bytes(self.read_array()?)),
+            Primitive::String => Value::String(self.read_string()?),
+            Primitive::Uuid => Value::Uuid(self.read_array()?),
+            Primitive::Timestamp | Primitive::Duration => Value::Integer(self.read_integer(&Primitive::Int64)?),
+            Primitive::AnyClass => self.decode_class_reference()?,
+            _ => Value::Integer(self.read_integer(primitive)?),
+        };
+        Ok(value)
+    }
+
+    fn decode_struct(&mut self, struct_def: &Struct) -> Result<Value, CodecError> {
+        let fields = self.decode_fields(&struct_def.fields(), !struct_def.is_compact)?;
+        Ok(Value::Struct(fields))
+    }
+
+    fn decode_enum(&mut self, enum_def: &Enum) -> Result<Value, CodecError> {
+        let value = match &enum_def.underlying {
+            Some(underlying) => self.read_integer(underlying.definition())?,
+            None => match self.encoding {
+                Encoding::Slice1 => self.read_size()? as i128,
+                Encoding::Slice2 => self.read_integer(&Primitive::VarInt32)?,
+            },
+        };
+
+        let enumerator = enum_def
+            .enumerators()
+            .into_iter()
+            .find(|e| !e.is_alias() && e.value() == value);
+        if enumerator.is_none() && !enum_def.is_unchecked && !enum_def.is_flags() {
+            return Err(CodecError::InvalidData(format!(
+                "{value} is not a valid value for the enum '{}'",
+                enum_def.parser_scoped_identifier(),
+            )));
+        }
+        let identifier = enumerator.map(|e| e.identifier().to_owned());
+
+        // Enums with fields encode the fields of their enumerators after the discriminant.
+        let mut fields = None;
+        if enum_def.enumerators().iter().any(|e| e.fields.is_some()) {
+            // Unchecked enums prefix their fields with a size, so unknown enumerators can be skipped.
+            let size = match enum_def.is_unchecked {
+                true => Some(self.read_size()?),
+                false => None,
+            };
+
+            match enumerator {
+                Some(enumerator) => {
+                    let start = self.position;
+                    fields = Some(self.decode_fields(&enumerator.fields(), !enum_def.is_compact)?);
+                    if let Some(size) = size {
+                        self.check_consumed(start, size)?;
+                    }
+                }
+                None => self.skip(size.unwrap())?,
+            }
+        }
+
+        Ok(Value::Enumerator {
+            identifier,
+            value,
+            fields,
+        })
+    }
+
+    fn decode_result(&mut self, result_type: &ResultType) -> Result<Value, CodecError> {
+        let discriminant = self.read_integer(&Primitive::VarInt32)?;
+        let type_ref = match discriminant {
+            0 => &result_type.success_type,
+            1 => &result_type.failure_type,
+            _ => {
+                return Err(CodecError::InvalidData(format!(
+                    "{discriminant} is not a valid result discriminant"
+                )))
+            }
+        };
+
+        let value = Box::new(self.decode_members(&[type_ref])?.remove(0));
+        Ok(match discriminant {
+            0 => Value::Success(value),
+            _ => Value::Failure(value),
+        })
+    }
+
+    fn decode_sequence(&mut self, sequence: &Sequence) -> Result<Value, CodecError> {
+        let element_type = &sequence.element_type;
+        let element_count = self.read_collection_size()?;
+
+        let mut elements = Vec::new();
+        if is_element_bit_sequence_encodable(element_type, self.encoding) {
+            let bit_sequence = self.read_bit_sequence(element_count)?;
+            for is_set in bit_sequence {
+                elements.push(match is_set {
+                    true => self.decode_type(element_type.definition())?,
+                    false => Value::Null,
+                });
+            }
+        } else {
+            for _ in 0..element_count {
+                elements.push(self.decode_type(element_type.definition())?);
+            }
+        }
+        Ok(Value::Sequence(elements))
+    }
+
+    fn decode_dictionary(&mut self, dictionary: &Dictionary) -> Result<Value, CodecError> {
+        let value_type = &dictionary.value_type;
+        let entry_count = self.read_collection_size()?;
+
+        let mut entries = Vec::new();
+        if is_element_bit_sequence_encodable(value_type, self.encoding) {
+            let bit_sequence = self.read_bit_sequence(entry_count)?;
+            for is_set in bit_sequence {
+                let key = self.decode_type(dictionary.key_type.definition())?;
+                let value = match is_set {
+                    true => self.decode_type(value_type.definition())?,
+                    false => Value::Null,
+                };
+                entries.push((key, value));
+            }
+        } else {
+            for _ in 0..entry_count {
+                let key = self.decode_type(dictionary.key_type.definition())?;
+                let value = self.decode_type(value_type.definition())?;
+                entries.push((key, value));
+            }
+        }
+        Ok(Value::Dictionary(entries))
+    }
+
+    /// Decodes a Slice1 class reference, followed by the instance it references (if it isn't null).
+    fn decode_class_reference(&mut self) -> Result<Value, CodecError> {
+        match self.read_size()? {
+            0 => return Ok(Value::Null),
+            1 => {}
+            _ => {
+                let message = "references to previously decoded instances are not supported";
+                return Err(CodecError::Unsupported(message.to_owned()));
+            }
+        }
+
+        let mut slices = Vec::new();
+        let mut next_slice: Option<&Class> = None;
+        loop {
+            let flags = self.read_slice_flags()?;
+            let is_first_slice = slices.is_empty();
+
+            // Type IDs are always present on the first slice, and on every slice when using the sliced format.
+            let class_def = match (is_first_slice || flags & FLAG_HAS_SLICE_SIZE != 0, flags & 0x03) {
+                (true, FLAG_HAS_TYPE_ID_STRING) => {
+                    let type_id = self.read_string()?;
+                    self.type_ids.push(type_id.clone());
+                    self.find_class(&type_id)?
+                }
+                (true, FLAG_HAS_TYPE_ID_INDEX) => {
+                    let index = self.read_size()?;
+                    let type_id = self.type_ids.get(index.wrapping_sub(1)).cloned();
+                    let type_id = type_id.ok_or_else(|| invalid_type_id_index(index))?;
+                    self.find_class(&type_id)?
+                }
+                (true, FLAG_HAS_TYPE_ID_COMPACT) => {
+                    let compact_id = self.read_size()?;
+                    self.find_class_by_compact_id(compact_id)?
+                }
+                _ => next_slice.ok_or_else(|| CodecError::InvalidData("missing type ID".to_owned()))?,
+            };
+            if flags & FLAG_HAS_SLICE_SIZE != 0 {
+                self.read_array::<4>()?; // The size is redundant, since we know how to decode the slice.
+            }
+
+            let fields = self.decode_fields(&class_def.fields(), flags & FLAG_HAS_TAGGED_MEMBERS != 0)?;
+            slices.push((class_def, fields));
+
+            if flags & FLAG_IS_LAST_SLICE != 0 {
+                break;
+            }
+            next_slice = class_def.base_class();
+        }
+
+        let type_name = slices[0].0.parser_scoped_identifier();
+        let fields = slices.into_iter().rev().flat_map(|(_, fields)| fields).collect();
+        Ok(Value::Instance { type_name, fields })
+    }
+
+    /// Decodes a Slice1 exception, which is encoded as a series of slices, like a class instance.
+    pub fn decode_exception(&mut self, exception_def: &Exception) -> Result<Value, CodecError> {
+        let mut slices = Vec::new();
+        let mut next_slice: Option<&Exception> = None;
+        loop {
+            let flags = self.read_slice_flags()?;
+            let is_first_slice = slices.is_empty();
+
+            // Type IDs are always present on the first slice, and on every slice when using the sliced format.
+            let slice_def = if is_first_slice || flags & FLAG_HAS_SLICE_SIZE != 0 {
+                let type_id = self.read_string()?;
+                match type_id.strip_prefix("::") == Some(&exception_def.parser_scoped_identifier()) {
+                    true => exception_def,
+                    false => self.find_element::<Exception>(&type_id)?,
+                }
+            } else {
+                next_slice.ok_or_else(|| CodecError::InvalidData("missing type ID".to_owned()))?
+            };
+            if flags & FLAG_HAS_SLICE_SIZE != 0 {
+                self.read_array::<4>()?; // The size is redundant, since we know how to decode the slice.
+            }
+
+            let fields = self.decode_fields(&slice_def.fields(), flags & FLAG_HAS_TAGGED_MEMBERS != 0)?;
+            slices.push((slice_def, fields));
+
+            if flags & FLAG_IS_LAST_SLICE != 0 {
+                break;
+            }
+            next_slice = slice_def.base_exception();
+        }
+
+        let type_name = slices[0].0.parser_scoped_identifier();
+        let fields = slices.into_iter().rev().flat_map(|(_, fields)| fields).collect();
+        Ok(Value::Instance { type_name, fields })
+    }
+
+    fn read_slice_flags(&mut self) -> Result<u8, CodecError> {
+        let flags = self.read_u8()?;
+        if flags & FLAG_HAS_INDIRECTION_TABLE != 0 {
+            let message = "slices with indirection tables are not supported";
+            return Err(CodecError::Unsupported(message.to_owned()));
+        }
+        Ok(flags)
+    }
+
+    fn find_class(&self, type_id: &str) -> Result<&'a Class, CodecError> {
+        self.find_element::<Class>(type_id)
+    }
+
+    fn find_class_by_compact_id(&self, compact_id: usize) -> Result<&'a Class, CodecError> {
+        let ast: &'a Ast = self.ast;
+        ast.as_slice()
+            .iter()
+            .find_map(|node| match node {
+                Node::Class(ptr) => {
+                    let class_def = ptr.borrow();
+                    let id = class_def.compact_id.as_ref().map(|id| id.value as usize);
+                    (id == Some(compact_id)).then_some(class_def)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| CodecError::UnknownType(compact_id.to_string()))
+    }
+
+    fn find_element<T: Element + ?Sized>(&self, type_id: &str) -> Result<&'a T, CodecError>
+    where
+        &'a T: TryFrom<&'a Node, Error = crate::ast::LookupError>,
+    {
+        let ast: &'a Ast = self.ast;
+        let identifier = type_id.strip_prefix("::").unwrap_or(type_id);
+        ast.find_element::<T>(identifier)
+            .map_err(|_| CodecError::UnknownType(type_id.to_owned()))
+    }
+
+    /// Decodes the parameters (or return members) of an operation.
+    pub fn decode_parameters(&mut self, parameters: &[&Parameter]) -> Result<Fields, CodecError> {
+        match self.encoding {
+            // With Slice1, tagged parameters are terminated by the end of the payload, instead of an end marker.
+            Encoding::Slice1 => self.decode_members_of(parameters, TaggedMembers::UntilEndOfBuffer),
+            Encoding::Slice2 => self.decode_members_of(parameters, TaggedMembers::UntilEndMarker),
+        }
+    }
+
+    /// Decodes the provided fields: first the untagged fields in the order they were declared,
+    /// followed by tagged fields, if `has_tagged_fields` is true (in which case, an end marker is expected).
+    fn decode_fields(&mut self, field_defs: &[&Field], has_tagged_fields: bool) -> Result<Fields, CodecError> {
+        let tagged_members = match has_tagged_fields {
+            true => TaggedMembers::UntilEndMarker,
+            false => TaggedMembers::None,
+        };
+        self.decode_members_of(field_defs, tagged_members)
+    }
+
+    /// Decodes the values of the provided members, returning them in the order they were declared.
+    /// Unknown tagged members are skipped, and any tagged members that aren't present are left unset.
+    fn decode_members_of<M: Member>(
+        &mut self,
+        member_defs: &[&M],
+        tagged_members: TaggedMembers,
+    ) -> Result<Fields, CodecError> {
+        let untagged_members = member_defs.iter().filter(|m| !m.is_tagged()).collect::<Vec<_>>();
+        let type_refs = untagged_members.iter().map(|m| m.data_type()).collect::<Vec<_>>();
+        let untagged_values = self.decode_members(&type_refs)?;
+
+        let mut tagged_values = Vec::new();
+        loop {
+            let (tag, format_code) = match tagged_members {
+                TaggedMembers::None => break,
+                TaggedMembers::UntilEndOfBuffer if self.position == self.bytes.len() => break,
+                TaggedMembers::UntilEndOfBuffer => self.read_tag()?.ok_or_else(unexpected_end_marker)?,
+                TaggedMembers::UntilEndMarker => match self.read_tag()? {
+                    Some(header) => header,
+                    None => break,
+                },
+            };
+            match member_defs.iter().find(|m| m.tag() == Some(tag)) {
+                Some(member) => tagged_values.push((tag, self.decode_tagged_value(member.data_type())?)),
+                None => self.skip_tagged_value(format_code)?,
+            }
+        }
+
+        // Assemble the values in the order they were declared in.
+        let mut untagged_values = untagged_values.into_iter();
+        let fields = member_defs.iter().map(|member| {
+            let value = match member.tag() {
+                None => untagged_values.next().unwrap(),
+                Some(tag) => {
+                    let tagged_value = tagged_values.iter().position(|(t, _)| *t == tag);
+                    tagged_value.map_or(Value::Null, |i| tagged_values.swap_remove(i).1)
+                }
+            };
+            (member.identifier().to_owned(), value)
+        });
+        Ok(fields.collect())
+    }
+
+    /// Decodes a series of untagged members. With Slice2 these are preceded by a bit sequence,
+    /// holding one bit for each optional member that indicates whether it's set.
+    fn decode_members(&mut self, type_refs: &[&TypeRef]) -> Result<Vec<Value>, CodecError> {
+        let uses_bit_sequence = self.uses_bit_sequences();
+        let optional_count = type_refs.iter().filter(|type_ref| type_ref.is_optional).count();
+        let mut bit_sequence = match uses_bit_sequence {
+            true => self.read_bit_sequence(optional_count)?.into_iter(),
+            false => Vec::new().into_iter(),
+        };
+
+        let mut values = Vec::new();
+        for type_ref in type_refs {
+            let is_set = match type_ref.is_optional && uses_bit_sequence {
+                true => bit_sequence.next().unwrap(),
+                false => true,
+            };
+            values.push(match is_set {
+                true => self.decode_type(type_ref.definition())?,
+                false => Value::Null,
+            });
+        }
+        Ok(values)
+    }
+
+    /// Reads the header of a tagged member, returning its tag and (with Slice1) its format code.
+    /// Returns `None` if the end marker was read instead.
+    fn read_tag(&mut self) -> Result<Option<(u32, u8)>, CodecError> {
+        match self.encoding {
+            Encoding::Slice1 => {
+                let header = self.read_u8()?;
+                if header == SLICE1_TAG_END_MARKER {
+                    return Ok(None);
+                }
+                let tag = match header >> 3 {
+                    30 => self.read_size()? as u32,
+                    tag => tag as u32,
+                };
+                Ok(Some((tag, header & 0x07)))
+            }
+            Encoding::Slice2 => match self.read_integer(&Primitive::VarInt32)? {
+                -1 => Ok(None),
+                tag if tag >= 0 => Ok(Some((tag as u32, 0))),
+                tag => Err(CodecError::InvalidData(format!("{tag} is not a valid tag"))),
+            },
+        }
+    }
+
+    fn decode_tagged_value(&mut self, type_ref: &TypeRef) -> Result<Value, CodecError> {
+        let size = match self.encoding {
+            // With Slice2, every tagged value is prefixed by its size.
+            Encoding::Slice2 => Some(self.read_size()?),
+
+            // With Slice1, whether a tagged value is prefixed by its size depends on its tag format.
+            Encoding::Slice1 => match type_ref.tag_format() {
+                Some(TagFormat::VSize) => Some(self.read_size()?),
+                Some(TagFormat::FSize) => Some(self.read_fixed_size()?),
+                _ => None,
+            },
+        };
+
+        let start = self.position;
+        let value = self.decode_type(type_ref.definition())?;
+        if let Some(size) = size {
+            self.check_consumed(start, size)?;
+        }
+        Ok(value)
+    }
+
+    fn skip_tagged_value(&mut self, format_code: u8) -> Result<(), CodecError> {
+        let size = match self.encoding {
+            Encoding::Slice2 => self.read_size()?,
+            Encoding::Slice1 => match format_code {
+                0 => 1,
+                1 => 2,
+                2 => 4,
+                3 => 8,
+                4 => {
+                    self.read_size()?;
+                    0
+                }
+                5 => self.read_size()?,
+                6 => self.read_fixed_size()?,
+                _ => return Err(CodecError::Unsupported("cannot skip tagged classes".to_owned())),
+            },
+        };
+        self.skip(size)
+    }
+
+    fn uses_bit_sequences(&self) -> bool {
+        self.encoding == Encoding::Slice2
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], CodecError> {
+        let bytes: &'a [u8] = self.bytes;
+        let end = self.position.checked_add(count).ok_or(CodecError::EndOfBuffer)?;
+        let slice = bytes.get(self.position..end).ok_or(CodecError::EndOfBuffer)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], CodecError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, CodecError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), CodecError> {
+        self.read_bytes(count).map(|_| ())
+    }
+
+    /// Returns an error unless exactly `size` bytes have been decoded since `start`.
+    fn check_consumed(&self, start: usize, size: usize) -> Result<(), CodecError> {
+        match self.position - start == size {
+            true => Ok(()),
+            false => Err(CodecError::InvalidData(format!(
+                "expected a value of {size} bytes, but decoded {} bytes",
+                self.position - start,
+            ))),
+        }
+    }
+
+    /// Reads an integral value using the encoding of the provided primitive.
+    fn read_integer(&mut self, primitive: &Primitive) -> Result<i128, CodecError> {
+        let value = match primitive {
+            Primitive::Int8 => i8::from_le_bytes(self.read_array()?) as i128,
+            Primitive::UInt8 => u8::from_le_bytes(self.read_array()?) as i128,
+            Primitive::Int16 => i16::from_le_bytes(self.read_array()?) as i128,
+            Primitive::UInt16 => u16::from_le_bytes(self.read_array()?) as i128,
+            Primitive::Int32 => i32::from_le_bytes(self.read_array()?) as i128,
+            Primitive::UInt32 => u32::from_le_bytes(self.read_array()?) as i128,
+            Primitive::Int64 => i64::from_le_bytes(self.read_array()?) as i128,
+            Primitive::UInt64 => u64::from_le_bytes(self.read_array()?) as i128,
+            Primitive::VarInt32 | Primitive::VarInt62 => {
+                let (encoded, byte_count) = self.read_var_length()?;
+                // Shift the value to the top of the integer, so the arithmetic shift back down sign-extends it.
+                let unused_bits = 64 - 8 * byte_count;
+                (((encoded << unused_bits) as i64) >> (unused_bits + 2)) as i128
+            }
+            Primitive::VarUInt32 | Primitive::VarUInt62 => (self.read_var_length()?.0 >> 2) as i128,
+            _ => unreachable!("'{}' is not an integral type", primitive.kind()),
+        };
+
+        // Variable-length integers can hold values outside the range of their type, so we have to check them.
+        let (min, max) = primitive.numeric_bounds().unwrap();
+        match (min..=max).contains(&value) {
+            true => Ok(value),
+            false => Err(CodecError::OutOfRange {
+                value,
+                type_string: primitive.type_string(),
+            }),
+        }
+    }
+
+    /// Reads a variable-length integer, returning its raw (still shifted) bits, and how many bytes it was encoded on.
+    fn read_var_length(&mut self) -> Result<(u64, u32), CodecError> {
+        let first_byte = *self.bytes.get(self.position).ok_or(CodecError::EndOfBuffer)?;
+        let byte_count = 1 << (first_byte & 0x03);
+
+        let mut buffer = [0; 8];
+        buffer[..byte_count].copy_from_slice(self.read_bytes(byte_count)?);
+        Ok((u64::from_le_bytes(buffer), byte_count as u32))
+    }
+
+    fn read_size(&mut self) -> Result<usize, CodecError> {
+        match self.encoding {
+            Encoding::Slice1 => match self.read_u8()? {
+                255 => self.read_fixed_size(),
+                size => Ok(size as usize),
+            },
+            Encoding::Slice2 => Ok(self.read_integer(&Primitive::VarUInt62)? as usize),
+        }
+    }
+
+    fn read_fixed_size(&mut self) -> Result<usize, CodecError> {
+        match i32::from_le_bytes(self.read_array()?) {
+            size if size >= 0 => Ok(size as usize),
+            size => Err(CodecError::InvalidData(format!("{size} is not a valid size"))),
+        }
+    }
+
+    /// Reads the size of a collection. Since every element is encoded on at least 1 byte (or 1 bit, if optional),
+    /// collections can't have more elements than there are bytes remaining, which protects against huge allocations.
+    fn read_collection_size(&mut self) -> Result<usize, CodecError> {
+        let size = self.read_size()?;
+        match size <= 8 * (self.bytes.len() - self.position) {
+            true => Ok(size),
+            false => Err(CodecError::EndOfBuffer),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, CodecError> {
+        let size = self.read_size()?;
+        let bytes = self.read_bytes(size)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| CodecError::InvalidData("strings must be valid UTF-8".to_owned()))
+    }
+
+    fn read_bit_sequence(&mut self, bit_count: usize) -> Result<Vec<bool>, CodecError> {
+        let bytes = self.read_bytes(bit_count.div_ceil(8))?;
+        Ok((0..bit_count).map(|i| bytes[i / 8] & (1 << (i % 8)) != 0).collect())
+    }
+}
+
+/// Specifies how the tagged members of a type are terminated, if it can have tagged members.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TaggedMembers {
+    None,
+    UntilEndMarker,
+    UntilEndOfBuffer,
+}
+
+fn unexpected_end_marker() -> CodecError {
+    CodecError::InvalidData("unexpected tag end marker".to_owned())
+}
+
+fn invalid_type_id_index(index: usize) -> CodecError {
+    CodecError::InvalidData(format!("{index} is not the index of a previously decoded type ID"))
+}
@@ -0,0 +1,575 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::value::{Fields, Value};
+use super::*;
+use crate::ast::Ast;
+use crate::grammar::*;
+use crate::utils::code_gen_util::is_element_bit_sequence_encodable;
+use std::collections::HashMap;
+
+/// Used for optional members that weren't given a value.
+static NULL: Value = Value::Null;
+
+/// Encodes [Value]s into a buffer, using the rules of a single Slice encoding.
+#[derive(Debug)]
+pub(super) struct Encoder<'a> {
+    encoding: Encoding,
+    buffer: Vec<u8>,
+
+    /// The AST that class and exception instances are resolved against.
+    ast: &'a Ast,
+
+    /// Maps the Slice1 type IDs that have already been written to the index they were registered with.
+    /// Subsequent occurrences of a type ID are encoded as this index instead of as a string.
+    type_ids: HashMap<String, usize>,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(encoding: Encoding, ast: &'a Ast) -> Self {
+        Encoder {
+            encoding,
+            buffer: Vec::new(),
+            ast,
+            type_ids: HashMap::new(),
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Encodes a (non-null) value of the provided type.
+    pub fn encode_type(&mut self, value: &Value, data_type: &dyn Type) -> Result<(), CodecError> {
+        match data_type.concrete_type() {
+            Types::Struct(struct_def) => self.encode_struct(value, struct_def),
+            Types::Class(class_def) => self.encode_class_reference(value, Some(class_def)),
+            Types::Enum(enum_def) => self.encode_enum(value, enum_def),
+            Types::CustomType(custom_type) => Err(CodecError::unsupported_custom_type(custom_type)),
+            Types::ResultType(result_type) => self.encode_result(value, result_type),
+            Types::Sequence(sequence) => self.encode_sequence(value, sequence),
+            Types::Dictionary(dictionary) => self.encode_dictionary(value, dictionary),
+            Types::Primitive(primitive) => self.encode_primitive(value, primitive),
+        }
+    }
+
+    fn encode_primitive(&mut self, value: &Value, primitive: &Primitive) -> Result<(), CodecError> {
+        match (primitive, value) {
+            (Primitive::Bool, Value::Bool(v)) => self.write_bool(*v),
+            (Primitive::Float32, Value::Float32(v)) => self.write_bytes(&v.to_le_bytes()),
+            (Primitive::Float64, Value::Float64(v)) => self.write_bytes(&v.to_le_bytes()),
+            (Primitive::String, Value::String(v)) => self.write_string(v),
+            (Primitive::Uuid, Value::Uuid(bytes)) => self.write_bytes(bytes),
+            (Primitive::Timestamp | Primitive::Duration, Value::Integer(v)) => {
+                check_bounds(*v, (i64::MIN as i128, i64::MAX as i128), primitive)?;
+                self.write_integer(&Primitive::Int64, *v);
+            }
+            (Primitive::AnyClass, _) => return self.encode_class_reference(value, None),
+            (_, Value::Integer(v)) if primitive.is_integral() => {
+                check_bounds(*v, primitive.numeric_bounds().unwrap(), primitive)?;
+                self.write_integer(primitive, *v);
+            }
+            _ => return Err(CodecError::mismatch(primitive, value)),
+        }
+        Ok(())
+    }
+
+    fn encode_struct(&mut self, value: &Value, struct_def: &Struct) -> Result<(), CodecError> {
+        let Value::Struct(fields) = value else {
+            return Err(CodecError::mismatch(struct_def, value));
+        };
+
+        self.encode_fields(&struct_def.fields(), fields)?;
+        if !struct_def.is_compact {
+            self.write_tag_end_marker();
+        }
+        Ok(())
+    }
+
+    fn encode_enum(&mut self, value: &Value, enum_def: &Enum) -> Result<(), CodecError> {
+        let Value::Enumerator {
+            identifier,
+            value: raw_value,
+            fields,
+        } = value
+        else {
+            return Err(CodecError::mismatch(enum_def, value));
+        };
+
+        // Determine which enumerator is being encoded. Only unchecked and flags enums can encode arbitrary values.
+        let enumerator = match identifier {
+            Some(identifier) => {
+                let enumerator = enum_def
+                    .enumerators()
+                    .into_iter()
+                    .find(|e| e.identifier() == identifier);
+                Some(enumerator.ok_or_else(|| CodecError::UnknownEnumerator {
+                    enum_identifier: enum_def.parser_scoped_identifier(),
+                    enumerator: identifier.clone(),
+                })?)
+            }
+            None if enum_def.is_unchecked || enum_def.is_flags() => None,
+            None => {
+                return Err(CodecError::UnknownEnumerator {
+                    enum_identifier: enum_def.parser_scoped_identifier(),
+                    enumerator: raw_value.to_string(),
+                })
+            }
+        };
+        let discriminant = enumerator.map_or(*raw_value, Enumerator::value);
+
+        match &enum_def.underlying {
+            Some(underlying) => {
+                check_bounds(
+                    discriminant,
+                    underlying.numeric_bounds().unwrap(),
+                    underlying.definition(),
+                )?;
+                self.write_integer(underlying.definition(), discriminant);
+            }
+            None => {
+                check_bounds(discriminant, (0, i32::MAX as i128), enum_def)?;
+                match self.encoding {
+                    Encoding::Slice1 => self.write_size(discriminant as usize),
+                    Encoding::Slice2 => self.write_varint62(discriminant as i64),
+                }
+            }
+        }
+
+        // Enums with fields encode the fields of their enumerators after the discriminant.
+        if !enum_def.enumerators().iter().any(|e| e.fields.is_some()) {
+            return Ok(());
+        }
+        let Some(enumerator) = enumerator else {
+            return Err(CodecError::Unsupported(format!(
+                "cannot encode the fields of an unknown enumerator of '{}'",
+                enum_def.parser_scoped_identifier(),
+            )));
+        };
+
+        let start = self.buffer.len();
+        self.encode_fields(&enumerator.fields(), fields.as_ref().unwrap_or(&Vec::new()))?;
+        if !enum_def.is_compact {
+            self.write_tag_end_marker();
+        }
+        // Unchecked enums prefix their fields with a size, so unknown enumerators can be skipped.
+        if enum_def.is_unchecked {
+            self.prefix_with_size(start);
+        }
+        Ok(())
+    }
+
+    fn encode_result(&mut self, value: &Value, result_type: &ResultType) -> Result<(), CodecError> {
+        let (discriminant, inner_value, type_ref) = match value {
+            Value::Success(inner_value) => (0, inner_value, &result_type.success_type),
+            Value::Failure(inner_value) => (1, inner_value, &result_type.failure_type),
+            _ => return Err(CodecError::mismatch(result_type, value)),
+        };
+
+        self.write_varint62(discriminant);
+        self.encode_members(&[(type_ref, inner_value)])
+    }
+
+    fn encode_sequence(&mut self, value: &Value, sequence: &Sequence) -> Result<(), CodecError> {
+        let Value::Sequence(elements) = value else {
+            return Err(CodecError::mismatch(sequence, value));
+        };
+        let element_type = &sequence.element_type;
+
+        self.write_size(elements.len());
+        if is_element_bit_sequence_encodable(element_type, self.encoding) {
+            let bit_sequence = self.reserve_bit_sequence(elements.len());
+            for (index, element) in elements.iter().enumerate() {
+                if *element != Value::Null {
+                    self.set_bit(bit_sequence, index);
+                    self.encode_type(element, element_type.definition())?;
+                }
+            }
+        } else {
+            for element in elements {
+                self.encode_type(element, element_type.definition())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_dictionary(&mut self, value: &Value, dictionary: &Dictionary) -> Result<(), CodecError> {
+        let Value::Dictionary(entries) = value else {
+            return Err(CodecError::mismatch(dictionary, value));
+        };
+        let value_type = &dictionary.value_type;
+
+        self.write_size(entries.len());
+        if is_element_bit_sequence_encodable(value_type, self.encoding) {
+            let bit_sequence = self.reserve_bit_sequence(entries.len());
+            for (index, (key, entry_value)) in entries.iter().enumerate() {
+                self.encode_type(key, dictionary.key_type.definition())?;
+                if *entry_value != Value::Null {
+                    self.set_bit(bit_sequence, index);
+                    self.encode_type(entry_value, value_type.definition())?;
+                }
+            }
+        } else {
+            for (key, entry_value) in entries {
+                self.encode_type(key, dictionary.key_type.definition())?;
+                self.encode_type(entry_value, value_type.definition())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes a Slice1 class reference, followed by the instance it references (if it isn't null).
+    /// Instances are encoded inline using the compact format: one slice per class in its hierarchy,
+    /// starting with the most derived one, where only the first slice holds a type ID.
+    ///
+    /// If `declared_class` is `None`, the reference is of type `AnyClass`, and can hold an instance of any class.
+    fn encode_class_reference(&mut self, value: &Value, declared_class: Option<&Class>) -> Result<(), CodecError> {
+        let (type_name, fields) = match value {
+            Value::Null => {
+                self.write_size(0); // Null references are encoded as '0'.
+                return Ok(());
+            }
+            Value::Instance { type_name, fields } => (type_name, fields),
+            _ => {
+                let expected = declared_class.map_or("AnyClass".to_owned(), Class::type_string);
+                return Err(CodecError::TypeMismatch {
+                    expected,
+                    found: value.kind(),
+                });
+            }
+        };
+
+        let class_def = self.find_instance_type::<Class>(type_name)?;
+        let slices = std::iter::successors(Some(class_def), |c| c.base_class()).collect::<Vec<_>>();
+        if let Some(declared_class) = declared_class {
+            if !slices.iter().any(|c| std::ptr::eq(*c, declared_class)) {
+                return Err(CodecError::mismatch(declared_class, value));
+            }
+        }
+
+        self.write_size(1); // New instances are marked with '1'.
+        for (index, slice) in slices.iter().enumerate() {
+            let flags_position = self.buffer.len();
+            self.write_u8(0); // Placeholder for the slice flags.
+            let mut flags = 0;
+
+            if index == 0 {
+                if let Some(compact_id) = &slice.compact_id {
+                    flags |= FLAG_HAS_TYPE_ID_COMPACT;
+                    self.write_size(compact_id.value as usize);
+                } else {
+                    let type_id = format!("::{}", slice.parser_scoped_identifier());
+                    match self.register_type_id(&type_id) {
+                        Some(type_id_index) => {
+                            flags |= FLAG_HAS_TYPE_ID_INDEX;
+                            self.write_size(type_id_index);
+                        }
+                        None => {
+                            flags |= FLAG_HAS_TYPE_ID_STRING;
+                            self.write_string(&type_id);
+                        }
+                    }
+                }
+            }
+            if index == slices.len() - 1 {
+                flags |= FLAG_IS_LAST_SLICE;
+            }
+
+            if self.encode_fields(&slice.fields(), fields)? {
+                flags |= FLAG_HAS_TAGGED_MEMBERS;
+                self.write_tag_end_marker();
+            }
+            self.buffer[flags_position] = flags;
+        }
+        Ok(())
+    }
+
+    /// Encodes a Slice1 exception. Like classes, exceptions are encoded as a series of slices,
+    /// except that the type ID of an exception is always encoded as a string.
+    pub fn encode_exception(&mut self, value: &Value, exception_def: &Exception) -> Result<(), CodecError> {
+        let Value::Instance { type_name, fields } = value else {
+            let expected = exception_def.identifier().to_owned();
+            return Err(CodecError::TypeMismatch {
+                expected,
+                found: value.kind(),
+            });
+        };
+
+        let exception_def = match type_name == &exception_def.parser_scoped_identifier() {
+            true => exception_def,
+            false => self.find_instance_type::<Exception>(type_name)?,
+        };
+        let slices = std::iter::successors(Some(exception_def), |e| e.base_exception()).collect::<Vec<_>>();
+
+        for (index, slice) in slices.iter().enumerate() {
+            let flags_position = self.buffer.len();
+            self.write_u8(0); // Placeholder for the slice flags.
+            let mut flags = 0;
+
+            if index == 0 {
+                self.write_string(&format!("::{}", slice.parser_scoped_identifier()));
+            }
+            if index == slices.len() - 1 {
+                flags |= FLAG_IS_LAST_SLICE;
+            }
+
+            if self.encode_fields(&slice.fields(), fields)? {
+                flags |= FLAG_HAS_TAGGED_MEMBERS;
+                self.write_tag_end_marker();
+            }
+            self.buffer[flags_position] = flags;
+        }
+        Ok(())
+    }
+
+    fn find_instance_type<T: Element + ?Sized>(&self, type_name: &str) -> Result<&'a T, CodecError>
+    where
+        &'a T: TryFrom<&'a crate::ast::node::Node, Error = crate::ast::LookupError>,
+    {
+        self.ast
+            .find_element::<T>(type_name)
+            .map_err(|_| CodecError::UnknownType(type_name.to_owned()))
+    }
+
+    /// Encodes the provided fields: first the untagged fields in the order they were declared,
+    /// followed by any (set) tagged fields in order of their tags.
+    ///
+    /// Returns whether any tagged fields were encoded.
+    fn encode_fields(&mut self, field_defs: &[&Field], values: &Fields) -> Result<bool, CodecError> {
+        let (mut tagged_fields, untagged_fields): (Vec<&Field>, Vec<&Field>) =
+            field_defs.iter().partition(|field| field.is_tagged());
+        tagged_fields.sort_by_key(|field| field.tag());
+
+        let members = untagged_fields
+            .iter()
+            .map(|field| Ok((&field.data_type, find_field(field, values)?)))
+            .collect::<Result<Vec<_>, CodecError>>()?;
+        self.encode_members(&members)?;
+
+        let mut wrote_tagged_fields = false;
+        for field in tagged_fields {
+            let value = find_field(field, values)?;
+            if *value != Value::Null {
+                self.encode_tagged_value(field.tag().unwrap(), value, &field.data_type)?;
+                wrote_tagged_fields = true;
+            }
+        }
+        Ok(wrote_tagged_fields)
+    }
+
+    /// Encodes a series of untagged members. With Slice2 these are preceded by a bit sequence,
+    /// holding one bit for each optional member that indicates whether it's set.
+    fn encode_members(&mut self, members: &[(&TypeRef, &Value)]) -> Result<(), CodecError> {
+        let uses_bit_sequence = self.uses_bit_sequences();
+        let optional_count = members.iter().filter(|(type_ref, _)| type_ref.is_optional).count();
+        let bit_sequence = match uses_bit_sequence {
+            true => self.reserve_bit_sequence(optional_count),
+            false => self.buffer.len(),
+        };
+
+        let mut bit_index = 0;
+        for (type_ref, value) in members {
+            if type_ref.is_optional && uses_bit_sequence {
+                if **value != Value::Null {
+                    self.set_bit(bit_sequence, bit_index);
+                    self.encode_type(value, type_ref.definition())?;
+                }
+                bit_index += 1;
+            } else {
+                self.encode_type(value, type_ref.definition())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_tagged_value(&mut self, tag: u32, value: &Value, type_ref: &TypeRef) -> Result<(), CodecError> {
+        match self.encoding {
+            // With Slice2, every tagged value is prefixed by its size.
+            Encoding::Slice2 => {
+                self.write_tag(tag, &TagFormat::VSize);
+                let start = self.buffer.len();
+                self.encode_type(value, type_ref.definition())?;
+                self.prefix_with_size(start);
+            }
+
+            // With Slice1, how a tagged value is encoded depends on its tag format.
+            Encoding::Slice1 => {
+                let format = match type_ref.tag_format() {
+                    Some(TagFormat::Class) | None => {
+                        let message = format!("'{}' cannot be encoded as a tagged value", type_ref.type_string());
+                        return Err(CodecError::Unsupported(message));
+                    }
+                    Some(format) => format,
+                };
+
+                self.write_tag(tag, &format);
+                let start = self.buffer.len();
+                self.encode_type(value, type_ref.definition())?;
+                match format {
+                    TagFormat::VSize => self.prefix_with_size(start),
+                    TagFormat::FSize => self.prefix_with_fixed_size(start),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn uses_bit_sequences(&self) -> bool {
+        self.encoding == Encoding::Slice2
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(u8::from(value));
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Writes an integral value using the encoding of the provided primitive.
+    /// The caller is responsible for ensuring that `value` is within the primitive's bounds.
+    fn write_integer(&mut self, primitive: &Primitive, value: i128) {
+        match primitive {
+            Primitive::Int8 => self.write_bytes(&(value as i8).to_le_bytes()),
+            Primitive::UInt8 => self.write_bytes(&(value as u8).to_le_bytes()),
+            Primitive::Int16 => self.write_bytes(&(value as i16).to_le_bytes()),
+            Primitive::UInt16 => self.write_bytes(&(value as u16).to_le_bytes()),
+            Primitive::Int32 => self.write_bytes(&(value as i32).to_le_bytes()),
+            Primitive::UInt32 => self.write_bytes(&(value as u32).to_le_bytes()),
+            Primitive::Int64 => self.write_bytes(&(value as i64).to_le_bytes()),
+            Primitive::UInt64 => self.write_bytes(&(value as u64).to_le_bytes()),
+            Primitive::VarInt32 | Primitive::VarInt62 => self.write_varint62(value as i64),
+            Primitive::VarUInt32 | Primitive::VarUInt62 => self.write_varuint62(value as u64),
+            _ => unreachable!("'{}' is not an integral type", primitive.kind()),
+        }
+    }
+
+    /// Writes a signed integer on 1, 2, 4, or 8 bytes, with the 2 least significant bits holding the length.
+    fn write_varint62(&mut self, value: i64) {
+        let length_code = match value {
+            -32..=31 => 0,
+            -8_192..=8_191 => 1,
+            -536_870_912..=536_870_911 => 2,
+            _ => 3,
+        };
+        self.write_var_length(((value << 2) as u64) | length_code, length_code);
+    }
+
+    /// Writes an unsigned integer on 1, 2, 4, or 8 bytes, with the 2 least significant bits holding the length.
+    fn write_varuint62(&mut self, value: u64) {
+        let length_code = match value {
+            0..=63 => 0,
+            64..=16_383 => 1,
+            16_384..=1_073_741_823 => 2,
+            _ => 3,
+        };
+        self.write_var_length((value << 2) | length_code, length_code);
+    }
+
+    fn write_var_length(&mut self, encoded: u64, length_code: u64) {
+        let byte_count = 1 << length_code;
+        self.write_bytes(&encoded.to_le_bytes()[..byte_count]);
+    }
+
+    /// Writes a size: a varuint62 with Slice2, or a byte (with an optional 4-byte extension) with Slice1.
+    fn write_size(&mut self, size: usize) {
+        match self.encoding {
+            Encoding::Slice1 if size < 255 => self.write_u8(size as u8),
+            Encoding::Slice1 => {
+                self.write_u8(255);
+                self.write_bytes(&(size as i32).to_le_bytes());
+            }
+            Encoding::Slice2 => self.write_varuint62(size as u64),
+        }
+    }
+
+    fn write_string(&mut self, value: &str) {
+        self.write_size(value.len());
+        self.write_bytes(value.as_bytes());
+    }
+
+    /// Reserves space for a bit sequence with `bit_count` bits, all of which are initially unset.
+    /// Returns the position of the bit sequence, which can be passed to [Self::set_bit].
+    fn reserve_bit_sequence(&mut self, bit_count: usize) -> usize {
+        let position = self.buffer.len();
+        self.buffer.resize(position + bit_count.div_ceil(8), 0);
+        position
+    }
+
+    /// Sets the `index`th bit of the bit sequence that starts at `position`.
+    fn set_bit(&mut self, position: usize, index: usize) {
+        self.buffer[position + index / 8] |= 1 << (index % 8);
+    }
+
+    /// Prefixes everything written since `start` with its length (encoded as a size).
+    fn prefix_with_size(&mut self, start: usize) {
+        let payload = self.buffer.split_off(start);
+        self.write_size(payload.len());
+        self.buffer.extend(payload);
+    }
+
+    /// Prefixes everything written since `start` with its length (encoded as a 4-byte integer).
+    fn prefix_with_fixed_size(&mut self, start: usize) {
+        let length = (self.buffer.len() - start) as i32;
+        self.buffer.splice(start..start, length.to_le_bytes());
+    }
+
+    /// Writes the header of a tagged member.
+    /// With Slice1 this is a byte holding the tag and its format, with Slice2 it's just the tag (as a varint32),
+    /// and `format` is ignored.
+    fn write_tag(&mut self, tag: u32, format: &TagFormat) {
+        match self.encoding {
+            Encoding::Slice1 => {
+                let format_code = slice1_tag_format_code(format);
+                if tag < 30 {
+                    self.write_u8(((tag as u8) << 3) | format_code);
+                } else {
+                    self.write_u8((30 << 3) | format_code);
+                    self.write_size(tag as usize);
+                }
+            }
+            Encoding::Slice2 => self.write_varint62(i64::from(tag)),
+        }
+    }
+
+    /// Writes the marker that terminates a sequence of tagged members.
+    fn write_tag_end_marker(&mut self) {
+        match self.encoding {
+            Encoding::Slice1 => self.write_u8(SLICE1_TAG_END_MARKER),
+            Encoding::Slice2 => self.write_varint62(-1),
+        }
+    }
+
+    /// Registers a Slice1 type ID, returning the index it was previously registered with (if any).
+    fn register_type_id(&mut self, type_id: &str) -> Option<usize> {
+        if let Some(index) = self.type_ids.get(type_id) {
+            return Some(*index);
+        }
+        let index = self.type_ids.len() + 1;
+        self.type_ids.insert(type_id.to_owned(), index);
+        None
+    }
+}
+
+/// Returns the value of the provided field. Optional fields which weren't given a value are treated as unset.
+fn find_field<'v>(field: &Field, values: &'v Fields) -> Result<&'v Value, CodecError> {
+    match values.iter().find(|(name, _)| name == field.identifier()) {
+        Some((_, value)) => Ok(value),
+        None if field.data_type.is_optional => Ok(&NULL),
+        None => Err(CodecError::MissingField(field.identifier().to_owned())),
+    }
+}
+
+fn check_bounds(value: i128, (min, max): (i128, i128), data_type: &dyn Type) -> Result<(), CodecError> {
+    match (min..=max).contains(&value) {
+        true => Ok(()),
+        false => Err(CodecError::OutOfRange {
+            value,
+            type_string: data_type.type_string(),
+        }),
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::{decode, decode_exception, decode_parameters, CodecError, Value};
+use crate::compilation_state::CompilationState;
+use crate::grammar::*;
+use crate::slice_options::DecodeOptions;
+
+/// Decodes the payload specified by `options`, against the type (or operation) it names.
+///
+/// For operations, their arguments are decoded, unless `options.return_value` is set. Either way, the decoded members
+/// are returned as a [Value::Struct]. If no encoding was specified, the payload is decoded with the compilation mode
+/// of the file that defines the named element (or the default encoding, for primitive types).
+pub fn decode_payload(options: &DecodeOptions, state: &CompilationState) -> Result<Value, CodecError> {
+    let ast = &state.ast;
+    let identifier = options.type_name.strip_prefix("::").unwrap_or(&options.type_name);
+    let bytes = parse_hex(&options.payload)?;
+
+    let encoding = options.encoding.unwrap_or_else(|| {
+        let file_name = ast.find_element::<dyn Entity>(identifier).map(|e| &e.span().file);
+        let file = file_name
+            .ok()
+            .and_then(|name| state.files.iter().find(|f| &f.relative_path == name));
+        file.map_or(Encoding::default(), |f| f.compilation_mode())
+    });
+
+    if let Ok(operation) = ast.find_element::<Operation>(identifier) {
+        let parameters = match options.return_value {
+            true => operation.non_streamed_return_members(),
+            false => operation.non_streamed_parameters(),
+        };
+        return decode_parameters(&bytes, &parameters, encoding, ast).map(Value::Struct);
+    }
+    if let Ok(exception_def) = ast.find_element::<Exception>(identifier) {
+        return decode_exception(&bytes, exception_def, encoding, ast);
+    }
+    match ast.find_element::<dyn Type>(identifier) {
+        Ok(data_type) => decode(&bytes, data_type, encoding, ast),
+        Err(_) => Err(CodecError::Unsupported(format!(
+            "'{identifier}' is not a type or operation that can be decoded",
+        ))),
+    }
+}
+
+/// Parses a string of hexadecimal digits into bytes. Whitespace and any `0x` prefixes are ignored.
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>, CodecError> {
+    let digits = hex
+        .split_whitespace()
+        .map(|word| word.strip_prefix("0x").unwrap_or(word))
+        .collect::<String>();
+
+    if let Some(invalid) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(CodecError::InvalidData(format!(
+            "'{invalid}' is not a hexadecimal digit"
+        )));
+    }
+    if digits.len() % 2 != 0 {
+        let message = "hexadecimal payloads must contain an even number of digits";
+        return Err(CodecError::InvalidData(message.to_owned()));
+    }
+
+    let bytes = (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap());
+    Ok(bytes.collect())
+}
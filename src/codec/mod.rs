@@ -0,0 +1,180 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains a reference implementation of the Slice encodings.
+//!
+//! It can encode and decode dynamically typed [Value]s of any type described by an AST. It's used for generating
+//! [test vectors](crate::test_vectors), and for inspecting captured payloads against a schema (see [decode_payload]).
+//! It isn't meant to be fast; generated code should always be preferred for real workloads.
+//!
+//! Class instances are encoded using the compact format, and graphs of class instances aren't supported:
+//! every instance is encoded inline, where it's referenced.
+
+mod decoder;
+mod encoder;
+mod inspect;
+mod value;
+
+pub use inspect::{decode_payload, parse_hex};
+pub use value::{Fields, Value};
+
+use crate::ast::Ast;
+use crate::grammar::*;
+use decoder::Decoder;
+use encoder::Encoder;
+use std::fmt;
+
+// Slice1 slice flags.
+const FLAG_HAS_TYPE_ID_STRING: u8 = 0x01;
+const FLAG_HAS_TYPE_ID_INDEX: u8 = 0x02;
+const FLAG_HAS_TYPE_ID_COMPACT: u8 = 0x03;
+const FLAG_HAS_TAGGED_MEMBERS: u8 = 0x04;
+const FLAG_HAS_INDIRECTION_TABLE: u8 = 0x08;
+const FLAG_HAS_SLICE_SIZE: u8 = 0x10;
+const FLAG_IS_LAST_SLICE: u8 = 0x20;
+
+/// With Slice1, this byte terminates the tagged members of a slice.
+const SLICE1_TAG_END_MARKER: u8 = 0xFF;
+
+/// Returns the code that Slice1 uses to encode the provided tag format.
+fn slice1_tag_format_code(format: &TagFormat) -> u8 {
+    match format {
+        TagFormat::F1 => 0,
+        TagFormat::F2 => 1,
+        TagFormat::F4 => 2,
+        TagFormat::F8 => 3,
+        TagFormat::Size => 4,
+        TagFormat::VSize => 5,
+        TagFormat::FSize => 6,
+        TagFormat::Class => 7,
+        TagFormat::OptimizedVSize => 5, // Encoded as 'VSize', but without a size prefix.
+    }
+}
+
+/// Errors that can occur while encoding or decoding a [Value].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    /// A value didn't match the type it was being encoded as.
+    TypeMismatch { expected: String, found: &'static str },
+
+    /// A value was missing one of the non-optional fields of its type.
+    MissingField(String),
+
+    /// An integral value was outside the range of values supported by its type.
+    OutOfRange { value: i128, type_string: String },
+
+    /// An enumerator value didn't correspond to any of its enum's enumerators.
+    UnknownEnumerator {
+        enum_identifier: String,
+        enumerator: String,
+    },
+
+    /// A class or exception instance referred to a type that doesn't exist in the AST.
+    UnknownType(String),
+
+    /// The value (or type) can't be encoded or decoded by the compiler, for the provided reason.
+    Unsupported(String),
+
+    /// The end of the buffer was reached before a value was fully decoded.
+    EndOfBuffer,
+
+    /// The buffer holds data which isn't a valid encoding of the type being decoded, for the provided reason.
+    InvalidData(String),
+}
+
+impl CodecError {
+    fn mismatch(expected: &dyn Type, found: &Value) -> Self {
+        CodecError::TypeMismatch {
+            expected: expected.type_string(),
+            found: found.kind(),
+        }
+    }
+
+    fn unsupported_custom_type(custom_type: &CustomType) -> Self {
+        let identifier = custom_type.parser_scoped_identifier();
+        CodecError::Unsupported(format!(
+            "the custom type '{identifier}' cannot be encoded by the compiler"
+        ))
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, found } => write!(f, "expected a value of type '{expected}', found {found}"),
+            Self::MissingField(field) => write!(f, "missing a value for the non-optional field '{field}'"),
+            Self::OutOfRange { value, type_string } => write!(f, "{value} is out of range for '{type_string}'"),
+            Self::UnknownEnumerator {
+                enum_identifier,
+                enumerator,
+            } => write!(f, "'{enumerator}' is not an enumerator of '{enum_identifier}'"),
+            Self::UnknownType(type_id) => write!(f, "no type with the ID '{type_id}' exists"),
+            Self::Unsupported(reason) | Self::InvalidData(reason) => f.write_str(reason),
+            Self::EndOfBuffer => f.write_str("reached the end of the buffer before decoding finished"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Encodes the provided value as an instance of `data_type`, using the specified encoding.
+///
+/// `ast` is used to look up the most derived types of any class instances held by `value`.
+pub fn encode(value: &Value, data_type: &dyn Type, encoding: Encoding, ast: &Ast) -> Result<Vec<u8>, CodecError> {
+    let mut encoder = Encoder::new(encoding, ast);
+    encoder.encode_type(value, data_type)?;
+    Ok(encoder.into_bytes())
+}
+
+/// Encodes the provided value as an instance of `exception_def` (or an exception derived from it).
+/// Exceptions can only be encoded with Slice1.
+pub fn encode_exception(
+    value: &Value,
+    exception_def: &Exception,
+    encoding: Encoding,
+    ast: &Ast,
+) -> Result<Vec<u8>, CodecError> {
+    let mut encoder = Encoder::new(encoding, ast);
+    encoder.encode_exception(value, exception_def)?;
+    Ok(encoder.into_bytes())
+}
+
+/// Decodes an instance of `data_type` from the provided bytes, using the specified encoding.
+/// It's an error for any bytes to remain after the value has been decoded.
+///
+/// `ast` is used to look up the types of any class instances held in `bytes`.
+pub fn decode(bytes: &[u8], data_type: &dyn Type, encoding: Encoding, ast: &Ast) -> Result<Value, CodecError> {
+    let mut decoder = Decoder::new(bytes, encoding, ast);
+    let value = decoder.decode_type(data_type)?;
+    decoder.finish()?;
+    Ok(value)
+}
+
+/// Decodes an instance of `exception_def` (or an exception derived from it) from the provided bytes.
+/// It's an error for any bytes to remain after the exception has been decoded.
+pub fn decode_exception(
+    bytes: &[u8],
+    exception_def: &Exception,
+    encoding: Encoding,
+    ast: &Ast,
+) -> Result<Value, CodecError> {
+    let mut decoder = Decoder::new(bytes, encoding, ast);
+    let value = decoder.decode_exception(exception_def)?;
+    decoder.finish()?;
+    Ok(value)
+}
+
+/// Decodes the provided parameters (or return members) of an operation from the provided bytes.
+/// It's an error for any bytes to remain after the parameters have been decoded.
+///
+/// Streamed parameters aren't part of an operation's payload, and so shouldn't be passed to this function.
+pub fn decode_parameters(
+    bytes: &[u8],
+    parameters: &[&Parameter],
+    encoding: Encoding,
+    ast: &Ast,
+) -> Result<Fields, CodecError> {
+    let mut decoder = Decoder::new(bytes, encoding, ast);
+    let fields = decoder.decode_parameters(parameters)?;
+    decoder.finish()?;
+    Ok(fields)
+}
@@ -0,0 +1,230 @@
+// Copyright (c) ZeroC, Inc.
+
+use serde_json::json;
+use std::fmt;
+
+/// The names and values of a struct's (or class's, or exception's) fields, in the order they were declared.
+pub type Fields = Vec<(String, Value)>;
+
+/// A dynamically typed value of a Slice type.
+///
+/// Values don't carry their Slice types with them; a value only has meaning when interpreted against a type from the
+/// AST. For instance, `Integer` is used for every integral type, along with `timestamp` and `duration`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// An optional value that isn't set, or a null class reference.
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float32(f32),
+    Float64(f64),
+    String(String),
+    Uuid([u8; 16]),
+    Sequence(Vec<Value>),
+    /// The entries of a dictionary, in the order they're encoded in.
+    Dictionary(Vec<(Value, Value)>),
+    Struct(Fields),
+    /// An instance of a class, or an exception.
+    /// `type_name` is the fully scoped identifier of the instance's (most derived) type.
+    Instance {
+        type_name: String,
+        fields: Fields,
+    },
+    /// An enumerator of an enum. `identifier` is only `None` for values of unchecked (or flags) enums
+    /// that don't correspond to a single enumerator. `fields` is only set for enums with fields.
+    Enumerator {
+        identifier: Option<String>,
+        value: i128,
+        fields: Option<Fields>,
+    },
+    Success(Box<Value>),
+    Failure(Box<Value>),
+}
+
+impl Value {
+    /// Returns a string describing what kind of value this is, for use in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "bool",
+            Self::Integer(_) => "integer",
+            Self::Float32(_) => "float32",
+            Self::Float64(_) => "float64",
+            Self::String(_) => "string",
+            Self::Uuid(_) => "uuid",
+            Self::Sequence(_) => "sequence",
+            Self::Dictionary(_) => "dictionary",
+            Self::Struct(_) => "struct",
+            Self::Instance { .. } => "instance",
+            Self::Enumerator { .. } => "enumerator",
+            Self::Success(_) => "success",
+            Self::Failure(_) => "failure",
+        }
+    }
+
+    /// Returns the value of the field with the provided name, if this value has fields and one of them matches.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        let fields = match self {
+            Self::Struct(fields) | Self::Instance { fields, .. } => fields,
+            Self::Enumerator {
+                fields: Some(fields), ..
+            } => fields,
+            _ => return None,
+        };
+        fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns a JSON representation of this value.
+    ///
+    /// Integers become JSON numbers (every Slice integral type fits in an `i64` or a `u64`), UUIDs become strings,
+    /// and anything with fields becomes an object. Dictionaries become arrays of `{ "key": ..., "value": ... }`
+    /// objects, since their keys aren't necessarily strings. Enumerators become their identifiers,
+    /// except for enumerators with fields which become `{ "enumerator": ..., "fields": ... }` objects.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Null => serde_json::Value::Null,
+            Self::Bool(value) => json!(value),
+            Self::Integer(value) => integer_to_json(*value),
+            Self::Float32(value) => json!(value),
+            Self::Float64(value) => json!(value),
+            Self::String(value) => json!(value),
+            Self::Uuid(bytes) => json!(uuid_to_string(bytes)),
+            Self::Sequence(elements) => serde_json::Value::Array(elements.iter().map(Value::to_json).collect()),
+            Self::Dictionary(entries) => entries
+                .iter()
+                .map(|(key, value)| json!({ "key": key.to_json(), "value": value.to_json() }))
+                .collect(),
+            Self::Struct(fields) | Self::Instance { fields, .. } => fields_to_json(fields),
+            Self::Enumerator {
+                identifier,
+                value,
+                fields,
+            } => {
+                let enumerator = match identifier {
+                    Some(identifier) => json!(identifier),
+                    None => integer_to_json(*value),
+                };
+                match fields {
+                    Some(fields) => json!({ "enumerator": enumerator, "fields": fields_to_json(fields) }),
+                    None => enumerator,
+                }
+            }
+            Self::Success(value) => json!({ "success": value.to_json() }),
+            Self::Failure(value) => json!({ "failure": value.to_json() }),
+        }
+    }
+}
+
+/// Values are displayed field-by-field, with each field (or element) of a value on its own line,
+/// indented beneath the value that holds it.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_value(f, self, 0)
+    }
+}
+
+fn write_value(f: &mut fmt::Formatter, value: &Value, indent: usize) -> fmt::Result {
+    match value {
+        Value::Null => f.write_str("null"),
+        Value::Bool(value) => write!(f, "{value}"),
+        Value::Integer(value) => write!(f, "{value}"),
+        Value::Float32(value) => write!(f, "{value}"),
+        Value::Float64(value) => write!(f, "{value}"),
+        Value::String(value) => write!(f, "{value:?}"),
+        Value::Uuid(bytes) => f.write_str(&uuid_to_string(bytes)),
+        Value::Sequence(elements) => write_block(f, "[", "]", elements, indent, |f, element| {
+            write_value(f, element, indent + 1)
+        }),
+        Value::Dictionary(entries) => write_block(f, "{", "}", entries, indent, |f, (key, value)| {
+            write_value(f, key, indent + 1)?;
+            f.write_str(": ")?;
+            write_value(f, value, indent + 1)
+        }),
+        Value::Struct(fields) => write_fields(f, fields, indent),
+        Value::Instance { type_name, fields } => {
+            write!(f, "{type_name} ")?;
+            write_fields(f, fields, indent)
+        }
+        Value::Enumerator {
+            identifier,
+            value,
+            fields,
+        } => {
+            match identifier {
+                Some(identifier) => f.write_str(identifier)?,
+                None => write!(f, "{value}")?,
+            }
+            match fields {
+                Some(fields) if !fields.is_empty() => {
+                    f.write_str(" ")?;
+                    write_fields(f, fields, indent)
+                }
+                _ => Ok(()),
+            }
+        }
+        Value::Success(value) => {
+            f.write_str("success: ")?;
+            write_value(f, value, indent)
+        }
+        Value::Failure(value) => {
+            f.write_str("failure: ")?;
+            write_value(f, value, indent)
+        }
+    }
+}
+
+fn write_fields(f: &mut fmt::Formatter, fields: &Fields, indent: usize) -> fmt::Result {
+    write_block(f, "{", "}", fields, indent, |f, (name, value)| {
+        write!(f, "{name}: ")?;
+        write_value(f, value, indent + 1)
+    })
+}
+
+/// Writes the provided items between a pair of delimiters, with each item on its own (indented) line.
+fn write_block<T>(
+    f: &mut fmt::Formatter,
+    open: &str,
+    close: &str,
+    items: &[T],
+    indent: usize,
+    mut write_item: impl FnMut(&mut fmt::Formatter, &T) -> fmt::Result,
+) -> fmt::Result {
+    if items.is_empty() {
+        return write!(f, "{open}{close}");
+    }
+
+    writeln!(f, "{open}")?;
+    for item in items {
+        write!(f, "{:width$}", "", width = (indent + 1) * 4)?;
+        write_item(f, item)?;
+        writeln!(f)?;
+    }
+    write!(f, "{:width$}{close}", "", width = indent * 4)
+}
+
+fn fields_to_json(fields: &Fields) -> serde_json::Value {
+    let map = fields.iter().map(|(name, value)| (name.clone(), value.to_json()));
+    serde_json::Value::Object(map.collect())
+}
+
+fn integer_to_json(value: i128) -> serde_json::Value {
+    match i64::try_from(value) {
+        Ok(signed) => json!(signed),
+        Err(_) => json!(value as u64),
+    }
+}
+
+/// Formats the provided bytes as a UUID: `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+fn uuid_to_string(bytes: &[u8; 16]) -> String {
+    let mut string = String::new();
+    for (index, byte) in bytes.iter().enumerate() {
+        if matches!(index, 4 | 6 | 8 | 10) {
+            string.push('-');
+        }
+        string += &format!("{byte:02x}");
+    }
+    string
+}
@@ -2,15 +2,33 @@
 
 use crate::ast::Ast;
 use crate::diagnostic_emitter::{emit_totals, DiagnosticEmitter};
-use crate::diagnostics::{get_totals, Diagnostic, Diagnostics};
+use crate::diagnostics::{get_totals, Baseline, Diagnostic, Diagnostics};
+use crate::packages::Package;
 use crate::slice_file::SliceFile;
 use crate::slice_options::{DiagnosticFormat, SliceOptions};
+use crate::summary::Summary;
+use crate::suppression_report::SuppressionReport;
+use crate::timings::Timings;
+use crate::unresolved_references::{UnresolvedReference, UnresolvedReferencesReport};
+use std::io::Write;
 
 #[derive(Debug, Default)]
 pub struct CompilationState {
     pub ast: Ast,
     pub diagnostics: Diagnostics,
     pub files: Vec<SliceFile>,
+    pub timings: Timings,
+
+    /// The type references which couldn't be resolved while patching the AST.
+    /// See [UnresolvedReference] for more information.
+    pub unresolved_references: Vec<UnresolvedReference>,
+
+    /// The paths that reference files were searched for in, if the files were resolved from [SliceOptions].
+    /// See [reference_search_path](crate::utils::file_util::reference_search_path) for more information.
+    pub reference_search_path: Option<Vec<String>>,
+
+    /// The packages whose files were compiled as references. See [Package] for more information.
+    pub packages: Vec<Package>,
 }
 
 impl CompilationState {
@@ -19,6 +37,10 @@ impl CompilationState {
             ast: Ast::create(),
             diagnostics: Diagnostics::new(),
             files: Vec::new(),
+            timings: Timings::default(),
+            unresolved_references: Vec::new(),
+            reference_search_path: None,
+            packages: Vec::new(),
         }
     }
 
@@ -44,23 +66,78 @@ impl CompilationState {
     }
 
     /// This function is the exit point of the compiler.
-    /// It emits diagnostics to the console, along with the total number of warning/errors emitted.
-    /// After this it returns whether any errors were emitted.
-    pub fn emit_diagnostics(self, options: &SliceOptions) -> bool {
+    /// It emits diagnostics to the console, along with the total number of warning/errors emitted, and any reports the
+    /// user requested. After this it returns a summary of the compilation's outcome, including the code the compiler
+    /// should exit with.
+    ///
+    /// Failing to emit something (like being unable to write a report) doesn't stop the compiler from emitting the
+    /// rest; these failures are printed on a best-effort basis, and returned as the summary's internal errors.
+    pub fn emit_diagnostics(self, options: &SliceOptions) -> CompilationSummary {
         let diagnostics = self.diagnostics.into_updated(&self.ast, &self.files, options);
         let (total_warnings, total_errors) = get_totals(&diagnostics);
+        let mut internal_errors = Vec::new();
+        let mut check = |result: std::io::Result<()>, action: &str| {
+            if let Err(error) = result {
+                internal_errors.push(format!("failed to {action}: {error}"));
+            }
+        };
+
+        // The suppression report and baseline must be created before the diagnostics are consumed by the emitter.
+        let suppression_report = options
+            .suppression_report
+            .map(|_| SuppressionReport::from_diagnostics(&diagnostics));
+        let new_baseline = options
+            .update_baseline
+            .then(|| Baseline::from_diagnostics(&diagnostics));
 
         // Print any diagnostics to the console, along with the total number of warnings and errors emitted.
         let mut stderr = console::Term::stderr();
         let mut emitter = DiagnosticEmitter::new(&mut stderr, options, &self.files);
-        DiagnosticEmitter::emit_diagnostics(&mut emitter, diagnostics).expect("failed to emit diagnostics");
+        check(emitter.emit_diagnostics(diagnostics), "emit diagnostics");
 
         // Only emit the summary message if we're writing human-readable output.
         if options.diagnostic_format == DiagnosticFormat::Human {
-            emit_totals(total_warnings, total_errors).expect("failed to emit totals");
+            check(emit_totals(total_warnings, total_errors), "emit totals");
+        }
+
+        // If the user requested it, record the warnings that were reported in the baseline file.
+        // Options that weren't parsed from the command line can set `update_baseline` without a baseline file.
+        if let Some(new_baseline) = new_baseline {
+            let result = match options.baseline.as_deref() {
+                Some(path) => new_baseline.write(path),
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "'--update-baseline' requires a baseline file to be specified with '--baseline'",
+                )),
+            };
+            check(result, "write baseline");
+        }
+
+        // If the user requested one, emit a report of the diagnostics that were suppressed.
+        if let Some(report) = suppression_report {
+            check(report.emit(options), "emit suppression report");
         }
 
-        total_errors != 0
+        // If the user requested one, emit a report of the type references which couldn't be resolved.
+        if options.list_unresolved.is_some() {
+            let report = UnresolvedReferencesReport::from_references(&self.unresolved_references);
+            check(report.emit(options), "emit unresolved references report");
+        }
+
+        // If the user requested them, emit how long each phase of compilation took.
+        check(self.timings.emit(options), "emit timings");
+
+        // If the user requested one, emit a summary of the source files. Summaries require a fully patched AST.
+        if options.summary.is_some() && total_errors == 0 {
+            let summary = Summary::from_files(&self.files);
+            check(summary.emit(options), "emit summary");
+        }
+
+        for internal_error in &internal_errors {
+            let _ = writeln!(std::io::stderr(), "internal error: {internal_error}");
+        }
+
+        CompilationSummary::new(total_warnings, total_errors, internal_errors, options)
     }
 
     /// Consumes this `CompilationState` and returns the diagnostics it contains.
@@ -69,3 +146,69 @@ impl CompilationState {
         self.diagnostics.into_updated(&self.ast, &self.files, options)
     }
 }
+
+/// The outcome of a compilation, as returned by [CompilationState::emit_diagnostics].
+#[derive(Debug)]
+pub struct CompilationSummary {
+    /// The number of warnings that were emitted.
+    pub total_warnings: usize,
+
+    /// The number of errors that were emitted.
+    pub total_errors: usize,
+
+    /// Any failures the compiler encountered which weren't caused by the Slice files, like being unable to write a
+    /// requested report.
+    pub internal_errors: Vec<String>,
+
+    /// The code the compiler should exit with. See [ExitCode].
+    pub exit_code: ExitCode,
+}
+
+impl CompilationSummary {
+    fn new(total_warnings: usize, total_errors: usize, internal_errors: Vec<String>, options: &SliceOptions) -> Self {
+        // Internal errors take precedence, since they mean the compiler's output may be incomplete.
+        let exit_code = if !internal_errors.is_empty() {
+            ExitCode::InternalError
+        } else if total_errors > 0 {
+            ExitCode::Errors
+        } else if total_warnings > 0 && options.warnings_exit_code {
+            ExitCode::Warnings
+        } else {
+            ExitCode::Success
+        };
+
+        CompilationSummary {
+            total_warnings,
+            total_errors,
+            internal_errors,
+            exit_code,
+        }
+    }
+
+    /// Returns true if compilation succeeded: no errors or internal errors occurred. Warnings are still allowed.
+    pub fn succeeded(&self) -> bool {
+        self.total_errors == 0 && self.internal_errors.is_empty()
+    }
+}
+
+/// The codes that a Slice compiler exits with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Compilation succeeded. Warnings may have been emitted, unless `--warnings-exit-code` was passed.
+    Success = 0,
+
+    /// Errors were found in the Slice files.
+    Errors = 1,
+
+    /// Compilation succeeded, but warnings were emitted. Only used if `--warnings-exit-code` was passed.
+    Warnings = 2,
+
+    /// The compiler failed for a reason unrelated to the Slice files, like being unable to write a requested report.
+    InternalError = 3,
+}
+
+impl From<ExitCode> for i32 {
+    fn from(exit_code: ExitCode) -> Self {
+        exit_code as i32
+    }
+}
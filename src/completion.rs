@@ -0,0 +1,393 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module determines which identifiers are valid at a position in a Slice file, for editors which provide
+//! autocompletion (like language servers answering `textDocument/completion` requests).
+//!
+//! The context of a position (a field's type, an enumerator's value, etc.) is determined from the tokens before it,
+//! so it works even while the file is being edited, and doesn't compile. The candidates for that context are taken
+//! from the compiled AST, and are resolved with the same scoping rules (and using directives) as type references.
+//! So if the AST is stale, the candidates will be too.
+
+use crate::ast::node::Node;
+use crate::ast::Ast;
+use crate::grammar::attributes::*;
+use crate::grammar::*;
+use crate::parsers::lossless::{self, SyntaxKind, SyntaxToken};
+use crate::slice_file::{Location, SliceFile};
+
+/// The syntactic context of a position, which determines what kinds of identifiers are valid there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionContext {
+    /// The type of a field, parameter, return type, or type alias, or an element of a generic type.
+    Type,
+
+    /// The underlying type of an enum, after `enum E :`.
+    UnderlyingType,
+
+    /// The base class of a class, after `class C :`.
+    BaseClass,
+
+    /// The base exception of an exception, after `exception E :`.
+    BaseException,
+
+    /// A base interface of an interface, after `interface I :`.
+    BaseInterface,
+
+    /// An exception in an operation's exception specification, after `throws`.
+    ThrownException,
+
+    /// A constant expression, after the `=` of an enumerator or constant.
+    ConstantExpression,
+
+    /// The directive of an attribute, at the start of `[...]`, or after a `,` within it.
+    AttributeDirective,
+
+    /// Any other position, where no identifiers are suggested.
+    None,
+}
+
+/// The kinds of completions that can be suggested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionKind {
+    Keyword,
+    Struct,
+    Class,
+    Exception,
+    Interface,
+    Enum,
+    CustomType,
+    TypeAlias,
+    Constant,
+    Enumerator,
+    AttributeDirective,
+}
+
+/// An identifier that's valid at a position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The text to insert. For definitions, this is the shortest identifier that resolves to it from the position.
+    pub label: String,
+
+    pub kind: CompletionKind,
+}
+
+/// Returns the directives of the attributes which are built into the compiler.
+/// Backends which support their own attributes should suggest those directives in addition to these.
+pub fn builtin_attribute_directives() -> [&'static str; 14] {
+    [
+        Allow::directive(),
+        Authors::directive(),
+        Boxed::directive(),
+        Compress::directive(),
+        Context::directive(),
+        Deprecated::directive(),
+        Flags::directive(),
+        Internal::directive(),
+        License::directive(),
+        Oneway::directive(),
+        Package::directive(),
+        Size::directive(),
+        SlicedFormat::directive(),
+        Version::directive(),
+    ]
+}
+
+/// Returns the context of the provided position in the provided file. The identifier being typed at the position (if
+/// any) is ignored, since completions replace it.
+pub fn completion_context(slice_file: &SliceFile, location: Location) -> CompletionContext {
+    analyze(slice_file, location).context
+}
+
+/// Returns the identifiers which are valid at the provided position in the provided file, sorted by their labels.
+/// `ast` should be the compiled AST that contains the file.
+pub fn completions(ast: &Ast, slice_file: &SliceFile, location: Location) -> Vec<Completion> {
+    let analysis = analyze(slice_file, location);
+    let resolver = Resolver {
+        ast,
+        slice_file,
+        scope: &analysis.scope,
+    };
+
+    let mut completions = match analysis.context {
+        CompletionContext::Type => {
+            let mut completions = keywords(["Sequence", "Dictionary", "Result"]);
+            completions.extend(primitive_keywords(ast, |_| true));
+            completions.extend(resolver.definitions(|node| match node {
+                Node::Struct(ptr) if !ptr.borrow().is_synthesized => Some((ptr.borrow(), CompletionKind::Struct)),
+                Node::Class(ptr) => Some((ptr.borrow(), CompletionKind::Class)),
+                Node::Enum(ptr) => Some((ptr.borrow(), CompletionKind::Enum)),
+                Node::CustomType(ptr) => Some((ptr.borrow(), CompletionKind::CustomType)),
+                Node::TypeAlias(ptr) => Some((ptr.borrow(), CompletionKind::TypeAlias)),
+                _ => None,
+            }));
+            completions
+        }
+        CompletionContext::UnderlyingType => primitive_keywords(ast, Primitive::is_integral),
+        CompletionContext::BaseClass => resolver.definitions(|node| match node {
+            Node::Class(ptr) => Some((ptr.borrow(), CompletionKind::Class)),
+            _ => None,
+        }),
+        CompletionContext::BaseException | CompletionContext::ThrownException => {
+            resolver.definitions(|node| match node {
+                Node::Exception(ptr) => Some((ptr.borrow(), CompletionKind::Exception)),
+                _ => None,
+            })
+        }
+        CompletionContext::BaseInterface => resolver.definitions(|node| match node {
+            Node::Interface(ptr) => Some((ptr.borrow(), CompletionKind::Interface)),
+            _ => None,
+        }),
+        CompletionContext::ConstantExpression => {
+            // Enumerators can reference the enumerators declared before them in the same enum.
+            let enum_def = ast.find_element::<Enum>(&analysis.scope).ok();
+            let enumerators = enum_def.map(Enum::enumerators).unwrap_or_default().into_iter();
+            let mut completions = enumerators
+                .filter(|enumerator| enumerator.span.end <= location)
+                .map(|enumerator| Completion {
+                    label: enumerator.identifier().to_owned(),
+                    kind: CompletionKind::Enumerator,
+                })
+                .collect::<Vec<_>>();
+            completions.extend(resolver.definitions(|node| match node {
+                Node::Constant(ptr) => Some((ptr.borrow(), CompletionKind::Constant)),
+                _ => None,
+            }));
+            completions
+        }
+        CompletionContext::AttributeDirective => builtin_attribute_directives()
+            .into_iter()
+            .map(|directive| Completion {
+                label: directive.to_owned(),
+                kind: CompletionKind::AttributeDirective,
+            })
+            .collect(),
+        CompletionContext::None => Vec::new(),
+    };
+
+    completions.sort_by(|a, b| a.label.cmp(&b.label));
+    completions.dedup();
+    completions
+}
+
+fn keywords<'a>(keywords: impl IntoIterator<Item = &'a str>) -> Vec<Completion> {
+    let completions = keywords.into_iter().map(|keyword| Completion {
+        label: keyword.to_owned(),
+        kind: CompletionKind::Keyword,
+    });
+    completions.collect()
+}
+
+fn primitive_keywords(ast: &Ast, filter: impl Fn(&Primitive) -> bool) -> Vec<Completion> {
+    keywords(ast.as_slice().iter().filter_map(|node| match node {
+        Node::Primitive(ptr) if filter(ptr.borrow()) => Some(ptr.borrow().kind()),
+        _ => None,
+    }))
+}
+
+/// Computes the labels of completions, by finding the shortest identifier that resolves to each definition.
+struct Resolver<'a> {
+    ast: &'a Ast,
+    slice_file: &'a SliceFile,
+    scope: &'a str,
+}
+
+impl<'a> Resolver<'a> {
+    /// Returns a completion for each definition selected by the provided function.
+    fn definitions(&self, select: impl Fn(&'a Node) -> Option<(&'a dyn Entity, CompletionKind)>) -> Vec<Completion> {
+        let definitions = self.ast.as_slice().iter().filter_map(select);
+        let completions = definitions.map(|(entity, kind)| Completion {
+            label: self.label_for(entity),
+            kind,
+        });
+        completions.collect()
+    }
+
+    /// Returns the shortest identifier which resolves to the provided entity from the current scope. If none of its
+    /// relatively scoped identifiers do (because they're shadowed), its globally scoped identifier is returned.
+    fn label_for(&self, entity: &dyn Entity) -> String {
+        let scoped_identifier = entity.parser_scoped_identifier();
+        let segments = scoped_identifier.split("::").collect::<Vec<_>>();
+
+        // Candidates are the suffixes of its scoped identifier, and those same identifiers with a using alias.
+        let mut candidates = (1..=segments.len())
+            .map(|n| segments[segments.len() - n..].join("::"))
+            .collect::<Vec<_>>();
+        for using_directive in &self.slice_file.using_directives {
+            let rest = scoped_identifier.strip_prefix(using_directive.target_identifier());
+            if let Some(rest) = rest.filter(|rest| rest.starts_with("::")) {
+                candidates.push(format!("{}{rest}", using_directive.alias.value));
+            }
+        }
+        candidates.sort_by_key(|candidate| (candidate.split("::").count(), candidate.len()));
+
+        let resolves_to_entity = |candidate: &&String| {
+            let expanded = self
+                .slice_file
+                .using_directives
+                .iter()
+                .find_map(|d| d.expand(candidate));
+            let identifier = expanded.as_deref().unwrap_or(candidate);
+            let resolved = self.ast.find_element_with_scope::<dyn Entity>(identifier, self.scope);
+            resolved.is_ok_and(|resolved| resolved.parser_scoped_identifier() == scoped_identifier)
+        };
+        match candidates.iter().find(resolves_to_entity) {
+            Some(candidate) => candidate.clone(),
+            None => format!("::{scoped_identifier}"),
+        }
+    }
+}
+
+struct Analysis {
+    context: CompletionContext,
+    /// The parser scope of the position, like `Foo::S` for a position within the struct `S` in module `Foo`.
+    scope: String,
+}
+
+/// A delimiter which encloses the position.
+enum Group {
+    /// A brace, along with the keyword and identifier of the definition it opens (if it opens one).
+    Brace(Option<(String, String)>),
+    Parenthesis,
+    Bracket,
+    Angle,
+}
+
+fn analyze(slice_file: &SliceFile, location: Location) -> Analysis {
+    let mut tokens = lossless::tokenize(&slice_file.raw_text, &slice_file.relative_path)
+        .take_while(|token| token.span.start < location)
+        .filter(|token| !token.kind.is_trivia() && token.kind != SyntaxKind::DocComment)
+        .filter(|token| token.kind != SyntaxKind::PreprocessorDirective)
+        .collect::<Vec<_>>();
+
+    // Ignore the (possibly scoped) identifier being typed at the position, since completions replace it.
+    if tokens
+        .last()
+        .is_some_and(|token| is_word(token) && token.span.end >= location)
+    {
+        tokens.pop();
+    }
+    while tokens.last().is_some_and(|token| token.text == "::") {
+        tokens.pop();
+        if tokens.last().is_some_and(|token| token.kind == SyntaxKind::Identifier) {
+            tokens.pop();
+        }
+    }
+
+    let mut groups = Vec::new();
+    let mut header: Option<(String, String)> = None;
+    let mut header_bases = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|j| &tokens[j]);
+        match token.text.as_str() {
+            "{" => {
+                groups.push(Group::Brace(header.take()));
+                header_bases = None;
+            }
+            "}" => {
+                while let Some(group) = groups.pop() {
+                    if matches!(group, Group::Brace(_)) {
+                        break;
+                    }
+                }
+            }
+            "(" => groups.push(Group::Parenthesis),
+            "[" | "[[" => groups.push(Group::Bracket),
+            ")" | "]" | "]]" | ">" => {
+                let closes = |group: &Group| match token.text.as_str() {
+                    ")" => matches!(group, Group::Parenthesis),
+                    ">" => matches!(group, Group::Angle),
+                    _ => matches!(group, Group::Bracket),
+                };
+                if groups.last().is_some_and(closes) {
+                    groups.pop();
+                }
+            }
+            // Angle brackets are only delimiters after generic type keywords, otherwise they're shift operators.
+            "<" if previous.is_some_and(|p| matches!(p.text.as_str(), "Sequence" | "Dictionary" | "Result")) => {
+                groups.push(Group::Angle);
+            }
+            ":" if header.is_some() && previous.is_some_and(|p| p.kind == SyntaxKind::Identifier) => {
+                header_bases = header.as_ref().map(|(keyword, _)| keyword.clone());
+            }
+            _ if token.kind == SyntaxKind::Identifier => {
+                let is_header = previous.is_some_and(|p| is_definition_keyword(&p.text));
+                if is_header && groups.iter().all(|group| matches!(group, Group::Brace(_))) {
+                    header = Some((previous.unwrap().text.clone(), token.text.clone()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Compute the scope from the module declaration and the names of the enclosing definitions.
+    let mut scope = slice_file
+        .module
+        .as_ref()
+        .map(|module_ptr| module_ptr.borrow().nested_module_identifier().to_owned())
+        .unwrap_or_default();
+    let mut enclosing_keyword = None;
+    for group in &groups {
+        if let Group::Brace(Some((keyword, identifier))) = group {
+            scope = match scope.is_empty() {
+                true => identifier.clone(),
+                false => format!("{scope}::{identifier}"),
+            };
+            enclosing_keyword = Some(keyword.as_str());
+        }
+    }
+
+    let text_at = |n: usize| tokens.len().checked_sub(n).map(|i| tokens[i].text.as_str());
+    let is_in_attribute = groups.iter().any(|group| matches!(group, Group::Bracket));
+    let context = match (text_at(1), text_at(2), text_at(3)) {
+        (Some("[" | "[[" | ","), ..) if matches!(groups.last(), Some(Group::Bracket)) => {
+            CompletionContext::AttributeDirective
+        }
+        _ if is_in_attribute => CompletionContext::None,
+
+        (Some(":" | ","), ..) if header_bases.is_some() => match header_bases.as_deref() {
+            Some("class") => CompletionContext::BaseClass,
+            Some("exception") => CompletionContext::BaseException,
+            Some("interface") => CompletionContext::BaseInterface,
+            Some("enum") => CompletionContext::UnderlyingType,
+            _ => CompletionContext::None,
+        },
+        (Some("throws"), ..) => CompletionContext::ThrownException,
+        (Some("(" | ","), ..) if is_in_exception_specification(&tokens, &groups) => CompletionContext::ThrownException,
+
+        (Some(":" | "->" | "stream" | "<"), ..) => CompletionContext::Type,
+        (Some(","), ..) if matches!(groups.last(), Some(Group::Angle)) => CompletionContext::Type,
+        (Some("="), Some(_), Some("typealias")) => CompletionContext::Type,
+        (Some("="), Some(_), Some("const")) => CompletionContext::ConstantExpression,
+        (Some("="), ..) if enclosing_keyword == Some("enum") => CompletionContext::ConstantExpression,
+
+        _ => CompletionContext::None,
+    };
+    Analysis { context, scope }
+}
+
+fn is_word(token: &SyntaxToken) -> bool {
+    matches!(token.kind, SyntaxKind::Identifier | SyntaxKind::Keyword)
+}
+
+fn is_definition_keyword(text: &str) -> bool {
+    matches!(text, "struct" | "class" | "exception" | "interface" | "enum")
+}
+
+/// Returns true if the innermost group is a parenthesis which directly follows a `throws` keyword.
+fn is_in_exception_specification(tokens: &[SyntaxToken], groups: &[Group]) -> bool {
+    if !matches!(groups.last(), Some(Group::Parenthesis)) {
+        return false;
+    }
+
+    // Find the opening parenthesis of the innermost group, by walking backwards over balanced parentheses.
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().rev() {
+        match token.text.as_str() {
+            ")" => depth += 1,
+            "(" if depth == 0 => return i > 0 && tokens[i - 1].text == "throws",
+            "(" => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
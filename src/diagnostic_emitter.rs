@@ -1,11 +1,12 @@
 // Copyright (c) ZeroC, Inc.
 
-use crate::diagnostics::{Diagnostic, DiagnosticLevel};
+use crate::diagnostics::{Diagnostic, DiagnosticLevel, Note};
 use crate::slice_file::{SliceFile, Span};
-use crate::slice_options::{DiagnosticFormat, SliceOptions};
+use crate::slice_options::{ColumnUnit, DiagnosticFormat, SliceOptions};
 use serde::ser::SerializeStruct;
 use serde::Serializer;
 use std::io::{Result, Write};
+use std::num::NonZeroUsize;
 use std::path::Path;
 
 #[derive(Debug)]
@@ -16,6 +17,10 @@ pub struct DiagnosticEmitter<'a, T: Write> {
     diagnostic_format: DiagnosticFormat,
     /// If true, diagnostic output will not be styled with colors (only used in `human` format).
     disable_color: bool,
+    /// Which units the columns of emitted locations are measured in.
+    column_unit: ColumnUnit,
+    /// How many columns a tab character occupies in emitted locations.
+    tab_width: usize,
     /// Provides the emitter access to the slice files that were compiled so it can extract snippets from them.
     files: &'a [SliceFile],
 }
@@ -26,6 +31,8 @@ impl<'a, T: Write> DiagnosticEmitter<'a, T> {
             output,
             diagnostic_format: slice_options.diagnostic_format,
             disable_color: slice_options.disable_color,
+            column_unit: slice_options.column_unit,
+            tab_width: slice_options.tab_width.map_or(1, NonZeroUsize::get),
             files,
         }
     }
@@ -89,12 +96,23 @@ impl<'a, T: Write> DiagnosticEmitter<'a, T> {
                 DiagnosticLevel::Allowed => continue,
             };
 
+            // Convert any spans into the units the user requested.
+            let span = diagnostic.span().map(|span| self.convert_span(span));
+            let notes = diagnostic
+                .notes()
+                .iter()
+                .map(|note| Note {
+                    message: note.message.clone(),
+                    span: note.span.as_ref().map(|span| self.convert_span(span)),
+                })
+                .collect::<Vec<_>>();
+
             let mut serializer = serde_json::Serializer::new(&mut *self.output);
             let mut state = serializer.serialize_struct("Diagnostic", 5)?;
             state.serialize_field("message", &diagnostic.message())?;
             state.serialize_field("severity", severity)?;
-            state.serialize_field("span", &diagnostic.span())?;
-            state.serialize_field("notes", diagnostic.notes())?;
+            state.serialize_field("span", &span)?;
+            state.serialize_field("notes", &notes)?;
             state.serialize_field("error_code", diagnostic.code())?;
             state.end()?;
             writeln!(self.output)?; // Separate each diagnostic by a newline character.
@@ -104,21 +122,32 @@ impl<'a, T: Write> DiagnosticEmitter<'a, T> {
 
     fn emit_snippet(&mut self, span: &Span) -> Result<()> {
         // Display the file name and line row and column where the error began.
+        let start = self.convert_span(span).start;
         writeln!(
             self.output,
             " {} {}:{}:{}",
             console::style("-->").blue().bold(),
             Path::new(&span.file).display(),
-            span.start.row,
-            span.start.col,
+            start.row,
+            start.col,
         )?;
 
         // Display the line of code where the error occurred.
-        let file = self.files.iter().find(|f| f.relative_path == span.file).unwrap();
-        writeln!(self.output, "{}", file.get_snippet(span.start, span.end))?;
+        // Built-in definitions (like the well-known types) aren't in any file, so there's no code to display for them.
+        if let Some(file) = self.files.iter().find(|f| f.relative_path == span.file) {
+            writeln!(self.output, "{}", file.get_snippet(span.start, span.end))?;
+        }
 
         Ok(())
     }
+
+    /// Converts the provided span into the units the user requested.
+    fn convert_span(&self, span: &Span) -> Span {
+        match self.files.iter().find(|f| f.relative_path == span.file) {
+            Some(file) => file.convert_span(span, self.column_unit, self.tab_width),
+            None => span.clone(),
+        }
+    }
 }
 
 pub fn emit_totals(total_warnings: usize, total_errors: usize) -> Result<()> {
@@ -0,0 +1,117 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides baselines, which record the warnings that a set of Slice files already has, so that lints can
+//! be adopted incrementally: the warnings in the baseline are allowed, and any new warnings are reported as errors.
+//!
+//! Warnings are matched against the baseline by their file, lint, and message, but not their location, so that
+//! unrelated edits which move a warning to a different line don't cause it to be reported again.
+
+use super::{AllowedBy, Diagnostic, DiagnosticLevel, Note};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fs, io};
+
+/// A record of the warnings that were reported when the baseline was created.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The recorded warnings, sorted by file, lint, and message.
+    pub warnings: Vec<BaselineEntry>,
+}
+
+/// A warning that was reported (possibly multiple times) when the baseline was created.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The relative path of the file the warning was reported in, if it was reported in a file.
+    pub path: Option<String>,
+
+    /// The name of the lint that reported the warning. Ex: `Deprecated`.
+    pub lint: String,
+
+    /// The message of the warning.
+    pub message: String,
+
+    /// How many times this warning was reported.
+    pub count: usize,
+}
+
+impl Baseline {
+    /// Creates a baseline of the warnings among the provided diagnostics.
+    /// Lints which were allowed, and errors, aren't recorded.
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> Self {
+        let mut warnings: BTreeMap<_, usize> = BTreeMap::new();
+        for diagnostic in diagnostics.iter().filter(|d| d.level() == DiagnosticLevel::Warning) {
+            *warnings.entry(key_of(diagnostic)).or_default() += 1;
+        }
+
+        let warnings = warnings
+            .into_iter()
+            .map(|((path, lint, message), count)| BaselineEntry {
+                path,
+                lint,
+                message,
+                count,
+            });
+        Baseline {
+            warnings: warnings.collect(),
+        }
+    }
+
+    /// Returns this baseline as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize baseline")
+    }
+
+    /// Parses a baseline from a JSON document, like one returned by [Baseline::to_json].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Reads the baseline stored at the provided path.
+    pub fn read(path: &str) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(Self::from_json(&json)?)
+    }
+
+    /// Writes this baseline to the provided path, replacing any baseline which was already stored there.
+    pub fn write(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+
+    /// Allows any warnings among the provided diagnostics which are recorded in this baseline, and promotes the rest
+    /// to errors. If a warning was reported more times than it was recorded, only the extra occurrences are promoted.
+    pub(super) fn apply_to(&self, diagnostics: &mut [Diagnostic], path: &str) {
+        let mut remaining = BTreeMap::new();
+        for entry in &self.warnings {
+            let key = (entry.path.clone(), entry.lint.clone(), entry.message.clone());
+            *remaining.entry(key).or_insert(0) += entry.count;
+        }
+
+        for diagnostic in diagnostics.iter_mut() {
+            // Only lints can have the `Warning` level, so this also skips errors.
+            if diagnostic.level != DiagnosticLevel::Warning {
+                continue;
+            }
+
+            match remaining.get_mut(&key_of(diagnostic)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    diagnostic.level = DiagnosticLevel::Allowed;
+                    diagnostic.allowed_by = Some(AllowedBy::Baseline);
+                }
+                _ => {
+                    diagnostic.level = DiagnosticLevel::Error;
+                    diagnostic.notes.push(Note {
+                        message: format!("this warning isn't recorded in the baseline '{path}'"),
+                        span: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the key that the provided diagnostic is matched against baseline entries with.
+fn key_of(diagnostic: &Diagnostic) -> (Option<String>, String, String) {
+    let path = diagnostic.span().map(|span| span.file.clone());
+    (path, diagnostic.code().to_owned(), diagnostic.message())
+}
@@ -1,8 +1,8 @@
 // Copyright (c) ZeroC, Inc.
 
-use super::{Error, Lint, Note};
+use super::{Baseline, Error, Lint, Note};
 use crate::ast::Ast;
-use crate::grammar::{attributes, Attributable, Entity};
+use crate::grammar::{attributes, AttributeFunctions, Entity};
 use crate::slice_file::{SliceFile, Span};
 use crate::slice_options::SliceOptions;
 
@@ -11,10 +11,11 @@ use crate::slice_options::SliceOptions;
 #[derive(Debug)]
 pub struct Diagnostic {
     kind: DiagnosticKind,
-    level: DiagnosticLevel,
+    pub(super) level: DiagnosticLevel,
     span: Option<Span>,
     scope: Option<String>,
-    notes: Vec<Note>,
+    pub(super) notes: Vec<Note>,
+    pub(super) allowed_by: Option<AllowedBy>,
 }
 
 impl Diagnostic {
@@ -31,6 +32,7 @@ impl Diagnostic {
             span: None,
             scope: None,
             notes: Vec::new(),
+            allowed_by: None,
         }
     }
 
@@ -71,6 +73,12 @@ impl Diagnostic {
         &self.notes
     }
 
+    /// Returns what allowed this diagnostic, if it's a lint that was allowed.
+    /// Like the diagnostic's level, this isn't set until the diagnostics are [updated](Diagnostics::into_updated).
+    pub fn allowed_by(&self) -> Option<&AllowedBy> {
+        self.allowed_by.as_ref()
+    }
+
     pub fn set_span(mut self, span: &Span) -> Self {
         self.span = Some(span.to_owned());
         self
@@ -131,6 +139,34 @@ pub enum DiagnosticLevel {
     Allowed,
 }
 
+/// Describes what caused a lint to be [allowed](DiagnosticLevel::Allowed).
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AllowedBy {
+    /// An `--allow` flag passed on the command line.
+    CommandLine,
+
+    /// An `allow` attribute on the file the lint was reported in: `[[allow(...)]]`.
+    FileAttribute,
+
+    /// An `allow` attribute on the lint's scope, any entity containing it, or the module it was declared in.
+    /// This holds the fully scoped identifier of the lint's scope.
+    Attribute(String),
+
+    /// The baseline file passed with `--baseline`, which recorded this lint as an existing warning.
+    Baseline,
+}
+
+impl std::fmt::Display for AllowedBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AllowedBy::CommandLine => f.write_str("command line"),
+            AllowedBy::FileAttribute => f.write_str("file attribute"),
+            AllowedBy::Attribute(scope) => write!(f, "attribute on '{scope}'"),
+            AllowedBy::Baseline => f.write_str("baseline"),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Diagnostics(Vec<Diagnostic>);
 
@@ -159,43 +195,62 @@ impl Diagnostics {
     /// Lint levels can be configured via attributes or command line options, but these aren't applied until this runs.
     pub fn into_updated(mut self, ast: &Ast, files: &[SliceFile], options: &SliceOptions) -> Vec<Diagnostic> {
         // Helper function that checks whether a lint should be allowed according to the provided identifiers.
-        fn is_lint_allowed_by<'b>(mut identifiers: impl Iterator<Item = &'b String>, lint: &Lint) -> bool {
+        fn is_lint_allowed_by<'b>(mut identifiers: impl Iterator<Item = &'b str>, lint: &Lint) -> bool {
             identifiers.any(|identifier| identifier == "All" || identifier == lint.code())
         }
 
-        // Helper function that checks whether a lint is allowed by attributes on the provided entity.
-        fn is_lint_allowed_by_attributes(attributable: &(impl Attributable + ?Sized), lint: &Lint) -> bool {
-            let attributes = attributable.all_attributes().concat().into_iter();
-            let mut allowed = attributes.filter_map(|a| a.downcast::<attributes::Allow>());
-            allowed.any(|allow| is_lint_allowed_by(allow.allowed_lints.iter(), lint))
-        }
-
+        // Lints can be allowed in multiple ways, but only the most specific one is recorded, so the checks below are
+        // ordered from least to most specific.
         for diagnostic in &mut self.0 {
             // If this diagnostic is a lint, update its diagnostic level. Errors always have a level of `Error`.
             if let DiagnosticKind::Lint(lint) = &diagnostic.kind {
                 // Check if the lint is allowed by an `--allow` flag passed on the command line.
-                if is_lint_allowed_by(options.allowed_lints.iter(), lint) {
+                if is_lint_allowed_by(options.allowed_lints.iter().map(String::as_str), lint) {
                     diagnostic.level = DiagnosticLevel::Allowed;
+                    diagnostic.allowed_by = Some(AllowedBy::CommandLine);
                 }
 
-                // If the diagnostic has a span, check if it's affected by an `allow` attribute on its file.
-                if let Some(span) = diagnostic.span() {
-                    let file = files.iter().find(|f| f.relative_path == span.file).expect("no file");
-                    if is_lint_allowed_by_attributes(file, lint) {
+                // If the diagnostic has a span in a file, check if it's affected by an `allow` attribute on that file.
+                if let Some(file) = diagnostic
+                    .span()
+                    .and_then(|s| files.iter().find(|f| f.relative_path == s.file))
+                {
+                    let allows = file.find_attributes::<attributes::Allow>().into_iter();
+                    let allowed_lints = allows.flat_map(|allow| allow.allowed_lints.iter().map(String::as_str));
+                    if is_lint_allowed_by(allowed_lints, lint) {
                         diagnostic.level = DiagnosticLevel::Allowed;
+                        diagnostic.allowed_by = Some(AllowedBy::FileAttribute);
                     }
                 }
 
                 // If the diagnostic has a scope, check if it's affected by an `allow` attribute in that scope.
-                if let Some(scope) = diagnostic.scope() {
-                    if let Ok(entity) = ast.find_element::<dyn Entity>(scope) {
-                        if is_lint_allowed_by_attributes(entity, lint) {
+                if let Some(scope) = diagnostic.scope.clone() {
+                    if let Ok(entity) = ast.find_element::<dyn Entity>(&scope) {
+                        if is_lint_allowed_by(entity.allowed_lints().into_iter(), lint) {
                             diagnostic.level = DiagnosticLevel::Allowed;
+                            diagnostic.allowed_by = Some(AllowedBy::Attribute(scope));
                         }
                     }
                 }
             }
         }
+
+        // If the user provided a baseline, allow the warnings it records, and promote any new warnings to errors.
+        // When updating the baseline, warnings are left as-is, since they'll all be recorded in the new baseline.
+        if let Some(path) = options.baseline.as_deref().filter(|_| !options.update_baseline) {
+            match Baseline::read(path) {
+                Ok(baseline) => baseline.apply_to(&mut self.0, path),
+                Err(error) => {
+                    let path = path.to_owned();
+                    Diagnostic::new(Error::IO {
+                        action: "read",
+                        path,
+                        error,
+                    })
+                    .push_into(&mut self);
+                }
+            }
+        }
         self.0
     }
 
@@ -17,6 +17,82 @@ pub enum Error {
         message: String,
     },
 
+    // ----------------  File Errors ---------------- //
+    /// A path passed to the compiler does not exist.
+    FileNotFound {
+        /// The path that was passed to the compiler.
+        path: String,
+    },
+
+    /// A file passed to the compiler does not have the '.slice' extension.
+    NotASliceFile {
+        /// The path of the file.
+        path: String,
+    },
+
+    /// A directory was passed to the compiler as a source, but directories can only be passed as references.
+    DirectoryPassedAsSource {
+        /// The path of the directory.
+        path: String,
+    },
+
+    /// A Slice file's contents were not valid UTF-8.
+    InvalidUtf8 {
+        /// The path of the file.
+        path: String,
+        /// The offset (in bytes) of the first byte which isn't part of a valid UTF-8 sequence.
+        offset: usize,
+    },
+
+    /// A Slice file was larger than the limit configured by `--max-file-size`.
+    FileTooLarge {
+        /// The path of the file.
+        path: String,
+        /// The size of the file (in bytes).
+        size: u64,
+        /// The configured maximum file size (in bytes).
+        max: u64,
+    },
+
+    // ----------------  Package Errors ---------------- //
+    /// A package passed with `--package` (or depended on by another package) couldn't be found in any package path.
+    PackageNotFound {
+        /// The name of the package.
+        name: String,
+        /// The version of the package that was requested, if one was.
+        version: Option<String>,
+    },
+
+    /// A package manifest couldn't be parsed, or contained invalid values.
+    InvalidPackageManifest {
+        /// The path of the manifest.
+        path: String,
+        /// A description of why the manifest is invalid.
+        message: String,
+    },
+
+    /// The same package was required at two different versions.
+    ConflictingPackageVersions {
+        /// The name of the package.
+        name: String,
+        /// The version of the package that was already resolved.
+        resolved: String,
+        /// The other version that was requested.
+        requested: String,
+    },
+
+    /// A file in a package uses a different compilation mode than the one listed for it in the package's manifest.
+    PackageModeMismatch {
+        /// The path of the file.
+        path: String,
+        /// The name of the package.
+        package: String,
+        /// The compilation mode listed in the manifest.
+        expected: CompilationMode,
+        /// The compilation mode the file actually uses.
+        actual: CompilationMode,
+    },
+
     // ---------------- Dictionary Errors ---------------- //
     /// Dictionaries cannot use optional types as keys.
     KeyMustBeNonOptional,
@@ -56,9 +132,24 @@ pub enum Error {
         kind: String,
     },
 
+    /// Sequences and dictionaries cannot have optional elements of this kind in Slice1 mode.
+    OptionalElementsNotSupported {
+        /// The kind of collection that contains the optional elements: "sequence" or "dictionary".
+        collection: String,
+
+        /// The kind of the optional elements.
+        kind: String,
+    },
+
     /// Streamed parameters cannot be used in Slice1 mode.
     StreamedParametersNotSupported,
 
+    /// Boxed types cannot be used in Slice1 mode.
+    BoxedTypesNotSupported {
+        /// The kind that is boxed.
+        kind: String,
+    },
+
     /// A slice type was used in a compilation mode that it isn't supported by.
     UnsupportedType {
         /// The kind that was used.
@@ -105,6 +196,12 @@ pub enum Error {
         max: i128,
     },
 
+    /// An enumerator's implicit value (the previous enumerator's value plus 1) was too large to be represented.
+    ImplicitEnumeratorValueOverflows {
+        /// The identifier of the enumerator.
+        enumerator_identifier: String,
+    },
+
     /// Enums must be contain at least one enumerator.
     MustContainEnumerators {
         /// The identifier of the enum.
@@ -133,13 +230,57 @@ pub enum Error {
     /// Return tuples for an operation must contain at least two element.
     ReturnTuplesMustContainAtLeastTwoElements,
 
+    /// An interface inherited multiple operations with the same name, but different signatures, from its bases.
+    ConflictingInheritedOperations {
+        /// The identifier of the interface that inherited the conflicting operations.
+        interface_identifier: String,
+        /// The identifier shared by the conflicting operations.
+        operation_identifier: String,
+    },
+
+    /// An interface defined multiple operations with the same identifier.
+    /// Operations are dispatched by name, so unlike other languages, Slice doesn't support overloading.
+    OverloadedOperation {
+        /// The identifier of the interface that defined the operations.
+        interface_identifier: String,
+        /// The identifier shared by the operations.
+        operation_identifier: String,
+    },
+
     /// Multiple streamed parameters were used as parameters for an operation.
     MultipleStreamedMembers,
 
+    /// A streamed parameter was also tagged.
+    StreamedMembersCannotBeTagged {
+        /// The identifier of the parameter that caused the error.
+        parameter_identifier: String,
+    },
+
     // ----------------  Struct Errors ---------------- //
     /// Compact structs cannot be empty.
     CompactStructCannotBeEmpty,
 
+    /// A type was used with a different number of type arguments than it has type parameters.
+    WrongNumberOfTypeArguments {
+        /// The identifier of the type.
+        identifier: String,
+
+        /// The number of type parameters the type has.
+        expected: usize,
+
+        /// The number of type arguments that were provided.
+        actual: usize,
+    },
+
+    /// Instantiating a generic struct required more nested instantiations than the compiler allows.
+    GenericInstantiationTooDeep {
+        /// The identifier of the generic struct.
+        identifier: String,
+
+        /// The maximum number of nested instantiations.
+        max_depth: usize,
+    },
+
     // ----------------  Tag Errors ---------------- //
     /// A duplicate tag value was found.
     CannotHaveDuplicateTag {
@@ -209,6 +350,25 @@ pub enum Error {
     /// An integer literal was outside the parsable range of 0..i128::MAX.
     IntegerLiteralOverflows,
 
+    /// Evaluating a constant expression produced a value outside the representable range of -2^127..2^127-1.
+    ConstantExpressionOverflows,
+
+    /// The value of a constant or enumerator depends on itself.
+    CyclicConstantExpression {
+        /// The identifier of the constant or enumerator.
+        identifier: String,
+        /// The cycle that was found.
+        cycle: String,
+    },
+
+    /// A primitive alias couldn't be registered.
+    InvalidPrimitiveAlias {
+        /// The alias, as it was provided by the user.
+        alias: String,
+        /// Message explaining why the alias is invalid.
+        message: String,
+    },
+
     /// An integer literal contained illegal characters for its base.
     InvalidIntegerLiteral {
         /// The base of the integer literal; Ex: 16 (hex), 10 (dec).
@@ -235,6 +395,40 @@ pub enum Error {
         identifier: String,
     },
 
+    /// An identifier could refer to more than one element, because it starts with a using directive's alias, but
+    /// would also resolve to a different element if the alias wasn't expanded.
+    AmbiguousReference {
+        /// The identifier that is ambiguous.
+        identifier: String,
+        /// The fully scoped identifiers of the elements it could refer to, quoted and separated by commas.
+        candidates: String,
+    },
+
+    /// A target or backend option named a backend that isn't registered with the compiler.
+    UnknownBackend {
+        /// The name of the backend.
+        name: String,
+    },
+
+    /// Two identifiers in the same scope would be the same in a backend's generated code. Either because its language
+    /// is case-insensitive, or because of how the backend converts the identifiers' case or escapes reserved words.
+    GeneratedIdentifierCollision {
+        /// The identifier that collides with an earlier one.
+        identifier: String,
+        /// The identifier it collides with.
+        other_identifier: String,
+        /// The name of the backend whose generated code they collide in.
+        backend: String,
+    },
+
+    /// A custom type doesn't have a mapping attribute for one or more of the selected backends.
+    MissingCustomTypeMappings {
+        /// The identifier of the custom type.
+        identifier: String,
+        /// The names of the backends it has no mapping for, quoted and separated by commas.
+        backends: String,
+    },
+
     // ----------------  Attribute Errors ---------------- //
     /// An invalid argument was provided to an attribute directive.
     ArgumentNotSupported {
@@ -268,6 +462,17 @@ pub enum Error {
     // ----------------  Type Alias Errors ---------------- //
     /// A type alias had an optional underlying type.
     TypeAliasOfOptional,
+
+    // ----------------  Visibility Errors ---------------- //
+    /// A public entity used an internal type in its signature.
+    InternalTypeExposed {
+        /// The kind of the public entity.
+        kind: &'static str,
+        /// The identifier of the public entity.
+        identifier: String,
+        /// The identifier of the internal type it exposes.
+        type_identifier: String,
+    },
 }
 
 implement_diagnostic_functions!(
@@ -559,6 +764,186 @@ implement_diagnostic_functions!(
         CannotBeCompact,
         format!("'{kind}' '{identifier}' cannot be marked compact"),
         kind, identifier
+    ),
+    (
+        "E056",
+        ImplicitEnumeratorValueOverflows,
+        format!("invalid enumerator '{enumerator_identifier}': its implicit value overflows the range of representable values"),
+        enumerator_identifier
+    ),
+    (
+        "E057",
+        ConstantExpressionOverflows,
+        "constant expression overflows the range of representable values -2^127 <= i <= 2^127 - 1"
+    ),
+    (
+        "E058",
+        StreamedMembersCannotBeTagged,
+        format!("invalid parameter '{parameter_identifier}': streamed parameters cannot be tagged"),
+        parameter_identifier
+    ),
+    (
+        "E059",
+        ConflictingInheritedOperations,
+        format!("interface '{interface_identifier}' inherits conflicting definitions of operation '{operation_identifier}' from its base interfaces"),
+        interface_identifier,
+        operation_identifier
+    ),
+    (
+        "E060",
+        FileNotFound,
+        format!("unable to read '{path}': no such file or directory"),
+        path
+    ),
+    (
+        "E061",
+        NotASliceFile,
+        format!("unable to read '{path}': Slice files must end with a '.slice' extension"),
+        path
+    ),
+    (
+        "E062",
+        DirectoryPassedAsSource,
+        format!("unable to read '{path}': expected a Slice file but found a directory; directories can only be passed as references"),
+        path
+    ),
+    (
+        "E063",
+        InvalidUtf8,
+        format!("unable to read '{path}': file contains invalid UTF-8 at byte offset {offset}"),
+        path,
+        offset
+    ),
+    (
+        "E064",
+        UnknownBackend,
+        format!("unknown backend '{name}'"),
+        name
+    ),
+    (
+        "E065",
+        GeneratedIdentifierCollision,
+        format!("'{identifier}' collides with '{other_identifier}' in the code generated by '{backend}'"),
+        identifier,
+        other_identifier,
+        backend
+    ),
+    (
+        "E066",
+        FileTooLarge,
+        format!("unable to read '{path}': file is {size} bytes, which exceeds the maximum file size of {max} bytes"),
+        path,
+        size,
+        max
+    ),
+    (
+        "E067",
+        InternalTypeExposed,
+        format!("{kind} '{identifier}' is public, but exposes the internal type '{type_identifier}'"),
+        kind,
+        identifier,
+        type_identifier
+    ),
+    (
+        "E068",
+        OverloadedOperation,
+        format!("interface '{interface_identifier}' defines multiple operations named '{operation_identifier}'; operations cannot be overloaded"),
+        interface_identifier,
+        operation_identifier
+    ),
+    (
+        "E069",
+        OptionalElementsNotSupported,
+        format!("{collection} elements cannot be optionals of type '{kind}' in Slice1 mode"),
+        collection,
+        kind
+    ),
+    (
+        "E070",
+        WrongNumberOfTypeArguments,
+        format!(
+            "'{identifier}' takes {expected} type {}, but {actual} {} provided",
+            if *expected == 1 { "argument" } else { "arguments" },
+            if *actual == 1 { "was" } else { "were" },
+        ),
+        identifier,
+        expected,
+        actual
+    ),
+    (
+        "E071",
+        GenericInstantiationTooDeep,
+        format!("instantiating '{identifier}' requires more than {max_depth} levels of nested generic instantiations"),
+        identifier,
+        max_depth
+    ),
+    (
+        "E072",
+        BoxedTypesNotSupported,
+        format!("'{kind}' types cannot be boxed in Slice1 mode"),
+        kind
+    ),
+    (
+        "E073",
+        MissingCustomTypeMappings,
+        format!("custom type '{identifier}' has no mapping for the following backends: {backends}"),
+        identifier,
+        backends
+    ),
+    (
+        "E074",
+        AmbiguousReference,
+        format!("'{identifier}' is ambiguous; it could refer to any of: {candidates}"),
+        identifier,
+        candidates
+    ),
+    (
+        "E075",
+        PackageNotFound,
+        match version {
+            Some(version) => format!("package '{name}' version '{version}' could not be found"),
+            None => format!("package '{name}' could not be found"),
+        },
+        name,
+        version
+    ),
+    (
+        "E076",
+        InvalidPackageManifest,
+        format!("invalid package manifest '{path}': {message}"),
+        path,
+        message
+    ),
+    (
+        "E077",
+        ConflictingPackageVersions,
+        format!("package '{name}' is required at version '{requested}', but version '{resolved}' was already resolved"),
+        name,
+        resolved,
+        requested
+    ),
+    (
+        "E078",
+        PackageModeMismatch,
+        format!("'{path}' uses {actual} mode, but package '{package}' lists it as a {expected} file"),
+        path,
+        package,
+        expected,
+        actual
+    ),
+    (
+        "E079",
+        CyclicConstantExpression,
+        format!("the value of '{identifier}' depends on itself: {cycle}"),
+        identifier,
+        cycle
+    ),
+    (
+        "E080",
+        InvalidPrimitiveAlias,
+        format!("invalid primitive alias '{alias}': {message}"),
+        alias,
+        message
     )
 );
 
@@ -13,6 +13,16 @@ pub enum Lint {
         path: String,
     },
 
+    /// Multiple input files had identical contents, even though they're at different paths.
+    /// This usually means a file was copied (or vendored) and both copies were passed to the compiler.
+    /// Only the first of these files is used; parsing the others would just produce redefinition errors.
+    DuplicateFileContents {
+        /// The path of the file that was ignored.
+        path: String,
+        /// The path of the file with the same contents that was used instead.
+        original_path: String,
+    },
+
     /// A deprecated Slice element was used.
     Deprecated {
         /// The element's identifier.
@@ -22,6 +32,17 @@ pub enum Lint {
         reason: Option<String>,
     },
 
+    /// A relatively scoped type reference resolved to a definition which shadows another definition, with the same
+    /// identifier, in an enclosing scope.
+    ShadowedReference {
+        /// The identifier used by the type reference.
+        identifier: String,
+        /// The fully scoped identifier of the definition it resolved to.
+        chosen: String,
+        /// The fully scoped identifier of the definition that was shadowed.
+        shadowed: String,
+    },
+
     /// A syntactical mistake in a doc-comment.
     MalformedDocComment { message: String },
 
@@ -34,6 +55,53 @@ pub enum Lint {
     /// - The link pointed to an un-linkable element, e.g. a module, result, sequence, dictionary, or primitive.
     /// - The link pointed to a non-existent element.
     BrokenDocLink { message: String },
+
+    /// An enumerator in a flags enum had a value which wasn't a power of 2 (or 0), or a combination of the enum's other
+    /// flags, so it doesn't represent a single flag or a named combination of flags.
+    NonPowerOfTwoFlag {
+        /// The identifier of the enumerator.
+        enumerator_identifier: String,
+        /// The value of the enumerator.
+        value: i128,
+    },
+
+    /// The compress attribute was applied to an operation, but had nothing to compress. Either:
+    /// - `compress(Args)` was used on an operation with no parameters.
+    /// - `compress(Return)` was used on an operation that doesn't return anything.
+    UnnecessaryCompression {
+        /// The identifier of the operation.
+        operation_identifier: String,
+        /// The argument of the compress attribute that has no effect (either "Args" or "Return").
+        argument: String,
+    },
+
+    /// A compact struct had more fields than the limit configured by `--max-compact-struct-fields`.
+    CompactStructTooLarge {
+        /// The identifier of the compact struct.
+        struct_identifier: String,
+        /// The number of fields the struct has.
+        field_count: usize,
+        /// The configured maximum number of fields.
+        max: usize,
+    },
+
+    /// An operation had more parameters than the limit configured by `--max-operation-parameters`.
+    TooManyParameters {
+        /// The identifier of the operation.
+        operation_identifier: String,
+        /// The number of parameters the operation has.
+        parameter_count: usize,
+        /// The configured maximum number of parameters.
+        max: usize,
+    },
+
+    /// Anonymous types were nested deeper than the limit configured by `--max-type-nesting-depth`.
+    TypeNestingTooDeep {
+        /// How many levels deep the anonymous types were nested.
+        depth: usize,
+        /// The configured maximum nesting depth.
+        max: usize,
+    },
 }
 
 impl Lint {
@@ -41,10 +109,17 @@ impl Lint {
     pub fn get_default_level(&self) -> DiagnosticLevel {
         match self {
             Self::DuplicateFile { .. } => DiagnosticLevel::Warning,
+            Self::DuplicateFileContents { .. } => DiagnosticLevel::Warning,
             Self::Deprecated { .. } => DiagnosticLevel::Warning,
+            Self::ShadowedReference { .. } => DiagnosticLevel::Warning,
             Self::MalformedDocComment { .. } => DiagnosticLevel::Warning,
             Self::BrokenDocLink { .. } => DiagnosticLevel::Warning,
             Self::IncorrectDocComment { .. } => DiagnosticLevel::Warning,
+            Self::NonPowerOfTwoFlag { .. } => DiagnosticLevel::Warning,
+            Self::UnnecessaryCompression { .. } => DiagnosticLevel::Warning,
+            Self::CompactStructTooLarge { .. } => DiagnosticLevel::Warning,
+            Self::TooManyParameters { .. } => DiagnosticLevel::Warning,
+            Self::TypeNestingTooDeep { .. } => DiagnosticLevel::Warning,
         }
     }
 }
@@ -56,6 +131,12 @@ implement_diagnostic_functions!(
         format!("slice file was provided more than once: '{path}'"),
         path
     ),
+    (
+        DuplicateFileContents,
+        format!("slice file '{path}' has the same contents as '{original_path}' and was ignored"),
+        path,
+        original_path
+    ),
     (
         Deprecated,
         if let Some(reason) = reason {
@@ -66,7 +147,46 @@ implement_diagnostic_functions!(
         identifier,
         reason
     ),
+    (
+        ShadowedReference,
+        format!("'{identifier}' refers to '{chosen}', which shadows '{shadowed}'"),
+        identifier,
+        chosen,
+        shadowed
+    ),
     (MalformedDocComment, message, message),
     (IncorrectDocComment, message, message),
-    (BrokenDocLink, message, message)
+    (BrokenDocLink, message, message),
+    (
+        NonPowerOfTwoFlag,
+        format!("enumerator '{enumerator_identifier}' has a value of '{value}', which is not a power of 2 or a combination of other flags"),
+        enumerator_identifier,
+        value
+    ),
+    (
+        UnnecessaryCompression,
+        format!("'compress({argument})' has no effect on operation '{operation_identifier}'"),
+        operation_identifier,
+        argument
+    ),
+    (
+        CompactStructTooLarge,
+        format!("compact struct '{struct_identifier}' has {field_count} fields, which exceeds the configured limit of {max}"),
+        struct_identifier,
+        field_count,
+        max
+    ),
+    (
+        TooManyParameters,
+        format!("operation '{operation_identifier}' has {parameter_count} parameters, which exceeds the configured limit of {max}"),
+        operation_identifier,
+        parameter_count,
+        max
+    ),
+    (
+        TypeNestingTooDeep,
+        format!("anonymous types are nested {depth} levels deep, which exceeds the configured limit of {max}"),
+        depth,
+        max
+    )
 );
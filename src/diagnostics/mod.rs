@@ -3,10 +3,12 @@
 use crate::slice_file::Span;
 use serde::Serialize;
 
+mod baseline;
 mod diagnostic;
 mod errors;
 mod lints;
 
+pub use baseline::{Baseline, BaselineEntry};
 pub use diagnostic::*;
 pub use errors::Error;
 pub use lints::Lint;
@@ -25,7 +27,7 @@ macro_rules! implement_diagnostic_functions {
         impl Lint {
             // TODO maybe we should move this somewhere other than `Lint`? Like in `Attribute` maybe?
             /// This array contains all the valid arguments for the 'allow' attribute.
-            pub const ALLOWABLE_LINT_IDENTIFIERS: [&'static str; 6] = [
+            pub const ALLOWABLE_LINT_IDENTIFIERS: [&'static str; 13] = [
                 "All",
                 $(stringify!($kind)),*
             ];
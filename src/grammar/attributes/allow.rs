@@ -8,16 +8,25 @@ pub struct Allow {
 }
 
 impl Allow {
-    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+    pub fn parse_from(
+        Unparsed {
+            directive,
+            args,
+            arg_spans,
+            ..
+        }: &Unparsed,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
         debug_assert_eq!(directive, Self::directive());
 
         check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
 
-        for arg in args {
+        for (arg, arg_span) in args.iter().zip(arg_spans) {
             let mut is_valid = Lint::ALLOWABLE_LINT_IDENTIFIERS.contains(&arg.as_str());
 
-            // The `DuplicateFile` lint can't be configured by attributes because it's a command-line specific lint.
-            if arg == "DuplicateFile" {
+            // The `DuplicateFile*` lints can't be configured by attributes because they're command-line specific lints.
+            if arg == "DuplicateFile" || arg == "DuplicateFileContents" {
                 is_valid = false;
             }
 
@@ -28,7 +37,7 @@ impl Allow {
                     argument: arg.to_owned(),
                     directive: "allow".to_owned(),
                 })
-                .set_span(span);
+                .set_span(arg_span);
 
                 // Check if the argument only differs in case from a valid one.
                 let suggestion = Lint::ALLOWABLE_LINT_IDENTIFIERS
@@ -48,7 +57,7 @@ impl Allow {
     }
 
     pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
-        if matches!(applied_on, Attributables::Module(_) | Attributables::TypeRef(_)) {
+        if matches!(applied_on, Attributables::TypeRef(_)) {
             report_unexpected_attribute(self, span, None, diagnostics);
         }
     }
@@ -0,0 +1,40 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::grammar::Types;
+
+/// Marks a type reference as an indirection, which languages should map to a heap-allocated (or reference) type.
+///
+/// Boxed types are encoded exactly like the type they box. But unlike other types, boxed types can be used to form
+/// cycles, as long as the box is the element of a sequence or the value of a dictionary, since those can be empty.
+/// This lets Slice2 express recursive data structures, like `struct Tree { children: Sequence<[boxed] Tree> }`.
+///
+/// Boxed types can only be used in Slice2 mode; Slice1 expresses recursive data structures with classes.
+#[derive(Debug)]
+pub struct Boxed {}
+
+impl Boxed {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        Boxed {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let is_valid = match applied_on {
+            Attributables::TypeRef(type_ref) => {
+                matches!(type_ref.concrete_type(), Types::Struct(_) | Types::Enum(_))
+            }
+            _ => false,
+        };
+
+        if !is_valid {
+            let note = "the boxed attribute can only be applied to type references of structs and enums";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(Boxed, "boxed", false);
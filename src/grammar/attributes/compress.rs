@@ -1,6 +1,7 @@
 // Copyright (c) ZeroC, Inc.
 
 use super::*;
+use crate::grammar::{NamedSymbol, Operation};
 
 #[derive(Debug)]
 pub struct Compress {
@@ -9,13 +10,22 @@ pub struct Compress {
 }
 
 impl Compress {
-    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+    pub fn parse_from(
+        Unparsed {
+            directive,
+            args,
+            arg_spans,
+            ..
+        }: &Unparsed,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
         debug_assert_eq!(directive, Self::directive());
 
         check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
 
         let (mut compress_args, mut compress_return) = (false, false);
-        for arg in args {
+        for (arg, arg_span) in args.iter().zip(arg_spans) {
             match arg.as_str() {
                 "Args" => compress_args = true,
                 "Return" => compress_return = true,
@@ -24,7 +34,7 @@ impl Compress {
                         argument: arg.clone(),
                         directive: Self::directive().to_owned(),
                     })
-                    .set_span(span)
+                    .set_span(arg_span)
                     .add_note("'Args' and 'Return' are the only valid arguments", None)
                     .push_into(diagnostics);
                 }
@@ -38,9 +48,45 @@ impl Compress {
     }
 
     pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
-        if !matches!(applied_on, Attributables::Operation(_)) {
-            let note = "the compress attribute can only be applied to operations";
-            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        match applied_on {
+            Attributables::Operation(operation) => self.check_for_unnecessary_compression(operation, span, diagnostics),
+            Attributables::Interface(_) => {}
+            _ => {
+                let note = "the compress attribute can only be applied to interfaces and operations";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+        }
+    }
+
+    /// Reports a warning if this attribute compresses the arguments of an operation with no parameters,
+    /// or compresses the return of an operation that doesn't return anything.
+    fn check_for_unnecessary_compression(&self, operation: &Operation, span: &Span, diagnostics: &mut Diagnostics) {
+        let checks = [
+            (
+                self.compress_args,
+                operation.parameters.is_empty(),
+                "Args",
+                "has no parameters",
+            ),
+            (
+                self.compress_return,
+                operation.return_type.is_empty(),
+                "Return",
+                "does not return anything",
+            ),
+        ];
+
+        for (is_compressed, is_empty, argument, reason) in checks {
+            if is_compressed && is_empty {
+                Diagnostic::new(Lint::UnnecessaryCompression {
+                    operation_identifier: operation.identifier().to_owned(),
+                    argument: argument.to_owned(),
+                })
+                .set_span(span)
+                .set_scope(operation.parser_scoped_identifier())
+                .add_note(format!("operation '{}' {reason}", operation.identifier()), None)
+                .push_into(diagnostics);
+            }
         }
     }
 }
@@ -0,0 +1,121 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::grammar::{Element, Primitive};
+
+/// Declares the context entries (request headers) that an operation expects, along with their types.
+/// Each argument is an entry of the form `"<key>: <type>"`, where the type is a primitive type, optionally marked with
+/// a '?'. For example: `[context("traceId: string", "retries: uint8?")]`.
+#[derive(Debug)]
+pub struct Context {
+    pub entries: Vec<ContextEntry>,
+}
+
+/// A single context entry, declared by the [Context] attribute.
+#[derive(Debug)]
+pub struct ContextEntry {
+    pub key: String,
+    pub value_type: Primitive,
+    pub is_optional: bool,
+}
+
+impl Context {
+    pub fn parse_from(
+        Unparsed {
+            directive,
+            args,
+            arg_spans,
+            ..
+        }: &Unparsed,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        let mut entries: Vec<ContextEntry> = Vec::new();
+        for (arg, arg_span) in args.iter().zip(arg_spans) {
+            let Some(entry) = Self::parse_entry(arg) else {
+                Diagnostic::new(Error::ArgumentNotSupported {
+                    argument: arg.clone(),
+                    directive: Self::directive().to_owned(),
+                })
+                .set_span(arg_span)
+                .add_note(
+                    "context entries must be of the form '<key>: <type>', where <type> is a primitive type",
+                    None,
+                )
+                .push_into(diagnostics);
+                continue;
+            };
+
+            if entries.iter().any(|other| other.key == entry.key) {
+                Diagnostic::new(Error::ArgumentNotSupported {
+                    argument: arg.clone(),
+                    directive: Self::directive().to_owned(),
+                })
+                .set_span(arg_span)
+                .add_note(format!("the context key '{}' was already declared", entry.key), None)
+                .push_into(diagnostics);
+                continue;
+            }
+            entries.push(entry);
+        }
+
+        Context { entries }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(applied_on, Attributables::Operation(_)) {
+            let note = "the context attribute can only be applied to operations";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+    }
+
+    /// Parses a context entry of the form `<key>: <type>`, returning `None` if the entry is malformed.
+    fn parse_entry(arg: &str) -> Option<ContextEntry> {
+        let (key, type_string) = arg.split_once(':')?;
+        let (key, type_string) = (key.trim(), type_string.trim());
+        let (type_string, is_optional) = match type_string.strip_suffix('?') {
+            Some(stripped) => (stripped.trim_end(), true),
+            None => (type_string, false),
+        };
+
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        let value_type = CONTEXT_TYPES.into_iter().find(|p| p.kind() == type_string)?;
+
+        Some(ContextEntry {
+            key: key.to_owned(),
+            value_type,
+            is_optional,
+        })
+    }
+}
+
+/// The primitive types that context entries can have. Classes cannot be sent as context.
+const CONTEXT_TYPES: [Primitive; 19] = [
+    Primitive::Bool,
+    Primitive::Int8,
+    Primitive::UInt8,
+    Primitive::Int16,
+    Primitive::UInt16,
+    Primitive::Int32,
+    Primitive::UInt32,
+    Primitive::VarInt32,
+    Primitive::VarUInt32,
+    Primitive::Int64,
+    Primitive::UInt64,
+    Primitive::VarInt62,
+    Primitive::VarUInt62,
+    Primitive::Float32,
+    Primitive::Float64,
+    Primitive::String,
+    Primitive::Uuid,
+    Primitive::Timestamp,
+    Primitive::Duration,
+];
+
+implement_attribute_kind_for!(Context, "context", false);
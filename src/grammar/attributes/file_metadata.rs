@@ -0,0 +1,114 @@
+// Copyright (c) ZeroC, Inc.
+
+//! Well-known file attributes, which describe the Slice file they're applied to. Backends and documentation tools can
+//! use them to stamp the artifacts they generate. See [SliceFile::metadata](crate::slice_file::SliceFile::metadata).
+
+use super::*;
+
+/// The version of a Slice file's definitions (`[[version("1.2")]]`). Versions are free-form, and aren't validated.
+#[derive(Debug)]
+pub struct Version {
+    pub version: String,
+}
+
+impl Version {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let version = args.first().cloned().unwrap_or_default();
+        Version { version }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        validate_on_file(self, applied_on, span, diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(Version, "version", false);
+
+/// The package that a Slice file's definitions are published in (`[[package("com.acme")]]`).
+#[derive(Debug)]
+pub struct Package {
+    pub package: String,
+}
+
+impl Package {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let package = args.first().cloned().unwrap_or_default();
+        Package { package }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        validate_on_file(self, applied_on, span, diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(Package, "package", false);
+
+/// The license that a Slice file is distributed under (`[[license("Apache-2.0")]]`).
+#[derive(Debug)]
+pub struct License {
+    pub license: String,
+}
+
+impl License {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let license = args.first().cloned().unwrap_or_default();
+        License { license }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        validate_on_file(self, applied_on, span, diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(License, "license", false);
+
+/// The authors of a Slice file (`[[authors("Alice", "Bob")]]`).
+#[derive(Debug)]
+pub struct Authors {
+    pub authors: Vec<String>,
+}
+
+impl Authors {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        let authors = args.clone();
+        Authors { authors }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        validate_on_file(self, applied_on, span, diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(Authors, "authors", false);
+
+/// Reports an error if the provided metadata attribute was applied to anything other than a Slice file.
+fn validate_on_file(
+    attribute: &impl AttributeKind,
+    applied_on: Attributables,
+    span: &Span,
+    diagnostics: &mut Diagnostics,
+) {
+    if !matches!(applied_on, Attributables::SliceFile(_)) {
+        let note = format!(
+            "the {} attribute can only be applied to Slice files, as a file attribute: '[[{0}(...)]]'",
+            attribute.directive(),
+        );
+        report_unexpected_attribute(attribute, span, Some(&note), diagnostics);
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct Flags {}
+
+impl Flags {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        Flags {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if let Attributables::Enum(enum_def) = applied_on {
+            // Flags enums must have an unsigned underlying type, so that each bit can be used as a flag.
+            let has_unsigned_underlying_type = enum_def
+                .underlying
+                .as_ref()
+                .is_some_and(|underlying| underlying.is_unsigned_numeric());
+            if !has_unsigned_underlying_type {
+                let note = "the flags attribute can only be applied to enums with unsigned underlying types";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+        } else {
+            let note = "the flags attribute can only be applied to enums";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(Flags, "flags", false);
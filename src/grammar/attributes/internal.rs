@@ -0,0 +1,37 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+/// Marks a definition as internal to the library it's defined in. See [Visibility](crate::grammar::Visibility).
+#[derive(Debug)]
+pub struct Internal {}
+
+impl Internal {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        Internal {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(_)
+            | Attributables::Class(_)
+            | Attributables::Exception(_)
+            | Attributables::Interface(_)
+            | Attributables::Enum(_)
+            | Attributables::CustomType(_)
+            | Attributables::TypeAlias(_)
+            | Attributables::Constant(_) => {}
+            _ => {
+                let note =
+                    "the internal attribute can only be applied to definitions; their members share their visibility";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+        }
+    }
+}
+
+implement_attribute_kind_for!(Internal, "internal", false);
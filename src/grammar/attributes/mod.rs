@@ -1,18 +1,30 @@
 // Copyright (c) ZeroC, Inc.
 
 mod allow;
+mod boxed;
 mod compress;
+mod context;
 mod deprecated;
+mod file_metadata;
+mod flags;
+mod internal;
 mod oneway;
+mod size;
 mod sliced_format;
 
 pub use allow::*;
+pub use boxed::*;
 pub use compress::*;
+pub use context::*;
 pub use deprecated::*;
+pub use file_metadata::*;
+pub use flags::*;
+pub use internal::*;
 pub use oneway::*;
+pub use size::*;
 pub use sliced_format::*;
 
-use super::Attributables;
+use super::{Attributables, Literal};
 use crate::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
 use crate::slice_file::Span;
 use crate::utils::attribute_parsing_util::*;
@@ -22,11 +34,20 @@ pub trait AttributeKind: std::fmt::Debug {
     fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics);
     fn as_any(&self) -> &dyn std::any::Any;
     fn directive(&self) -> &str;
+
+    /// Returns true if this attribute describes how a type should be mapped, rather than the element it's applied to.
+    /// Only type-metadata attributes are forwarded from type aliases, members, and operations to their type references.
+    fn is_type_metadata(&self) -> bool {
+        false
+    }
 }
 
 #[macro_export] // We export this macro so languages can implement their own attributes.
 macro_rules! implement_attribute_kind_for {
     ($type:ty, $directive:literal, $is_repeatable:literal) => {
+        implement_attribute_kind_for!($type, $directive, $is_repeatable, false);
+    };
+    ($type:ty, $directive:literal, $is_repeatable:literal, $is_type_metadata:literal) => {
         impl $type {
             pub fn directive() -> &'static str {
                 $directive
@@ -49,6 +70,10 @@ macro_rules! implement_attribute_kind_for {
             fn directive(&self) -> &str {
                 Self::directive()
             }
+
+            fn is_type_metadata(&self) -> bool {
+                $is_type_metadata
+            }
         }
     };
 }
@@ -59,6 +84,14 @@ pub use implement_attribute_kind_for;
 pub struct Unparsed {
     pub directive: String,
     pub args: Vec<String>,
+
+    /// The span of each argument, in the same order as [args](Unparsed::args).
+    /// Each span covers the argument as it was written, including the quotes of string literals.
+    pub arg_spans: Vec<Span>,
+
+    /// The literal each argument was written as, in the same order as [args](Unparsed::args).
+    /// This is `None` for arguments which were written as identifiers (other than `true` and `false`).
+    pub arg_literals: Vec<Option<Literal>>,
 }
 
 impl AttributeKind for Unparsed {
@@ -76,4 +109,10 @@ impl AttributeKind for Unparsed {
     fn directive(&self) -> &str {
         &self.directive
     }
+
+    // The compiler doesn't know what unparsed attributes mean, so we conservatively treat them as type-metadata.
+    // This leaves it up to the languages that parse them to decide what to do with any forwarded attributes.
+    fn is_type_metadata(&self) -> bool {
+        true
+    }
 }
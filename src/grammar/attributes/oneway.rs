@@ -1,12 +1,13 @@
 // Copyright (c) ZeroC, Inc.
 
 use super::*;
+use crate::grammar::{NamedSymbol, Operation, Symbol, TypeRef};
 
 #[derive(Debug)]
 pub struct Oneway {}
 
 impl Oneway {
-    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+    pub fn parse_from(Unparsed { directive, args, .. }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
         debug_assert_eq!(directive, Self::directive());
 
         check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
@@ -15,17 +16,72 @@ impl Oneway {
     }
 
     pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
-        if let Attributables::Operation(operation) = applied_on {
-            // If the operation can return or throw data, it can't be marked oneway.
-            if !operation.return_type.is_empty() || !operation.exception_specification.is_empty() {
-                let note = "operations that return or throw data cannot be marked oneway";
+        match applied_on {
+            Attributables::Operation(operation) => self.validate_on_operation(operation, false, span, diagnostics),
+
+            // Marking an interface oneway marks all of its operations oneway.
+            Attributables::Interface(interface_def) => {
+                for operation in interface_def.operations() {
+                    self.validate_on_operation(operation, true, span, diagnostics);
+                }
+            }
+
+            _ => {
+                let note = "the oneway attribute can only be applied to interfaces and operations";
                 report_unexpected_attribute(self, span, Some(note), diagnostics);
             }
-        } else {
-            let note = "the oneway attribute can only be applied to operations";
-            report_unexpected_attribute(self, span, Some(note), diagnostics);
         }
     }
+
+    fn validate_on_operation(
+        &self,
+        operation: &Operation,
+        is_inherited: bool,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) {
+        // If the operation can return or throw data, it can't be marked oneway, since no response is sent.
+        let return_members = operation.return_members();
+        let return_span = span_of(return_members.iter().map(|member| member.span()));
+        let exception_spans = operation.exception_specification.iter().map(TypeRef::span);
+        let exception_span = span_of(exception_spans.chain(&operation.throws_any_exception));
+        if return_span.is_none() && exception_span.is_none() {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: Self::directive().to_owned(),
+        })
+        .set_span(span);
+        if is_inherited {
+            let message = format!(
+                "'{}' is marked oneway because its interface is marked oneway",
+                operation.identifier(),
+            );
+            diagnostic = diagnostic.add_note(message, Some(operation.raw_identifier().span()));
+        }
+        if let Some(return_span) = &return_span {
+            let note = "operations marked oneway cannot return anything, since they don't receive a response";
+            diagnostic = diagnostic.add_note(note, Some(return_span));
+        }
+        if let Some(exception_span) = &exception_span {
+            let note = "operations marked oneway cannot throw exceptions, since they don't receive a response";
+            diagnostic = diagnostic.add_note(note, Some(exception_span));
+        }
+        diagnostic.push_into(diagnostics);
+    }
+}
+
+/// Returns a span covering all the provided spans, or `None` if no spans were provided.
+/// The spans must be in the same file, and in order.
+fn span_of<'a>(mut spans: impl Iterator<Item = &'a Span>) -> Option<Span> {
+    let first = spans.next()?;
+    let last = spans.last().unwrap_or(first);
+    Some(Span {
+        start: first.start,
+        end: last.end,
+        file: first.file.clone(),
+    })
 }
 
 implement_attribute_kind_for!(Oneway, "oneway", false);
@@ -0,0 +1,99 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::grammar::Types;
+
+/// Declares how many elements a sequence or dictionary is expected to hold, so languages can preallocate storage for
+/// them, or map them to fixed-capacity collections.
+///
+/// It takes either a single argument, for collections with a fixed size (`[size(4)]`), or a minimum and maximum size
+/// (`[size(0, 16)]`). Sizes are only hints, and don't affect how collections are encoded.
+#[derive(Debug)]
+pub struct Size {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Size {
+    /// Returns true if this attribute was given a single size, instead of a range of sizes.
+    pub fn is_fixed(&self) -> bool {
+        self.min == self.max
+    }
+
+    pub fn parse_from(
+        Unparsed {
+            directive,
+            args,
+            arg_spans,
+            ..
+        }: &Unparsed,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
+        if args.len() > 2 {
+            Diagnostic::new(Error::TooManyArguments {
+                expected: Self::directive().to_owned(),
+            })
+            .set_span(span)
+            .add_note(
+                "the size attribute takes either a single size, or a minimum and maximum size",
+                None,
+            )
+            .push_into(diagnostics);
+        }
+
+        let sizes = args.iter().zip(arg_spans).map(|(arg, arg_span)| {
+            arg.parse::<u64>().unwrap_or_else(|_| {
+                Diagnostic::new(Error::ArgumentNotSupported {
+                    argument: arg.clone(),
+                    directive: Self::directive().to_owned(),
+                })
+                .set_span(arg_span)
+                .add_note("sizes must be non-negative integers", None)
+                .push_into(diagnostics);
+                0
+            })
+        });
+        let sizes = sizes.collect::<Vec<_>>();
+
+        let min = sizes.first().copied().unwrap_or_default();
+        let max = match sizes.get(1) {
+            Some(&max) if max < min => {
+                Diagnostic::new(Error::ArgumentNotSupported {
+                    argument: args[1].clone(),
+                    directive: Self::directive().to_owned(),
+                })
+                .set_span(&arg_spans[1])
+                .add_note(
+                    format!("the maximum size cannot be less than the minimum size ({min})"),
+                    None,
+                )
+                .push_into(diagnostics);
+                min
+            }
+            Some(&max) => max,
+            None => min,
+        };
+
+        Size { min, max }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let is_valid = match applied_on {
+            Attributables::TypeRef(type_ref) => {
+                matches!(type_ref.concrete_type(), Types::Sequence(_) | Types::Dictionary(_))
+            }
+            _ => false,
+        };
+
+        if !is_valid {
+            let note = "the size attribute can only be applied to type references of sequences and dictionaries";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(Size, "size", false);
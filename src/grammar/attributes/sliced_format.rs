@@ -9,13 +9,22 @@ pub struct SlicedFormat {
 }
 
 impl SlicedFormat {
-    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+    pub fn parse_from(
+        Unparsed {
+            directive,
+            args,
+            arg_spans,
+            ..
+        }: &Unparsed,
+        span: &Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
         debug_assert_eq!(directive, Self::directive());
 
         check_that_arguments_were_provided(args, Self::directive(), span, diagnostics);
 
         let (mut sliced_args, mut sliced_return) = (false, false);
-        for arg in args {
+        for (arg, arg_span) in args.iter().zip(arg_spans) {
             match arg.as_str() {
                 "Args" => sliced_args = true,
                 "Return" => sliced_return = true,
@@ -24,7 +33,7 @@ impl SlicedFormat {
                         argument: arg.clone(),
                         directive: Self::directive().to_owned(),
                     })
-                    .set_span(span)
+                    .set_span(arg_span)
                     .add_note("'Args' and 'Return' are the only valid arguments", None)
                     .push_into(diagnostics);
                 }
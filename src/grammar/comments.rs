@@ -10,7 +10,40 @@ pub struct DocComment {
     pub returns: Vec<ReturnsTag>,
     pub throws: Vec<ThrowsTag>,
     pub see: Vec<SeeTag>,
+    /// The locale this comment is written in, if it's a localization of another comment (`@locale fr`).
+    pub locale: Option<Identifier>,
+    /// Translations of this comment into other locales. Each one is written as a `@locale` block at the end of the
+    /// comment, and holds the overview and tags which follow it.
+    pub localizations: Vec<DocComment>,
     pub span: Span,
+    /// The comment's text, exactly as it was written, without the leading "///" on each line.
+    pub raw_text: String,
+}
+
+impl DocComment {
+    /// Returns this comment's localization for the provided locale, or this comment itself if it hasn't been
+    /// localized into that locale. Locales are compared case-insensitively, so `pt_BR` matches `pt_br`.
+    pub fn localized(&self, locale: &str) -> &DocComment {
+        let mut localizations = self.localizations.iter();
+        localizations
+            .find(|localization| localization.locale().is_some_and(|l| l.eq_ignore_ascii_case(locale)))
+            .unwrap_or(self)
+    }
+
+    /// Returns the locale this comment is written in, if it's a localization of another comment.
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_ref().map(|locale| locale.value.as_str())
+    }
+
+    /// Returns the locales which this comment has been localized into, in the order they were written.
+    pub fn locales(&self) -> Vec<&str> {
+        self.localizations.iter().filter_map(DocComment::locale).collect()
+    }
+
+    /// Returns this comment, followed by each of its localizations.
+    pub fn with_localizations(&self) -> impl Iterator<Item = &DocComment> {
+        std::iter::once(self).chain(&self.localizations)
+    }
 }
 
 #[derive(Debug)]
@@ -18,6 +51,8 @@ pub struct ParamTag {
     pub identifier: Identifier,
     pub message: Message,
     pub span: Span,
+    /// The tag's text, exactly as it was written, from the tag's keyword to the end of its message.
+    pub raw_text: String,
 }
 
 #[derive(Debug)]
@@ -25,6 +60,8 @@ pub struct ReturnsTag {
     pub identifier: Option<Identifier>,
     pub message: Message,
     pub span: Span,
+    /// The tag's text, exactly as it was written, from the tag's keyword to the end of its message.
+    pub raw_text: String,
 }
 
 #[derive(Debug)]
@@ -32,6 +69,8 @@ pub struct ThrowsTag {
     pub thrown_type: TypeRefDefinition<Exception>,
     pub message: Message,
     pub span: Span,
+    /// The tag's text, exactly as it was written, from the tag's keyword to the end of its message.
+    pub raw_text: String,
 }
 
 impl ThrowsTag {
@@ -47,6 +86,8 @@ impl ThrowsTag {
 pub struct SeeTag {
     pub link: TypeRefDefinition<dyn Entity>,
     pub span: Span,
+    /// The tag's text, exactly as it was written.
+    pub raw_text: String,
 }
 
 impl SeeTag {
@@ -62,6 +103,8 @@ impl SeeTag {
 pub struct LinkTag {
     pub link: TypeRefDefinition<dyn Entity>,
     pub span: Span,
+    /// The tag's text, exactly as it was written.
+    pub raw_text: String,
 }
 
 impl LinkTag {
@@ -83,6 +126,8 @@ pub enum MessageComponent {
 pub struct Message {
     pub value: Vec<MessageComponent>,
     pub span: Span,
+    /// The message's text, exactly as it was written, before any whitespace was stripped or links were parsed.
+    pub raw_text: String,
 }
 
 implement_Element_for!(DocComment, "doc comment");
@@ -10,8 +10,21 @@ pub struct Attribute {
 }
 
 impl Attribute {
-    pub fn new(directive: String, args: Vec<String>, span: Span) -> Self {
-        let kind = Box::new(attributes::Unparsed { directive, args });
+    pub fn new(
+        directive: String,
+        args: Vec<String>,
+        arg_spans: Vec<Span>,
+        arg_literals: Vec<Option<Literal>>,
+        span: Span,
+    ) -> Self {
+        debug_assert_eq!(args.len(), arg_spans.len());
+        debug_assert_eq!(args.len(), arg_literals.len());
+        let kind = Box::new(attributes::Unparsed {
+            directive,
+            args,
+            arg_spans,
+            arg_literals,
+        });
         Self { kind, span }
     }
 
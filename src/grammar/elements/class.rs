@@ -55,6 +55,10 @@ impl Type for Class {
         true
     }
 
+    fn uses_classes(&self) -> bool {
+        true
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         Some(TagFormat::Class)
     }
@@ -65,7 +69,7 @@ impl Type for Class {
 }
 
 implement_Element_for!(Class, "class");
-implement_Attributable_for!(Class);
+implement_Attributable_for!(@Scoped Class);
 implement_Entity_for!(Class);
 implement_Commentable_for!(Class);
 implement_Container_for!(Class, Field, fields);
@@ -0,0 +1,99 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::super::*;
+use crate::slice_file::Span;
+use crate::utils::ptr_util::WeakPtr;
+
+/// A named integer constant, who's value is computed at compile time.
+/// Constants can be referenced by constant expressions (enumerator values, tags, compact IDs, and other constants).
+#[derive(Debug)]
+pub struct Constant {
+    pub identifier: Identifier,
+    pub value: Integer<i128>,
+    pub scope: Scope,
+    pub attributes: Vec<WeakPtr<Attribute>>,
+    pub comment: Option<DocComment>,
+    pub span: Span,
+}
+
+impl Constant {
+    /// Returns the value of this constant.
+    pub fn value(&self) -> i128 {
+        self.value.value
+    }
+}
+
+implement_Element_for!(Constant, "constant");
+implement_Attributable_for!(@Scoped Constant);
+implement_Entity_for!(Constant);
+implement_Commentable_for!(Constant);
+
+/// A compile-time integer expression, as it was written in the Slice file.
+///
+/// Since expressions can reference constants which are declared later, or in other files, they aren't evaluated by
+/// the parser. Instead, the parser stores them on the [integers](Integer::expression) they compute, and they're
+/// evaluated once every file has been parsed.
+#[derive(Clone, Debug)]
+pub enum ConstantExpression {
+    Literal(Integer<i128>),
+    Reference(Identifier),
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<ConstantExpression>,
+        span: Span,
+    },
+    Binary {
+        lhs: Box<ConstantExpression>,
+        operator: BinaryOperator,
+        rhs: Box<ConstantExpression>,
+        span: Span,
+    },
+}
+
+impl ConstantExpression {
+    pub fn unary(operator: UnaryOperator, operand: ConstantExpression, span: Span) -> Self {
+        let operand = Box::new(operand);
+        ConstantExpression::Unary {
+            operator,
+            operand,
+            span,
+        }
+    }
+
+    pub fn binary(lhs: ConstantExpression, operator: BinaryOperator, rhs: ConstantExpression, span: Span) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        ConstantExpression::Binary {
+            lhs,
+            operator,
+            rhs,
+            span,
+        }
+    }
+
+    /// Returns the span of this expression.
+    pub fn span(&self) -> &Span {
+        match self {
+            Self::Literal(integer) => &integer.span,
+            Self::Reference(identifier) => &identifier.span,
+            Self::Unary { span, .. } | Self::Binary { span, .. } => span,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum UnaryOperator {
+    Negate,
+    BitwiseNot,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BinaryOperator {
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    ShiftLeft,
+    ShiftRight,
+    Add,
+    Subtract,
+    Multiply,
+}
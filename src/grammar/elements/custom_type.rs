@@ -28,6 +28,10 @@ impl Type for CustomType {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        false
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         Some(TagFormat::FSize)
     }
@@ -38,6 +42,6 @@ impl Type for CustomType {
 }
 
 implement_Element_for!(CustomType, "custom type");
-implement_Attributable_for!(CustomType);
+implement_Attributable_for!(@Scoped CustomType);
 implement_Entity_for!(CustomType);
 implement_Commentable_for!(CustomType);
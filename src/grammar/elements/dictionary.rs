@@ -26,6 +26,11 @@ impl Type for Dictionary {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        // It is disallowed for key types to use classes, so we only need to check the value type.
+        self.value_type.uses_classes()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         if self.key_type.fixed_wire_size().is_some() && self.value_type.fixed_wire_size().is_some() {
             Some(TagFormat::VSize)
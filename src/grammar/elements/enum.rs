@@ -1,5 +1,6 @@
 // Copyright (c) ZeroC, Inc.
 
+use super::super::attributes::Flags;
 use super::super::*;
 use crate::slice_file::Span;
 use crate::supported_encodings::SupportedEncodings;
@@ -17,6 +18,7 @@ pub struct Enum {
     pub comment: Option<DocComment>,
     pub span: Span,
     pub(crate) supported_encodings: Option<SupportedEncodings>,
+    pub(crate) uses_classes: Option<bool>,
 }
 
 impl Enum {
@@ -24,6 +26,11 @@ impl Enum {
         self.contents()
     }
 
+    /// Returns true if this enum has the `flags` attribute, meaning its enumerators represent bit flags.
+    pub fn is_flags(&self) -> bool {
+        self.has_attribute::<Flags>()
+    }
+
     pub fn get_min_max_values(&self) -> Option<(i128, i128)> {
         let values = self.enumerators.iter().map(|enumerator| enumerator.borrow().value());
 
@@ -50,6 +57,10 @@ impl Type for Enum {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        self.uses_classes.unwrap()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         self.underlying.as_ref().map_or(
             Some(TagFormat::Size),              // Default value if `underlying` == None
@@ -63,7 +74,7 @@ impl Type for Enum {
 }
 
 implement_Element_for!(Enum, "enum");
-implement_Attributable_for!(Enum);
+implement_Attributable_for!(@Scoped Enum);
 implement_Entity_for!(Enum);
 implement_Commentable_for!(Enum);
 implement_Container_for!(Enum, Enumerator, enumerators);
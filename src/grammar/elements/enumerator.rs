@@ -21,6 +21,30 @@ impl Enumerator {
         match &self.value {
             EnumeratorValue::Implicit(value) => *value,
             EnumeratorValue::Explicit(integer) => integer.value,
+            EnumeratorValue::Alias { target, .. } => target.borrow().value(),
+        }
+    }
+
+    /// Returns true if this enumerator is an alias for another enumerator (ie: it was declared like `B = A`).
+    pub fn is_alias(&self) -> bool {
+        matches!(self.value, EnumeratorValue::Alias { .. })
+    }
+
+    /// If this enumerator is an alias, this returns the enumerator that it aliases, otherwise this returns `None`.
+    pub fn aliased_enumerator(&self) -> Option<&Enumerator> {
+        match &self.value {
+            EnumeratorValue::Alias { target, .. } => Some(target.borrow()),
+            _ => None,
+        }
+    }
+
+    /// Returns the span of this enumerator's value if it was explicitly set,
+    /// otherwise returns the span of the enumerator itself.
+    pub fn value_span(&self) -> &Span {
+        match &self.value {
+            EnumeratorValue::Implicit(_) => &self.span,
+            EnumeratorValue::Explicit(integer) => &integer.span,
+            EnumeratorValue::Alias { identifier, .. } => &identifier.span,
         }
     }
 
@@ -36,6 +60,13 @@ impl Enumerator {
 pub enum EnumeratorValue {
     Implicit(i128),
     Explicit(Integer<i128>),
+    Alias {
+        /// The identifier of the aliased enumerator, as it was written in the Slice file.
+        identifier: Identifier,
+        /// A pointer to the aliased enumerator. Aliased enumerators are always in the same enum, but can be declared
+        /// before or after their aliases.
+        target: WeakPtr<Enumerator>,
+    },
 }
 
 impl Container<Field> for Enumerator {
@@ -46,6 +77,6 @@ impl Container<Field> for Enumerator {
 
 implement_Element_for!(Enumerator, "enumerator");
 implement_Attributable_for!(@Contained Enumerator);
-implement_Entity_for!(Enumerator);
+implement_Entity_for!(@Contained Enumerator);
 implement_Commentable_for!(Enumerator);
 implement_Contained_for!(Enumerator, Enum);
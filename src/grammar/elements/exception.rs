@@ -15,6 +15,7 @@ pub struct Exception {
     pub comment: Option<DocComment>,
     pub span: Span,
     pub(crate) supported_encodings: Option<SupportedEncodings>,
+    pub(crate) uses_classes: Option<bool>,
 }
 
 impl Exception {
@@ -44,10 +45,16 @@ impl Exception {
     pub fn supported_encodings(&self) -> SupportedEncodings {
         self.supported_encodings.clone().unwrap()
     }
+
+    /// Returns true if any of this exception's fields (including inherited fields) use classes.
+    /// See [Type::uses_classes].
+    pub fn uses_classes(&self) -> bool {
+        self.uses_classes.unwrap()
+    }
 }
 
 implement_Element_for!(Exception, "exception");
-implement_Attributable_for!(Exception);
+implement_Attributable_for!(@Scoped Exception);
 implement_Entity_for!(Exception);
 implement_Commentable_for!(Exception);
 implement_Container_for!(Exception, Field, fields);
@@ -9,6 +9,7 @@ pub struct Field {
     pub identifier: Identifier,
     pub data_type: TypeRef,
     pub tag: Option<Integer<u32>>,
+    pub(crate) tag_format: Option<TagFormat>,
     pub parent: WeakPtr<dyn Container<Field>>,
     pub scope: Scope,
     pub attributes: Vec<WeakPtr<Attribute>>,
@@ -18,7 +19,7 @@ pub struct Field {
 
 implement_Element_for!(Field, "field");
 implement_Attributable_for!(@Contained Field);
-implement_Entity_for!(Field);
+implement_Entity_for!(@Contained Field);
 implement_Commentable_for!(Field);
 implement_Contained_for!(Field, dyn Container<Field> + 'static);
 implement_Member_for!(Field);
@@ -4,11 +4,25 @@ use super::super::*;
 use crate::slice_file::Span;
 use std::fmt::Debug;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Integer<T: Debug> {
     pub value: T,
+    /// The literal this integer was written as, if it was written as a single (possibly negated) integer literal.
+    /// This is `None` for integers that were computed from larger constant expressions; Ex: `1 << 4`.
+    pub literal: Option<IntegerLiteral>,
+    /// The constant expression this integer's value is computed from, or `None` if this integer is itself part of a
+    /// larger expression (see [ConstantExpression::Literal]). These expressions are evaluated by the constant patcher
+    /// once parsing is complete; until then, `value` holds a placeholder.
+    pub expression: Option<Box<ConstantExpression>>,
     pub span: Span,
 }
 
+impl<T: Debug> Integer<T> {
+    /// Returns the original source text of this integer, if it was written as a single integer literal.
+    pub fn lexeme(&self) -> Option<&str> {
+        self.literal.as_ref().map(|literal| literal.lexeme.as_str())
+    }
+}
+
 implement_Element_for!(Integer<T>, "integer", Debug);
 implement_Symbol_for!(Integer<T>, Debug);
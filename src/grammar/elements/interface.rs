@@ -51,15 +51,53 @@ impl Interface {
         self.bases.iter().map(TypeRef::definition).collect()
     }
 
+    /// Returns all the interfaces this interface inherits from, directly or indirectly, in [linearized](Self::linearization)
+    /// order. Interfaces inherited through multiple paths (diamond inheritance) are only included once.
     pub fn all_base_interfaces(&self) -> Vec<&Interface> {
-        let mut all_bases = self.base_interfaces();
-        all_bases.extend(self.bases.iter().flat_map(|type_ref| type_ref.all_base_interfaces()));
+        let mut linearization = self.linearization();
+        linearization.remove(0);
+        linearization
+    }
 
-        // Filter duplicates created by diamond inheritance in-place.
-        let mut seen_identifiers = std::collections::HashSet::new();
-        all_bases.retain(|base| seen_identifiers.insert(base.parser_scoped_identifier()));
+    /// Returns the linearization of this interface: the interface itself, followed by all of its bases, ordered with
+    /// the C3 algorithm (also known as the method resolution order).
+    ///
+    /// This order is deterministic, and guarantees that each interface comes before all of its bases, and that the
+    /// direct bases of each interface are in the order they were listed. Backends should use this order when they need
+    /// to process an interface's bases (ex: when generating dispatch tables), so that all backends agree on it.
+    ///
+    /// Some hierarchies have no such order (ex: if `C : A, B` and `D : B, A` are both bases of the same interface).
+    /// For these, we fall back to taking the bases in the order they're first encountered, so the result is still
+    /// deterministic, and each base is still only included once.
+    pub fn linearization(&self) -> Vec<&Interface> {
+        // The sequences to merge are the linearizations of each direct base, followed by the direct bases themselves.
+        let mut sequences = self
+            .base_interfaces()
+            .into_iter()
+            .map(Interface::linearization)
+            .collect::<Vec<_>>();
+        sequences.push(self.base_interfaces());
+
+        let mut linearization = vec![self];
+        loop {
+            sequences.retain(|sequence| !sequence.is_empty());
+            let Some(first_sequence) = sequences.first() else { break };
+
+            // Take the first head which doesn't appear in the tail of any sequence. If there isn't one, the hierarchy
+            // is inconsistent, and we just take the first head, removing it from every sequence to prevent duplicates.
+            let is_in_a_tail = |candidate: &Interface| {
+                let mut tails = sequences.iter().flat_map(|sequence| &sequence[1..]);
+                tails.any(|other| std::ptr::eq(*other, candidate))
+            };
+            let mut heads = sequences.iter().map(|sequence| sequence[0]);
+            let next = heads.find(|head| !is_in_a_tail(head)).unwrap_or(first_sequence[0]);
 
-        all_bases
+            linearization.push(next);
+            for sequence in &mut sequences {
+                sequence.retain(|other| !std::ptr::eq(*other, next));
+            }
+        }
+        linearization
     }
 
     // This intentionally shadows the trait method of the same name on `Type`.
@@ -69,7 +107,7 @@ impl Interface {
 }
 
 implement_Element_for!(Interface, "interface");
-implement_Attributable_for!(Interface);
+implement_Attributable_for!(@Scoped Interface);
 implement_Entity_for!(Interface);
 implement_Commentable_for!(Interface);
 implement_Container_for!(Interface, Operation, operations);
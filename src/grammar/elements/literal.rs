@@ -0,0 +1,135 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::super::*;
+use crate::slice_file::Span;
+use std::fmt;
+
+/// A literal value, exactly as it was written in a Slice file.
+///
+/// Each kind of literal stores both its parsed value, and its original lexeme (the literal's source text).
+/// This allows tools like formatters and doc generators to reprint literals as they were written (Ex: `0xFF`),
+/// without losing any information by reprinting the parsed value instead (Ex: `255`).
+#[derive(Clone, Debug)]
+pub enum Literal {
+    Integer(IntegerLiteral),
+    Float(FloatLiteral),
+    Bool(BoolLiteral),
+    String(StringLiteral),
+}
+
+impl Literal {
+    /// Returns the original source text of this literal.
+    pub fn lexeme(&self) -> &str {
+        match self {
+            Self::Integer(literal) => &literal.lexeme,
+            Self::Float(literal) => &literal.lexeme,
+            Self::Bool(literal) => &literal.lexeme,
+            Self::String(literal) => &literal.lexeme,
+        }
+    }
+}
+
+impl Element for Literal {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Integer(literal) => literal.kind(),
+            Self::Float(literal) => literal.kind(),
+            Self::Bool(literal) => literal.kind(),
+            Self::String(literal) => literal.kind(),
+        }
+    }
+}
+
+impl Symbol for Literal {
+    fn span(&self) -> &Span {
+        match self {
+            Self::Integer(literal) => literal.span(),
+            Self::Float(literal) => literal.span(),
+            Self::Bool(literal) => literal.span(),
+            Self::String(literal) => literal.span(),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    /// Writes the original lexeme of this literal to the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.lexeme())
+    }
+}
+
+/// The base that an integer literal was written in, as determined by its prefix.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Radix {
+    /// Binary literals are prefixed with "0b".
+    Binary,
+
+    /// Decimal literals have no prefix.
+    #[default]
+    Decimal,
+
+    /// Hexadecimal literals are prefixed with "0x".
+    Hexadecimal,
+}
+
+impl Radix {
+    /// Returns the numeric base of this radix; Ex: 16 for [Radix::Hexadecimal].
+    pub fn base(&self) -> u32 {
+        match self {
+            Self::Binary => 2,
+            Self::Decimal => 10,
+            Self::Hexadecimal => 16,
+        }
+    }
+
+    /// Returns the prefix that literals in this radix must start with, or an empty string for decimal literals.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::Binary => "0b",
+            Self::Decimal => "",
+            Self::Hexadecimal => "0x",
+        }
+    }
+}
+
+/// An integer literal, possibly negated; Ex: `42`, `-0b101`, or `0xFF_FF`.
+#[derive(Clone, Debug)]
+pub struct IntegerLiteral {
+    pub value: i128,
+    pub radix: Radix,
+    pub lexeme: String,
+    pub span: Span,
+}
+
+/// A floating point literal; Ex: `3.14`.
+#[derive(Clone, Debug)]
+pub struct FloatLiteral {
+    pub value: f64,
+    pub lexeme: String,
+    pub span: Span,
+}
+
+/// A boolean literal; either `true` or `false`.
+#[derive(Clone, Debug)]
+pub struct BoolLiteral {
+    pub value: bool,
+    pub lexeme: String,
+    pub span: Span,
+}
+
+/// A string literal. Its value has had any escape sequences resolved, but its lexeme is left untouched (with quotes).
+#[derive(Clone, Debug)]
+pub struct StringLiteral {
+    pub value: String,
+    pub lexeme: String,
+    pub span: Span,
+}
+
+implement_Element_for!(IntegerLiteral, "integer literal");
+implement_Symbol_for!(IntegerLiteral);
+implement_Element_for!(FloatLiteral, "float literal");
+implement_Symbol_for!(FloatLiteral);
+implement_Element_for!(BoolLiteral, "bool literal");
+implement_Symbol_for!(BoolLiteral);
+implement_Element_for!(StringLiteral, "string literal");
+implement_Symbol_for!(StringLiteral);
@@ -3,6 +3,7 @@
 mod attribute;
 mod class;
 mod compilation_mode;
+mod constant;
 mod custom_type;
 mod dictionary;
 mod r#enum;
@@ -12,7 +13,9 @@ mod field;
 mod identifier;
 mod integer;
 mod interface;
+mod literal;
 mod module;
+mod module_alias;
 mod operation;
 mod parameter;
 mod primitive;
@@ -21,11 +24,13 @@ mod sequence;
 mod r#struct;
 mod type_alias;
 mod type_ref;
+mod using_directive;
 
 // Re-export the grammar elements directly into this module so consumers don't need to think about submodule structure).
 pub use self::attribute::*;
 pub use self::class::*;
 pub use self::compilation_mode::*;
+pub use self::constant::*;
 pub use self::custom_type::*;
 pub use self::dictionary::*;
 pub use self::enumerator::*;
@@ -34,7 +39,9 @@ pub use self::field::*;
 pub use self::identifier::*;
 pub use self::integer::*;
 pub use self::interface::*;
+pub use self::literal::*;
 pub use self::module::*;
+pub use self::module_alias::*;
 pub use self::operation::*;
 pub use self::parameter::*;
 pub use self::primitive::*;
@@ -44,3 +51,4 @@ pub use self::result::*;
 pub use self::sequence::*;
 pub use self::type_alias::*;
 pub use self::type_ref::*;
+pub use self::using_directive::*;
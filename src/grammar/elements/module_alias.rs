@@ -0,0 +1,47 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::super::*;
+use crate::slice_file::Span;
+
+/// An alias for a module, declared with `module <alias> = <target>` after a file's module declaration.
+/// Unlike a [UsingDirective], which only applies to the file it's declared in, a module alias is defined in the module
+/// it's declared in, so it can be used from any file, just like the module it aliases. For example, after declaring
+/// `module Short = Very::Long::Nested` in module `Test`, `Test::Short::S` resolves to `::Very::Long::Nested::S`.
+#[derive(Debug)]
+pub struct ModuleAlias {
+    pub alias: Identifier,
+    pub target: Identifier,
+    /// The fully scoped identifier of the module that this alias is declared in.
+    pub scope: String,
+    pub span: Span,
+}
+
+impl ModuleAlias {
+    /// Returns the fully scoped identifier of this alias, without a leading '::'.
+    pub fn scoped_identifier(&self) -> String {
+        match self.scope.as_str() {
+            "" => self.alias.value.clone(),
+            scope => format!("{scope}::{}", self.alias.value),
+        }
+    }
+
+    /// Returns the fully qualified identifier of the module that this alias stands for, without a leading '::'.
+    pub fn target_identifier(&self) -> &str {
+        self.target.value.strip_prefix("::").unwrap_or(&self.target.value)
+    }
+
+    /// If the provided fully scoped identifier (without a leading '::') starts with this alias' scoped identifier,
+    /// returns the identifier with the alias replaced by the target (as a globally scoped identifier).
+    /// Otherwise returns `None`.
+    pub fn expand(&self, scoped_identifier: &str) -> Option<String> {
+        let rest = scoped_identifier.strip_prefix(&self.scoped_identifier())?;
+        if rest.starts_with("::") {
+            Some(format!("::{}{rest}", self.target_identifier()))
+        } else {
+            None
+        }
+    }
+}
+
+implement_Element_for!(ModuleAlias, "module alias");
+implement_Symbol_for!(ModuleAlias);
@@ -1,6 +1,6 @@
 // Copyright (c) ZeroC, Inc.
 
-use super::super::attributes::{Compress, SlicedFormat};
+use super::super::attributes::{Compress, Context, ContextEntry, Oneway, SlicedFormat};
 use super::super::*;
 use crate::slice_file::Span;
 use crate::utils::ptr_util::WeakPtr;
@@ -11,6 +11,9 @@ pub struct Operation {
     pub parameters: Vec<WeakPtr<Parameter>>,
     pub return_type: Vec<WeakPtr<Parameter>>,
     pub exception_specification: Vec<TypeRef<Exception>>,
+    /// The span of `AnyException` if this operation was declared with `throws AnyException`, otherwise `None`.
+    /// Operations which throw any exception have an empty [exception specification](Self::exception_specification).
+    pub throws_any_exception: Option<Span>,
     pub is_idempotent: bool,
     pub encoding: Encoding,
     pub parent: WeakPtr<Interface>,
@@ -36,6 +39,18 @@ impl Operation {
         parameters.chain(return_members).map(WeakPtr::borrow).collect()
     }
 
+    /// Returns the exceptions this operation can throw.
+    pub fn throws(&self) -> Throws<'_> {
+        if self.throws_any_exception.is_some() {
+            Throws::AnyException
+        } else if self.exception_specification.is_empty() {
+            Throws::Nothing
+        } else {
+            let exceptions = self.exception_specification.iter();
+            Throws::Specific(exceptions.map(TypeRef::definition).collect())
+        }
+    }
+
     pub fn has_non_streamed_parameters(&self) -> bool {
         // Operations can have at most 1 streamed parameter. So, if it has more than 1 parameter
         // there must be non streamed parameters. Otherwise we check if the 1 parameter is
@@ -87,25 +102,61 @@ impl Operation {
             .cloned()
     }
 
+    /// Returns the compress attribute that applies to this operation, if there is one.
+    ///
+    /// If this operation has its own compress attribute, it's returned. Otherwise, we fall back to the compress
+    /// attribute of this operation's interface (if it has one), which acts as a default for all its operations.
+    pub fn compress(&self) -> Option<&Compress> {
+        self.find_attribute::<Compress>(true)
+    }
+
     pub fn compress_arguments(&self) -> bool {
-        self.find_attribute::<Compress>().is_some_and(|a| a.compress_args)
+        self.compress().is_some_and(|a| a.compress_args)
     }
 
     pub fn compress_return(&self) -> bool {
-        self.find_attribute::<Compress>().is_some_and(|a| a.compress_return)
+        self.compress().is_some_and(|a| a.compress_return)
+    }
+
+    /// Returns the context entries declared on this operation with the `context` attribute, in the order they were
+    /// declared. If the operation has no `context` attribute, this returns an empty vector.
+    pub fn context_entries(&self) -> Vec<&ContextEntry> {
+        self.find_attribute::<Context>(false)
+            .map_or(Vec::new(), |context| context.entries.iter().collect())
+    }
+
+    /// Returns true if this operation is marked with the `oneway` attribute, meaning its invocations don't wait for
+    /// (or receive) a response. Operations are also oneway if their interface is marked with the attribute.
+    pub fn is_oneway(&self) -> bool {
+        self.find_attribute::<Oneway>(true).is_some()
     }
 
     pub fn slice_classes_in_arguments(&self) -> bool {
-        self.find_attribute::<SlicedFormat>().is_some_and(|a| a.sliced_args)
+        self.find_attribute::<SlicedFormat>(false)
+            .is_some_and(|a| a.sliced_args)
     }
 
     pub fn slice_classes_in_return(&self) -> bool {
-        self.find_attribute::<SlicedFormat>().is_some_and(|a| a.sliced_return)
+        self.find_attribute::<SlicedFormat>(false)
+            .is_some_and(|a| a.sliced_return)
     }
 }
 
+/// The exceptions an operation can throw. See [Operation::throws].
+#[derive(Debug)]
+pub enum Throws<'a> {
+    /// The operation doesn't have a throws clause, so it can't throw any exceptions.
+    Nothing,
+
+    /// The operation can throw the specified exceptions, along with any exceptions derived from them.
+    Specific(Vec<&'a Exception>),
+
+    /// The operation was declared with `throws AnyException`, so it can throw any exception.
+    AnyException,
+}
+
 implement_Element_for!(Operation, "operation");
 implement_Attributable_for!(@Contained Operation);
-implement_Entity_for!(Operation);
+implement_Entity_for!(@Contained Operation);
 implement_Commentable_for!(Operation);
 implement_Contained_for!(Operation, Interface);
@@ -1,5 +1,6 @@
 // Copyright (c) ZeroC, Inc.
 
+use super::super::attributes::SlicedFormat;
 use super::super::*;
 use crate::slice_file::Span;
 use crate::utils::ptr_util::WeakPtr;
@@ -9,15 +10,62 @@ pub struct Parameter {
     pub identifier: Identifier,
     pub data_type: TypeRef,
     pub tag: Option<Integer<u32>>,
+    pub(crate) tag_format: Option<TagFormat>,
     pub is_streamed: bool,
+
+    /// True if this is an operation's nameless return type (ex: `op() -> string`), which the parser gives a generated
+    /// identifier that spans the entire return type.
+    pub is_synthesized: bool,
     pub parent: WeakPtr<Operation>,
     pub scope: Scope,
     pub attributes: Vec<WeakPtr<Attribute>>,
     pub span: Span,
 }
 
+impl Parameter {
+    /// Returns true if this is one of its operation's return members, and false if it's one of its parameters.
+    pub fn is_return_member(&self) -> bool {
+        let return_members = self.parent().return_members();
+        return_members.into_iter().any(|member| std::ptr::eq(member, self))
+    }
+
+    /// Returns the format that classes in this parameter are encoded with.
+    /// This is [ClassFormat::Sliced] if the operation's `slicedFormat` attribute applies to this parameter (`Args` for
+    /// parameters, and `Return` for return members), and the provided default otherwise.
+    pub fn class_format(&self, default: ClassFormat) -> ClassFormat {
+        let sliced_format = self.parent().find_attribute::<SlicedFormat>(false);
+        let is_sliced = match self.is_return_member() {
+            true => sliced_format.is_some_and(|attribute| attribute.sliced_return),
+            false => sliced_format.is_some_and(|attribute| attribute.sliced_args),
+        };
+        if is_sliced {
+            ClassFormat::Sliced
+        } else {
+            default
+        }
+    }
+}
+
 implement_Element_for!(Parameter, "parameter");
 implement_Attributable_for!(@Contained Parameter);
-implement_Entity_for!(Parameter);
+implement_Entity_for!(@Contained Parameter);
 implement_Contained_for!(Parameter, Operation);
-implement_Member_for!(Parameter);
+
+impl Member for Parameter {
+    fn data_type(&self) -> &TypeRef {
+        &self.data_type
+    }
+
+    fn raw_tag(&self) -> Option<&Integer<u32>> {
+        self.tag.as_ref()
+    }
+
+    fn tag_format(&self) -> Option<TagFormat> {
+        self.tag_format.clone()
+    }
+
+    fn data_type_attributes(&self) -> Vec<&Attribute> {
+        self.data_type
+            .merge_attributes([self.attributes(), self.parent().attributes()])
+    }
+}
@@ -21,10 +21,40 @@ pub enum Primitive {
     Float32,
     Float64,
     String,
+    Uuid,
+    Timestamp,
+    Duration,
     AnyClass,
 }
 
 impl Primitive {
+    /// Returns the primitive type with the provided Slice keyword (ex: `float32`), or `None` if there isn't one.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "bool" => Some(Self::Bool),
+            "int8" => Some(Self::Int8),
+            "uint8" => Some(Self::UInt8),
+            "int16" => Some(Self::Int16),
+            "uint16" => Some(Self::UInt16),
+            "int32" => Some(Self::Int32),
+            "uint32" => Some(Self::UInt32),
+            "varint32" => Some(Self::VarInt32),
+            "varuint32" => Some(Self::VarUInt32),
+            "int64" => Some(Self::Int64),
+            "uint64" => Some(Self::UInt64),
+            "varint62" => Some(Self::VarInt62),
+            "varuint62" => Some(Self::VarUInt62),
+            "float32" => Some(Self::Float32),
+            "float64" => Some(Self::Float64),
+            "string" => Some(Self::String),
+            "uuid" => Some(Self::Uuid),
+            "timestamp" => Some(Self::Timestamp),
+            "duration" => Some(Self::Duration),
+            "AnyClass" => Some(Self::AnyClass),
+            _ => None,
+        }
+    }
+
     pub fn is_numeric(&self) -> bool {
         matches!(
             self,
@@ -116,6 +146,9 @@ impl Type for Primitive {
             Self::Float32 => Some(4),
             Self::Float64 => Some(8),
             Self::String => None,
+            Self::Uuid => Some(16),
+            Self::Timestamp => Some(8),
+            Self::Duration => Some(8),
             Self::AnyClass => None,
         }
     }
@@ -124,6 +157,10 @@ impl Type for Primitive {
         matches!(self, Self::AnyClass)
     }
 
+    fn uses_classes(&self) -> bool {
+        self.is_class_type()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         match self {
             Self::Bool => Some(TagFormat::F1),
@@ -142,6 +179,9 @@ impl Type for Primitive {
             Self::Float32 => Some(TagFormat::F4),
             Self::Float64 => Some(TagFormat::F8),
             Self::String => Some(TagFormat::OptimizedVSize),
+            Self::Uuid => None,
+            Self::Timestamp => None,
+            Self::Duration => None,
             Self::AnyClass => Some(TagFormat::Class),
         }
     }
@@ -164,6 +204,9 @@ impl Type for Primitive {
             Self::Float32 => vec![Encoding::Slice1, Encoding::Slice2],
             Self::Float64 => vec![Encoding::Slice1, Encoding::Slice2],
             Self::String => vec![Encoding::Slice1, Encoding::Slice2],
+            Self::Uuid => vec![Encoding::Slice2],
+            Self::Timestamp => vec![Encoding::Slice2],
+            Self::Duration => vec![Encoding::Slice2],
             Self::AnyClass => vec![Encoding::Slice1],
         })
     }
@@ -188,6 +231,9 @@ impl Element for Primitive {
             Self::Float32 => "float32",
             Self::Float64 => "float64",
             Self::String => "string",
+            Self::Uuid => "uuid",
+            Self::Timestamp => "timestamp",
+            Self::Duration => "duration",
             Self::AnyClass => "AnyClass",
         }
     }
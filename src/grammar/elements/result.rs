@@ -26,6 +26,11 @@ impl Type for ResultType {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        // 'Result' is Slice2 only, and classes are Slice1 only.
+        false
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         unreachable!("tag format was called on a Slice2 only type!")
     }
@@ -44,6 +44,10 @@ impl Type for Sequence {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        self.element_type.uses_classes()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         match self.element_type.fixed_wire_size() {
             Some(1) => Some(TagFormat::OptimizedVSize),
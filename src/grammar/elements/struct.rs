@@ -10,17 +10,35 @@ pub struct Struct {
     pub identifier: Identifier,
     pub fields: Vec<WeakPtr<Field>>,
     pub is_compact: bool,
+
+    /// The type parameters of this struct, if it's generic (ex: `T` in `struct Box<T>`).
+    ///
+    /// Generic structs are templates: each distinct set of type arguments they're used with is instantiated into an
+    /// ordinary (non-generic) struct while patching, whose type parameters are substituted with those arguments.
+    /// Within the generic struct itself, its type parameters are opaque custom types.
+    pub type_parameters: Vec<Identifier>,
+
+    /// True if this struct was synthesized by the parser from a tuple type (ex: `pos: (x: int32, y: int32)`), instead
+    /// of being defined in Slice. Its identifier is generated, and its span covers the entire tuple.
+    pub is_synthesized: bool,
     pub scope: Scope,
     pub attributes: Vec<WeakPtr<Attribute>>,
     pub comment: Option<DocComment>,
     pub span: Span,
     pub(crate) supported_encodings: Option<SupportedEncodings>,
+    pub(crate) uses_classes: Option<bool>,
 }
 
 impl Struct {
     pub fn fields(&self) -> Vec<&Field> {
         self.contents()
     }
+
+    /// Returns true if this struct has type parameters. Code shouldn't be generated for generic structs, only for
+    /// their instantiations.
+    pub fn is_generic(&self) -> bool {
+        !self.type_parameters.is_empty()
+    }
 }
 
 impl Type for Struct {
@@ -44,6 +62,10 @@ impl Type for Struct {
         false
     }
 
+    fn uses_classes(&self) -> bool {
+        self.uses_classes.unwrap()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         if self.fixed_wire_size().is_some() {
             Some(TagFormat::VSize)
@@ -58,7 +80,7 @@ impl Type for Struct {
 }
 
 implement_Element_for!(Struct, "struct");
-implement_Attributable_for!(Struct);
+implement_Attributable_for!(@Scoped Struct);
 implement_Entity_for!(Struct);
 implement_Commentable_for!(Struct);
 implement_Container_for!(Struct, Field, fields);
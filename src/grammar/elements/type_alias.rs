@@ -35,6 +35,10 @@ impl Type for TypeAlias {
         self.underlying.is_class_type()
     }
 
+    fn uses_classes(&self) -> bool {
+        self.underlying.uses_classes()
+    }
+
     fn tag_format(&self) -> Option<TagFormat> {
         self.underlying.tag_format()
     }
@@ -45,6 +49,6 @@ impl Type for TypeAlias {
 }
 
 implement_Element_for!(TypeAlias, "type alias");
-implement_Attributable_for!(TypeAlias);
+implement_Attributable_for!(@Scoped TypeAlias);
 implement_Entity_for!(TypeAlias);
 implement_Commentable_for!(TypeAlias);
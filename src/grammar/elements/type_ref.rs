@@ -9,7 +9,16 @@ pub struct TypeRef<T: Element + ?Sized = dyn Type> {
     pub definition: TypeRefDefinition<T>,
     pub is_optional: bool,
     pub scope: Scope,
+    /// The attributes that were applied directly to this type reference.
     pub attributes: Vec<WeakPtr<Attribute>>,
+    /// The type-metadata attributes inherited from any type aliases this type reference was resolved through,
+    /// ordered from the nearest alias to the furthest.
+    pub inherited_attributes: Vec<WeakPtr<Attribute>>,
+    /// The type aliases this type reference was resolved through, ordered from the nearest alias to the furthest.
+    pub type_aliases: Vec<WeakPtr<TypeAlias>>,
+    /// The type arguments this type reference was written with, if it references a generic struct
+    /// (ex: `int32` in `Box<int32>`). These are only used for instantiating that struct, and are never patched.
+    pub type_arguments: Vec<TypeRef>,
     pub span: Span,
 }
 
@@ -21,12 +30,64 @@ impl<T: Element + ?Sized> TypeRef<T> {
         }
     }
 
-    pub(crate) fn patch(&mut self, ptr: WeakPtr<T>, additional_attributes: Vec<WeakPtr<Attribute>>) {
+    pub(crate) fn patch(
+        &mut self,
+        ptr: WeakPtr<T>,
+        inherited_attributes: Vec<WeakPtr<Attribute>>,
+        type_aliases: Vec<WeakPtr<TypeAlias>>,
+    ) {
         // Assert that the typeref hasn't already been patched.
         debug_assert!(matches!(&self.definition, TypeRefDefinition::Unpatched(_)));
 
         self.definition = TypeRefDefinition::Patched(ptr);
-        self.attributes.extend(additional_attributes);
+        self.inherited_attributes.extend(inherited_attributes);
+        self.type_aliases.extend(type_aliases);
+    }
+
+    /// Returns true if this type reference is marked with the [boxed](attributes::Boxed) attribute.
+    pub fn is_boxed(&self) -> bool {
+        self.has_attribute::<attributes::Boxed>()
+    }
+
+    /// Returns the expected size of this type reference, if it's marked with the [size](attributes::Size) attribute.
+    pub fn size_bounds(&self) -> Option<&attributes::Size> {
+        self.find_attribute::<attributes::Size>(false)
+    }
+
+    /// Returns the type aliases this type reference was resolved through, ordered from the nearest alias to the
+    /// furthest. If this type reference didn't use a type alias, this is empty.
+    pub fn type_aliases(&self) -> Vec<&TypeAlias> {
+        self.type_aliases.iter().map(WeakPtr::borrow).collect()
+    }
+
+    /// Merges the attributes that apply to this type reference with the type-metadata attributes of the elements
+    /// enclosing it, like the member it's the type of, or the operation that member belongs to.
+    ///
+    /// Attributes are returned in order of precedence:
+    /// 1. attributes applied directly to this type reference
+    /// 2. type-metadata attributes of each of the `enclosing` attribute lists, in the order they were provided
+    /// 3. type-metadata attributes inherited from type aliases, from the nearest alias to the furthest
+    ///
+    /// If an attribute isn't repeatable, only its highest precedence occurrence is kept.
+    pub fn merge_attributes<'a>(
+        &'a self,
+        enclosing: impl IntoIterator<Item = Vec<&'a Attribute>>,
+    ) -> Vec<&'a Attribute> {
+        let applied = self.attributes.iter().map(WeakPtr::borrow);
+        let forwarded = enclosing
+            .into_iter()
+            .flatten()
+            .chain(self.inherited_attributes.iter().map(WeakPtr::borrow))
+            .filter(|attribute| attribute.kind.is_type_metadata());
+
+        let mut merged: Vec<&Attribute> = Vec::new();
+        for attribute in applied.chain(forwarded) {
+            let kind = &attribute.kind;
+            if kind.is_repeatable() || !merged.iter().any(|other| other.kind.directive() == kind.directive()) {
+                merged.push(attribute);
+            }
+        }
+        merged
     }
 
     pub(crate) fn downcast<U: Element + 'static>(&self) -> Result<TypeRef<U>, ()> {
@@ -43,6 +104,9 @@ impl<T: Element + ?Sized> TypeRef<T> {
             is_optional: self.is_optional,
             scope: self.scope.clone(),
             attributes: self.attributes.clone(),
+            inherited_attributes: self.inherited_attributes.clone(),
+            type_aliases: self.type_aliases.clone(),
+            type_arguments: self.type_arguments.clone(),
             span: self.span.clone(),
         })
     }
@@ -59,8 +123,9 @@ impl<T: Type + ?Sized> TypeRef<T> {
     }
 
     // This intentionally shadows the trait method of the same name on `Type`.
+    // Boxed types are never of fixed size, since they can be used to form cycles.
     pub fn fixed_wire_size(&self) -> Option<u32> {
-        if self.is_optional {
+        if self.is_optional || self.is_boxed() {
             None
         } else {
             T::fixed_wire_size(self)
@@ -68,6 +133,21 @@ impl<T: Type + ?Sized> TypeRef<T> {
     }
 }
 
+impl<T: Element + ?Sized> Clone for TypeRef<T> {
+    fn clone(&self) -> Self {
+        TypeRef {
+            definition: self.definition.clone(),
+            is_optional: self.is_optional,
+            scope: self.scope.clone(),
+            attributes: self.attributes.clone(),
+            inherited_attributes: self.inherited_attributes.clone(),
+            type_aliases: self.type_aliases.clone(),
+            type_arguments: self.type_arguments.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
+
 impl<T: Element + ?Sized> std::ops::Deref for TypeRef<T> {
     type Target = T;
 
@@ -79,10 +159,30 @@ impl<T: Element + ?Sized> std::ops::Deref for TypeRef<T> {
 implement_Element_for!(TypeRef<T>, "type reference", Element + ?Sized);
 implement_Symbol_for!(TypeRef<T>, Element + ?Sized);
 implement_Scoped_Symbol_for!(TypeRef<T>, Element + ?Sized);
-implement_Attributable_for!(TypeRef<T>, Element + ?Sized);
+
+impl<T: Element + ?Sized> Attributable for TypeRef<T> {
+    /// Returns the attributes applied directly to this type reference, followed by those inherited from type aliases.
+    fn attributes(&self) -> Vec<&Attribute> {
+        let attributes = self.attributes.iter().chain(&self.inherited_attributes);
+        attributes.map(WeakPtr::borrow).collect()
+    }
+
+    fn all_attributes(&self) -> Vec<Vec<&Attribute>> {
+        vec![self.attributes()]
+    }
+}
 
 #[derive(Debug)]
 pub enum TypeRefDefinition<T: Element + ?Sized = dyn Type> {
     Patched(WeakPtr<T>),
     Unpatched(Identifier),
 }
+
+impl<T: Element + ?Sized> Clone for TypeRefDefinition<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Patched(ptr) => Self::Patched(ptr.clone()),
+            Self::Unpatched(identifier) => Self::Unpatched(identifier.clone()),
+        }
+    }
+}
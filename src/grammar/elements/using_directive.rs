@@ -0,0 +1,37 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::super::*;
+use crate::slice_file::Span;
+
+/// A file-scoped alias for a module or type, declared with `using <alias> = <target>`, or `using <target>`.
+/// Within the file it's declared in, type references starting with the alias are resolved as if they started with the
+/// (fully qualified) target instead. For example, after `using Baz = Foo::Bar::Baz`, `Baz::S` resolves to
+/// `::Foo::Bar::Baz::S`. When no alias is specified, the last segment of the target is used as the alias.
+#[derive(Debug)]
+pub struct UsingDirective {
+    pub alias: Identifier,
+    pub target: Identifier,
+    pub span: Span,
+}
+
+impl UsingDirective {
+    /// Returns the fully qualified identifier that this directive's alias stands for, without a leading '::'.
+    pub fn target_identifier(&self) -> &str {
+        self.target.value.strip_prefix("::").unwrap_or(&self.target.value)
+    }
+
+    /// If the provided identifier starts with this directive's alias, returns the identifier with the alias replaced
+    /// by the target (as a globally scoped identifier). Otherwise returns `None`.
+    /// Globally scoped identifiers (those starting with '::') are never affected by aliases.
+    pub fn expand(&self, identifier: &str) -> Option<String> {
+        let rest = identifier.strip_prefix(self.alias.value.as_str())?;
+        if rest.is_empty() || rest.starts_with("::") {
+            Some(format!("::{}{rest}", self.target_identifier()))
+        } else {
+            None
+        }
+    }
+}
+
+implement_Element_for!(UsingDirective, "using directive");
+implement_Symbol_for!(UsingDirective);
@@ -1,12 +1,12 @@
 // Copyright (c) ZeroC, Inc.
 
-use super::attributes::AttributeKind;
+use super::attributes::{Allow, AttributeKind, Deprecated, Internal};
 use super::comments::DocComment;
 use super::elements::{Attribute, Identifier, Integer, Module, TypeRef};
-use super::util::{Scope, TagFormat};
-use super::wrappers::{AsEntities, AsTypes};
+use super::util::{Scope, TagFormat, Visibility};
+use super::wrappers::{AsEntities, AsTypes, Entities};
 use crate::slice_file::Span;
-use crate::supported_encodings::SupportedEncodings;
+use crate::supported_encodings::{EncodingRestriction, SupportedEncodings};
 
 pub trait Element: std::fmt::Debug {
     fn kind(&self) -> &'static str;
@@ -34,7 +34,8 @@ pub trait Attributable {
     /// Returns the attributes of the element.
     fn attributes(&self) -> Vec<&Attribute>;
 
-    /// Returns all the attributes of the element and its parents.
+    /// Returns all the attributes of the element and its parents, ordered from the innermost to the outermost.
+    /// The outermost attributes are those of the module the element was declared in (if it has one).
     fn all_attributes(&self) -> Vec<Vec<&Attribute>>;
 }
 
@@ -49,7 +50,11 @@ pub trait AttributeFunctions {
 
     /// Returns the first attribute of the specified type that is applied to this element.
     /// If no attributes of the specified type can be found, this returns `None`.
-    fn find_attribute<T: AttributeKind + 'static>(&self) -> Option<&T>;
+    ///
+    /// If `recurse` is true, and this element doesn't have such an attribute, we also search the element's parents
+    /// (ex: an operation's interface), and then the module it was declared in, returning the innermost one we find.
+    /// This is how attributes like `compress` and `deprecated` are inherited from enclosing scopes.
+    fn find_attribute<T: AttributeKind + 'static>(&self, recurse: bool) -> Option<&T>;
 
     /// Returns all the attributes applied to this element that are of the specified type.
     fn find_attributes<T: AttributeKind + 'static>(&self) -> Vec<&T>;
@@ -58,11 +63,18 @@ pub trait AttributeFunctions {
 // Blanket impl to ensure that everything implementing `Attributable` also gets `AttributeFunctions` for free.
 impl<A: Attributable + ?Sized> AttributeFunctions for A {
     fn has_attribute<T: AttributeKind + 'static>(&self) -> bool {
-        self.find_attribute::<T>().is_some()
+        self.find_attribute::<T>(false).is_some()
     }
 
-    fn find_attribute<T: AttributeKind + 'static>(&self) -> Option<&T> {
-        self.attributes().into_iter().find_map(Attribute::downcast)
+    fn find_attribute<T: AttributeKind + 'static>(&self, recurse: bool) -> Option<&T> {
+        match recurse {
+            true => self
+                .all_attributes()
+                .into_iter()
+                .flatten()
+                .find_map(Attribute::downcast),
+            false => self.attributes().into_iter().find_map(Attribute::downcast),
+        }
     }
 
     fn find_attributes<T: AttributeKind + 'static>(&self) -> Vec<&T> {
@@ -70,7 +82,94 @@ impl<A: Attributable + ?Sized> AttributeFunctions for A {
     }
 }
 
-pub trait Entity: ScopedSymbol + NamedSymbol + Attributable + AsEntities {}
+pub trait Entity: ScopedSymbol + NamedSymbol + Attributable + AsEntities {
+    /// Returns a stable checksum of this entity's definition. See [checksums](crate::checksums) for more information.
+    fn checksum(&self) -> String {
+        crate::checksums::compute_checksum(self)
+    }
+
+    /// Returns the Slice encodings that this entity can be used with.
+    /// This is limited by the compilation mode of the file it's defined in, and by the types and members it uses.
+    ///
+    /// This can only be called after the AST has been patched.
+    fn supported_modes(&self) -> SupportedEncodings {
+        crate::supported_encodings::supported_encodings_of(self)
+    }
+
+    /// Returns an explanation of why this entity can't be used with each of the Slice encodings it doesn't support.
+    /// If this entity supports every encoding, the returned vector is empty.
+    ///
+    /// This can only be called after the AST has been patched.
+    fn explain_supported_modes(&self) -> Vec<EncodingRestriction> {
+        crate::supported_encodings::explain_supported_encodings_of(self)
+    }
+
+    /// Returns true if this entity was synthesized by the parser, instead of being defined in Slice: either a struct
+    /// that a tuple type was desugared into, or an operation's nameless return type. Synthesized entities are given
+    /// generated identifiers, which don't appear in the source code.
+    fn is_synthesized(&self) -> bool {
+        match self.concrete_entity() {
+            Entities::Struct(struct_def) => struct_def.is_synthesized,
+            Entities::Parameter(parameter) => parameter.is_synthesized,
+            _ => false,
+        }
+    }
+
+    /// Returns the module this entity was defined in, or `None` if it wasn't defined in a module.
+    fn enclosing_module(&self) -> Option<&Module> {
+        self.get_raw_scope()
+            .module
+            .as_ref()
+            .map(|module_ptr| module_ptr.borrow())
+    }
+
+    /// Returns the entity that directly contains this entity, like the interface an operation is defined in.
+    /// Returns `None` for entities defined directly in a module.
+    fn parent_entity(&self) -> Option<&dyn Entity> {
+        None
+    }
+
+    /// Returns whether this entity can be used outside the library it's defined in. An entity is internal if it, or
+    /// any entity containing it, has the `[internal]` attribute. See [Visibility] for more information.
+    fn visibility(&self) -> Visibility {
+        let is_internal = |entity: &dyn Entity| entity.has_attribute::<Internal>();
+        if self.has_attribute::<Internal>() || self.ancestors().into_iter().any(is_internal) {
+            Visibility::Internal
+        } else {
+            Visibility::Public
+        }
+    }
+
+    /// Returns the `deprecated` attribute that applies to this entity, if there is one.
+    /// Entities are deprecated if they, or any entity containing them, have the attribute. If multiple do, this
+    /// returns the innermost one.
+    fn deprecation(&self) -> Option<&Deprecated> {
+        self.find_attribute::<Deprecated>(true)
+    }
+
+    /// Returns the identifiers of the lints allowed by `allow` attributes on this entity, any entity containing it, or
+    /// the module it was declared in.
+    /// This doesn't include lints allowed by file level attributes, or by command line options.
+    fn allowed_lints(&self) -> Vec<&str> {
+        let attributes = self.all_attributes().into_iter().flatten();
+        let allows = attributes.filter_map(Attribute::downcast::<Allow>);
+        allows
+            .flat_map(|allow| allow.allowed_lints.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Returns the entities that contain this entity, ordered from the innermost to the outermost.
+    /// For example, the ancestors of a parameter are the operation it belongs to, and the interface containing that.
+    fn ancestors(&self) -> Vec<&dyn Entity> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent_entity();
+        while let Some(entity) = current {
+            ancestors.push(entity);
+            current = entity.parent_entity();
+        }
+        ancestors
+    }
+}
 
 pub trait Container<T: Entity>: Entity {
     fn contents(&self) -> Vec<&T>;
@@ -84,6 +183,12 @@ pub trait Member: Entity {
     fn data_type(&self) -> &TypeRef;
     fn raw_tag(&self) -> Option<&Integer<u32>>;
 
+    /// Returns the format this member's value is encoded with when it's tagged, or `None` if it isn't tagged.
+    ///
+    /// With Slice1, this depends on the member's type, and is also `None` for types which can't be tagged.
+    /// With Slice2, every tagged value is prefixed by its size, so this is always [TagFormat::VSize].
+    fn tag_format(&self) -> Option<TagFormat>;
+
     fn tag(&self) -> Option<u32> {
         self.raw_tag().map(|tag| tag.value)
     }
@@ -91,6 +196,17 @@ pub trait Member: Entity {
     fn is_tagged(&self) -> bool {
         self.raw_tag().is_some()
     }
+
+    /// Returns the attributes that apply to this member's type, merged from its type reference, this member, and (for
+    /// parameters) the operation it belongs to. See [TypeRef::merge_attributes] for the precedence rules.
+    fn data_type_attributes(&self) -> Vec<&Attribute> {
+        self.data_type().merge_attributes([self.attributes()])
+    }
+
+    /// Returns true if this member's type is a class, or transitively contains a class. See [Type::uses_classes].
+    fn uses_classes(&self) -> bool {
+        self.data_type().uses_classes()
+    }
 }
 
 pub trait Commentable: Entity {
@@ -101,6 +217,10 @@ pub trait Type: Element + AsTypes {
     fn type_string(&self) -> String;
     fn fixed_wire_size(&self) -> Option<u32>;
     fn is_class_type(&self) -> bool;
+
+    /// Returns true if this type is a class, or contains a class, either directly or transitively (ex: a struct with
+    /// a field whose type is a sequence of classes). For user-defined types, this is computed once during patching.
+    fn uses_classes(&self) -> bool;
     fn tag_format(&self) -> Option<TagFormat>;
     fn supported_encodings(&self) -> SupportedEncodings;
 }
@@ -184,6 +304,21 @@ macro_rules! implement_Attributable_for {
             }
         }
     };
+    (@Scoped $type:ty) => {
+        impl Attributable for $type {
+            fn attributes(&self) -> Vec<&Attribute> {
+                self.attributes.iter().map(WeakPtr::borrow).collect()
+            }
+
+            fn all_attributes(&self) -> Vec<Vec<&Attribute>> {
+                let mut attributes_list = vec![self.attributes()];
+                if let Some(module_ptr) = &self.scope.module {
+                    attributes_list.push(module_ptr.borrow().attributes());
+                }
+                attributes_list
+            }
+        }
+    };
     (@Contained $type:ty$(, $($bounds:tt)+)?) => {
         impl$(<T: $($bounds)+>)? Attributable for $type {
             fn attributes(&self) -> Vec<&Attribute> {
@@ -217,6 +352,17 @@ macro_rules! implement_Entity_for {
 
         impl Entity for $type {}
     };
+    (@Contained $type:ty) => {
+        implement_Symbol_for!($type);
+        implement_Scoped_Symbol_for!($type);
+        implement_Named_Symbol_for!($type);
+
+        impl Entity for $type {
+            fn parent_entity(&self) -> Option<&dyn Entity> {
+                Some(self.parent().concrete_entity().as_entity())
+            }
+        }
+    };
 }
 
 macro_rules! implement_Container_for {
@@ -249,6 +395,10 @@ macro_rules! implement_Member_for {
             fn raw_tag(&self) -> Option<&Integer<u32>> {
                 self.tag.as_ref()
             }
+
+            fn tag_format(&self) -> Option<TagFormat> {
+                self.tag_format.clone()
+            }
         }
     };
 }
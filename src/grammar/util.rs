@@ -2,6 +2,7 @@
 
 use super::Module;
 use crate::utils::ptr_util::WeakPtr;
+use clap::ValueEnum;
 use std::fmt;
 
 #[derive(Clone, Debug, Default)]
@@ -49,7 +50,7 @@ pub fn get_scoped_identifier(identifier: &str, scope: &str) -> String {
 /// This enum specifies all the encodings supported by IceRPC.
 ///
 /// These encodings identity the format used to convert Slice types to and from byte streams.
-#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Encoding {
     /// Version 1 of the Slice encoding, supported by IceRPC, and compatible with Ice 3.5 or
     /// greater.
@@ -125,3 +126,42 @@ impl fmt::Display for TagFormat {
         }
     }
 }
+
+/// Whether an entity can be used outside the library it's defined in.
+///
+/// Definitions are public by default, and can be made internal with the `[internal]` attribute. Members (like fields
+/// and operations) always have the same visibility as the definition containing them. Public definitions can't expose
+/// internal types in their signatures, so backends can map internal definitions to a language's equivalent (like C#'s
+/// `internal` modifier) without the generated code failing to compile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Internal,
+}
+
+impl Visibility {
+    /// Returns the name of this visibility, as a lowercase keyword (`public` or `internal`).
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Internal => "internal",
+        }
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.keyword())
+    }
+}
+
+/// The formats that classes can be encoded with, when they're used in an operation's arguments or return members.
+/// Classes use the compact format unless the operation has a `slicedFormat` attribute. This only applies to Slice1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassFormat {
+    /// Classes are encoded without any slicing information, so receivers must know every class they're sent.
+    Compact,
+
+    /// Classes are encoded in slices, so receivers can skip the parts of classes they don't know.
+    Sliced,
+}
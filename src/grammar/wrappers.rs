@@ -23,7 +23,7 @@ macro_rules! generate_definition_wrapper {
     };
 }
 
-generate_definition_wrapper!(Struct, Class, Exception, Interface, Enum, CustomType, TypeAlias);
+generate_definition_wrapper!(Struct, Class, Exception, Interface, Enum, CustomType, TypeAlias, Constant);
 
 macro_rules! generate_entities_wrapper {
     ($($variant:ident),*) => {
@@ -32,6 +32,15 @@ macro_rules! generate_entities_wrapper {
             $($variant(&'a $variant),)*
         }
 
+        impl<'a> Entities<'a> {
+            /// Returns the underlying entity as a trait object.
+            pub fn as_entity(&self) -> &'a dyn Entity {
+                match self {
+                    $(Self::$variant(x) => *x,)*
+                }
+            }
+        }
+
         $(
         impl AsEntities for $variant {
             fn concrete_entity(&self) -> Entities<'_> {
@@ -47,12 +56,12 @@ pub trait AsEntities {
 }
 
 generate_entities_wrapper!(
-    Struct, Class, Exception, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias
+    Struct, Class, Exception, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias, Constant
 );
 
 macro_rules! generate_attributables_wrapper {
     ($($variant:ident),*) => {
-        #[derive(Debug)]
+        #[derive(Clone, Copy, Debug)]
         pub enum Attributables<'a> {
             $($variant(&'a $variant),)*
         }
@@ -73,7 +82,7 @@ pub trait AsAttributables {
 
 generate_attributables_wrapper!(
     Module, Struct, Class, Exception, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias,
-    TypeRef, SliceFile
+    Constant, TypeRef, SliceFile
 );
 
 macro_rules! generate_types_wrapper {
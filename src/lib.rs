@@ -1,42 +1,126 @@
 // Copyright (c) ZeroC, Inc.
 
+pub mod api_surface;
 pub mod ast;
+pub mod backend;
+pub mod checksums;
+pub mod codec;
 pub mod compilation_state;
+pub mod completion;
 pub mod diagnostic_emitter;
 pub mod diagnostics;
 pub mod grammar;
+pub mod logging;
+pub mod migration;
+pub mod output_manifest;
+pub mod packages;
+pub mod panic_handler;
+pub mod rendering;
+pub mod response_files;
+pub mod semantic_tokens;
 pub mod slice_file;
 pub mod slice_options;
+pub mod summary;
 pub mod supported_encodings;
+pub mod suppression_report;
+pub mod symbol_tree;
 pub mod test_helpers;
+pub mod test_vectors;
+pub mod timings;
+pub mod type_registry;
+pub mod unresolved_references;
+pub mod upgrade;
 pub mod utils;
 pub mod visitor;
+pub mod well_known_types;
+pub mod workspace;
 
 mod parsers;
 mod patchers;
 mod validators;
 
+#[doc(hidden)]
+pub use parsers::fuzzing;
+pub use parsers::lossless;
+pub use rendering::render_signature;
+
 use compilation_state::CompilationState;
+use diagnostics::{Diagnostic, Diagnostics, Error};
 use slice_file::SliceFile;
 use slice_options::SliceOptions;
 use std::collections::HashSet;
-use utils::file_util;
+use std::time::Instant;
+use utils::file_util::{self, FileCache};
 
 pub fn compile_from_options(
     options: &SliceOptions,
     patcher: unsafe fn(&mut CompilationState),
     validator: fn(&mut CompilationState),
+) -> CompilationState {
+    compile_from_options_using(options, &mut FileCache::default(), patcher, validator)
+}
+
+/// Compiles the Slice files specified by `options` like [compile_from_options], but reads them through the provided
+/// cache. Files which are already cached aren't read from disk again, and their text is shared with the cache.
+pub fn compile_from_options_using(
+    options: &SliceOptions,
+    cache: &mut FileCache,
+    patcher: unsafe fn(&mut CompilationState),
+    validator: fn(&mut CompilationState),
+) -> CompilationState {
+    compile_from_options_with_prelude_using(&[], options, cache, patcher, validator)
+}
+
+/// Compiles the Slice files specified by `options` once, and then runs each of the provided backends over them.
+///
+/// Only the backends selected with `--target` are run, or every backend if no targets were specified.
+/// See [backend::run_backends] for more information.
+pub fn compile_with_backends(options: &SliceOptions, backends: &[&dyn backend::Backend]) -> CompilationState {
+    let mut state = compile_from_options(options, |_| {}, |_| {});
+    backend::run_backends(&mut state, options, backends);
+    state
+}
+
+/// Compiles the Slice files specified by `options`, along with a 'prelude' of Slice definitions.
+///
+/// Each element of `prelude` is a `(name, text)` pair, where `name` is used to identify the fragment in diagnostics.
+/// Prelude fragments are treated like reference files: definitions in them can be referenced by the source files,
+/// but since they aren't marked as source files, no code should be generated for them.
+pub fn compile_with_prelude(
+    prelude: &[(&str, &str)],
+    options: &SliceOptions,
+    patcher: unsafe fn(&mut CompilationState),
+    validator: fn(&mut CompilationState),
+) -> CompilationState {
+    compile_from_options_with_prelude_using(prelude, options, &mut FileCache::default(), patcher, validator)
+}
+
+/// Compiles the Slice files specified by `options` (read through the provided cache), along with any prelude fragments.
+/// This is the shared implementation of [compile_from_options_using] and [compile_with_prelude].
+fn compile_from_options_with_prelude_using(
+    prelude: &[(&str, &str)],
+    options: &SliceOptions,
+    cache: &mut FileCache,
+    patcher: unsafe fn(&mut CompilationState),
+    validator: fn(&mut CompilationState),
 ) -> CompilationState {
     // Create an instance of `CompilationState` for holding all the compiler's state.
     let mut state = CompilationState::create();
 
     // Recursively resolve any Slice files contained in the paths specified by the user.
-    state.files = file_util::resolve_files_from(options, &mut state.diagnostics);
+    let mut file_diagnostics = Diagnostics::new();
+    state.packages = packages::resolve_packages(options, &mut file_diagnostics);
+    state.files = file_util::resolve_files_with_packages(options, &state.packages, cache, &mut file_diagnostics);
+    state.reference_search_path = Some(file_util::reference_search_path(options));
 
-    // If any files were unreadable, return without parsing. Otherwise, parse the files normally.
-    if !state.diagnostics.has_errors() {
-        compile_files(&mut state, options, patcher, validator);
+    // Create a non-source Slice file from each of the prelude fragments.
+    for &(name, text) in prelude {
+        let slice_file = SliceFile::new(name.to_owned(), text.to_owned(), false);
+        state.files.push(slice_file);
     }
+
+    // Compile whichever files could be read. Any files which couldn't be were reported by `resolve_files_from`.
+    compile_with_file_diagnostics(&mut state, file_diagnostics, options, patcher, validator);
     state
 }
 
@@ -63,6 +147,23 @@ pub fn compile_from_strings(
     state
 }
 
+/// Compiles the files in `state`, and then reports any `file_diagnostics` ahead of the diagnostics from compilation.
+///
+/// The file diagnostics are kept separate until compilation finishes, so that an unreadable file doesn't stop the
+/// remaining files from being fully validated. Since they're still reported as errors, no code will be generated.
+fn compile_with_file_diagnostics(
+    state: &mut CompilationState,
+    file_diagnostics: Diagnostics,
+    options: &SliceOptions,
+    patcher: unsafe fn(&mut CompilationState),
+    validator: fn(&mut CompilationState),
+) {
+    compile_files(state, options, patcher, validator);
+
+    let compilation_diagnostics = std::mem::replace(&mut state.diagnostics, file_diagnostics);
+    state.diagnostics.extend(compilation_diagnostics);
+}
+
 fn compile_files(
     state: &mut CompilationState,
     options: &SliceOptions,
@@ -72,17 +173,60 @@ fn compile_files(
     // Retrieve any preprocessor symbols defined by the compiler itself, or by the user on the command line.
     let defined_symbols = HashSet::from_iter(options.defined_symbols.clone());
 
+    // Register any primitive aliases the user specified, so they can be resolved while patching type references.
+    for option in &options.primitive_aliases {
+        let result = slice_options::split_primitive_alias(option).and_then(|(alias, primitive)| {
+            match state.ast.add_primitive_alias(alias, &primitive) {
+                true => Ok(()),
+                false => Err(format!("'{alias}' is already the name of a type or another alias")),
+            }
+        });
+        if let Err(message) = result {
+            let error = Error::InvalidPrimitiveAlias {
+                alias: option.clone(),
+                message,
+            };
+            Diagnostic::new(error).push_into(&mut state.diagnostics);
+        }
+    }
+
+    // If the user requested them, register the well-known types, so they can be resolved like any other definition.
+    if options.well_known_types {
+        state.ast.add_well_known_types();
+    }
+
     // There are several phases of compilation handled by `slicec`:
     // 1) Parse the files passed in by the user.
     // 2) Patch the abstract syntax tree generated by the parser.
     // 3) Apply the user-provided patching function.
     // 4) Validate the AST, checking for language-mapping agnostic errors.
+    //    This includes checking any (opt-in) limits the user configured on the command line.
     // 5) Apply the user-provided validation function.
-    parsers::parse_files(state, &defined_symbols);
+    let measure_lexing = options.timings.is_some() || options.trace.is_some();
+    tracing::info!(files = state.files.len(), symbols = ?defined_symbols, "compiling Slice files");
+    let span = tracing::info_span!("parse").entered();
+    panic_handler::set_phase("parsing");
+    parsers::parse_files(state, &defined_symbols, measure_lexing);
+    packages::check_compilation_modes(state);
+    state.timings.record_file_phases();
+    drop(span);
 
+    let start = Instant::now();
+    let span = tracing::info_span!("patch").entered();
+    panic_handler::set_phase("patching");
     unsafe { state.apply_unsafe(patchers::patch_ast) };
     unsafe { state.apply_unsafe(patcher) };
+    state.timings.record_phase("patch", start.elapsed());
+    drop(span);
 
+    let start = Instant::now();
+    let span = tracing::info_span!("validate").entered();
+    panic_handler::set_phase("validating");
     state.apply(validators::validate_ast);
+    if !state.diagnostics.has_errors() {
+        validators::check_limits(state, options);
+    }
     state.apply(validator);
+    state.timings.record_phase("validate", start.elapsed());
+    drop(span);
 }
@@ -0,0 +1,42 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module handles the compiler's logs, which describe what the compiler is doing as it runs.
+//!
+//! The compiler is instrumented with [tracing] spans and events: each phase of compilation (and each file within it)
+//! is a span, and notable steps, like reading a file or resolving a type reference, are events. These are discarded
+//! unless a subscriber is installed, which compilers do with [install]. It's only installed if the user passed
+//! `--verbose`, and writes logs to stderr in the format specified by `--log-format`.
+
+use crate::slice_options::{LogFormat, SliceOptions};
+use std::io;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Installs a subscriber which writes the compiler's logs to stderr, if the user passed `--verbose`.
+/// Returns true if the subscriber was installed, and false if logging is disabled, or another subscriber was already
+/// installed (in which case logs are sent to it instead).
+pub fn install(options: &SliceOptions) -> bool {
+    if !options.verbose {
+        return false;
+    }
+    tracing::subscriber::set_global_default(create_subscriber(options, io::stderr)).is_ok()
+}
+
+/// Returns a subscriber which writes logs to the provided writer, in the format specified by `options`.
+/// Events are logged at the `DEBUG` level and above, and spans are logged when they close, along with their duration.
+pub fn create_subscriber<W>(options: &SliceOptions, writer: W) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_max_level(Level::DEBUG)
+        .with_target(false)
+        .with_span_events(FmtSpan::CLOSE);
+
+    match options.log_format {
+        LogFormat::Human => Box::new(builder.with_ansi(!options.disable_color).finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module analyzes Slice1 files, and reports what prevents them from being switched to Slice2 mode.
+//!
+//! For each Slice1 source file, the report lists every blocker that must be removed by hand before the file can be
+//! switched, like class definitions or uses of `AnyClass`. If a file has no blockers, the report also lists the
+//! rewrites needed to switch it, which can be applied automatically with [FileMigration::apply_rewrites].
+
+use crate::grammar::*;
+use crate::slice_file::{SliceFile, Span};
+use crate::slice_options::SliceOptions;
+use crate::visitor::{VisitControl, Visitor};
+use serde::Serialize;
+use std::{fs, io};
+
+/// A migration report for all the Slice1 source files, in the order they were provided.
+#[derive(Debug, Default, Serialize)]
+pub struct MigrationReport {
+    pub files: Vec<FileMigration>,
+}
+
+/// The migration report for a single Slice1 file.
+#[derive(Debug, Serialize)]
+pub struct FileMigration {
+    /// The relative path of the file this report is for.
+    pub path: String,
+
+    /// Everything that prevents this file from being switched to Slice2 mode. Blockers in the file itself come first,
+    /// in the order they appear, followed by any uses of its definitions in other Slice1 files.
+    pub blockers: Vec<Blocker>,
+
+    /// The rewrites that switch this file to Slice2 mode. This is empty unless the file has no blockers.
+    pub rewrites: Vec<Rewrite>,
+}
+
+/// Something which prevents a file from being switched to Slice2 mode.
+#[derive(Debug, Serialize)]
+pub struct Blocker {
+    pub kind: BlockerKind,
+
+    /// A human readable description of the blocker.
+    pub message: String,
+
+    /// Where the blocker is. For [BlockerKind::UsedBySlice1File] blockers, this is in the file using the definition.
+    pub span: Span,
+}
+
+/// The kinds of blockers that can prevent a file from being switched to Slice2 mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockerKind {
+    /// A class is defined in the file. Classes can only be defined in Slice1 mode.
+    ClassDefinition,
+
+    /// An exception is defined in the file. Exceptions can only be defined in Slice1 mode.
+    ExceptionDefinition,
+
+    /// An operation in the file has an exception specification, which can only be used in Slice1 mode.
+    ExceptionSpecification,
+
+    /// A type which is only supported by Slice1 is used in the file, like a class or `AnyClass`.
+    UnsupportedType,
+
+    /// A definition in the file is used by another Slice1 file, which can't use Slice2 definitions.
+    /// Both files must be switched to Slice2 mode together.
+    UsedBySlice1File,
+}
+
+/// A textual replacement that can be automatically applied to a file.
+#[derive(Debug, Serialize)]
+pub struct Rewrite {
+    /// The span of text to replace.
+    pub span: Span,
+
+    /// The text to replace it with.
+    pub replacement: String,
+
+    /// A human readable description of the rewrite.
+    pub description: String,
+}
+
+impl MigrationReport {
+    /// Creates a migration report for the Slice1 source files in the provided files.
+    /// Reference files aren't reported on, but they're still checked for uses of the source files' definitions.
+    pub fn from_files(files: &[SliceFile]) -> Self {
+        let is_slice1 = |file: &&SliceFile| file.compilation_mode() == CompilationMode::Slice1;
+        let slice1_files = files.iter().filter(is_slice1).collect::<Vec<_>>();
+
+        let mut report = MigrationReport::default();
+        for slice_file in slice1_files.iter().filter(|file| file.is_source) {
+            let mut blocker_finder = BlockerFinder { blockers: Vec::new() };
+            slice_file.visit_with(&mut blocker_finder);
+            let mut blockers = blocker_finder.blockers;
+
+            // Check whether any other Slice1 files use definitions from this file.
+            for other_file in slice1_files
+                .iter()
+                .filter(|f| f.relative_path != slice_file.relative_path)
+            {
+                let mut usage_finder = UsageFinder {
+                    file: &slice_file.relative_path,
+                    other_file: &other_file.relative_path,
+                    blockers: &mut blockers,
+                };
+                other_file.visit_with(&mut usage_finder);
+            }
+
+            let rewrites = match blockers.is_empty() {
+                true => rewrites_for(slice_file),
+                false => Vec::new(),
+            };
+            report.files.push(FileMigration {
+                path: slice_file.relative_path.clone(),
+                blockers,
+                rewrites,
+            });
+        }
+        report
+    }
+
+    /// Returns this report as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize migration report")
+    }
+
+    /// Writes this report to the path specified by the `--migration-report` option, if the user specified one.
+    /// Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        match &options.migration_report {
+            Some(path) => fs::write(path, self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FileMigration {
+    /// Returns true if nothing prevents this file from being switched to Slice2 mode.
+    pub fn can_migrate(&self) -> bool {
+        self.blockers.is_empty()
+    }
+
+    /// Returns the text of the provided file after applying this report's rewrites to it.
+    /// If the file has any blockers, there are no rewrites to apply, and its text is returned unchanged.
+    pub fn apply_rewrites(&self, slice_file: &SliceFile) -> String {
+        debug_assert_eq!(slice_file.relative_path, self.path);
+
+        // Apply the rewrites from last to first, so earlier rewrites don't shift the offsets of later ones.
+        let mut text = slice_file.raw_text.to_string();
+        for rewrite in self.rewrites.iter().rev() {
+            let start = slice_file.byte_offset(rewrite.span.start).unwrap();
+            let end = slice_file.byte_offset(rewrite.span.end).unwrap();
+            text.replace_range(start..end, &rewrite.replacement);
+        }
+        text
+    }
+}
+
+fn rewrites_for(slice_file: &SliceFile) -> Vec<Rewrite> {
+    // Slice1 mode must always be explicitly set, so every Slice1 file has a compilation mode statement.
+    let mode = slice_file.mode.as_ref().unwrap();
+    vec![Rewrite {
+        span: mode.span.clone(),
+        replacement: "mode = Slice2".to_owned(),
+        description: "switch the file's compilation mode to Slice2".to_owned(),
+    }]
+}
+
+/// Finds the blockers in a file's own definitions.
+struct BlockerFinder {
+    blockers: Vec<Blocker>,
+}
+
+impl BlockerFinder {
+    fn push(&mut self, kind: BlockerKind, message: String, span: &Span) {
+        let span = span.clone();
+        self.blockers.push(Blocker { kind, message, span });
+    }
+}
+
+impl Visitor for BlockerFinder {
+    fn visit_class(&mut self, class_def: &Class) -> VisitControl {
+        let message = format!("class '{}' can only be defined in Slice1 mode", class_def.identifier());
+        self.push(BlockerKind::ClassDefinition, message, class_def.span());
+        VisitControl::Continue
+    }
+
+    fn visit_exception(&mut self, exception_def: &Exception) -> VisitControl {
+        let message = format!(
+            "exception '{}' can only be defined in Slice1 mode",
+            exception_def.identifier()
+        );
+        self.push(BlockerKind::ExceptionDefinition, message, exception_def.span());
+        VisitControl::Continue
+    }
+
+    fn visit_operation(&mut self, operation: &Operation) -> VisitControl {
+        let exception_spans = operation.exception_specification.iter().map(TypeRef::span);
+        if let Some(span) = operation.throws_any_exception.iter().chain(exception_spans).next() {
+            let message = format!(
+                "operation '{}' has an exception specification, which can only be used in Slice1 mode",
+                operation.identifier(),
+            );
+            self.push(BlockerKind::ExceptionSpecification, message, span);
+        }
+        VisitControl::Continue
+    }
+
+    fn visit_type_ref(&mut self, type_ref: &TypeRef) -> VisitControl {
+        // Anonymous types are visited separately, so we only need to check the types of their elements.
+        let is_supported_by_slice2 = match type_ref.concrete_type() {
+            Types::Class(_) => false,
+            Types::Primitive(primitive) => primitive.supported_encodings().supports(Encoding::Slice2),
+            _ => true,
+        };
+
+        if !is_supported_by_slice2 {
+            let message = format!("the type '{}' is only supported by Slice1", type_ref.type_string());
+            self.push(BlockerKind::UnsupportedType, message, type_ref.span());
+        }
+        VisitControl::Continue
+    }
+}
+
+/// Finds the uses of a file's definitions in another Slice1 file.
+struct UsageFinder<'a> {
+    file: &'a str,
+    other_file: &'a str,
+    blockers: &'a mut Vec<Blocker>,
+}
+
+impl Visitor for UsageFinder<'_> {
+    fn visit_type_ref(&mut self, type_ref: &TypeRef) -> VisitControl {
+        let entity: &dyn Entity = match type_ref.concrete_type() {
+            Types::Struct(struct_def) => struct_def,
+            Types::Class(class_def) => class_def,
+            Types::Enum(enum_def) => enum_def,
+            Types::CustomType(custom_type) => custom_type,
+            _ => return VisitControl::Continue,
+        };
+
+        if entity.span().file == self.file {
+            let message = format!(
+                "'{}' is used by '{}', which is a Slice1 file",
+                entity.identifier(),
+                self.other_file,
+            );
+            self.blockers.push(Blocker {
+                kind: BlockerKind::UsedBySlice1File,
+                message,
+                span: type_ref.span().clone(),
+            });
+        }
+        VisitControl::Continue
+    }
+}
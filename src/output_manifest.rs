@@ -0,0 +1,86 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides a manifest of the files generated by a Slice compiler.
+//!
+//! The manifest lists each generated file, along with the Slice files and entities it was generated from, and a hash
+//! of its contents. Build systems can use it to track dependencies between Slice files and generated files, and
+//! compilers can compare it against the manifest from a previous run to precisely clean up stale outputs.
+
+use crate::grammar::Entity;
+use crate::slice_options::SliceOptions;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fs, io};
+
+/// A manifest of every file generated by a Slice compiler, in the order they were generated.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputManifest {
+    pub files: Vec<GeneratedFile>,
+}
+
+/// A single file that was generated by a Slice compiler.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratedFile {
+    /// The path that the file was written to.
+    pub path: String,
+
+    /// The relative paths of the Slice files that the generated code came from.
+    pub sources: Vec<String>,
+
+    /// The fully scoped identifiers of the Slice entities that the generated code came from.
+    pub entities: Vec<String>,
+
+    /// The SHA-256 hash of the file's contents, as a lowercase-hexadecimal string.
+    pub hash: String,
+}
+
+impl OutputManifest {
+    /// Records a generated file in this manifest, along with the entities that its contents were generated from.
+    /// The Slice files it came from are the files that those entities were defined in.
+    pub fn add_file(&mut self, path: impl Into<String>, contents: &[u8], entities: &[&dyn Entity]) {
+        let mut sources: Vec<String> = Vec::new();
+        for entity in entities {
+            let file = &entity.span().file;
+            if !sources.contains(file) {
+                sources.push(file.clone());
+            }
+        }
+
+        self.files.push(GeneratedFile {
+            path: path.into(),
+            sources,
+            entities: entities.iter().map(|e| e.parser_scoped_identifier()).collect(),
+            hash: format!("{:x}", Sha256::digest(contents)),
+        });
+    }
+
+    /// Returns the paths of any files listed in the `previous` manifest which aren't listed in this one.
+    /// These files were generated by a previous run of the compiler, but weren't generated by this one, and are stale.
+    pub fn stale_files<'a>(&self, previous: &'a OutputManifest) -> Vec<&'a str> {
+        previous
+            .files
+            .iter()
+            .filter(|old| !self.files.iter().any(|new| new.path == old.path))
+            .map(|old| old.path.as_str())
+            .collect()
+    }
+
+    /// Returns this manifest as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize output manifest")
+    }
+
+    /// Parses a manifest from a JSON document, like one returned by [OutputManifest::to_json].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes this manifest to the path specified by the `--manifest` option, if the user specified one.
+    /// Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        match &options.manifest {
+            Some(path) => fs::write(path, self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module resolves Slice packages: versioned sets of Slice files that can be referenced by name, instead of by
+//! listing their files with `--reference`.
+//!
+//! A package is a directory containing a [MANIFEST_FILE_NAME] file, which names the package, and lists its Slice files
+//! (relative to the package's directory), the compilation mode each of them uses, and any packages it depends on:
+//! ```json
+//! {
+//!     "name": "Greetings",
+//!     "version": "1.2.0",
+//!     "files": [
+//!         { "path": "slice/Greeter.slice", "mode": "Slice2" },
+//!         { "path": "slice/Legacy.slice", "mode": "Slice1" }
+//!     ],
+//!     "dependencies": { "Common": "2.0.0" }
+//! }
+//! ```
+//!
+//! Packages are requested with `--package <NAME>` or `--package <NAME>@<VERSION>`, and are searched for in each of the
+//! `--package-path` directories: both the directory itself and its immediate subdirectories can be packages. If no
+//! version is requested, the latest version that was found is used. The files of each requested package, and of the
+//! packages they (transitively) depend on, are compiled as reference files.
+
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::CompilationMode;
+use crate::slice_options::SliceOptions;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the file that describes a package. See [PackageManifest].
+pub const MANIFEST_FILE_NAME: &str = "slice-package.json";
+
+/// The contents of a package's manifest file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageManifest {
+    /// The name that the package is referenced by.
+    pub name: String,
+
+    /// The version of the package.
+    pub version: String,
+
+    /// The Slice files that make up the package.
+    #[serde(default)]
+    pub files: Vec<PackageFile>,
+
+    /// The packages that this package depends on, mapped to the version of them it requires.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// A Slice file that is part of a package.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageFile {
+    /// The path of the file, relative to the package's directory.
+    pub path: String,
+
+    /// The compilation mode that the file uses: either 'Slice1' or 'Slice2'.
+    /// If it isn't specified, the file's mode isn't checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+impl PackageManifest {
+    /// Parses a manifest from a JSON document, and checks that its values are valid.
+    /// Otherwise, this returns an error message describing why the manifest is invalid.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let manifest: PackageManifest = serde_json::from_str(json).map_err(|error| error.to_string())?;
+
+        if manifest.name.is_empty() || manifest.name.contains('@') {
+            return Err(format!("'{}' is not a valid package name", manifest.name));
+        }
+        for file in &manifest.files {
+            if let Some(mode) = &file.mode {
+                parse_compilation_mode(mode)?;
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Returns this manifest as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize package manifest")
+    }
+}
+
+impl PackageFile {
+    /// Returns the compilation mode listed for this file, or `None` if no mode was listed.
+    pub fn compilation_mode(&self) -> Option<CompilationMode> {
+        self.mode.as_deref().and_then(|mode| parse_compilation_mode(mode).ok())
+    }
+}
+
+/// A package that was found in one of the package paths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Package {
+    /// The package's manifest.
+    pub manifest: PackageManifest,
+
+    /// The directory that contains the package's manifest. Its files are relative to this directory.
+    pub directory: PathBuf,
+}
+
+impl Package {
+    /// Loads the package in the provided directory.
+    /// Returns `None` if the directory doesn't contain a manifest, and an error if its manifest is invalid.
+    pub fn load(directory: impl Into<PathBuf>) -> Result<Option<Self>, Error> {
+        let directory = directory.into();
+        let manifest_path = directory.join(MANIFEST_FILE_NAME);
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+
+        let path = manifest_path.display().to_string();
+        let json = fs::read_to_string(&manifest_path).map_err(|error| Error::IO {
+            action: "read",
+            path: path.clone(),
+            error,
+        })?;
+        let manifest =
+            PackageManifest::from_json(&json).map_err(|message| Error::InvalidPackageManifest { path, message })?;
+        Ok(Some(Package { manifest, directory }))
+    }
+
+    /// Returns the paths of this package's files.
+    pub fn file_paths(&self) -> Vec<String> {
+        let paths = self.manifest.files.iter().map(|file| self.directory.join(&file.path));
+        paths.map(|path| path.display().to_string()).collect()
+    }
+}
+
+/// A request for a package, as passed with `--package`: a name, and optionally the version that's required.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageRequest {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl PackageRequest {
+    /// Parses a request of the form `<NAME>` or `<NAME>@<VERSION>`.
+    pub fn parse(request: &str) -> Result<Self, String> {
+        let (name, version) = match request.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (request, None),
+        };
+        if name.is_empty() || version.is_some_and(str::is_empty) {
+            return Err("packages must be of the form '<NAME>' or '<NAME>@<VERSION>'".to_owned());
+        }
+
+        Ok(PackageRequest {
+            name: name.to_owned(),
+            version: version.map(str::to_owned),
+        })
+    }
+
+    /// Returns true if the provided manifest satisfies this request.
+    fn matches(&self, manifest: &PackageManifest) -> bool {
+        self.name == manifest.name && self.version.as_ref().is_none_or(|version| *version == manifest.version)
+    }
+}
+
+pub(crate) fn parse_package(option: &str) -> Result<String, String> {
+    PackageRequest::parse(option).map(|_| option.to_owned())
+}
+
+/// Resolves the packages requested with `--package`, along with every package they depend on.
+///
+/// Packages are returned in the order they were resolved: requested packages first (in the order they were passed),
+/// followed by their dependencies. Requests without a version resolve to the latest version of a package, unless
+/// another request (like a dependency) requires an older version of it, in which case they resolve to that version.
+/// Every request that specifies a version must specify the same version. Any problems are reported as errors.
+pub fn resolve_packages(options: &SliceOptions, diagnostics: &mut Diagnostics) -> Vec<Package> {
+    if options.packages.is_empty() {
+        return Vec::new();
+    }
+
+    let available = find_packages(&options.package_paths, diagnostics);
+    let requests = options
+        .packages
+        .iter()
+        .filter_map(|package| PackageRequest::parse(package).ok())
+        .collect::<Vec<_>>();
+
+    // Each time a request without a version turns out to conflict with one that has a version, resolution starts over,
+    // with the request pinned to the required version. Since each package can only be pinned once, this terminates.
+    let mut pinned_versions = BTreeMap::new();
+    loop {
+        match resolve_requests(&available, &requests, &pinned_versions) {
+            Ok((resolved, errors)) => {
+                for error in errors {
+                    Diagnostic::new(error).push_into(diagnostics);
+                }
+                return resolved;
+            }
+            Err((name, version)) => {
+                pinned_versions.insert(name, version);
+            }
+        }
+    }
+}
+
+/// Resolves the provided requests, and the dependencies of the packages they resolve to, returning the resolved
+/// packages along with any errors. Requests without a version resolve to the version in `pinned_versions` if there is
+/// one, and otherwise to the latest available version.
+///
+/// If a request with a version conflicts with a package that was resolved by requests without a version, and that
+/// version is available, this returns the package's name and the requested version, so they can be pinned instead.
+fn resolve_requests(
+    available: &[Package],
+    requests: &[PackageRequest],
+    pinned_versions: &BTreeMap<String, String>,
+) -> Result<(Vec<Package>, Vec<Error>), (String, String)> {
+    let mut requests = requests.iter().cloned().collect::<VecDeque<_>>();
+    let mut errors = Vec::new();
+
+    // Each resolved package is stored along with whether it was resolved to a specific version.
+    let mut resolved: Vec<(Package, bool)> = Vec::new();
+    while let Some(mut request) = requests.pop_front() {
+        // If this package was already resolved, check that it was resolved to a compatible version.
+        if let Some((package, is_pinned)) = resolved
+            .iter()
+            .find(|(package, _)| package.manifest.name == request.name)
+        {
+            if let Some(version) = request.version.filter(|version| *version != package.manifest.version) {
+                let is_available = available
+                    .iter()
+                    .any(|package| request.name == package.manifest.name && version == package.manifest.version);
+                if !is_pinned && is_available {
+                    return Err((request.name, version));
+                }
+
+                errors.push(Error::ConflictingPackageVersions {
+                    name: request.name,
+                    resolved: package.manifest.version.clone(),
+                    requested: version,
+                });
+            }
+            continue;
+        }
+
+        // Otherwise, find the latest version of the package that satisfies the request.
+        if request.version.is_none() {
+            request.version = pinned_versions.get(&request.name).cloned();
+        }
+        let candidates = available.iter().filter(|package| request.matches(&package.manifest));
+        let Some(package) = candidates.max_by(|a, b| compare_versions(&a.manifest.version, &b.manifest.version)) else {
+            errors.push(Error::PackageNotFound {
+                name: request.name,
+                version: request.version,
+            });
+            continue;
+        };
+
+        tracing::debug!(
+            name = package.manifest.name,
+            version = package.manifest.version,
+            path = %package.directory.display(),
+            "resolved package",
+        );
+        requests.extend(
+            package
+                .manifest
+                .dependencies
+                .iter()
+                .map(|(name, version)| PackageRequest {
+                    name: name.clone(),
+                    version: Some(version.clone()),
+                }),
+        );
+        resolved.push((package.clone(), request.version.is_some()));
+    }
+
+    let resolved = resolved.into_iter().map(|(package, _)| package).collect();
+    Ok((resolved, errors))
+}
+
+/// Checks that each file of the resolved packages uses the compilation mode listed for it in its package's manifest.
+pub(crate) fn check_compilation_modes(state: &mut CompilationState) {
+    for package in &state.packages {
+        for package_file in &package.manifest.files {
+            let Some(expected) = package_file.compilation_mode() else { continue };
+            let Ok(path) = package.directory.join(&package_file.path).canonicalize() else { continue };
+
+            let slice_file = state.files.iter().find(|file| {
+                Path::new(&file.relative_path)
+                    .canonicalize()
+                    .is_ok_and(|file_path| file_path == path)
+            });
+            if let Some(slice_file) = slice_file.filter(|file| file.compilation_mode() != expected) {
+                let error = Error::PackageModeMismatch {
+                    path: slice_file.relative_path.clone(),
+                    package: package.manifest.name.clone(),
+                    expected,
+                    actual: slice_file.compilation_mode(),
+                };
+                let diagnostic = Diagnostic::new(error);
+                let diagnostic = match &slice_file.mode {
+                    Some(mode) => diagnostic.set_span(&mode.span),
+                    None => diagnostic,
+                };
+                diagnostic.push_into(&mut state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Returns every package in the provided package paths. Invalid manifests are reported as errors.
+fn find_packages(package_paths: &[String], diagnostics: &mut Diagnostics) -> Vec<Package> {
+    let mut directories = Vec::new();
+    for package_path in package_paths {
+        let path = PathBuf::from(package_path);
+        if !path.is_dir() {
+            let error = Error::FileNotFound {
+                path: package_path.clone(),
+            };
+            Diagnostic::new(error).push_into(diagnostics);
+            continue;
+        }
+
+        directories.push(path.clone());
+        match fs::read_dir(&path) {
+            Ok(entries) => {
+                let mut subdirectories = entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_dir())
+                    .collect::<Vec<_>>();
+                // Sort the subdirectories, so packages are always found in the same order.
+                subdirectories.sort();
+                directories.extend(subdirectories);
+            }
+            Err(error) => Diagnostic::new(Error::IO {
+                action: "read",
+                path: package_path.clone(),
+                error,
+            })
+            .push_into(diagnostics),
+        }
+    }
+
+    let mut packages = Vec::new();
+    for directory in directories {
+        match Package::load(directory) {
+            Ok(package) => packages.extend(package),
+            Err(error) => Diagnostic::new(error).push_into(diagnostics),
+        }
+    }
+    packages
+}
+
+/// Compares two versions by their dot-separated components. Numeric components are compared numerically, and any
+/// other components are compared lexicographically, so '1.10.0' is later than '1.9.0'.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a_components = a.split('.');
+    let mut b_components = b.split('.');
+    loop {
+        let ordering = match (a_components.next(), b_components.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn parse_compilation_mode(mode: &str) -> Result<CompilationMode, String> {
+    match mode {
+        "Slice1" => Ok(CompilationMode::Slice1),
+        "Slice2" => Ok(CompilationMode::Slice2),
+        _ => Err(format!(
+            "'{mode}' is not a valid compilation mode; it must be 'Slice1' or 'Slice2'"
+        )),
+    }
+}
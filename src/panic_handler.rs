@@ -0,0 +1,141 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module handles internal compiler errors (ICEs): panics caused by bugs in the compiler, instead of by problems
+//! in the Slice files being compiled.
+//!
+//! Compilers install the handler with [install], which replaces Rust's default panic message with one asking the user
+//! to report the bug, along with the compiler's version and what it was doing when it panicked: the current phase of
+//! compilation, and the file and top-level definition being processed (if any).
+//!
+//! If the user passed `--ice-bundle <DIRECTORY>`, a reproduction bundle is also written to that directory, holding a
+//! copy of the input files, the command line the compiler was run with, and the ICE message itself.
+
+use crate::diagnostics::Diagnostics;
+use crate::slice_options::SliceOptions;
+use crate::utils::file_util;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Component, Path, PathBuf};
+use std::{env, fs};
+
+/// Where users should report internal compiler errors.
+pub const ISSUE_TRACKER: &str = "https://github.com/icerpc/slicec/issues";
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::default();
+}
+
+/// What the compiler is currently doing, reported alongside internal compiler errors.
+#[derive(Clone, Debug, Default)]
+struct Context {
+    phase: Option<String>,
+    file: Option<String>,
+    entity: Option<String>,
+}
+
+/// Records the phase of compilation the compiler is starting, like `parsing` or `generating code (cs)`.
+/// This clears the current file and entity, since they belonged to the previous phase.
+pub fn set_phase(phase: impl Into<String>) {
+    CONTEXT.with_borrow_mut(|context| {
+        *context = Context {
+            phase: Some(phase.into()),
+            ..Context::default()
+        };
+    });
+}
+
+/// Records the (relative) path of the file the compiler is processing. This clears the current entity.
+pub fn set_file(path: &str) {
+    CONTEXT.with_borrow_mut(|context| {
+        context.file = Some(path.to_owned());
+        context.entity = None;
+    });
+}
+
+/// Records the fully scoped identifier of the definition the compiler is processing.
+pub fn set_entity(identifier: String) {
+    CONTEXT.with_borrow_mut(|context| context.entity = Some(identifier));
+}
+
+/// Installs a panic hook, which reports any panics as internal compiler errors, and writes a reproduction bundle if
+/// the user passed `--ice-bundle`. This replaces any previously installed panic hook.
+pub fn install(options: &SliceOptions) {
+    let options = options.clone();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = ice_message(info);
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{message}");
+
+        if let Some(directory) = &options.ice_bundle {
+            let _ = match write_bundle(Path::new(directory), &message, &options) {
+                Ok(()) => writeln!(stderr, "note: a reproduction bundle was written to '{directory}'"),
+                Err(error) => writeln!(
+                    stderr,
+                    "note: failed to write a reproduction bundle to '{directory}': {error}"
+                ),
+            };
+        }
+    }));
+}
+
+/// Returns the message reported for the provided panic, including the compiler's version and what it was doing.
+fn ice_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let reason = (payload.downcast_ref::<&str>().copied())
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+
+    let mut message = format!("error: internal compiler error: {reason}\n");
+    if let Some(location) = info.location() {
+        message += &format!(" --> {location}\n");
+    }
+    message += &format!(
+        "note: this is a bug in slicec {}, please report it at {ISSUE_TRACKER}\n",
+        env!("CARGO_PKG_VERSION"),
+    );
+
+    // The context may already be borrowed if the panic happened while it was being updated.
+    let context = CONTEXT.try_with(|context| context.try_borrow().map(|c| c.clone()).unwrap_or_default());
+    let context = context.unwrap_or_default();
+    if let Some(phase) = &context.phase {
+        message += &format!("note: the compiler panicked while {phase}");
+        if let Some(file) = &context.file {
+            message += &format!(", in '{file}'");
+        }
+        if let Some(entity) = &context.entity {
+            message += &format!(", on '{entity}'");
+        }
+        message += "\n";
+    }
+    message
+}
+
+/// Writes a reproduction bundle to the provided directory, holding the provided ICE message, the command line the
+/// compiler was run with, and a copy of each input file (at its relative path, under `files/`).
+fn write_bundle(directory: &Path, message: &str, options: &SliceOptions) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+    fs::write(directory.join("ice.txt"), message)?;
+
+    let arguments = env::args().collect::<Vec<_>>();
+    fs::write(directory.join("command_line.txt"), arguments.join("\n"))?;
+
+    // Reading the files can report diagnostics, but the bundle only needs the files that could be read.
+    let files = file_util::resolve_files_from(options, &mut Diagnostics::new());
+    for file in files {
+        let path = directory.join("files").join(sanitize(&file.relative_path));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &*file.raw_text)?;
+    }
+    Ok(())
+}
+
+/// Returns the provided path without any components that could escape the bundle's directory, like `..` or `/`.
+fn sanitize(path: &str) -> PathBuf {
+    let components = Path::new(path).components();
+    components
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
@@ -5,7 +5,7 @@
 //! While many of these functions could be written directly into the parser rules, we implement them here instead, to
 //! keep the rules focused on grammar instead of implementation details, making the grammar easier to read and modify.
 
-use crate::grammar::{DocComment, Message, MessageComponent};
+use crate::grammar::{DocComment, Identifier, Message, MessageComponent};
 use crate::slice_file::{Location, Span};
 use lalrpop_util::lalrpop_mod;
 
@@ -18,10 +18,17 @@ lalrpop_mod!(
 
 // Helper macro for storing parsed tags inside the correct field of a doc comment,
 // and extending the doc comment's span to the end of the new tag.
+// Tags which follow a `@locale` block are stored in that block's localization instead of the comment itself.
 macro_rules! append_tag_to_comment {
     ($comment:ident, $field:ident, $block:expr) => {{
         $comment.span.end = $block.span.end;
-        $comment.$field.push($block);
+        match $comment.localizations.last_mut() {
+            Some(localization) => {
+                localization.span.end = $block.span.end;
+                localization.$field.push($block);
+            }
+            None => $comment.$field.push($block),
+        }
         $comment
     }};
 }
@@ -31,7 +38,7 @@ use append_tag_to_comment; // To let LALRPOP use the macro.
 // Grammar Rule Functions
 
 /// Creates a new doc comment with the specified overview and everything else empty.
-fn create_doc_comment(overview: Option<Message>, start: Location, file: &str) -> DocComment {
+fn create_doc_comment(overview: Option<Message>, start: Location, file: &str, raw_text: String) -> DocComment {
     // We subtract 3 from the start of the comment to account for the leading "///" that is always present.
     // This span is automatically extended as more constructs are parsed.
     let mut span = Span::new(start, start, file);
@@ -48,10 +55,44 @@ fn create_doc_comment(overview: Option<Message>, start: Location, file: &str) ->
         returns: Vec::new(),
         throws: Vec::new(),
         see: Vec::new(),
+        locale: None,
+        localizations: Vec::new(),
+        span,
+        raw_text,
+    }
+}
+
+/// Creates a localization of a doc comment, in the specified locale, whose overview is the locale block's message.
+fn create_localization(locale: Identifier, message: Message, start: Location, raw_text: String) -> DocComment {
+    let span = Span::new(start, message.span.end, &message.span.file);
+
+    // If the locale block has no message (just `@locale fr`), the localization has no overview.
+    let has_overview = message.value.iter().any(|component| match component {
+        MessageComponent::Text(text) => !text.trim().is_empty(),
+        MessageComponent::Link(_) => true,
+    });
+    let overview = has_overview.then_some(message);
+
+    DocComment {
+        overview,
+        params: Vec::new(),
+        returns: Vec::new(),
+        throws: Vec::new(),
+        see: Vec::new(),
+        locale: Some(locale),
+        localizations: Vec::new(),
         span,
+        raw_text,
     }
 }
 
+/// Stores the provided localization in the doc comment, and extends the doc comment's span to the end of it.
+fn add_localization_to_comment(mut comment: DocComment, localization: DocComment) -> DocComment {
+    comment.span.end = localization.span.end;
+    comment.localizations.push(localization);
+    comment
+}
+
 /// Creates a string representing a Slice identifier that can be relatively or globally scoped.
 fn get_scoped_identifier_string<'a>(first: &'a str, mut others: Vec<&'a str>, is_globally_scoped: bool) -> String {
     others.insert(0, first);
@@ -66,6 +107,7 @@ fn construct_section_message(
     inline_message: Option<Vec<MessageComponent>>,
     message_lines: Option<Message>,
     span: Span,
+    raw_text: String,
 ) -> Message {
     let mut value = message_lines.map(|m| m.value).unwrap_or_default();
 
@@ -83,7 +125,7 @@ fn construct_section_message(
         value = message;
     }
 
-    Message { value, span }
+    Message { value, span, raw_text }
 }
 
 /// Removes any common leading whitespace from the provided lines and returns the result.
@@ -91,7 +133,7 @@ fn construct_section_message(
 /// `None` means the line existed but was empty, `Some(message)` means the line had a message.
 ///
 /// Note that the message's span is not updated to reflect the stripping of common leading whitespace.
-fn sanitize_message_lines(lines: Vec<Option<Vec<MessageComponent>>>, span: Span) -> Message {
+fn sanitize_message_lines(lines: Vec<Option<Vec<MessageComponent>>>, span: Span, raw_text: String) -> Message {
     // First compute the amount of leading whitespace that is common to every line.
     let mut common_leading_whitespace = usize::MAX;
     for line in &lines {
@@ -134,5 +176,5 @@ fn sanitize_message_lines(lines: Vec<Option<Vec<MessageComponent>>>, span: Span)
         })
         .collect();
 
-    Message { value, span }
+    Message { value, span, raw_text }
 }
@@ -34,6 +34,10 @@ pub struct Lexer<'input> {
 
     /// The current mode of the lexer; controls how the input is tokenized in a context-dependent manner.
     mode: LexerMode,
+
+    /// The location of the '{' that opened the inline tag currently being lexed.
+    /// Used to report unterminated inline tags with a span covering the entire tag.
+    inline_tag_start: Location,
 }
 
 impl<'input> Lexer<'input> {
@@ -50,6 +54,7 @@ impl<'input> Lexer<'input> {
             position: 0,
             cursor: Location::default(),
             mode: LexerMode::Message,
+            inline_tag_start: Location::default(),
         };
         lexer.switch_to_next_line(first_line, first_span); // Actually initialize the lexer.
         lexer
@@ -123,6 +128,7 @@ impl<'input> Lexer<'input> {
             "returns" => Ok((start_location, TokenKind::ReturnsKeyword, self.cursor)),
             "throws" => Ok((start_location, TokenKind::ThrowsKeyword, self.cursor)),
             "see" => Ok((start_location, TokenKind::SeeKeyword, self.cursor)),
+            "locale" => Ok((start_location, TokenKind::LocaleKeyword, self.cursor)),
             "link" => Ok((start_location, TokenKind::LinkKeyword, self.cursor)),
             "" => Err((start_location, ErrorKind::MissingTag, self.cursor)),
             tag => Err((start_location, ErrorKind::UnknownTag { tag }, self.cursor)),
@@ -136,7 +142,8 @@ impl<'input> Lexer<'input> {
                 TokenKind::ParamKeyword
                 | TokenKind::ReturnsKeyword
                 | TokenKind::ThrowsKeyword
-                | TokenKind::SeeKeyword => !is_inline,
+                | TokenKind::SeeKeyword
+                | TokenKind::LocaleKeyword => !is_inline,
 
                 // These tags are only valid inline.
                 TokenKind::LinkKeyword => is_inline,
@@ -171,6 +178,7 @@ impl<'input> Lexer<'input> {
 
             if matches!(self.buffer.peek(), Some('@')) {
                 self.mode = LexerMode::InlineTag;
+                self.inline_tag_start = start_location;
                 return (start_location, TokenKind::LeftBrace, self.cursor);
             }
         }
@@ -270,7 +278,11 @@ impl<'input> Iterator for Lexer<'input> {
             // So, we return an `UnterminatedInlineTag` error since inline tags can't span multiple lines.
             LexerMode::InlineTag => {
                 self.mode = LexerMode::Message; // Change the mode so the error is only reported once.
-                Some(Err((self.cursor, ErrorKind::UnterminatedInlineTag, self.cursor)))
+                Some(Err((
+                    self.inline_tag_start,
+                    ErrorKind::UnterminatedInlineTag,
+                    self.cursor,
+                )))
             }
 
             // If the lexer is in `Message` or `BlockTag` mode when it hit EOL, this is normal and expected.
@@ -5,8 +5,9 @@ pub mod lexer;
 pub mod parser;
 pub mod tokens;
 
-use self::tokens::{Error, TokenKind};
-use crate::diagnostics::{Diagnostic, Lint};
+use self::tokens::{Error, ErrorKind, TokenKind};
+use crate::diagnostics::{Diagnostic, Diagnostics, Lint};
+use crate::grammar::{DocComment, Element, TypeRefDefinition};
 use crate::slice_file::{Location, Span};
 
 type ParseError<'a> = lalrpop_util::ParseError<Location, TokenKind<'a>, Error<'a>>;
@@ -14,7 +15,7 @@ type ParseError<'a> = lalrpop_util::ParseError<Location, TokenKind<'a>, Error<'a
 // TODO add more specific error messages for common cases.
 
 /// Converts an [error](Error) that was emitted from the parser/lexer into a [lint](Lint)
-/// that can be stored in a [`Diagnostics`](crate::diagnostics::Diagnostics) struct.
+/// that can be stored in a [`Diagnostics`] struct.
 fn construct_lint_from(parse_error: ParseError, file_name: &str) -> Diagnostic {
     match parse_error {
         // A custom error we emitted; See `ErrorKind`.
@@ -24,7 +25,27 @@ fn construct_lint_from(parse_error: ParseError, file_name: &str) -> Diagnostic {
             let converted = Lint::MalformedDocComment {
                 message: parse_error_kind.to_string(),
             };
-            Diagnostic::new(converted).set_span(&Span::new(start, end, file_name))
+            let mut diagnostic = Diagnostic::new(converted).set_span(&Span::new(start, end, file_name));
+
+            // If an unknown tag is only a typo away from a real tag, suggest the real tag.
+            if let ErrorKind::UnknownTag { tag } = parse_error_kind {
+                if let Some(suggestion) = find_similar_tag(tag) {
+                    diagnostic = diagnostic.add_note(format!("did you mean '@{suggestion}'?"), None);
+                }
+            }
+            diagnostic
+        }
+
+        // A block tag's identifier was followed by another identifier, instead of a ':' or the end of the line.
+        // This almost always means that the ':' separating the tag from its description was forgotten.
+        ParseError::UnrecognizedToken {
+            token: (start, TokenKind::Identifier(identifier), end),
+            expected,
+        } if expected.iter().any(|e| e == r#"":""#) => {
+            let message = format!("expected ':' before the tag's description, but found '{identifier}'");
+            Diagnostic::new(Lint::MalformedDocComment { message })
+                .set_span(&Span::new(start, end, file_name))
+                .add_note("block tags must be separated from their descriptions by a ':'", None)
         }
 
         // The parser encountered a token that didn't fit any grammar rule.
@@ -43,7 +64,123 @@ fn construct_lint_from(parse_error: ParseError, file_name: &str) -> Diagnostic {
             Diagnostic::new(Lint::MalformedDocComment { message }).set_span(&Span::new(location, location, file_name))
         }
 
-        _ => unreachable!("impossible error encountered in comment parser: {parse_error:?}"),
+        // Only the built-in lexer emits 'InvalidToken' errors, and none of our rules can emit 'ExtraToken' errors.
+        // Even though these are impossible, we report them instead of panicking, so no input can crash the compiler.
+        ParseError::InvalidToken { location } => {
+            let message = "invalid token".to_owned();
+            Diagnostic::new(Lint::MalformedDocComment { message }).set_span(&Span::new(location, location, file_name))
+        }
+        ParseError::ExtraToken {
+            token: (start, token_kind, end),
+        } => {
+            let message = format!("unexpected token '{token_kind:?}'");
+            Diagnostic::new(Lint::MalformedDocComment { message }).set_span(&Span::new(start, end, file_name))
+        }
+    }
+}
+
+/// The names of all the tags that can appear in a doc comment.
+const TAGS: [&str; 6] = ["param", "returns", "throws", "see", "locale", "link"];
+
+/// Returns the tag that the provided (unknown) tag was most likely meant to be, if there is one.
+/// A tag is only suggested if it differs from the unknown tag by case, or by a single character.
+fn find_similar_tag(tag: &str) -> Option<&'static str> {
+    TAGS.into_iter()
+        .find(|known| known.eq_ignore_ascii_case(tag) || edit_distance(known, &tag.to_ascii_lowercase()) <= 1)
+}
+
+/// Returns the number of single character insertions, deletions, or substitutions needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = previous_row[j] + usize::from(a_char != *b_char);
+            let cost = substitution_cost.min(previous_row[j + 1] + 1).min(current_row[j] + 1);
+            current_row.push(cost);
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+/// Reports a warning for any tags in the provided doc comment that duplicate an earlier tag.
+/// Two tags are duplicates if they're of the same kind and describe the same thing: a parameter with the same name,
+/// an exception with the same type, etc. Duplicated tags are still stored in the comment; only a warning is reported.
+pub(crate) fn check_for_duplicate_tags(comment: &DocComment, scope: &str, diagnostics: &mut Diagnostics) {
+    let params = comment
+        .params
+        .iter()
+        .map(|tag| (Some(tag.identifier.value.as_str()), &tag.span));
+    report_duplicates("param", params, scope, diagnostics);
+
+    let returns = comment
+        .returns
+        .iter()
+        .map(|tag| (tag.identifier.as_ref().map(|i| i.value.as_str()), &tag.span));
+    report_duplicates("returns", returns, scope, diagnostics);
+
+    let throws = comment
+        .throws
+        .iter()
+        .map(|tag| (unpatched_identifier(&tag.thrown_type), &tag.span));
+    report_duplicates("throws", throws, scope, diagnostics);
+
+    let see = comment
+        .see
+        .iter()
+        .map(|tag| (unpatched_identifier(&tag.link), &tag.span));
+    report_duplicates("see", see, scope, diagnostics);
+
+    let locales = comment
+        .localizations
+        .iter()
+        .filter_map(|localization| localization.locale.as_ref())
+        .map(|locale| (Some(locale.value.as_str()), &locale.span));
+    report_duplicates("locale", locales, scope, diagnostics);
+
+    // Tags are only duplicates of tags in the same localization, so each localization is checked separately.
+    for localization in &comment.localizations {
+        check_for_duplicate_tags(localization, scope, diagnostics);
+    }
+}
+
+fn unpatched_identifier<T: Element + ?Sized>(type_ref: &TypeRefDefinition<T>) -> Option<&str> {
+    match type_ref {
+        TypeRefDefinition::Unpatched(identifier) => Some(&identifier.value),
+        TypeRefDefinition::Patched(_) => None,
+    }
+}
+
+fn report_duplicates<'a>(
+    tag: &str,
+    tags: impl Iterator<Item = (Option<&'a str>, &'a Span)>,
+    scope: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    let mut seen: Vec<(Option<&str>, &Span)> = Vec::new();
+    for (identifier, span) in tags {
+        if let Some((_, original_span)) = seen.iter().find(|(other, _)| *other == identifier) {
+            let (message, note) = match identifier {
+                Some(identifier) => (
+                    format!("duplicate '@{tag}' tag for '{identifier}'"),
+                    format!("'{identifier}' was previously documented here"),
+                ),
+                None => (
+                    format!("duplicate '@{tag}' tag"),
+                    format!("'@{tag}' was previously used here"),
+                ),
+            };
+            Diagnostic::new(Lint::MalformedDocComment { message })
+                .set_span(span)
+                .set_scope(scope)
+                .add_note(note, Some(original_span))
+                .push_into(diagnostics);
+        } else {
+            seen.push((identifier, span));
+        }
     }
 }
 
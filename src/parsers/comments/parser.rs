@@ -6,13 +6,14 @@ use super::grammar::lalrpop;
 use super::lexer::Lexer;
 use crate::diagnostics::Diagnostics;
 use crate::grammar::DocComment;
-use crate::slice_file::Span;
+use crate::slice_file::{Location, Span};
 
 /// Helper macro for generating parsing functions.
 macro_rules! implement_parse_function {
     ($function_name:ident, $underlying_parser:ident, $return_type:ty $(,)?) => {
         #[allow(clippy::result_unit_err)]
-        pub fn $function_name(mut self, input: Vec<(&str, Span)>) -> ParserResult<$return_type> {
+        pub fn $function_name(mut self, input: Vec<(&'a str, Span)>) -> ParserResult<$return_type> {
+            self.lines = input.clone();
             match lalrpop::$underlying_parser::new().parse(&mut self, Lexer::new(input)) {
                 Err(parse_error) => {
                     let lint = construct_lint_from(parse_error, self.file_name).set_scope(self.identifier);
@@ -32,6 +33,7 @@ pub struct CommentParser<'a> {
     pub file_name: &'a str,
     pub(super) identifier: &'a String,
     pub(super) diagnostics: &'a mut Diagnostics,
+    lines: Vec<(&'a str, Span)>,
 }
 
 impl<'a> CommentParser<'a> {
@@ -42,6 +44,40 @@ impl<'a> CommentParser<'a> {
             file_name,
             identifier,
             diagnostics,
+            lines: Vec::new(),
         }
     }
+
+    /// Returns the text of the entire comment being parsed, exactly as it was written.
+    /// Lines are separated by '\n', and don't include their leading "///".
+    pub(super) fn comment_text(&self) -> String {
+        let lines = self.lines.iter().map(|(line, _)| *line);
+        lines.collect::<Vec<_>>().join("\n")
+    }
+
+    /// Returns the text between the provided locations, exactly as it was written in the comment being parsed.
+    pub(super) fn raw_text(&self, start: Location, end: Location) -> String {
+        let mut raw_lines = Vec::new();
+        for (line, span) in &self.lines {
+            let row = span.start.row;
+            if row < start.row || row > end.row {
+                continue;
+            }
+
+            // Convert the (column) locations into character indexes, then the character indexes into byte offsets.
+            let start_index = if row == start.row {
+                start.col - span.start.col
+            } else {
+                0
+            };
+            let end_index = if row == end.row {
+                end.col - span.start.col
+            } else {
+                usize::MAX
+            };
+            let byte_offset = |index: usize| line.char_indices().nth(index).map_or(line.len(), |(offset, _)| offset);
+            raw_lines.push(&line[byte_offset(start_index)..byte_offset(end_index)]);
+        }
+        raw_lines.join("\n")
+    }
 }
@@ -26,6 +26,7 @@ pub enum TokenKind<'input> {
     ReturnsKeyword, // "@returns"
     ThrowsKeyword,  // "@throws"
     SeeKeyword,     // "@see"
+    LocaleKeyword,  // "@locale"
     LinkKeyword,    // "@link"
 
     // Symbols
@@ -0,0 +1,51 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains the entry points used by the fuzz targets in the `fuzz` directory.
+//!
+//! Each function runs a single stage of parsing over arbitrary input, and returns the diagnostics it reported.
+//! None of these functions should ever panic, regardless of their input.
+//! They aren't part of the compiler's public API, and may change at any time.
+
+use super::slice::lexer::Lexer;
+use super::{Parser, Preprocessor};
+use crate::ast::Ast;
+use crate::diagnostics::Diagnostics;
+use std::collections::HashSet;
+
+const FILE_NAME: &str = "fuzz.slice";
+
+/// Runs the preprocessor over the provided input, consuming all the source blocks it produces.
+pub fn preprocess(input: &str) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    let mut symbols = HashSet::new();
+    let preprocessor = Preprocessor::new(FILE_NAME, &mut symbols, &mut diagnostics);
+    if let Ok(source_blocks) = preprocessor.parse_slice_file(input) {
+        source_blocks.for_each(drop);
+    }
+    diagnostics
+}
+
+/// Runs the preprocessor and the Slice lexer over the provided input, consuming all the tokens it produces.
+pub fn lex(input: &str) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    let mut symbols = HashSet::new();
+    let preprocessor = Preprocessor::new(FILE_NAME, &mut symbols, &mut diagnostics);
+    if let Ok(source_blocks) = preprocessor.parse_slice_file(input) {
+        Lexer::from(source_blocks).for_each(drop);
+    }
+    diagnostics
+}
+
+/// Runs the preprocessor and the Slice parser over the provided input, discarding the AST it produces.
+pub fn parse(input: &str) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    let mut symbols = HashSet::new();
+    let preprocessor = Preprocessor::new(FILE_NAME, &mut symbols, &mut diagnostics);
+    let Ok(source_blocks) = preprocessor.parse_slice_file(input) else { return diagnostics };
+    let source_blocks = source_blocks.collect::<Vec<_>>();
+
+    let mut ast = Ast::create();
+    let parser = Parser::new(FILE_NAME, &mut ast, &mut diagnostics);
+    let _ = parser.parse_slice_file(source_blocks.into_iter());
+    diagnostics
+}
@@ -0,0 +1,341 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides a lossless representation of Slice files, for tools which need to rewrite Slice source code,
+//! and a [Tokenizer] for tools which only need its tokens, like syntax highlighters.
+//!
+//! The AST only stores what's needed to compile a file, so it drops comments, whitespace, preprocessor directives,
+//! and punctuation. Instead, this representation keeps every character of a file; each is stored in exactly one
+//! [SyntaxToken], and the text of a file can be reconstructed exactly by concatenating the text of its tokens.
+//! Tokens which don't affect the meaning of a file (whitespace and non-doc comments) are called 'trivia'.
+//!
+//! Tokens are grouped into a tree of [SyntaxNode]s by the delimiters which enclose them (braces, parentheses, and
+//! brackets). This tree is built without checking whether the file is valid Slice. Unbalanced delimiters are kept as
+//! regular tokens, and unterminated groups extend to the end of the file, so any file can be represented.
+
+use super::slice::lexer::check_if_keyword;
+use super::slice::tokens::TokenKind as SliceTokenKind;
+use crate::slice_file::{Location, SliceFile, Span};
+use std::fmt;
+
+/// The kinds of tokens that can appear in a lossless syntax tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyntaxKind {
+    /// A run of whitespace characters, including newlines.
+    Whitespace,
+
+    /// A line comment, not including the newline that ends it: `// comment`.
+    LineComment,
+
+    /// A block comment: `/* comment */`.
+    BlockComment,
+
+    /// A single line of a doc comment, not including the newline that ends it: `/// comment`.
+    DocComment,
+
+    /// A preprocessor directive, not including the newline that ends it: `#if FOO`.
+    PreprocessorDirective,
+
+    /// A Slice keyword, like `struct` or `int32`. Words within attributes are never keywords.
+    Keyword,
+
+    /// An identifier, including any leading `\` used to escape it.
+    Identifier,
+
+    /// An integer literal, like `79` or `0xff`.
+    IntegerLiteral,
+
+    /// A string literal, including its quotation marks.
+    StringLiteral,
+
+    /// A symbol, like `{` or `->`.
+    Symbol,
+
+    /// A character which isn't valid in Slice.
+    Unknown,
+}
+
+impl SyntaxKind {
+    /// Returns true if tokens of this kind don't affect the meaning of a file.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Self::Whitespace | Self::LineComment | Self::BlockComment)
+    }
+}
+
+/// A single token, along with its exact text and where it is in its file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxToken {
+    pub kind: SyntaxKind,
+    pub text: String,
+    pub span: Span,
+}
+
+/// The kinds of nodes that can appear in a lossless syntax tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    /// The root node, which holds the entire contents of a file.
+    File,
+
+    /// Tokens enclosed in braces: `{ ... }`.
+    Braces,
+
+    /// Tokens enclosed in parentheses: `( ... )`.
+    Parentheses,
+
+    /// Tokens enclosed in brackets: `[ ... ]`.
+    Brackets,
+
+    /// Tokens enclosed in double brackets, as used by file attributes: `[[ ... ]]`.
+    DoubleBrackets,
+}
+
+/// A group of tokens and nodes. Except for [NodeKind::File], nodes start with their opening delimiter, and end with
+/// their closing delimiter (if the group was terminated).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxNode {
+    pub kind: NodeKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+/// An element of a syntax tree, which is either a single token, or a node containing other elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxNode {
+    /// Returns all the tokens within this node (including in nested nodes), in the order they appear.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+
+    /// Returns all the tokens within this node which aren't trivia, in the order they appear.
+    pub fn significant_tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = self.tokens();
+        tokens.retain(|token| !token.kind.is_trivia());
+        tokens
+    }
+
+    /// Returns the span of this node, from the start of its first token, to the end of its last token.
+    /// If this node has no tokens (which is only possible for an empty file), this returns `None`.
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.tokens();
+        let (first, last) = (tokens.first()?, tokens.last()?);
+        Some(Span::new(first.span.start, last.span.end, &first.span.file))
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    /// Writes the exact text this node was created from.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.tokens().iter().try_for_each(|token| f.write_str(&token.text))
+    }
+}
+
+impl SliceFile {
+    /// Returns a lossless syntax tree of this file's text. See the [lossless](crate::lossless) module for more information.
+    pub fn syntax_tree(&self) -> SyntaxNode {
+        parse(&self.raw_text, &self.relative_path)
+    }
+}
+
+/// Parses the provided text into a lossless syntax tree. `file_name` is only used for the spans of its tokens.
+pub fn parse(text: &str, file_name: &str) -> SyntaxNode {
+    let mut stack = vec![SyntaxNode {
+        kind: NodeKind::File,
+        children: Vec::new(),
+    }];
+
+    for token in Tokenizer::new(text, file_name) {
+        let opens = match (token.kind, token.text.as_str()) {
+            (SyntaxKind::Symbol, "{") => Some(NodeKind::Braces),
+            (SyntaxKind::Symbol, "(") => Some(NodeKind::Parentheses),
+            (SyntaxKind::Symbol, "[") => Some(NodeKind::Brackets),
+            (SyntaxKind::Symbol, "[[") => Some(NodeKind::DoubleBrackets),
+            _ => None,
+        };
+        let closes = match (token.kind, token.text.as_str()) {
+            (SyntaxKind::Symbol, "}") => Some(NodeKind::Braces),
+            (SyntaxKind::Symbol, ")") => Some(NodeKind::Parentheses),
+            (SyntaxKind::Symbol, "]") => Some(NodeKind::Brackets),
+            (SyntaxKind::Symbol, "]]") => Some(NodeKind::DoubleBrackets),
+            _ => None,
+        };
+
+        if let Some(kind) = opens {
+            let children = vec![SyntaxElement::Token(token)];
+            stack.push(SyntaxNode { kind, children });
+        } else if closes.is_some() && closes == stack.last().map(|node| node.kind) {
+            let mut node = stack.pop().unwrap();
+            node.children.push(SyntaxElement::Token(token));
+            stack.last_mut().unwrap().children.push(SyntaxElement::Node(node));
+        } else {
+            // Unbalanced closing delimiters are kept as regular tokens.
+            stack.last_mut().unwrap().children.push(SyntaxElement::Token(token));
+        }
+    }
+
+    // Close any unterminated nodes at the end of the file.
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(SyntaxElement::Node(node));
+    }
+    stack.pop().unwrap()
+}
+
+/// Returns an iterator over the tokens of the provided text. `file_name` is only used for the spans of its tokens.
+///
+/// This doesn't parse or validate the text, so it can be used to tokenize Slice without running the compiler, like for
+/// syntax highlighting. See [Tokenizer] for more information.
+pub fn tokenize<'a>(text: &'a str, file_name: &'a str) -> Tokenizer<'a> {
+    Tokenizer::new(text, file_name)
+}
+
+/// An iterator over the tokens of a Slice file, which never discards any characters (including trivia).
+///
+/// Tokens are produced lazily, and there are no lexing errors; characters which aren't valid in Slice are returned as
+/// [SyntaxKind::Unknown] tokens, and unterminated comments and string literals extend to the end of the text.
+#[derive(Debug)]
+pub struct Tokenizer<'a> {
+    chars: Vec<char>,
+    file_name: &'a str,
+    position: usize,
+    location: Location,
+    attribute_depth: i32,
+    is_start_of_line: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Creates a tokenizer over the provided text. `file_name` is only used for the spans of its tokens.
+    pub fn new(text: &str, file_name: &'a str) -> Self {
+        Tokenizer {
+            chars: text.chars().collect(),
+            file_name,
+            position: 0,
+            location: Location::default(),
+            attribute_depth: 0,
+            is_start_of_line: true,
+        }
+    }
+
+    /// Returns the position of the first character at or after `i` which doesn't match `predicate`.
+    fn end_of_run(&self, mut i: usize, predicate: impl Fn(char) -> bool) -> usize {
+        while i < self.chars.len() && predicate(self.chars[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    /// Returns the kind of the token starting at the current position, along with the position where it ends.
+    fn lex_token(&mut self) -> (SyntaxKind, usize) {
+        let chars = &self.chars;
+        let start = self.position;
+        let c = chars[start];
+        let next = chars.get(start + 1).copied();
+
+        match c {
+            _ if c.is_whitespace() => (SyntaxKind::Whitespace, self.end_of_run(start, char::is_whitespace)),
+            '#' if self.is_start_of_line => (SyntaxKind::PreprocessorDirective, self.end_of_run(start, |c| c != '\n')),
+            '/' if next == Some('/') => {
+                let end = self.end_of_run(start, |c| c != '\n');
+                let is_doc_comment =
+                    chars[start..end].starts_with(&['/'; 3]) && !chars[start..end].starts_with(&['/'; 4]);
+                match is_doc_comment {
+                    true => (SyntaxKind::DocComment, end),
+                    false => (SyntaxKind::LineComment, end),
+                }
+            }
+            '/' if next == Some('*') => {
+                let mut end = start + 2;
+                while end < chars.len() && !(chars[end - 1] == '*' && chars[end] == '/' && end - start > 2) {
+                    end += 1;
+                }
+                (SyntaxKind::BlockComment, (end + 1).min(chars.len()))
+            }
+            '"' => {
+                let mut end = start + 1;
+                let mut is_next_char_escaped = false;
+                while end < chars.len() {
+                    let c = chars[end];
+                    end += 1;
+                    match c {
+                        '"' if !is_next_char_escaped => break,
+                        '\\' if !is_next_char_escaped => is_next_char_escaped = true,
+                        _ => is_next_char_escaped = false,
+                    }
+                }
+                (SyntaxKind::StringLiteral, end)
+            }
+            _ if c.is_ascii_digit() => {
+                let end = self.end_of_run(start, |c| c.is_alphanumeric() || c == '_');
+                (SyntaxKind::IntegerLiteral, end)
+            }
+            '\\' if next.is_some_and(char::is_alphabetic) => {
+                let end = self.end_of_run(start + 1, |c| c.is_alphanumeric() || c == '_');
+                (SyntaxKind::Identifier, end)
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let end = self.end_of_run(start, |c| c.is_alphanumeric() || c == '_');
+                let word = chars[start..end].iter().collect::<String>();
+                let is_keyword = self.attribute_depth == 0
+                    && word.is_ascii()
+                    && !matches!(check_if_keyword(&word), SliceTokenKind::Identifier(_));
+                match is_keyword {
+                    true => (SyntaxKind::Keyword, end),
+                    false => (SyntaxKind::Identifier, end),
+                }
+            }
+            ':' if next == Some(':') => (SyntaxKind::Symbol, start + 2),
+            '-' if next == Some('>') => (SyntaxKind::Symbol, start + 2),
+            '[' | ']' => {
+                self.attribute_depth += if c == '[' { 1 } else { -1 };
+                (SyntaxKind::Symbol, if next == Some(c) { start + 2 } else { start + 1 })
+            }
+            '(' | ')' | '{' | '}' | '<' | '>' | ',' | ':' | '=' | '?' | '-' | '+' | '*' | '|' | '&' | '^' | '~' => {
+                (SyntaxKind::Symbol, start + 1)
+            }
+            _ => (SyntaxKind::Unknown, start + 1),
+        }
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = SyntaxToken;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.chars.len() {
+            return None;
+        }
+        let (kind, end) = self.lex_token();
+
+        // Compute where the token ends, by walking over its characters.
+        let token_chars = &self.chars[self.position..end];
+        let start_location = self.location;
+        for &c in token_chars {
+            match c {
+                '\n' => self.location = Location::from((self.location.row + 1, 1)),
+                _ => self.location.col += 1,
+            }
+        }
+        if kind != SyntaxKind::Whitespace {
+            self.is_start_of_line = false;
+        } else if token_chars.contains(&'\n') {
+            self.is_start_of_line = true;
+        }
+
+        let text = token_chars.iter().collect();
+        self.position = end;
+        Some(SyntaxToken {
+            kind,
+            text,
+            span: Span::new(start_location, self.location, self.file_name),
+        })
+    }
+}
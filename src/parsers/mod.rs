@@ -2,41 +2,105 @@
 
 //! TODO write a comment about how parsing works in Slice.
 
-// We only export the parsers and keep all the other logic private.
+// We only export the parsers (and the lossless tokenizer) and keep all the other logic private.
 pub use self::comments::parser::CommentParser;
 pub use self::preprocessor::parser::Preprocessor;
 pub use self::slice::parser::Parser;
 
+pub mod fuzzing;
+pub mod lossless;
+
 mod comments;
 mod common;
 mod preprocessor;
 mod slice;
 
+use self::slice::lexer::Lexer;
 use crate::ast::Ast;
 use crate::compilation_state::CompilationState;
 use crate::diagnostics::{Diagnostic, Diagnostics, Error};
 use crate::slice_file::SliceFile;
+use crate::timings::FileTiming;
 use std::collections::HashSet;
+use std::time::Instant;
+
+/// Returns true if the provided identifier is a Slice keyword (including contextual keywords like `const`).
+pub(crate) fn is_keyword(identifier: &str) -> bool {
+    !matches!(
+        slice::lexer::check_if_keyword(identifier),
+        slice::tokens::TokenKind::Identifier(_)
+    )
+}
 
-pub fn parse_files(state: &mut CompilationState, symbols: &HashSet<String>) {
+/// Parses each of the files in `state`, recording how long each file took to parse in `state.timings`.
+/// If `measure_lexing` is true, each file is also lexed in a separate pass, to measure how long lexing takes.
+pub fn parse_files(state: &mut CompilationState, symbols: &HashSet<String>, measure_lexing: bool) {
     for file in &mut state.files {
+        crate::panic_handler::set_file(&file.relative_path);
+        let _span = tracing::debug_span!("file", path = file.relative_path).entered();
+
         // Attempt to parse the file.
         let mut diagnostics = Diagnostics::new();
-        parse_file(file, &mut state.ast, &mut diagnostics, symbols.clone());
+        let mut timing = FileTiming {
+            path: file.relative_path.clone(),
+            start: state.timings.elapsed(),
+            ..FileTiming::default()
+        };
+        parse_file(
+            file,
+            &mut state.ast,
+            &mut diagnostics,
+            symbols.clone(),
+            measure_lexing,
+            &mut timing,
+        );
 
-        // Store any diagnostics that were emitted during parsing.
+        // Store any diagnostics that were emitted during parsing, and how long it took.
         state.diagnostics.extend(diagnostics);
+        state.timings.files.push(timing);
     }
+
+    // Tuple structs are named after the member they're declared on, so these names could be used by other definitions.
+    // Now that every file has been parsed, any tuple structs with colliding names are given unique ones.
+    unsafe { state.ast.rename_colliding_synthesized_structs() };
 }
 
-fn parse_file(file: &mut SliceFile, ast: &mut Ast, diagnostics: &mut Diagnostics, mut symbols: HashSet<String>) {
+fn parse_file(
+    file: &mut SliceFile,
+    ast: &mut Ast,
+    diagnostics: &mut Diagnostics,
+    mut symbols: HashSet<String>,
+    measure_lexing: bool,
+    timing: &mut FileTiming,
+) {
     // Pre-process the file's raw text.
+    let start = Instant::now();
     let preprocessor = Preprocessor::new(&file.relative_path, &mut symbols, diagnostics);
-    let Ok(preprocessed_text) = preprocessor.parse_slice_file(file.raw_text.as_str()) else { return };
+    let Ok(preprocessed_text) = preprocessor.parse_slice_file(&*file.raw_text) else {
+        tracing::debug!("failed to preprocess file");
+        return;
+    };
+    let source_blocks = preprocessed_text.collect::<Vec<_>>();
+    timing.preprocess = start.elapsed();
+    tracing::debug!(source_blocks = source_blocks.len(), ?symbols, "preprocessed file");
+
+    // The parser lexes its input on demand, so to measure lexing on its own, we have to lex the file separately.
+    if measure_lexing {
+        let start = Instant::now();
+        Lexer::from(source_blocks.iter().copied()).for_each(drop);
+        timing.lex = start.elapsed();
+    }
 
     // Parse the preprocessed text.
+    let start = Instant::now();
     let parser = Parser::new(&file.relative_path, ast, diagnostics);
-    let Ok((mode, attributes, module, definitions)) = parser.parse_slice_file(preprocessed_text) else { return };
+    let parse_result = parser.parse_slice_file(source_blocks.into_iter());
+    timing.parse = start.elapsed().saturating_sub(timing.lex);
+    let Ok((mode, attributes, module, usings, module_aliases, definitions)) = parse_result else {
+        tracing::debug!("failed to parse file");
+        return;
+    };
+    tracing::debug!(?mode, definitions = definitions.len(), "parsed file");
 
     // Issue a syntax error if the user had definitions but forgot to declare a module.
     if !definitions.is_empty() && module.is_none() {
@@ -51,5 +115,7 @@ fn parse_file(file: &mut SliceFile, ast: &mut Ast, diagnostics: &mut Diagnostics
     file.mode = mode;
     file.module = module.map(|m| ast.add_named_element(m));
     file.attributes = attributes;
+    file.using_directives = usings;
+    file.module_aliases = module_aliases;
     file.contents = definitions;
 }
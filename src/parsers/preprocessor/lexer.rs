@@ -79,11 +79,13 @@ impl<'input> Lexer<'input> {
 
     /// Reads, consumes, and returns a string of alphanumeric characters from the buffer.
     /// After calling this function, the next char will be a non-alphanumeric character or `None` (end-of-buffer).
+    ///
+    /// Non-ASCII alphanumeric characters are consumed too, so that invalid identifiers can be reported in their entirety.
     fn read_identifier(&mut self) -> &'input str {
         let start_position = self.position;
 
         // Loop while the next character in the buffer is an alphanumeric or underscore.
-        while matches!(self.buffer.peek(), Some(c) if (c.is_ascii_alphanumeric() || *c == '_')) {
+        while matches!(self.buffer.peek(), Some(c) if (c.is_alphanumeric() || *c == '_')) {
             self.advance_buffer(); // Consume the character.
         }
 
@@ -185,9 +187,17 @@ impl<'input> Lexer<'input> {
                     }
                 }
             }
-            ch if ch.is_ascii_alphabetic() => {
+            ch if ch.is_alphabetic() => {
                 let identifier = self.read_identifier();
-                Some(Ok((start_location, TokenKind::Identifier(identifier), self.cursor)))
+                if identifier.is_ascii() {
+                    Some(Ok((start_location, TokenKind::Identifier(identifier), self.cursor)))
+                } else {
+                    // Identifiers can only contain ASCII characters.
+                    let error = ErrorKind::NonAsciiIdentifier {
+                        identifier: identifier.to_owned(),
+                    };
+                    Some(Err((start_location, error, self.cursor)))
+                }
             }
             ch if !ch.is_whitespace() => {
                 self.advance_buffer(); // Consume the unknown character.
@@ -46,11 +46,21 @@ fn construct_error_from(parse_error: ParseError, file_name: &str) -> Diagnostic
         }
 
         // Only the built-in lexer emits 'InvalidToken' errors. We use our own lexer so this is impossible.
-        ParseError::InvalidToken { .. } => panic!("impossible 'InvalidToken' encountered in preprocessor"),
+        // But we still report it instead of panicking, so that no input can crash the compiler.
+        ParseError::InvalidToken { location } => {
+            let message = "invalid token".to_owned();
+            Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(location, location, file_name))
+        }
 
         // Only rules that explicitly match 'EOF' or only match a finite number of tokens can emit this error.
         // None of our rules do, so this is impossible (there's no limit to the length of a slice file's contents).
-        ParseError::ExtraToken { .. } => panic!("impossible 'ExtraToken' encountered in preprocessor"),
+        // But we still report it instead of panicking, so that no input can crash the compiler.
+        ParseError::ExtraToken {
+            token: (start, token_kind, end),
+        } => {
+            let message = format!("unexpected token '{token_kind:?}'");
+            Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(start, end, file_name))
+        }
     }
 }
 
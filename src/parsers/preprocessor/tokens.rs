@@ -49,6 +49,11 @@ pub enum ErrorKind {
     /// Ex: `#if (foo & bar)`, '&' isn't valid, but '&&' is valid. The preprocessor will suggest '&&' to the user.
     UnknownSymbol { symbol: String, suggestion: Option<String> },
 
+    /// Returned when an identifier contains non-ASCII characters. Identifiers can only contain ASCII letters, digits,
+    /// and underscores.
+    /// Ex: `#define café`, 'é' isn't an ASCII character.
+    NonAsciiIdentifier { identifier: String },
+
     /// Returned when an unknown directive was specified.
     /// Ex: `#foo`, "foo" isn't a valid directive.
     UnknownDirective { keyword: String },
@@ -65,6 +70,10 @@ impl fmt::Display for ErrorKind {
                 Some(s) => write!(f, "unknown symbol '{symbol}', try using '{s}' instead"),
                 None => write!(f, "unknown symbol '{symbol}'"),
             },
+            Self::NonAsciiIdentifier { identifier } => write!(
+                f,
+                "invalid identifier '{identifier}': identifiers can only contain ASCII letters, digits, and underscores",
+            ),
             Self::UnknownDirective { keyword } => write!(f, "unknown preprocessor directive: '{keyword}'"),
             Self::MissingDirective => f.write_str("missing preprocessor directive"),
         }
@@ -9,13 +9,14 @@ use super::parser::Parser;
 use crate::ast::node::Node;
 use crate::diagnostics::{Diagnostic, Error};
 use crate::grammar::*;
+use crate::parsers::comments::check_for_duplicate_tags;
 use crate::parsers::CommentParser;
-use crate::slice_file::Span;
+use crate::slice_file::{Location, Span};
+use crate::utils::casing::to_pascal_case;
 use crate::utils::ptr_util::{OwnedPtr, WeakPtr};
 use crate::{downgrade_as, upcast_weak_as};
 use lalrpop_util::lalrpop_mod;
 use std::num::IntErrorKind;
-use std::ops::RangeInclusive;
 
 // Place the code generated by LALRPOP into a submodule named 'lalrpop'.
 lalrpop_mod!(
@@ -56,6 +57,19 @@ macro_rules! set_fields_for {
 // Convenience type for storing an unparsed doc comment. Each element of the vector is one line of the comment.
 type RawDocComment<'a> = Vec<(&'a str, Span)>;
 
+/// The type of a field or parameter, which is either a normal type reference, or an inline tuple type.
+/// Tuple types store their attributes, fields, optionality, and span, until they're desugared into a struct.
+pub enum MemberTypeRef {
+    TypeRef(TypeRef),
+    Tuple(Vec<WeakPtr<Attribute>>, Vec<OwnedPtr<Field>>, bool, Span),
+}
+
+/// The exceptions an operation can throw, as written in its throws clause.
+pub enum ExceptionSpecification {
+    Specific(Vec<TypeRef>),
+    AnyException(Span),
+}
+
 // Grammar Rule Functions
 
 fn handle_file_compilation_mode(
@@ -90,6 +104,81 @@ fn construct_file_compilation_mode(parser: &mut Parser, i: Identifier, span: Spa
     FileCompilationMode { version, span }
 }
 
+fn construct_implicit_using_directive(target: Identifier, span: Span) -> UsingDirective {
+    // If no alias was specified, the last segment of the target is used as the alias (`using A::B` is `using B = A::B`).
+    let last_segment = target.value.rsplit("::").next().unwrap();
+    let alias = Identifier {
+        value: last_segment.to_owned(),
+        span: target.span.clone(),
+    };
+    UsingDirective { alias, target, span }
+}
+
+fn check_using_directives(parser: &mut Parser, using_directives: &[UsingDirective]) {
+    // Each alias can only be declared once per file.
+    for (i, using_directive) in using_directives.iter().enumerate() {
+        let alias = &using_directive.alias;
+        if let Some(original) = using_directives[..i]
+            .iter()
+            .find(|other| other.alias.value == alias.value)
+        {
+            Diagnostic::new(Error::Redefinition {
+                identifier: alias.value.clone(),
+            })
+            .set_span(alias.span())
+            .add_note(
+                format!("'{}' was previously defined here", alias.value),
+                Some(original.alias.span()),
+            )
+            .push_into(parser.diagnostics);
+        }
+    }
+}
+
+/// The declarations which can appear between a file's module declaration and its definitions.
+enum FileHeader {
+    UsingDirective(UsingDirective),
+    ModuleAlias(ModuleAlias),
+}
+
+fn split_file_headers(parser: &mut Parser, file_headers: Vec<FileHeader>) -> (Vec<UsingDirective>, Vec<ModuleAlias>) {
+    let mut using_directives = Vec::new();
+    let mut module_aliases = Vec::new();
+    for file_header in file_headers {
+        match file_header {
+            FileHeader::UsingDirective(using_directive) => using_directives.push(using_directive),
+            FileHeader::ModuleAlias(module_alias) => module_aliases.push(module_alias),
+        }
+    }
+    check_using_directives(parser, &using_directives);
+    (using_directives, module_aliases)
+}
+
+fn construct_module_alias(parser: &mut Parser, alias: Identifier, target: Identifier, span: Span) -> ModuleAlias {
+    let scope = parser.current_scope.parser_scope.clone();
+    ModuleAlias {
+        alias,
+        target,
+        scope,
+        span,
+    }
+}
+
+fn report_module_body(parser: &mut Parser, span: Span) {
+    // Module declarations apply to the entire file, so they can't have bodies. We still parse the body's contents
+    // as if they were in the file, so any errors in them are also reported.
+    let error = Error::Syntax {
+        message: "module declarations cannot have bodies".to_owned(),
+    };
+    Diagnostic::new(error)
+        .set_span(&span)
+        .add_note(
+            "a module declaration applies to every definition after it in the file; remove the braces",
+            None,
+        )
+        .push_into(parser.diagnostics);
+}
+
 fn construct_module(
     parser: &mut Parser,
     (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
@@ -119,6 +208,7 @@ fn construct_struct(
     (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
     is_compact: bool,
     identifier: Identifier,
+    type_parameters: Vec<Identifier>,
     fields: Vec<OwnedPtr<Field>>,
     span: Span,
 ) -> OwnedPtr<Struct> {
@@ -127,11 +217,14 @@ fn construct_struct(
         identifier,
         fields: Vec::new(),
         is_compact,
+        type_parameters,
+        is_synthesized: false,
         scope: parser.current_scope.clone(),
         attributes,
         comment,
         span,
         supported_encodings: None, // Patched by the encoding patcher.
+        uses_classes: None,        // Patched by the class usage patcher.
     });
 
     // Add all the fields to the struct.
@@ -148,7 +241,7 @@ fn construct_exception(
     fields: Vec<OwnedPtr<Field>>,
     span: Span,
 ) -> OwnedPtr<Exception> {
-    let base = base_type.map(|type_ref| type_ref.downcast::<Exception>().unwrap());
+    let base = base_type.and_then(|type_ref| try_downcast_type_ref(parser, type_ref, "exception"));
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
 
     let mut exception_ptr = OwnedPtr::new(Exception {
@@ -160,6 +253,7 @@ fn construct_exception(
         comment,
         span,
         supported_encodings: None, // Patched by the encoding patcher.
+        uses_classes: None,        // Patched by the class usage patcher.
     });
 
     // Add all the fields to the exception.
@@ -177,7 +271,7 @@ fn construct_class(
     fields: Vec<OwnedPtr<Field>>,
     span: Span,
 ) -> OwnedPtr<Class> {
-    let base = base_type.map(|type_ref| type_ref.downcast::<Class>().unwrap());
+    let base = base_type.and_then(|type_ref| try_downcast_type_ref(parser, type_ref, "class"));
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
 
     let mut class_ptr = OwnedPtr::new(Class {
@@ -198,6 +292,73 @@ fn construct_class(
     class_ptr
 }
 
+fn construct_member_type_ref(parser: &mut Parser, member_identifier: &Identifier, type_ref: MemberTypeRef) -> TypeRef {
+    let (attributes, fields, is_optional, span) = match type_ref {
+        MemberTypeRef::TypeRef(type_ref) => return type_ref,
+        MemberTypeRef::Tuple(attributes, fields, is_optional, span) => (attributes, fields, is_optional, span),
+    };
+
+    // Tuple types are desugared into compact structs, defined in the enclosing module. The struct's name is generated
+    // from the scopes enclosing the member, and the member's identifier. Ex: `Point::pos` becomes `PointPos`.
+    // If another definition uses this name, the struct is renamed once every file has been parsed (see
+    // `Ast::rename_colliding_synthesized_structs`).
+    let mut struct_scope = Scope {
+        parser_scope: String::new(),
+        module: parser.current_scope.module.clone(),
+    };
+    if let Some(module_ptr) = &struct_scope.module {
+        struct_scope.parser_scope = module_ptr.borrow().nested_module_identifier().to_owned();
+    }
+    let enclosing_scopes = parser.current_scope.parser_scope[struct_scope.parser_scope.len()..].split("::");
+    let struct_name = enclosing_scopes
+        .chain(std::iter::once(member_identifier.value.as_str()))
+        .map(to_pascal_case)
+        .collect::<String>();
+
+    // The tuple's fields were parsed in the member's scope, so we move them into the synthesized struct's scope.
+    let mut fields_scope = struct_scope.clone();
+    fields_scope.push_scope(&struct_name);
+    let mut fields = fields;
+    for field in &mut fields {
+        unsafe { field.borrow_mut().scope = fields_scope.clone() };
+    }
+
+    let identifier = Identifier {
+        value: struct_name,
+        span: span.clone(),
+    };
+    let mut struct_ptr = OwnedPtr::new(Struct {
+        identifier,
+        fields: Vec::new(),
+        is_compact: true,
+        type_parameters: Vec::new(),
+        is_synthesized: true,
+        scope: struct_scope,
+        attributes: Vec::new(),
+        comment: None,
+        span: span.clone(),
+        supported_encodings: None, // Patched by the encoding patcher.
+        uses_classes: None,        // Patched by the class usage patcher.
+    });
+    set_fields_for!(struct_ptr, fields, parser);
+
+    let weak_ptr = parser.ast.add_named_element(struct_ptr);
+    parser
+        .synthesized_definitions
+        .push(Definition::Struct(weak_ptr.clone()));
+
+    TypeRef {
+        definition: TypeRefDefinition::Patched(upcast_weak_as!(weak_ptr, dyn Type)),
+        is_optional,
+        scope: parser.current_scope.clone(),
+        attributes,
+        inherited_attributes: Vec::new(),
+        type_aliases: Vec::new(),
+        type_arguments: Vec::new(),
+        span,
+    }
+}
+
 pub fn construct_field(
     parser: &mut Parser,
     (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
@@ -211,6 +372,7 @@ pub fn construct_field(
         identifier,
         data_type,
         tag,
+        tag_format: None,                        // Patched by the tag format patcher.
         parent: WeakPtr::create_uninitialized(), // Patched by its container.
         scope: parser.current_scope.clone(),
         attributes,
@@ -230,7 +392,7 @@ fn construct_interface(
     let bases = bases
         .unwrap_or_default() // Create an empty vector if no bases were specified.
         .into_iter()
-        .map(|base| base.downcast::<Interface>().unwrap())
+        .filter_map(|base| try_downcast_type_ref(parser, base, "interface"))
         .collect::<Vec<_>>();
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
 
@@ -259,17 +421,21 @@ fn construct_operation(
     identifier: Identifier,
     parameters: Vec<OwnedPtr<Parameter>>,
     return_type: Option<Vec<OwnedPtr<Parameter>>>,
-    throws_clause: Option<Vec<TypeRef>>,
+    throws_clause: Option<ExceptionSpecification>,
     span: Span,
 ) -> OwnedPtr<Operation> {
     // If no return type was provided set the return type to an empty Vec.
     let return_type = return_type.unwrap_or_default();
 
     // If no throws clause was present, set the exception specification to an empty Vec.
-    let throws_clause = throws_clause.unwrap_or_default();
+    let (throws_clause, throws_any_exception) = match throws_clause {
+        Some(ExceptionSpecification::Specific(type_refs)) => (type_refs, None),
+        Some(ExceptionSpecification::AnyException(span)) => (Vec::new(), Some(span)),
+        None => (Vec::new(), None),
+    };
     let exception_specification = throws_clause
         .into_iter()
-        .map(|type_ref| type_ref.downcast::<Exception>().unwrap())
+        .filter_map(|type_ref| try_downcast_type_ref(parser, type_ref, "exception"))
         .collect();
 
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
@@ -279,6 +445,7 @@ fn construct_operation(
         parameters: Vec::new(),
         return_type: Vec::new(),
         exception_specification,
+        throws_any_exception,
         is_idempotent,
         encoding: parser.compilation_mode,
         parent: WeakPtr::create_uninitialized(), // Patched by its container.
@@ -319,7 +486,9 @@ fn construct_parameter(
         identifier,
         data_type,
         tag,
+        tag_format: None, // Patched by the tag format patcher.
         is_streamed,
+        is_synthesized: false,
         parent: WeakPtr::create_uninitialized(), // Patched by its container.
         scope: parser.current_scope.clone(),
         attributes,
@@ -344,7 +513,9 @@ fn construct_single_return_type(
         identifier: dummy_identifier,
         data_type,
         tag,
+        tag_format: None, // Patched by the tag format patcher.
         is_streamed,
+        is_synthesized: true,
         parent: WeakPtr::create_uninitialized(), // Patched by its container.
         scope: parser.current_scope.clone(),
         attributes: Vec::new(),
@@ -359,6 +530,25 @@ fn check_return_tuple(parser: &mut Parser, return_tuple: &[OwnedPtr<Parameter>],
     }
 }
 
+fn report_missing_comma(parser: &mut Parser, span: Span) {
+    Diagnostic::new(Error::Syntax {
+        message: "missing ',' between list elements".to_owned(),
+    })
+    .set_span(&span)
+    .add_note("elements of this list must be separated by a ','", None)
+    .push_into(parser.diagnostics);
+}
+
+fn check_for_repeated_commas(parser: &mut Parser, count: usize, span: Span) {
+    if count > 1 {
+        Diagnostic::new(Error::Syntax {
+            message: "list elements must be separated by a single ','".to_owned(),
+        })
+        .set_span(&span)
+        .push_into(parser.diagnostics);
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn construct_enum(
     parser: &mut Parser,
@@ -370,7 +560,7 @@ fn construct_enum(
     enumerators: Vec<OwnedPtr<Enumerator>>,
     span: Span,
 ) -> OwnedPtr<Enum> {
-    let underlying = underlying_type.map(|type_ref| type_ref.downcast::<Primitive>().unwrap());
+    let underlying = underlying_type.and_then(|type_ref| try_downcast_type_ref(parser, type_ref, "primitive"));
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
 
     let mut enum_ptr = OwnedPtr::new(Enum {
@@ -384,14 +574,12 @@ fn construct_enum(
         comment,
         span,
         supported_encodings: None, // Patched by the encoding patcher.
+        uses_classes: None,        // Patched by the class usage patcher.
     });
 
     // Add all the enumerators to the enum.
     set_children_for!(enum_ptr, enumerators, parser);
 
-    // Clear the `previous_enumerator_value` field since this is the end of the enum.
-    parser.previous_enumerator_value = None;
-
     enum_ptr
 }
 
@@ -400,17 +588,18 @@ fn construct_enumerator(
     (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
     identifier: Identifier,
     fields: Option<Vec<OwnedPtr<Field>>>,
-    enumerator_value: Option<Integer<i128>>,
+    enumerator_value: Option<ConstantExpression>,
     span: Span,
 ) -> OwnedPtr<Enumerator> {
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
 
-    // If the enumerator was given an explicit value, use it. Otherwise an implicit value is calculated as follows:
-    // If this is the first enumerator in the enum (`previous_enumerator_value` is `None`), its value is set to 0.
-    // Otherwise, this enumerator's value is set to the previous enumerator's value plus 1.
+    // If the enumerator was given an explicit value, use it. Otherwise, it's given an implicit value, which is computed
+    // (along with any explicit values) by the constant patcher, after parsing is complete.
+    // Explicit values which are just the identifier of another enumerator in the same enum are turned into aliases by
+    // the constant patcher too, since the aliased enumerator might not have been parsed yet.
     let value = match enumerator_value {
-        Some(integer) => EnumeratorValue::Explicit(integer),
-        None => EnumeratorValue::Implicit(parser.previous_enumerator_value.map_or(0, |x| x.wrapping_add(1))),
+        Some(expression) => EnumeratorValue::Explicit(defer_evaluation(expression)),
+        None => EnumeratorValue::Implicit(0), // Placeholder value
     };
 
     let mut enumerator = OwnedPtr::new(Enumerator {
@@ -435,9 +624,6 @@ fn construct_enumerator(
             enumerator.borrow_mut().fields = Some(converted_fields.collect());
         }
     }
-
-    // Update `previous_enumerator_value` to be this enumerator's value.
-    parser.previous_enumerator_value = Some(enumerator.borrow().value());
     enumerator
 }
 
@@ -477,22 +663,87 @@ fn construct_type_alias(
     })
 }
 
+fn construct_constant(
+    parser: &mut Parser,
+    (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
+    identifier: Identifier,
+    expression: ConstantExpression,
+    span: Span,
+) -> OwnedPtr<Constant> {
+    let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
+    OwnedPtr::new(Constant {
+        identifier,
+        value: defer_evaluation(expression),
+        scope: parser.current_scope.clone(),
+        attributes,
+        comment,
+        span,
+    })
+}
+
 fn construct_type_ref(
-    parser: &Parser,
+    parser: &mut Parser,
     attributes: Vec<WeakPtr<Attribute>>,
     definition: TypeRefDefinition,
+    type_arguments: Vec<TypeRef>,
     is_optional: bool,
     span: Span,
 ) -> TypeRef {
+    // Only user-defined types can be generic, and these aren't patched until after parsing.
+    if let TypeRefDefinition::Patched(ptr) = &definition {
+        if !type_arguments.is_empty() {
+            let identifier = ptr.borrow().type_string();
+            Diagnostic::new(Error::WrongNumberOfTypeArguments {
+                identifier: identifier.clone(),
+                expected: 0,
+                actual: type_arguments.len(),
+            })
+            .set_span(&span)
+            .add_note(format!("'{identifier}' isn't a generic struct"), None)
+            .push_into(parser.diagnostics);
+        }
+    }
+
     TypeRef {
         definition,
         is_optional,
         scope: parser.current_scope.clone(),
         attributes,
+        inherited_attributes: Vec::new(),
+        type_aliases: Vec::new(),
+        type_arguments,
         span,
     }
 }
 
+/// Attempts to downcast a type reference to a specific kind of type, reporting a type mismatch if it can't be.
+///
+/// Only type references that are patched during parsing (primitives and anonymous types) can fail this downcast.
+/// Other type references are still unpatched, and are checked by the type-ref patcher once they've been resolved.
+fn try_downcast_type_ref<T: Element + 'static>(
+    parser: &mut Parser,
+    type_ref: TypeRef,
+    expected: &str,
+) -> Option<TypeRef<T>> {
+    let result = type_ref.downcast::<T>();
+    if result.is_err() {
+        // Describe the type the same way the type-ref patcher does, so these errors match those reported for aliases.
+        let actual = match type_ref.definition().concrete_type() {
+            Types::Primitive(_) => "primitive",
+            Types::ResultType(_) => "result type",
+            _ => type_ref.definition().kind(),
+        };
+        Diagnostic::new(Error::TypeMismatch {
+            expected: expected.to_owned(),
+            actual: actual.to_owned(),
+            is_concrete: true,
+        })
+        .set_span(&type_ref.span)
+        .push_into(parser.diagnostics);
+    }
+    result.ok()
+}
+
 fn primitive_to_type_ref_definition(parser: &Parser, primitive: Primitive) -> TypeRefDefinition {
     // These unwraps are safe because the primitive types are always defined in the AST.
     let node = parser.ast.find_node(primitive.kind()).unwrap();
@@ -518,13 +769,51 @@ fn construct_unpatched_type_ref_definition(mut identifier: Identifier) -> TypeRe
 fn construct_attribute(
     parser: &mut Parser,
     directive: Identifier,
-    arguments: Option<Vec<String>>,
+    arguments: Option<Vec<(String, Span, Option<Literal>)>>,
     span: Span,
 ) -> WeakPtr<Attribute> {
-    let attribute = Attribute::new(directive.value, arguments.unwrap_or_default(), span);
+    let (mut args, mut arg_spans, mut arg_literals) = (Vec::new(), Vec::new(), Vec::new());
+    for (arg, arg_span, arg_literal) in arguments.unwrap_or_default() {
+        args.push(arg);
+        arg_spans.push(arg_span);
+        arg_literals.push(arg_literal);
+    }
+    let attribute = Attribute::new(directive.value, args, arg_spans, arg_literals, span);
     parser.ast.add_element(OwnedPtr::new(attribute))
 }
 
+fn construct_string_argument(s: &str, span: Span) -> (String, Span, Option<Literal>) {
+    let value = unescape_string_literal(s);
+    let literal = StringLiteral {
+        value: value.clone(),
+        lexeme: format!("\"{s}\""), // The lexer strips the quotes from string literals, so we add them back.
+        span: span.clone(),
+    };
+    (value, span, Some(Literal::String(literal)))
+}
+
+fn construct_identifier_argument(identifier: &str, span: Span) -> (String, Span, Option<Literal>) {
+    let value = match identifier {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    };
+    let literal = value.map(|value| {
+        Literal::Bool(BoolLiteral {
+            value,
+            lexeme: identifier.to_owned(),
+            span: span.clone(),
+        })
+    });
+    (identifier.to_owned(), span, literal)
+}
+
+fn construct_integer_argument(integer: &str, span: Span) -> (String, Span, Option<Literal>) {
+    // Attributes validate their own arguments, so integers which can't be parsed are left as plain arguments here.
+    let literal = parse_integer_literal(integer, &span).ok().map(Literal::Integer);
+    (integer.to_owned(), span, literal)
+}
+
 fn unescape_string_literal(s: &str) -> String {
     // Flag that stores whether the next character we read is being escaped.
     let mut is_escaped = false;
@@ -542,56 +831,67 @@ fn unescape_string_literal(s: &str) -> String {
 }
 
 fn try_parse_integer(parser: &mut Parser, s: &str, span: Span) -> Integer<i128> {
+    let (value, literal) = match parse_integer_literal(s, &span) {
+        Ok(literal) => (literal.value, Some(literal)),
+        Err(error) => {
+            Diagnostic::new(error).set_span(&span).push_into(parser.diagnostics);
+            (0, None) // Dummy value
+        }
+    };
+    Integer {
+        value,
+        literal,
+        expression: None,
+        span,
+    }
+}
+
+/// Parses an integer literal, returning an error if it has invalid digits, or doesn't fit in an `i128`.
+fn parse_integer_literal(s: &str, span: &Span) -> Result<IntegerLiteral, Error> {
     // Remove any underscores from the integer literal before trying to parse it.
     let sanitized = s.replace('_', "");
 
     // Check the literal for a base prefix. If present, remove it and set the base.
     // "0b" = binary, "0x" = hexadecimal, otherwise we assume it's decimal.
-    let (literal, base) = match sanitized {
-        _ if sanitized.starts_with("0b") => (&sanitized[2..], 2),
-        _ if sanitized.starts_with("0x") => (&sanitized[2..], 16),
-        _ => (sanitized.as_str(), 10),
-    };
-
-    let value = match i128::from_str_radix(literal, base) {
-        Ok(x) => x,
-        Err(err) => {
-            let e = match err.kind() {
-                IntErrorKind::InvalidDigit => Error::InvalidIntegerLiteral { base },
-                _ => Error::IntegerLiteralOverflows,
-            };
-            Diagnostic::new(e).set_span(&span).push_into(parser.diagnostics);
-            0 // Dummy value
-        }
+    let radix = match sanitized {
+        _ if sanitized.starts_with("0b") => Radix::Binary,
+        _ if sanitized.starts_with("0x") => Radix::Hexadecimal,
+        _ => Radix::Decimal,
     };
+    let (digits, base) = (&sanitized[radix.prefix().len()..], radix.base());
 
-    Integer { value, span }
+    let value = i128::from_str_radix(digits, base).map_err(|err| match err.kind() {
+        IntErrorKind::InvalidDigit => Error::InvalidIntegerLiteral { base },
+        _ => Error::IntegerLiteralOverflows,
+    })?;
+    Ok(IntegerLiteral {
+        value,
+        radix,
+        lexeme: s.to_owned(),
+        span: span.clone(),
+    })
 }
 
-fn parse_tag_value(parser: &mut Parser, i: Integer<i128>) -> Integer<u32> {
-    // Verify that the provided integer is a valid tag id.
-    if !RangeInclusive::new(0, i32::MAX as i128).contains(&i.value) {
-        let diagnostic = Diagnostic::new(Error::TagValueOutOfBounds).set_span(&i.span);
-        diagnostic.push_into(parser.diagnostics);
+/// Reports an error if the chevrons of a shift operator aren't adjacent (Ex: `1 < < 2`).
+fn check_shift_operator(parser: &mut Parser, operator: &str, first_end: Location, second_start: Location, span: Span) {
+    if first_end != second_start {
+        Diagnostic::new(Error::Syntax {
+            message: format!("expected '{operator}', but found whitespace between its chevrons"),
+        })
+        .set_span(&span)
+        .push_into(parser.diagnostics);
     }
-
-    // Cast the integer to a `u32` since it most closely matches the allowed range of tags.
-    // It's fine if the value doesn't fit, the cast will just give us a dummy value.
-    let value = i.value as u32;
-    Integer { value, span: i.span }
 }
 
-fn parse_compact_id_value(parser: &mut Parser, i: Integer<i128>) -> Integer<u32> {
-    // Verify that the provided integer is a valid compact id.
-    if !RangeInclusive::new(0, i32::MAX as i128).contains(&i.value) {
-        let diagnostic = Diagnostic::new(Error::CompactIdOutOfBounds).set_span(&i.span);
-        diagnostic.push_into(parser.diagnostics);
+/// Wraps the provided expression in an integer, so it can be evaluated after parsing, by the constant patcher.
+/// Until then, the integer's value is a placeholder.
+fn defer_evaluation<T: std::fmt::Debug + Default>(expression: ConstantExpression) -> Integer<T> {
+    Integer {
+        value: T::default(),
+        literal: None,
+        span: expression.span().clone(),
+        expression: Some(Box::new(expression)),
     }
-
-    // Cast the integer to a `u32` since it most closely matches the allowed range of compact ids.
-    // It's fine if the value doesn't fit, the cast will just give us a dummy value.
-    let value = i.value as u32;
-    Integer { value, span: i.span }
 }
 
 fn parse_doc_comment(parser: &mut Parser, identifier: &str, raw_comment: RawDocComment) -> Option<DocComment> {
@@ -601,6 +901,8 @@ fn parse_doc_comment(parser: &mut Parser, identifier: &str, raw_comment: RawDocC
     } else {
         let scoped_identifier = get_scoped_identifier(identifier, &parser.current_scope.parser_scope);
         let comment_parser = CommentParser::new(parser.file_name, &scoped_identifier, parser.diagnostics);
-        comment_parser.parse_doc_comment(raw_comment).ok()
+        let comment = comment_parser.parse_doc_comment(raw_comment).ok()?;
+        check_for_duplicate_tags(&comment, &scoped_identifier, parser.diagnostics);
+        Some(comment)
     }
 }
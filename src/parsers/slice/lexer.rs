@@ -39,6 +39,14 @@ where
     /// This flag stores whether the lexer is currently lexing the inside of an attribute.
     /// It is set to true upon encountering an '[' character, and false upon an ']' character.
     attribute_mode: bool,
+
+    /// How many braces the lexer is currently nested within.
+    /// Contextual keywords (see [Lexer::is_declaration_start]) are only recognized outside of braces.
+    brace_depth: usize,
+
+    /// This flag stores whether the last token the lexer returned was the `throws` keyword.
+    /// `AnyException` is a contextual keyword, which is only recognized immediately after `throws`.
+    is_after_throws: bool,
 }
 
 impl<'input, T> Lexer<'input, T>
@@ -61,6 +69,8 @@ where
             buffer,
             cursor: start_location,
             attribute_mode: false,
+            brace_depth: 0,
+            is_after_throws: false,
         }
     }
 
@@ -118,6 +128,30 @@ where
         &self.current_block.content[start_position..end_position]
     }
 
+    /// Reads, consumes, and returns an identifier from the buffer.
+    /// After calling this function, the next character will be a non-alphanumeric character or `None` (end of buffer).
+    ///
+    /// Identifiers can only contain ASCII letters, digits, and underscores. But so that invalid identifiers can be
+    /// reported in their entirety, this function consumes any alphanumeric characters, and returns an error if any of
+    /// them were non-ASCII.
+    fn read_identifier(&mut self) -> Result<&'input str, ErrorKind> {
+        let start_position = self.get_position();
+
+        // Loop while the next character in the buffer is alphanumeric or an underscore.
+        while matches!(self.buffer.peek(), Some((_, c)) if (c.is_alphanumeric() || *c == '_')) {
+            self.advance_buffer(); // Consume the alphanumeric character.
+        }
+
+        let end_position = self.get_position();
+        let identifier = &self.current_block.content[start_position..end_position];
+        match identifier.is_ascii() {
+            true => Ok(identifier),
+            false => Err(ErrorKind::NonAsciiIdentifier {
+                identifier: identifier.to_owned(),
+            }),
+        }
+    }
+
     /// Reads, consumes, and returns a string literal from the buffer.
     /// String literals are any characters contained within a pair of un-escaped double-quotes.
     /// The returned string doesn't include the opening and closing quotation marks, just the content between them.
@@ -182,49 +216,28 @@ where
         Err(ErrorKind::UnterminatedBlockComment)
     }
 
-    /// Checks if an identifier corresponds to a Slice keyword. If it does,
-    /// return the keyword's token. Otherwise, return an `[TokenKind::Identifier]` token.
-    fn check_if_keyword(identifier: &str) -> TokenKind<'_> {
-        debug_assert!(identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
-        debug_assert!(!identifier.is_empty());
-
-        match identifier {
-            "module" => TokenKind::ModuleKeyword,
-            "struct" => TokenKind::StructKeyword,
-            "exception" => TokenKind::ExceptionKeyword,
-            "class" => TokenKind::ClassKeyword,
-            "interface" => TokenKind::InterfaceKeyword,
-            "enum" => TokenKind::EnumKeyword,
-            "custom" => TokenKind::CustomKeyword,
-            "typealias" => TokenKind::TypeAliasKeyword,
-            "Result" => TokenKind::ResultKeyword,
-            "Sequence" => TokenKind::SequenceKeyword,
-            "Dictionary" => TokenKind::DictionaryKeyword,
-            "bool" => TokenKind::BoolKeyword,
-            "int8" => TokenKind::Int8Keyword,
-            "uint8" => TokenKind::UInt8Keyword,
-            "int16" => TokenKind::Int16Keyword,
-            "uint16" => TokenKind::UInt16Keyword,
-            "int32" => TokenKind::Int32Keyword,
-            "uint32" => TokenKind::UInt32Keyword,
-            "varint32" => TokenKind::VarInt32Keyword,
-            "varuint32" => TokenKind::VarUInt32Keyword,
-            "int64" => TokenKind::Int64Keyword,
-            "uint64" => TokenKind::UInt64Keyword,
-            "varint62" => TokenKind::VarInt62Keyword,
-            "varuint62" => TokenKind::VarUInt62Keyword,
-            "float32" => TokenKind::Float32Keyword,
-            "float64" => TokenKind::Float64Keyword,
-            "string" => TokenKind::StringKeyword,
-            "AnyClass" => TokenKind::AnyClassKeyword,
-            "compact" => TokenKind::CompactKeyword,
-            "idempotent" => TokenKind::IdempotentKeyword,
-            "mode" => TokenKind::ModeKeyword,
-            "stream" => TokenKind::StreamKeyword,
-            "tag" => TokenKind::TagKeyword,
-            "throws" => TokenKind::ThrowsKeyword,
-            "unchecked" => TokenKind::UncheckedKeyword,
-            ident => TokenKind::Identifier(ident),
+    /// Returns true if the lexer is positioned right after the keyword of a declaration, and false otherwise.
+    ///
+    /// Some keywords (`const` and `using`) are contextual: since they can only start top-level declarations, they can
+    /// still be used as identifiers everywhere else (Ex: `using: int32`). So we only treat them as keywords when they're
+    /// outside of any braces, and are followed by an identifier (the name of the declaration, or of the module being
+    /// used), or by a '::' (the start of a global identifier). Any whitespace and comments in between are skipped.
+    fn is_declaration_start(&mut self) -> bool {
+        if self.brace_depth > 0 {
+            return false;
+        }
+
+        let position = self.get_position();
+        let remaining = trim_whitespace_and_comments(&self.current_block.content[position..]);
+        let word_length = remaining
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(remaining.len());
+        match &remaining[..word_length] {
+            "" => remaining.starts_with('\\') || remaining.starts_with("::"), // Escaped identifiers start with '\'.
+            word => {
+                let is_identifier = matches!(check_if_keyword(word), TokenKind::Identifier(_));
+                is_identifier && !word.starts_with(|c: char| c.is_ascii_digit())
+            }
         }
     }
 
@@ -270,8 +283,14 @@ where
                     Some(Ok((start_location, TokenKind::RightBracket, self.cursor)))
                 }
             }
-            '{' => self.return_simple_token(TokenKind::LeftBrace, start_location),
-            '}' => self.return_simple_token(TokenKind::RightBrace, start_location),
+            '{' => {
+                self.brace_depth += 1;
+                self.return_simple_token(TokenKind::LeftBrace, start_location)
+            }
+            '}' => {
+                self.brace_depth = self.brace_depth.saturating_sub(1);
+                self.return_simple_token(TokenKind::RightBrace, start_location)
+            }
             '<' => self.return_simple_token(TokenKind::LeftChevron, start_location),
             '>' => self.return_simple_token(TokenKind::RightChevron, start_location),
             ',' => self.return_simple_token(TokenKind::Comma, start_location),
@@ -297,6 +316,12 @@ where
                     Some(Ok((start_location, TokenKind::Minus, self.cursor)))
                 }
             }
+            '+' => self.return_simple_token(TokenKind::Plus, start_location),
+            '*' => self.return_simple_token(TokenKind::Star, start_location),
+            '|' => self.return_simple_token(TokenKind::Pipe, start_location),
+            '&' => self.return_simple_token(TokenKind::Ampersand, start_location),
+            '^' => self.return_simple_token(TokenKind::Caret, start_location),
+            '~' => self.return_simple_token(TokenKind::Tilde, start_location),
             '"' => {
                 let result = self.read_string_literal();
                 Some(match result {
@@ -352,9 +377,11 @@ where
             '\\' => {
                 self.advance_buffer(); // Consume the '\' character.
                                        // Check if the next character could be the start of an identifier.
-                if matches!(self.buffer.peek(), Some((_, ch)) if ch.is_ascii_alphabetic()) {
-                    let identifier = self.read_alphanumeric();
-                    Some(Ok((start_location, TokenKind::Identifier(identifier), self.cursor)))
+                if matches!(self.buffer.peek(), Some((_, ch)) if ch.is_alphabetic()) {
+                    Some(match self.read_identifier() {
+                        Ok(identifier) => Ok((start_location, TokenKind::Identifier(identifier), self.cursor)),
+                        Err(err) => Err((start_location, err, self.cursor)),
+                    })
                 } else {
                     // The token is just "\", indicating a syntax error. '\' on its own isn't a valid Slice token.
                     let error = ErrorKind::UnknownSymbol {
@@ -364,12 +391,22 @@ where
                     Some(Err((start_location, error, self.cursor)))
                 }
             }
-            _ if c.is_ascii_alphabetic() => {
+            _ if c.is_alphabetic() => {
+                let identifier = match self.read_identifier() {
+                    Ok(identifier) => identifier,
+                    Err(err) => return Some(Err((start_location, err, self.cursor))),
+                };
                 let token = if self.attribute_mode {
                     // If we're lexing an attribute, return the identifier as-is, without checking if it's a keyword.
-                    TokenKind::Identifier(self.read_alphanumeric())
+                    TokenKind::Identifier(identifier)
                 } else {
-                    Self::check_if_keyword(self.read_alphanumeric())
+                    match check_if_keyword(identifier) {
+                        TokenKind::ConstKeyword | TokenKind::UsingKeyword if !self.is_declaration_start() => {
+                            TokenKind::Identifier(identifier)
+                        }
+                        TokenKind::AnyExceptionKeyword if !self.is_after_throws => TokenKind::Identifier(identifier),
+                        token => token,
+                    }
                 };
                 Some(Ok((start_location, token, self.cursor)))
             }
@@ -409,6 +446,7 @@ where
             while let Some((_, c)) = self.buffer.peek().cloned() {
                 // If the lexer has lexed a token or encountered an error, return it.
                 if let Some(token) = self.lex_next_slice_token(c) {
+                    self.is_after_throws = matches!(token, Ok((_, TokenKind::ThrowsKeyword, _)));
                     return Some(token);
                 }
             }
@@ -455,3 +493,70 @@ impl<'input> From<&'input str> for Lexer<'input, std::iter::Once<SourceBlock<'in
         Lexer::new(std::iter::once(source_block))
     }
 }
+
+/// Returns the provided string without any leading whitespace, line comments, or block comments.
+/// This is used for looking ahead in the buffer without consuming anything (see [Lexer::is_declaration_start]).
+fn trim_whitespace_and_comments(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        if let Some(rest) = s.strip_prefix("//") {
+            s = rest.find('\n').map_or("", |i| &rest[i..]);
+        } else if let Some(rest) = s.strip_prefix("/*") {
+            s = rest.find("*/").map_or("", |i| &rest[i + 2..]);
+        } else {
+            return s;
+        }
+    }
+}
+
+/// Checks if an identifier corresponds to a Slice keyword. If it does,
+/// return the keyword's token. Otherwise, return an `[TokenKind::Identifier]` token.
+///
+/// Contextual keywords (like `using` and `AnyException`) are always returned as keywords by this function; it's up to
+/// the caller to check whether they're being used as one (see [Lexer::is_declaration_start]).
+pub(crate) fn check_if_keyword(identifier: &str) -> TokenKind<'_> {
+    debug_assert!(identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+    debug_assert!(!identifier.is_empty());
+
+    match identifier {
+        "module" => TokenKind::ModuleKeyword,
+        "struct" => TokenKind::StructKeyword,
+        "exception" => TokenKind::ExceptionKeyword,
+        "class" => TokenKind::ClassKeyword,
+        "interface" => TokenKind::InterfaceKeyword,
+        "enum" => TokenKind::EnumKeyword,
+        "custom" => TokenKind::CustomKeyword,
+        "typealias" => TokenKind::TypeAliasKeyword,
+        "const" => TokenKind::ConstKeyword,
+        "Result" => TokenKind::ResultKeyword,
+        "Sequence" => TokenKind::SequenceKeyword,
+        "Dictionary" => TokenKind::DictionaryKeyword,
+        "bool" => TokenKind::BoolKeyword,
+        "int8" => TokenKind::Int8Keyword,
+        "uint8" => TokenKind::UInt8Keyword,
+        "int16" => TokenKind::Int16Keyword,
+        "uint16" => TokenKind::UInt16Keyword,
+        "int32" => TokenKind::Int32Keyword,
+        "uint32" => TokenKind::UInt32Keyword,
+        "varint32" => TokenKind::VarInt32Keyword,
+        "varuint32" => TokenKind::VarUInt32Keyword,
+        "int64" => TokenKind::Int64Keyword,
+        "uint64" => TokenKind::UInt64Keyword,
+        "varint62" => TokenKind::VarInt62Keyword,
+        "varuint62" => TokenKind::VarUInt62Keyword,
+        "float32" => TokenKind::Float32Keyword,
+        "float64" => TokenKind::Float64Keyword,
+        "string" => TokenKind::StringKeyword,
+        "AnyClass" => TokenKind::AnyClassKeyword,
+        "AnyException" => TokenKind::AnyExceptionKeyword,
+        "compact" => TokenKind::CompactKeyword,
+        "idempotent" => TokenKind::IdempotentKeyword,
+        "mode" => TokenKind::ModeKeyword,
+        "stream" => TokenKind::StreamKeyword,
+        "tag" => TokenKind::TagKeyword,
+        "throws" => TokenKind::ThrowsKeyword,
+        "unchecked" => TokenKind::UncheckedKeyword,
+        "using" => TokenKind::UsingKeyword,
+        ident => TokenKind::Identifier(ident),
+    }
+}
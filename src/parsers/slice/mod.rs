@@ -32,8 +32,14 @@ fn construct_error_from(parse_error: ParseError, file_name: &str) -> Diagnostic
             token: (start, token_kind, end),
             expected,
         } => {
+            let note = note_for_unexpected_token(&token_kind);
             let message = generate_message(&expected, token_kind);
-            Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(start, end, file_name))
+            let diagnostic = Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(start, end, file_name));
+
+            match note {
+                Some(note) => diagnostic.add_note(note, None),
+                None => diagnostic,
+            }
         }
 
         // The parser hit EOF in the middle of a grammar rule.
@@ -42,7 +48,40 @@ fn construct_error_from(parse_error: ParseError, file_name: &str) -> Diagnostic
             Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(location, location, file_name))
         }
 
-        _ => unreachable!("impossible error encountered in Slice parser: '{parse_error:?}'"),
+        // Only the built-in lexer emits 'InvalidToken' errors, and none of our rules can emit 'ExtraToken' errors.
+        // Even though these are impossible, we report them instead of panicking, so no input can crash the compiler.
+        ParseError::InvalidToken { location } => {
+            let message = "invalid token".to_owned();
+            Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(location, location, file_name))
+        }
+        ParseError::ExtraToken {
+            token: (start, token_kind, end),
+        } => {
+            let message = format!("unexpected token '{token_kind}'");
+            Diagnostic::new(Error::Syntax { message }).set_span(&Span::new(start, end, file_name))
+        }
+    }
+}
+
+/// Returns a note explaining how to fix common mistakes that cause the provided token to be unexpected, if any.
+fn note_for_unexpected_token(token_kind: &TokenKind) -> Option<&'static str> {
+    match token_kind {
+        // A common mistake is trying to declare multiple modules in a single file.
+        TokenKind::ModuleKeyword => {
+            Some("each file can only have one module declaration; put definitions in other modules in separate files")
+        }
+
+        // Modifiers are often misplaced, or applied to elements that don't support them.
+        TokenKind::StreamKeyword => Some(
+            "'stream' can only be applied to the type of an operation's last parameter or return type, as in \
+             'op(data: stream uint8)'",
+        ),
+        TokenKind::IdempotentKeyword => Some("'idempotent' can only be applied to operations, as in 'idempotent op()'"),
+        TokenKind::TagKeyword => Some(
+            "tags can only be applied to fields, parameters, and return types, and must come before the member's \
+             name, as in 'tag(1) a: int32?'",
+        ),
+        _ => None,
     }
 }
 
@@ -65,6 +104,7 @@ fn generate_message(expected: &[String], found: impl std::fmt::Display) -> Strin
             "enum_keyword" => tokens::TokenKind::EnumKeyword.to_string(),
             "custom_keyword" => tokens::TokenKind::CustomKeyword.to_string(),
             "type_alias_keyword" => tokens::TokenKind::TypeAliasKeyword.to_string(),
+            "const_keyword" => tokens::TokenKind::ConstKeyword.to_string(),
             "result_keyword" => tokens::TokenKind::ResultKeyword.to_string(),
 
             // Collection keywords
@@ -91,6 +131,7 @@ fn generate_message(expected: &[String], found: impl std::fmt::Display) -> Strin
             "any_class_keyword" => tokens::TokenKind::AnyClassKeyword.to_string(),
 
             // Other keywords
+            "any_exception_keyword" => tokens::TokenKind::AnyExceptionKeyword.to_string(),
             "compact_keyword" => tokens::TokenKind::CompactKeyword.to_string(),
             "idempotent_keyword" => tokens::TokenKind::IdempotentKeyword.to_string(),
             "mode_keyword" => tokens::TokenKind::ModeKeyword.to_string(),
@@ -98,6 +139,7 @@ fn generate_message(expected: &[String], found: impl std::fmt::Display) -> Strin
             "tag_keyword" => tokens::TokenKind::TagKeyword.to_string(),
             "throws_keyword" => tokens::TokenKind::ThrowsKeyword.to_string(),
             "unchecked_keyword" => tokens::TokenKind::UncheckedKeyword.to_string(),
+            "using_keyword" => tokens::TokenKind::UsingKeyword.to_string(),
 
             // Brackets
             "\"(\"" => tokens::TokenKind::LeftParenthesis.to_string(),
@@ -119,6 +161,12 @@ fn generate_message(expected: &[String], found: impl std::fmt::Display) -> Strin
             "\"?\"" => tokens::TokenKind::QuestionMark.to_string(),
             "\"->\"" => tokens::TokenKind::Arrow.to_string(),
             "\"-\"" => tokens::TokenKind::Minus.to_string(),
+            "\"+\"" => tokens::TokenKind::Plus.to_string(),
+            "\"*\"" => tokens::TokenKind::Star.to_string(),
+            "\"|\"" => tokens::TokenKind::Pipe.to_string(),
+            "\"&\"" => tokens::TokenKind::Ampersand.to_string(),
+            "\"^\"" => tokens::TokenKind::Caret.to_string(),
+            "\"~\"" => tokens::TokenKind::Tilde.to_string(),
             _ => s.to_owned(),
         })
         .map(|s| format!("'{s}'"))
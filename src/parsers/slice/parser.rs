@@ -38,7 +38,9 @@ pub struct Parser<'a> {
     pub(super) diagnostics: &'a mut Diagnostics,
     pub(super) current_scope: Scope,
     pub(super) compilation_mode: CompilationMode,
-    pub(super) previous_enumerator_value: Option<i128>,
+    /// Definitions that were synthesized by the parser (like the structs that tuple types are desugared into).
+    /// These are appended to the file's definitions once it's been parsed.
+    pub(super) synthesized_definitions: Vec<Definition>,
 }
 
 impl<'a> Parser<'a> {
@@ -49,6 +51,8 @@ impl<'a> Parser<'a> {
             Option<FileCompilationMode>,
             Vec<WeakPtr<Attribute>>,
             Option<OwnedPtr<Module>>,
+            Vec<UsingDirective>,
+            Vec<ModuleAlias>,
             Vec<Definition>,
         ),
     );
@@ -60,7 +64,7 @@ impl<'a> Parser<'a> {
             diagnostics,
             compilation_mode: CompilationMode::default(),
             current_scope: Scope::default(),
-            previous_enumerator_value: None,
+            synthesized_definitions: Vec::new(),
         }
     }
 }
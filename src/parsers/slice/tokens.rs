@@ -38,6 +38,7 @@ pub enum TokenKind<'input> {
     EnumKeyword,      // "enum"
     CustomKeyword,    // "custom"
     TypeAliasKeyword, // "typealias"
+    ConstKeyword,     // "const"
     ResultKeyword,    // "Result"
 
     // Collection keywords
@@ -64,13 +65,15 @@ pub enum TokenKind<'input> {
     AnyClassKeyword,  // "AnyClass"
 
     // Other keywords
-    CompactKeyword,    // "compact"
-    IdempotentKeyword, // "idempotent"
-    ModeKeyword,       // "mode"
-    StreamKeyword,     // "stream"
-    TagKeyword,        // "tag"
-    ThrowsKeyword,     // "throws"
-    UncheckedKeyword,  // "unchecked"
+    AnyExceptionKeyword, // "AnyException"
+    CompactKeyword,      // "compact"
+    IdempotentKeyword,   // "idempotent"
+    ModeKeyword,         // "mode"
+    StreamKeyword,       // "stream"
+    TagKeyword,          // "tag"
+    ThrowsKeyword,       // "throws"
+    UncheckedKeyword,    // "unchecked"
+    UsingKeyword,        // "using"
 
     // Brackets
     LeftParenthesis,    // "("
@@ -92,6 +95,12 @@ pub enum TokenKind<'input> {
     QuestionMark, // "?"
     Arrow,        // "->"
     Minus,        // "-"
+    Plus,         // "+"
+    Star,         // "*"
+    Pipe,         // "|"
+    Ampersand,    // "&"
+    Caret,        // "^"
+    Tilde,        // "~"
 }
 
 impl fmt::Display for TokenKind<'_> {
@@ -111,6 +120,7 @@ impl fmt::Display for TokenKind<'_> {
             Self::EnumKeyword => "enum",
             Self::CustomKeyword => "custom",
             Self::TypeAliasKeyword => "typealias",
+            Self::ConstKeyword => "const",
             Self::ResultKeyword => "Result",
             Self::SequenceKeyword => "Sequence",
             Self::DictionaryKeyword => "Dictionary",
@@ -131,6 +141,7 @@ impl fmt::Display for TokenKind<'_> {
             Self::Float64Keyword => "float64",
             Self::StringKeyword => "string",
             Self::AnyClassKeyword => "AnyClass",
+            Self::AnyExceptionKeyword => "AnyException",
             Self::CompactKeyword => "compact",
             Self::IdempotentKeyword => "idempotent",
             Self::ModeKeyword => "mode",
@@ -138,6 +149,7 @@ impl fmt::Display for TokenKind<'_> {
             Self::TagKeyword => "tag",
             Self::ThrowsKeyword => "throws",
             Self::UncheckedKeyword => "unchecked",
+            Self::UsingKeyword => "using",
 
             // Symbols
             Self::LeftParenthesis => "(",
@@ -157,6 +169,12 @@ impl fmt::Display for TokenKind<'_> {
             Self::QuestionMark => "?",
             Self::Arrow => "->",
             Self::Minus => "-",
+            Self::Plus => "+",
+            Self::Star => "*",
+            Self::Pipe => "|",
+            Self::Ampersand => "&",
+            Self::Caret => "^",
+            Self::Tilde => "~",
         })
     }
 }
@@ -171,6 +189,11 @@ pub enum ErrorKind {
     /// Ex: `-` isn't a valid symbol, but "->" is a valid symbol. So the parser will suggest "->" to the user.
     UnknownSymbol { symbol: String, suggestion: Option<String> },
 
+    /// Returned when an identifier contains non-ASCII characters. Identifiers can only contain ASCII letters, digits,
+    /// and underscores.
+    /// Ex: `struct Café {}`, 'é' isn't an ASCII character.
+    NonAsciiIdentifier { identifier: String },
+
     /// Returned when a string is missing its closing quotation mark. Note that strings cannot span multiple lines.
     /// Ex: `"this is a bad string`, there's no closing '"' before EOL.
     UnterminatedStringLiteral,
@@ -187,6 +210,10 @@ impl fmt::Display for ErrorKind {
                 Some(s) => write!(f, "unknown symbol '{symbol}', try using '{s}' instead"),
                 None => write!(f, "unknown symbol '{symbol}'"),
             },
+            Self::NonAsciiIdentifier { identifier } => write!(
+                f,
+                "invalid identifier '{identifier}': identifiers can only contain ASCII letters, digits, and underscores",
+            ),
             Self::UnterminatedStringLiteral => f.write_str("unterminated string literal"),
             Self::UnterminatedBlockComment => f.write_str("unterminated block comment"),
         }
@@ -0,0 +1,60 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::ast::node::Node;
+use crate::compilation_state::CompilationState;
+use crate::grammar::*;
+
+/// Computes and caches whether each struct, exception, and enum uses classes (see [Type::uses_classes]).
+///
+/// Types can reference each other in cycles (ex: a struct with a field of type `Sequence<Self>`), so instead of
+/// recursively walking each type's fields, we start by assuming that no type uses classes, and then repeatedly mark
+/// any type with a field that uses classes, until no more types are marked. Each pass only checks the cached results
+/// of other types, so no type graph is ever walked more than once per pass.
+pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
+    let nodes = compilation_state.ast.as_mut_slice();
+
+    for node in nodes.iter_mut() {
+        if let Some(uses_classes) = cached_uses_classes_of(node) {
+            *uses_classes = Some(false);
+        }
+    }
+
+    let mut was_changed = true;
+    while was_changed {
+        was_changed = false;
+        for node in nodes.iter_mut() {
+            if compute_uses_classes_of(node) == Some(true) {
+                let uses_classes = cached_uses_classes_of(node).unwrap();
+                if *uses_classes != Some(true) {
+                    *uses_classes = Some(true);
+                    was_changed = true;
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether the provided node has any fields which use classes, based on the currently cached results.
+/// Returns `None` if the node doesn't cache whether it uses classes.
+fn compute_uses_classes_of(node: &Node) -> Option<bool> {
+    let fields_use_classes = |fields: Vec<&Field>| fields.into_iter().any(|field| field.uses_classes());
+    match node {
+        Node::Struct(struct_ptr) => Some(fields_use_classes(struct_ptr.borrow().fields())),
+        // Exceptions aren't types, so nothing depends on them, but they inherit the fields of their base exceptions.
+        Node::Exception(exception_ptr) => Some(fields_use_classes(exception_ptr.borrow().all_fields())),
+        Node::Enum(enum_ptr) => {
+            let mut enumerators = enum_ptr.borrow().enumerators().into_iter();
+            Some(enumerators.any(|enumerator| fields_use_classes(enumerator.fields())))
+        }
+        _ => None,
+    }
+}
+
+unsafe fn cached_uses_classes_of(node: &mut Node) -> Option<&mut Option<bool>> {
+    match node {
+        Node::Struct(struct_ptr) => Some(&mut struct_ptr.borrow_mut().uses_classes),
+        Node::Exception(exception_ptr) => Some(&mut exception_ptr.borrow_mut().uses_classes),
+        Node::Enum(enum_ptr) => Some(&mut enum_ptr.borrow_mut().uses_classes),
+        _ => None,
+    }
+}
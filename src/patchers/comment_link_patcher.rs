@@ -43,6 +43,7 @@ pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
             Node::Enumerator(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             Node::CustomType(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             Node::TypeAlias(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
+            Node::Constant(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             _ => {} // Skip any elements that don't implement `Commentable`.
         }
     }
@@ -60,6 +61,7 @@ pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
             Node::Enumerator(ptr) => patch_element!(ptr, patcher),
             Node::CustomType(ptr) => patch_element!(ptr, patcher),
             Node::TypeAlias(ptr) => patch_element!(ptr, patcher),
+            Node::Constant(ptr) => patch_element!(ptr, patcher),
             _ => {} // Skip any elements that don't implement `Commentable`.
         }
     }
@@ -73,7 +75,13 @@ struct CommentLinkPatcher<'a> {
 
 impl CommentLinkPatcher<'_> {
     fn compute_patches_for(&mut self, commentable: &impl Commentable, ast: &Ast) {
-        if let Some(comment) = commentable.comment() {
+        // Localizations are patched after the comment they're localizing, in the order they were written.
+        // `apply_patches` must visit the links in this same order, since patches are stored in a queue.
+        let comments = commentable
+            .comment()
+            .into_iter()
+            .flat_map(DocComment::with_localizations);
+        for comment in comments {
             if let Some(overview) = &comment.overview {
                 self.resolve_links_in(overview, commentable, ast);
             }
@@ -145,22 +153,29 @@ impl CommentLinkPatcher<'_> {
 
     fn apply_patches(&mut self, scope: &str, comment: &mut Option<DocComment>) {
         if let Some(comment) = comment {
-            if let Some(overview) = &mut comment.overview {
-                self.patch_links_in(overview);
-            }
-            for param_tag in &mut comment.params {
-                self.patch_links_in(&mut param_tag.message);
-            }
-            for returns_tag in &mut comment.returns {
-                self.patch_links_in(&mut returns_tag.message);
-            }
-            for throws_tag in &mut comment.throws {
-                self.patch_thrown_type(scope, throws_tag);
-                self.patch_links_in(&mut throws_tag.message);
-            }
-            for see_tag in &mut comment.see {
-                patch_link!(self, see_tag);
-            }
+            self.apply_patches_in(scope, comment);
+        }
+    }
+
+    fn apply_patches_in(&mut self, scope: &str, comment: &mut DocComment) {
+        if let Some(overview) = &mut comment.overview {
+            self.patch_links_in(overview);
+        }
+        for param_tag in &mut comment.params {
+            self.patch_links_in(&mut param_tag.message);
+        }
+        for returns_tag in &mut comment.returns {
+            self.patch_links_in(&mut returns_tag.message);
+        }
+        for throws_tag in &mut comment.throws {
+            self.patch_thrown_type(scope, throws_tag);
+            self.patch_links_in(&mut throws_tag.message);
+        }
+        for see_tag in &mut comment.see {
+            patch_link!(self, see_tag);
+        }
+        for localization in &mut comment.localizations {
+            self.apply_patches_in(scope, localization);
         }
     }
 
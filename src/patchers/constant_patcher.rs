@@ -0,0 +1,419 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::ast::node::{Node, NodeId};
+use crate::ast::{Ast, LookupError};
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+use crate::slice_file::Span;
+use crate::utils::ptr_util::WeakPtr;
+use std::collections::HashMap;
+
+/// Evaluates the constant expressions that constants, enumerator values, tags, and compact IDs are written with, and
+/// computes the values of any enumerators which weren't given explicit values.
+///
+/// Enumerators whose value is just the identifier of another enumerator in the same enum are turned into aliases of it.
+///
+/// This runs after every file has been parsed, so expressions can reference constants from any file, regardless of
+/// the order the files were passed in, or whether the constant is declared before or after its uses in a file.
+pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
+    let mut evaluator = ConstantEvaluator {
+        ast: &compilation_state.ast,
+        diagnostics: &mut compilation_state.diagnostics,
+        values: HashMap::new(),
+        evaluation_stack: Vec::new(),
+    };
+
+    // Evaluate everything first, since expressions can reference elements that come later in the AST.
+    let mut patches = Vec::new();
+    for (i, node) in compilation_state.ast.as_slice().iter().enumerate() {
+        let patch = match node {
+            Node::Constant(constant_ptr) => Patch::Value(evaluator.constant_value(constant_ptr.borrow())),
+            Node::Enumerator(enumerator_ptr) => {
+                let enumerator = enumerator_ptr.borrow();
+                let value = evaluator.enumerator_value(enumerator);
+                match alias_target(enumerator) {
+                    Some((identifier, target)) if !is_cyclic_alias(enumerator) => {
+                        Patch::Alias(identifier.clone(), target.clone())
+                    }
+                    _ => Patch::Value(value),
+                }
+            }
+            Node::Field(field_ptr) => {
+                let field = field_ptr.borrow();
+                let Some(tag) = &field.tag else { continue };
+                Patch::Id(evaluator.evaluate_id(tag, &field.scope, Error::TagValueOutOfBounds))
+            }
+            Node::Parameter(parameter_ptr) => {
+                let parameter = parameter_ptr.borrow();
+                let Some(tag) = &parameter.tag else { continue };
+                Patch::Id(evaluator.evaluate_id(tag, &parameter.scope, Error::TagValueOutOfBounds))
+            }
+            Node::Class(class_ptr) => {
+                let class = class_ptr.borrow();
+                let Some(compact_id) = &class.compact_id else { continue };
+                Patch::Id(evaluator.evaluate_id(compact_id, &class.scope, Error::CompactIdOutOfBounds))
+            }
+            _ => continue,
+        };
+        patches.push((i, patch));
+    }
+
+    // Then store the computed values in the AST.
+    let elements = compilation_state.ast.as_mut_slice();
+    for (i, patch) in patches {
+        match (&mut elements[i], patch) {
+            (Node::Constant(constant_ptr), Patch::Value(value)) => {
+                apply_value(&mut constant_ptr.borrow_mut().value, value);
+            }
+            (Node::Enumerator(enumerator_ptr), Patch::Value(value)) => match &mut enumerator_ptr.borrow_mut().value {
+                EnumeratorValue::Implicit(implicit_value) => *implicit_value = value.value,
+                EnumeratorValue::Explicit(integer) => apply_value(integer, value),
+                EnumeratorValue::Alias { .. } => unreachable!("aliases are only created by this patcher"),
+            },
+            (Node::Enumerator(enumerator_ptr), Patch::Alias(identifier, target)) => {
+                enumerator_ptr.borrow_mut().value = EnumeratorValue::Alias { identifier, target };
+            }
+            (Node::Field(field_ptr), Patch::Id(id)) => {
+                apply_value(field_ptr.borrow_mut().tag.as_mut().unwrap(), id);
+            }
+            (Node::Parameter(parameter_ptr), Patch::Id(id)) => {
+                apply_value(parameter_ptr.borrow_mut().tag.as_mut().unwrap(), id);
+            }
+            (Node::Class(class_ptr), Patch::Id(id)) => {
+                apply_value(class_ptr.borrow_mut().compact_id.as_mut().unwrap(), id);
+            }
+            _ => unreachable!("patch was computed for a different kind of element"),
+        }
+    }
+}
+
+enum Patch {
+    /// The value of a constant or enumerator.
+    Value(Integer<i128>),
+    /// The value of a tag or compact ID.
+    Id(Integer<u32>),
+    /// The enumerator that an enumerator aliases, and the identifier it was referenced by.
+    Alias(Identifier, WeakPtr<Enumerator>),
+}
+
+/// If the provided enumerator's value is just the identifier of another enumerator in the same enum, this returns
+/// that identifier, and a pointer to the enumerator it refers to. Otherwise this returns `None`.
+fn alias_target(enumerator: &Enumerator) -> Option<(&Identifier, &WeakPtr<Enumerator>)> {
+    let EnumeratorValue::Explicit(integer) = &enumerator.value else { return None };
+    let Some(ConstantExpression::Reference(identifier)) = integer.expression.as_deref() else { return None };
+
+    let enumerators = &enumerator.parent.borrow().enumerators;
+    let target = enumerators
+        .iter()
+        .find(|other| other.borrow().identifier() == identifier.value)?;
+    Some((identifier, target))
+}
+
+/// Returns true if following the provided enumerator's chain of aliases never reaches a non-alias enumerator.
+/// The values of cyclic aliases can't be computed, so they're reported as errors while evaluating them instead.
+fn is_cyclic_alias(enumerator: &Enumerator) -> bool {
+    // A chain of aliases that's longer than the number of enumerators in the enum must contain a cycle.
+    let enumerator_count = enumerator.parent.borrow().enumerators.len();
+    let mut current = enumerator;
+    for _ in 0..=enumerator_count {
+        match alias_target(current) {
+            Some((_, target)) => current = target.borrow(),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Stores the value of an evaluated integer (and the literal it was written as, if any) in an unevaluated integer.
+/// The unevaluated integer's expression is kept, so tools can still see how the value was written.
+fn apply_value<T: std::fmt::Debug>(integer: &mut Integer<T>, evaluated: Integer<T>) {
+    integer.value = evaluated.value;
+    integer.literal = evaluated.literal;
+}
+
+struct ConstantEvaluator<'a> {
+    ast: &'a Ast,
+    diagnostics: &'a mut Diagnostics,
+
+    /// The values of the constants and enumerators which have already been evaluated.
+    values: HashMap<NodeId, Integer<i128>>,
+
+    /// The constants and enumerators which are currently being evaluated (along with their identifiers), in the order
+    /// that evaluation started. If evaluating an element requires its own value, the element is part of a cycle.
+    evaluation_stack: Vec<(NodeId, String)>,
+}
+
+impl<'a> ConstantEvaluator<'a> {
+    fn constant_value(&mut self, constant: &Constant) -> Integer<i128> {
+        self.memoize(constant, |evaluator| {
+            evaluator.evaluate_integer(&constant.value, &constant.scope, None)
+        })
+    }
+
+    fn enumerator_value(&mut self, enumerator: &Enumerator) -> Integer<i128> {
+        self.memoize(enumerator, |evaluator| match &enumerator.value {
+            EnumeratorValue::Implicit(_) => evaluator.implicit_value(enumerator),
+            EnumeratorValue::Explicit(integer) => {
+                evaluator.evaluate_integer(integer, &enumerator.scope, Some(enumerator))
+            }
+            EnumeratorValue::Alias { target, .. } => evaluator.enumerator_value(target.borrow()),
+        })
+    }
+
+    /// Computes the implicit value of an enumerator: the first enumerator in an enum has a value of 0, and every other
+    /// enumerator has the value of the enumerator before it plus 1. If this would overflow, we report an error.
+    fn implicit_value(&mut self, enumerator: &Enumerator) -> Integer<i128> {
+        let enumerators = enumerator.parent.borrow().enumerators();
+        let index = enumerators.iter().position(|other| std::ptr::eq(*other, enumerator));
+        let previous = index.and_then(|i| i.checked_sub(1)).map(|i| enumerators[i]);
+
+        let value = match previous {
+            Some(previous) => self.enumerator_value(previous).value.checked_add(1).unwrap_or_else(|| {
+                Diagnostic::new(Error::ImplicitEnumeratorValueOverflows {
+                    enumerator_identifier: enumerator.identifier().to_owned(),
+                })
+                .set_span(enumerator.span())
+                .add_note("try giving this enumerator an explicit value", None)
+                .push_into(self.diagnostics);
+                i128::MAX
+            }),
+            None => 0,
+        };
+
+        Integer {
+            value,
+            literal: None,
+            expression: None,
+            span: enumerator.span().clone(),
+        }
+    }
+
+    /// Evaluates a tag or compact ID, and checks that its value is between 0 and `i32::MAX`, reporting the provided
+    /// error if it isn't.
+    fn evaluate_id(&mut self, id: &Integer<u32>, scope: &Scope, out_of_bounds_error: Error) -> Integer<u32> {
+        let Some(expression) = &id.expression else { return id.clone() };
+        let integer = self.evaluate(expression, scope, None);
+
+        // Verify that the provided integer is in range.
+        if !(0..=i32::MAX as i128).contains(&integer.value) {
+            Diagnostic::new(out_of_bounds_error)
+                .set_span(&integer.span)
+                .push_into(self.diagnostics);
+        }
+
+        // Cast the integer to a `u32` since it most closely matches the allowed range of tags and compact IDs.
+        // It's fine if the value doesn't fit, the cast will just give us a dummy value.
+        Integer {
+            value: integer.value as u32,
+            literal: integer.literal,
+            expression: None,
+            span: integer.span,
+        }
+    }
+
+    /// Returns the value of the provided element, computing it with `compute` if it hasn't already been evaluated.
+    /// If the element's value depends on itself, an error is reported, and a dummy value of 0 is used.
+    fn memoize(&mut self, element: &dyn Entity, compute: impl FnOnce(&mut Self) -> Integer<i128>) -> Integer<i128> {
+        let id = self
+            .ast
+            .id_of(element)
+            .expect("constants and enumerators are stored in the AST");
+        if let Some(integer) = self.values.get(&id) {
+            return integer.clone();
+        }
+
+        if let Some(start) = self.evaluation_stack.iter().position(|(other, _)| *other == id) {
+            let mut cycle = self.evaluation_stack[start..]
+                .iter()
+                .map(|(_, identifier)| identifier.as_str())
+                .collect::<Vec<_>>();
+            cycle.push(element.identifier());
+
+            Diagnostic::new(Error::CyclicConstantExpression {
+                identifier: element.identifier().to_owned(),
+                cycle: cycle.join(" -> "),
+            })
+            .set_span(element.span())
+            .push_into(self.diagnostics);
+            return dummy_integer(element.span());
+        }
+
+        self.evaluation_stack.push((id, element.identifier().to_owned()));
+        let integer = compute(self);
+        self.evaluation_stack.pop();
+
+        self.values.insert(id, integer.clone());
+        integer
+    }
+
+    fn evaluate_integer(
+        &mut self,
+        integer: &Integer<i128>,
+        scope: &Scope,
+        enumerator: Option<&Enumerator>,
+    ) -> Integer<i128> {
+        match &integer.expression {
+            Some(expression) => self.evaluate(expression, scope, enumerator),
+            None => integer.clone(),
+        }
+    }
+
+    /// Evaluates the provided expression using checked arithmetic, and returns the result with the expression's span.
+    /// References are resolved relative to `scope`. If the expression is an enumerator's value, references can also be
+    /// to other enumerators in the same enum.
+    ///
+    /// If evaluation fails (an overflow or an unresolvable reference), an error is reported and a dummy value of 0 is
+    /// used.
+    fn evaluate(
+        &mut self,
+        expression: &ConstantExpression,
+        scope: &Scope,
+        enumerator: Option<&Enumerator>,
+    ) -> Integer<i128> {
+        match expression {
+            ConstantExpression::Literal(integer) => integer.clone(),
+
+            ConstantExpression::Reference(identifier) => {
+                let value = self.resolve(identifier, scope, enumerator).unwrap_or(0); // Dummy value
+                Integer {
+                    value,
+                    literal: None,
+                    expression: None,
+                    span: identifier.span.clone(),
+                }
+            }
+
+            ConstantExpression::Unary {
+                operator,
+                operand,
+                span,
+            } => {
+                let operand = self.evaluate(operand, scope, enumerator);
+                let result = match operator {
+                    UnaryOperator::Negate => operand.value.checked_neg(),
+                    UnaryOperator::BitwiseNot => Some(!operand.value),
+                };
+                let value = result.unwrap_or_else(|| self.report_overflow(span, None));
+
+                // A negated integer literal is still a literal (Ex: `-5`), so we preserve it, including the minus sign.
+                let literal = match (operator, operand.literal) {
+                    (UnaryOperator::Negate, Some(literal)) => Some(IntegerLiteral {
+                        value,
+                        radix: literal.radix,
+                        lexeme: format!("-{}", literal.lexeme),
+                        span: span.clone(),
+                    }),
+                    _ => None,
+                };
+                Integer {
+                    value,
+                    literal,
+                    expression: None,
+                    span: span.clone(),
+                }
+            }
+
+            ConstantExpression::Binary {
+                lhs,
+                operator,
+                rhs,
+                span,
+            } => {
+                let lhs = self.evaluate(lhs, scope, enumerator).value;
+                let rhs = self.evaluate(rhs, scope, enumerator).value;
+
+                // Shifting by a negative amount, or by more than the number of bits in the value, isn't allowed.
+                let shift_amount = u32::try_from(rhs).ok().filter(|amount| *amount < i128::BITS);
+                let result = match operator {
+                    BinaryOperator::BitwiseOr => Some(lhs | rhs),
+                    BinaryOperator::BitwiseXor => Some(lhs ^ rhs),
+                    BinaryOperator::BitwiseAnd => Some(lhs & rhs),
+                    BinaryOperator::ShiftLeft => shift_amount.and_then(|amount| {
+                        // Unlike `checked_shl`, we also check whether any set bits were shifted out of the value.
+                        let shifted = lhs << amount;
+                        (shifted >> amount == lhs).then_some(shifted)
+                    }),
+                    BinaryOperator::ShiftRight => shift_amount.map(|amount| lhs >> amount),
+                    BinaryOperator::Add => lhs.checked_add(rhs),
+                    BinaryOperator::Subtract => lhs.checked_sub(rhs),
+                    BinaryOperator::Multiply => lhs.checked_mul(rhs),
+                };
+
+                let value = result.unwrap_or_else(|| {
+                    let is_shift = matches!(operator, BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight);
+                    let note =
+                        (is_shift && shift_amount.is_none()).then_some("shift amounts must be between 0 and 127");
+                    self.report_overflow(span, note)
+                });
+                Integer {
+                    value,
+                    literal: None,
+                    expression: None,
+                    span: span.clone(),
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the constant or enumerator with the provided identifier, or reports an error if there is
+    /// no such element.
+    fn resolve(&mut self, identifier: &Identifier, scope: &Scope, enumerator: Option<&Enumerator>) -> Option<i128> {
+        // Enumerators in the enclosing enum take precedence over constants.
+        if let Some(enumerator) = enumerator {
+            let enumerators = enumerator.parent.borrow().enumerators();
+            if let Some(referenced) = enumerators.iter().find(|other| other.identifier() == identifier.value) {
+                return Some(self.enumerator_value(referenced).value);
+            }
+        }
+
+        let ast = self.ast;
+        match ast.find_element_with_scope::<Constant>(&identifier.value, &scope.parser_scope) {
+            Ok(constant) => Some(self.constant_value(constant).value),
+            Err(error) => {
+                let error = match error {
+                    LookupError::DoesNotExist { identifier } => Error::DoesNotExist { identifier },
+                    LookupError::TypeMismatch {
+                        expected,
+                        actual,
+                        is_concrete,
+                    } => Error::TypeMismatch {
+                        expected,
+                        actual,
+                        is_concrete,
+                    },
+                };
+                self.report_missing_reference(error, identifier, enumerator.is_some());
+                None
+            }
+        }
+    }
+
+    fn report_missing_reference(&mut self, error: Error, identifier: &Identifier, is_enumerator_value: bool) {
+        let is_missing = matches!(error, Error::DoesNotExist { .. });
+        let mut diagnostic = Diagnostic::new(error).set_span(&identifier.span);
+        if is_missing && is_enumerator_value {
+            let message = "enumerator values can only reference constants, and enumerators in the same enum";
+            diagnostic = diagnostic.add_note(message, None);
+        }
+        diagnostic.push_into(self.diagnostics);
+    }
+
+    fn report_overflow(&mut self, span: &Span, note: Option<&str>) -> i128 {
+        let mut diagnostic = Diagnostic::new(Error::ConstantExpressionOverflows).set_span(span);
+        if let Some(note) = note {
+            diagnostic = diagnostic.add_note(note, None);
+        }
+        diagnostic.push_into(self.diagnostics);
+        0 // Dummy value
+    }
+}
+
+fn dummy_integer(span: &Span) -> Integer<i128> {
+    Integer {
+        value: 0,
+        literal: None,
+        expression: None,
+        span: span.clone(),
+    }
+}
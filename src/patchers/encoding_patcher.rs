@@ -4,8 +4,8 @@ use crate::ast::node::Node;
 use crate::compilation_state::CompilationState;
 use crate::diagnostics::*;
 use crate::grammar::*;
-use crate::slice_file::SliceFile;
-use crate::supported_encodings::SupportedEncodings;
+use crate::slice_file::{SliceFile, Span};
+use crate::supported_encodings::{allows_optional_with_slice1, type_ref_encodings_with, SupportedEncodings};
 use std::collections::HashMap;
 
 pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
@@ -74,9 +74,9 @@ impl EncodingPatcher<'_> {
         }
 
         // Store which Slice encodings can possibly be supported based on the file's compilation mode.
-        let mut files = self.slice_files.iter();
-        let slice_file = files.find(|f| f.relative_path == entity_def.span().file).unwrap();
-        let compilation_mode = slice_file.compilation_mode();
+        // Built-in types (like the well-known types) aren't defined in a file, so they use the default mode.
+        let slice_file = self.slice_file_of(entity_def.span());
+        let compilation_mode = slice_file.map_or_else(CompilationMode::default, SliceFile::compilation_mode);
         let mut supported_encodings = SupportedEncodings::new(match compilation_mode {
             CompilationMode::Slice1 => vec![Encoding::Slice1, Encoding::Slice2],
             CompilationMode::Slice2 => vec![Encoding::Slice2],
@@ -169,17 +169,43 @@ impl EncodingPatcher<'_> {
             }
             Types::Sequence(sequence) => {
                 // Sequences are supported by any encoding that supports their elements.
-                self.get_supported_encodings_for_type_ref(&sequence.element_type, compilation_mode, false, None)
+                let element_type = &sequence.element_type;
+                let elements_support_slice1 = self.check_optional_elements(
+                    type_ref,
+                    element_type,
+                    "sequence",
+                    compilation_mode,
+                    &mut diagnostics,
+                );
+
+                // Optional elements are checked above, so we allow them here to avoid reporting them twice.
+                let mut supported_encodings =
+                    self.get_supported_encodings_for_type_ref(element_type, compilation_mode, true, None);
+                if !elements_support_slice1 {
+                    supported_encodings.disable(Encoding::Slice1);
+                }
+                supported_encodings
             }
             Types::Dictionary(dictionary) => {
                 // Dictionaries are supported by any encoding that supports their keys and values.
+                let value_type = &dictionary.value_type;
+                let values_support_slice1 = self.check_optional_elements(
+                    type_ref,
+                    value_type,
+                    "dictionary",
+                    compilation_mode,
+                    &mut diagnostics,
+                );
                 let key_encodings =
                     self.get_supported_encodings_for_type_ref(&dictionary.key_type, compilation_mode, false, None);
                 let value_encodings =
-                    self.get_supported_encodings_for_type_ref(&dictionary.value_type, compilation_mode, false, None);
+                    self.get_supported_encodings_for_type_ref(value_type, compilation_mode, true, None);
 
                 let mut supported_encodings = key_encodings;
                 supported_encodings.intersect_with(&value_encodings);
+                if !values_support_slice1 {
+                    supported_encodings.disable(Encoding::Slice1);
+                }
                 supported_encodings
             }
             Types::Primitive(primitive) => {
@@ -205,17 +231,38 @@ impl EncodingPatcher<'_> {
             }
         }
 
+        // Boxed types can only be used in Slice2 mode.
+        if type_ref.is_boxed() {
+            supported_encodings.disable(Encoding::Slice1);
+
+            if compilation_mode == CompilationMode::Slice1 {
+                let diagnostic = Diagnostic::new(Error::BoxedTypesNotSupported {
+                    kind: type_ref.definition().kind().to_owned(),
+                })
+                .set_span(type_ref.span())
+                .add_note(
+                    "Slice1 can express recursive data structures with classes instead",
+                    None,
+                );
+
+                diagnostics.push(diagnostic);
+            }
+        }
+
         // Ensure the type supports the encodings required by its compilation mode.
         if supported_encodings.supports(compilation_mode) {
             supported_encodings
         } else {
             // If no specific reasons were given for the error, generate a generic one.
+            // We trace through the type to find what caused the problem, since it could be deeply nested in aliases.
             if diagnostics.is_empty() {
+                let trace = self.trace_unsupported_type(type_ref, compilation_mode, &mut Vec::new());
                 let diagnostic = Diagnostic::new(Error::UnsupportedType {
                     kind: type_ref.type_string(),
                     mode: compilation_mode,
                 })
                 .set_span(type_ref.span())
+                .extend_notes(trace)
                 .extend_notes(self.get_mode_mismatch_note(type_ref));
 
                 diagnostics.push(diagnostic);
@@ -232,9 +279,192 @@ impl EncodingPatcher<'_> {
         }
     }
 
+    /// Checks whether the elements of a sequence (or the values of a dictionary) are optionals that Slice1 can't encode.
+    ///
+    /// Returns true if they can be encoded. Otherwise this returns false, and if we're in Slice1 mode, stores an error
+    /// which points at the collection in `diagnostics`.
+    fn check_optional_elements(
+        &self,
+        collection: &TypeRef<impl Type + ?Sized>,
+        element_type: &TypeRef,
+        collection_kind: &str,
+        compilation_mode: CompilationMode,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        if allows_optional_with_slice1(element_type, false) {
+            return true;
+        }
+        if compilation_mode != CompilationMode::Slice1 {
+            return false;
+        }
+
+        let element_kind = if collection_kind == "dictionary" {
+            "values"
+        } else {
+            "elements"
+        };
+        let diagnostic = Diagnostic::new(Error::OptionalElementsNotSupported {
+            collection: collection_kind.to_owned(),
+            kind: element_type.definition().kind().to_owned(),
+        })
+        .set_span(collection.span())
+        .add_note(
+            format!("Slice1 doesn't support optional {collection_kind} {element_kind}, except for classes, custom types, and 'AnyClass'"),
+            Some(element_type.span()),
+        )
+        .extend_notes(self.get_mode_mismatch_note(collection));
+        diagnostics.push(diagnostic);
+        false
+    }
+
+    /// Returns a chain of notes explaining why the provided type isn't supported by the specified encoding.
+    /// The chain walks through any type aliases the type was resolved through, and then into the elements of anonymous
+    /// types and the fields of user defined types, until it reaches whatever caused the type to be unsupported.
+    ///
+    /// This never reports any errors itself; it only checks encodings which have already been computed (or that can be
+    /// computed without side effects), so it's safe to call while reporting another error.
+    fn trace_unsupported_type(
+        &mut self,
+        type_ref: &TypeRef<impl Type + ?Sized>,
+        encoding: Encoding,
+        visited: &mut Vec<String>,
+    ) -> Vec<Note> {
+        let note = |message: String, span: &Span| Note {
+            message,
+            span: Some(span.clone()),
+        };
+
+        // First, walk through the chain of type aliases that this type was resolved through (if there are any).
+        let type_aliases = type_ref.type_aliases();
+        let mut notes = Vec::new();
+        for (i, type_alias) in type_aliases.iter().enumerate() {
+            let target = match type_aliases.get(i + 1) {
+                Some(next_type_alias) => next_type_alias.identifier().to_owned(),
+                None => type_ref.definition().type_string(),
+            };
+            let message = format!("'{}' is an alias of '{target}'", type_alias.identifier());
+            notes.push(note(message, type_alias.underlying.span()));
+        }
+
+        // Then, check whether the type itself (or anything it contains) is what isn't supported.
+        let mut nested_type_refs: Vec<&TypeRef> = Vec::new();
+        let mut fields = Vec::new();
+        let mut reason = None;
+        let entity: Option<&dyn Entity> = match type_ref.concrete_type() {
+            Types::Struct(struct_def) => {
+                if !struct_def.is_compact && encoding == Encoding::Slice1 {
+                    reason = Some("structs must be 'compact' to be supported by Slice1");
+                }
+                fields = struct_def.fields();
+                Some(struct_def)
+            }
+            Types::Class(class_def) => {
+                if encoding == Encoding::Slice2 {
+                    reason = Some("classes are only supported by Slice1");
+                }
+                fields = class_def.all_fields();
+                Some(class_def)
+            }
+            Types::Enum(enum_def) => {
+                if encoding == Encoding::Slice1 {
+                    if enum_def.underlying.is_some() {
+                        reason = Some("enums with underlying types aren't supported by Slice1");
+                    } else if enum_def.is_compact {
+                        reason = Some("compact enums aren't supported by Slice1");
+                    } else if enum_def.enumerators().iter().any(|e| e.fields.is_some()) {
+                        reason = Some("enumerators with fields aren't supported by Slice1");
+                    }
+                }
+                Some(enum_def)
+            }
+            Types::CustomType(custom_type) => Some(custom_type),
+            Types::ResultType(result_type) => {
+                if encoding == Encoding::Slice1 {
+                    reason = Some("'Result' is only supported by Slice2");
+                }
+                nested_type_refs.extend([&result_type.success_type, &result_type.failure_type]);
+                None
+            }
+            Types::Sequence(sequence) => {
+                nested_type_refs.push(&sequence.element_type);
+                None
+            }
+            Types::Dictionary(dictionary) => {
+                nested_type_refs.extend([&dictionary.key_type, &dictionary.value_type]);
+                None
+            }
+            Types::Primitive(_) => None,
+        };
+
+        if let Some(reason) = reason {
+            let span = entity.map_or(type_ref.span(), |e| e.span());
+            notes.push(note(reason.to_owned(), span));
+            return notes;
+        }
+
+        // Guard against infinite recursion through types that (indirectly) contain themselves.
+        if let Some(entity) = entity {
+            let type_id = entity.parser_scoped_identifier();
+            if visited.contains(&type_id) {
+                return notes;
+            }
+            visited.push(type_id);
+        }
+
+        let notes_before_members = notes.len();
+        for nested_type_ref in nested_type_refs {
+            if !self.supports_without_reporting(nested_type_ref, false, encoding) {
+                let message = format!("'{}' isn't supported by {encoding}", nested_type_ref.type_string());
+                notes.push(note(message, nested_type_ref.span()));
+                notes.extend(self.trace_unsupported_type(nested_type_ref, encoding, visited));
+            }
+        }
+        for field in fields {
+            if !self.supports_without_reporting(field.data_type(), field.is_tagged(), encoding) {
+                let message = format!("field '{}' isn't supported by {encoding}", field.identifier());
+                notes.push(note(message, field.span()));
+                notes.extend(self.trace_unsupported_type(field.data_type(), encoding, visited));
+            }
+        }
+
+        // If nothing the type contains is the problem, it must be the compilation mode of the file it's defined in.
+        // Types defined in Slice2 files can only be used with Slice2, but types in Slice1 files can be used with both.
+        // Built-in types (like the well-known types) aren't defined in a file, so there's no mode to point out for them.
+        let entity_and_file = entity.and_then(|entity| Some((entity, self.slice_file_of(entity.span())?)));
+        if let Some((entity, slice_file)) = entity_and_file {
+            let mode = slice_file.compilation_mode();
+            if notes.len() == notes_before_members && mode == CompilationMode::Slice2 && encoding == Encoding::Slice1 {
+                let message = format!(
+                    "'{}' is defined in a file whose compilation mode is {mode}",
+                    entity.identifier()
+                );
+                notes.push(note(message, entity.span()));
+            }
+        }
+        notes
+    }
+
+    /// Returns whether a type reference supports the specified encoding, without reporting any errors.
+    fn supports_without_reporting(&self, type_ref: &TypeRef, is_tagged: bool, encoding: Encoding) -> bool {
+        let encodings_of = |entity_def: &dyn Entity| self.cached_encodings_of(entity_def);
+        type_ref_encodings_with(type_ref, is_tagged, &encodings_of).supports(encoding)
+    }
+
+    /// Returns the encodings an entity supports, if they've already been computed, or all encodings otherwise.
+    /// If an entity's encodings haven't been computed yet, any problems with them will be reported at the entity.
+    fn cached_encodings_of(&self, entity_def: &dyn Entity) -> SupportedEncodings {
+        let type_id = entity_def.parser_scoped_identifier();
+        let cached = self.supported_encodings_cache.get(&type_id).cloned();
+        cached.unwrap_or_else(SupportedEncodings::dummy)
+    }
+
+    /// Returns the file that the provided span is in, or `None` if it isn't in any file (for built-in types).
+    fn slice_file_of(&self, span: &Span) -> Option<&SliceFile> {
+        self.slice_files.iter().find(|f| f.relative_path == span.file)
+    }
+
     fn get_mode_mismatch_note(&self, symbol: &impl Symbol) -> Option<Note> {
-        let mut files = self.slice_files.iter();
-        let slice_file = files.find(|f| f.relative_path == symbol.span().file).unwrap();
+        let slice_file = self.slice_file_of(symbol.span())?;
 
         // Emit a note if the file's compilation mode wasn't explicitly set.
         match slice_file.mode.as_ref() {
@@ -403,13 +633,6 @@ impl ComputeSupportedEncodings for Interface {
                     member.is_tagged(),
                     Some(member),
                 );
-
-                // Streamed parameters cannot be used in Slice1 mode.
-                if member.is_streamed && compilation_mode == CompilationMode::Slice1 {
-                    Diagnostic::new(Error::StreamedParametersNotSupported)
-                        .set_span(member.span())
-                        .push_into(patcher.diagnostics)
-                }
             }
         }
         None
@@ -0,0 +1,597 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::type_ref_patcher::{collect_module_aliases, expand_aliases};
+use crate::ast::node::Node;
+use crate::ast::{Ast, LookupError};
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::*;
+use crate::grammar::*;
+use crate::slice_file::{SliceFile, Span};
+use crate::utils::casing::to_pascal_case;
+use crate::utils::ptr_util::{OwnedPtr, WeakPtr};
+use crate::{downgrade_as, upcast_weak_as};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// The maximum number of nested instantiations that instantiating a generic struct can require.
+/// This stops generic structs which instantiate themselves with ever larger type arguments from expanding forever.
+/// Ex: `struct Nested<T> { inner: Nested<Sequence<T>>? }`.
+pub const MAX_INSTANTIATION_DEPTH: usize = 16;
+
+/// Instantiates generic structs with the type arguments they're used with.
+///
+/// Each distinct use of a generic struct (ex: `Pair<int32>`) is monomorphized into an ordinary struct, whose fields
+/// are copies of the generic struct's fields, with its type parameters substituted by the type arguments. These
+/// instantiations are named after the generic struct and its type arguments (ex: `PairInt32`), are defined in the same
+/// module and file as it. If that name is already taken, a numeric suffix is appended to it (ex: `PairInt322`).
+///
+/// Within the generic struct itself, each type parameter is patched to an opaque custom type of the same name, so the
+/// generic struct still forms a valid AST, and is checked like any other struct.
+///
+/// This runs before the type-ref patcher, which then resolves the types referenced by the instantiations' fields.
+pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
+    let (elements, patches) = {
+        // Collect the using directives of each file, so type references can be resolved using their file's aliases.
+        let using_directives = compilation_state
+            .files
+            .iter()
+            .map(|file| (file.relative_path.as_str(), file.using_directives.as_slice()))
+            .collect();
+
+        let module_aliases = collect_module_aliases(&compilation_state.files);
+
+        let mut patcher = GenericsPatcher {
+            ast: &compilation_state.ast,
+            using_directives,
+            module_aliases,
+            diagnostics: &mut compilation_state.diagnostics,
+            type_parameters: HashMap::new(),
+            instantiations: HashMap::new(),
+            instantiation_names: HashSet::new(),
+            elements: NewElements::default(),
+            patches: Vec::new(),
+            has_exceeded_depth: false,
+        };
+        patcher.create_placeholders();
+        patcher.compute_patches();
+        (patcher.elements, patcher.patches)
+    };
+
+    // If anything couldn't be instantiated, the new elements may be incomplete, so we don't add any of them.
+    if compilation_state.diagnostics.has_errors() {
+        return;
+    }
+    elements.add_to(&mut compilation_state.ast, &mut compilation_state.files);
+
+    let nodes = compilation_state.ast.as_mut_slice();
+    for (index, slot, ptr) in patches {
+        type_refs_of_mut(&mut nodes[index])
+            .swap_remove(slot)
+            .patch(ptr, Vec::new(), Vec::new());
+    }
+}
+
+struct GenericsPatcher<'a> {
+    ast: &'a Ast,
+    using_directives: Vec<(&'a str, &'a [UsingDirective])>,
+    module_aliases: Vec<&'a ModuleAlias>,
+    diagnostics: &'a mut Diagnostics,
+
+    /// The type parameters of each generic struct, along with the placeholder custom type created for each of them.
+    /// Keys are the generic structs' fully scoped identifiers.
+    type_parameters: HashMap<String, Vec<(&'a str, WeakPtr<CustomType>)>>,
+
+    /// The instantiations that have been created so far, keyed by their generic struct's fully scoped identifier, and
+    /// the fully scoped types of their arguments. Ex: `Test::Pair<int32, Test::Point?>`.
+    instantiations: HashMap<String, WeakPtr<Struct>>,
+
+    /// The fully scoped identifiers of the instantiations that have been created so far.
+    instantiation_names: HashSet<String>,
+
+    elements: NewElements,
+    /// The type references to patch, stored as the index of the node holding them, their position among the node's
+    /// [type references](type_refs_of), and the type to patch them with.
+    patches: Vec<(usize, usize, WeakPtr<dyn Type>)>,
+
+    /// Whether an instantiation has already exceeded [MAX_INSTANTIATION_DEPTH].
+    has_exceeded_depth: bool,
+}
+
+impl<'a> GenericsPatcher<'a> {
+    /// Creates a placeholder custom type for each type parameter of each generic struct.
+    fn create_placeholders(&mut self) {
+        for node in self.ast.as_slice() {
+            let Node::Struct(struct_ptr) = node else { continue };
+            let struct_def = struct_ptr.borrow();
+            if !struct_def.is_generic() {
+                continue;
+            }
+
+            let scope = Scope {
+                parser_scope: struct_def.parser_scoped_identifier(),
+                module: struct_def.scope.module.clone(),
+            };
+            let placeholders = struct_def.type_parameters.iter().map(|type_parameter| {
+                let placeholder = OwnedPtr::new(CustomType {
+                    identifier: type_parameter.clone(),
+                    scope: scope.clone(),
+                    attributes: Vec::new(),
+                    comment: None,
+                    span: type_parameter.span.clone(),
+                    supported_encodings: None, // Patched by the encoding patcher.
+                });
+                let weak_ptr = placeholder.downgrade();
+                self.elements.custom_types.push(placeholder);
+                (type_parameter.value.as_str(), weak_ptr)
+            });
+            let placeholders = placeholders.collect();
+            self.type_parameters.insert(scope.parser_scope, placeholders);
+        }
+    }
+
+    fn compute_patches(&mut self) {
+        let ast = self.ast;
+        for (index, node) in ast.as_slice().iter().enumerate() {
+            for (slot, type_ref) in type_refs_of(node).into_iter().enumerate() {
+                let TypeRefDefinition::Unpatched(identifier) = &type_ref.definition else { continue };
+
+                // Type references within a generic struct are only checked, since its type parameters are unknown.
+                // Its type parameters are patched to their placeholders, and any other types are resolved as usual.
+                if let Some(type_parameters) = self.type_parameters.get(&type_ref.scope.parser_scope).cloned() {
+                    let placeholder = type_parameters.iter().find(|(name, _)| *name == identifier.value);
+                    match placeholder {
+                        Some((_, placeholder)) if type_ref.type_arguments.is_empty() => {
+                            let ptr = upcast_weak_as!(placeholder.clone(), dyn Type);
+                            self.patches.push((index, slot, ptr));
+                        }
+                        _ => {
+                            let names = type_parameters.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                            self.describe(type_ref, &names);
+                        }
+                    }
+                    continue;
+                }
+
+                if type_ref.type_arguments.is_empty() {
+                    self.describe(type_ref, &[]);
+                } else if let Some(instantiation) = self.instantiate(type_ref, 0) {
+                    self.patches
+                        .push((index, slot, upcast_weak_as!(instantiation, dyn Type)));
+                }
+            }
+
+            // Only structs can be generic, so type arguments can't be used in any other kind of type reference.
+            for (identifier, argument_count, span) in non_generic_type_refs_of(node) {
+                if argument_count > 0 {
+                    self.report_wrong_number_of_type_arguments(&identifier.value, 0, argument_count, span);
+                }
+            }
+        }
+    }
+
+    /// Returns the identifier with any using directive alias or module alias it refers to expanded to its target.
+    /// See [expand_aliases] for more information.
+    fn expand_aliases<'b>(&self, identifier: &'b Identifier, scope: &str) -> Cow<'b, str> {
+        expand_aliases(
+            &self.using_directives,
+            &self.module_aliases,
+            identifier,
+            scope,
+            self.ast,
+        )
+    }
+
+    /// Returns the struct that a type reference with type arguments refers to, if it's a generic struct with the same
+    /// number of type parameters as the type reference has arguments. Otherwise this reports an error.
+    fn find_generic_struct(&mut self, type_ref: &TypeRef) -> Option<&'a Struct> {
+        let TypeRefDefinition::Unpatched(identifier) = &type_ref.definition else { return None };
+        let identifier_string = self.expand_aliases(identifier, type_ref.module_scope());
+
+        let struct_def = match self
+            .ast
+            .find_node_with_scope(&identifier_string, type_ref.module_scope())
+        {
+            Ok(Node::Struct(struct_ptr)) => Some(struct_ptr.borrow()),
+            Ok(_) => None,
+            Err(LookupError::DoesNotExist {
+                identifier: lookup_identifier,
+            }) => {
+                Diagnostic::new(Error::DoesNotExist {
+                    identifier: lookup_identifier,
+                })
+                .set_span(identifier.span())
+                .push_into(self.diagnostics);
+                return None;
+            }
+            Err(_) => return None,
+        };
+
+        let expected = struct_def.map_or(0, |struct_def| struct_def.type_parameters.len());
+        let actual = type_ref.type_arguments.len();
+        if expected != actual {
+            self.report_wrong_number_of_type_arguments(&identifier.value, expected, actual, &type_ref.span);
+            return None;
+        }
+        struct_def.filter(|struct_def| struct_def.is_generic())
+    }
+
+    /// Returns the fully scoped representation of the provided type (which uniquely identifies it), along with the
+    /// name it contributes to the names of instantiations; Ex: `(Test::Pair<int32>?, OptionalPairInt32)`.
+    ///
+    /// Type arguments are checked while describing them, so this returns `None` if any of them are invalid.
+    /// `type_parameters` are the names of any type parameters in scope, which are described by their names.
+    fn describe(&mut self, type_ref: &TypeRef, type_parameters: &[&str]) -> Option<(String, String)> {
+        let (key, name) = self.describe_type(type_ref, type_parameters)?;
+        match type_ref.is_optional {
+            true => Some((key + "?", format!("Optional{name}"))),
+            false => Some((key, name)),
+        }
+    }
+
+    /// Same as [describe](Self::describe), but ignores whether the type is optional.
+    fn describe_type(&mut self, type_ref: &TypeRef, type_parameters: &[&str]) -> Option<(String, String)> {
+        let description = match &type_ref.definition {
+            TypeRefDefinition::Unpatched(identifier) if type_ref.type_arguments.is_empty() => {
+                if type_parameters.contains(&identifier.value.as_str()) {
+                    (identifier.value.clone(), to_pascal_case(&identifier.value))
+                } else {
+                    let identifier_string = self.expand_aliases(identifier, type_ref.module_scope());
+                    let node = self
+                        .ast
+                        .find_node_with_scope(&identifier_string, type_ref.module_scope());
+
+                    // Generic structs must be used with type arguments.
+                    if let Ok(Node::Struct(struct_ptr)) = node {
+                        let expected = struct_ptr.borrow().type_parameters.len();
+                        if expected > 0 {
+                            self.report_wrong_number_of_type_arguments(&identifier.value, expected, 0, &type_ref.span);
+                            return None;
+                        }
+                    }
+
+                    let key = match node.ok().and_then(scoped_identifier_of) {
+                        Some(scoped_identifier) => scoped_identifier,
+                        // If the type doesn't exist, the type-ref patcher will report it.
+                        None => identifier.value.clone(),
+                    };
+                    let name = to_pascal_case(key.rsplit("::").next().unwrap());
+                    (key, name)
+                }
+            }
+            TypeRefDefinition::Unpatched(_) => {
+                let struct_def = self.find_generic_struct(type_ref)?;
+                let arguments = type_ref.type_arguments.iter();
+                let arguments = arguments
+                    .map(|argument| self.describe(argument, type_parameters))
+                    .collect::<Option<Vec<_>>>()?;
+                let (keys, names): (Vec<_>, Vec<_>) = arguments.into_iter().unzip();
+
+                let key = format!("{}<{}>", struct_def.parser_scoped_identifier(), keys.join(", "));
+                (key, struct_def.identifier().to_owned() + &names.concat())
+            }
+            TypeRefDefinition::Patched(ptr) => match ptr.borrow().concrete_type() {
+                Types::Primitive(primitive) => (primitive.kind().to_owned(), to_pascal_case(primitive.kind())),
+                Types::Sequence(sequence) => {
+                    let (key, name) = self.describe(&sequence.element_type, type_parameters)?;
+                    (format!("Sequence<{key}>"), format!("Sequence{name}"))
+                }
+                Types::Dictionary(dictionary) => {
+                    let (key_key, key_name) = self.describe(&dictionary.key_type, type_parameters)?;
+                    let (value_key, value_name) = self.describe(&dictionary.value_type, type_parameters)?;
+                    let key = format!("Dictionary<{key_key}, {value_key}>");
+                    (key, format!("Dictionary{key_name}{value_name}"))
+                }
+                Types::ResultType(result_type) => {
+                    let (success_key, success_name) = self.describe(&result_type.success_type, type_parameters)?;
+                    let (failure_key, failure_name) = self.describe(&result_type.failure_type, type_parameters)?;
+                    let key = format!("Result<{success_key}, {failure_key}>");
+                    (key, format!("Result{success_name}{failure_name}"))
+                }
+                _ => {
+                    let type_string = ptr.borrow().type_string();
+                    (type_string.clone(), to_pascal_case(&type_string))
+                }
+            },
+        };
+        Some(description)
+    }
+
+    /// Returns the instantiation of the generic struct referenced by the provided type reference, with its type
+    /// arguments. If the struct hasn't been instantiated with these arguments yet, a new instantiation is created.
+    /// Returns `None` if the struct couldn't be instantiated, in which case an error was reported.
+    fn instantiate(&mut self, type_ref: &TypeRef, depth: usize) -> Option<WeakPtr<Struct>> {
+        // Optional and non-optional uses of a generic struct share the same instantiation.
+        let (key, name) = self.describe_type(type_ref, &[])?;
+        if let Some(instantiation) = self.instantiations.get(&key) {
+            return Some(instantiation.clone());
+        }
+        let struct_def = self.find_generic_struct(type_ref)?;
+
+        if depth >= MAX_INSTANTIATION_DEPTH {
+            // Only report the first instantiation that's too deep, since every enclosing instantiation fails with it.
+            if !self.has_exceeded_depth {
+                Diagnostic::new(Error::GenericInstantiationTooDeep {
+                    identifier: struct_def.identifier().to_owned(),
+                    max_depth: MAX_INSTANTIATION_DEPTH,
+                })
+                .set_span(&type_ref.span)
+                .add_note(
+                    "this is usually caused by a generic struct which instantiates itself with a different type argument",
+                    Some(struct_def.span()),
+                )
+                .push_into(self.diagnostics);
+                self.has_exceeded_depth = true;
+            }
+            return None;
+        }
+
+        // Instantiations use the generic struct's spans, since that's where their contents come from.
+        let identifier = Identifier {
+            value: self.unique_name(struct_def, name),
+            span: struct_def.identifier.span.clone(),
+        };
+        let mut fields_scope = struct_def.scope.clone();
+        fields_scope.push_scope(&identifier.value);
+
+        let mut instantiation = OwnedPtr::new(Struct {
+            identifier,
+            fields: Vec::new(),
+            is_compact: struct_def.is_compact,
+            type_parameters: Vec::new(),
+            is_synthesized: false,
+            scope: struct_def.scope.clone(),
+            attributes: struct_def.attributes.clone(),
+            comment: None,
+            span: struct_def.span.clone(),
+            supported_encodings: None, // Patched by the encoding patcher.
+            uses_classes: None,        // Patched by the class usage patcher.
+        });
+
+        // Register the instantiation before substituting its fields, so fields can refer back to it.
+        let weak_ptr = instantiation.downgrade();
+        self.instantiations.insert(key, weak_ptr.clone());
+
+        let parent = downgrade_as!(instantiation, dyn Container<Field>);
+        let type_parameters = struct_def.type_parameters.iter().map(|p| p.value.as_str());
+        let arguments = type_parameters.zip(&type_ref.type_arguments).collect::<Vec<_>>();
+        for field in struct_def.fields() {
+            let data_type = self.substitute(&field.data_type, &arguments, depth);
+            let field_ptr = OwnedPtr::new(Field {
+                identifier: field.identifier.clone(),
+                data_type: data_type?,
+                tag: field.tag.clone(),
+                tag_format: None, // Patched by the tag format patcher.
+                parent: parent.clone(),
+                scope: fields_scope.clone(),
+                attributes: field.attributes.clone(),
+                comment: None,
+                span: field.span.clone(),
+            });
+            unsafe { instantiation.borrow_mut().fields.push(field_ptr.downgrade()) };
+            self.elements.fields.push(field_ptr);
+        }
+
+        let file = struct_def.span.file.clone();
+        self.elements.structs.push((file, instantiation));
+        Some(weak_ptr)
+    }
+
+    /// Returns the provided name for an instantiation of `struct_def`, unless another definition or instantiation in
+    /// the generic struct's module already uses it. Then a numeric suffix is appended to make it unique (ex: if
+    /// `Pair<A::Point>` is instantiated as `PairPoint`, `Pair<B::Point>` is instantiated as `PairPoint2`).
+    fn unique_name(&mut self, struct_def: &Struct, name: String) -> String {
+        let scoped_name = |name: &str| match struct_def.scope.parser_scope.as_str() {
+            "" => name.to_owned(),
+            scope => format!("{scope}::{name}"),
+        };
+
+        let mut unique_name = name.clone();
+        let mut suffix = 2;
+        while self.ast.find_node(&scoped_name(&unique_name)).is_ok()
+            || self.instantiation_names.contains(&scoped_name(&unique_name))
+        {
+            unique_name = format!("{name}{suffix}");
+            suffix += 1;
+        }
+        self.instantiation_names.insert(scoped_name(&unique_name));
+        unique_name
+    }
+
+    /// Returns a copy of the provided type reference (from a generic struct), with its type parameters substituted by
+    /// the provided type arguments. Any generic structs used by the substituted type are instantiated.
+    fn substitute(&mut self, type_ref: &TypeRef, arguments: &[(&str, &TypeRef)], depth: usize) -> Option<TypeRef> {
+        let mut substituted = match &type_ref.definition {
+            TypeRefDefinition::Unpatched(identifier) => {
+                let argument = arguments.iter().find(|(name, _)| *name == identifier.value);
+                match argument {
+                    Some((_, argument)) if type_ref.type_arguments.is_empty() => {
+                        // The argument's type is used, but with the parameter's attributes, optionality, and span.
+                        let mut substituted = (*argument).clone();
+                        substituted.is_optional |= type_ref.is_optional;
+                        substituted.attributes = type_ref.attributes.clone();
+                        substituted.attributes.extend(argument.attributes.iter().cloned());
+                        substituted.span = type_ref.span.clone();
+                        substituted
+                    }
+                    _ => {
+                        let mut substituted = type_ref.clone();
+                        substituted.type_arguments = type_ref
+                            .type_arguments
+                            .iter()
+                            .map(|type_argument| self.substitute(type_argument, arguments, depth))
+                            .collect::<Option<_>>()?;
+                        substituted
+                    }
+                }
+            }
+            TypeRefDefinition::Patched(ptr) => {
+                let mut substituted = type_ref.clone();
+                substituted.definition = match ptr.borrow().concrete_type() {
+                    Types::Sequence(sequence) => {
+                        let element_type = self.substitute(&sequence.element_type, arguments, depth)?;
+                        let sequence_ptr = OwnedPtr::new(Sequence { element_type });
+                        let definition = TypeRefDefinition::Patched(downgrade_as!(sequence_ptr, dyn Type));
+                        self.elements.sequences.push(sequence_ptr);
+                        definition
+                    }
+                    Types::Dictionary(dictionary) => {
+                        let key_type = self.substitute(&dictionary.key_type, arguments, depth)?;
+                        let value_type = self.substitute(&dictionary.value_type, arguments, depth)?;
+                        let dictionary_ptr = OwnedPtr::new(Dictionary { key_type, value_type });
+                        let definition = TypeRefDefinition::Patched(downgrade_as!(dictionary_ptr, dyn Type));
+                        self.elements.dictionaries.push(dictionary_ptr);
+                        definition
+                    }
+                    Types::ResultType(result_type) => {
+                        let success_type = self.substitute(&result_type.success_type, arguments, depth)?;
+                        let failure_type = self.substitute(&result_type.failure_type, arguments, depth)?;
+                        let result_type_ptr = OwnedPtr::new(ResultType {
+                            success_type,
+                            failure_type,
+                        });
+                        let definition = TypeRefDefinition::Patched(downgrade_as!(result_type_ptr, dyn Type));
+                        self.elements.result_types.push(result_type_ptr);
+                        definition
+                    }
+                    _ => type_ref.definition.clone(),
+                };
+                substituted
+            }
+        };
+
+        // If the substituted type is itself a use of a generic struct, instantiate it too.
+        if matches!(substituted.definition, TypeRefDefinition::Unpatched(_)) && !substituted.type_arguments.is_empty() {
+            let instantiation = self.instantiate(&substituted, depth + 1)?;
+            substituted.definition = TypeRefDefinition::Patched(upcast_weak_as!(instantiation, dyn Type));
+        }
+        Some(substituted)
+    }
+
+    fn report_wrong_number_of_type_arguments(&mut self, identifier: &str, expected: usize, actual: usize, span: &Span) {
+        let mut error = Diagnostic::new(Error::WrongNumberOfTypeArguments {
+            identifier: identifier.to_owned(),
+            expected,
+            actual,
+        })
+        .set_span(span);
+        if expected == 0 {
+            error = error.add_note(format!("'{identifier}' isn't a generic struct"), None);
+        }
+        error.push_into(self.diagnostics);
+    }
+}
+
+/// The elements created while instantiating generic structs, which are added to the AST once patching is done.
+#[derive(Default)]
+struct NewElements {
+    /// Each instantiation, along with the path of the file it's defined in.
+    structs: Vec<(String, OwnedPtr<Struct>)>,
+    fields: Vec<OwnedPtr<Field>>,
+    custom_types: Vec<OwnedPtr<CustomType>>,
+    sequences: Vec<OwnedPtr<Sequence>>,
+    dictionaries: Vec<OwnedPtr<Dictionary>>,
+    result_types: Vec<OwnedPtr<ResultType>>,
+}
+
+impl NewElements {
+    fn add_to(self, ast: &mut Ast, files: &mut [SliceFile]) {
+        for (path, struct_ptr) in self.structs {
+            let weak_ptr = ast.add_named_element(struct_ptr);
+            if let Some(file) = files.iter_mut().find(|file| file.relative_path == path) {
+                file.contents.push(Definition::Struct(weak_ptr));
+            }
+        }
+        for field_ptr in self.fields {
+            ast.add_named_element(field_ptr);
+        }
+        for custom_type_ptr in self.custom_types {
+            ast.add_named_element(custom_type_ptr);
+        }
+        for sequence_ptr in self.sequences {
+            ast.add_element(sequence_ptr);
+        }
+        for dictionary_ptr in self.dictionaries {
+            ast.add_element(dictionary_ptr);
+        }
+        for result_type_ptr in self.result_types {
+            ast.add_element(result_type_ptr);
+        }
+    }
+}
+
+/// Returns the type references held directly by the provided node which can reference any type.
+fn type_refs_of(node: &Node) -> Vec<&TypeRef> {
+    match node {
+        Node::Field(field_ptr) => vec![&field_ptr.borrow().data_type],
+        Node::Parameter(parameter_ptr) => vec![&parameter_ptr.borrow().data_type],
+        Node::TypeAlias(type_alias_ptr) => vec![&type_alias_ptr.borrow().underlying],
+        Node::Sequence(sequence_ptr) => vec![&sequence_ptr.borrow().element_type],
+        Node::Dictionary(dictionary_ptr) => {
+            let dictionary = dictionary_ptr.borrow();
+            vec![&dictionary.key_type, &dictionary.value_type]
+        }
+        Node::ResultType(result_type_ptr) => {
+            let result_type = result_type_ptr.borrow();
+            vec![&result_type.success_type, &result_type.failure_type]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Mutable version of [type_refs_of]. The type references are returned in the same order.
+unsafe fn type_refs_of_mut(node: &mut Node) -> Vec<&mut TypeRef> {
+    match node {
+        Node::Field(field_ptr) => vec![&mut field_ptr.borrow_mut().data_type],
+        Node::Parameter(parameter_ptr) => vec![&mut parameter_ptr.borrow_mut().data_type],
+        Node::TypeAlias(type_alias_ptr) => vec![&mut type_alias_ptr.borrow_mut().underlying],
+        Node::Sequence(sequence_ptr) => vec![&mut sequence_ptr.borrow_mut().element_type],
+        Node::Dictionary(dictionary_ptr) => {
+            let dictionary = dictionary_ptr.borrow_mut();
+            vec![&mut dictionary.key_type, &mut dictionary.value_type]
+        }
+        Node::ResultType(result_type_ptr) => {
+            let result_type = result_type_ptr.borrow_mut();
+            vec![&mut result_type.success_type, &mut result_type.failure_type]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the unpatched type references held directly by the provided node which can only reference specific kinds of
+/// types (like base classes), as their identifier, number of type arguments, and span.
+fn non_generic_type_refs_of(node: &Node) -> Vec<(&Identifier, usize, &Span)> {
+    fn unpatched<T: Element + ?Sized>(type_ref: &TypeRef<T>) -> Option<(&Identifier, usize, &Span)> {
+        match &type_ref.definition {
+            TypeRefDefinition::Unpatched(identifier) => {
+                Some((identifier, type_ref.type_arguments.len(), &type_ref.span))
+            }
+            TypeRefDefinition::Patched(_) => None,
+        }
+    }
+
+    match node {
+        Node::Class(class_ptr) => class_ptr.borrow().base.iter().filter_map(unpatched).collect(),
+        Node::Exception(exception_ptr) => exception_ptr.borrow().base.iter().filter_map(unpatched).collect(),
+        Node::Interface(interface_ptr) => interface_ptr.borrow().bases.iter().filter_map(unpatched).collect(),
+        Node::Enum(enum_ptr) => enum_ptr.borrow().underlying.iter().filter_map(unpatched).collect(),
+        Node::Operation(operation_ptr) => {
+            let exceptions = operation_ptr.borrow().exception_specification.iter();
+            exceptions.filter_map(unpatched).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the fully scoped identifier of the type held by the provided node, or its keyword if it's a primitive.
+/// Returns `None` if the node doesn't hold a named type.
+fn scoped_identifier_of(node: &Node) -> Option<String> {
+    match node {
+        Node::Struct(struct_ptr) => Some(struct_ptr.borrow().parser_scoped_identifier()),
+        Node::Class(class_ptr) => Some(class_ptr.borrow().parser_scoped_identifier()),
+        Node::Enum(enum_ptr) => Some(enum_ptr.borrow().parser_scoped_identifier()),
+        Node::CustomType(custom_type_ptr) => Some(custom_type_ptr.borrow().parser_scoped_identifier()),
+        Node::TypeAlias(type_alias_ptr) => Some(type_alias_ptr.borrow().parser_scoped_identifier()),
+        Node::Primitive(primitive_ptr) => Some(primitive_ptr.borrow().kind().to_owned()),
+        _ => None,
+    }
+}
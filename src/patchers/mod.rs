@@ -2,8 +2,12 @@
 
 //! TODO write a doc comment for the module.
 
+pub mod class_usage_patcher;
 pub mod comment_link_patcher;
+pub mod constant_patcher;
 pub mod encoding_patcher;
+pub mod generics_patcher;
+pub mod tag_format_patcher;
 pub mod type_ref_patcher;
 
 use crate::ast::node::Node;
@@ -17,15 +21,39 @@ use crate::grammar::Symbol;
 ///
 /// So, after parsing is complete, we modify the AST in place, 'patching' in the information that can only now be
 /// computed, in the following order:
-/// 1. References to other Slice types are verified and resolved.
-/// 2. Compute and store the Slice encodings that each element can be used with.
+/// 1. Constant expressions are evaluated, and enumerators are given their implicit values.
+/// 2. Generic structs are instantiated with the type arguments they're used with.
+/// 3. References to other Slice types are verified and resolved.
+/// 4. Compute and store the Slice encodings that each element can be used with.
+/// 5. Compute and store whether each type uses classes.
+/// 6. Compute and store the tag format of each tagged member.
 ///
 /// This function fails fast, so if any phase of patching fails, we skip any remaining phases.
 pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
-    let attribute_patcher = crate::patch_attributes!("", Allow, Compress, Deprecated, Oneway, SlicedFormat);
+    let attribute_patcher = crate::patch_attributes!(
+        "",
+        Allow,
+        Authors,
+        Boxed,
+        Compress,
+        Context,
+        Deprecated,
+        Flags,
+        Internal,
+        License,
+        Oneway,
+        Package,
+        Size,
+        SlicedFormat,
+        Version
+    );
     compilation_state.apply_unsafe(attribute_patcher);
+    compilation_state.apply_unsafe(constant_patcher::patch_ast);
+    compilation_state.apply_unsafe(generics_patcher::patch_ast);
     compilation_state.apply_unsafe(type_ref_patcher::patch_ast);
     compilation_state.apply_unsafe(encoding_patcher::patch_ast);
+    compilation_state.apply_unsafe(class_usage_patcher::patch_ast);
+    compilation_state.apply_unsafe(tag_format_patcher::patch_ast);
     compilation_state.apply_unsafe(comment_link_patcher::patch_ast);
 }
 
@@ -0,0 +1,40 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::ast::node::Node;
+use crate::compilation_state::CompilationState;
+use crate::grammar::*;
+use std::collections::HashMap;
+
+/// Computes and caches the tag format of each tagged field and parameter (see [Member::tag_format]), so backends
+/// don't need to compute them when generating code.
+pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
+    let files = compilation_state.files.iter();
+    let modes = files
+        .map(|file| (file.relative_path.as_str(), file.compilation_mode()))
+        .collect::<HashMap<_, _>>();
+
+    for node in compilation_state.ast.as_mut_slice() {
+        match node {
+            Node::Field(field_ptr) => {
+                let tag_format = compute_tag_format_of(field_ptr.borrow(), &modes);
+                field_ptr.borrow_mut().tag_format = tag_format;
+            }
+            Node::Parameter(parameter_ptr) => {
+                let tag_format = compute_tag_format_of(parameter_ptr.borrow(), &modes);
+                parameter_ptr.borrow_mut().tag_format = tag_format;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn compute_tag_format_of(member: &impl Member, modes: &HashMap<&str, CompilationMode>) -> Option<TagFormat> {
+    if !member.is_tagged() {
+        return None;
+    }
+
+    match modes[member.span().file.as_str()] {
+        CompilationMode::Slice1 => member.data_type().tag_format(),
+        CompilationMode::Slice2 => Some(TagFormat::VSize),
+    }
+}
@@ -4,27 +4,164 @@ use crate::ast::node::Node;
 use crate::ast::{Ast, LookupError};
 use crate::compilation_state::CompilationState;
 use crate::diagnostics::*;
-use crate::grammar::attributes::Deprecated;
 use crate::grammar::*;
+use crate::slice_file::{SliceFile, Span};
+use crate::unresolved_references::UnresolvedReference;
 use crate::utils::ptr_util::{OwnedPtr, WeakPtr};
+use std::borrow::Cow;
 
+/// Resolves every type reference in the AST to the definition it refers to.
+///
+/// This runs after every file has been parsed, so a definition can be referenced from any file, regardless of the
+/// order the files were passed in, or whether the definition comes before or after its uses in a file. This holds
+/// for type aliases (including chains of aliases) and base types too.
 pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
+    // Collect the using directives of each file, so type references can be resolved using their file's aliases.
+    let using_directives = compilation_state
+        .files
+        .iter()
+        .map(|file| (file.relative_path.as_str(), file.using_directives.as_slice()))
+        .collect();
+
+    let module_aliases = collect_module_aliases(&compilation_state.files);
+
     let mut patcher = TypeRefPatcher {
         type_ref_patches: Vec::new(),
+        using_directives,
+        module_aliases,
         diagnostics: &mut compilation_state.diagnostics,
+        unresolved_references: &mut compilation_state.unresolved_references,
+        reference_search_path: compilation_state.reference_search_path.as_deref(),
+        failed_identifiers: Vec::new(),
     };
 
     // TODO why explain we split this logic so that we can for sure have an immutable AST.
+    patcher.check_using_directive_targets(&compilation_state.ast);
+    patcher.check_module_aliases(&compilation_state.ast);
     patcher.compute_patches(&compilation_state.ast);
     patcher.apply_patches(&mut compilation_state.ast);
 }
 
+/// Returns the module aliases declared in each of the provided files.
+pub(super) fn collect_module_aliases(files: &[SliceFile]) -> Vec<&ModuleAlias> {
+    files.iter().flat_map(|file| &file.module_aliases).collect()
+}
+
+/// Returns the identifier with any using directive alias or module alias it refers to expanded to that alias' target.
+///
+/// Using directive aliases are expanded first (see [expand_using_aliases]). Then, like any other definition, module
+/// aliases are searched for in each enclosing scope, starting from `scope`. The search stops at the first scope where
+/// the identifier either refers to an element in the AST, or starts with a module alias.
+pub(super) fn expand_aliases<'b>(
+    using_directives: &[(&str, &[UsingDirective])],
+    module_aliases: &[&ModuleAlias],
+    identifier: &'b Identifier,
+    scope: &str,
+    ast: &Ast,
+) -> Cow<'b, str> {
+    let identifier = expand_using_aliases(using_directives, identifier);
+    if module_aliases.is_empty() {
+        return identifier;
+    }
+
+    for candidate in UnresolvedReference::candidates_for(&identifier, scope) {
+        if ast.find_node(&candidate).is_ok() {
+            break;
+        }
+        if let Some(expanded_identifier) = module_aliases.iter().find_map(|alias| alias.expand(&candidate)) {
+            return Cow::Owned(expanded_identifier);
+        }
+    }
+    identifier
+}
+
+/// Returns the identifier with any using directive alias it starts with expanded to that directive's target.
+/// Only the aliases declared in the file containing the identifier are considered.
+fn expand_using_aliases<'b>(
+    using_directives: &[(&str, &[UsingDirective])],
+    identifier: &'b Identifier,
+) -> Cow<'b, str> {
+    let file_directives = using_directives.iter().find(|(file, _)| *file == identifier.span.file);
+    let expanded =
+        file_directives.and_then(|(_, directives)| directives.iter().find_map(|d| d.expand(&identifier.value)));
+    match expanded {
+        Some(expanded_identifier) => Cow::Owned(expanded_identifier),
+        None => Cow::Borrowed(&identifier.value),
+    }
+}
+
 struct TypeRefPatcher<'a> {
     type_ref_patches: Vec<PatchKind>,
+    using_directives: Vec<(&'a str, &'a [UsingDirective])>,
+    module_aliases: Vec<&'a ModuleAlias>,
     diagnostics: &'a mut Diagnostics,
+    unresolved_references: &'a mut Vec<UnresolvedReference>,
+    reference_search_path: Option<&'a [String]>,
+
+    /// The spans of the identifiers that have already failed to resolve. A type argument of a generic struct is copied
+    /// into every field of its instantiation that uses the corresponding type parameter, so the same identifier can be
+    /// resolved more than once. Each is only reported the first time.
+    failed_identifiers: Vec<Span>,
 }
 
 impl TypeRefPatcher<'_> {
+    /// Reports an error for any using directive whose target isn't a module or type defined in the AST.
+    fn check_using_directive_targets(&mut self, ast: &Ast) {
+        for using_directive in self
+            .using_directives
+            .iter()
+            .flat_map(|(_, directives)| directives.iter())
+        {
+            let target = using_directive.target_identifier();
+            if !is_module(target, ast) && ast.find_node(target).is_err() {
+                Diagnostic::new(Error::DoesNotExist {
+                    identifier: target.to_owned(),
+                })
+                .set_span(using_directive.target.span())
+                .push_into(self.diagnostics);
+            }
+        }
+    }
+
+    /// Reports an error for any module alias whose target isn't a module, or whose identifier is already used by a
+    /// module, another Slice element, or an earlier module alias.
+    fn check_module_aliases(&mut self, ast: &Ast) {
+        for (i, module_alias) in self.module_aliases.iter().enumerate() {
+            let target = module_alias.target_identifier();
+            if !is_module(target, ast) {
+                let error = match ast.find_node(target) {
+                    Ok(node) => Error::TypeMismatch {
+                        expected: "module".to_owned(),
+                        actual: <&dyn Element>::from(node).kind().to_owned(),
+                        is_concrete: true,
+                    },
+                    Err(_) => Error::DoesNotExist {
+                        identifier: target.to_owned(),
+                    },
+                };
+                Diagnostic::new(error)
+                    .set_span(module_alias.target.span())
+                    .push_into(self.diagnostics);
+            }
+
+            let identifier = module_alias.scoped_identifier();
+            let original = self.module_aliases[..i]
+                .iter()
+                .find(|other| other.scoped_identifier() == identifier);
+            if original.is_some() || is_module(&identifier, ast) || ast.find_node(&identifier).is_ok() {
+                let mut diagnostic = Diagnostic::new(Error::Redefinition {
+                    identifier: identifier.clone(),
+                })
+                .set_span(module_alias.alias.span());
+                if let Some(original) = original {
+                    let message = format!("'{identifier}' was previously defined here");
+                    diagnostic = diagnostic.add_note(message, Some(original.alias.span()));
+                }
+                diagnostic.push_into(self.diagnostics);
+            }
+        }
+    }
+
     fn compute_patches(&mut self, ast: &Ast) {
         for node in ast.as_slice() {
             let patch = match node {
@@ -105,15 +242,15 @@ impl TypeRefPatcher<'_> {
         // patching, then we patch in its definition and any attributes it might of picked up from type aliases.
         for (patch, element) in self.type_ref_patches.into_iter().zip(elements) {
             match patch {
-                PatchKind::BaseClass((base_class_ptr, attributes)) => {
+                PatchKind::BaseClass((base_class_ptr, attributes, type_aliases)) => {
                     let class_ptr: &mut OwnedPtr<Class> = element.try_into().unwrap();
                     let base_class_ref = class_ptr.borrow_mut().base.as_mut().unwrap();
-                    base_class_ref.patch(base_class_ptr, attributes);
+                    base_class_ref.patch(base_class_ptr, attributes, type_aliases);
                 }
-                PatchKind::BaseException((base_exception_ptr, attributes)) => {
+                PatchKind::BaseException((base_exception_ptr, attributes, type_aliases)) => {
                     let exception_ptr: &mut OwnedPtr<Exception> = element.try_into().unwrap();
                     let base_exception_ref = exception_ptr.borrow_mut().base.as_mut().unwrap();
-                    base_exception_ref.patch(base_exception_ptr, attributes);
+                    base_exception_ref.patch(base_exception_ptr, attributes, type_aliases);
                 }
                 PatchKind::BaseInterfaces(base_interface_patches) => {
                     let interface_ptr: &mut OwnedPtr<Interface> = element.try_into().unwrap();
@@ -122,20 +259,20 @@ impl TypeRefPatcher<'_> {
 
                     // Iterate through and patch each base interface.
                     for (j, patch) in base_interface_patches.into_iter().enumerate() {
-                        let (base_interface_ptr, attributes) = patch;
+                        let (base_interface_ptr, attributes, type_aliases) = patch;
                         let base_interface_ref = &mut interface_ptr.borrow_mut().bases[j];
-                        base_interface_ref.patch(base_interface_ptr, attributes);
+                        base_interface_ref.patch(base_interface_ptr, attributes, type_aliases);
                     }
                 }
-                PatchKind::FieldType((field_type_ptr, attributes)) => {
+                PatchKind::FieldType((field_type_ptr, attributes, type_aliases)) => {
                     let field_ptr: &mut OwnedPtr<Field> = element.try_into().unwrap();
                     let field_type_ref = &mut field_ptr.borrow_mut().data_type;
-                    field_type_ref.patch(field_type_ptr, attributes);
+                    field_type_ref.patch(field_type_ptr, attributes, type_aliases);
                 }
-                PatchKind::ParameterType((parameter_type_ptr, attributes)) => {
+                PatchKind::ParameterType((parameter_type_ptr, attributes, type_aliases)) => {
                     let parameter_ptr: &mut OwnedPtr<Parameter> = element.try_into().unwrap();
                     let parameter_type_ref = &mut parameter_ptr.borrow_mut().data_type;
-                    parameter_type_ref.patch(parameter_type_ptr, attributes);
+                    parameter_type_ref.patch(parameter_type_ptr, attributes, type_aliases);
                 }
                 PatchKind::ExceptionSpecification(exception_patches) => {
                     let operation_ptr: &mut OwnedPtr<Operation> = element.try_into().unwrap();
@@ -145,42 +282,54 @@ impl TypeRefPatcher<'_> {
 
                     // Iterate through and patch each exception type.
                     for (j, patch) in exception_patches.into_iter().enumerate() {
-                        let (exception_type_ptr, attributes) = patch;
+                        let (exception_type_ptr, attributes, type_aliases) = patch;
                         let exception_type_ref = &mut exception_specification[j];
-                        exception_type_ref.patch(exception_type_ptr, attributes);
+                        exception_type_ref.patch(exception_type_ptr, attributes, type_aliases);
                     }
                 }
-                PatchKind::EnumUnderlyingType((enum_underlying_type_ptr, attributes)) => {
+                PatchKind::EnumUnderlyingType((enum_underlying_type_ptr, attributes, type_aliases)) => {
                     let enum_ptr: &mut OwnedPtr<Enum> = element.try_into().unwrap();
                     let enum_underlying_type_ref = enum_ptr.borrow_mut().underlying.as_mut().unwrap();
-                    enum_underlying_type_ref.patch(enum_underlying_type_ptr, attributes);
+                    enum_underlying_type_ref.patch(enum_underlying_type_ptr, attributes, type_aliases);
                 }
-                PatchKind::TypeAliasUnderlyingType((type_alias_underlying_type_ptr, attributes)) => {
+                PatchKind::TypeAliasUnderlyingType((type_alias_underlying_type_ptr, attributes, type_aliases)) => {
                     let type_alias_ptr: &mut OwnedPtr<TypeAlias> = element.try_into().unwrap();
                     let type_alias_underlying_type_ref = &mut type_alias_ptr.borrow_mut().underlying;
-                    type_alias_underlying_type_ref.patch(type_alias_underlying_type_ptr, attributes);
+                    type_alias_underlying_type_ref.patch(type_alias_underlying_type_ptr, attributes, type_aliases);
                 }
                 PatchKind::ResultTypes(success_patch, failure_patch) => {
                     let result_ptr: &mut OwnedPtr<ResultType> = element.try_into().unwrap();
-                    if let Some((success_type_ptr, attributes)) = success_patch {
-                        result_ptr.borrow_mut().success_type.patch(success_type_ptr, attributes);
+                    if let Some((success_type_ptr, attributes, type_aliases)) = success_patch {
+                        result_ptr
+                            .borrow_mut()
+                            .success_type
+                            .patch(success_type_ptr, attributes, type_aliases);
                     }
-                    if let Some((failure_type_ptr, attributes)) = failure_patch {
-                        result_ptr.borrow_mut().failure_type.patch(failure_type_ptr, attributes);
+                    if let Some((failure_type_ptr, attributes, type_aliases)) = failure_patch {
+                        result_ptr
+                            .borrow_mut()
+                            .failure_type
+                            .patch(failure_type_ptr, attributes, type_aliases);
                     }
                 }
-                PatchKind::SequenceType((element_type_ptr, attributes)) => {
+                PatchKind::SequenceType((element_type_ptr, attributes, type_aliases)) => {
                     let sequence_ptr: &mut OwnedPtr<Sequence> = element.try_into().unwrap();
                     let element_type_ref = &mut sequence_ptr.borrow_mut().element_type;
-                    element_type_ref.patch(element_type_ptr, attributes);
+                    element_type_ref.patch(element_type_ptr, attributes, type_aliases);
                 }
                 PatchKind::DictionaryTypes(key_patch, value_patch) => {
                     let dictionary_ptr: &mut OwnedPtr<Dictionary> = element.try_into().unwrap();
-                    if let Some((key_type_ptr, attributes)) = key_patch {
-                        dictionary_ptr.borrow_mut().key_type.patch(key_type_ptr, attributes);
+                    if let Some((key_type_ptr, attributes, type_aliases)) = key_patch {
+                        dictionary_ptr
+                            .borrow_mut()
+                            .key_type
+                            .patch(key_type_ptr, attributes, type_aliases);
                     }
-                    if let Some((value_type_ptr, attributes)) = value_patch {
-                        dictionary_ptr.borrow_mut().value_type.patch(value_type_ptr, attributes);
+                    if let Some((value_type_ptr, attributes, type_aliases)) = value_patch {
+                        dictionary_ptr
+                            .borrow_mut()
+                            .value_type
+                            .patch(value_type_ptr, attributes, type_aliases);
                     }
                 }
                 PatchKind::None => {}
@@ -190,7 +339,7 @@ impl TypeRefPatcher<'_> {
 
     fn resolve_definition<'a, T>(&mut self, type_ref: &TypeRef<T>, ast: &'a Ast) -> Option<Patch<T>>
     where
-        T: Element + ?Sized,
+        T: Element + ExpectedKind + ?Sized,
         &'a Node: TryInto<WeakPtr<T>, Error = LookupError>,
     {
         // If the definition is already patched, we skip the function and return `None` immediately.
@@ -201,24 +350,63 @@ impl TypeRefPatcher<'_> {
         // First, lookup the type as a node in the AST.
         // Second, handle the case where the type is an alias (by resolving down to its concrete underlying type).
         // Third, get the type's pointer from its node and attempt to cast it to `T` (the required Slice type).
+        let expanded_identifier = expand_aliases(
+            &self.using_directives,
+            &self.module_aliases,
+            identifier,
+            type_ref.module_scope(),
+            ast,
+        );
         let lookup_result = ast
-            .find_node_with_scope(&identifier.value, type_ref.module_scope())
+            .find_node_with_scope(&expanded_identifier, type_ref.module_scope())
             .and_then(|node| {
                 // We perform the deprecation check here instead of the validators since we need to check type-aliases
                 // which are resolved and erased after TypeRef patching is completed.
                 self.check_for_deprecated_type(type_ref, node);
 
-                if let Node::TypeAlias(type_alias) = node {
-                    self.resolve_type_alias(type_alias.borrow(), ast)
+                // Check whether the identifier could refer to other definitions than the one it resolved to.
+                if expanded_identifier != identifier.value {
+                    self.check_for_ambiguous_reference(type_ref, identifier, node, ast);
                 } else {
-                    try_into_patch(node, Vec::new())
+                    self.check_for_shadowed_definition(type_ref, &identifier.value, node, ast);
+                }
+
+                if let Node::TypeAlias(type_alias_ptr) = node {
+                    self.resolve_type_alias(type_alias_ptr, ast)
+                } else {
+                    try_into_patch(node, Vec::new(), Vec::new())
                 }
             });
 
         // If we resolved a definition for the type reference, return it, otherwise report what went wrong.
         match lookup_result {
-            Ok(definition) => Some(definition),
+            Ok(definition) => {
+                tracing::debug!(
+                    identifier = identifier.value,
+                    expanded_identifier = %expanded_identifier,
+                    scope = type_ref.module_scope(),
+                    type_aliases = definition.2.len(),
+                    "resolved type reference",
+                );
+                Some(definition)
+            }
+            Err(_) if self.failed_identifiers.contains(identifier.span()) => None,
             Err(err) => {
+                self.failed_identifiers.push(identifier.span().clone());
+
+                // Record the reference as unresolved, so it can be reported separately from other errors.
+                let found = match &err {
+                    LookupError::TypeMismatch { actual, .. } => Some(actual.clone()),
+                    LookupError::DoesNotExist { .. } => None,
+                };
+                self.unresolved_references.push(UnresolvedReference {
+                    identifier: identifier.value.clone(),
+                    expected: T::EXPECTED_KIND.to_owned(),
+                    found,
+                    candidates: UnresolvedReference::candidates_for(&expanded_identifier, type_ref.module_scope()),
+                    span: identifier.span().clone(),
+                });
+
                 let mapped_error = match err {
                     LookupError::DoesNotExist { identifier } => Error::DoesNotExist { identifier },
                     LookupError::TypeMismatch {
@@ -231,19 +419,117 @@ impl TypeRefPatcher<'_> {
                         is_concrete,
                     },
                 };
-                Diagnostic::new(mapped_error)
-                    .set_span(identifier.span())
-                    .push_into(self.diagnostics);
+                let is_missing = matches!(mapped_error, Error::DoesNotExist { .. });
+                let mut diagnostic = Diagnostic::new(mapped_error).set_span(identifier.span());
+
+                // If the definition doesn't exist, it may be in a reference file that wasn't found, so we list where
+                // reference files were searched for.
+                if let Some(search_path) = self.reference_search_path.filter(|_| is_missing) {
+                    let message = match search_path {
+                        [] => "no reference files were searched; they can be provided with '-R'".to_owned(),
+                        _ => {
+                            let paths = search_path.iter().map(|p| format!("'{p}'")).collect::<Vec<_>>();
+                            format!("reference files were searched for in: {}", paths.join(", "))
+                        }
+                    };
+                    diagnostic = diagnostic.add_note(message, None);
+                }
+                tracing::debug!(
+                    identifier = identifier.value,
+                    expanded_identifier = %expanded_identifier,
+                    scope = type_ref.module_scope(),
+                    error = diagnostic.message(),
+                    "failed to resolve type reference",
+                );
+                diagnostic.push_into(self.diagnostics);
                 None
             }
         }
     }
 
+    /// If `identifier` starts with a using directive's alias, but would resolve to a different node than `node` without
+    /// expanding the alias, then the reference is ambiguous. We report an error listing both candidates, but still
+    /// resolve the reference to the alias' target.
+    fn check_for_ambiguous_reference<T: Element + ?Sized>(
+        &mut self,
+        type_ref: &TypeRef<T>,
+        identifier: &Identifier,
+        node: &Node,
+        ast: &Ast,
+    ) {
+        let Ok(other_node) = ast.find_node_with_scope(&identifier.value, type_ref.module_scope()) else { return };
+        if std::ptr::eq(node, other_node) {
+            return;
+        }
+
+        let candidates = [node, other_node];
+        let mut diagnostic = Diagnostic::new(Error::AmbiguousReference {
+            identifier: identifier.value.clone(),
+            candidates: candidates.map(|n| format!("'{}'", describe_node(n))).join(", "),
+        })
+        .set_span(identifier.span());
+        for candidate in candidates {
+            if let Ok(entity) = <&dyn Entity>::try_from(candidate) {
+                let message = format!("'{}' is defined here:", entity.parser_scoped_identifier());
+                diagnostic = diagnostic.add_note(message, Some(entity.span()));
+            }
+        }
+        let suggestion = describe_node(node);
+        diagnostic
+            .add_note(
+                format!("use a globally scoped identifier, like '::{suggestion}', to refer to a specific element"),
+                None,
+            )
+            .push_into(self.diagnostics);
+    }
+
+    /// If the reference is relatively scoped, and the identifier is also defined in a scope enclosing `node`'s, then
+    /// `node` shadows that definition, so we report a lint, suggesting to use a globally scoped identifier instead.
+    fn check_for_shadowed_definition<T: Element + ?Sized>(
+        &mut self,
+        type_ref: &TypeRef<T>,
+        identifier: &str,
+        node: &Node,
+        ast: &Ast,
+    ) {
+        // Modules can't be referenced by types, so we don't consider them to shadow, or be shadowed by, anything.
+        if matches!(node, Node::Module(_)) {
+            return;
+        }
+        let candidates = ast.find_nodes_with_scope(identifier, type_ref.module_scope());
+        let mut shadowed_nodes = candidates
+            .into_iter()
+            .filter(|candidate| !std::ptr::eq(*candidate, node) && !matches!(candidate, Node::Module(_)));
+        let Some(shadowed_node) = shadowed_nodes.next() else { return };
+
+        let chosen = describe_node(node);
+        let shadowed = describe_node(shadowed_node);
+        let mut diagnostic = Diagnostic::new(Lint::ShadowedReference {
+            identifier: identifier.to_owned(),
+            chosen: chosen.clone(),
+            shadowed: shadowed.clone(),
+        })
+        .set_span(type_ref.span())
+        .set_scope(type_ref.parser_scope());
+        for candidate in [node, shadowed_node] {
+            if let Ok(entity) = <&dyn Entity>::try_from(candidate) {
+                let message = format!("'{}' is defined here:", entity.parser_scoped_identifier());
+                diagnostic = diagnostic.add_note(message, Some(entity.span()));
+            }
+        }
+        diagnostic
+            .add_note(
+                format!("use '::{chosen}' to keep referring to '{chosen}', or '::{shadowed}' to refer to '{shadowed}'"),
+                None,
+            )
+            .push_into(self.diagnostics);
+    }
+
     fn check_for_deprecated_type<T: Element + ?Sized>(&mut self, type_ref: &TypeRef<T>, node: &Node) {
         // Check if the type is an entity, and if so, check if it has the `deprecated` attribute.
         // Only entities can be deprecated, so this check is sufficient.
         if let Ok(entity) = <&dyn Entity>::try_from(node) {
-            if let Some(deprecated) = entity.find_attribute::<Deprecated>() {
+            if let Some(deprecated) = entity.deprecation() {
                 // Compute the lint message. The `deprecated` attribute can have either 0 or 1 arguments, so we
                 // only check the first argument. If it's present, we attach it to the lint message.
                 let identifier = entity.identifier().to_owned();
@@ -260,7 +546,11 @@ impl TypeRefPatcher<'_> {
         }
     }
 
-    fn resolve_type_alias<'a, T>(&mut self, type_alias: &'a TypeAlias, ast: &'a Ast) -> Result<Patch<T>, LookupError>
+    fn resolve_type_alias<'a, T>(
+        &mut self,
+        type_alias_ptr: &'a OwnedPtr<TypeAlias>,
+        ast: &'a Ast,
+    ) -> Result<Patch<T>, LookupError>
     where
         T: Element + ?Sized,
         &'a Node: TryInto<WeakPtr<T>, Error = LookupError>,
@@ -273,8 +563,10 @@ impl TypeRefPatcher<'_> {
         let mut type_alias_chain = Vec::new();
 
         let mut attributes: Vec<WeakPtr<Attribute>> = Vec::new();
-        let mut current_type_alias = type_alias;
+        let mut type_aliases: Vec<WeakPtr<TypeAlias>> = Vec::new();
+        let mut current_type_alias_ptr = type_alias_ptr;
         loop {
+            let current_type_alias = current_type_alias_ptr.borrow();
             let type_alias_id = current_type_alias.module_scoped_identifier();
 
             // If we've already seen the current type alias, it must have a cycle in it's definition.
@@ -303,8 +595,18 @@ impl TypeRefPatcher<'_> {
             // If we reach this point, we haven't hit a cycle in the type aliases yet.
 
             type_alias_chain.push(current_type_alias.module_scoped_identifier());
+            type_aliases.push(current_type_alias_ptr.downgrade());
             let underlying_type = &current_type_alias.underlying;
-            attributes.extend(underlying_type.attributes.clone());
+            // Only type-metadata attributes are forwarded from an alias to the type references that use it.
+            let underlying_attributes = underlying_type
+                .attributes
+                .iter()
+                .chain(&underlying_type.inherited_attributes);
+            attributes.extend(
+                underlying_attributes
+                    .filter(|a| a.borrow().kind.is_type_metadata())
+                    .cloned(),
+            );
 
             // If we hit a type alias that is already patched, we immediately return its underlying type.
             // Otherwise we retrieve the alias' type string and try to resolve it in the ast.
@@ -313,24 +615,77 @@ impl TypeRefPatcher<'_> {
                     // Lookup the node that is being aliased in the AST, and convert it into a patch.
                     // TODO: when `T = dyn Type` we can skip this, and use `ptr.clone()` directly.
                     let node = ast.as_slice().iter().find(|node| ptr == &<&dyn Element>::from(*node));
-                    return try_into_patch(node.unwrap(), attributes);
+                    let Some(node) = node else {
+                        return Err(LookupError::DoesNotExist {
+                            identifier: current_type_alias.module_scoped_identifier(),
+                        });
+                    };
+                    type_aliases.extend(underlying_type.type_aliases.iter().cloned());
+                    return try_into_patch(node, attributes, type_aliases);
                 }
                 TypeRefDefinition::Unpatched(identifier) => identifier,
             };
 
             // We hit another unpatched alias; try to resolve its underlying type's identifier in the AST.
-            let node = ast.find_node_with_scope(&identifier.value, underlying_type.module_scope())?;
+            let scope = underlying_type.module_scope();
+            let identifier = expand_aliases(&self.using_directives, &self.module_aliases, identifier, scope, ast);
+            let node = ast.find_node_with_scope(&identifier, scope)?;
             // If the resolved node is another type alias, push it onto the chain and loop again, otherwise return it.
-            if let Node::TypeAlias(next_type_alias) = node {
-                current_type_alias = next_type_alias.borrow();
+            if let Node::TypeAlias(next_type_alias_ptr) = node {
+                current_type_alias_ptr = next_type_alias_ptr;
             } else {
-                return try_into_patch(node, attributes);
+                return try_into_patch(node, attributes, type_aliases);
             }
         }
     }
 }
 
-type Patch<T> = (WeakPtr<T>, Vec<WeakPtr<Attribute>>);
+/// Returns true if `identifier` is the fully scoped identifier of a module, or the leading segments of one.
+fn is_module(identifier: &str, ast: &Ast) -> bool {
+    ast.as_slice().iter().any(|node| match node {
+        Node::Module(module_ptr) => {
+            let module_identifier = module_ptr.borrow().nested_module_identifier();
+            let rest = module_identifier.strip_prefix(identifier);
+            rest.is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        }
+        _ => false,
+    })
+}
+
+/// Returns the fully scoped identifier of the element held by `node`, or its kind if it doesn't have an identifier.
+fn describe_node(node: &Node) -> String {
+    match <&dyn NamedSymbol>::try_from(node) {
+        Ok(symbol) => symbol.parser_scoped_identifier(),
+        Err(_) => <&dyn Element>::from(node).kind().to_owned(),
+    }
+}
+
+/// The kind of element that a [TypeRef] to `Self` must resolve to, as reported in [UnresolvedReference]s.
+trait ExpectedKind {
+    const EXPECTED_KIND: &'static str;
+}
+
+impl ExpectedKind for dyn Type {
+    const EXPECTED_KIND: &'static str = "type";
+}
+
+impl ExpectedKind for Class {
+    const EXPECTED_KIND: &'static str = "class";
+}
+
+impl ExpectedKind for Exception {
+    const EXPECTED_KIND: &'static str = "exception";
+}
+
+impl ExpectedKind for Interface {
+    const EXPECTED_KIND: &'static str = "interface";
+}
+
+impl ExpectedKind for Primitive {
+    const EXPECTED_KIND: &'static str = "primitive";
+}
+
+type Patch<T> = (WeakPtr<T>, Vec<WeakPtr<Attribute>>, Vec<WeakPtr<TypeAlias>>);
 
 #[derive(Default)]
 enum PatchKind {
@@ -349,9 +704,13 @@ enum PatchKind {
     DictionaryTypes(Option<Patch<dyn Type>>, Option<Patch<dyn Type>>),
 }
 
-fn try_into_patch<'a, T: ?Sized>(node: &'a Node, attributes: Vec<WeakPtr<Attribute>>) -> Result<Patch<T>, LookupError>
+fn try_into_patch<'a, T: ?Sized>(
+    node: &'a Node,
+    attributes: Vec<WeakPtr<Attribute>>,
+    type_aliases: Vec<WeakPtr<TypeAlias>>,
+) -> Result<Patch<T>, LookupError>
 where
     &'a Node: TryInto<WeakPtr<T>, Error = LookupError>,
 {
-    node.try_into().map(|ptr| (ptr, attributes))
+    node.try_into().map(|ptr| (ptr, attributes, type_aliases))
 }
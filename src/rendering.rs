@@ -0,0 +1,399 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module renders entities and their doc comments as text, for tools which display them to users, like
+//! language servers answering hover requests, and documentation backends.
+//!
+//! Signatures are rendered in a canonical form, independent of how the entity was written: types are referenced by
+//! their fully scoped identifiers, and each signature fits on a single line.
+
+use crate::grammar::*;
+
+/// Returns the canonical one-line Slice signature of the provided entity, including its attributes.
+///
+/// Types, exceptions, and interfaces are rendered with their fully scoped identifiers (`struct Foo::S`), while
+/// members, operations, and enumerators are rendered with their unscoped identifiers, as they'd appear in their
+/// parent's definition (`tag(1) a: Foo::S?`). Types referenced through a type alias are rendered as the alias.
+///
+/// Attributes are rendered with their directive and arguments. Arguments that aren't identifiers or integers are
+/// rendered as string literals.
+pub fn render_signature(entity: &dyn Entity) -> String {
+    render_signature_with(entity, RenderOptions::SIGNATURE)
+}
+
+/// Options that control how entities are rendered.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RenderOptions {
+    /// If true, entities are rendered with their attributes.
+    pub include_attributes: bool,
+
+    /// If true, types referenced through a type alias are rendered as the alias's underlying type, instead of the alias.
+    pub expand_type_aliases: bool,
+}
+
+impl RenderOptions {
+    /// The options used by [render_signature].
+    pub const SIGNATURE: Self = RenderOptions {
+        include_attributes: true,
+        expand_type_aliases: false,
+    };
+}
+
+/// Returns the one-line Slice signature of the provided entity, rendered with the provided options.
+pub(crate) fn render_signature_with(entity: &dyn Entity, options: RenderOptions) -> String {
+    let identifier = entity.parser_scoped_identifier();
+    let signature = match entity.concrete_entity() {
+        Entities::Struct(struct_def) => {
+            let compact = if struct_def.is_compact { "compact " } else { "" };
+            let type_parameters = struct_def.type_parameters.iter().map(|p| p.value.as_str());
+            match struct_def.is_generic() {
+                true => format!(
+                    "{compact}struct {identifier}<{}>",
+                    type_parameters.collect::<Vec<_>>().join(", ")
+                ),
+                false => format!("{compact}struct {identifier}"),
+            }
+        }
+        Entities::Class(class_def) => {
+            let compact_id = class_def.compact_id.as_ref().map(|id| format!("({})", id.value));
+            let base = class_def
+                .base
+                .as_ref()
+                .map(|base| format!(" : {}", render_type(base, options)));
+            format!(
+                "class {identifier}{}{}",
+                compact_id.unwrap_or_default(),
+                base.unwrap_or_default(),
+            )
+        }
+        Entities::Exception(exception_def) => {
+            let base = exception_def.base_exception();
+            let base = base.map(|base| format!(" : {}", base.parser_scoped_identifier()));
+            format!("exception {identifier}{}", base.unwrap_or_default())
+        }
+        Entities::Interface(interface_def) => {
+            let bases = interface_def.base_interfaces().into_iter();
+            let bases = bases.map(|base| base.parser_scoped_identifier()).collect::<Vec<_>>();
+            match bases.is_empty() {
+                true => format!("interface {identifier}"),
+                false => format!("interface {identifier} : {}", bases.join(", ")),
+            }
+        }
+        Entities::Enum(enum_def) => {
+            let compact = if enum_def.is_compact { "compact " } else { "" };
+            let unchecked = if enum_def.is_unchecked { "unchecked " } else { "" };
+            let underlying = enum_def
+                .underlying
+                .as_ref()
+                .map(|u| format!(" : {}", render_type(u, options)));
+            format!(
+                "{compact}{unchecked}enum {identifier}{}",
+                underlying.unwrap_or_default()
+            )
+        }
+        Entities::CustomType(_) => format!("custom {identifier}"),
+        Entities::TypeAlias(type_alias) => {
+            format!(
+                "typealias {identifier} = {}",
+                render_type(&type_alias.underlying, options)
+            )
+        }
+        Entities::Constant(constant) => format!("const {identifier} = {}", constant.value()),
+        Entities::Operation(operation) => render_operation(operation, options),
+        Entities::Field(field) => render_member(field, options),
+        Entities::Parameter(parameter) => render_member(parameter, options),
+        Entities::Enumerator(enumerator) => {
+            let fields = enumerator
+                .fields
+                .as_ref()
+                .map(|_| render_members(enumerator.fields(), options));
+            format!(
+                "{}{} = {}",
+                enumerator.identifier(),
+                fields.unwrap_or_default(),
+                enumerator.value(),
+            )
+        }
+    };
+
+    match render_attributes(entity.attributes()) {
+        _ if !options.include_attributes => signature,
+        attributes if attributes.is_empty() => signature,
+        attributes => format!("{attributes} {signature}"),
+    }
+}
+
+/// Returns the text of the provided doc comment, formatted for display.
+///
+/// The overview comes first, followed by a section for each kind of tag the comment has. Links are replaced by the
+/// fully scoped identifiers of the entities they link to.
+///
+/// The comment's localizations aren't included. To render a comment in a specific locale, render its localization:
+/// `render_doc_comment(comment.localized("fr"))`.
+pub fn render_doc_comment(comment: &DocComment) -> String {
+    let mut sections = Vec::new();
+
+    if let Some(overview) = &comment.overview {
+        sections.push(render_message(overview));
+    }
+
+    if !comment.params.is_empty() {
+        let lines = comment.params.iter().map(|tag| {
+            let message = render_message(&tag.message);
+            format!("- {}: {message}", tag.identifier.value)
+        });
+        sections.push(section("Parameters:", lines));
+    }
+
+    if !comment.returns.is_empty() {
+        let lines = comment.returns.iter().map(|tag| {
+            let message = render_message(&tag.message);
+            match &tag.identifier {
+                Some(identifier) => format!("- {}: {message}", identifier.value),
+                None => format!("- {message}"),
+            }
+        });
+        sections.push(section("Returns:", lines));
+    }
+
+    if !comment.throws.is_empty() {
+        let lines = comment.throws.iter().map(|tag| {
+            let thrown_type = match tag.thrown_type() {
+                Ok(exception) => exception.parser_scoped_identifier(),
+                Err(identifier) => identifier.value.clone(),
+            };
+            format!("- {thrown_type}: {}", render_message(&tag.message))
+        });
+        sections.push(section("Throws:", lines));
+    }
+
+    if !comment.see.is_empty() {
+        let lines = comment
+            .see
+            .iter()
+            .map(|tag| format!("- {}", render_link(tag.linked_entity())));
+        sections.push(section("See:", lines));
+    }
+
+    sections.join("\n\n")
+}
+
+/// Returns the signature of the provided entity, followed by its formatted doc comment (if it has one).
+/// This is the text language servers should display when hovering over the entity.
+pub fn render_hover(entity: &dyn Entity) -> String {
+    let signature = render_signature(entity);
+    let comment = match entity.concrete_entity() {
+        Entities::Struct(struct_def) => struct_def.comment(),
+        Entities::Class(class_def) => class_def.comment(),
+        Entities::Exception(exception_def) => exception_def.comment(),
+        Entities::Field(field) => field.comment(),
+        Entities::Interface(interface_def) => interface_def.comment(),
+        Entities::Operation(operation) => operation.comment(),
+        Entities::Enum(enum_def) => enum_def.comment(),
+        Entities::Enumerator(enumerator) => enumerator.comment(),
+        Entities::CustomType(custom_type) => custom_type.comment(),
+        Entities::TypeAlias(type_alias) => type_alias.comment(),
+        Entities::Constant(constant) => constant.comment(),
+        Entities::Parameter(parameter) => {
+            // Parameters don't have doc comments, but they can be described by their operation's `@param` tags.
+            let operation_comment = parameter.parent().comment();
+            let param_tag = operation_comment.and_then(|comment| {
+                let mut tags = comment.params.iter();
+                tags.find(|tag| tag.identifier.value == parameter.identifier())
+            });
+            return match param_tag {
+                Some(tag) => format!("{signature}\n\n{}", render_message(&tag.message)),
+                None => signature,
+            };
+        }
+    };
+
+    match comment.map(render_doc_comment) {
+        Some(comment) if !comment.is_empty() => format!("{signature}\n\n{comment}"),
+        _ => signature,
+    }
+}
+
+fn render_operation(operation: &Operation, options: RenderOptions) -> String {
+    let idempotent = if operation.is_idempotent { "idempotent " } else { "" };
+    let mut signature = format!(
+        "{idempotent}{}{}",
+        operation.identifier(),
+        render_members(operation.parameters(), options),
+    );
+
+    match operation.return_members().as_slice() {
+        [] => {}
+        [member] if member.is_synthesized => {
+            let tag = member.tag().map(|tag| format!("tag({tag}) "));
+            signature = format!(
+                "{signature} -> {}{}",
+                tag.unwrap_or_default(),
+                render_member_type(*member, options)
+            );
+        }
+        members => signature = format!("{signature} -> {}", render_members(members.to_vec(), options)),
+    }
+
+    let exceptions = operation.exception_specification.iter();
+    let exceptions = exceptions.map(|e| e.parser_scoped_identifier()).collect::<Vec<_>>();
+    match exceptions.as_slice() {
+        [] if operation.throws_any_exception.is_some() => format!("{signature} throws AnyException"),
+        [] => signature,
+        [exception] => format!("{signature} throws {exception}"),
+        exceptions => format!("{signature} throws ({})", exceptions.join(", ")),
+    }
+}
+
+/// Returns the provided members as a parenthesized, comma-separated list.
+pub(crate) fn render_members<T: Member>(members: Vec<&T>, options: RenderOptions) -> String {
+    let members = members.into_iter().map(|member| render_member(member, options));
+    let members = members.collect::<Vec<_>>();
+    format!("({})", members.join(", "))
+}
+
+fn render_member(member: &impl Member, options: RenderOptions) -> String {
+    let tag = member.tag().map(|tag| format!("tag({tag}) "));
+    format!(
+        "{}{}: {}",
+        tag.unwrap_or_default(),
+        member.identifier(),
+        render_member_type(member, options),
+    )
+}
+
+/// Returns the type of the provided member, prefixed by `stream` if it's a streamed parameter.
+fn render_member_type(member: &impl Member, options: RenderOptions) -> String {
+    let is_streamed = matches!(member.concrete_entity(), Entities::Parameter(parameter) if parameter.is_streamed);
+    let stream = if is_streamed { "stream " } else { "" };
+    format!("{stream}{}", render_type(member.data_type(), options))
+}
+
+/// Returns the provided type, with any user-defined types (or type aliases) fully scoped.
+fn render_type<T: Type + ?Sized>(type_ref: &TypeRef<T>, options: RenderOptions) -> String {
+    let type_aliases = type_ref.type_aliases();
+    let type_alias = type_aliases.first().filter(|_| !options.expand_type_aliases);
+    let mut s = match type_alias {
+        Some(type_alias) => type_alias.parser_scoped_identifier(),
+        None => match type_ref.concrete_type() {
+            Types::Struct(struct_def) => struct_def.parser_scoped_identifier(),
+            Types::Class(class_def) => class_def.parser_scoped_identifier(),
+            Types::Enum(enum_def) => enum_def.parser_scoped_identifier(),
+            Types::CustomType(custom_type) => custom_type.parser_scoped_identifier(),
+            Types::ResultType(result_type) => format!(
+                "Result<{}, {}>",
+                render_type(&result_type.success_type, options),
+                render_type(&result_type.failure_type, options),
+            ),
+            Types::Sequence(sequence) => format!("Sequence<{}>", render_type(&sequence.element_type, options)),
+            Types::Dictionary(dictionary) => format!(
+                "Dictionary<{}, {}>",
+                render_type(&dictionary.key_type, options),
+                render_type(&dictionary.value_type, options),
+            ),
+            Types::Primitive(primitive) => primitive.kind().to_owned(),
+        },
+    };
+    if type_ref.is_optional {
+        s += "?";
+    }
+    s
+}
+
+fn render_attributes(attributes: Vec<&Attribute>) -> String {
+    let attributes = attributes.into_iter().map(|attribute| {
+        let directive = attribute.kind.directive();
+        let arguments = match attribute.downcast::<attributes::Unparsed>() {
+            // Unparsed arguments are rendered as they were written, using the lexemes of any literals.
+            Some(unparsed) => {
+                let arguments = unparsed.args.iter().zip(&unparsed.arg_literals);
+                let arguments = arguments.map(|(arg, literal)| literal.as_ref().map_or(arg.as_str(), Literal::lexeme));
+                arguments.map(str::to_owned).collect()
+            }
+            None => {
+                let arguments = attribute_arguments(attribute).into_iter();
+                arguments
+                    .map(|argument| render_attribute_argument(&argument))
+                    .collect::<Vec<_>>()
+            }
+        };
+        match arguments.is_empty() {
+            true => format!("[{directive}]"),
+            false => format!("[{directive}({})]", arguments.join(", ")),
+        }
+    });
+    attributes.collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the arguments of a parsed attribute, rebuilt from its parsed values. Attributes parsed by language mappings
+/// have no arguments, since slicec can't inspect them.
+fn attribute_arguments(attribute: &Attribute) -> Vec<String> {
+    let flags = |first: bool, second: bool| {
+        let flags = [(first, "Args"), (second, "Return")].into_iter();
+        flags
+            .filter(|(is_set, _)| *is_set)
+            .map(|(_, flag)| flag.to_owned())
+            .collect()
+    };
+
+    if let Some(allow) = attribute.downcast::<attributes::Allow>() {
+        allow.allowed_lints.clone()
+    } else if let Some(compress) = attribute.downcast::<attributes::Compress>() {
+        flags(compress.compress_args, compress.compress_return)
+    } else if let Some(sliced_format) = attribute.downcast::<attributes::SlicedFormat>() {
+        flags(sliced_format.sliced_args, sliced_format.sliced_return)
+    } else if let Some(deprecated) = attribute.downcast::<attributes::Deprecated>() {
+        deprecated.reason.iter().cloned().collect()
+    } else if let Some(size) = attribute.downcast::<attributes::Size>() {
+        match size.is_fixed() {
+            true => vec![size.min.to_string()],
+            false => vec![size.min.to_string(), size.max.to_string()],
+        }
+    } else if let Some(context) = attribute.downcast::<attributes::Context>() {
+        let entries = context.entries.iter().map(|entry| {
+            let optional = if entry.is_optional { "?" } else { "" };
+            format!("{}: {}{optional}", entry.key, entry.value_type.kind())
+        });
+        entries.collect()
+    } else if let Some(version) = attribute.downcast::<attributes::Version>() {
+        vec![version.version.clone()]
+    } else if let Some(package) = attribute.downcast::<attributes::Package>() {
+        vec![package.package.clone()]
+    } else if let Some(license) = attribute.downcast::<attributes::License>() {
+        vec![license.license.clone()]
+    } else if let Some(authors) = attribute.downcast::<attributes::Authors>() {
+        authors.authors.clone()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Returns the provided attribute argument as it'd be written in Slice: identifiers and integers are written as-is,
+/// and anything else is written as a string literal.
+fn render_attribute_argument(argument: &str) -> String {
+    match !argument.is_empty() && argument.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        true => argument.to_owned(),
+        false => format!("\"{}\"", argument.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
+
+fn render_message(message: &Message) -> String {
+    let components = message.value.iter().map(|component| match component {
+        MessageComponent::Text(text) => text.clone(),
+        MessageComponent::Link(link) => render_link(link.linked_entity()),
+    });
+    components.collect::<String>().trim().to_owned()
+}
+
+fn render_link(link: Result<&dyn Entity, &Identifier>) -> String {
+    match link {
+        Ok(entity) => entity.parser_scoped_identifier(),
+        Err(identifier) => identifier.value.clone(),
+    }
+}
+
+fn section(header: &str, lines: impl Iterator<Item = String>) -> String {
+    std::iter::once(header.to_owned())
+        .chain(lines)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
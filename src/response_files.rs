@@ -0,0 +1,126 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module expands response files on the command line, so build systems can pass more arguments to the compiler
+//! than the operating system allows on a single command line (like thousands of source files).
+//!
+//! Any argument of the form `@<path>` is replaced by the arguments read from the file at `<path>`.
+//! Arguments in a response file are separated by whitespace (including newlines), and are parsed with these rules:
+//! - Text within double quotes (`"`) is part of a single argument, even if it contains whitespace.
+//!   Within double quotes, a backslash escapes the character after it; this is how `"` and `\` can be included.
+//! - Text within single quotes (`'`) is also part of a single argument, but is used exactly as written.
+//! - Outside of quotes, a backslash escapes the character after it, including whitespace and quotes.
+//!   So paths containing backslashes (like Windows paths) should be written within single quotes.
+//! - A `#` at the start of an argument begins a comment, which continues until the end of the line.
+//!
+//! Response files can reference other response files. Relative paths are always resolved against the current working
+//! directory, not the directory of the response file that references them. A response file cannot (directly or
+//! indirectly) reference itself, and response files can only be nested [MAX_DEPTH] levels deep.
+//!
+//! # Examples
+//! ```no_run
+//! # use clap::Parser;
+//! # use slicec::slice_options::SliceOptions;
+//! // Running `slicec @args.rsp` compiles the files listed in 'args.rsp'.
+//! let options = SliceOptions::parse_from(slicec::response_files::args());
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// The maximum number of response files which can be nested within each other.
+pub const MAX_DEPTH: usize = 16;
+
+/// Returns the arguments this process was started with, with any response files expanded.
+///
+/// If a response file couldn't be expanded, this prints an error and exits the process, like clap does for invalid
+/// arguments.
+pub fn args() -> Vec<String> {
+    match expand(env::args()) {
+        Ok(args) => args,
+        Err(message) => clap::Error::raw(clap::error::ErrorKind::Io, format!("{message}\n")).exit(),
+    }
+}
+
+/// Returns the provided arguments with any response files (`@<path>`) replaced by the arguments they contain.
+/// Otherwise, if a response file couldn't be read, or references itself, this returns an error message describing why.
+pub fn expand(args: impl IntoIterator<Item = String>) -> Result<Vec<String>, String> {
+    let mut expanded_args = Vec::new();
+    for arg in args {
+        expand_arg(arg, &mut Vec::new(), &mut expanded_args)?;
+    }
+    Ok(expanded_args)
+}
+
+/// Expands a single argument into `expanded_args`.
+/// `stack` holds the canonical paths of the response files being expanded, from outermost to innermost.
+fn expand_arg(arg: String, stack: &mut Vec<PathBuf>, expanded_args: &mut Vec<String>) -> Result<(), String> {
+    // A lone '@' isn't a response file, and is passed through as-is.
+    let Some(path) = arg.strip_prefix('@').filter(|path| !path.is_empty()) else {
+        expanded_args.push(arg);
+        return Ok(());
+    };
+
+    let canonical_path = Path::new(path)
+        .canonicalize()
+        .map_err(|error| format!("unable to read response file '{path}': {error}"))?;
+    if stack.contains(&canonical_path) {
+        return Err(format!("response file '{path}' references itself"));
+    }
+    if stack.len() >= MAX_DEPTH {
+        return Err(format!(
+            "response file '{path}' is nested more than {MAX_DEPTH} levels deep"
+        ));
+    }
+    let contents = fs::read_to_string(&canonical_path)
+        .map_err(|error| format!("unable to read response file '{path}': {error}"))?;
+
+    stack.push(canonical_path);
+    let nested_args = split(&contents).map_err(|error| format!("unable to parse response file '{path}': {error}"))?;
+    for nested_arg in nested_args {
+        expand_arg(nested_arg, stack, expanded_args)?;
+    }
+    stack.pop();
+    Ok(())
+}
+
+/// Splits the contents of a response file into arguments, following the rules described in the module documentation.
+pub fn split(contents: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut chars = contents.chars().peekable();
+
+    loop {
+        // Skip any whitespace before the next argument.
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        match chars.peek() {
+            None => return Ok(args),
+            // Skip comments until the end of the line.
+            Some('#') => while chars.next_if(|c| *c != '\n').is_some() {},
+            Some(_) => {
+                let mut arg = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    match c {
+                        '\\' => arg.extend(chars.next()),
+                        '\'' => loop {
+                            match chars.next() {
+                                Some('\'') => break,
+                                Some(c) => arg.push(c),
+                                None => return Err("unterminated single quote".to_owned()),
+                            }
+                        },
+                        '"' => loop {
+                            match chars.next() {
+                                Some('"') => break,
+                                Some('\\') => arg.extend(chars.next()),
+                                Some(c) => arg.push(c),
+                                None => return Err("unterminated double quote".to_owned()),
+                            }
+                        },
+                        c => arg.push(c),
+                    }
+                }
+                args.push(arg);
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module classifies the identifiers and keywords in a compiled Slice file, for editors which provide semantic
+//! highlighting (like language servers answering `textDocument/semanticTokens` requests).
+//!
+//! Unlike a [Tokenizer](crate::parsers::lossless::Tokenizer), which only knows that a word is an identifier, this
+//! uses the AST to determine what each identifier refers to. So the `Foo` in `a: Foo` is classified as a struct,
+//! enum, etc. depending on what `Foo` resolved to.
+
+use crate::grammar::*;
+use crate::parsers::lossless::{self, SyntaxElement, SyntaxKind};
+use crate::slice_file::{Location, SliceFile, Span};
+use crate::visitor::{VisitControl, Visitor};
+
+/// The kinds of semantic tokens that can appear in a Slice file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SemanticTokenKind {
+    Module,
+    Struct,
+    Class,
+    Exception,
+    Interface,
+    Enum,
+    CustomType,
+    TypeAlias,
+    Constant,
+    Operation,
+    Field,
+    Parameter,
+    Enumerator,
+    Keyword,
+}
+
+impl SemanticTokenKind {
+    /// Returns the name of the standard LSP token type that best matches this kind.
+    pub fn lsp_token_type(&self) -> &'static str {
+        match self {
+            Self::Module => "namespace",
+            Self::Struct => "struct",
+            Self::Class | Self::Exception => "class",
+            Self::Interface => "interface",
+            Self::Enum => "enum",
+            Self::CustomType | Self::TypeAlias => "type",
+            Self::Constant => "variable",
+            Self::Operation => "method",
+            Self::Field => "property",
+            Self::Parameter => "parameter",
+            Self::Enumerator => "enumMember",
+            Self::Keyword => "keyword",
+        }
+    }
+}
+
+/// A classified identifier or keyword in a Slice file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub kind: SemanticTokenKind,
+
+    /// The span of the token. Tokens never span multiple lines.
+    pub span: Span,
+
+    /// True if this token is the identifier of a definition, and false if it's a reference to one (or a keyword).
+    pub is_definition: bool,
+}
+
+/// Returns the semantic tokens of the provided file, in the order they appear.
+///
+/// Each segment of a scoped identifier is its own token; in `Foo::Bar` the `Foo` is classified as a module, and
+/// `Bar` is classified by what it refers to. Identifiers which couldn't be resolved aren't included, so this should
+/// only be called on files which compiled without errors.
+pub fn semantic_tokens(slice_file: &SliceFile) -> Vec<SemanticToken> {
+    let mut classifier = Classifier {
+        slice_file,
+        tokens: Vec::new(),
+    };
+    slice_file.visit_with(&mut classifier);
+    let mut tokens = classifier.tokens;
+
+    // Keywords don't depend on the AST, so we just take them from the file's tokens.
+    let keywords = lossless::tokenize(&slice_file.raw_text, &slice_file.relative_path)
+        .filter(|token| token.kind == SyntaxKind::Keyword)
+        .map(|token| SemanticToken {
+            kind: SemanticTokenKind::Keyword,
+            span: token.span,
+            is_definition: false,
+        });
+    tokens.extend(keywords);
+
+    tokens.sort_by_key(|token| token.span.start);
+    tokens
+}
+
+struct Classifier<'a> {
+    slice_file: &'a SliceFile,
+    tokens: Vec<SemanticToken>,
+}
+
+impl Classifier<'_> {
+    fn push(&mut self, kind: SemanticTokenKind, span: Span, is_definition: bool) {
+        self.tokens.push(SemanticToken {
+            kind,
+            span,
+            is_definition,
+        });
+    }
+
+    fn push_definition(&mut self, kind: SemanticTokenKind, entity: &dyn Entity) {
+        // Structs synthesized from tuples, and nameless return types, are given dummy identifiers which don't appear
+        // in the source code, so there's nothing to classify.
+        let identifier = entity.raw_identifier();
+        let text = self.slice_file.snippet(&identifier.span).unwrap_or_default();
+        if text.trim_start_matches('\\') == identifier.value {
+            self.push(kind, identifier.span.clone(), true);
+        }
+    }
+
+    /// Pushes a token for each segment of the identifier in the provided span. The last segment is given the
+    /// provided kind, and any segments before it are classified as modules.
+    fn push_scoped(&mut self, kind: SemanticTokenKind, span: &Span, is_definition: bool) {
+        let segments = self.identifier_segments(span);
+        if let Some((last, scopes)) = segments.split_last() {
+            for scope in scopes {
+                self.push(SemanticTokenKind::Module, scope.clone(), is_definition);
+            }
+            self.push(kind, last.clone(), is_definition);
+        }
+    }
+
+    /// Returns the spans of the identifiers in the provided span, skipping over any attributes within it.
+    fn identifier_segments(&self, span: &Span) -> Vec<Span> {
+        let Some(snippet) = self.slice_file.snippet(span) else {
+            return Vec::new();
+        };
+
+        // Attributes are always enclosed in brackets, so any identifiers directly in the root node are segments.
+        lossless::parse(snippet, &span.file)
+            .children
+            .into_iter()
+            .filter_map(|element| match element {
+                SyntaxElement::Token(token) if token.kind == SyntaxKind::Identifier => Some(token.span),
+                _ => None,
+            })
+            .map(|segment| Span {
+                start: offset_location(segment.start, span.start),
+                end: offset_location(segment.end, span.start),
+                file: span.file.clone(),
+            })
+            .collect()
+    }
+
+    fn push_type_ref<T: Type + ?Sized>(&mut self, type_ref: &TypeRef<T>) {
+        let kind = match type_ref.concrete_type() {
+            _ if !type_ref.type_aliases().is_empty() => SemanticTokenKind::TypeAlias,
+            Types::Struct(_) => SemanticTokenKind::Struct,
+            Types::Class(_) => SemanticTokenKind::Class,
+            Types::Enum(_) => SemanticTokenKind::Enum,
+            Types::CustomType(_) => SemanticTokenKind::CustomType,
+            // Anonymous types are written with keywords, and their elements are visited separately.
+            Types::ResultType(_) | Types::Sequence(_) | Types::Dictionary(_) | Types::Primitive(_) => return,
+        };
+        self.push_scoped(kind, type_ref.span(), false);
+    }
+
+    fn push_entity_ref<T: Entity + ?Sized>(&mut self, kind: SemanticTokenKind, type_ref: &TypeRef<T>) {
+        self.push_scoped(kind, type_ref.span(), false);
+    }
+}
+
+/// Converts a location relative to the start of a snippet into a location within the snippet's file.
+fn offset_location(location: Location, snippet_start: Location) -> Location {
+    match location.row {
+        1 => Location {
+            row: snippet_start.row,
+            col: snippet_start.col + location.col - 1,
+        },
+        _ => Location {
+            row: snippet_start.row + location.row - 1,
+            col: location.col,
+        },
+    }
+}
+
+impl Visitor for Classifier<'_> {
+    fn visit_module(&mut self, module_def: &Module) -> VisitControl {
+        self.push_scoped(SemanticTokenKind::Module, &module_def.identifier.span, true);
+        VisitControl::Continue
+    }
+
+    fn visit_struct(&mut self, struct_def: &Struct) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Struct, struct_def);
+        VisitControl::Continue
+    }
+
+    fn visit_class(&mut self, class_def: &Class) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Class, class_def);
+        if let Some(base) = &class_def.base {
+            self.push_entity_ref(SemanticTokenKind::Class, base);
+        }
+        VisitControl::Continue
+    }
+
+    fn visit_exception(&mut self, exception_def: &Exception) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Exception, exception_def);
+        if let Some(base) = &exception_def.base {
+            self.push_entity_ref(SemanticTokenKind::Exception, base);
+        }
+        VisitControl::Continue
+    }
+
+    fn visit_interface(&mut self, interface_def: &Interface) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Interface, interface_def);
+        for base in &interface_def.bases {
+            self.push_entity_ref(SemanticTokenKind::Interface, base);
+        }
+        VisitControl::Continue
+    }
+
+    fn visit_enum(&mut self, enum_def: &Enum) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Enum, enum_def);
+        VisitControl::Continue
+    }
+
+    fn visit_operation(&mut self, operation: &Operation) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Operation, operation);
+        for exception_ref in &operation.exception_specification {
+            self.push_entity_ref(SemanticTokenKind::Exception, exception_ref);
+        }
+        VisitControl::Continue
+    }
+
+    fn visit_custom_type(&mut self, custom_type: &CustomType) -> VisitControl {
+        self.push_definition(SemanticTokenKind::CustomType, custom_type);
+        VisitControl::Continue
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> VisitControl {
+        self.push_definition(SemanticTokenKind::TypeAlias, type_alias);
+        VisitControl::Continue
+    }
+
+    fn visit_constant(&mut self, constant: &Constant) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Constant, constant);
+        VisitControl::Continue
+    }
+
+    fn visit_field(&mut self, field: &Field) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Field, field);
+        VisitControl::Continue
+    }
+
+    fn visit_parameter(&mut self, parameter: &Parameter) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Parameter, parameter);
+        VisitControl::Continue
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) -> VisitControl {
+        self.push_definition(SemanticTokenKind::Enumerator, enumerator);
+        VisitControl::Continue
+    }
+
+    fn visit_type_ref(&mut self, type_ref: &TypeRef) -> VisitControl {
+        self.push_type_ref(type_ref);
+        VisitControl::Continue
+    }
+}
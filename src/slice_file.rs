@@ -1,17 +1,24 @@
 // Copyright (c) ZeroC, Inc.
 
 use crate::grammar::*;
+use crate::slice_options::ColumnUnit;
 use crate::utils::ptr_util::WeakPtr;
 use console::style;
 use serde::Serialize;
 use std::cmp::{max, min, Ordering};
 use std::fmt::{Display, Write};
+use std::sync::Arc;
+use unicode_width::UnicodeWidthChar;
 
 const EXPANDED_TAB: &str = "    ";
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
 
 /// Stores the row and column numbers of a location in a Slice file.
 /// These values are indexed starting at 1 instead of 0 for human readability.
 /// Ex: (1,1) is the start of a file: the first column in the first row.
+///
+/// Columns count characters (Unicode scalar values), not bytes or display width. So a character which is encoded with
+/// multiple bytes, or which is displayed two columns wide (like most CJK characters), still only occupies one column.
 #[derive(Serialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Location {
     pub row: usize,
@@ -66,22 +73,55 @@ impl std::ops::Add for &Span {
     }
 }
 
+/// The metadata of a Slice file, as declared by its well-known file attributes.
+/// See [SliceFile::metadata].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FileMetadata<'a> {
+    /// The file's version, from its `version` attribute.
+    pub version: Option<&'a str>,
+
+    /// The package the file's definitions are published in, from its `package` attribute.
+    pub package: Option<&'a str>,
+
+    /// The license the file is distributed under, from its `license` attribute.
+    pub license: Option<&'a str>,
+
+    /// The file's authors, from its `authors` attribute.
+    pub authors: Vec<&'a str>,
+}
+
 #[derive(Debug)]
 pub struct SliceFile {
     pub filename: String,
     pub relative_path: String,
-    pub raw_text: String,
+    /// The text of the file. This is reference counted, so it can be shared (by backends or tools) without copying it.
+    /// The parser doesn't copy it either; the source blocks and tokens it works with are slices into this text.
+    pub raw_text: Arc<str>,
 
     pub mode: Option<FileCompilationMode>,
     pub module: Option<WeakPtr<Module>>,
     pub attributes: Vec<WeakPtr<Attribute>>,
+    pub using_directives: Vec<UsingDirective>,
+    pub module_aliases: Vec<ModuleAlias>,
     pub contents: Vec<Definition>,
 
     pub is_source: bool,
+
+    /// The byte offset of the start of each line in `raw_text`; the first line always starts at offset 0.
+    /// This table is computed when the file is created, and is used for converting between locations and offsets.
+    line_positions: Vec<usize>,
 }
 
 impl SliceFile {
-    pub fn new(relative_path: String, raw_text: String, is_source: bool) -> Self {
+    /// Creates a new [SliceFile] from the provided text.
+    ///
+    /// If the text starts with a UTF-8 byte order mark, it's removed, since it isn't part of the file's content.
+    pub fn new(relative_path: String, raw_text: impl Into<Arc<str>>, is_source: bool) -> Self {
+        let mut raw_text = raw_text.into();
+        if let Some(stripped_text) = raw_text.strip_prefix(BYTE_ORDER_MARK) {
+            raw_text = Arc::from(stripped_text);
+        }
+
         // Extract the name of the slice file without its extension.
         let filename = std::path::Path::new(&relative_path)
             .file_stem()
@@ -90,6 +130,10 @@ impl SliceFile {
             .into_string()
             .unwrap();
 
+        // Store the offset of the start of each line; each line (after the first) starts immediately after a '\n'.
+        let newlines = raw_text.match_indices('\n').map(|(i, _)| i + 1);
+        let line_positions = std::iter::once(0).chain(newlines).collect();
+
         SliceFile {
             filename,
             relative_path,
@@ -97,11 +141,65 @@ impl SliceFile {
             mode: None,
             module: None,
             attributes: Vec::new(),
+            using_directives: Vec::new(),
+            module_aliases: Vec::new(),
             contents: Vec::new(),
             is_source,
+            line_positions,
         }
     }
 
+    /// Returns the number of lines in this file.
+    pub fn line_count(&self) -> usize {
+        self.line_positions.len()
+    }
+
+    /// Returns the text of the specified line (indexed from 1), without its terminating '\n'.
+    /// Returns `None` if this file doesn't contain the line.
+    pub fn line(&self, row: usize) -> Option<&str> {
+        let start = *self.line_positions.get(row.checked_sub(1)?)?;
+        let end = self
+            .line_positions
+            .get(row)
+            .map_or(self.raw_text.len(), |next_start| next_start - 1);
+        Some(&self.raw_text[start..end])
+    }
+
+    /// Returns the byte offset into `raw_text` of the specified location.
+    ///
+    /// A location can point at most one column past the end of its line (at its '\n', or the end of the file).
+    /// Returns `None` for locations which point past this, or aren't contained in this file.
+    pub fn byte_offset(&self, location: Location) -> Option<usize> {
+        let line = self.line(location.row)?;
+        let line_start = self.line_positions[location.row - 1];
+
+        let preceding_chars = location.col.checked_sub(1)?;
+        let mut char_offsets = line.char_indices().map(|(i, _)| i).chain(std::iter::once(line.len()));
+        char_offsets.nth(preceding_chars).map(|offset| line_start + offset)
+    }
+
+    /// Returns the location of the specified byte offset into `raw_text`.
+    /// Returns `None` if the offset is past the end of the file, or isn't on a character boundary.
+    pub fn position_at(&self, offset: usize) -> Option<Location> {
+        if !self.raw_text.is_char_boundary(offset) {
+            return None;
+        }
+
+        // Find the last line that starts at or before the offset. Since the first line starts at 0, there always is one.
+        let row = self.line_positions.partition_point(|&line_start| line_start <= offset);
+        let line_start = self.line_positions[row - 1];
+        let col = self.raw_text[line_start..offset].chars().count() + 1;
+        Some(Location { row, col })
+    }
+
+    /// Returns the raw text covered by the specified span.
+    /// Returns `None` if either end of the span isn't contained in this file. See [SliceFile::byte_offset].
+    pub fn snippet(&self, span: &Span) -> Option<&str> {
+        let start = self.byte_offset(span.start)?;
+        let end = self.byte_offset(span.end)?;
+        self.raw_text.get(start..end)
+    }
+
     /// Returns the compilation mode used by this file.
     ///
     /// If a mode wasn't explicitly stated, it returns the default mode.
@@ -113,6 +211,63 @@ impl SliceFile {
             .map_or(CompilationMode::default(), |mode| mode.version)
     }
 
+    /// Returns the metadata declared by this file's [well-known file attributes](attributes::Version), like its
+    /// version and license. Any metadata that the file doesn't declare is left empty.
+    pub fn metadata(&self) -> FileMetadata<'_> {
+        FileMetadata {
+            version: self.find_attribute::<attributes::Version>().map(|a| a.version.as_str()),
+            package: self.find_attribute::<attributes::Package>().map(|a| a.package.as_str()),
+            license: self.find_attribute::<attributes::License>().map(|a| a.license.as_str()),
+            authors: self
+                .find_attribute::<attributes::Authors>()
+                .map(|a| a.authors.iter().map(String::as_str).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the first file attribute of the specified type, or `None` if this file doesn't have one.
+    fn find_attribute<T: AttributeKind + 'static>(&self) -> Option<&T> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| attribute.borrow().downcast())
+    }
+
+    /// Converts a location in this file into one whose column is measured in the specified `unit`, and where each tab
+    /// character occupies `tab_width` columns. This is used when reporting locations to external tools like editors.
+    pub fn convert_location(&self, location: Location, unit: ColumnUnit, tab_width: usize) -> Location {
+        // Locations are stored in characters, where tabs occupy a single column. So there's nothing to convert.
+        if unit == ColumnUnit::Character && tab_width == 1 {
+            return location;
+        }
+
+        // We don't strip any trailing '\r' from the line, since the lexers count them as columns too.
+        let line = self.line(location.row).unwrap_or_default();
+        let preceding_chars = location.col - 1;
+
+        let mut col = 1;
+        for c in line.chars().take(preceding_chars) {
+            col += match (c, unit) {
+                ('\t', _) => tab_width,
+                (_, ColumnUnit::Character) => 1,
+                (_, ColumnUnit::Utf16) => c.len_utf16(),
+            };
+        }
+
+        // Locations can point past the end of a line (at its newline character), each of these columns counts as one.
+        col += preceding_chars.saturating_sub(line.chars().count());
+
+        Location { row: location.row, col }
+    }
+
+    /// Converts a span in this file into the specified units. See [SliceFile::convert_location] for more information.
+    pub fn convert_span(&self, span: &Span, unit: ColumnUnit, tab_width: usize) -> Span {
+        Span {
+            start: self.convert_location(span.start, unit, tab_width),
+            end: self.convert_location(span.end, unit, tab_width),
+            file: span.file.clone(),
+        }
+    }
+
     /// Retrieves a formatted snippet from the slice file.
     pub(crate) fn get_snippet(&self, start: Location, end: Location) -> String {
         debug_assert!(start <= end);
@@ -135,20 +290,15 @@ impl SliceFile {
         // The prefix for lines not showing a line number.
         let line_prefix = line_number_prefix(None);
 
-        // The lines of text that should be included in the snippet.
-        let lines = self
-            .raw_text
-            .lines()
-            .enumerate()
-            .filter(|(line_number, _)| *line_number >= start.row - 1 && *line_number < end.row);
+        // The lines of text that should be included in the snippet, without any trailing '\r' character.
+        let lines = (start.row..=end.row)
+            .filter_map(|line_number| self.line(line_number).map(|line| (line_number, line)))
+            .map(|(line_number, line)| (line_number, line.strip_suffix('\r').unwrap_or(line)));
 
         // The formatted snippet.
         let mut formatted_snippet = line_prefix.clone() + "\n";
 
-        for (i, line) in lines {
-            // The actual line number
-            let line_number = i + 1;
-
+        for (line_number, line) in lines {
             let width = line.chars().count();
 
             // The start and end positions of the highlight.
@@ -180,7 +330,7 @@ pub fn compute_sha256_hash_of_source_files(files: &[SliceFile]) -> String {
     let mut hash_engine = Sha256::new();
     for file in sorted_sources {
         hash_engine.update(&file.filename);
-        hash_engine.update(&file.raw_text);
+        hash_engine.update(file.raw_text.as_bytes());
     }
 
     // Return the hash engine's final result, formatted as a lowercase-hexadecimal string.
@@ -193,14 +343,7 @@ implement_Attributable_for!(SliceFile);
 fn get_highlight(line: &str, highlight_start: usize, highlight_end: usize) -> String {
     // The whitespace that should be displayed before the highlight. Tabs are expanded to 4 spaces.
     // We always start with one space to separate the highlight from the vertical separator.
-    let mut whitespace_count = 1;
-    for c in line.chars().take(highlight_start) {
-        if c == '\t' {
-            whitespace_count += EXPANDED_TAB.len();
-        } else {
-            whitespace_count += 1;
-        }
-    }
+    let mut whitespace_count = 1 + line.chars().take(highlight_start).map(display_width).sum::<usize>();
 
     // The highlight that should be displayed.
     // If it's between 2 characters (same start and end), then we use a single point.
@@ -212,19 +355,26 @@ fn get_highlight(line: &str, highlight_start: usize, highlight_end: usize) -> St
         // Point to a single character.
         style(r"/\".to_owned()).yellow().bold()
     } else {
-        // Number of tabs between the start and end of the highlight.
-        let highlight_tab_count = line
+        // Since characters can be displayed wider (or narrower) than a single column, we sum their display widths,
+        // making sure to always highlight at least one column.
+        let highlight_length = line
             .chars()
             .skip(highlight_start)
             .take(highlight_end - highlight_start)
-            .filter(|c| *c == '\t')
-            .count();
-
-        // Since tab is only 1 character, we have to account for the extra 3 characters that are displayed
-        // for each tab.
-        let highlight_length = (highlight_end - highlight_start) + (highlight_tab_count * (EXPANDED_TAB.len() - 1));
+            .map(display_width)
+            .sum::<usize>()
+            .max(1);
         style(format!("{:-<1$}", "", highlight_length)).yellow().bold()
     };
 
     " ".repeat(whitespace_count) + &highlight.to_string()
 }
+
+/// Returns the number of columns the provided character occupies when displayed in a snippet.
+/// Tabs are expanded to 4 spaces, and wide characters (like most CJK characters) occupy 2 columns.
+fn display_width(c: char) -> usize {
+    match c {
+        '\t' => EXPANDED_TAB.len(),
+        _ => UnicodeWidthChar::width(c).unwrap_or(0),
+    }
+}
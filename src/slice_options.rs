@@ -1,25 +1,45 @@
 // Copyright (c) ZeroC, Inc.
 
+use crate::backend::parse_backend_option;
 use crate::diagnostics::Lint;
+use crate::grammar::{Encoding, Primitive};
+use crate::packages::parse_package;
+use crate::parsers::is_keyword;
 use clap::ArgAction::Append;
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, ValueEnum};
+use std::num::NonZeroUsize;
 
 // Note: clap uses the doc-comments of fields to populate the '--help' output of slicec-xxx.
 //       boolean flags automatically default to false, and strings automatically default to empty.
 
 /// This struct is responsible for parsing the command line options common to all slice compilers.
 /// The option parsing capabilities are generated on the struct by the `clap` macro.
-#[derive(Debug, Default, Hash, Parser)]
+#[derive(Clone, Debug, Default, Hash, Parser)]
 #[command(rename_all = "kebab-case")]
 pub struct SliceOptions {
-    /// List of Slice files to compile.
-    #[arg(required = true)]
+    /// List of Slice files to compile. Required, unless '--validate-references' is specified.
+    #[arg(required_unless_present = "validate_references")]
     pub sources: Vec<String>,
 
     /// Add a directory or Slice file to the list of references.
     #[arg(short = 'R', num_args = 1, action = Append, value_name = "REFERENCE")]
     pub references: Vec<String>,
 
+    /// Don't search the default reference directories: those listed in the project's '.slicepath' file, and in the
+    /// 'SLICE_PATH' environment variable. Only the references passed with '-R' are searched.
+    #[arg(long)]
+    pub no_default_references: bool,
+
+    /// Add the Slice files of a package to the list of references, along with the files of any packages it depends
+    /// on. Packages are specified as `<NAME>` or `<NAME>@<VERSION>`; if no version is specified, the latest one is used.
+    #[arg(long = "package", num_args = 1, action = Append, value_name = "PACKAGE", value_parser = parse_package)]
+    pub packages: Vec<String>,
+
+    /// Add a directory to search for packages in. Each package is a directory containing a 'slice-package.json'
+    /// manifest; the directory itself, and its immediate subdirectories, are searched.
+    #[arg(long = "package-path", num_args = 1, action = Append, value_name = "DIRECTORY")]
+    pub package_paths: Vec<String>,
+
     /// Define a preprocessor symbol.
     #[arg(short = 'D', num_args = 1, action = Append, value_name = "SYMBOL")]
     pub defined_symbols: Vec<String>,
@@ -29,14 +49,84 @@ pub struct SliceOptions {
     #[arg(short = 'A', long = "allow", num_args = 1, action = Append, value_name = "LINT_NAME", value_parser = Lint::ALLOWABLE_LINT_IDENTIFIERS, hide_possible_values = true, ignore_case = true)]
     pub allowed_lints: Vec<String>,
 
+    /// Allow the warnings recorded in the specified baseline file, and report any other warnings as errors.
+    /// Use this to adopt lints incrementally, by only failing the build for new warnings.
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<String>,
+
+    /// Record the warnings reported by this compilation in the file specified by '--baseline', instead of checking
+    /// them against it. Any existing baseline is replaced.
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Exit with a code of 2 if compilation succeeded, but emitted warnings. By default warnings don't affect the exit
+    /// code; it's 0 on success, 1 if the Slice files have errors, and 3 if the compiler failed for any other reason.
+    #[arg(long)]
+    pub warnings_exit_code: bool,
+
+    /// Warn if a compact struct has more than this many fields.
+    #[arg(long, value_name = "COUNT")]
+    pub max_compact_struct_fields: Option<usize>,
+
+    /// Warn if an operation has more than this many parameters.
+    #[arg(long, value_name = "COUNT")]
+    pub max_operation_parameters: Option<usize>,
+
+    /// Warn if anonymous types (results, sequences, and dictionaries) are nested more than this many levels deep.
+    #[arg(long, value_name = "DEPTH")]
+    pub max_type_nesting_depth: Option<usize>,
+
+    /// Reject any Slice file that is larger than this many bytes, instead of reading it into memory.
+    #[arg(long, value_name = "BYTES")]
+    pub max_file_size: Option<u64>,
+
+    /// Make the well-known types (Duration, Timestamp, Uuid, and Uri) available for use in Slice files.
+    #[arg(long)]
+    pub well_known_types: bool,
+
+    /// Run the specified backend. Can be repeated to run multiple backends. Defaults to running every backend.
+    #[arg(long = "target", num_args = 1, action = Append, value_name = "BACKEND")]
+    pub targets: Vec<String>,
+
+    /// Allow a primitive type to be referenced by an additional name, in the form `<ALIAS>=<PRIMITIVE>`.
+    /// For example, '--primitive-alias f32=float32'.
+    #[arg(long = "primitive-alias", num_args = 1, action = Append, value_name = "ALIAS=PRIMITIVE", value_parser = parse_primitive_alias)]
+    pub primitive_aliases: Vec<String>,
+
+    /// Pass an option to a specific backend, in the form `<BACKEND>:<KEY>` or `<BACKEND>:<KEY>=<VALUE>`.
+    #[arg(short = 'X', num_args = 1, action = Append, value_name = "BACKEND:KEY=VALUE", value_parser = parse_backend_option)]
+    pub backend_options: Vec<String>,
+
     /// Validate input files without generating code for them.
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Only check input files for errors, stopping after validation. Unlike '--dry-run', no backends are run at all.
+    /// This is intended for editor save-hooks and pre-commit checks, where speed matters more than backend validation.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+
+    /// Validate the reference files on their own, without any source files, and stop after validation, like '--check'.
+    /// Use this to check a library of shared Slice definitions for errors, independent of the projects that use it.
+    #[arg(long)]
+    pub validate_references: bool,
+
     /// Set the output directory for the generated code. Defaults to the current working directory.
     #[arg(short = 'O', long, value_name = "DIRECTORY")]
     pub output_dir: Option<String>,
 
+    /// Write a JSON manifest listing the generated files, and the Slice files and entities they came from.
+    #[arg(long, value_name = "FILE")]
+    pub manifest: Option<String>,
+
+    /// Write a JSON registry of the interfaces, operations, classes, and exceptions defined in the source files.
+    #[arg(long, value_name = "FILE")]
+    pub type_registry: Option<String>,
+
+    /// Write a JSON report of what prevents each Slice1 source file from being switched to Slice2 mode.
+    #[arg(long, value_name = "FILE")]
+    pub migration_report: Option<String>,
+
     /// Set which format to emit errors and warnings with.
     #[arg(long, value_name = "FORMAT", value_enum, default_value_t = DiagnosticFormat::Human, ignore_case = true)]
     pub diagnostic_format: DiagnosticFormat,
@@ -44,6 +134,287 @@ pub struct SliceOptions {
     /// Disable ANSI color codes in diagnostic output.
     #[arg(long)]
     pub disable_color: bool,
+
+    /// Report the paths of Slice files as absolute paths, instead of relative to the current working directory.
+    /// Use this to get identical diagnostics from builds which are run from different directories.
+    #[arg(long)]
+    pub absolute_paths: bool,
+
+    /// Set which units the columns of reported locations are measured in.
+    #[arg(long, value_name = "UNIT", value_enum, default_value_t = ColumnUnit::Character, ignore_case = true)]
+    pub column_unit: ColumnUnit,
+
+    /// Set how many columns a tab character occupies in reported locations. Defaults to 1.
+    #[arg(long, value_name = "WIDTH")]
+    pub tab_width: Option<NonZeroUsize>,
+
+    /// Report how long each phase of compilation took, along with how long each file took to parse.
+    #[arg(long, value_name = "FORMAT", value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "human", ignore_case = true)]
+    pub timings: Option<TimingsFormat>,
+
+    /// Report the number of entities of each kind in the source files (per file and per compilation mode), along with
+    /// their largest types and deepest inheritance chains. Only reported if compilation succeeds.
+    #[arg(long, value_name = "FORMAT", value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "human", ignore_case = true)]
+    pub summary: Option<SummaryFormat>,
+
+    /// Report every diagnostic that was suppressed by an `allow` attribute or '--allow' flag, counted per lint and per
+    /// location, along with what suppressed it. Use this to audit whether suppressions are still justified.
+    #[arg(long, value_name = "FORMAT", value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "human", ignore_case = true)]
+    pub suppression_report: Option<SuppressionReportFormat>,
+
+    /// Report every type reference that couldn't be resolved, along with the kind of element it had to resolve to and
+    /// the scopes that were searched for it. Use this to tell missing references apart from other errors.
+    #[arg(long, value_name = "FORMAT", value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "human", ignore_case = true)]
+    pub list_unresolved: Option<UnresolvedReferencesFormat>,
+
+    /// If the compiler crashes, write a reproduction bundle to the specified directory, to attach to a bug report.
+    /// The bundle holds a copy of the input files, and the command line the compiler was run with.
+    #[arg(long, value_name = "DIRECTORY")]
+    pub ice_bundle: Option<String>,
+
+    /// Write a trace of each phase of compilation and each file, which can be viewed with Perfetto or chrome://tracing.
+    #[arg(long, value_name = "FILE")]
+    pub trace: Option<String>,
+
+    /// Log what the compiler is doing to stderr: which files it reads, how type references are resolved, and how long
+    /// each phase of compilation took. Use this to debug unexpected diagnostics, or missing files.
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Set which format to write logs with. Only used if '--verbose' was passed.
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = LogFormat::Human, ignore_case = true, requires = "verbose")]
+    pub log_format: LogFormat,
+}
+
+impl SliceOptions {
+    /// Returns a builder for constructing [SliceOptions] programmatically, without parsing a command line.
+    ///
+    /// # Examples
+    /// ```
+    /// # use slicec::slice_options::{RunMode, SliceOptions};
+    /// let options = SliceOptions::builder()
+    ///     .source("Greeter.slice")
+    ///     .reference("vendor/")
+    ///     .mode(RunMode::Check)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(options.sources, ["Greeter.slice"]);
+    /// assert!(options.check);
+    /// ```
+    pub fn builder() -> SliceOptionsBuilder {
+        SliceOptionsBuilder::default()
+    }
+}
+
+/// Constructs [SliceOptions] programmatically, for embedders (like build scripts and language servers) which don't
+/// have a command line to parse. See [SliceOptions::builder].
+///
+/// Options are validated by [build](SliceOptionsBuilder::build), with the same rules as the command line, so options
+/// built with this are always ones that could've been passed to the compiler. Conflicting options (like '--check' and
+/// '--dry-run') can't be expressed by the builder at all. Any option without a setter can still be set directly on the
+/// built [SliceOptions].
+#[derive(Clone, Debug, Default)]
+pub struct SliceOptionsBuilder {
+    options: SliceOptions,
+}
+
+impl SliceOptionsBuilder {
+    /// Adds a Slice file to compile.
+    pub fn source(mut self, path: impl Into<String>) -> Self {
+        self.options.sources.push(path.into());
+        self
+    }
+
+    /// Adds each of the provided Slice files to compile.
+    pub fn sources(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.sources.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a directory or Slice file to the list of references.
+    pub fn reference(mut self, path: impl Into<String>) -> Self {
+        self.options.references.push(path.into());
+        self
+    }
+
+    /// Adds each of the provided directories or Slice files to the list of references.
+    pub fn references(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.references.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds the Slice files of a package to the list of references, in the form `<NAME>` or `<NAME>@<VERSION>`.
+    pub fn package(mut self, package: impl Into<String>) -> Self {
+        self.options.packages.push(package.into());
+        self
+    }
+
+    /// Adds a directory to search for packages in.
+    pub fn package_path(mut self, path: impl Into<String>) -> Self {
+        self.options.package_paths.push(path.into());
+        self
+    }
+
+    /// Defines a preprocessor symbol.
+    pub fn define(mut self, symbol: impl Into<String>) -> Self {
+        self.options.defined_symbols.push(symbol.into());
+        self
+    }
+
+    /// Allows the specified lint.
+    pub fn allow(mut self, lint: impl Into<String>) -> Self {
+        self.options.allowed_lints.push(lint.into());
+        self
+    }
+
+    /// Sets whether code is generated, or the input files are only validated. See [RunMode].
+    pub fn mode(mut self, mode: RunMode) -> Self {
+        self.options.dry_run = mode == RunMode::DryRun;
+        self.options.check = mode == RunMode::Check;
+        self
+    }
+
+    /// Sets whether the reference files are validated on their own, without requiring any source files.
+    /// Like [RunMode::Check], no backends are run.
+    pub fn validate_references(mut self, enabled: bool) -> Self {
+        self.options.validate_references = enabled;
+        self
+    }
+
+    /// Adds a backend to run. If no targets are added, every backend is run.
+    pub fn target(mut self, backend: impl Into<String>) -> Self {
+        self.options.targets.push(backend.into());
+        self
+    }
+
+    /// Passes an option to a specific backend, in the form `<BACKEND>:<KEY>` or `<BACKEND>:<KEY>=<VALUE>`.
+    pub fn backend_option(mut self, option: impl Into<String>) -> Self {
+        self.options.backend_options.push(option.into());
+        self
+    }
+
+    /// Allows a primitive type to be referenced by an additional name, in the form `<ALIAS>=<PRIMITIVE>`.
+    pub fn primitive_alias(mut self, alias: impl Into<String>) -> Self {
+        self.options.primitive_aliases.push(alias.into());
+        self
+    }
+
+    /// Makes the well-known types available for use in Slice files.
+    pub fn well_known_types(mut self, enabled: bool) -> Self {
+        self.options.well_known_types = enabled;
+        self
+    }
+
+    /// Sets the output directory for the generated code.
+    pub fn output_dir(mut self, path: impl Into<String>) -> Self {
+        self.options.output_dir = Some(path.into());
+        self
+    }
+
+    /// Sets the baseline file to check warnings against. If `update` is true, the baseline file is rewritten with the
+    /// warnings of the compilation instead.
+    pub fn baseline(mut self, path: impl Into<String>, update: bool) -> Self {
+        self.options.baseline = Some(path.into());
+        self.options.update_baseline = update;
+        self
+    }
+
+    /// Sets which format to emit errors and warnings with.
+    pub fn diagnostic_format(mut self, format: DiagnosticFormat) -> Self {
+        self.options.diagnostic_format = format;
+        self
+    }
+
+    /// Sets whether ANSI color codes are disabled in diagnostic output.
+    pub fn disable_color(mut self, disabled: bool) -> Self {
+        self.options.disable_color = disabled;
+        self
+    }
+
+    /// Validates the options, and returns them if they're valid.
+    /// Otherwise this returns an error message describing the first invalid option.
+    pub fn build(self) -> Result<SliceOptions, String> {
+        let options = self.options;
+
+        if options.sources.is_empty() && !options.validate_references {
+            return Err("at least one source file must be provided".to_owned());
+        }
+        for lint in &options.allowed_lints {
+            let is_allowable = Lint::ALLOWABLE_LINT_IDENTIFIERS
+                .iter()
+                .any(|allowable| allowable.eq_ignore_ascii_case(lint));
+            if !is_allowable {
+                return Err(format!("'{lint}' is not a lint that can be allowed"));
+            }
+        }
+        for package in &options.packages {
+            parse_package(package)?;
+        }
+        for option in &options.backend_options {
+            parse_backend_option(option)?;
+        }
+        for alias in &options.primitive_aliases {
+            parse_primitive_alias(alias)?;
+        }
+        Ok(options)
+    }
+}
+
+/// This enum is used to specify whether code is generated, or the input files are only validated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RunMode {
+    /// Input files are validated, and code is generated for them.
+    #[default]
+    Generate,
+
+    /// Input files are validated, and backends are run, but no code is generated. Equivalent to '--dry-run'.
+    DryRun,
+
+    /// Input files are only checked for errors, without running any backends. Equivalent to '--check'.
+    Check,
+}
+
+/// This struct is responsible for parsing the command line options of the `decode` subcommand.
+/// This subcommand decodes a payload against a Slice type (or operation), and prints the decoded value.
+///
+/// Compilers can expose it by embedding this struct in a [clap::Subcommand]. See `examples/decode.rs`.
+#[derive(Debug, Default, Hash, Args)]
+#[command(rename_all = "kebab-case")]
+pub struct DecodeOptions {
+    /// The fully scoped identifier of the type or operation to decode the payload as.
+    #[arg(value_name = "TYPE")]
+    pub type_name: String,
+
+    /// The payload to decode, as a string of hexadecimal digits. Whitespace is ignored.
+    #[arg(value_name = "HEX")]
+    pub payload: String,
+
+    /// Decode an operation's return value instead of its arguments.
+    #[arg(long)]
+    pub return_value: bool,
+
+    /// Set which encoding to decode the payload with. Defaults to the compilation mode of the file defining the type.
+    #[arg(long, value_name = "ENCODING", value_enum, ignore_case = true)]
+    pub encoding: Option<Encoding>,
+
+    #[command(flatten)]
+    pub slice_options: SliceOptions,
+}
+
+/// This struct is responsible for parsing the command line options of the `upgrade` subcommand.
+/// This subcommand rewrites Slice files written with older versions of the Slice syntax to use the current syntax.
+///
+/// Compilers can expose it by embedding this struct in a [clap::Subcommand]. See `examples/upgrade.rs`.
+#[derive(Debug, Default, Hash, Args)]
+#[command(rename_all = "kebab-case")]
+pub struct UpgradeOptions {
+    /// List of Slice files to upgrade.
+    #[arg(required = true)]
+    pub sources: Vec<String>,
+
+    /// Overwrite each file with its upgraded contents, instead of printing them.
+    #[arg(long)]
+    pub in_place: bool,
 }
 
 /// This enum is used to specify the format for emitted diagnostics.
@@ -56,3 +427,102 @@ pub enum DiagnosticFormat {
     /// Diagnostics will be serialized as JSON objects and printed to the console, one diagnostic per line.
     Json,
 }
+
+/// This enum is used to specify the format for unresolved references reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum UnresolvedReferencesFormat {
+    /// The report is printed to the console as an easily readable table.
+    #[default]
+    Human,
+
+    /// The report is serialized as a single JSON object and printed to the console.
+    Json,
+}
+
+/// This enum is used to specify the format for logs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum LogFormat {
+    /// Logs are printed to the console as easily readable lines, one event per line.
+    #[default]
+    Human,
+
+    /// Logs are serialized as JSON objects and printed to the console, one event per line.
+    Json,
+}
+
+/// This enum is used to specify the format for timing reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum TimingsFormat {
+    /// Timings are printed to the console as an easily readable table.
+    #[default]
+    Human,
+
+    /// Timings are serialized as a single JSON object and printed to the console.
+    Json,
+}
+
+/// This enum is used to specify the format for summary reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum SummaryFormat {
+    /// The summary is printed to the console as easily readable tables.
+    #[default]
+    Human,
+
+    /// The summary is serialized as a single JSON object and printed to the console.
+    Json,
+}
+
+/// This enum is used to specify the format for suppression reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum SuppressionReportFormat {
+    /// The report is printed to the console as easily readable tables.
+    #[default]
+    Human,
+
+    /// The report is serialized as a single JSON object and printed to the console.
+    Json,
+}
+
+/// This enum is used to specify which units the columns of reported locations are measured in.
+///
+/// Regardless of this setting, the compiler always stores locations in characters. They're only converted into other
+/// units when they're reported, see [SliceFile::convert_location](crate::slice_file::SliceFile::convert_location).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ColumnUnit {
+    /// Columns count characters (Unicode scalar values).
+    #[default]
+    Character,
+
+    /// Columns count UTF-16 code units, as used by the Language Server Protocol.
+    Utf16,
+}
+
+/// Splits a primitive alias of the form `<ALIAS>=<PRIMITIVE>` into the alias and the primitive it refers to.
+/// Returns an error message if the alias isn't a valid identifier or is a Slice keyword, or if the primitive isn't a
+/// primitive type's keyword.
+pub(crate) fn split_primitive_alias(option: &str) -> Result<(&str, Primitive), String> {
+    let malformed = || {
+        "primitive aliases must be of the form '<ALIAS>=<PRIMITIVE>', where <PRIMITIVE> is the keyword of a primitive type"
+            .to_owned()
+    };
+    let (alias, keyword) = option.split_once('=').ok_or_else(malformed)?;
+    let (alias, keyword) = (alias.trim(), keyword.trim());
+
+    let mut chars = alias.chars();
+    let is_identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(malformed());
+    }
+    if is_keyword(alias) {
+        return Err(format!(
+            "'{alias}' is a Slice keyword and cannot be used as a primitive alias"
+        ));
+    }
+    let primitive = Primitive::from_keyword(keyword).ok_or_else(malformed)?;
+    Ok((alias, primitive))
+}
+
+fn parse_primitive_alias(option: &str) -> Result<String, String> {
+    split_primitive_alias(option).map(|_| option.to_owned())
+}
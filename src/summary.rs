@@ -0,0 +1,281 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module summarizes the contents of a set of Slice files, for auditing large Slice repositories, and tracking
+//! how they grow over time (for example, by storing the summary produced by each CI build).
+//!
+//! Summaries count the entities of each kind that are defined in the source files, both per file and per compilation
+//! mode, and list the source files' largest types and deepest inheritance chains.
+
+use crate::grammar::*;
+use crate::slice_file::SliceFile;
+use crate::slice_options::{SliceOptions, SummaryFormat};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// The maximum number of entries in the [largest types](Summary::largest_types) and
+/// [deepest inheritance chains](Summary::deepest_inheritance) of a summary.
+pub const MAX_LISTED_TYPES: usize = 10;
+
+/// A summary of the entities defined in a set of source files.
+/// Entities defined in reference files aren't included, and neither are the structs synthesized for tuples.
+#[derive(Debug, Default, Serialize)]
+pub struct Summary {
+    /// The number of entities of each kind, across all the source files. Keys are entity kinds, like `struct`.
+    pub totals: BTreeMap<String, usize>,
+
+    /// The number of entities of each kind, for each compilation mode. Keys are compilation modes, like `Slice2`.
+    pub modes: BTreeMap<String, BTreeMap<String, usize>>,
+
+    /// A summary of each source file, in the order they were compiled.
+    pub files: Vec<FileSummary>,
+
+    /// The types with the most members, from largest to smallest.
+    /// The members of structs, classes, and exceptions are their fields, of interfaces are their operations, and of
+    /// enums are their enumerators.
+    pub largest_types: Vec<TypeSize>,
+
+    /// The classes, exceptions, and interfaces with the longest chains of base types, from longest to shortest.
+    /// Types without any bases aren't included.
+    pub deepest_inheritance: Vec<InheritanceChain>,
+}
+
+/// A summary of the entities defined in a single source file.
+#[derive(Debug, Serialize)]
+pub struct FileSummary {
+    /// The relative path of the file.
+    pub path: String,
+
+    /// The file's compilation mode.
+    pub mode: String,
+
+    /// The number of entities of each kind defined in the file. Keys are entity kinds, like `struct`.
+    pub entities: BTreeMap<String, usize>,
+}
+
+/// The number of members a type has.
+#[derive(Debug, Serialize)]
+pub struct TypeSize {
+    /// The fully scoped identifier of the type. Ex: `Foo::Bar::MyStruct`.
+    pub identifier: String,
+
+    /// The kind of the type. Ex: `struct`.
+    pub kind: &'static str,
+
+    /// How many members the type has.
+    pub members: usize,
+}
+
+/// The chain of base types a type inherits from.
+#[derive(Debug, Serialize)]
+pub struct InheritanceChain {
+    /// The fully scoped identifier of the type. Ex: `Foo::Bar::MyClass`.
+    pub identifier: String,
+
+    /// The kind of the type. Ex: `class`.
+    pub kind: &'static str,
+
+    /// The fully scoped identifiers of the type's bases, starting with its direct base.
+    /// Interfaces can have multiple bases, in which case this is the longest chain of them.
+    pub bases: Vec<String>,
+}
+
+impl Summary {
+    /// Creates a summary of the provided files. Only source files are included.
+    ///
+    /// The files must have been compiled without errors, since computing inheritance chains requires every type
+    /// reference to have been patched, and the types to be free of cycles.
+    pub fn from_files(files: &[SliceFile]) -> Self {
+        let mut summary = Summary::default();
+
+        for file in files.iter().filter(|file| file.is_source) {
+            let mode = file.compilation_mode().to_string();
+            let mut entities = BTreeMap::new();
+
+            for definition in &file.contents {
+                let entity = definition.borrow();
+                if entity.is_synthesized() {
+                    continue;
+                }
+
+                for entity in entity_and_members(entity) {
+                    let kind = entity.kind().to_owned();
+                    *entities.entry(kind.clone()).or_default() += 1;
+                    *summary.totals.entry(kind.clone()).or_default() += 1;
+                    *summary.modes.entry(mode.clone()).or_default().entry(kind).or_default() += 1;
+                }
+
+                if let Some(members) = member_count_of(entity) {
+                    summary.largest_types.push(TypeSize {
+                        identifier: entity.parser_scoped_identifier(),
+                        kind: entity.kind(),
+                        members,
+                    });
+                }
+
+                let bases = bases_of(entity);
+                if !bases.is_empty() {
+                    summary.deepest_inheritance.push(InheritanceChain {
+                        identifier: entity.parser_scoped_identifier(),
+                        kind: entity.kind(),
+                        bases,
+                    });
+                }
+            }
+
+            let path = file.relative_path.clone();
+            summary.files.push(FileSummary { path, mode, entities });
+        }
+
+        // Sort the types from largest to smallest, and then by identifier, so the order is stable between builds.
+        let largest_types = &mut summary.largest_types;
+        largest_types.sort_by(|a, b| b.members.cmp(&a.members).then_with(|| a.identifier.cmp(&b.identifier)));
+        largest_types.truncate(MAX_LISTED_TYPES);
+
+        let deepest_inheritance = &mut summary.deepest_inheritance;
+        deepest_inheritance.sort_by(|a, b| {
+            let ordering = b.bases.len().cmp(&a.bases.len());
+            ordering.then_with(|| a.identifier.cmp(&b.identifier))
+        });
+        deepest_inheritance.truncate(MAX_LISTED_TYPES);
+
+        summary
+    }
+
+    /// Returns this summary as an easily readable report.
+    pub fn to_report(&self) -> String {
+        let modes = self.modes.keys().collect::<Vec<_>>();
+
+        let mut report = format!("{:<40}{:>12}", "Kind", "Total");
+        for mode in &modes {
+            report += &format!("{mode:>12}");
+        }
+        report += "\n";
+        for (kind, total) in &self.totals {
+            report += &format!("{kind:<40}{total:>12}");
+            for mode in &modes {
+                let count = self.modes[*mode].get(kind).copied().unwrap_or_default();
+                report += &format!("{count:>12}");
+            }
+            report += "\n";
+        }
+
+        if !self.files.is_empty() {
+            report += &format!("\n{:<40}{:>12}{:>12}\n", "File", "Mode", "Entities");
+            for file in &self.files {
+                let entities = file.entities.values().sum::<usize>();
+                report += &format!("{:<40}{:>12}{entities:>12}\n", file.path, file.mode);
+            }
+        }
+
+        if !self.largest_types.is_empty() {
+            report += &format!("\n{:<40}{:>12}{:>12}\n", "Largest Types", "Kind", "Members");
+            for type_size in &self.largest_types {
+                report += &format!(
+                    "{:<40}{:>12}{:>12}\n",
+                    type_size.identifier, type_size.kind, type_size.members,
+                );
+            }
+        }
+
+        if !self.deepest_inheritance.is_empty() {
+            report += "\nDeepest Inheritance Chains\n";
+            for chain in &self.deepest_inheritance {
+                report += &format!("{} -> {}\n", chain.identifier, chain.bases.join(" -> "));
+            }
+        }
+        report
+    }
+
+    /// Returns this summary as a JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize summary")
+    }
+
+    /// Writes this summary to stderr in the format specified by the `--summary` option, if the user specified it.
+    /// Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        match options.summary {
+            Some(SummaryFormat::Human) => write!(stderr, "{}", self.to_report()),
+            Some(SummaryFormat::Json) => writeln!(stderr, "{}", self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the provided entity, followed by all the members it contains (including the members of its members).
+fn entity_and_members(entity: &dyn Entity) -> Vec<&dyn Entity> {
+    let members = match entity.concrete_entity() {
+        Entities::Struct(struct_def) => as_entities(struct_def.fields()),
+        Entities::Class(class_def) => as_entities(class_def.fields()),
+        Entities::Exception(exception_def) => as_entities(exception_def.fields()),
+        Entities::Interface(interface_def) => {
+            let operations = interface_def.operations().into_iter();
+            operations.flat_map(|operation| entity_and_members(operation)).collect()
+        }
+        Entities::Operation(operation) => as_entities(operation.parameters_and_return_members()),
+        Entities::Enum(enum_def) => {
+            let enumerators = enum_def.enumerators().into_iter();
+            enumerators
+                .flat_map(|enumerator| entity_and_members(enumerator))
+                .collect()
+        }
+        Entities::Enumerator(enumerator) => as_entities(enumerator.fields()),
+        _ => Vec::new(),
+    };
+
+    let mut entities = vec![entity];
+    entities.extend(members);
+    entities
+}
+
+fn as_entities<T: Entity>(members: Vec<&T>) -> Vec<&dyn Entity> {
+    members.into_iter().map(|member| member as &dyn Entity).collect()
+}
+
+/// Returns how many members the provided entity has, or `None` if it's not a type with members.
+fn member_count_of(entity: &dyn Entity) -> Option<usize> {
+    match entity.concrete_entity() {
+        Entities::Struct(struct_def) => Some(struct_def.fields().len()),
+        Entities::Class(class_def) => Some(class_def.fields().len()),
+        Entities::Exception(exception_def) => Some(exception_def.fields().len()),
+        Entities::Interface(interface_def) => Some(interface_def.operations().len()),
+        Entities::Enum(enum_def) => Some(enum_def.enumerators().len()),
+        _ => None,
+    }
+}
+
+/// Returns the fully scoped identifiers of the provided entity's bases, starting with its direct base.
+/// For interfaces, this is the longest chain of bases (preferring the first base listed if multiple are as long).
+fn bases_of(entity: &dyn Entity) -> Vec<String> {
+    let mut bases = Vec::new();
+    match entity.concrete_entity() {
+        Entities::Class(class_def) => {
+            let mut current = class_def.base_class();
+            while let Some(base) = current {
+                bases.push(base.parser_scoped_identifier());
+                current = base.base_class();
+            }
+        }
+        Entities::Exception(exception_def) => {
+            let mut current = exception_def.base_exception();
+            while let Some(base) = current {
+                bases.push(base.parser_scoped_identifier());
+                current = base.base_exception();
+            }
+        }
+        Entities::Interface(interface_def) => {
+            let chains = interface_def.base_interfaces().into_iter().map(|base| {
+                let mut chain = vec![base.parser_scoped_identifier()];
+                chain.extend(bases_of(base));
+                chain
+            });
+            // `max_by_key` returns the last maximum, so we reverse the chains to prefer the first listed base.
+            let chains = chains.collect::<Vec<_>>();
+            bases = chains.into_iter().rev().max_by_key(Vec::len).unwrap_or_default();
+        }
+        _ => {}
+    }
+    bases
+}
@@ -1,6 +1,7 @@
 // Copyright (c) ZeroC, Inc.
 
-use crate::grammar::Encoding;
+use crate::grammar::*;
+use crate::slice_file::Span;
 
 /// A struct for storing and computing what Slice encodings a Slice construct supports.
 #[derive(Clone, Debug)]
@@ -73,3 +74,246 @@ impl<I: std::slice::SliceIndex<[Encoding]>> std::ops::Index<I> for SupportedEnco
         &self.0[index]
     }
 }
+
+/// An explanation of why an entity can't be used with one of the Slice encodings.
+#[derive(Clone, Debug)]
+pub struct EncodingRestriction {
+    /// The encoding that isn't supported.
+    pub encoding: Encoding,
+
+    /// A human-readable description of why the encoding isn't supported.
+    pub reason: String,
+
+    /// The span of whatever caused this restriction (a field, a type reference, etc.), if it was caused by something
+    /// other than the entity itself.
+    pub span: Option<Span>,
+}
+
+/// Returns the encodings supported by the provided entity. See [Entity::supported_modes].
+pub fn supported_encodings_of(entity: &(impl Entity + ?Sized)) -> SupportedEncodings {
+    match entity.concrete_entity() {
+        Entities::Struct(struct_def) => struct_def.supported_encodings(),
+        Entities::Class(class_def) => class_def.supported_encodings(),
+        Entities::Exception(exception_def) => exception_def.supported_encodings(),
+        Entities::Interface(interface_def) => interface_def.supported_encodings(),
+        Entities::Enum(enum_def) => enum_def.supported_encodings(),
+        Entities::CustomType(custom_type) => custom_type.supported_encodings(),
+        Entities::TypeAlias(type_alias) => type_alias.supported_encodings(),
+        Entities::Enumerator(enumerator) => enumerator.parent().supported_encodings(),
+
+        // Members only support the encodings that both their type, and the entity they belong to support.
+        Entities::Field(field) => {
+            let mut encodings = member_encodings(field);
+            encodings.intersect_with(&supported_encodings_of(field.parent()));
+            encodings
+        }
+        Entities::Parameter(parameter) => {
+            // We check the parameter's interface, instead of its operation, since an operation's encodings are
+            // computed from its parameters; each parameter would be restricted by all the others.
+            let mut encodings = member_encodings(parameter);
+            encodings.intersect_with(&parameter.parent().parent().supported_encodings());
+            encodings
+        }
+
+        // Operations only support the encodings that their interface, and all of their members support.
+        Entities::Operation(operation) => {
+            let mut encodings = operation.parent().supported_encodings();
+            for member in operation.parameters_and_return_members() {
+                encodings.intersect_with(&member_encodings(member));
+            }
+            encodings
+        }
+
+        // Constants are only used at compile time, and so are never encoded.
+        Entities::Constant(_) => SupportedEncodings::new(vec![Encoding::Slice1, Encoding::Slice2]),
+    }
+}
+
+/// Returns an explanation for each encoding that the provided entity doesn't support.
+/// See [Entity::explain_supported_modes].
+pub fn explain_supported_encodings_of(entity: &(impl Entity + ?Sized)) -> Vec<EncodingRestriction> {
+    let supported_encodings = supported_encodings_of(entity);
+    let mut restrictions = Vec::new();
+
+    for encoding in [Encoding::Slice1, Encoding::Slice2] {
+        if supported_encodings.supports(encoding) {
+            continue;
+        }
+
+        let reasons_start = restrictions.len();
+        let mut restrict = |reason: String, span: Option<&Span>| {
+            let span = span.cloned();
+            restrictions.push(EncodingRestriction { encoding, reason, span });
+        };
+
+        match entity.concrete_entity() {
+            Entities::Struct(struct_def) => {
+                if !struct_def.is_compact && encoding == Encoding::Slice1 {
+                    restrict("only compact structs are supported by Slice1".to_owned(), None);
+                }
+                explain_fields(struct_def.fields(), encoding, &mut restrict);
+            }
+            Entities::Class(class_def) => {
+                if encoding == Encoding::Slice2 {
+                    restrict("classes are only supported by Slice1".to_owned(), None);
+                }
+                explain_fields(class_def.all_fields(), encoding, &mut restrict);
+            }
+            Entities::Exception(exception_def) => {
+                if encoding == Encoding::Slice2 {
+                    restrict("exceptions are only supported by Slice1".to_owned(), None);
+                }
+                explain_fields(exception_def.all_fields(), encoding, &mut restrict);
+            }
+            Entities::Enum(enum_def) if encoding == Encoding::Slice1 => {
+                if let Some(underlying) = &enum_def.underlying {
+                    restrict(
+                        "enums with underlying types are not supported by Slice1".to_owned(),
+                        None,
+                    );
+                    explain_type_ref(underlying, false, encoding, &mut restrict);
+                }
+                if enum_def.is_compact {
+                    restrict("compact enums are not supported by Slice1".to_owned(), None);
+                }
+                if enum_def
+                    .enumerators()
+                    .iter()
+                    .any(|enumerator| enumerator.fields.is_some())
+                {
+                    restrict("enumerators with fields are not supported by Slice1".to_owned(), None);
+                }
+            }
+            Entities::TypeAlias(type_alias) => explain_type_ref(&type_alias.underlying, false, encoding, &mut restrict),
+            Entities::Enumerator(enumerator) => explain_parent(enumerator.parent(), encoding, &mut restrict),
+            // Members are only restricted by their parents if their own types support the encoding.
+            Entities::Field(field) => match member_encodings(field).supports(encoding) {
+                true => explain_parent(field.parent(), encoding, &mut restrict),
+                false => explain_type_ref(field.data_type(), field.is_tagged(), encoding, &mut restrict),
+            },
+            Entities::Parameter(parameter) => match member_encodings(parameter).supports(encoding) {
+                true => explain_parent(parameter.parent().parent(), encoding, &mut restrict),
+                false => explain_type_ref(parameter.data_type(), parameter.is_tagged(), encoding, &mut restrict),
+            },
+            Entities::Operation(operation) => {
+                for member in operation.parameters_and_return_members() {
+                    if !member_encodings(member).supports(encoding) {
+                        let reason = format!(
+                            "{} '{}' isn't supported by {encoding}",
+                            member.kind(),
+                            member.identifier()
+                        );
+                        restrict(reason, Some(member.span()));
+                    }
+                }
+                explain_parent(operation.parent(), encoding, &mut restrict);
+            }
+            _ => {}
+        }
+
+        // If nothing else restricted the entity, it must be because of the compilation mode of the file it's in.
+        // Files can only restrict what encodings are supported to Slice2.
+        if restrictions.len() == reasons_start && encoding == Encoding::Slice1 {
+            let reason = format!("it's defined in a file whose compilation mode is {}", Encoding::Slice2);
+            restrictions.push(EncodingRestriction {
+                encoding,
+                reason,
+                span: None,
+            });
+        }
+    }
+    restrictions
+}
+
+/// Returns the encodings supported by a member's type, taking into account whether the member is optional or tagged.
+fn member_encodings(member: &impl Member) -> SupportedEncodings {
+    type_ref_encodings(member.data_type(), member.is_tagged())
+}
+
+/// Returns the encodings supported by a type reference. This is stricter than just checking its underlying type,
+/// since optional types (and anonymous types containing optionals) can't be used with Slice1 unless they're tagged.
+fn type_ref_encodings(type_ref: &TypeRef<impl Type + ?Sized>, is_tagged: bool) -> SupportedEncodings {
+    type_ref_encodings_with(type_ref, is_tagged, &|type_def| supported_encodings_of(type_def))
+}
+
+/// Returns the encodings supported by a type reference, like [type_ref_encodings], but uses `encodings_of` to get the
+/// encodings of any user-defined types it references. This lets the encoding patcher check type references before the
+/// encodings of the types they reference have been stored in the AST.
+pub(crate) fn type_ref_encodings_with(
+    type_ref: &TypeRef<impl Type + ?Sized>,
+    is_tagged: bool,
+    encodings_of: &dyn Fn(&dyn Entity) -> SupportedEncodings,
+) -> SupportedEncodings {
+    let mut encodings = match type_ref.concrete_type() {
+        Types::Struct(struct_def) => encodings_of(struct_def),
+        Types::Class(class_def) => encodings_of(class_def),
+        Types::Enum(enum_def) => encodings_of(enum_def),
+        Types::CustomType(custom_type) => encodings_of(custom_type),
+        Types::Sequence(sequence) => type_ref_encodings_with(&sequence.element_type, false, encodings_of),
+        Types::Dictionary(dictionary) => {
+            let mut encodings = type_ref_encodings_with(&dictionary.key_type, false, encodings_of);
+            encodings.intersect_with(&type_ref_encodings_with(&dictionary.value_type, false, encodings_of));
+            encodings
+        }
+        Types::ResultType(result_type) => {
+            let mut encodings = type_ref_encodings_with(&result_type.success_type, false, encodings_of);
+            encodings.intersect_with(&type_ref_encodings_with(&result_type.failure_type, false, encodings_of));
+            encodings.disable(Encoding::Slice1);
+            encodings
+        }
+        Types::Primitive(primitive) => primitive.supported_encodings(),
+    };
+
+    if !allows_optional_with_slice1(type_ref, is_tagged) {
+        encodings.disable(Encoding::Slice1);
+    }
+    encodings
+}
+
+/// Returns true if the type reference can be used with Slice1 regardless of whether it's optional.
+/// Slice1 only supports optional types if they're tagged, or are classes, custom types, or 'AnyClass'.
+pub(crate) fn allows_optional_with_slice1(type_ref: &TypeRef<impl Type + ?Sized>, is_tagged: bool) -> bool {
+    let allows_optionals = matches!(
+        type_ref.concrete_type(),
+        Types::Class(_) | Types::CustomType(_) | Types::Primitive(Primitive::AnyClass),
+    );
+    !type_ref.is_optional || is_tagged || allows_optionals
+}
+
+fn explain_fields(fields: Vec<&Field>, encoding: Encoding, restrict: &mut impl FnMut(String, Option<&Span>)) {
+    for field in fields {
+        if !member_encodings(field).supports(encoding) {
+            let reason = format!("field '{}' isn't supported by {encoding}", field.identifier());
+            restrict(reason, Some(field.span()));
+        }
+    }
+}
+
+fn explain_type_ref(
+    type_ref: &TypeRef<impl Type + ?Sized>,
+    is_tagged: bool,
+    encoding: Encoding,
+    restrict: &mut impl FnMut(String, Option<&Span>),
+) {
+    if type_ref_encodings(type_ref, is_tagged).supports(encoding) {
+        return;
+    }
+
+    // If the type would be supported if it were tagged, then the only problem is that it's an untagged optional.
+    let reason = match type_ref_encodings(type_ref, true).supports(encoding) {
+        true => format!("optional types are only supported by {encoding} if they're tagged"),
+        false => format!("its type '{}' isn't supported by {encoding}", type_ref.type_string()),
+    };
+    restrict(reason, Some(type_ref.span()));
+}
+
+fn explain_parent(parent: &dyn Entity, encoding: Encoding, restrict: &mut impl FnMut(String, Option<&Span>)) {
+    if !supported_encodings_of(parent).supports(encoding) {
+        let reason = format!(
+            "{} '{}' isn't supported by {encoding}",
+            parent.kind(),
+            parent.identifier()
+        );
+        restrict(reason, Some(parent.span()));
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module reports the diagnostics which were suppressed by `allow` attributes or `--allow` flags, so that teams
+//! can audit whether their suppressions are still justified (and remove the ones which no longer suppress anything).
+//!
+//! Suppressions are counted per lint, and per place they happened: diagnostics with the same lint and location, which
+//! were allowed by the same thing, are only listed once.
+
+use crate::diagnostics::Diagnostic;
+use crate::slice_options::{SliceOptions, SuppressionReportFormat};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// A report of the diagnostics which were suppressed during compilation.
+#[derive(Debug, Default, Serialize)]
+pub struct SuppressionReport {
+    /// The number of diagnostics which were suppressed, for each lint. Keys are lint names, like `Deprecated`.
+    pub lints: BTreeMap<String, usize>,
+
+    /// Each place that diagnostics were suppressed, sorted by file and location.
+    /// Suppressed diagnostics without a location (like `DuplicateFile`) are listed first.
+    pub suppressions: Vec<Suppression>,
+}
+
+/// A place where diagnostics were suppressed.
+#[derive(Debug, Serialize)]
+pub struct Suppression {
+    /// The relative path of the file the diagnostics were reported in, if they were reported in a file.
+    pub path: Option<String>,
+
+    /// The line the diagnostics were reported on, if they were reported in a file.
+    pub line: Option<usize>,
+
+    /// The column the diagnostics were reported at, if they were reported in a file.
+    pub column: Option<usize>,
+
+    /// The name of the lint that was suppressed. Ex: `Deprecated`.
+    pub lint: String,
+
+    /// What suppressed the diagnostics: the command line, a file attribute, or an attribute on an entity.
+    pub allowed_by: String,
+
+    /// How many diagnostics were suppressed here.
+    pub count: usize,
+}
+
+impl SuppressionReport {
+    /// Creates a report of the suppressed diagnostics among the provided diagnostics.
+    ///
+    /// The diagnostics must have been [updated](crate::diagnostics::Diagnostics::into_updated), since that's when
+    /// diagnostics are checked against the allowed lints.
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> Self {
+        let mut report = SuppressionReport::default();
+        let mut suppressions: BTreeMap<_, usize> = BTreeMap::new();
+
+        for diagnostic in diagnostics {
+            let Some(allowed_by) = diagnostic.allowed_by() else { continue };
+            let lint = diagnostic.code().to_owned();
+            *report.lints.entry(lint.clone()).or_default() += 1;
+
+            let span = diagnostic.span();
+            let path = span.map(|span| span.file.clone());
+            let location = span.map(|span| (span.start.row, span.start.col));
+            *suppressions.entry((path, location, lint, allowed_by)).or_default() += 1;
+        }
+
+        report.suppressions = suppressions
+            .into_iter()
+            .map(|((path, location, lint, allowed_by), count)| Suppression {
+                path,
+                line: location.map(|(line, _)| line),
+                column: location.map(|(_, column)| column),
+                lint,
+                allowed_by: allowed_by.to_string(),
+                count,
+            })
+            .collect();
+        report
+    }
+
+    /// Returns this report as an easily readable table.
+    pub fn to_report(&self) -> String {
+        let total = self.lints.values().sum::<usize>();
+        let mut report = format!("{:<40}{:>12}\n", "Suppressed Lint", "Count");
+        for (lint, count) in &self.lints {
+            report += &format!("{lint:<40}{count:>12}\n");
+        }
+        report += &format!("{:<40}{total:>12}\n", "Total");
+
+        if !self.suppressions.is_empty() {
+            report += &format!(
+                "\n{:<40}{:<28}{:<32}{:>12}\n",
+                "Location", "Lint", "Allowed By", "Count"
+            );
+            for suppression in &self.suppressions {
+                let location = match (&suppression.path, suppression.line, suppression.column) {
+                    (Some(path), Some(line), Some(column)) => format!("{path}:{line}:{column}"),
+                    _ => "-".to_owned(),
+                };
+                report += &format!(
+                    "{location:<40}{:<28}{:<32}{:>12}\n",
+                    suppression.lint, suppression.allowed_by, suppression.count,
+                );
+            }
+        }
+        report
+    }
+
+    /// Returns this report as a JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize suppression report")
+    }
+
+    /// Writes this report to stderr in the format specified by the `--suppression-report` option, if the user
+    /// specified it. Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        match options.suppression_report {
+            Some(SuppressionReportFormat::Human) => write!(stderr, "{}", self.to_report()),
+            Some(SuppressionReportFormat::Json) => writeln!(stderr, "{}", self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
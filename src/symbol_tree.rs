@@ -0,0 +1,160 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides an outline of the definitions in a Slice file, for tools like IDE breadcrumbs and
+//! documentation generators which need the file's structure, but not its full AST. See [SliceFile::symbol_tree].
+
+use crate::grammar::*;
+use crate::slice_file::{SliceFile, Span};
+
+/// The kinds of symbols that can appear in a symbol tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Module,
+    Struct,
+    Class,
+    Exception,
+    Interface,
+    Enum,
+    CustomType,
+    TypeAlias,
+    Constant,
+    Operation,
+    Field,
+    Parameter,
+    ReturnMember,
+    Enumerator,
+}
+
+/// A named definition in a Slice file, along with the definitions it contains.
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    /// The symbol's unscoped identifier, or for modules, its entire (possibly nested) identifier.
+    pub name: String,
+
+    pub kind: SymbolKind,
+
+    /// The span of the symbol's definition. For modules and container types (like structs), this only covers their
+    /// declaration (`struct S`), not their body.
+    pub span: Span,
+
+    /// The span of the symbol's identifier.
+    pub identifier_span: Span,
+
+    /// The symbols defined within this one, in the order they're defined.
+    pub children: Vec<Symbol>,
+}
+
+impl Symbol {
+    fn new(kind: SymbolKind, entity: &dyn Entity, children: Vec<Symbol>) -> Self {
+        Symbol {
+            name: entity.identifier().to_owned(),
+            kind,
+            span: entity.span().clone(),
+            identifier_span: entity.raw_identifier().span.clone(),
+            children,
+        }
+    }
+}
+
+impl SliceFile {
+    /// Returns an outline of the definitions in this file.
+    ///
+    /// If the file has a module declaration, this returns a single root symbol for the module, which contains all
+    /// the file's definitions. Otherwise it returns a root symbol for each definition in the file. Anonymous
+    /// definitions, like the structs synthesized for tuples, and nameless return types, aren't included.
+    pub fn symbol_tree(&self) -> Vec<Symbol> {
+        let definitions = self.contents.iter().filter_map(symbol_for_definition).collect();
+
+        match &self.module {
+            Some(module_ptr) => {
+                let module_def = module_ptr.borrow();
+                let symbol = Symbol {
+                    name: module_def.nested_module_identifier().to_owned(),
+                    kind: SymbolKind::Module,
+                    span: module_def.span.clone(),
+                    identifier_span: module_def.identifier.span.clone(),
+                    children: definitions,
+                };
+                vec![symbol]
+            }
+            None => definitions,
+        }
+    }
+}
+
+fn symbol_for_definition(definition: &Definition) -> Option<Symbol> {
+    let symbol = match definition {
+        Definition::Struct(struct_ptr) => {
+            let struct_def = struct_ptr.borrow();
+            if struct_def.is_synthesized {
+                return None;
+            }
+            let children = symbols_for_fields(struct_def.fields());
+            Symbol::new(SymbolKind::Struct, struct_def, children)
+        }
+        Definition::Class(class_ptr) => {
+            let class_def = class_ptr.borrow();
+            let children = symbols_for_fields(class_def.fields());
+            Symbol::new(SymbolKind::Class, class_def, children)
+        }
+        Definition::Exception(exception_ptr) => {
+            let exception_def = exception_ptr.borrow();
+            let children = symbols_for_fields(exception_def.fields());
+            Symbol::new(SymbolKind::Exception, exception_def, children)
+        }
+        Definition::Interface(interface_ptr) => {
+            let interface_def = interface_ptr.borrow();
+            let children = interface_def
+                .operations()
+                .into_iter()
+                .map(symbol_for_operation)
+                .collect();
+            Symbol::new(SymbolKind::Interface, interface_def, children)
+        }
+        Definition::Enum(enum_ptr) => {
+            let enum_def = enum_ptr.borrow();
+            let children = enum_def
+                .enumerators()
+                .into_iter()
+                .map(|enumerator| {
+                    let fields = symbols_for_fields(enumerator.fields());
+                    Symbol::new(SymbolKind::Enumerator, enumerator, fields)
+                })
+                .collect();
+            Symbol::new(SymbolKind::Enum, enum_def, children)
+        }
+        Definition::CustomType(custom_type_ptr) => {
+            Symbol::new(SymbolKind::CustomType, custom_type_ptr.borrow(), Vec::new())
+        }
+        Definition::TypeAlias(type_alias_ptr) => {
+            Symbol::new(SymbolKind::TypeAlias, type_alias_ptr.borrow(), Vec::new())
+        }
+        Definition::Constant(constant_ptr) => Symbol::new(SymbolKind::Constant, constant_ptr.borrow(), Vec::new()),
+    };
+    Some(symbol)
+}
+
+fn symbol_for_operation(operation: &Operation) -> Symbol {
+    let parameters = operation
+        .parameters()
+        .into_iter()
+        .map(|parameter| (SymbolKind::Parameter, parameter));
+    let return_members = operation
+        .return_members()
+        .into_iter()
+        .map(|member| (SymbolKind::ReturnMember, member));
+
+    let children = parameters
+        .chain(return_members)
+        .filter(|(_, member)| !member.is_synthesized)
+        .map(|(kind, member)| Symbol::new(kind, member, Vec::new()))
+        .collect();
+    Symbol::new(SymbolKind::Operation, operation, children)
+}
+
+fn symbols_for_fields(fields: Vec<&Field>) -> Vec<Symbol> {
+    fields
+        .into_iter()
+        .map(|field| Symbol::new(SymbolKind::Field, field, Vec::new()))
+        .collect()
+}
@@ -0,0 +1,369 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::SampleKind;
+use crate::ast::Ast;
+use crate::codec::{self, CodecError, Fields, Value};
+use crate::grammar::*;
+
+/// Once values are nested this deeply, optional members are left unset, collections are left empty,
+/// and class references are left null. This guarantees that generated values of recursive types are finite.
+const MAX_DEPTH: usize = 3;
+
+/// The maximum number of elements (or entries) in a randomly generated collection.
+const MAX_RANDOM_COLLECTION_SIZE: u64 = 4;
+
+/// The characters that randomly generated strings are made of. This includes multi-byte characters,
+/// so that the difference between a string's length and its encoded size is exercised.
+const RANDOM_STRING_CHARACTERS: [char; 8] = ['a', 'Z', '0', ' ', '~', '\u{e9}', '\u{2713}', '\u{1f980}'];
+
+type GeneratorResult = Result<Value, CodecError>;
+
+/// Generates instances of Slice types, either as [boundary values](SampleKind), or randomly.
+///
+/// Random values are generated deterministically from a seed, so that any failures they uncover are reproducible.
+/// They're biased towards boundary values, which are the most likely to expose bugs.
+///
+/// # Examples
+///
+/// ```
+/// # use slicec::grammar::{Encoding, Struct};
+/// # use slicec::test_vectors::ValueGenerator;
+/// # let state = slicec::compile_from_strings(&["module Test\nstruct S { i: int32, s: string? }"], None, |_| {}, |_| {});
+/// let struct_def = state.ast.find_element::<Struct>("Test::S").unwrap();
+///
+/// let mut generator = ValueGenerator::random(1234);
+/// let (value, bytes) = generator.generate_encoded(struct_def, Encoding::Slice2, &state.ast).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ValueGenerator {
+    strategy: Strategy,
+    depth: usize,
+}
+
+#[derive(Debug)]
+enum Strategy {
+    Boundary(SampleKind),
+    Random(SplitMix64),
+}
+
+impl ValueGenerator {
+    /// Creates a generator which produces the specified kind of boundary value.
+    pub fn boundary(kind: SampleKind) -> Self {
+        ValueGenerator {
+            strategy: Strategy::Boundary(kind),
+            depth: 0,
+        }
+    }
+
+    /// Creates a generator which produces random values, seeded with the provided seed.
+    pub fn random(seed: u64) -> Self {
+        ValueGenerator {
+            strategy: Strategy::Random(SplitMix64(seed)),
+            depth: 0,
+        }
+    }
+
+    /// Returns an instance of the provided type.
+    ///
+    /// Class types are nullable, so depending on the generator, `Value::Null` might be returned for them.
+    pub fn generate(&mut self, data_type: &dyn Type) -> GeneratorResult {
+        self.generate_type(data_type)
+    }
+
+    /// Returns an instance of the provided exception.
+    pub fn generate_exception(&mut self, exception_def: &Exception) -> GeneratorResult {
+        self.generate_instance(exception_def.parser_scoped_identifier(), exception_def.all_fields())
+    }
+
+    /// Returns an instance of the provided type, along with the bytes it encodes to with the specified encoding.
+    pub fn generate_encoded(
+        &mut self,
+        data_type: &dyn Type,
+        encoding: Encoding,
+        ast: &Ast,
+    ) -> Result<(Value, Vec<u8>), CodecError> {
+        let value = self.generate(data_type)?;
+        let bytes = codec::encode(&value, data_type, encoding, ast)?;
+        Ok((value, bytes))
+    }
+
+    /// Returns an instance of the provided entity (which must be a type).
+    /// Unlike [generate](Self::generate), this always returns an instance for classes, instead of a null reference.
+    pub(super) fn generate_entity(&mut self, entity: &Entities) -> GeneratorResult {
+        match entity {
+            Entities::Struct(struct_def) => self.generate_struct(struct_def),
+            Entities::Enum(enum_def) => self.generate_enum(enum_def),
+            Entities::Class(class_def) => {
+                self.generate_instance(class_def.parser_scoped_identifier(), class_def.all_fields())
+            }
+            Entities::Exception(exception_def) => self.generate_exception(exception_def),
+            _ => unreachable!("attempted to generate a value of a non-type entity"),
+        }
+    }
+
+    /// Returns true if optional members should be set (and collections populated) at the current depth.
+    /// Random generators only make this decision for each member individually, see [Self::is_set].
+    fn is_populated(&self) -> bool {
+        match &self.strategy {
+            Strategy::Boundary(kind) => *kind != SampleKind::Zero && self.depth < MAX_DEPTH,
+            Strategy::Random(_) => self.depth < MAX_DEPTH,
+        }
+    }
+
+    /// Returns true if an optional member (or class reference) should be set.
+    fn is_set(&mut self) -> bool {
+        let is_populated = self.is_populated();
+        match &mut self.strategy {
+            Strategy::Boundary(_) => is_populated,
+            Strategy::Random(rng) => is_populated && rng.next_bool(),
+        }
+    }
+
+    /// Returns a (non-null) value of the provided type.
+    fn generate_type(&mut self, data_type: &dyn Type) -> GeneratorResult {
+        match data_type.concrete_type() {
+            Types::Struct(struct_def) => self.generate_struct(struct_def),
+            Types::Class(class_def) => match self.is_set() {
+                true => self.generate_instance(class_def.parser_scoped_identifier(), class_def.all_fields()),
+                false => Ok(Value::Null),
+            },
+            Types::Enum(enum_def) => self.generate_enum(enum_def),
+            Types::CustomType(custom_type) => Err(CodecError::Unsupported(format!(
+                "the custom type '{}' cannot be encoded by the compiler",
+                custom_type.parser_scoped_identifier(),
+            ))),
+            Types::ResultType(result_type) => self.generate_result(result_type),
+            Types::Sequence(sequence) => self.generate_sequence(sequence),
+            Types::Dictionary(dictionary) => self.generate_dictionary(dictionary),
+            Types::Primitive(primitive) => Ok(self.generate_primitive(primitive)),
+        }
+    }
+
+    fn generate_primitive(&mut self, primitive: &Primitive) -> Value {
+        match primitive {
+            Primitive::Bool => Value::Bool(match &mut self.strategy {
+                Strategy::Boundary(kind) => *kind == SampleKind::Maximum,
+                Strategy::Random(rng) => rng.next_bool(),
+            }),
+            Primitive::Float32 => Value::Float32(match &mut self.strategy {
+                Strategy::Boundary(kind) => pick_boundary(*kind, 0.0, f32::MIN, f32::MAX),
+                Strategy::Random(rng) => rng.next_float(f32::MIN as f64, f32::MAX as f64) as f32,
+            }),
+            Primitive::Float64 => Value::Float64(match &mut self.strategy {
+                Strategy::Boundary(kind) => pick_boundary(*kind, 0.0, f64::MIN, f64::MAX),
+                Strategy::Random(rng) => rng.next_float(f64::MIN, f64::MAX),
+            }),
+            Primitive::String => Value::String(match &mut self.strategy {
+                Strategy::Boundary(SampleKind::Maximum) => "Slice \u{2713}".to_owned(),
+                Strategy::Boundary(_) => String::new(),
+                Strategy::Random(rng) => {
+                    let length = rng.next_below(MAX_RANDOM_COLLECTION_SIZE * 2 + 1);
+                    let characters = (0..length).map(|_| rng.next_below(RANDOM_STRING_CHARACTERS.len() as u64));
+                    characters.map(|i| RANDOM_STRING_CHARACTERS[i as usize]).collect()
+                }
+            }),
+            Primitive::Uuid => Value::Uuid(match &mut self.strategy {
+                Strategy::Boundary(kind) => [if *kind == SampleKind::Maximum { 0xFF } else { 0x00 }; 16],
+                Strategy::Random(rng) => std::array::from_fn(|_| rng.next_u64() as u8),
+            }),
+            Primitive::Timestamp | Primitive::Duration => {
+                Value::Integer(self.pick_integer((i64::MIN as i128, i64::MAX as i128)))
+            }
+            Primitive::AnyClass => Value::Null,
+            _ => Value::Integer(self.pick_integer(primitive.numeric_bounds().unwrap())),
+        }
+    }
+
+    fn pick_integer(&mut self, (min, max): (i128, i128)) -> i128 {
+        match &mut self.strategy {
+            Strategy::Boundary(kind) => pick_boundary(*kind, 0, min, max),
+            Strategy::Random(rng) => rng.next_integer(min, max),
+        }
+    }
+
+    fn generate_struct(&mut self, struct_def: &Struct) -> GeneratorResult {
+        Ok(Value::Struct(self.generate_fields(&struct_def.fields())?))
+    }
+
+    fn generate_instance(&mut self, type_name: String, fields: Vec<&Field>) -> GeneratorResult {
+        let fields = self.generate_fields(&fields)?;
+        Ok(Value::Instance { type_name, fields })
+    }
+
+    fn generate_enum(&mut self, enum_def: &Enum) -> GeneratorResult {
+        let mut enumerators = enum_def.enumerators().into_iter().filter(|e| !e.is_alias());
+        let enumerator = match &mut self.strategy {
+            Strategy::Boundary(SampleKind::Zero) => enumerators.next(),
+            Strategy::Boundary(SampleKind::Minimum) => enumerators.min_by_key(|e| e.value()),
+            Strategy::Boundary(SampleKind::Maximum) => enumerators.max_by_key(|e| e.value()),
+            Strategy::Random(rng) => {
+                let enumerators = enumerators.collect::<Vec<_>>();
+                let index = rng.next_below(enumerators.len() as u64) as usize;
+                enumerators.get(index).copied()
+            }
+        };
+        let Some(enumerator) = enumerator else {
+            let message = format!("the enum '{}' has no enumerators", enum_def.parser_scoped_identifier());
+            return Err(CodecError::Unsupported(message));
+        };
+
+        let has_fields = enum_def.enumerators().iter().any(|e| e.fields.is_some());
+        let fields = match has_fields {
+            true => Some(self.generate_fields(&enumerator.fields())?),
+            false => None,
+        };
+        Ok(Value::Enumerator {
+            identifier: Some(enumerator.identifier().to_owned()),
+            value: enumerator.value(),
+            fields,
+        })
+    }
+
+    fn generate_result(&mut self, result_type: &ResultType) -> GeneratorResult {
+        let is_failure = match &mut self.strategy {
+            Strategy::Boundary(kind) => *kind == SampleKind::Maximum,
+            Strategy::Random(rng) => rng.next_bool(),
+        };
+        Ok(match is_failure {
+            true => Value::Failure(Box::new(self.generate_member(&result_type.failure_type)?)),
+            false => Value::Success(Box::new(self.generate_member(&result_type.success_type)?)),
+        })
+    }
+
+    fn generate_sequence(&mut self, sequence: &Sequence) -> GeneratorResult {
+        let element_type = &sequence.element_type;
+        let mut elements = Vec::new();
+
+        if self.is_populated() {
+            self.depth += 1;
+            match &mut self.strategy {
+                // Populated sequences hold a single element, followed by an unset element if the element type is
+                // optional.
+                Strategy::Boundary(_) => {
+                    elements.push(self.generate_type(element_type.definition())?);
+                    if element_type.is_optional {
+                        elements.push(Value::Null);
+                    }
+                }
+                Strategy::Random(rng) => {
+                    for _ in 0..rng.next_below(MAX_RANDOM_COLLECTION_SIZE + 1) {
+                        elements.push(self.generate_member(element_type)?);
+                    }
+                }
+            }
+            self.depth -= 1;
+        }
+        Ok(Value::Sequence(elements))
+    }
+
+    fn generate_dictionary(&mut self, dictionary: &Dictionary) -> GeneratorResult {
+        let mut entries: Vec<(Value, Value)> = Vec::new();
+
+        // Populated dictionaries hold a single entry, or a random number of entries (with unique keys).
+        if self.is_populated() {
+            self.depth += 1;
+            let entry_count = match &mut self.strategy {
+                Strategy::Boundary(_) => 1,
+                Strategy::Random(rng) => rng.next_below(MAX_RANDOM_COLLECTION_SIZE + 1),
+            };
+            for _ in 0..entry_count {
+                let key = self.generate_type(dictionary.key_type.definition())?;
+                let value = self.generate_member(&dictionary.value_type)?;
+                if !entries.iter().any(|(k, _)| *k == key) {
+                    entries.push((key, value));
+                }
+            }
+            self.depth -= 1;
+        }
+        Ok(Value::Dictionary(entries))
+    }
+
+    fn generate_fields(&mut self, fields: &[&Field]) -> Result<Fields, CodecError> {
+        self.depth += 1;
+        let fields = fields
+            .iter()
+            .map(|field| Ok((field.identifier().to_owned(), self.generate_member(&field.data_type)?)))
+            .collect::<Result<Fields, CodecError>>()?;
+        self.depth -= 1;
+        Ok(fields)
+    }
+
+    /// Returns a value for a member of the provided type, which is only set if the member isn't optional,
+    /// or if the generator decides to set it.
+    fn generate_member(&mut self, type_ref: &TypeRef) -> GeneratorResult {
+        match type_ref.is_optional && !self.is_set() {
+            true => Ok(Value::Null),
+            false => self.generate_type(type_ref.definition()),
+        }
+    }
+}
+
+fn pick_boundary<T>(kind: SampleKind, zero: T, min: T, max: T) -> T {
+    match kind {
+        SampleKind::Zero => zero,
+        SampleKind::Minimum => min,
+        SampleKind::Maximum => max,
+    }
+}
+
+/// A small, fast, pseudo-random number generator. See: <https://prng.di.unimi.it/splitmix64.c>.
+///
+/// Generated values only need to be reproducible, not cryptographically secure.
+#[derive(Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns a random number in the range `0..bound`, or 0 if `bound` is 0.
+    fn next_below(&mut self, bound: u64) -> u64 {
+        match bound {
+            0 => 0,
+            _ => self.next_u64() % bound,
+        }
+    }
+
+    /// Returns a random integer in the range `min..=max`.
+    /// Half of the time, one of the range's boundary values (or zero) is returned instead.
+    fn next_integer(&mut self, min: i128, max: i128) -> i128 {
+        match self.next_below(8) {
+            0 => min,
+            1 => max,
+            2 if min <= 0 && max >= 0 => 0,
+            3 => min.saturating_add(1).min(max),
+            _ => {
+                let range = (max - min) as u128 + 1;
+                let random = ((self.next_u64() as u128) << 64) | self.next_u64() as u128;
+                min + (random % range) as i128
+            }
+        }
+    }
+
+    /// Returns a random (finite) float in the range `min..=max`.
+    /// Half of the time, one of the range's boundary values (or zero) is returned instead.
+    fn next_float(&mut self, min: f64, max: f64) -> f64 {
+        match self.next_below(8) {
+            0 => min,
+            1 => max,
+            2 => 0.0,
+            3 => -0.0,
+            _ => {
+                // Pick a random magnitude, so that both small and large values are generated.
+                let fraction = self.next_u64() as f64 / u64::MAX as f64;
+                let exponent = self.next_below(64) as i32 - 32;
+                let value = (fraction * 2.0 - 1.0) * 2f64.powi(exponent);
+                value.clamp(min, max)
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module generates wire-format conformance test vectors from a compiled AST.
+//!
+//! A test vector consists of a sample value of a Slice type, along with the exact bytes that value encodes to
+//! with a specific Slice encoding. Language mappings can check their encoders and decoders against these vectors,
+//! using the compiler as the authoritative model of the wire format. Values are encoded with the [codec] module.
+//!
+//! It also provides a [ValueGenerator] for producing boundary-value and random instances of any Slice type,
+//! for property-based testing of runtimes, and for seeding fuzzers (see [generate_fuzz_corpus]).
+
+mod generator;
+
+pub use generator::ValueGenerator;
+
+use crate::ast::node::Node;
+use crate::ast::Ast;
+use crate::codec::{self, CodecError, Value};
+use crate::grammar::*;
+use serde::Serialize;
+
+/// The different kinds of sample values that test vectors are generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleKind {
+    /// Every value is as small as possible: numerics are zero, strings and collections are empty,
+    /// optional and tagged members are unset, and results hold their success value.
+    Zero,
+
+    /// Numerics hold their minimum values, collections contain a single element, and all optional members are set.
+    Minimum,
+
+    /// Numerics hold their maximum values, collections contain a single element, all optional members are set,
+    /// and results hold their failure value.
+    Maximum,
+}
+
+impl SampleKind {
+    pub const ALL: [SampleKind; 3] = [SampleKind::Zero, SampleKind::Minimum, SampleKind::Maximum];
+}
+
+/// A sample value of a Slice type, along with the bytes it encodes to.
+#[derive(Debug, Serialize)]
+pub struct TestVector {
+    /// The fully scoped identifier of the type that was encoded.
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// The encoding that `bytes` was encoded with.
+    pub encoding: String,
+
+    /// The kind of sample that `value` is.
+    pub sample: SampleKind,
+
+    /// A JSON representation of the sample value.
+    pub value: serde_json::Value,
+
+    /// The encoded value, as a string of lowercase hexadecimal digits.
+    pub bytes: String,
+}
+
+/// A type that no test vectors could be generated for, along with the reason why.
+#[derive(Debug, Serialize)]
+pub struct SkippedType {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub encoding: String,
+    pub reason: String,
+}
+
+/// All the test vectors that were generated for an AST.
+#[derive(Debug, Default, Serialize)]
+pub struct TestVectors {
+    pub vectors: Vec<TestVector>,
+    pub skipped: Vec<SkippedType>,
+}
+
+impl TestVectors {
+    /// Returns these test vectors as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize test vectors")
+    }
+}
+
+/// Generates test vectors for every struct, enum, class, and exception in the provided AST.
+///
+/// Each type gets one vector per [SampleKind], for each of the encodings it supports.
+/// Types which can't be encoded by the compiler (because they contain custom types for example)
+/// are recorded in [TestVectors::skipped] instead.
+pub fn generate_test_vectors(ast: &Ast) -> TestVectors {
+    let mut test_vectors = TestVectors::default();
+
+    for node in ast.as_slice() {
+        let (type_name, encodings, entity) = match node {
+            Node::Struct(ptr) => {
+                let struct_def = ptr.borrow();
+                let encodings = struct_def.supported_encodings();
+                (
+                    struct_def.parser_scoped_identifier(),
+                    encodings,
+                    Entities::Struct(struct_def),
+                )
+            }
+            Node::Enum(ptr) => {
+                let enum_def = ptr.borrow();
+                let encodings = enum_def.supported_encodings();
+                (enum_def.parser_scoped_identifier(), encodings, Entities::Enum(enum_def))
+            }
+            Node::Class(ptr) => {
+                let class_def = ptr.borrow();
+                let encodings = class_def.supported_encodings();
+                (
+                    class_def.parser_scoped_identifier(),
+                    encodings,
+                    Entities::Class(class_def),
+                )
+            }
+            Node::Exception(ptr) => {
+                let exception_def = ptr.borrow();
+                let encodings = exception_def.supported_encodings();
+                (
+                    exception_def.parser_scoped_identifier(),
+                    encodings,
+                    Entities::Exception(exception_def),
+                )
+            }
+            _ => continue,
+        };
+
+        for encoding in [Encoding::Slice1, Encoding::Slice2] {
+            if !encodings.supports(encoding) {
+                continue;
+            }
+
+            let result = SampleKind::ALL
+                .into_iter()
+                .map(|kind| {
+                    let value = ValueGenerator::boundary(kind).generate_entity(&entity)?;
+                    let bytes = encode_entity(&value, &entity, encoding, ast)?;
+                    Ok(TestVector {
+                        type_name: type_name.clone(),
+                        encoding: encoding.to_string(),
+                        sample: kind,
+                        value: value.to_json(),
+                        bytes: to_hex(&bytes),
+                    })
+                })
+                .collect::<Result<Vec<_>, CodecError>>();
+
+            match result {
+                Ok(vectors) => test_vectors.vectors.extend(vectors),
+                Err(reason) => test_vectors.skipped.push(SkippedType {
+                    type_name: type_name.clone(),
+                    encoding: encoding.to_string(),
+                    reason: reason.to_string(),
+                }),
+            }
+        }
+    }
+    test_vectors
+}
+
+/// Generates a corpus of encoded instances of the provided type, for seeding fuzzers.
+///
+/// The corpus starts with an instance of each [SampleKind], followed by `random_count` random instances,
+/// which are generated from `seed`. Duplicate payloads are only included once.
+pub fn generate_fuzz_corpus(
+    data_type: &dyn Type,
+    encoding: Encoding,
+    ast: &Ast,
+    seed: u64,
+    random_count: usize,
+) -> Result<Vec<Vec<u8>>, CodecError> {
+    let boundary_generators = SampleKind::ALL.into_iter().map(ValueGenerator::boundary);
+    let mut random_generator = ValueGenerator::random(seed);
+
+    let mut corpus: Vec<Vec<u8>> = Vec::new();
+    for mut generator in boundary_generators {
+        let (_, bytes) = generator.generate_encoded(data_type, encoding, ast)?;
+        if !corpus.contains(&bytes) {
+            corpus.push(bytes);
+        }
+    }
+    for _ in 0..random_count {
+        let (_, bytes) = random_generator.generate_encoded(data_type, encoding, ast)?;
+        if !corpus.contains(&bytes) {
+            corpus.push(bytes);
+        }
+    }
+    Ok(corpus)
+}
+
+fn encode_entity(value: &Value, entity: &Entities, encoding: Encoding, ast: &Ast) -> Result<Vec<u8>, CodecError> {
+    match entity {
+        Entities::Struct(struct_def) => codec::encode(value, *struct_def, encoding, ast),
+        Entities::Enum(enum_def) => codec::encode(value, *enum_def, encoding, ast),
+        Entities::Class(class_def) => codec::encode(value, *class_def, encoding, ast),
+        Entities::Exception(exception_def) => codec::encode_exception(value, exception_def, encoding, ast),
+        _ => unreachable!("attempted to encode a non-type entity"),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
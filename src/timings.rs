@@ -0,0 +1,205 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module records how long each phase of compilation takes, so users can diagnose slow builds.
+//!
+//! The compiler records the time spent preprocessing, lexing, and parsing each file, along with the time spent
+//! patching and validating the AST, and the time each backend spent validating and generating code.
+//! Since the parser lexes its input on demand, lexing is only measured when timings or a trace were requested (by
+//! lexing each file in a separate pass), and that time is subtracted from the file's parsing time.
+//!
+//! These timings can also be written as a trace (see [Timings::to_trace_json]), which can be loaded into tools like
+//! Perfetto or `chrome://tracing`, to visualize when each phase and file was processed.
+
+use crate::slice_options::{SliceOptions, TimingsFormat};
+use serde::{Serialize, Serializer};
+use serde_json::json;
+use std::fs;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// The timings recorded during a single run of the compiler.
+#[derive(Debug, Serialize)]
+pub struct Timings {
+    /// When these timings started being recorded. The start of each phase and file is relative to this.
+    #[serde(skip)]
+    origin: Instant,
+
+    /// How long each phase of compilation took, in the order they were run.
+    pub phases: Vec<PhaseTiming>,
+
+    /// How long each file took to preprocess, lex, and parse, in the order they were parsed.
+    pub files: Vec<FileTiming>,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Timings {
+            origin: Instant::now(),
+            phases: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+}
+
+/// How long a single phase of compilation took.
+#[derive(Debug, Serialize)]
+pub struct PhaseTiming {
+    /// The name of the phase. Ex: `validate`, or `generate (cs)`.
+    pub phase: String,
+
+    /// How long the phase took. This is serialized as a (fractional) number of milliseconds.
+    #[serde(rename = "milliseconds", serialize_with = "serialize_as_milliseconds")]
+    pub duration: Duration,
+
+    /// When the phase started, relative to when the timings started being recorded.
+    /// This is `None` for phases which are totals of non-contiguous work, like the total time spent parsing files.
+    #[serde(skip)]
+    pub start: Option<Duration>,
+}
+
+/// How long a single file took to preprocess, lex, and parse.
+#[derive(Debug, Default, Serialize)]
+pub struct FileTiming {
+    /// The relative path of the file.
+    pub path: String,
+
+    /// When the file started being preprocessed, relative to when the timings started being recorded.
+    #[serde(skip)]
+    pub start: Duration,
+
+    #[serde(rename = "preprocess_milliseconds", serialize_with = "serialize_as_milliseconds")]
+    pub preprocess: Duration,
+
+    #[serde(rename = "lex_milliseconds", serialize_with = "serialize_as_milliseconds")]
+    pub lex: Duration,
+
+    #[serde(rename = "parse_milliseconds", serialize_with = "serialize_as_milliseconds")]
+    pub parse: Duration,
+}
+
+impl Timings {
+    /// Returns how much time has passed since these timings started being recorded.
+    pub fn elapsed(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    /// Records how long a phase of compilation took. The phase is assumed to have just finished.
+    pub fn record_phase(&mut self, phase: impl Into<String>, duration: Duration) {
+        self.phases.push(PhaseTiming {
+            phase: phase.into(),
+            duration,
+            start: Some(self.elapsed().saturating_sub(duration)),
+        });
+    }
+
+    /// Records the total preprocessing, lexing, and parsing times of all the files as separate phases.
+    pub(crate) fn record_file_phases(&mut self) {
+        let total = |f: fn(&FileTiming) -> Duration| self.files.iter().map(f).sum::<Duration>();
+        let (preprocess, lex, parse) = (total(|f| f.preprocess), total(|f| f.lex), total(|f| f.parse));
+
+        for (phase, duration) in [("preprocess", preprocess), ("lex", lex), ("parse", parse)] {
+            let phase = phase.to_owned();
+            self.phases.push(PhaseTiming {
+                phase,
+                duration,
+                start: None,
+            });
+        }
+    }
+
+    /// Returns the total time spent across all phases of compilation.
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|timing| timing.duration).sum()
+    }
+
+    /// Returns these timings as an easily readable table.
+    pub fn to_report(&self) -> String {
+        let mut report = format!("{:<40}{:>12}\n", "Phase", "Time");
+        for timing in &self.phases {
+            report += &format!("{:<40}{:>12}\n", timing.phase, format_duration(timing.duration));
+        }
+        report += &format!("{:<40}{:>12}\n", "total", format_duration(self.total()));
+
+        if !self.files.is_empty() {
+            report += &format!("\n{:<40}{:>12}{:>12}{:>12}\n", "File", "Preprocess", "Lex", "Parse");
+            for timing in &self.files {
+                report += &format!(
+                    "{:<40}{:>12}{:>12}{:>12}\n",
+                    timing.path,
+                    format_duration(timing.preprocess),
+                    format_duration(timing.lex),
+                    format_duration(timing.parse),
+                );
+            }
+        }
+        report
+    }
+
+    /// Returns these timings as a JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize timings")
+    }
+
+    /// Returns these timings as a JSON document in the trace event format used by `chrome://tracing` and Perfetto.
+    ///
+    /// Each phase is a complete event (`"ph": "X"`), and each file is an event containing separate events for when it
+    /// was preprocessed, lexed, and parsed. Phases which are totals of per-file work aren't included, since the files
+    /// already are. Timestamps and durations are in microseconds.
+    pub fn to_trace_json(&self) -> String {
+        let event = |name: &str, category: &str, start: Duration, duration: Duration| {
+            json!({
+                "name": name,
+                "cat": category,
+                "ph": "X",
+                "ts": start.as_secs_f64() * 1_000_000.0,
+                "dur": duration.as_secs_f64() * 1_000_000.0,
+                "pid": 1,
+                "tid": 1,
+            })
+        };
+
+        let mut events = Vec::new();
+        for timing in &self.phases {
+            if let Some(start) = timing.start {
+                events.push(event(&timing.phase, "phase", start, timing.duration));
+            }
+        }
+        for timing in &self.files {
+            let lex_start = timing.start + timing.preprocess;
+            let parse_start = lex_start + timing.lex;
+            let total = timing.preprocess + timing.lex + timing.parse;
+
+            events.push(event(&timing.path, "file", timing.start, total));
+            events.push(event("preprocess", "file", timing.start, timing.preprocess));
+            events.push(event("lex", "file", lex_start, timing.lex));
+            events.push(event("parse", "file", parse_start, timing.parse));
+        }
+
+        json!({ "traceEvents": events }).to_string()
+    }
+
+    /// Writes these timings to stderr in the format specified by the `--timings` option, if the user specified it, and
+    /// writes a trace of them to the path specified by the `--trace` option, if the user specified one.
+    /// Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        match options.timings {
+            Some(TimingsFormat::Human) => write!(stderr, "{}", self.to_report())?,
+            Some(TimingsFormat::Json) => writeln!(stderr, "{}", self.to_json())?,
+            None => {}
+        }
+
+        match &options.trace {
+            Some(path) => fs::write(path, self.to_trace_json()),
+            None => Ok(()),
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn serialize_as_milliseconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,157 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides a machine-readable registry of the types defined in a set of Slice files.
+//!
+//! The registry lists each interface along with its operations, and each class and exception, along with their type
+//! IDs and compact IDs. Runtimes can load it to support reflection-based dispatch, logging, and routing, without
+//! needing to parse the Slice files themselves.
+
+use crate::grammar::*;
+use crate::slice_file::SliceFile;
+use crate::slice_options::SliceOptions;
+use serde::{Deserialize, Serialize};
+use std::{fs, io};
+
+/// A registry of the interfaces, classes, and exceptions defined in the source files, in the order they were defined.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeRegistry {
+    pub interfaces: Vec<InterfaceEntry>,
+    pub classes: Vec<ClassEntry>,
+    pub exceptions: Vec<ExceptionEntry>,
+}
+
+/// A single interface, along with the operations it defines.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceEntry {
+    /// The interface's type ID. Ex: `::Foo::Bar::MyInterface`.
+    pub type_id: String,
+
+    /// The type IDs of the interfaces this interface directly inherits from.
+    pub bases: Vec<String>,
+
+    /// The operations this interface defines. This doesn't include any inherited operations.
+    pub operations: Vec<OperationEntry>,
+}
+
+/// A single operation.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationEntry {
+    /// The operation's identifier. This is the name used to dispatch it.
+    pub identifier: String,
+
+    /// Whether the operation was marked as `idempotent`.
+    pub is_idempotent: bool,
+
+    /// The identifiers of the operation's parameters, in the order they were defined.
+    pub parameters: Vec<String>,
+
+    /// The identifiers of the operation's return members, in the order they were defined.
+    pub return_members: Vec<String>,
+
+    /// The type IDs of the exceptions this operation can throw.
+    pub exceptions: Vec<String>,
+}
+
+/// A single class.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassEntry {
+    /// The class's type ID. Ex: `::Foo::Bar::MyClass`.
+    pub type_id: String,
+
+    /// The class's compact ID, if it has one.
+    pub compact_id: Option<u32>,
+
+    /// The type ID of the class's base class, if it has one.
+    pub base: Option<String>,
+}
+
+/// A single exception.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExceptionEntry {
+    /// The exception's type ID. Ex: `::Foo::Bar::MyException`.
+    pub type_id: String,
+
+    /// The type ID of the exception's base exception, if it has one.
+    pub base: Option<String>,
+}
+
+impl TypeRegistry {
+    /// Creates a registry of the types defined in the provided files.
+    /// Only source files are included; types defined in reference files are skipped.
+    pub fn from_files(files: &[SliceFile]) -> Self {
+        let mut registry = TypeRegistry::default();
+
+        let definitions = files
+            .iter()
+            .filter(|file| file.is_source)
+            .flat_map(|file| &file.contents);
+        for definition in definitions {
+            match definition {
+                Definition::Interface(interface_ptr) => {
+                    let interface_def = interface_ptr.borrow();
+                    registry.interfaces.push(InterfaceEntry {
+                        type_id: type_id_of(interface_def),
+                        bases: interface_def.base_interfaces().into_iter().map(type_id_of).collect(),
+                        operations: interface_def.operations().into_iter().map(operation_entry_of).collect(),
+                    });
+                }
+                Definition::Class(class_ptr) => {
+                    let class_def = class_ptr.borrow();
+                    registry.classes.push(ClassEntry {
+                        type_id: type_id_of(class_def),
+                        compact_id: class_def.compact_id.as_ref().map(|compact_id| compact_id.value),
+                        base: class_def.base_class().map(type_id_of),
+                    });
+                }
+                Definition::Exception(exception_ptr) => {
+                    let exception_def = exception_ptr.borrow();
+                    registry.exceptions.push(ExceptionEntry {
+                        type_id: type_id_of(exception_def),
+                        base: exception_def.base_exception().map(type_id_of),
+                    });
+                }
+                _ => {}
+            }
+        }
+        registry
+    }
+
+    /// Returns this registry as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize type registry")
+    }
+
+    /// Parses a registry from a JSON document, like one returned by [TypeRegistry::to_json].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes this registry to the path specified by the `--type-registry` option, if the user specified one.
+    /// Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        match &options.type_registry {
+            Some(path) => fs::write(path, self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
+
+fn operation_entry_of(operation: &Operation) -> OperationEntry {
+    let identifiers_of = |members: Vec<&Parameter>| members.iter().map(|m| m.identifier().to_owned()).collect();
+    let exceptions = operation.exception_specification.iter();
+
+    OperationEntry {
+        identifier: operation.identifier().to_owned(),
+        is_idempotent: operation.is_idempotent,
+        parameters: identifiers_of(operation.parameters()),
+        return_members: identifiers_of(operation.return_members()),
+        exceptions: exceptions
+            .map(|exception_ref| type_id_of(exception_ref.definition()))
+            .collect(),
+    }
+}
+
+/// Returns the type ID of the provided entity, which is its fully scoped identifier, with a leading '::'.
+fn type_id_of(entity: &impl Entity) -> String {
+    format!("::{}", entity.module_scoped_identifier())
+}
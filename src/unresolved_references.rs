@@ -0,0 +1,110 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module reports the type references which couldn't be resolved while patching the AST, so that build tooling
+//! can tell missing references (like a file that wasn't passed to the compiler) apart from other errors.
+//!
+//! Unresolved references are recorded by the type-reference patcher, in
+//! [CompilationState::unresolved_references](crate::compilation_state::CompilationState::unresolved_references).
+//! They're still reported as errors; this report just lists them in a form that's easier for tools to consume.
+
+use crate::slice_file::Span;
+use crate::slice_options::{SliceOptions, UnresolvedReferencesFormat};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// A type reference which couldn't be resolved to a definition.
+#[derive(Clone, Debug, Serialize)]
+pub struct UnresolvedReference {
+    /// The identifier of the type, as it was written in the Slice file.
+    pub identifier: String,
+
+    /// The kind of element the reference had to resolve to: `type`, `class`, `exception`, `interface`, or `primitive`.
+    pub expected: String,
+
+    /// The kind of element that was found instead, if the identifier resolved to an element of the wrong kind.
+    /// This is `None` if no element exists with the identifier.
+    pub found: Option<String>,
+
+    /// The fully scoped identifiers that were searched for, in the order they were searched in. The reference is first
+    /// looked up in the scope it was written in, then in each enclosing scope, and finally at global scope.
+    pub candidates: Vec<String>,
+
+    /// Where the reference was written.
+    pub span: Span,
+}
+
+impl UnresolvedReference {
+    /// Returns the fully scoped identifiers that a reference to `identifier` (written in `scope`) is searched for as.
+    /// This matches the search order of [Ast::find_node_with_scope](crate::ast::Ast::find_node_with_scope).
+    pub fn candidates_for(identifier: &str, scope: &str) -> Vec<String> {
+        // Globally scoped identifiers (starting with '::') are only searched for at global scope.
+        if let Some(unprefixed_identifier) = identifier.strip_prefix("::") {
+            return vec![unprefixed_identifier.to_owned()];
+        }
+
+        let mut scopes = scope.split("::").filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        let mut candidates = Vec::new();
+        while !scopes.is_empty() {
+            candidates.push(scopes.join("::") + "::" + identifier);
+            scopes.pop();
+        }
+        candidates.push(identifier.to_owned());
+        candidates
+    }
+}
+
+/// A report of the type references which couldn't be resolved during compilation.
+#[derive(Debug, Default, Serialize)]
+pub struct UnresolvedReferencesReport {
+    /// The unresolved references, sorted by file and location.
+    pub references: Vec<UnresolvedReference>,
+}
+
+impl UnresolvedReferencesReport {
+    /// Creates a report of the provided unresolved references.
+    pub fn from_references(references: &[UnresolvedReference]) -> Self {
+        let mut references = references.to_vec();
+        references.sort_by(|a, b| (&a.span.file, a.span.start).cmp(&(&b.span.file, b.span.start)));
+        UnresolvedReferencesReport { references }
+    }
+
+    /// Returns this report as an easily readable table.
+    pub fn to_report(&self) -> String {
+        if self.references.is_empty() {
+            return "No unresolved references\n".to_owned();
+        }
+
+        let mut report = format!(
+            "{:<40}{:<32}{:<12}{:<12}{}\n",
+            "Location", "Identifier", "Expected", "Found", "Searched"
+        );
+        for reference in &self.references {
+            let span = &reference.span;
+            let location = format!("{}:{}:{}", span.file, span.start.row, span.start.col);
+            report += &format!(
+                "{location:<40}{:<32}{:<12}{:<12}{}\n",
+                reference.identifier,
+                reference.expected,
+                reference.found.as_deref().unwrap_or("-"),
+                reference.candidates.join(", "),
+            );
+        }
+        report
+    }
+
+    /// Returns this report as a JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize unresolved references report")
+    }
+
+    /// Writes this report to stderr in the format specified by the `--list-unresolved` option, if the user specified
+    /// it. Otherwise this function is a no-op.
+    pub fn emit(&self, options: &SliceOptions) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        match options.list_unresolved {
+            Some(UnresolvedReferencesFormat::Human) => write!(stderr, "{}", self.to_report()),
+            Some(UnresolvedReferencesFormat::Json) => writeln!(stderr, "{}", self.to_json()),
+            None => Ok(()),
+        }
+    }
+}
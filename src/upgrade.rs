@@ -0,0 +1,382 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module upgrades Slice files written with older versions of the Slice syntax to use the current syntax.
+//!
+//! Old syntax can't be parsed by the compiler, so instead of parsing files normally, the upgrader uses their
+//! [lossless syntax trees](crate::lossless), skipping over any comments, and looks for specific patterns of old syntax:
+//! - statements ending with semicolons: `a: int32;` becomes `a: int32`
+//! - the `encoding` statement: `encoding = 1` becomes `mode = Slice1`
+//! - old primitive and keyword names used as types: `long` becomes `int64`, `sequence<T>` becomes `Sequence<T>`, etc.
+//! - module blocks: `module Foo { module Bar { ... } }` becomes `module Foo::Bar`, followed by its contents
+//!
+//! Each pattern is rewritten in place, so everything else in the file (including comments and formatting) is
+//! preserved. Anything which can't be rewritten automatically is reported as an issue, and left unchanged.
+
+use crate::lossless::{self, SyntaxKind};
+use crate::slice_file::Location;
+use crate::slice_options::UpgradeOptions;
+use std::{fs, io};
+
+/// The old names of primitive types and keywords, along with their current names.
+const RENAMED_TYPES: [(&str, &str); 15] = [
+    ("byte", "uint8"),
+    ("short", "int16"),
+    ("ushort", "uint16"),
+    ("int", "int32"),
+    ("uint", "uint32"),
+    ("varint", "varint32"),
+    ("varuint", "varuint32"),
+    ("long", "int64"),
+    ("ulong", "uint64"),
+    ("varlong", "varint62"),
+    ("varulong", "varuint62"),
+    ("float", "float32"),
+    ("double", "float64"),
+    ("sequence", "Sequence"),
+    ("dictionary", "Dictionary"),
+];
+
+/// The result of upgrading a single Slice file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Upgrade {
+    /// The upgraded text of the file.
+    pub text: String,
+
+    /// The changes that were made to the file, in the order they appear in it.
+    pub changes: Vec<Change>,
+
+    /// Old syntax that was found in the file, but couldn't be rewritten automatically.
+    pub issues: Vec<Change>,
+}
+
+impl Upgrade {
+    /// Returns true if the file already used the current syntax, and nothing needed to be changed.
+    pub fn is_up_to_date(&self) -> bool {
+        self.changes.is_empty() && self.issues.is_empty()
+    }
+}
+
+/// A single change made by the upgrader (or that it couldn't make).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change {
+    /// Where the old syntax is in the original file.
+    pub location: Location,
+
+    /// A human readable description of the change.
+    pub description: String,
+}
+
+/// Upgrades each of the files specified by `options`, returning the path of each file along with its upgrade.
+/// If the `--in-place` option was set, each file that isn't up to date is also overwritten with its upgraded text.
+pub fn upgrade_files(options: &UpgradeOptions) -> io::Result<Vec<(String, Upgrade)>> {
+    let mut upgrades = Vec::new();
+    for path in &options.sources {
+        let upgrade = upgrade(&fs::read_to_string(path)?);
+        if options.in_place && !upgrade.is_up_to_date() {
+            fs::write(path, &upgrade.text)?;
+        }
+        upgrades.push((path.clone(), upgrade));
+    }
+    Ok(upgrades)
+}
+
+/// Upgrades the text of a single Slice file to use the current Slice syntax.
+pub fn upgrade(text: &str) -> Upgrade {
+    let tokens = tokenize(text);
+    let mut upgrader = Upgrader {
+        text,
+        tokens: &tokens,
+        edits: Vec::new(),
+        issues: Vec::new(),
+    };
+    upgrader.upgrade_statements();
+    upgrader.upgrade_module_blocks();
+
+    // Sort the edits, and drop any which overlap with an earlier one (like a semicolon on a line which was removed).
+    let mut edits = upgrader.edits;
+    edits.sort_by_key(|edit| edit.start);
+    let mut end_of_last_edit = 0;
+    edits.retain(|edit| {
+        let overlaps = edit.start < end_of_last_edit;
+        end_of_last_edit = end_of_last_edit.max(edit.end);
+        !overlaps
+    });
+
+    // Apply the edits from last to first, so earlier edits don't shift the offsets of later ones.
+    let mut upgraded_text = text.to_owned();
+    for edit in edits.iter().rev() {
+        upgraded_text.replace_range(edit.start..edit.end, &edit.replacement);
+    }
+
+    let changes = edits
+        .into_iter()
+        .filter_map(|edit| edit.description.map(|d| (edit.start, d)));
+    Upgrade {
+        text: upgraded_text,
+        changes: changes.map(|(offset, d)| change_at(text, offset, d)).collect(),
+        issues: upgrader.issues,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Identifier,
+    Symbol,
+    Other,
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// A replacement of the text between `start` and `end`. Edits without a description are part of a larger change.
+struct Edit {
+    start: usize,
+    end: usize,
+    replacement: String,
+    description: Option<String>,
+}
+
+struct Upgrader<'a> {
+    text: &'a str,
+    tokens: &'a [Token],
+    edits: Vec<Edit>,
+    issues: Vec<Change>,
+}
+
+impl Upgrader<'_> {
+    fn lexeme(&self, token: &Token) -> &str {
+        &self.text[token.start..token.end]
+    }
+
+    fn is_symbol(&self, i: usize, symbol: &str) -> bool {
+        let token = self.tokens.get(i);
+        token.is_some_and(|token| token.kind == TokenKind::Symbol && self.lexeme(token) == symbol)
+    }
+
+    fn edit(&mut self, start: usize, end: usize, replacement: impl Into<String>, description: Option<String>) {
+        let replacement = replacement.into();
+        self.edits.push(Edit {
+            start,
+            end,
+            replacement,
+            description,
+        });
+    }
+
+    /// Rewrites semicolons, `encoding` statements, and renamed types.
+    fn upgrade_statements(&mut self) {
+        let mut attribute_depth = 0;
+        let mut angle_depth = 0;
+        let mut brace_depth = 0;
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            match (token.kind, self.lexeme(token)) {
+                (TokenKind::Symbol, "[" | "[[") => attribute_depth += 1,
+                (TokenKind::Symbol, "]" | "]]") => attribute_depth -= 1,
+                (TokenKind::Symbol, "<") => angle_depth += 1,
+                (TokenKind::Symbol, ">") => angle_depth -= 1,
+                (TokenKind::Symbol, "{") => brace_depth += 1,
+                (TokenKind::Symbol, "}") => brace_depth -= 1,
+                (TokenKind::Symbol, ";") if attribute_depth == 0 => {
+                    let description = "removed the semicolon".to_owned();
+                    self.edit(token.start, token.end, "", Some(description));
+                }
+                // The `encoding` statement could only appear at the top of a file, outside of any definitions.
+                (TokenKind::Identifier, "encoding") if brace_depth == 0 => {
+                    let Some(value) = self.tokens.get(i + 2) else {
+                        continue;
+                    };
+                    let mode = match self.lexeme(value) {
+                        "1" | "Slice1" => "Slice1",
+                        "2" | "Slice2" => "Slice2",
+                        _ => continue,
+                    };
+                    if self.is_symbol(i + 1, "=") {
+                        let description = format!("replaced the 'encoding' statement with 'mode = {mode}'");
+                        self.edit(token.start, value.end, format!("mode = {mode}"), Some(description));
+                    }
+                }
+                (TokenKind::Identifier, lexeme) if attribute_depth == 0 => {
+                    // Only rename identifiers which are in the position of a type, so identifiers which happen to
+                    // share a name with an old keyword (like a field named `float`) are left alone.
+                    // Escaped identifiers (`\long`) include their backslash, so they never match an old keyword.
+                    let is_type_position = i.checked_sub(1).is_some_and(|j| {
+                        [":", "<", "=", "->"].iter().any(|symbol| self.is_symbol(j, symbol))
+                            || (self.is_symbol(j, ",") && angle_depth > 0)
+                    });
+                    let renamed = RENAMED_TYPES.iter().find(|(old, _)| *old == lexeme);
+                    if let Some((old, new)) = renamed.filter(|_| is_type_position) {
+                        let description = format!("renamed '{old}' to '{new}'");
+                        self.edit(token.start, token.end, *new, Some(description));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Replaces a file's module block (and any module blocks directly nested in it) with a module declaration.
+    fn upgrade_module_blocks(&mut self) {
+        let module_blocks = self.find_module_blocks(0, self.tokens.len());
+        let Some(&(mut block)) = module_blocks.first() else {
+            return; // There are no module blocks in this file.
+        };
+
+        // Files can only have a single module declaration, so multiple module blocks can't be upgraded.
+        if module_blocks.len() > 1 {
+            let message = "files can only contain a single module; these must be split into separate files";
+            let second_block = module_blocks[1];
+            self.issues
+                .push(change_at(self.text, self.tokens[second_block.0].start, message));
+            return;
+        }
+
+        // Descend through any module blocks which only contain another module block.
+        let mut module_identifier = self.path_of(block);
+        let mut closing_braces = vec![block.2];
+        loop {
+            let inner_blocks = self.find_module_blocks(block.1 + 1, block.2);
+            match inner_blocks.as_slice() {
+                [] => break,
+                [inner_block] if self.only_contains(block, *inner_block) => {
+                    module_identifier = format!("{module_identifier}::{}", self.path_of(*inner_block));
+                    closing_braces.push(inner_block.2);
+                    block = *inner_block;
+                }
+                _ => {
+                    let message = "modules can't contain other modules alongside definitions; these must be split into separate files";
+                    self.issues
+                        .push(change_at(self.text, self.tokens[inner_blocks[0].0].start, message));
+                    return;
+                }
+            }
+        }
+
+        // Replace the opening of the module block(s) with a module declaration, and remove their closing braces.
+        let start = self.tokens[module_blocks[0].0].start;
+        let end = self.tokens[block.1].end;
+        let description = format!("replaced the module block with 'module {module_identifier}'");
+        self.edit(start, end, format!("module {module_identifier}"), Some(description));
+        for closing_brace in closing_braces {
+            let (start, end) = self.line_extent(&self.tokens[closing_brace]);
+            self.edit(start, end, "", None);
+        }
+    }
+
+    /// Returns the module blocks which are directly within the specified range of tokens (not nested in other braces).
+    /// Each block is returned as the indexes of its `module` keyword, its opening brace, and its closing brace.
+    fn find_module_blocks(&self, start: usize, end: usize) -> Vec<(usize, usize, usize)> {
+        let mut blocks = Vec::new();
+        let mut i = start;
+        while i < end {
+            let token = &self.tokens[i];
+            if token.kind == TokenKind::Identifier && self.lexeme(token) == "module" {
+                // Skip over the module's identifier, which may be made up of several segments (`Foo::Bar`).
+                let is_identifier = |j: usize| j < end && self.tokens[j].kind == TokenKind::Identifier;
+                if !is_identifier(i + 1) {
+                    i += 1;
+                    continue;
+                }
+                let mut j = i + 2;
+                while self.is_symbol(j, "::") && is_identifier(j + 1) {
+                    j += 2;
+                }
+                if j < end && self.is_symbol(j, "{") {
+                    let Some(closing_brace) = self.find_closing_brace(j, end) else {
+                        return blocks; // The module block is unterminated, so there's nothing more to find.
+                    };
+                    blocks.push((i, j, closing_brace));
+                    i = closing_brace;
+                }
+            } else if self.is_symbol(i, "{") {
+                // Skip over the contents of any other braces, like the body of a struct.
+                match self.find_closing_brace(i, end) {
+                    Some(closing_brace) => i = closing_brace,
+                    None => return blocks,
+                }
+            }
+            i += 1;
+        }
+        blocks
+    }
+
+    /// Returns the index of the brace which closes the opening brace at `open`, if there is one before `end`.
+    fn find_closing_brace(&self, open: usize, end: usize) -> Option<usize> {
+        let mut depth = 0;
+        for i in open..end {
+            if self.is_symbol(i, "{") {
+                depth += 1;
+            } else if self.is_symbol(i, "}") {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the text of a module block's identifier, with any whitespace between its segments removed.
+    fn path_of(&self, block: (usize, usize, usize)) -> String {
+        let segments = &self.tokens[block.0 + 1..block.1];
+        segments.iter().map(|token| self.lexeme(token)).collect()
+    }
+
+    /// Returns true if the outer module block contains nothing except the inner module block (and semicolons).
+    fn only_contains(&self, outer: (usize, usize, usize), inner: (usize, usize, usize)) -> bool {
+        let is_semicolon = |i: usize| self.is_symbol(i, ";");
+        (outer.1 + 1..inner.0).all(is_semicolon) && (inner.2 + 1..outer.2).all(is_semicolon)
+    }
+
+    /// Returns the extent of a token, extended to cover its entire line if nothing else is on the line with it.
+    fn line_extent(&self, token: &Token) -> (usize, usize) {
+        let line_start = self.text[..token.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[token.end..]
+            .find('\n')
+            .map_or(self.text.len(), |i| token.end + i + 1);
+        let is_alone = self.text[line_start..token.start].trim().is_empty()
+            && self.text[token.end..line_end]
+                .trim()
+                .trim_end_matches(';')
+                .trim()
+                .is_empty();
+        match is_alone {
+            true => (line_start, line_end),
+            false => (token.start, token.end),
+        }
+    }
+}
+
+/// Splits the text of a Slice file into tokens, skipping any trivia, doc comments, and preprocessor directives.
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    for syntax_token in lossless::tokenize(text, "") {
+        let (start, end) = (offset, offset + syntax_token.text.len());
+        offset = end;
+
+        let kind = match syntax_token.kind {
+            SyntaxKind::Keyword | SyntaxKind::Identifier => TokenKind::Identifier,
+            // Semicolons aren't valid in the current syntax, so they're lexed as unknown symbols.
+            SyntaxKind::Symbol | SyntaxKind::Unknown => TokenKind::Symbol,
+            SyntaxKind::IntegerLiteral | SyntaxKind::StringLiteral => TokenKind::Other,
+            _ => continue,
+        };
+        tokens.push(Token { kind, start, end });
+    }
+    tokens
+}
+
+fn change_at(text: &str, offset: usize, description: impl Into<String>) -> Change {
+    let preceding = &text[..offset];
+    let row = preceding.matches('\n').count() + 1;
+    let col = preceding.rsplit('\n').next().unwrap().chars().count() + 1;
+    Change {
+        location: Location { row, col },
+        description: description.into(),
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module provides case conversion and keyword escaping functions for use by code generation backends.
+//!
+//! Identifiers are first split into words, and then re-joined in the requested case. Words are separated by
+//! underscores, hyphens, and whitespace, by a lowercase letter or digit followed by an uppercase letter (`myName`), and
+//! by the last letter in a run of uppercase letters, if it's followed by a lowercase letter (`HTTPServer`).
+//! So `HTTPServer`, `http_server`, and `http-server` are all split into the words `HTTP` and `Server`, etc.
+
+use std::collections::HashMap;
+
+/// The cases that identifiers can be converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// Each word is capitalized, and there is no separator between them: `HttpServerName`.
+    Pascal,
+
+    /// Each word except the first is capitalized, and there is no separator between them: `httpServerName`.
+    Camel,
+
+    /// Each word is lowercase, and they are separated by underscores: `http_server_name`.
+    Snake,
+
+    /// Each word is lowercase, and they are separated by hyphens: `http-server-name`.
+    Kebab,
+}
+
+/// Splits an identifier into its component words. See the module documentation for how word boundaries are found.
+pub fn split_words(identifier: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let chars = identifier.char_indices().collect::<Vec<_>>();
+    let mut word_start = None;
+
+    for (i, &(index, c)) in chars.iter().enumerate() {
+        // Separators end the current word and are dropped.
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                words.push(&identifier[start..index]);
+            }
+            continue;
+        }
+
+        if let Some(start) = word_start {
+            let previous = chars[i - 1].1;
+            let next = chars.get(i + 1).map(|(_, c)| *c);
+
+            let is_camel_boundary = c.is_uppercase() && (previous.is_lowercase() || previous.is_numeric());
+            let is_acronym_boundary =
+                c.is_uppercase() && previous.is_uppercase() && next.is_some_and(char::is_lowercase);
+            if is_camel_boundary || is_acronym_boundary {
+                words.push(&identifier[start..index]);
+                word_start = Some(index);
+            }
+        } else {
+            word_start = Some(index);
+        }
+    }
+
+    if let Some(start) = word_start {
+        words.push(&identifier[start..]);
+    }
+    words
+}
+
+/// Converts an identifier to the specified case.
+///
+/// Acronyms are treated like any other word; `HTTPServer` becomes `HttpServer` in Pascal case.
+/// To preserve specific acronyms, use [convert_case_with_acronyms] instead.
+pub fn convert_case(identifier: &str, case: Case) -> String {
+    convert_case_with_acronyms(identifier, case, &[])
+}
+
+/// Converts an identifier to the specified case, writing any words that match one of the provided `acronyms`
+/// (ignoring case) in uppercase when converting to Pascal or Camel case.
+/// Acronyms are still lowercase when converting to Snake or Kebab case, or if they're the first word in Camel case.
+///
+/// For example, with the acronym `"IO"`, `io_stream` becomes `IOStream` in Pascal case, and `ioStream` in Camel case.
+pub fn convert_case_with_acronyms(identifier: &str, case: Case, acronyms: &[&str]) -> String {
+    let words = split_words(identifier);
+    let separator = match case {
+        Case::Pascal | Case::Camel => "",
+        Case::Snake => "_",
+        Case::Kebab => "-",
+    };
+
+    let converted_words = words.iter().enumerate().map(|(i, word)| match case {
+        Case::Camel if i == 0 => word.to_lowercase(),
+        Case::Pascal | Case::Camel => {
+            if acronyms.iter().any(|acronym| acronym.eq_ignore_ascii_case(word)) {
+                word.to_uppercase()
+            } else {
+                capitalize(word)
+            }
+        }
+        Case::Snake | Case::Kebab => word.to_lowercase(),
+    });
+    converted_words.collect::<Vec<_>>().join(separator)
+}
+
+/// Converts an identifier to Pascal case. See [convert_case](fn@convert_case).
+pub fn to_pascal_case(identifier: &str) -> String {
+    convert_case(identifier, Case::Pascal)
+}
+
+/// Converts an identifier to Camel case. See [convert_case](fn@convert_case).
+pub fn to_camel_case(identifier: &str) -> String {
+    convert_case(identifier, Case::Camel)
+}
+
+/// Converts an identifier to Snake case. See [convert_case](fn@convert_case).
+pub fn to_snake_case(identifier: &str) -> String {
+    convert_case(identifier, Case::Snake)
+}
+
+/// Converts an identifier to Kebab case. See [convert_case](fn@convert_case).
+pub fn to_kebab_case(identifier: &str) -> String {
+    convert_case(identifier, Case::Kebab)
+}
+
+/// Returns the word with its first character in uppercase, and the rest in lowercase.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+/// A language's reserved words, along with how identifiers that conflict with them should be escaped.
+#[derive(Debug)]
+pub struct ReservedWords {
+    words: Vec<&'static str>,
+    case_sensitive: bool,
+    escape: fn(&str) -> String,
+}
+
+impl ReservedWords {
+    /// Creates a table of case-sensitive reserved words, where conflicting identifiers are escaped by `escape`.
+    pub fn new(words: &[&'static str], escape: fn(&str) -> String) -> Self {
+        ReservedWords {
+            words: words.to_vec(),
+            case_sensitive: true,
+            escape,
+        }
+    }
+
+    /// Creates a table of reserved words which are matched without regard to (ASCII) case.
+    pub fn new_case_insensitive(words: &[&'static str], escape: fn(&str) -> String) -> Self {
+        ReservedWords {
+            case_sensitive: false,
+            ..Self::new(words, escape)
+        }
+    }
+
+    /// Returns true if the identifier is one of this language's reserved words.
+    pub fn is_reserved(&self, identifier: &str) -> bool {
+        self.words.iter().any(|word| match self.case_sensitive {
+            true => *word == identifier,
+            false => word.eq_ignore_ascii_case(identifier),
+        })
+    }
+
+    /// Returns the identifier, escaped if it's one of this language's reserved words.
+    pub fn escape(&self, identifier: &str) -> String {
+        match self.is_reserved(identifier) {
+            true => (self.escape)(identifier),
+            false => identifier.to_owned(),
+        }
+    }
+}
+
+/// A registry of reserved word tables, keyed by the name of the language they belong to.
+///
+/// Each backend registers the tables for the languages it generates, so that escaping logic is shared between all the
+/// code that generates identifiers, instead of being re-implemented at each call site.
+#[derive(Debug, Default)]
+pub struct ReservedWordsRegistry {
+    tables: HashMap<String, ReservedWords>,
+}
+
+impl ReservedWordsRegistry {
+    /// Registers a language's reserved words, replacing any table previously registered for that language.
+    pub fn register(&mut self, language: impl Into<String>, reserved_words: ReservedWords) {
+        self.tables.insert(language.into(), reserved_words);
+    }
+
+    /// Returns the reserved words registered for the specified language, if there are any.
+    pub fn get(&self, language: &str) -> Option<&ReservedWords> {
+        self.tables.get(language)
+    }
+
+    /// Returns the identifier, escaped if it's one of the specified language's reserved words.
+    /// If no reserved words have been registered for the language, the identifier is returned unchanged.
+    pub fn escape(&self, language: &str, identifier: &str) -> String {
+        match self.get(language) {
+            Some(reserved_words) => reserved_words.escape(identifier),
+            None => identifier.to_owned(),
+        }
+    }
+}
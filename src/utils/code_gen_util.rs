@@ -0,0 +1,30 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains helper functions for backends which generate encoding and decoding code, so that every
+//! backend agrees with the rules implemented by `slicec`.
+
+use crate::grammar::*;
+
+/// Returns true if the elements of a sequence, or the values of a dictionary, with the provided element type are
+/// preceded by a bit sequence when encoded with the specified encoding.
+///
+/// With Slice2, collections of optional elements are encoded with a bit sequence, holding one bit for each element
+/// that indicates whether it's set, and only the set elements are encoded after it. Slice1 doesn't have bit
+/// sequences; the only optional elements it supports are classes and custom types, which encode their own nullability.
+pub fn is_element_bit_sequence_encodable(element_type: &TypeRef, encoding: Encoding) -> bool {
+    element_type.is_optional && encoding == Encoding::Slice2
+}
+
+/// Returns the number of bits in the bit sequence which precedes the provided members when they're encoded with the
+/// specified encoding. This is the number of untagged optional members, since tagged members encode their own
+/// presence. With Slice1, this is always zero.
+pub fn get_bit_sequence_size<T: Member>(members: &[&T], encoding: Encoding) -> usize {
+    if encoding == Encoding::Slice1 {
+        return 0;
+    }
+
+    let untagged_optionals = members
+        .iter()
+        .filter(|member| !member.is_tagged() && member.data_type().is_optional);
+    untagged_optionals.count()
+}
@@ -1,11 +1,73 @@
 // Copyright (c) ZeroC, Inc.
 
 use crate::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
+use crate::packages::{self, Package};
 use crate::slice_file::SliceFile;
 use crate::slice_options::SliceOptions;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::{fs, io};
 
+/// A cache of the text of Slice files, keyed by their canonicalized paths.
+///
+/// When files are resolved through a cache, each file is only read from disk the first time it's resolved; after that,
+/// its text is shared (not copied) by every [SliceFile] created for it. This lets multiple compilations which share
+/// files (like the compilation units of a [Workspace](crate::workspace::Workspace)) only read and store them once.
+#[derive(Debug, Default)]
+pub struct FileCache {
+    texts: HashMap<PathBuf, Arc<str>>,
+}
+
+impl FileCache {
+    /// Removes the cached text of the file at the provided path, so it's re-read the next time it's resolved.
+    /// This should be called whenever a file changes on disk. Returns true if the file was cached.
+    pub fn invalidate(&mut self, path: impl AsRef<Path>) -> bool {
+        match path.as_ref().canonicalize() {
+            Ok(canonicalized_path) => self.texts.remove(&canonicalized_path).is_some(),
+            Err(_) => false,
+        }
+    }
+
+    /// Removes the cached text of every file.
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+
+    /// Returns the number of files whose text is cached.
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Returns true if no files are cached.
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Returns the text of the provided file, reading it from disk if it isn't already cached.
+    /// Files that couldn't be read aren't cached. See [read_file] for the possible errors.
+    fn get_or_read(&mut self, file_path: &FilePath, max_size: Option<u64>) -> Result<Arc<str>, Error> {
+        if let Some(text) = self.texts.get(&file_path.canonicalized_path) {
+            tracing::debug!(path = file_path.path, "using cached file");
+            // The maximum size is checked again, since the file may have been cached with different options.
+            return match max_size.filter(|&max| text.len() as u64 > max) {
+                Some(max) => Err(Error::FileTooLarge {
+                    path: file_path.path.clone(),
+                    size: text.len() as u64,
+                    max,
+                }),
+                None => Ok(text.clone()),
+            };
+        }
+
+        tracing::debug!(path = file_path.path, "reading file");
+        let text: Arc<str> = read_file(&file_path.path, max_size)?.into();
+        self.texts.insert(file_path.canonicalized_path.clone(), text.clone());
+        Ok(text)
+    }
+}
+
 /// A wrapper around a file path that implements Hash and Eq. This allows us to use a HashMap to store the path the user
 /// supplied while using the canonicalized path as the key.
 #[derive(Debug, Eq)]
@@ -29,6 +91,21 @@ impl FilePath {
     }
 }
 
+impl FilePath {
+    /// Returns the path this file should be reported with in diagnostics (and stored in its [SliceFile]).
+    ///
+    /// Since the path is derived from the canonicalized path, it doesn't depend on how the user spelled the path
+    /// (`foo/../bar.slice` and `./bar.slice` are both reported as `bar.slice`) or on any symbolic links in it.
+    /// If `use_absolute_path` is true, the canonicalized path is used as-is. Otherwise, files within the working
+    /// directory are reported relative to it, and any other files are reported with their canonicalized paths.
+    fn display_path(&self, working_directory: Option<&Path>, use_absolute_path: bool) -> String {
+        let relative_path = working_directory
+            .filter(|_| !use_absolute_path)
+            .and_then(|directory| self.canonicalized_path.strip_prefix(directory).ok());
+        relative_path.unwrap_or(&self.canonicalized_path).display().to_string()
+    }
+}
+
 impl PartialEq for FilePath {
     fn eq(&self, other: &Self) -> bool {
         self.canonicalized_path == other.canonicalized_path
@@ -41,6 +118,7 @@ fn remove_duplicate_file_paths(file_paths: Vec<FilePath>, diagnostics: &mut Diag
     let mut deduped_file_paths = Vec::with_capacity(file_paths.len());
     for file_path in file_paths {
         if deduped_file_paths.contains(&file_path) {
+            tracing::debug!(path = file_path.path, "skipping duplicate file");
             let lint = Lint::DuplicateFile { path: file_path.path };
             Diagnostic::new(lint).push_into(diagnostics);
         } else {
@@ -50,17 +128,89 @@ fn remove_duplicate_file_paths(file_paths: Vec<FilePath>, diagnostics: &mut Diag
     deduped_file_paths
 }
 
+/// The environment variable that lists directories to search for reference files, separated by the platform's path
+/// separator (':' on Unix, and ';' on Windows). See [reference_search_path].
+pub const SLICE_PATH_VARIABLE: &str = "SLICE_PATH";
+
+/// The name of the file that lists a project's default reference directories, one per line. It's read from the current
+/// working directory, and relative directories in it are relative to that directory. Empty lines, and lines starting
+/// with '#', are ignored. See [reference_search_path].
+pub const PROJECT_SEARCH_PATH_FILE: &str = ".slicepath";
+
+/// Returns the paths that reference files are searched for in, in the order they're searched.
+///
+/// These are the paths passed with '--reference', followed by the project's default reference directories (listed in
+/// the [PROJECT_SEARCH_PATH_FILE]), and then the directories listed in the [SLICE_PATH_VARIABLE] environment variable.
+/// The default directories are skipped if '--no-default-references' was passed, or if they don't exist.
+pub fn reference_search_path(options: &SliceOptions) -> Vec<String> {
+    let mut search_path = options.references.clone();
+    if options.no_default_references {
+        return search_path;
+    }
+
+    let project_directories = fs::read_to_string(PROJECT_SEARCH_PATH_FILE).unwrap_or_default();
+    let project_directories = project_directories
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(PathBuf::from);
+    let environment_directories = std::env::var_os(SLICE_PATH_VARIABLE)
+        .map(|value| std::env::split_paths(&value).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    for directory in project_directories.chain(environment_directories) {
+        let directory = directory.display().to_string();
+        if directory.is_empty() || search_path.contains(&directory) {
+            continue;
+        }
+        match Path::new(&directory).is_dir() {
+            true => search_path.push(directory),
+            false => tracing::debug!(
+                path = directory,
+                "skipping default reference directory, since it doesn't exist"
+            ),
+        }
+    }
+    search_path
+}
+
 pub fn resolve_files_from(options: &SliceOptions, diagnostics: &mut Diagnostics) -> Vec<SliceFile> {
+    resolve_files_using(options, &mut FileCache::default(), diagnostics)
+}
+
+/// Resolves the files specified by `options` like [resolve_files_from], but reads their text through the provided
+/// cache, so files which were already read by a previous call aren't read again.
+pub fn resolve_files_using(
+    options: &SliceOptions,
+    cache: &mut FileCache,
+    diagnostics: &mut Diagnostics,
+) -> Vec<SliceFile> {
+    let packages = packages::resolve_packages(options, diagnostics);
+    resolve_files_with_packages(options, &packages, cache, diagnostics)
+}
+
+/// Resolves the files specified by `options` like [resolve_files_using], but takes the packages whose files should be
+/// referenced, instead of resolving them from `options`. See [resolve_packages](packages::resolve_packages).
+pub fn resolve_files_with_packages(
+    options: &SliceOptions,
+    packages: &[Package],
+    cache: &mut FileCache,
+    diagnostics: &mut Diagnostics,
+) -> Vec<SliceFile> {
+    let _span = tracing::info_span!("resolve").entered();
     let mut file_paths = Vec::new();
 
     // Add any source files to the list of file paths, after removing duplicates.
     let source_files = find_slice_files(&options.sources, true, diagnostics);
     file_paths.extend(remove_duplicate_file_paths(source_files, diagnostics));
 
-    // Add any reference files to the list of file paths, after removing duplicates. We omit reference files that have
-    // already been included as source files; we don't emit a warning for them, we just silently omit them. It's
-    // important to do this after the source files, to ensure source files are given 'priority' over reference files.
-    let reference_files = find_slice_files(&options.references, false, diagnostics);
+    // Add any reference files (including the files of any packages) to the list of file paths, after removing
+    // duplicates. We omit reference files that have already been included as source files; we don't emit a warning
+    // for them, we just silently omit them. It's important to do this after the source files, to ensure source files
+    // are given 'priority' over reference files.
+    let mut reference_paths = reference_search_path(options);
+    reference_paths.extend(packages.iter().flat_map(Package::file_paths));
+    let reference_files = find_slice_files(&reference_paths, false, diagnostics);
     for reference_file in remove_duplicate_file_paths(reference_files, diagnostics) {
         if !file_paths.contains(&reference_file) {
             file_paths.push(reference_file);
@@ -69,19 +219,73 @@ pub fn resolve_files_from(options: &SliceOptions, diagnostics: &mut Diagnostics)
 
     // Iterate through the discovered files and try to read them into Strings.
     // Report an error if it fails, otherwise create a new `SliceFile` to hold the data.
+    // Unreadable files are skipped, so compilation can continue with the remaining files.
+    let working_directory = std::env::current_dir()
+        .and_then(|directory| directory.canonicalize())
+        .ok();
     let mut files = Vec::new();
     for file_path in file_paths {
-        match fs::read_to_string(&file_path.path) {
-            Ok(raw_text) => files.push(SliceFile::new(file_path.path, raw_text, file_path.is_source)),
-            Err(error) => Diagnostic::new(Error::IO {
-                action: "read",
-                path: file_path.path,
-                error,
-            })
-            .push_into(diagnostics),
+        match cache.get_or_read(&file_path, options.max_file_size) {
+            Ok(raw_text) => {
+                let path = file_path.display_path(working_directory.as_deref(), options.absolute_paths);
+                files.push(SliceFile::new(path, raw_text, file_path.is_source));
+            }
+            Err(error) => {
+                let diagnostic = Diagnostic::new(error);
+                tracing::debug!(
+                    path = file_path.path,
+                    error = diagnostic.message(),
+                    "failed to read file"
+                );
+                diagnostic.push_into(diagnostics);
+            }
+        }
+    }
+    remove_duplicate_file_contents(files, diagnostics)
+}
+
+/// This function takes a `Vec<SliceFile>` and returns it, after removing any files whose contents are identical to an
+/// earlier file's. Since the order of `files` is preserved, source files are always kept over reference files.
+/// A lint violation is reported for each removed file.
+fn remove_duplicate_file_contents(files: Vec<SliceFile>, diagnostics: &mut Diagnostics) -> Vec<SliceFile> {
+    // Maps the hash of each kept file's contents to the indexes of the kept files with that hash.
+    let mut seen_hashes: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut deduped_files: Vec<SliceFile> = Vec::with_capacity(files.len());
+
+    for file in files {
+        let hash = hash_contents(&file.raw_text);
+        let candidates = seen_hashes.entry(hash).or_default();
+
+        // Empty files can't cause redefinitions, so there's no reason to ignore (or warn about) them.
+        let is_empty = file.raw_text.trim().is_empty();
+        let original = candidates
+            .iter()
+            .map(|&i| &deduped_files[i])
+            .find(|original| !is_empty && original.raw_text == file.raw_text);
+
+        if let Some(original) = original {
+            tracing::debug!(
+                path = file.relative_path,
+                original_path = original.relative_path,
+                "skipping file with duplicate contents",
+            );
+            let lint = Lint::DuplicateFileContents {
+                path: file.relative_path,
+                original_path: original.relative_path.clone(),
+            };
+            Diagnostic::new(lint).push_into(diagnostics);
+        } else {
+            candidates.push(deduped_files.len());
+            deduped_files.push(file);
         }
     }
-    files
+    deduped_files
+}
+
+fn hash_contents(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
 }
 
 fn find_slice_files(paths: &[String], are_source_files: bool, diagnostics: &mut Diagnostics) -> Vec<FilePath> {
@@ -94,40 +298,22 @@ fn find_slice_files(paths: &[String], are_source_files: bool, diagnostics: &mut
 
         // If the path does not exist, report an error and continue.
         if !path_buf.exists() {
-            Diagnostic::new(Error::IO {
-                action: "read",
-                path: path.to_owned(),
-                error: io::ErrorKind::NotFound.into(),
-            })
-            .push_into(diagnostics);
+            let error = Error::FileNotFound { path: path.to_owned() };
+            Diagnostic::new(error).push_into(diagnostics);
             continue;
         }
 
         // If the path is a file but is not a Slice file, report an error and continue.
         if path_buf.is_file() && !is_slice_file(&path_buf) {
-            // If the path is a file, check if it is a slice file.
-            // TODO: It would be better to use `io::ErrorKind::InvalidFilename`, however it is an unstable feature.
-            let io_error = io::Error::other("Slice files must end with a '.slice' extension");
-            Diagnostic::new(Error::IO {
-                action: "read",
-                path: path.to_owned(),
-                error: io_error,
-            })
-            .push_into(diagnostics);
+            let error = Error::NotASliceFile { path: path.to_owned() };
+            Diagnostic::new(error).push_into(diagnostics);
             continue;
         }
 
         // If the path is a directory and directories are not allowed, report an error and continue.
         if path_buf.is_dir() && !allow_directories {
-            // If the path is a file, check if it is a slice file.
-            // TODO: It would be better to use `io::ErrorKind::InvalidFilename`, however it is an unstable feature.
-            let io_error = io::Error::other("Expected a Slice file but found a directory.");
-            Diagnostic::new(Error::IO {
-                action: "read",
-                path: path.to_owned(),
-                error: io_error,
-            })
-            .push_into(diagnostics);
+            let error = Error::DirectoryPassedAsSource { path: path.to_owned() };
+            Diagnostic::new(error).push_into(diagnostics);
             continue;
         }
 
@@ -167,9 +353,11 @@ fn find_slice_files_in_path(path: PathBuf, diagnostics: &mut Diagnostics) -> Vec
         }
     } else if path.is_file() && is_slice_file(&path) {
         // Add the file to the list of paths.
+        tracing::debug!(path = %path.display(), "found Slice file");
         paths.push(path);
+    } else {
+        tracing::debug!(path = %path.display(), "ignoring path, since it isn't a Slice file or directory");
     }
-    // else we ignore the path
 
     paths
 }
@@ -197,6 +385,38 @@ fn find_slice_files_in_directory(path: &Path, diagnostics: &mut Diagnostics) ->
     Ok(paths)
 }
 
+/// Reads the file at the provided path into a String.
+/// If the file cannot be read, its contents aren't valid UTF-8, or it's larger than `max_size` bytes (when specified),
+/// an [Error] describing the problem is returned. The size is checked before the file is read.
+fn read_file(path: &str, max_size: Option<u64>) -> Result<String, Error> {
+    let map_io_error = |error: io::Error| match error.kind() {
+        io::ErrorKind::NotFound => Error::FileNotFound { path: path.to_owned() },
+        _ => Error::IO {
+            action: "read",
+            path: path.to_owned(),
+            error,
+        },
+    };
+
+    if let Some(max) = max_size {
+        let size = fs::metadata(path).map_err(map_io_error)?.len();
+        if size > max {
+            return Err(Error::FileTooLarge {
+                path: path.to_owned(),
+                size,
+                max,
+            });
+        }
+    }
+
+    let bytes = fs::read(path).map_err(map_io_error)?;
+
+    String::from_utf8(bytes).map_err(|error| Error::InvalidUtf8 {
+        path: path.to_owned(),
+        offset: error.utf8_error().valid_up_to(),
+    })
+}
+
 /// Returns true if the path has the 'slice' extension.
 fn is_slice_file(path: &Path) -> bool {
     path.extension().filter(|ext| ext.to_str() == Some("slice")).is_some()
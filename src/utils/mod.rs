@@ -1,6 +1,8 @@
 // Copyright (c) ZeroC, Inc.
 
 pub mod attribute_parsing_util;
+pub mod casing;
+pub mod code_gen_util;
 pub mod file_util;
 pub mod ptr_util;
 pub mod string_util;
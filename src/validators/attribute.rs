@@ -7,10 +7,14 @@ use std::collections::HashMap;
 
 pub fn validate_attributes(attributable: &(impl Attributable + AsAttributables), diagnostics: &mut Diagnostics) {
     let attributes = attributable.attributes();
+    validate_attribute_list(attributes, attributable.concrete_attributable(), diagnostics);
+}
+
+/// Validates a list of attributes that were applied to the provided element.
+pub fn validate_attribute_list(attributes: Vec<&Attribute>, applied_on: Attributables, diagnostics: &mut Diagnostics) {
     validate_repeated_attributes(&attributes, diagnostics);
     for attribute in attributes {
-        let concrete_type = attributable.concrete_attributable();
-        attribute.kind.validate_on(concrete_type, attribute.span(), diagnostics);
+        attribute.kind.validate_on(applied_on, attribute.span(), diagnostics);
     }
 }
 
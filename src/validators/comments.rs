@@ -8,9 +8,12 @@ pub fn validate_common_doc_comments(commentable: &dyn Commentable, diagnostics:
     // Only run this validation if a doc comment is present.
     let Some(comment) = commentable.comment() else { return };
 
-    only_operations_have_parameters(comment, commentable, diagnostics);
-    only_operations_can_return(comment, commentable, diagnostics);
-    only_operations_can_throw(comment, commentable, diagnostics);
+    // Localizations are held to the same rules as the comments they localize.
+    for comment in comment.with_localizations() {
+        only_operations_have_parameters(comment, commentable, diagnostics);
+        only_operations_can_return(comment, commentable, diagnostics);
+        only_operations_can_throw(comment, commentable, diagnostics);
+    }
 }
 
 fn only_operations_have_parameters(comment: &DocComment, entity: &dyn Commentable, diagnostics: &mut Diagnostics) {
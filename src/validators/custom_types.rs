@@ -0,0 +1,54 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+use crate::slice_file::SliceFile;
+
+/// Reports any custom types defined in the source files which are missing a mapping for one of the provided backends.
+///
+/// Each mapping is a backend's name, along with the directive of the attribute it maps custom types with.
+/// A single error is reported for each custom type, listing every backend it's missing a mapping for.
+pub fn check_for_missing_custom_type_mappings(
+    files: &[SliceFile],
+    mappings: &[(&str, &str)],
+    diagnostics: &mut Diagnostics,
+) {
+    if mappings.is_empty() {
+        return;
+    }
+
+    let definitions = files
+        .iter()
+        .filter(|file| file.is_source)
+        .flat_map(|file| &file.contents);
+    for definition in definitions {
+        let Definition::CustomType(custom_type_ptr) = definition else { continue };
+        let custom_type = custom_type_ptr.borrow();
+
+        let directives = custom_type
+            .attributes()
+            .into_iter()
+            .map(|attribute| attribute.kind.directive());
+        let directives = directives.collect::<Vec<_>>();
+        let missing = mappings.iter().filter(|(_, directive)| !directives.contains(directive));
+        let missing = missing.collect::<Vec<_>>();
+        if missing.is_empty() {
+            continue;
+        }
+
+        let backends = missing
+            .iter()
+            .map(|(backend, _)| format!("'{backend}'"))
+            .collect::<Vec<_>>();
+        let mut error = Diagnostic::new(Error::MissingCustomTypeMappings {
+            identifier: custom_type.identifier().to_owned(),
+            backends: backends.join(", "),
+        })
+        .set_span(custom_type.span());
+        for (backend, directive) in missing {
+            let message = format!("the '{backend}' backend maps custom types with the '{directive}' attribute");
+            error = error.add_note(message, None);
+        }
+        error.push_into(diagnostics);
+    }
+}
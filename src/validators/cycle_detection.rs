@@ -71,25 +71,38 @@ struct CycleDetector<'a> {
 impl<'a> CycleDetector<'a> {
     fn check_fields_for_cycles(&mut self, container: &'a dyn Container<Field>) {
         for field in container.contents() {
-            self.check_field_type_for_cycles(field.data_type(), field);
+            self.check_field_type_for_cycles(field.data_type(), field, false);
         }
     }
 
-    fn check_field_type_for_cycles(&mut self, type_ref: &'a TypeRef, origin: &'a Field) {
+    /// `is_in_collection` is true if the type is (somewhere within) the element of a sequence or value of a dictionary.
+    fn check_field_type_for_cycles(&mut self, type_ref: &'a TypeRef, origin: &'a Field, is_in_collection: bool) {
+        // Optional types always break cycles, since they can be unset, giving the cycle a finite size.
+        // So cycles through optional types are legal, the same as cycles through classes (which use reference semantics).
+        if type_ref.is_optional {
+            return;
+        }
+
+        // Boxed types break cycles within collections, since collections can be empty, giving the cycle a finite size.
+        // Outside of collections, a boxed type is always present, so unless it's optional, the cycle is still infinite.
+        if type_ref.is_boxed() && is_in_collection {
+            return;
+        }
+
         match type_ref.concrete_type() {
             // For struct or enum types, we push them onto the stack, and attempt to recursively check them.
             Types::Struct(struct_ref) => self.push_to_stack_and_check(struct_ref, origin),
             Types::Enum(enum_ref) => self.push_to_stack_and_check(enum_ref, origin),
 
             Types::ResultType(result_type) => {
-                self.check_field_type_for_cycles(&result_type.success_type, origin);
-                self.check_field_type_for_cycles(&result_type.failure_type, origin);
+                self.check_field_type_for_cycles(&result_type.success_type, origin, is_in_collection);
+                self.check_field_type_for_cycles(&result_type.failure_type, origin, is_in_collection);
             }
 
-            Types::Sequence(sequence) => self.check_field_type_for_cycles(&sequence.element_type, origin),
+            Types::Sequence(sequence) => self.check_field_type_for_cycles(&sequence.element_type, origin, true),
             Types::Dictionary(dictionary) => {
-                self.check_field_type_for_cycles(&dictionary.key_type, origin);
-                self.check_field_type_for_cycles(&dictionary.value_type, origin);
+                self.check_field_type_for_cycles(&dictionary.key_type, origin, is_in_collection);
+                self.check_field_type_for_cycles(&dictionary.value_type, origin, true);
             }
 
             // Classes always break cycles since they use reference semantics.
@@ -155,6 +168,10 @@ impl<'a> CycleDetector<'a> {
         Diagnostic::new(Error::InfiniteSizeCycle { type_id, cycle })
             .set_span(type_being_checked.1.span())
             .extend_notes(cycle_notes)
+            .add_note(
+                "cycles can be broken by making one of these fields optional, or by using a class",
+                None,
+            )
             .push_into(self.diagnostics);
     }
 
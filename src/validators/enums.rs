@@ -1,6 +1,6 @@
 // Copyright (c) ZeroC, Inc.
 
-use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
 use crate::grammar::*;
 
 use std::collections::HashMap;
@@ -13,6 +13,7 @@ pub fn validate_enum(enum_def: &Enum, diagnostics: &mut Diagnostics) {
     nonempty_if_checked(enum_def, diagnostics);
     check_compact_modifier(enum_def, diagnostics);
     compact_enums_cannot_contain_tags(enum_def, diagnostics);
+    flag_values_are_powers_of_two(enum_def, diagnostics);
 
     // Fields in Slice1 files are already rejected by `encoding_patcher`.
     if enum_def.underlying.is_some() && !enum_def.supported_encodings().supports(Encoding::Slice1) {
@@ -21,10 +22,11 @@ pub fn validate_enum(enum_def: &Enum, diagnostics: &mut Diagnostics) {
 }
 
 /// Validate that the enumerators are within the bounds of the specified underlying type.
+/// Aliases are skipped, since they share the value of the enumerator they alias, which is checked already.
 fn backing_type_bounds(enum_def: &Enum, diagnostics: &mut Diagnostics) {
     if enum_def.supported_encodings().supports(Encoding::Slice1) {
         // Enum was defined in a Slice1 file, so it's underlying type is int32 and its enumerators must be positive.
-        for enumerator in enum_def.enumerators() {
+        for enumerator in enum_def.enumerators().into_iter().filter(|e| !e.is_alias()) {
             let value = enumerator.value();
             if value < 0 || value > i32::MAX as i128 {
                 Diagnostic::new(Error::EnumeratorValueOutOfBounds {
@@ -33,17 +35,23 @@ fn backing_type_bounds(enum_def: &Enum, diagnostics: &mut Diagnostics) {
                     min: 0,
                     max: i32::MAX as i128,
                 })
-                .set_span(enumerator.span())
+                .set_span(enumerator.value_span())
                 .push_into(diagnostics);
             }
         }
     } else {
         // Enum was defined in a Slice2 file.
 
-        fn check_bounds(enum_def: &Enum, (min, max): (i128, i128), diagnostics: &mut Diagnostics) {
+        fn check_bounds(
+            enum_def: &Enum,
+            (min, max): (i128, i128),
+            underlying_type: Option<&TypeRef<Primitive>>,
+            diagnostics: &mut Diagnostics,
+        ) {
             enum_def
                 .enumerators()
                 .iter()
+                .filter(|enumerator| !enumerator.is_alias())
                 .filter(|enumerator| enumerator.value() < min || enumerator.value() > max)
                 .for_each(|enumerator| {
                     let error = Error::EnumeratorValueOutOfBounds {
@@ -52,22 +60,25 @@ fn backing_type_bounds(enum_def: &Enum, diagnostics: &mut Diagnostics) {
                         min,
                         max,
                     };
-                    Diagnostic::new(error)
-                        .set_span(enumerator.span())
-                        .push_into(diagnostics);
+                    let mut diagnostic = Diagnostic::new(error).set_span(enumerator.value_span());
+                    if let Some(underlying_type) = underlying_type {
+                        let message = format!("the enum's underlying type is '{}'", underlying_type.type_string());
+                        diagnostic = diagnostic.add_note(message, Some(underlying_type.span()));
+                    }
+                    diagnostic.push_into(diagnostics);
                 });
         }
         match &enum_def.underlying {
             Some(underlying_type) => {
                 // Non-integral underlying types are rejected by the `allowed_underlying_types` check.
                 if let Some(bounds) = underlying_type.numeric_bounds() {
-                    check_bounds(enum_def, bounds, diagnostics);
+                    check_bounds(enum_def, bounds, Some(underlying_type), diagnostics);
                 }
             }
             None => {
                 // For enumerators in Slice2, values must fit within varint32 and be positive.
                 const VARINT32_MAX: i128 = i32::MAX as i128;
-                check_bounds(enum_def, (0, VARINT32_MAX), diagnostics);
+                check_bounds(enum_def, (0, VARINT32_MAX), None, diagnostics);
             }
         }
     }
@@ -88,9 +99,10 @@ fn allowed_underlying_types(enum_def: &Enum, diagnostics: &mut Diagnostics) {
 }
 
 /// Validate that enumerator values aren't re-used within an enum.
+/// Aliases are allowed to re-use values, since sharing the value of another enumerator is their purpose.
 fn enumerator_values_are_unique(enum_def: &Enum, diagnostics: &mut Diagnostics) {
     let mut value_to_enumerator_map: HashMap<i128, &Enumerator> = HashMap::new();
-    for enumerator in enum_def.enumerators() {
+    for enumerator in enum_def.enumerators().into_iter().filter(|e| !e.is_alias()) {
         // If the value is already in the map, another enumerator already used it. Get that enumerator from the map
         // and report an error. Otherwise add the enumerator and its value to the map.
         if let Some(alt_enum) = value_to_enumerator_map.get(&enumerator.value()) {
@@ -202,3 +214,39 @@ fn compact_enums_cannot_contain_tags(enum_def: &Enum, diagnostics: &mut Diagnost
         }
     }
 }
+
+/// Validate that the enumerators of a flags enum each represent a single flag (their values are powers of 2, or 0), or a
+/// named combination of the enum's single flags (their values are a bitwise OR of other enumerators' values).
+fn flag_values_are_powers_of_two(enum_def: &Enum, diagnostics: &mut Diagnostics) {
+    if enum_def.is_flags() {
+        let enumerators = enum_def
+            .enumerators()
+            .into_iter()
+            .filter(|e| !e.is_alias())
+            .collect::<Vec<_>>();
+        let is_single_flag = |value: i128| value == 0 || (value > 0 && value & (value - 1) == 0);
+        let declared_flags = enumerators
+            .iter()
+            .map(|enumerator| enumerator.value())
+            .filter(|value| is_single_flag(*value))
+            .fold(0, |flags, value| flags | value);
+
+        for enumerator in enumerators {
+            let value = enumerator.value();
+            let is_combination = value > 0 && value & !declared_flags == 0;
+            if !is_single_flag(value) && !is_combination {
+                Diagnostic::new(Lint::NonPowerOfTwoFlag {
+                    enumerator_identifier: enumerator.identifier().to_owned(),
+                    value,
+                })
+                .set_span(enumerator.value_span())
+                .set_scope(enumerator.parser_scoped_identifier())
+                .add_note(
+                    "enumerators in a flags enum should represent a single bit, or a combination of the enum's other enumerators",
+                    None,
+                )
+                .push_into(diagnostics);
+            }
+        }
+    }
+}
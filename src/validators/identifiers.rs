@@ -1,8 +1,10 @@
 // Copyright (c) ZeroC, Inc.
 
 use crate::ast::Ast;
+use crate::backend::IdentifierRules;
 use crate::diagnostics::{Diagnostic, Diagnostics, Error};
 use crate::grammar::*;
+use crate::utils::casing::to_pascal_case;
 use std::collections::HashMap;
 
 pub fn validate_inherited_identifiers(
@@ -51,10 +53,6 @@ struct RedefinitionChecker<'a> {
 
 impl<'a> RedefinitionChecker<'a> {
     fn check_for_redefinitions(&mut self, ast: &'a Ast) {
-        // Stores all the _module-scoped_ Slice definitions we've seen so far.
-        // Keys are the definition's fully-scoped identifiers, and values are references to the definitions themselves.
-        let mut seen_definitions = HashMap::new();
-
         for node in ast.as_slice() {
             // We only check `Entity`s so as to exclude any Slice elements which don't have names (and hence cannot be
             // redefined), and also to exclude modules (which are reopened, not redefined).
@@ -62,20 +60,20 @@ impl<'a> RedefinitionChecker<'a> {
 
             match definition.concrete_entity() {
                 Entities::Struct(struct_def) => {
-                    self.check_if_redefined(struct_def, &mut seen_definitions);
+                    self.check_if_redefined(ast, definition);
                     self.check_contents_for_redefinitions(struct_def.contents());
                 }
                 Entities::Class(class_def) => {
-                    self.check_if_redefined(class_def, &mut seen_definitions);
+                    self.check_if_redefined(ast, definition);
                     self.check_contents_for_redefinitions(class_def.contents());
                 }
                 Entities::Exception(exception_def) => {
-                    self.check_if_redefined(exception_def, &mut seen_definitions);
+                    self.check_if_redefined(ast, definition);
                     self.check_contents_for_redefinitions(exception_def.contents());
                 }
                 Entities::Interface(interface_def) => {
-                    self.check_if_redefined(interface_def, &mut seen_definitions);
-                    self.check_contents_for_redefinitions(interface_def.contents());
+                    self.check_if_redefined(ast, definition);
+                    self.check_for_overloaded_operations(interface_def);
 
                     for operation in interface_def.operations() {
                         self.check_contents_for_redefinitions(operation.parameters());
@@ -83,14 +81,11 @@ impl<'a> RedefinitionChecker<'a> {
                     }
                 }
                 Entities::Enum(enum_def) => {
-                    self.check_if_redefined(enum_def, &mut seen_definitions);
+                    self.check_if_redefined(ast, definition);
                     self.check_contents_for_redefinitions(enum_def.contents());
                 }
-                Entities::CustomType(custom_type) => {
-                    self.check_if_redefined(custom_type, &mut seen_definitions);
-                }
-                Entities::TypeAlias(type_alias) => {
-                    self.check_if_redefined(type_alias, &mut seen_definitions);
+                Entities::CustomType(_) | Entities::TypeAlias(_) | Entities::Constant(_) => {
+                    self.check_if_redefined(ast, definition);
                 }
 
                 // No need to check `Field`, `Enumerator`, `Operation`, or `Parameter`; We just check their containers.
@@ -99,17 +94,65 @@ impl<'a> RedefinitionChecker<'a> {
         }
     }
 
+    /// Checks if the provided _module-scoped_ `definition` is the first definition with its identifier, using the
+    /// AST's definition index. If it isn't, we report a redefinition error against the first definition.
+    fn check_if_redefined(&mut self, ast: &Ast, definition: &dyn Entity) {
+        let definitions = ast.find_definitions(&definition.parser_scoped_identifier());
+
+        // Members can share an identifier with module-scoped definitions (`Foo::S::a` can be both a field, and a
+        // struct in a `Foo::S` module), but they can't collide with each other, so we only check module-scoped ones.
+        let mut module_scoped_definitions = definitions.into_iter().filter(|other| is_module_scoped(*other));
+        if let Some(original) = module_scoped_definitions.next() {
+            if !std::ptr::addr_eq(original, definition) {
+                self.report_redefinition_error(definition, original);
+            }
+        }
+    }
+
     fn check_contents_for_redefinitions<T: NamedSymbol>(&mut self, contents: Vec<&T>) {
         // We create a separate hashmap, so redefinitions are isolated to just the container we're checking.
         let mut seen_definitions = HashMap::new();
         for element in contents {
-            self.check_if_redefined(element, &mut seen_definitions);
+            self.check_if_member_redefined(element, &mut seen_definitions);
+        }
+    }
+
+    /// Operations can't be overloaded, so unlike other containers, we report a dedicated error for redefinitions.
+    fn check_for_overloaded_operations(&mut self, interface_def: &Interface) {
+        let mut seen_operations: HashMap<&str, &Operation> = HashMap::new();
+        for operation in interface_def.operations() {
+            let Some(original) = seen_operations.get(operation.identifier()) else {
+                seen_operations.insert(operation.identifier(), operation);
+                continue;
+            };
+
+            // If the operation has parameters, we suggest a name based on its first one (`getUser(id)` => `getUserWithId`).
+            let explanation = "operations are dispatched by name, so each operation must have a unique identifier";
+            let rename_note = match operation.parameters().first() {
+                Some(parameter) => format!(
+                    "{explanation}; consider renaming one of them, for example to '{}With{}'",
+                    operation.identifier(),
+                    to_pascal_case(parameter.identifier()),
+                ),
+                None => format!("{explanation}; consider renaming one of them"),
+            };
+            Diagnostic::new(Error::OverloadedOperation {
+                interface_identifier: interface_def.identifier().to_owned(),
+                operation_identifier: operation.identifier().to_owned(),
+            })
+            .set_span(operation.raw_identifier().span())
+            .add_note(
+                format!("'{}' was previously defined here", original.identifier()),
+                Some(original.raw_identifier().span()),
+            )
+            .add_note(rename_note, None)
+            .push_into(self.diagnostics);
         }
     }
 
     /// Checks if the provided `definition` already has an entry in the `already_seen` map. If it does, we report a
     /// redefinition error, otherwise, we just add it to the map and return.
-    fn check_if_redefined<'b>(
+    fn check_if_member_redefined<'b>(
         &mut self,
         definition: &'b impl NamedSymbol,
         already_seen: &mut HashMap<String, &'b dyn NamedSymbol>,
@@ -137,3 +180,135 @@ impl<'a> RedefinitionChecker<'a> {
         .push_into(self.diagnostics);
     }
 }
+
+/// Returns true if the provided entity is defined directly in a module (as opposed to being a member of a container).
+fn is_module_scoped(entity: &dyn Entity) -> bool {
+    !matches!(
+        entity.concrete_entity(),
+        Entities::Field(_) | Entities::Enumerator(_) | Entities::Operation(_) | Entities::Parameter(_),
+    )
+}
+
+pub fn check_for_generated_identifier_collisions(
+    ast: &Ast,
+    backend: &str,
+    rules: &IdentifierRules,
+    diagnostics: &mut Diagnostics,
+) {
+    let mut checker = CollisionChecker {
+        backend,
+        rules,
+        diagnostics,
+    };
+    checker.check_for_collisions(ast);
+}
+
+struct CollisionChecker<'a> {
+    backend: &'a str,
+    rules: &'a IdentifierRules<'a>,
+    diagnostics: &'a mut Diagnostics,
+}
+
+impl<'a> CollisionChecker<'a> {
+    fn check_for_collisions(&mut self, ast: &'a Ast) {
+        // Stores the _module-scoped_ Slice definitions we've seen so far.
+        // Keys are the definition's scope, along with its generated identifier (see `generated_key`).
+        let mut seen_definitions = HashMap::new();
+
+        for node in ast.as_slice() {
+            let Ok(definition) = <&dyn Entity>::try_from(node) else { continue };
+
+            match definition.concrete_entity() {
+                Entities::Struct(struct_def) => {
+                    self.check_if_collides(struct_def, &mut seen_definitions);
+                    self.check_contents_for_collisions(struct_def.contents());
+                }
+                Entities::Class(class_def) => {
+                    self.check_if_collides(class_def, &mut seen_definitions);
+                    self.check_contents_for_collisions(class_def.contents());
+                }
+                Entities::Exception(exception_def) => {
+                    self.check_if_collides(exception_def, &mut seen_definitions);
+                    self.check_contents_for_collisions(exception_def.contents());
+                }
+                Entities::Interface(interface_def) => {
+                    self.check_if_collides(interface_def, &mut seen_definitions);
+                    self.check_contents_for_collisions(interface_def.contents());
+
+                    for operation in interface_def.operations() {
+                        self.check_contents_for_collisions(operation.parameters());
+                        self.check_contents_for_collisions(operation.return_members());
+                    }
+                }
+                Entities::Enum(enum_def) => {
+                    self.check_if_collides(enum_def, &mut seen_definitions);
+                    self.check_contents_for_collisions(enum_def.contents());
+                }
+                Entities::CustomType(custom_type) => self.check_if_collides(custom_type, &mut seen_definitions),
+                Entities::TypeAlias(type_alias) => self.check_if_collides(type_alias, &mut seen_definitions),
+                Entities::Constant(constant) => self.check_if_collides(constant, &mut seen_definitions),
+
+                // No need to check `Field`, `Enumerator`, `Operation`, or `Parameter`; We just check their containers.
+                Entities::Field(_) | Entities::Enumerator(_) | Entities::Operation(_) | Entities::Parameter(_) => {}
+            }
+        }
+    }
+
+    fn check_contents_for_collisions<T: NamedSymbol>(&mut self, contents: Vec<&T>) {
+        // We create a separate hashmap, so collisions are isolated to just the container we're checking.
+        let mut seen_definitions = HashMap::new();
+        for element in contents {
+            self.check_if_collides(element, &mut seen_definitions);
+        }
+    }
+
+    fn check_if_collides<'b>(
+        &mut self,
+        definition: &'b impl NamedSymbol,
+        already_seen: &mut HashMap<(String, String), &'b dyn NamedSymbol>,
+    ) {
+        // Definitions only collide with other definitions in the same scope.
+        let scoped_identifier = definition.parser_scoped_identifier();
+        let scope = scoped_identifier.rsplit_once("::").map_or("", |(scope, _)| scope);
+
+        let generated_identifier = self.rules.generated_identifier(definition.identifier());
+        let key = (scope.to_owned(), self.generated_key(&generated_identifier));
+
+        if let Some(other_definition) = already_seen.get(&key) {
+            self.report_collision_error(definition, generated_identifier, *other_definition);
+        } else {
+            already_seen.insert(key, definition);
+        }
+    }
+
+    /// Returns the key used to compare generated identifiers; for case-insensitive languages, case is ignored.
+    fn generated_key(&self, generated_identifier: &str) -> String {
+        match self.rules.case_insensitive {
+            true => generated_identifier.to_lowercase(),
+            false => generated_identifier.to_owned(),
+        }
+    }
+
+    fn report_collision_error(&mut self, new: &dyn NamedSymbol, generated: String, original: &dyn NamedSymbol) {
+        let other_generated = self.rules.generated_identifier(original.identifier());
+        let explanation = match generated == other_generated {
+            true => format!("both identifiers are generated as '{generated}'"),
+            false => format!(
+                "these identifiers are generated as '{other_generated}' and '{generated}', which only differ in case"
+            ),
+        };
+
+        Diagnostic::new(Error::GeneratedIdentifierCollision {
+            identifier: new.identifier().to_owned(),
+            other_identifier: original.identifier().to_owned(),
+            backend: self.backend.to_owned(),
+        })
+        .set_span(new.raw_identifier().span())
+        .add_note(
+            format!("'{}' was previously defined here", original.identifier()),
+            Some(original.raw_identifier().span()),
+        )
+        .add_note(explanation, None)
+        .push_into(self.diagnostics);
+    }
+}
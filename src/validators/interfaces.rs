@@ -0,0 +1,87 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+use std::collections::BTreeMap;
+
+pub fn validate_interface(interface: &Interface, diagnostics: &mut Diagnostics) {
+    inherited_operations_do_not_conflict(interface, diagnostics);
+}
+
+/// With multiple inheritance, it's possible for an interface to inherit multiple operations with the same name from
+/// different bases. This is only allowed if all these operations have the same signature. Otherwise it's ambiguous
+/// which operation the interface actually has, and we report an error listing all the conflicting definitions.
+///
+/// Note that inheriting the same operation multiple times (through a diamond) isn't a conflict, since
+/// [`all_inherited_operations`](Interface::all_inherited_operations) already filters out these duplicates.
+fn inherited_operations_do_not_conflict(interface: &Interface, diagnostics: &mut Diagnostics) {
+    // Operations defined directly on this interface shadow inherited ones, which is already reported as an error.
+    let own_identifiers = interface
+        .operations()
+        .into_iter()
+        .map(|op| op.identifier())
+        .collect::<Vec<_>>();
+
+    // Group the inherited operations by identifier. We use a `BTreeMap` so that errors are reported in a stable order.
+    let mut operations_by_identifier: BTreeMap<&str, Vec<&Operation>> = BTreeMap::new();
+    for operation in interface.all_inherited_operations() {
+        if !own_identifiers.contains(&operation.identifier()) {
+            operations_by_identifier
+                .entry(operation.identifier())
+                .or_default()
+                .push(operation);
+        }
+    }
+
+    for (identifier, operations) in operations_by_identifier {
+        let first_signature = signature_of(operations[0]);
+        if operations.iter().all(|op| signature_of(op) == first_signature) {
+            continue;
+        }
+
+        let mut diagnostic = Diagnostic::new(Error::ConflictingInheritedOperations {
+            interface_identifier: interface.identifier().to_owned(),
+            operation_identifier: identifier.to_owned(),
+        })
+        .set_span(interface.span())
+        .set_scope(interface.parser_scoped_identifier());
+
+        for operation in operations {
+            let message = format!(
+                "'{}' is defined here as '{}'",
+                operation.parser_scoped_identifier(),
+                signature_of(operation),
+            );
+            diagnostic = diagnostic.add_note(message, Some(operation.span()));
+        }
+        diagnostic.push_into(diagnostics);
+    }
+}
+
+/// Returns a string representation of an operation's signature, suitable for comparing operations with.
+/// Ex: `idempotent op(tag(1) a: int32?, b: stream string) -> bool`.
+fn signature_of(operation: &Operation) -> String {
+    let format_members = |members: Vec<&Parameter>| {
+        members
+            .into_iter()
+            .map(|member| {
+                let tag = member.tag().map_or(String::new(), |tag| format!("tag({tag}) "));
+                let stream = if member.is_streamed { "stream " } else { "" };
+                format!(
+                    "{tag}{}: {stream}{}",
+                    member.identifier(),
+                    member.data_type.type_string()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let idempotent = if operation.is_idempotent { "idempotent " } else { "" };
+    let parameters = format_members(operation.parameters());
+    let return_type = match operation.return_members().len() {
+        0 => String::new(),
+        _ => format!(" -> ({})", format_members(operation.return_members())),
+    };
+    format!("{idempotent}{}({parameters}){return_type}", operation.identifier())
+}
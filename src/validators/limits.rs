@@ -0,0 +1,104 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::ast::node::Node;
+use crate::compilation_state::CompilationState;
+use crate::diagnostics::{Diagnostic, Diagnostics, Lint};
+use crate::grammar::*;
+use crate::slice_options::SliceOptions;
+
+/// Checks the AST against any of the (opt-in) ergonomic limits that the user configured.
+/// These limits don't affect whether Slice definitions are valid, but help keep the generated APIs usable.
+pub(crate) fn check_limits(compilation_state: &mut CompilationState, options: &SliceOptions) {
+    let diagnostics = &mut compilation_state.diagnostics;
+
+    for node in compilation_state.ast.as_slice() {
+        match node {
+            Node::Struct(struct_ptr) => {
+                if let Some(max) = options.max_compact_struct_fields {
+                    check_compact_struct_field_count(struct_ptr.borrow(), max, diagnostics);
+                }
+            }
+            Node::Operation(operation_ptr) => {
+                if let Some(max) = options.max_operation_parameters {
+                    check_operation_parameter_count(operation_ptr.borrow(), max, diagnostics);
+                }
+            }
+            Node::Field(field_ptr) => {
+                if let Some(max) = options.max_type_nesting_depth {
+                    let field = field_ptr.borrow();
+                    check_type_nesting_depth(field.data_type(), field, max, diagnostics);
+                }
+            }
+            Node::Parameter(parameter_ptr) => {
+                if let Some(max) = options.max_type_nesting_depth {
+                    let parameter = parameter_ptr.borrow();
+                    check_type_nesting_depth(parameter.data_type(), parameter, max, diagnostics);
+                }
+            }
+            Node::TypeAlias(type_alias_ptr) => {
+                if let Some(max) = options.max_type_nesting_depth {
+                    let type_alias = type_alias_ptr.borrow();
+                    check_type_nesting_depth(&type_alias.underlying, type_alias, max, diagnostics);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_compact_struct_field_count(struct_def: &Struct, max: usize, diagnostics: &mut Diagnostics) {
+    let field_count = struct_def.fields.len();
+    if struct_def.is_compact && field_count > max {
+        Diagnostic::new(Lint::CompactStructTooLarge {
+            struct_identifier: struct_def.identifier().to_owned(),
+            field_count,
+            max,
+        })
+        .set_span(struct_def.span())
+        .set_scope(struct_def.parser_scoped_identifier())
+        .push_into(diagnostics);
+    }
+}
+
+fn check_operation_parameter_count(operation: &Operation, max: usize, diagnostics: &mut Diagnostics) {
+    let parameter_count = operation.parameters.len();
+    if parameter_count > max {
+        Diagnostic::new(Lint::TooManyParameters {
+            operation_identifier: operation.identifier().to_owned(),
+            parameter_count,
+            max,
+        })
+        .set_span(operation.span())
+        .set_scope(operation.parser_scoped_identifier())
+        .push_into(diagnostics);
+    }
+}
+
+fn check_type_nesting_depth(type_ref: &TypeRef, user: &dyn Entity, max: usize, diagnostics: &mut Diagnostics) {
+    let depth = nesting_depth_of(type_ref);
+    if depth > max {
+        Diagnostic::new(Lint::TypeNestingTooDeep { depth, max })
+            .set_span(type_ref.span())
+            .set_scope(user.parser_scoped_identifier())
+            .push_into(diagnostics);
+    }
+}
+
+/// Returns how many levels of anonymous types (results, sequences, and dictionaries) are nested in the provided type.
+/// Ex: `int32` has a depth of 0, `Sequence<int32>` has a depth of 1, and `Sequence<Dictionary<K, V>>` has depth 2.
+fn nesting_depth_of(type_ref: &TypeRef) -> usize {
+    match type_ref.concrete_type() {
+        Types::ResultType(result_type) => {
+            let success_depth = nesting_depth_of(&result_type.success_type);
+            let failure_depth = nesting_depth_of(&result_type.failure_type);
+            1 + success_depth.max(failure_depth)
+        }
+        Types::Sequence(sequence) => 1 + nesting_depth_of(&sequence.element_type),
+        Types::Dictionary(dictionary) => {
+            let key_depth = nesting_depth_of(&dictionary.key_type);
+            let value_depth = nesting_depth_of(&dictionary.value_type);
+            1 + key_depth.max(value_depth)
+        }
+        _ => 0,
+    }
+}
@@ -2,32 +2,43 @@
 
 mod attribute;
 mod comments;
+mod custom_types;
 mod cycle_detection;
 mod dictionary;
 mod enums;
 mod identifiers;
+mod interfaces;
+mod limits;
 mod members;
 mod operations;
-mod parameters;
+mod streams;
 mod structs;
 mod type_aliases;
+mod visibility;
 
 use crate::compilation_state::CompilationState;
 use crate::diagnostics::Diagnostics;
 use crate::grammar::*;
 use crate::slice_file::SliceFile;
-use crate::visitor::Visitor;
+use crate::utils::ptr_util::WeakPtr;
+use crate::visitor::{VisitControl, Visitor};
 
-use attribute::validate_attributes;
+pub(crate) use custom_types::check_for_missing_custom_type_mappings;
+pub(crate) use identifiers::check_for_generated_identifier_collisions;
+pub(crate) use limits::check_limits;
+
+use attribute::{validate_attribute_list, validate_attributes};
 use comments::validate_common_doc_comments;
 use dictionary::validate_dictionary;
 use enums::validate_enum;
 use identifiers::validate_inherited_identifiers;
+use interfaces::validate_interface;
 use members::validate_members;
 use operations::validate_operation;
-use parameters::validate_parameters;
+use streams::validate_streamed_members;
 use structs::validate_struct;
 use type_aliases::validate_type_alias;
+use visibility::validate_exposed_types;
 
 pub(crate) fn validate_ast(compilation_state: &mut CompilationState) {
     let diagnostics = &mut compilation_state.diagnostics;
@@ -46,6 +57,7 @@ pub(crate) fn validate_ast(compilation_state: &mut CompilationState) {
 
     let mut validator = ValidatorVisitor::new(diagnostics);
     for slice_file in &compilation_state.files {
+        let _span = tracing::debug_span!("file", path = slice_file.relative_path).entered();
         slice_file.visit_with(&mut validator);
     }
 }
@@ -61,63 +73,83 @@ impl<'a> ValidatorVisitor<'a> {
 }
 
 impl<'a> Visitor for ValidatorVisitor<'a> {
-    fn visit_file(&mut self, slice_file: &SliceFile) {
+    fn visit_file(&mut self, slice_file: &SliceFile) -> VisitControl {
         validate_attributes(slice_file, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_module(&mut self, module_def: &Module) {
+    fn visit_module(&mut self, module_def: &Module) -> VisitControl {
         validate_attributes(module_def, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_class(&mut self, class: &Class) {
+    fn visit_class(&mut self, class: &Class) -> VisitControl {
         validate_common_doc_comments(class, self.diagnostics);
         validate_attributes(class, self.diagnostics);
+        validate_exposed_types(class, self.diagnostics);
 
         validate_members(class.fields(), self.diagnostics);
 
         validate_inherited_identifiers(class.fields(), class.all_inherited_fields(), self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_enum(&mut self, enum_def: &Enum) {
+    fn visit_enum(&mut self, enum_def: &Enum) -> VisitControl {
         validate_common_doc_comments(enum_def, self.diagnostics);
         validate_attributes(enum_def, self.diagnostics);
+        validate_exposed_types(enum_def, self.diagnostics);
 
         validate_enum(enum_def, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_custom_type(&mut self, custom_type: &CustomType) {
+    fn visit_custom_type(&mut self, custom_type: &CustomType) -> VisitControl {
         validate_common_doc_comments(custom_type, self.diagnostics);
         validate_attributes(custom_type, self.diagnostics);
+        VisitControl::Continue
+    }
+
+    fn visit_constant(&mut self, constant: &Constant) -> VisitControl {
+        validate_common_doc_comments(constant, self.diagnostics);
+        validate_attributes(constant, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_enumerator(&mut self, enumerator: &Enumerator) {
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) -> VisitControl {
         validate_common_doc_comments(enumerator, self.diagnostics);
         validate_attributes(enumerator, self.diagnostics);
 
         validate_members(enumerator.contents(), self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_exception(&mut self, exception: &Exception) {
+    fn visit_exception(&mut self, exception: &Exception) -> VisitControl {
         validate_common_doc_comments(exception, self.diagnostics);
         validate_attributes(exception, self.diagnostics);
+        validate_exposed_types(exception, self.diagnostics);
 
         validate_members(exception.fields(), self.diagnostics);
 
         validate_inherited_identifiers(exception.fields(), exception.all_inherited_fields(), self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_interface(&mut self, interface: &Interface) {
+    fn visit_interface(&mut self, interface: &Interface) -> VisitControl {
         validate_common_doc_comments(interface, self.diagnostics);
         validate_attributes(interface, self.diagnostics);
+        validate_exposed_types(interface, self.diagnostics);
 
         validate_inherited_identifiers(
             interface.operations(),
             interface.all_inherited_operations(),
             self.diagnostics,
         );
+
+        validate_interface(interface, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_operation(&mut self, operation: &Operation) {
+    fn visit_operation(&mut self, operation: &Operation) -> VisitControl {
         validate_common_doc_comments(operation, self.diagnostics);
         validate_attributes(operation, self.diagnostics);
 
@@ -126,40 +158,49 @@ impl<'a> Visitor for ValidatorVisitor<'a> {
         validate_members(operation.parameters(), self.diagnostics);
         validate_members(operation.return_members(), self.diagnostics);
 
-        validate_parameters(&operation.parameters(), self.diagnostics);
-        validate_parameters(&operation.return_members(), self.diagnostics);
+        validate_streamed_members(operation, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_parameter(&mut self, parameter: &Parameter) {
+    fn visit_parameter(&mut self, parameter: &Parameter) -> VisitControl {
         validate_attributes(parameter, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_struct(&mut self, struct_def: &Struct) {
+    fn visit_struct(&mut self, struct_def: &Struct) -> VisitControl {
         validate_common_doc_comments(struct_def, self.diagnostics);
         validate_attributes(struct_def, self.diagnostics);
+        validate_exposed_types(struct_def, self.diagnostics);
 
         validate_struct(struct_def, self.diagnostics);
 
         validate_members(struct_def.fields(), self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_field(&mut self, field: &Field) {
+    fn visit_field(&mut self, field: &Field) -> VisitControl {
         validate_common_doc_comments(field, self.diagnostics);
         validate_attributes(field, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_type_alias(&mut self, type_alias: &TypeAlias) {
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> VisitControl {
         validate_common_doc_comments(type_alias, self.diagnostics);
         validate_attributes(type_alias, self.diagnostics);
+        validate_exposed_types(type_alias, self.diagnostics);
 
         validate_type_alias(type_alias, self.diagnostics);
+        VisitControl::Continue
     }
 
-    fn visit_type_ref(&mut self, type_ref: &TypeRef) {
-        validate_attributes(type_ref, self.diagnostics);
+    fn visit_type_ref(&mut self, type_ref: &TypeRef) -> VisitControl {
+        // Inherited attributes are validated where they're applied, so we only validate those applied to the type-ref.
+        let applied_attributes = type_ref.attributes.iter().map(WeakPtr::borrow).collect();
+        validate_attribute_list(applied_attributes, type_ref.concrete_attributable(), self.diagnostics);
 
         if let Types::Dictionary(dictionary) = type_ref.concrete_type() {
             validate_dictionary(dictionary, self.diagnostics);
         }
+        VisitControl::Continue
     }
 }
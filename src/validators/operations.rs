@@ -5,7 +5,7 @@ use crate::grammar::*;
 
 pub fn validate_operation(operation: &Operation, diagnostics: &mut Diagnostics) {
     exception_specifications_can_only_be_used_in_slice1_mode(operation, diagnostics);
-    if let Some(comment) = operation.comment() {
+    for comment in operation.comment().into_iter().flat_map(DocComment::with_localizations) {
         validate_param_tags(comment, operation, diagnostics);
         validate_returns_tags(comment, operation, diagnostics);
         validate_throws_tags(comment, operation, diagnostics);
@@ -13,16 +13,26 @@ pub fn validate_operation(operation: &Operation, diagnostics: &mut Diagnostics)
 }
 
 fn exception_specifications_can_only_be_used_in_slice1_mode(operation: &Operation, diagnostics: &mut Diagnostics) {
-    if operation.encoding != CompilationMode::Slice1 && !operation.exception_specification.is_empty() {
-        // Create a span that covers the entire exception specification.
-        let mut span = operation.exception_specification.first().unwrap().span().clone();
-        span.end = operation.exception_specification.last().unwrap().span().end;
-
-        Diagnostic::new(Error::ExceptionSpecificationNotSupported)
-            .set_span(&span)
-            .set_scope(operation.parser_scoped_identifier())
-            .push_into(diagnostics);
+    if operation.encoding == CompilationMode::Slice1 {
+        return;
     }
+
+    // Create a span that covers the entire exception specification.
+    let span = match &operation.throws_any_exception {
+        Some(span) => span.clone(),
+        None => {
+            let exceptions = &operation.exception_specification;
+            let (Some(first), Some(last)) = (exceptions.first(), exceptions.last()) else { return };
+            let mut span = first.span().clone();
+            span.end = last.span().end;
+            span
+        }
+    };
+
+    Diagnostic::new(Error::ExceptionSpecificationNotSupported)
+        .set_span(&span)
+        .set_scope(operation.parser_scoped_identifier())
+        .push_into(diagnostics);
 }
 
 fn validate_param_tags(comment: &DocComment, operation: &Operation, diagnostics: &mut Diagnostics) {
@@ -130,13 +140,25 @@ fn validate_returns_tags_for_operation_with_return_tuple(
 
 fn validate_throws_tags(comment: &DocComment, operation: &Operation, diagnostics: &mut Diagnostics) {
     let throws_tags = &comment.throws;
-    if operation.exception_specification.is_empty() {
+    match operation.throws() {
         // If the operation doesn't throw, but its doc comment has 'throws' tags, report an error.
-        validate_throws_tags_for_operation_with_no_throws_clause(throws_tags, operation, diagnostics);
-    } else {
+        Throws::Nothing => {
+            validate_throws_tags_for_operation_with_no_throws_clause(throws_tags, operation, diagnostics)
+        }
+
         // If the operation can throw exceptions, ensure that its 'throws' tags agree with them.
-        let thrown_exceptions = &operation.exception_specification;
-        validate_throws_tags_for_operation_with_throws_clause(throws_tags, operation, thrown_exceptions, diagnostics);
+        Throws::Specific(_) => {
+            let thrown_exceptions = &operation.exception_specification;
+            validate_throws_tags_for_operation_with_throws_clause(
+                throws_tags,
+                operation,
+                thrown_exceptions,
+                diagnostics,
+            );
+        }
+
+        // If the operation can throw any exception, its 'throws' tags can be for any exception.
+        Throws::AnyException => {}
     }
 }
 
@@ -0,0 +1,79 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+
+pub fn validate_streamed_members(operation: &Operation, diagnostics: &mut Diagnostics) {
+    // Streams are a Slice2 only feature. If we're in Slice1 mode, there's no point in checking anything else.
+    if operation.encoding == CompilationMode::Slice1 {
+        streams_are_not_supported_in_slice1_mode(operation, diagnostics);
+        return;
+    }
+
+    for (members, kind) in [
+        (operation.parameters(), "parameter"),
+        (operation.return_members(), "return member"),
+    ] {
+        streamed_member_is_last(&members, kind, diagnostics);
+        at_most_one_streamed_member(&members, diagnostics);
+        streamed_members_are_not_tagged(&members, diagnostics);
+    }
+}
+
+fn streams_are_not_supported_in_slice1_mode(operation: &Operation, diagnostics: &mut Diagnostics) {
+    for member in operation.parameters_and_return_members() {
+        if member.is_streamed {
+            Diagnostic::new(Error::StreamedParametersNotSupported)
+                .set_span(member.span())
+                .set_scope(member.parser_scoped_identifier())
+                .add_note("streams can only be used in Slice2 mode", None)
+                .push_into(diagnostics);
+        }
+    }
+}
+
+fn at_most_one_streamed_member(members: &[&Parameter], diagnostics: &mut Diagnostics) {
+    // We only report errors for the extra streamed members, and point back to the last one, which is allowed.
+    let streamed_members = members.iter().filter(|member| member.is_streamed).collect::<Vec<_>>();
+    if let Some((last, extras)) = streamed_members.split_last() {
+        for member in extras {
+            Diagnostic::new(Error::MultipleStreamedMembers)
+                .set_span(member.span())
+                .set_scope(member.parser_scoped_identifier())
+                .add_note(
+                    format!("'{}' is already streamed", last.identifier()),
+                    Some(last.span()),
+                )
+                .push_into(diagnostics);
+        }
+    }
+}
+
+fn streamed_member_is_last(members: &[&Parameter], kind: &str, diagnostics: &mut Diagnostics) {
+    // Returns None if members is empty.
+    let Some((last, remaining)) = members.split_last() else { return };
+
+    for member in remaining.iter().filter(|member| member.is_streamed) {
+        Diagnostic::new(Error::StreamedMembersMustBeLast {
+            parameter_identifier: member.identifier().to_owned(),
+        })
+        .set_span(member.span())
+        .set_scope(member.parser_scoped_identifier())
+        .add_note(format!("the last {kind} is '{}'", last.identifier()), Some(last.span()))
+        .push_into(diagnostics);
+    }
+}
+
+fn streamed_members_are_not_tagged(members: &[&Parameter], diagnostics: &mut Diagnostics) {
+    // Note that streamed members can be optional, since a stream's elements can be optional in Slice2.
+    for member in members.iter().filter(|member| member.is_streamed && member.is_tagged()) {
+        let tag_span = member.raw_tag().unwrap().span();
+        Diagnostic::new(Error::StreamedMembersCannotBeTagged {
+            parameter_identifier: member.identifier().to_owned(),
+        })
+        .set_span(member.span())
+        .set_scope(member.parser_scoped_identifier())
+        .add_note("try removing this tag", Some(tag_span))
+        .push_into(diagnostics);
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::attributes::Internal;
+use crate::grammar::*;
+
+/// Reports an error for each internal type used in the signature of the provided entity, if it's public.
+/// The signature of an entity is: the types of its fields, its base types, and the types used by its operations.
+pub fn validate_exposed_types(entity: &dyn Entity, diagnostics: &mut Diagnostics) {
+    if entity.visibility() == Visibility::Internal || entity.is_synthesized() {
+        return;
+    }
+
+    let mut internal_types = Vec::new();
+    match entity.concrete_entity() {
+        Entities::Struct(struct_def) => fields_of(struct_def.fields(), &mut internal_types),
+        Entities::Class(class_def) => {
+            internal_types.extend(
+                class_def
+                    .base
+                    .as_ref()
+                    .map(|base| internal_entity(base.definition(), base)),
+            );
+            fields_of(class_def.fields(), &mut internal_types);
+        }
+        Entities::Exception(exception_def) => {
+            internal_types.extend(
+                exception_def
+                    .base
+                    .as_ref()
+                    .map(|base| internal_entity(base.definition(), base)),
+            );
+            fields_of(exception_def.fields(), &mut internal_types);
+        }
+        Entities::Interface(interface_def) => {
+            for base in &interface_def.bases {
+                internal_types.push(internal_entity(base.definition(), base));
+            }
+            for operation in interface_def.operations() {
+                for member in operation.parameters_and_return_members() {
+                    internal_types_in(&member.data_type, &mut internal_types);
+                }
+                for exception_ref in &operation.exception_specification {
+                    internal_types.push(internal_entity(exception_ref.definition(), exception_ref));
+                }
+            }
+        }
+        Entities::Enum(enum_def) => {
+            for enumerator in enum_def.enumerators() {
+                fields_of(enumerator.fields(), &mut internal_types);
+            }
+        }
+        Entities::TypeAlias(type_alias) => internal_types_in(&type_alias.underlying, &mut internal_types),
+        _ => {}
+    }
+
+    for (internal_type, type_ref) in internal_types.into_iter().flatten() {
+        let mut diagnostic = Diagnostic::new(Error::InternalTypeExposed {
+            kind: entity.kind(),
+            identifier: entity.identifier().to_owned(),
+            type_identifier: internal_type.identifier().to_owned(),
+        })
+        .set_span(type_ref.span());
+
+        let attribute = internal_type
+            .attributes()
+            .into_iter()
+            .find(|attribute| attribute.downcast::<Internal>().is_some());
+        if let Some(attribute) = attribute {
+            let message = format!("'{}' was marked internal here", internal_type.identifier());
+            diagnostic = diagnostic.add_note(message, Some(attribute.span()));
+        }
+        diagnostic.push_into(diagnostics);
+    }
+}
+
+type InternalType<'a> = Option<(&'a dyn Entity, &'a dyn Symbol)>;
+
+fn fields_of<'a>(fields: Vec<&'a Field>, internal_types: &mut Vec<InternalType<'a>>) {
+    for field in fields {
+        internal_types_in(&field.data_type, internal_types);
+    }
+}
+
+/// Returns the provided entity (and the type reference to it) if it's internal.
+fn internal_entity<'a>(entity: &'a dyn Entity, type_ref: &'a dyn Symbol) -> InternalType<'a> {
+    (entity.visibility() == Visibility::Internal).then_some((entity, type_ref))
+}
+
+/// Collects any internal types used by the provided type reference, including through anonymous types.
+/// Type aliases aren't looked through, since their underlying types are checked where they're defined.
+fn internal_types_in<'a>(type_ref: &'a TypeRef, internal_types: &mut Vec<InternalType<'a>>) {
+    if let Some(type_alias) = type_ref.type_aliases().first() {
+        internal_types.push(internal_entity(*type_alias, type_ref));
+        return;
+    }
+
+    match type_ref.concrete_type() {
+        // Tuples are synthesized into structs, which share the visibility of wherever they're used.
+        Types::Struct(struct_def) if struct_def.is_synthesized => fields_of(struct_def.fields(), internal_types),
+        Types::Struct(struct_def) => internal_types.push(internal_entity(struct_def, type_ref)),
+        Types::Class(class_def) => internal_types.push(internal_entity(class_def, type_ref)),
+        Types::Enum(enum_def) => internal_types.push(internal_entity(enum_def, type_ref)),
+        Types::CustomType(custom_type) => internal_types.push(internal_entity(custom_type, type_ref)),
+        Types::ResultType(result_type) => {
+            internal_types_in(&result_type.success_type, internal_types);
+            internal_types_in(&result_type.failure_type, internal_types);
+        }
+        Types::Sequence(sequence) => internal_types_in(&sequence.element_type, internal_types),
+        Types::Dictionary(dictionary) => {
+            internal_types_in(&dictionary.key_type, internal_types);
+            internal_types_in(&dictionary.value_type, internal_types);
+        }
+        Types::Primitive(_) => {}
+    }
+}
@@ -3,6 +3,46 @@
 use crate::grammar::*;
 use crate::slice_file::SliceFile;
 
+/// Returned by each of the [Visitor]'s methods, to control how visitation continues after visiting an element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VisitControl {
+    /// Continue visiting, including the contents of the element that was just visited.
+    #[default]
+    Continue,
+
+    /// Continue visiting, but skip the contents of the element that was just visited.
+    /// For elements without any contents, this is the same as [VisitControl::Continue].
+    SkipChildren,
+
+    /// Stop visiting. No more `visit_x` methods are called, and every `visit_with` call returns immediately.
+    Stop,
+}
+
+impl VisitControl {
+    /// Calls `visit_children` if this is [VisitControl::Continue], and returns whether visitation should stop.
+    /// The returned value is only ever [VisitControl::Continue] or [VisitControl::Stop].
+    fn then_visit(self, visit_children: impl FnOnce() -> VisitControl) -> VisitControl {
+        match self {
+            VisitControl::Continue => visit_children(),
+            VisitControl::SkipChildren => VisitControl::Continue,
+            VisitControl::Stop => VisitControl::Stop,
+        }
+    }
+}
+
+/// Visits each of the provided elements in order, stopping early if any of them return [VisitControl::Stop].
+fn visit_each<'a, T: 'a>(
+    elements: impl IntoIterator<Item = &'a T>,
+    mut visit: impl FnMut(&'a T) -> VisitControl,
+) -> VisitControl {
+    for element in elements {
+        if visit(element) == VisitControl::Stop {
+            return VisitControl::Stop;
+        }
+    }
+    VisitControl::Continue
+}
+
 /// The `Visitor` trait is used to recursively visit through a tree of slice elements.
 /// It automatically traverses through the tree, calling the various `visit_x` methods as applicable.
 ///
@@ -19,85 +59,125 @@ use crate::slice_file::SliceFile;
 /// - visit_interface
 ///     - visit_operation
 ///         - visit_parameter (called once per parameter, in the order they're listed)
+///
+/// Each method returns a [VisitControl], which controls how visitation continues: whether the element's contents are
+/// visited, or whether visitation stops altogether. To only visit some of the definitions in a set of files, see
+/// [visit_definitions], [visit_types], and [visit_module_subtree]. To visit entities along with their parents, or
+/// after their contents (post-order), see [EntityVisitor].
 #[allow(unused_variables)] // Keep parameter names for doc generation, even if not used in the default implementations.
 pub trait Visitor {
     /// This function is called by the visitor when it begins visiting a slice file,
     /// before it visits through the file's contents.
     ///
     /// This shouldn't be called by users. To visit a slice file, use `[SliceFile::visit_with]`.
-    fn visit_file(&mut self, slice_file: &SliceFile) {}
+    fn visit_file(&mut self, slice_file: &SliceFile) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [Module],
     ///
     /// This shouldn't be called by users. To visit a module, use `[Module::visit_with]`.
-    fn visit_module(&mut self, module_def: &Module) {}
+    fn visit_module(&mut self, module_def: &Module) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting a [Struct],
     /// before it visits through the struct's contents.
     ///
     /// This shouldn't be called by users. To visit a struct, use `[Struct::visit_with]`.
-    fn visit_struct(&mut self, struct_def: &Struct) {}
+    fn visit_struct(&mut self, struct_def: &Struct) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting a [Class],
     /// before it visits through the class' contents.
     ///
     /// This shouldn't be called by users. To visit a class, use `[Class::visit_with]`.
-    fn visit_class(&mut self, class_def: &Class) {}
+    fn visit_class(&mut self, class_def: &Class) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting an [Exception],
     /// before it visits through the exception's contents.
     ///
     /// This shouldn't be called by users. To visit an exception, use `[Exception::visit_with]`.
-    fn visit_exception(&mut self, exception_def: &Exception) {}
+    fn visit_exception(&mut self, exception_def: &Exception) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting an [Interface],
     /// before it visits through the interface's contents.
     ///
     /// This shouldn't be called by users. To visit an interface, use `[Interface::visit_with]`.
-    fn visit_interface(&mut self, interface_def: &Interface) {}
+    fn visit_interface(&mut self, interface_def: &Interface) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting an [Enum],
     /// before it visits through the enum's contents.
     ///
     /// This shouldn't be called by users. To visit an enum, use `[Enum::visit_with]`.
-    fn visit_enum(&mut self, enum_def: &Enum) {}
+    fn visit_enum(&mut self, enum_def: &Enum) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it begins visiting an [Operation],
     /// before it visits through the operation's contents.
     ///
     /// This shouldn't be called by users. To visit an operation, use `[Operation::visit_with]`.
-    fn visit_operation(&mut self, operation: &Operation) {}
+    fn visit_operation(&mut self, operation: &Operation) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [CustomType],
     ///
     /// This shouldn't be called by users. To visit a custom type, use `[CustomType::visit_with]`.
-    fn visit_custom_type(&mut self, custom_type: &CustomType) {}
+    fn visit_custom_type(&mut self, custom_type: &CustomType) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [TypeAlias],
     ///
     /// This shouldn't be called by users. To visit a type alias, use `[TypeAlias::visit_with]`.
-    fn visit_type_alias(&mut self, type_alias: &TypeAlias) {}
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    /// This function is called by the visitor when it visits a [Constant],
+    ///
+    /// This shouldn't be called by users. To visit a constant, use `[Constant::visit_with]`.
+    fn visit_constant(&mut self, constant: &Constant) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [Field],
     ///
     /// This shouldn't be called by users. To visit a field, use `[Field::visit_with]`.
-    fn visit_field(&mut self, field: &Field) {}
+    fn visit_field(&mut self, field: &Field) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [Parameter],
     ///
     /// This shouldn't be called by users. To visit a parameter, use `[Parameter::visit_with]`.
-    fn visit_parameter(&mut self, parameter: &Parameter) {}
+    fn visit_parameter(&mut self, parameter: &Parameter) -> VisitControl {
+        VisitControl::Continue
+    }
 
     /// This function is called by the visitor when it visits a [Enumerator],
     ///
     /// This shouldn't be called by users. To visit an enumerator, use `[Enumerator::visit_with]`.
-    fn visit_enumerator(&mut self, enumerator: &Enumerator) {}
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) -> VisitControl {
+        VisitControl::Continue
+    }
 
     // TODO: This can probably be improved after splitting `TypeRef`. See https://github.com/icerpc/slicec/issues/452.
     /// This function is called by the visitor when it visits a [TypeRef].
     ///
     /// This shouldn't be called by users. To visit a type reference, use `[TypeRef::visit_with]`.
-    fn visit_type_ref(&mut self, type_ref: &TypeRef) {}
+    fn visit_type_ref(&mut self, type_ref: &TypeRef) -> VisitControl {
+        VisitControl::Continue
+    }
 }
 
 impl SliceFile {
@@ -105,24 +185,186 @@ impl SliceFile {
     ///
     /// This function first calls `visitor.visit_file`, then if the file contains a module declaration it calls
     /// `visitor.visit_module`, and finally it recursively visits any definitions defined in the file.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_file(self);
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        crate::panic_handler::set_file(&self.relative_path);
+        visitor.visit_file(self).then_visit(|| {
+            if let Some(module_def) = &self.module {
+                if module_def.borrow().visit_with(visitor) == VisitControl::Stop {
+                    return VisitControl::Stop;
+                }
+            }
+            visit_each(&self.contents, |definition| visit_definition(definition, visitor))
+        })
+    }
+}
 
-        if let Some(module_def) = &self.module {
-            module_def.borrow().visit_with(visitor);
-        }
+/// Visits the provided definition with `visitor`, by calling `visit_with` on the element it wraps.
+fn visit_definition(definition: &Definition, visitor: &mut impl Visitor) -> VisitControl {
+    crate::panic_handler::set_entity(definition.borrow().parser_scoped_identifier());
+    match definition {
+        Definition::Struct(struct_def) => struct_def.borrow().visit_with(visitor),
+        Definition::Class(class_def) => class_def.borrow().visit_with(visitor),
+        Definition::Exception(exception_def) => exception_def.borrow().visit_with(visitor),
+        Definition::Interface(interface_def) => interface_def.borrow().visit_with(visitor),
+        Definition::Enum(enum_def) => enum_def.borrow().visit_with(visitor),
+        Definition::CustomType(custom_type) => custom_type.borrow().visit_with(visitor),
+        Definition::TypeAlias(type_alias) => type_alias.borrow().visit_with(visitor),
+        Definition::Constant(constant) => constant.borrow().visit_with(visitor),
+    }
+}
+
+/// Visits the definitions in the provided files which match `filter` (along with their contents), in the order they
+/// were defined. Files and modules aren't visited, so `visit_file` and `visit_module` are never called.
+///
+/// Only top-level definitions are passed to `filter`; the contents of a definition are visited if it matches.
+/// For example, a backend generating code for a single interface can visit only that interface with:
+/// ```
+/// # use slicec::grammar::*;
+/// # use slicec::visitor::{visit_definitions, Visitor};
+/// # struct OperationCounter(usize);
+/// # impl Visitor for OperationCounter {}
+/// # let state = slicec::compile_from_strings(&["module M\ninterface I { op() }"], None, |_| {}, |_| {});
+/// let mut visitor = OperationCounter(0);
+/// visit_definitions(&state.files, |e| e.parser_scoped_identifier() == "M::I", &mut visitor);
+/// ```
+pub fn visit_definitions(
+    files: &[SliceFile],
+    filter: impl Fn(&dyn Entity) -> bool,
+    visitor: &mut impl Visitor,
+) -> VisitControl {
+    visit_each(files, |slice_file| {
+        crate::panic_handler::set_file(&slice_file.relative_path);
+        let definitions = slice_file
+            .contents
+            .iter()
+            .filter(|definition| filter(definition.borrow()));
+        visit_each(definitions, |definition| visit_definition(definition, visitor))
+    })
+}
+
+/// Visits the type definitions in the provided files (structs, classes, enums, and custom types), along with their
+/// contents. Any other definitions, like interfaces and constants, are skipped. See [visit_definitions].
+pub fn visit_types(files: &[SliceFile], visitor: &mut impl Visitor) -> VisitControl {
+    let is_type = |entity: &dyn Entity| {
+        matches!(
+            entity.concrete_entity(),
+            Entities::Struct(_) | Entities::Class(_) | Entities::Enum(_) | Entities::CustomType(_)
+        )
+    };
+    visit_definitions(files, is_type, visitor)
+}
 
-        for definition in &self.contents {
-            match definition {
-                Definition::Struct(struct_def) => struct_def.borrow().visit_with(visitor),
-                Definition::Class(class_def) => class_def.borrow().visit_with(visitor),
-                Definition::Exception(exception_def) => exception_def.borrow().visit_with(visitor),
-                Definition::Interface(interface_def) => interface_def.borrow().visit_with(visitor),
-                Definition::Enum(enum_def) => enum_def.borrow().visit_with(visitor),
-                Definition::CustomType(custom_type) => custom_type.borrow().visit_with(visitor),
-                Definition::TypeAlias(type_alias) => type_alias.borrow().visit_with(visitor),
+/// Visits the files whose module is the specified module, or is nested within it, with the provided `visitor`.
+/// `module_identifier` is the fully scoped identifier of the module, without a leading '::'. Files are visited like
+/// they are by [SliceFile::visit_with], including calling `visit_file` and `visit_module`.
+pub fn visit_module_subtree(files: &[SliceFile], module_identifier: &str, visitor: &mut impl Visitor) -> VisitControl {
+    let is_in_subtree = |slice_file: &&SliceFile| {
+        slice_file.module.as_ref().is_some_and(|module_def| {
+            let identifier = module_def.borrow().nested_module_identifier();
+            identifier == module_identifier
+                || identifier
+                    .strip_prefix(module_identifier)
+                    .is_some_and(|rest| rest.starts_with("::"))
+        })
+    };
+    visit_each(files.iter().filter(is_in_subtree), |slice_file| {
+        slice_file.visit_with(visitor)
+    })
+}
+
+/// The `EntityVisitor` trait is used to visit the entities in a tree of slice elements, along with the chain of
+/// entities containing each one. Unlike [Visitor], it has a single pair of methods for all kinds of entities, which
+/// are called both before and after an entity's contents are visited.
+///
+/// - [enter](EntityVisitor::enter) is called before an entity's contents are visited (pre-order).
+/// - [leave](EntityVisitor::leave) is called after an entity's contents are visited (post-order).
+///
+/// Both are passed the entity's parents, from outermost to innermost; so an entity's depth is `parents.len()`.
+/// Types and modules aren't entities, so they aren't visited. To visit entities, use [walk] or [walk_entity].
+///
+/// For example, calling [walk_entity] on an interface containing only a single operation would invoke:
+/// - enter(interface, \[\])
+///     - enter(operation, \[interface\])
+///         - enter(parameter, \[interface, operation\]), then leave(parameter, \[interface, operation\])
+///     - leave(operation, \[interface\])
+/// - leave(interface, \[\])
+#[allow(unused_variables)] // Keep parameter names for doc generation, even if not used in the default implementations.
+pub trait EntityVisitor {
+    /// This function is called before the entity's contents are visited. If it returns [VisitControl::SkipChildren],
+    /// the entity's contents aren't visited, but `leave` is still called for it.
+    fn enter(&mut self, entity: &dyn Entity, parents: &[&dyn Entity]) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    /// This function is called after the entity's contents have been visited.
+    /// If visitation was stopped while visiting the entity or its contents, this isn't called.
+    fn leave(&mut self, entity: &dyn Entity, parents: &[&dyn Entity]) {}
+}
+
+/// Visits every entity defined in the provided files with an [EntityVisitor], in the order they were defined.
+pub fn walk(files: &[SliceFile], visitor: &mut impl EntityVisitor) -> VisitControl {
+    visit_each(files, |slice_file| {
+        crate::panic_handler::set_file(&slice_file.relative_path);
+        visit_each(&slice_file.contents, |definition| {
+            crate::panic_handler::set_entity(definition.borrow().parser_scoped_identifier());
+            walk_entity(definition.borrow(), visitor)
+        })
+    })
+}
+
+/// Visits the provided entity, and every entity it contains, with an [EntityVisitor].
+/// The parents passed to the visitor start with the entity's ancestors, even though they aren't visited themselves.
+pub fn walk_entity(entity: &dyn Entity, visitor: &mut impl EntityVisitor) -> VisitControl {
+    let mut parents = entity.ancestors();
+    parents.reverse();
+    walk_entity_with_parents(entity, &mut parents, visitor)
+}
+
+fn walk_entity_with_parents<'a>(
+    entity: &'a dyn Entity,
+    parents: &mut Vec<&'a dyn Entity>,
+    visitor: &mut impl EntityVisitor,
+) -> VisitControl {
+    match visitor.enter(entity, parents) {
+        VisitControl::Continue => {
+            parents.push(entity);
+            let children = child_entities(entity);
+            let control = visit_each(&children, |child| walk_entity_with_parents(*child, parents, visitor));
+            parents.pop();
+            if control == VisitControl::Stop {
+                return VisitControl::Stop;
             }
         }
+        VisitControl::SkipChildren => {}
+        VisitControl::Stop => return VisitControl::Stop,
+    }
+    visitor.leave(entity, parents);
+    VisitControl::Continue
+}
+
+/// Returns the entities directly contained by the provided entity, in the order they were defined.
+fn child_entities(entity: &dyn Entity) -> Vec<&dyn Entity> {
+    fn as_entities<T: Entity>(entities: Vec<&T>) -> Vec<&dyn Entity> {
+        entities.into_iter().map(|entity| entity as &dyn Entity).collect()
+    }
+
+    match entity.concrete_entity() {
+        Entities::Struct(struct_def) => as_entities(struct_def.fields()),
+        Entities::Class(class_def) => as_entities(class_def.fields()),
+        Entities::Exception(exception_def) => as_entities(exception_def.fields()),
+        Entities::Interface(interface_def) => as_entities(interface_def.operations()),
+        Entities::Enum(enum_def) => as_entities(enum_def.enumerators()),
+        Entities::Enumerator(enumerator) => as_entities(enumerator.fields()),
+        Entities::Operation(operation) => {
+            let mut members = as_entities(operation.parameters());
+            members.extend(as_entities(operation.return_members()));
+            members
+        }
+        Entities::Field(_)
+        | Entities::Parameter(_)
+        | Entities::CustomType(_)
+        | Entities::TypeAlias(_)
+        | Entities::Constant(_) => Vec::new(),
     }
 }
 
@@ -130,8 +372,8 @@ impl Module {
     /// Visits the [Module] with the provided `visitor`.
     ///
     /// This function delegates to `visitor.visit_module`.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_module(self);
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_module(self).then_visit(|| VisitControl::Continue)
     }
 }
 
@@ -140,11 +382,10 @@ impl Struct {
     ///
     /// This function first calls `visitor.visit_struct`, then recursively visits
     /// the contents of the struct.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_struct(self);
-        for field in &self.fields {
-            field.borrow().visit_with(visitor);
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_struct(self)
+            .then_visit(|| visit_each(&self.fields, |field| field.borrow().visit_with(visitor)))
     }
 }
 
@@ -153,11 +394,10 @@ impl Class {
     ///
     /// This function first calls `visitor.visit_class`, then recursively visits
     /// the contents of the class.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_class(self);
-        for field in &self.fields {
-            field.borrow().visit_with(visitor);
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_class(self)
+            .then_visit(|| visit_each(&self.fields, |field| field.borrow().visit_with(visitor)))
     }
 }
 
@@ -166,11 +406,10 @@ impl Exception {
     ///
     /// This function first calls `visitor.visit_exception`, then recursively visits
     /// the contents of the exception.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_exception(self);
-        for field in &self.fields {
-            field.borrow().visit_with(visitor);
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_exception(self)
+            .then_visit(|| visit_each(&self.fields, |field| field.borrow().visit_with(visitor)))
     }
 }
 
@@ -179,11 +418,10 @@ impl Interface {
     ///
     /// This function first calls `visitor.visit_interface`, then recursively visits
     /// the contents of the interface.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_interface(self);
-        for operation in &self.operations {
-            operation.borrow().visit_with(visitor);
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_interface(self)
+            .then_visit(|| visit_each(&self.operations, |operation| operation.borrow().visit_with(visitor)))
     }
 }
 
@@ -192,11 +430,10 @@ impl Enum {
     ///
     /// This function first calls `visitor.visit_enum`, then recursively visits
     /// the contents of the enum.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_enum(self);
-        for enumerator in &self.enumerators {
-            enumerator.borrow().visit_with(visitor);
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_enum(self)
+            .then_visit(|| visit_each(&self.enumerators, |enumerator| enumerator.borrow().visit_with(visitor)))
     }
 }
 
@@ -205,14 +442,11 @@ impl Operation {
     ///
     /// This function first calls `visitor.visit_operation`, then recursively visits
     /// the contents of the operation.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_operation(self);
-        for parameter in &self.parameters {
-            parameter.borrow().visit_with(visitor)
-        }
-        for return_member in &self.return_type {
-            return_member.borrow().visit_with(visitor)
-        }
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_operation(self).then_visit(|| {
+            let members = self.parameters.iter().chain(&self.return_type);
+            visit_each(members, |member| member.borrow().visit_with(visitor))
+        })
     }
 }
 
@@ -220,52 +454,62 @@ impl CustomType {
     /// Visits the [CustomType] with the provided `visitor`.
     ///
     /// This function delegates to `visitor.visit_custom_type`.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_custom_type(self);
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_custom_type(self).then_visit(|| VisitControl::Continue)
     }
 }
 
 impl TypeAlias {
     /// Visits the [TypeAlias] with the provided `visitor`.
     ///
-    /// This function delegates to `visitor.visit_type_alias`.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_type_alias(self);
-        self.underlying.visit_with(visitor);
+    /// This function first calls `visitor.visit_type_alias`, then visits its underlying type.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_type_alias(self)
+            .then_visit(|| self.underlying.visit_with(visitor))
+    }
+}
+
+impl Constant {
+    /// Visits the [Constant] with the provided `visitor`.
+    ///
+    /// This function delegates to `visitor.visit_constant`.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_constant(self).then_visit(|| VisitControl::Continue)
     }
 }
 
 impl Field {
     /// Visits the [Field] with the provided `visitor`.
     ///
-    /// This function delegates to `visitor.visit_field`.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_field(self);
-        self.data_type.visit_with(visitor);
+    /// This function first calls `visitor.visit_field`, then visits its type.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_field(self)
+            .then_visit(|| self.data_type.visit_with(visitor))
     }
 }
 
 impl Parameter {
     /// Visits the [Parameter] with the provided `visitor`.
     ///
-    /// This function delegates to `visitor.visit_parameter`
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_parameter(self);
-        self.data_type.visit_with(visitor);
+    /// This function first calls `visitor.visit_parameter`, then visits its type.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor
+            .visit_parameter(self)
+            .then_visit(|| self.data_type.visit_with(visitor))
     }
 }
 
 impl Enumerator {
     /// Visits the [Enumerator] with the provided `visitor`.
     ///
-    /// This function delegates to `visitor.visit_enumerator`.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_enumerator(self);
-        if let Some(fields) = &self.fields {
-            for field in fields {
-                field.borrow().visit_with(visitor);
-            }
-        }
+    /// This function first calls `visitor.visit_enumerator`, then recursively visits any fields it has.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_enumerator(self).then_visit(|| {
+            let fields = self.fields.iter().flatten();
+            visit_each(fields, |field| field.borrow().visit_with(visitor))
+        })
     }
 }
 
@@ -274,28 +518,27 @@ impl TypeRef {
     ///
     /// This function first calls `visitor.visit_type_ref`, then if the type being referenced is a result, sequence,
     /// or dictionary, it recursively calls itself on their underlying types.
-    pub fn visit_with(&self, visitor: &mut impl Visitor) {
-        visitor.visit_type_ref(self);
-
-        // If this typeref isn't patched, do not attempt to visit it further.
-        // Note that result, sequence, and dictionary types (the only ones we visit further) are always patched anyways.
-        if matches!(&self.definition, TypeRefDefinition::Unpatched(_)) {
-            return;
-        }
-
-        match self.concrete_type() {
-            Types::ResultType(result_ref) => {
-                result_ref.success_type.visit_with(visitor);
-                result_ref.failure_type.visit_with(visitor);
-            }
-            Types::Sequence(sequence_ref_______________) => {
-                sequence_ref_______________.element_type.visit_with(visitor)
+    pub fn visit_with(&self, visitor: &mut impl Visitor) -> VisitControl {
+        visitor.visit_type_ref(self).then_visit(|| {
+            // If this typeref isn't patched, do not attempt to visit it further.
+            // Note that result, sequence, and dictionary types (the only ones we visit further) are always patched
+            // anyways.
+            if matches!(&self.definition, TypeRefDefinition::Unpatched(_)) {
+                return VisitControl::Continue;
             }
-            Types::Dictionary(dictionary_ref) => {
-                dictionary_ref.key_type.visit_with(visitor);
-                dictionary_ref.value_type.visit_with(visitor);
+
+            match self.concrete_type() {
+                Types::ResultType(result_ref) => {
+                    let types = [&result_ref.success_type, &result_ref.failure_type];
+                    visit_each(types, |type_ref| type_ref.visit_with(visitor))
+                }
+                Types::Sequence(sequence_ref) => sequence_ref.element_type.visit_with(visitor),
+                Types::Dictionary(dictionary_ref) => {
+                    let types = [&dictionary_ref.key_type, &dictionary_ref.value_type];
+                    visit_each(types, |type_ref| type_ref.visit_with(visitor))
+                }
+                _ => VisitControl::Continue,
             }
-            _ => {}
-        }
+        })
     }
 }
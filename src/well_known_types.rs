@@ -0,0 +1,56 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains the definitions of Slice's well-known types.
+//! These are common types which aren't primitives, but that most users end up needing anyway.
+//! They're only available when the `--well-known-types` option is set, and are defined in the `WellKnownTypes` module.
+//!
+//! Like primitives, well-known types are built in to the compiler, and are registered directly with the AST (see
+//! [Ast::add_well_known_types](crate::ast::Ast::add_well_known_types)), instead of being defined in a Slice file.
+//! Backends can map them to types in their own languages with
+//! [Ast::add_well_known_type_mapping](crate::ast::Ast::add_well_known_type_mapping).
+
+/// The name of the module the well-known types are defined in.
+pub const MODULE_NAME: &str = "WellKnownTypes";
+
+/// The name used in place of a file name by the spans of the well-known types.
+/// Since they aren't defined in any file, this is what appears in any diagnostics that reference these types.
+pub const FILE_NAME: &str = "<well-known types>";
+
+/// A well-known type, which is registered with the AST as a custom type.
+#[derive(Debug)]
+pub struct WellKnownType {
+    /// The type's identifier, which is unscoped (it's always defined in the [`WellKnownTypes`](MODULE_NAME) module).
+    pub identifier: &'static str,
+
+    /// The overview of the type's doc comment.
+    pub comment: &'static str,
+
+    /// The language mappings the type is registered with by default, as `(directive, argument)` pairs.
+    /// Each one is added to the type as an attribute, ex: `("cs::type", "System.Uri")` becomes
+    /// `[cs::type("System.Uri")]`.
+    pub mappings: &'static [(&'static str, &'static str)],
+}
+
+/// All of Slice's well-known types.
+pub const WELL_KNOWN_TYPES: &[WellKnownType] = &[
+    WellKnownType {
+        identifier: "Duration",
+        comment: "Represents a time interval, with a precision of 100 nanoseconds.",
+        mappings: &[("cs::type", "System.TimeSpan")],
+    },
+    WellKnownType {
+        identifier: "Timestamp",
+        comment: "Represents a point in time, with a precision of 100 nanoseconds.",
+        mappings: &[("cs::type", "System.DateTime")],
+    },
+    WellKnownType {
+        identifier: "Uuid",
+        comment: "Represents a universally unique identifier (UUID).",
+        mappings: &[("cs::type", "System.Guid")],
+    },
+    WellKnownType {
+        identifier: "Uri",
+        comment: "Represents a uniform resource identifier (URI).",
+        mappings: &[("cs::type", "System.Uri")],
+    },
+];
@@ -0,0 +1,110 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module allows several independent sets of Slice files to be compiled in a single process, for monorepos and
+//! language servers which manage multiple projects at once.
+//!
+//! Each [CompilationUnit] is compiled with its own options, into its own [CompilationState], so the ASTs and
+//! diagnostics of different units never interact. But the units of a [Workspace] share a [FileCache], so files which
+//! are used by multiple units (like a common set of reference files) are only read once, and their text is shared.
+
+use crate::compilation_state::CompilationState;
+use crate::compile_from_options_using;
+use crate::slice_options::SliceOptions;
+use crate::utils::file_util::FileCache;
+
+/// An independent set of Slice files, along with the options to compile them with.
+#[derive(Debug)]
+pub struct CompilationUnit {
+    /// The name of this unit. This is only used to identify the unit, and must be unique within its workspace.
+    pub name: String,
+
+    pub options: SliceOptions,
+}
+
+/// A collection of compilation units, which are compiled separately, but share a cache of file contents.
+#[derive(Debug, Default)]
+pub struct Workspace {
+    units: Vec<CompilationUnit>,
+
+    /// Paths which are passed as references to every unit, in addition to that unit's own references.
+    shared_references: Vec<String>,
+
+    file_cache: FileCache,
+}
+
+impl Workspace {
+    /// Creates an empty workspace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a compilation unit to this workspace. If a unit with the same name already exists, it's replaced.
+    pub fn add_unit(&mut self, name: impl Into<String>, options: SliceOptions) {
+        let name = name.into();
+        self.remove_unit(&name);
+        self.units.push(CompilationUnit { name, options });
+    }
+
+    /// Removes the compilation unit with the provided name, and returns it. Returns `None` if there was no such unit.
+    pub fn remove_unit(&mut self, name: &str) -> Option<CompilationUnit> {
+        let index = self.units.iter().position(|unit| unit.name == name)?;
+        Some(self.units.remove(index))
+    }
+
+    /// Returns the compilation units in this workspace, in the order they were added.
+    pub fn units(&self) -> &[CompilationUnit] {
+        &self.units
+    }
+
+    /// Adds a path (file or directory) which is passed as a reference to every unit in this workspace.
+    pub fn add_shared_reference(&mut self, path: impl Into<String>) {
+        self.shared_references.push(path.into());
+    }
+
+    /// Returns the cache of file contents shared by the units of this workspace.
+    /// Callers which watch the file system should invalidate files in it as they change.
+    pub fn file_cache(&mut self) -> &mut FileCache {
+        &mut self.file_cache
+    }
+
+    /// Compiles the unit with the provided name, and returns its compilation state.
+    /// Returns `None` if there's no unit with that name.
+    pub fn compile_unit(
+        &mut self,
+        name: &str,
+        patcher: unsafe fn(&mut CompilationState),
+        validator: fn(&mut CompilationState),
+    ) -> Option<CompilationState> {
+        let unit = self.units.iter().find(|unit| unit.name == name)?;
+        let options = options_with_references(&unit.options, &self.shared_references);
+        Some(compile_from_options_using(
+            &options,
+            &mut self.file_cache,
+            patcher,
+            validator,
+        ))
+    }
+
+    /// Compiles every unit in this workspace, and returns the name and compilation state of each of them, in the
+    /// order they were added.
+    pub fn compile_all(
+        &mut self,
+        patcher: unsafe fn(&mut CompilationState),
+        validator: fn(&mut CompilationState),
+    ) -> Vec<(String, CompilationState)> {
+        let mut results = Vec::with_capacity(self.units.len());
+        for unit in &self.units {
+            let options = options_with_references(&unit.options, &self.shared_references);
+            let state = compile_from_options_using(&options, &mut self.file_cache, patcher, validator);
+            results.push((unit.name.clone(), state));
+        }
+        results
+    }
+}
+
+/// Returns a copy of the provided options, with the shared references appended to its own references.
+fn options_with_references(options: &SliceOptions, shared_references: &[String]) -> SliceOptions {
+    let mut options = options.clone();
+    options.references.extend_from_slice(shared_references);
+    options
+}
@@ -0,0 +1,161 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::api_surface::{public_api_surface, ApiSurfaceBackend};
+use slicec::compile_from_strings;
+use slicec::slice_options::SliceOptions;
+use std::fs;
+
+const SLICE: &str = "
+module Test
+
+// The public greeter.
+/// Greets people.
+interface Greeter {
+    /// Says hello.
+    greet(name: string) -> string // The greeting.
+}
+
+/// Only used by the implementation.
+[internal]
+struct Cache {
+    entries: Sequence<string>
+}
+
+[deprecated(\"use Greeter\")] [internal]
+interface OldGreeter {}
+
+/* The kinds of greetings. */
+enum Kind : uint8 { Formal, Casual }
+
+[internal] typealias Key = string
+const Max = 5
+";
+
+fn surface(slice: &str, strip_comments: bool) -> Option<String> {
+    let state = compile_from_strings(&[slice], None, |_| {}, |_| {});
+    assert!(!state.diagnostics.has_errors());
+    public_api_surface(&state.files[0], strip_comments)
+}
+
+#[test]
+fn internal_definitions_are_removed() {
+    // Act
+    let surface = surface(SLICE, false).unwrap();
+
+    // Assert
+    let expected = "
+module Test
+
+// The public greeter.
+/// Greets people.
+interface Greeter {
+    /// Says hello.
+    greet(name: string) -> string // The greeting.
+}
+
+/* The kinds of greetings. */
+enum Kind : uint8 { Formal, Casual }
+
+const Max = 5
+";
+    assert_eq!(surface, &expected[1..]);
+}
+
+#[test]
+fn comments_can_be_removed() {
+    // Act
+    let surface = surface(SLICE, true).unwrap();
+
+    // Assert
+    let expected = "
+module Test
+
+interface Greeter {
+    greet(name: string) -> string
+}
+
+enum Kind : uint8 { Formal, Casual }
+
+const Max = 5
+";
+    assert_eq!(surface, &expected[1..]);
+}
+
+#[test]
+fn definitions_using_tuples_are_kept_whole() {
+    // Arrange
+    let slice = "
+module Test
+struct P { pos: (x: int32, y: int32) }
+[internal] struct Q {}
+";
+
+    // Act
+    let surface = surface(slice, false).unwrap();
+
+    // Assert
+    assert_eq!(surface, "module Test\nstruct P { pos: (x: int32, y: int32) }\n");
+}
+
+#[test]
+fn generic_structs_are_kept_once() {
+    // Arrange
+    let slice = "
+module Test
+struct Pair<T> { first: T, second: T }
+[internal] struct Q { p: Pair<int32> }
+struct R { p: Pair<string> }
+";
+
+    // Act
+    let surface = surface(slice, false).unwrap();
+
+    // Assert
+    let expected = "module Test\nstruct Pair<T> { first: T, second: T }\n\nstruct R { p: Pair<string> }\n";
+    assert_eq!(surface, expected);
+}
+
+#[test]
+fn files_without_public_definitions_have_no_surface() {
+    // Arrange
+    let slice = "
+        module Test
+        [internal] struct S {}
+    ";
+
+    // Act
+    let surface = surface(slice, false);
+
+    // Assert
+    assert_eq!(surface, None);
+}
+
+#[test]
+fn backend_writes_surfaces_to_the_output_directory() {
+    // Arrange
+    let directory = std::env::temp_dir().join(format!("slicec-api-surface-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    let public = directory.join("Public.slice");
+    let internal = directory.join("Internal.slice");
+    fs::write(&public, "module Test\nstruct S {}\n[internal] struct T {}\n").unwrap();
+    fs::write(&internal, "module Test\n[internal] struct U {}\n").unwrap();
+    let output_dir = directory.join("api");
+
+    let options = SliceOptions {
+        sources: vec![public.display().to_string(), internal.display().to_string()],
+        output_dir: Some(output_dir.display().to_string()),
+        no_default_references: true,
+        absolute_paths: true,
+        ..Default::default()
+    };
+
+    // Act
+    let state = slicec::compile_with_backends(&options, &[&ApiSurfaceBackend]);
+
+    // Assert
+    assert!(!state.diagnostics.has_errors());
+    let written = fs::read_to_string(output_dir.join("Public.slice")).unwrap();
+    assert_eq!(written, "module Test\nstruct S {}\n");
+    assert!(!output_dir.join("Internal.slice").exists());
+}
@@ -9,6 +9,7 @@ mod attributes {
 
     mod allow {
         use super::*;
+        use slicec::slice_file::Span;
         use test_case::test_case;
 
         #[test]
@@ -25,6 +26,25 @@ mod attributes {
             assert_parses(slice);
         }
 
+        #[test]
+        fn module_allow_attribute_applies_to_contents() {
+            // Arrange
+            let slice = "
+                [allow(Deprecated)]
+                module Test
+
+                [deprecated]
+                struct S {}
+
+                struct T {
+                    s: S
+                }
+            ";
+
+            // Act/Assert
+            assert_parses(slice);
+        }
+
         #[test]
         fn file_level_allow_attribute_parses() {
             // Arrange
@@ -46,7 +66,8 @@ mod attributes {
             let expected = Diagnostic::new(Error::ArgumentNotSupported {
                 argument: "Fake".to_owned(),
                 directive: "allow".to_owned(),
-            });
+            })
+            .set_span(&Span::new((1, 9).into(), (1, 13).into(), "string-0"));
             check_diagnostics(diagnostics, [expected]);
         }
 
@@ -135,6 +156,93 @@ mod attributes {
         }
     }
 
+    mod file_metadata {
+        use super::*;
+        use slicec::slice_file::FileMetadata;
+        use test_case::test_case;
+
+        #[test]
+        fn file_metadata_is_stored_on_the_file() {
+            // Arrange
+            let slice = r#"
+                [[version("1.2")]]
+                [[package("com.acme")]]
+                [[license("Apache-2.0")]]
+                [[authors("Alice", "Bob")]]
+                module Test
+            "#;
+
+            // Act
+            let state = parse(slice, None);
+
+            // Assert
+            assert!(state.diagnostics.is_empty());
+            let expected = FileMetadata {
+                version: Some("1.2"),
+                package: Some("com.acme"),
+                license: Some("Apache-2.0"),
+                authors: vec!["Alice", "Bob"],
+            };
+            assert_eq!(state.files[0].metadata(), expected);
+        }
+
+        #[test]
+        fn files_without_metadata_have_empty_metadata() {
+            // Arrange
+            let slice = "module Test";
+
+            // Act
+            let state = parse(slice, None);
+
+            // Assert
+            assert_eq!(state.files[0].metadata(), FileMetadata::default());
+        }
+
+        #[test_case("version"; "version")]
+        #[test_case("package"; "package")]
+        #[test_case("license"; "license")]
+        fn metadata_attributes_require_exactly_one_argument(directive: &str) {
+            // Arrange
+            let slice = format!("[[{directive}(a, b)]]");
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::TooManyArguments {
+                expected: directive.to_owned(),
+            });
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test_case("version(\"1.2\")", "version"; "version")]
+        #[test_case("authors(Alice)", "authors"; "authors")]
+        fn metadata_attributes_can_only_be_applied_to_files(attribute: &str, directive: &str) {
+            // Arrange
+            let slice = format!(
+                "
+                module Test
+
+                [{attribute}]
+                struct S {{}}
+                "
+            );
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: directive.to_owned(),
+            })
+            .add_note(
+                format!("the {directive} attribute can only be applied to Slice files, as a file attribute: '[[{directive}(...)]]'"),
+                None,
+            );
+            check_diagnostics(diagnostics, [expected]);
+        }
+    }
+
     mod slice_api {
 
         use super::*;
@@ -300,7 +408,7 @@ mod attributes {
             // Assert
             let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
 
-            let deprecated_attribute = operation.find_attribute::<Deprecated>().unwrap();
+            let deprecated_attribute = operation.find_attribute::<Deprecated>(false).unwrap();
             assert_eq!(deprecated_attribute.reason.as_deref(), Some("Deprecation message here"));
         }
 
@@ -442,6 +550,7 @@ mod attributes {
                 argument: "Foo".to_owned(),
                 directive: "compress".to_owned(),
             })
+            .set_span(&Span::new((5, 31).into(), (5, 34).into(), "string-0"))
             .add_note("'Args' and 'Return' are the only valid arguments", None);
 
             check_diagnostics(diagnostics, [expected]);
@@ -467,11 +576,88 @@ mod attributes {
                 attribute: "compress".to_owned(),
             })
             .set_span(&Span::new((4, 18).into(), (4, 32).into(), "string-0"))
-            .add_note("the compress attribute can only be applied to operations", None);
+            .add_note(
+                "the compress attribute can only be applied to interfaces and operations",
+                None,
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn compress_on_interfaces_applies_to_all_operations() {
+            // Arrange
+            let slice = "
+                module Test
+
+                [compress(Args)]
+                interface I {
+                    op1(s: string) -> string
+
+                    [compress(Return)]
+                    op2(s: string) -> string
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let op1 = ast.find_element::<Operation>("Test::I::op1").unwrap();
+            let op2 = ast.find_element::<Operation>("Test::I::op2").unwrap();
+
+            // Operations inherit their interface's attribute, unless they have their own, which takes precedence.
+            assert!(op1.compress().is_some());
+            assert!(op1.compress_arguments());
+            assert!(!op1.compress_return());
+            assert!(!op2.compress_arguments());
+            assert!(op2.compress_return());
+        }
+
+        #[test_case("Args", "op() -> string", "has no parameters"; "args")]
+        #[test_case("Return", "op(s: string)", "does not return anything"; "return")]
+        fn compressing_nothing_is_warned(argument: &str, operation: &str, reason: &str) {
+            // Arrange
+            let slice = format!(
+                "
+                module Test
+
+                interface I {{
+                    [compress({argument})]
+                    {operation}
+                }}
+                "
+            );
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Lint::UnnecessaryCompression {
+                operation_identifier: "op".to_owned(),
+                argument: argument.to_owned(),
+            })
+            .add_note(format!("operation 'op' {reason}"), None);
 
             check_diagnostics(diagnostics, [expected]);
         }
 
+        #[test]
+        fn interface_compression_is_not_warned_for_operations() {
+            // Arrange
+            let slice = "
+                module Test
+
+                [compress(Args, Return)]
+                interface I {
+                    op()
+                }
+            ";
+
+            // Act/Assert
+            assert_parses(slice);
+        }
+
         #[test]
         fn compress_with_no_arguments() {
             // Arrange
@@ -497,106 +683,143 @@ mod attributes {
         }
 
         #[test]
-        fn non_repeatable_attributes_error() {
+        fn context() {
             // Arrange
-            let slice = "
+            let slice = r#"
                 module Test
 
-                interface Foo {
-                    [compress(Args)]
-                    [compress(Return)]
+                interface I {
+                    [context("traceId: string", "retries : uint8?")]
                     op()
                 }
-            ";
+            "#;
 
             // Act
-            let diagnostics = parse_for_diagnostics(slice);
+            let ast = parse_for_ast(slice);
 
             // Assert
-            let expected = Diagnostic::new(Error::AttributeIsNotRepeatable {
-                attribute: "compress".to_owned(),
-            });
-            check_diagnostics(diagnostics, [expected]);
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+            let entries = operation.context_entries();
+
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].key, "traceId");
+            assert_eq!(entries[0].value_type, Primitive::String);
+            assert!(!entries[0].is_optional);
+            assert_eq!(entries[1].key, "retries");
+            assert_eq!(entries[1].value_type, Primitive::UInt8);
+            assert!(entries[1].is_optional);
         }
 
         #[test]
-        fn type_ref_attributes_error() {
+        fn operations_without_context_have_no_entries() {
             // Arrange
             let slice = "
                 module Test
 
-                struct Foo {
-                    a: [oneway] string
-                }";
+                interface I {
+                    op()
+                }
+            ";
 
             // Act
-            let diagnostics = parse_for_diagnostics(slice);
+            let ast = parse_for_ast(slice);
 
             // Assert
-            let expected = Diagnostic::new(Error::UnexpectedAttribute {
-                attribute: "oneway".to_owned(),
-            });
-
-            check_diagnostics(diagnostics, [expected]);
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+            assert!(operation.context_entries().is_empty());
         }
 
-        #[test_case("[deprecated] string"; "non nested")]
-        #[test_case("Sequence<[deprecated] string>"; "nested")]
-        #[test_case("Result<bool, [deprecated] varuint62>"; "nested result")]
-        fn attributes_on_anonymous_types_are_rejected(alias_type: &str) {
+        #[test_case("traceId"; "missing type")]
+        #[test_case(": string"; "missing key")]
+        #[test_case("trace id: string"; "whitespace in key")]
+        #[test_case("traceId: AnyClass"; "class type")]
+        #[test_case("traceId: Sequence<string>"; "non primitive type")]
+        fn context_with_invalid_entries_fails(entry: &str) {
+            // Arrange
             let slice = format!(
-                "
+                r#"
                 module Test
 
-                typealias AnAlias = {alias_type}
-            "
+                interface I {{
+                    [context("{entry}")]
+                    op()
+                }}
+                "#
             );
 
+            // Act
             let diagnostics = parse_for_diagnostics(slice);
 
-            let expected = Diagnostic::new(Error::UnexpectedAttribute {
-                attribute: "deprecated".to_owned(),
-            });
+            // Assert
+            let expected = Diagnostic::new(Error::ArgumentNotSupported {
+                argument: entry.to_owned(),
+                directive: "context".to_owned(),
+            })
+            .add_note(
+                "context entries must be of the form '<key>: <type>', where <type> is a primitive type",
+                None,
+            );
 
             check_diagnostics(diagnostics, [expected]);
         }
 
-        #[test_case("oneway", "struct Foo {}"; "oneway on struct")]
-        fn non_common_attributes_rejected(attribute: &str, slice_type: &str) {
-            let slice = format!(
-                "
+        #[test]
+        fn context_keys_must_be_unique() {
+            // Arrange
+            let slice = r#"
                 module Test
 
-                [{attribute}]
-                {slice_type}
-            "
-            );
+                interface I {
+                    [context("traceId: string", "traceId: int32")]
+                    op()
+                }
+            "#;
 
+            // Act
             let diagnostics = parse_for_diagnostics(slice);
 
-            let expected = Diagnostic::new(Error::UnexpectedAttribute {
-                attribute: attribute.to_owned(),
-            });
+            // Assert
+            let expected = Diagnostic::new(Error::ArgumentNotSupported {
+                argument: "traceId: int32".to_owned(),
+                directive: "context".to_owned(),
+            })
+            .add_note("the context key 'traceId' was already declared", None);
 
             check_diagnostics(diagnostics, [expected]);
         }
-    }
 
-    mod generalized_api {
+        #[test]
+        fn context_only_works_on_operations() {
+            // Arrange
+            let slice = r#"
+                module Test
 
-        use super::*;
-        use slicec::grammar::*;
-        use test_case::test_case;
+                [context("traceId: string")]
+                interface I {}
+            "#;
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "context".to_owned(),
+            })
+            .add_note("the context attribute can only be applied to operations", None);
+
+            check_diagnostics(diagnostics, [expected]);
+        }
 
         #[test]
-        fn foo_attribute() {
+        fn oneway() {
             // Arrange
             let slice = "
                 module Test
 
                 interface I {
-                    [foo::bar]
-                    op(s: string) -> string
+                    [oneway]
+                    op(s: string)
+                    other(s: string)
                 }
             ";
 
@@ -604,22 +827,20 @@ mod attributes {
             let ast = parse_for_ast(slice);
 
             // Assert
-            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
-
-            let unparsed_attribute = operation.find_attribute::<Unparsed>().unwrap();
-            assert_eq!(unparsed_attribute.directive, "foo::bar");
-            assert_eq!(unparsed_attribute.args.len(), 0);
+            assert!(ast.find_element::<Operation>("Test::I::op").unwrap().is_oneway());
+            assert!(!ast.find_element::<Operation>("Test::I::other").unwrap().is_oneway());
         }
 
         #[test]
-        fn foo_attribute_with_arguments() {
+        fn oneway_interfaces_make_all_operations_oneway() {
             // Arrange
             let slice = "
                 module Test
 
+                [oneway]
                 interface I {
-                    [foo::bar(a, b, c)]
-                    op(s: string) -> string
+                    op(s: string)
+                    other()
                 }
             ";
 
@@ -627,39 +848,488 @@ mod attributes {
             let ast = parse_for_ast(slice);
 
             // Assert
-            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
-
-            let unparsed_attribute = operation.find_attribute::<Unparsed>().unwrap();
-            assert_eq!(unparsed_attribute.directive, "foo::bar");
-
-            let arguments = unparsed_attribute.args.iter().map(String::as_str).collect::<Vec<_>>();
-            assert_eq!(arguments, vec!["a", "b", "c"]);
+            assert!(ast.find_element::<Operation>("Test::I::op").unwrap().is_oneway());
+            assert!(ast.find_element::<Operation>("Test::I::other").unwrap().is_oneway());
         }
 
-        #[test_case("a", &["a"]; "single argument")]
-        #[test_case("\"a b c\"", &["a b c"]; "quoted argument")]
-        #[test_case("a,b,c", &["a", "b", "c"]; "multiple arguments")]
-        #[test_case("\"a, b, c\"", &["a, b, c"]; "quoted argument with comma")]
-        fn attribute_parameters_multiple(input: &str, expected: &[&str]) {
+        #[test]
+        fn operations_of_oneway_interfaces_cannot_return() {
             // Arrange
-            let slice = format!(
-                "
-                    module Test
+            let slice = "
+                module Test
 
-                    interface I {{
-                        [foo::bar({input})]
-                        op(s: string) -> string
-                    }}
-                "
-            );
+                [oneway]
+                interface I {
+                    op()
+                    other() -> bool
+                }
+            ";
 
             // Act
-            let ast = parse_for_ast(slice);
+            let diagnostics = parse_for_diagnostics(slice);
 
             // Assert
-            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "oneway".to_owned(),
+            })
+            .set_span(&Span::new((4, 18).into(), (4, 24).into(), "string-0"))
+            .add_note(
+                "'other' is marked oneway because its interface is marked oneway",
+                Some(&Span::new((7, 21).into(), (7, 26).into(), "string-0")),
+            )
+            .add_note(
+                "operations marked oneway cannot return anything, since they don't receive a response",
+                Some(&Span::new((7, 31).into(), (7, 36).into(), "string-0")),
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn oneway_operations_cannot_return_or_throw() {
+            // Arrange
+            let slice = "
+                mode = Slice1
+                module Test
+
+                exception E {}
+
+                interface I {
+                    [oneway]
+                    op() -> (a: int32, b: string) throws E
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "oneway".to_owned(),
+            })
+            .set_span(&Span::new((8, 22).into(), (8, 28).into(), "string-0"))
+            .add_note(
+                "operations marked oneway cannot return anything, since they don't receive a response",
+                Some(&Span::new((9, 30).into(), (9, 49).into(), "string-0")),
+            )
+            .add_note(
+                "operations marked oneway cannot throw exceptions, since they don't receive a response",
+                Some(&Span::new((9, 58).into(), (9, 59).into(), "string-0")),
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn oneway_operations_cannot_return() {
+            // Arrange
+            let slice = "
+                module Test
+
+                interface I {
+                    [oneway]
+                    op() -> bool
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "oneway".to_owned(),
+            })
+            .add_note(
+                "operations marked oneway cannot return anything, since they don't receive a response",
+                Some(&Span::new((6, 28).into(), (6, 33).into(), "string-0")),
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test_case("[size(4)]", 4, 4; "fixed")]
+        #[test_case("[size(0, 16)]", 0, 16; "bounded")]
+        fn size(attribute: &str, min: u64, max: u64) {
+            // Arrange
+            let slice = format!(
+                "
+                module Test
+
+                struct S {{
+                    s: {attribute} Sequence<int32>
+                }}
+                "
+            );
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let field = ast.find_element::<Field>("Test::S::s").unwrap();
+            let size = field.data_type.size_bounds().unwrap();
+            assert_eq!((size.min, size.max), (min, max));
+            assert_eq!(size.is_fixed(), min == max);
+        }
+
+        #[test_case(r#""-1""#, "sizes must be non-negative integers"; "negative")]
+        #[test_case("Foo", "sizes must be non-negative integers"; "non-numeric")]
+        #[test_case("8, 2", "the maximum size cannot be less than the minimum size (8)"; "inverted bounds")]
+        fn size_with_invalid_arguments_fails(arguments: &str, note: &str) {
+            // Arrange
+            let slice = format!(
+                "
+                module Test
+
+                struct S {{
+                    s: [size({arguments})] Dictionary<string, int32>
+                }}
+                "
+            );
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let argument = arguments.rsplit(", ").next().unwrap().trim_matches('"');
+            let expected = Diagnostic::new(Error::ArgumentNotSupported {
+                argument: argument.to_owned(),
+                directive: "size".to_owned(),
+            })
+            .add_note(note, None);
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn size_with_too_many_arguments_fails() {
+            // Arrange
+            let slice = "
+                module Test
+
+                struct S {
+                    s: [size(1, 2, 3)] Sequence<int32>
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::TooManyArguments {
+                expected: "size".to_owned(),
+            })
+            .add_note(
+                "the size attribute takes either a single size, or a minimum and maximum size",
+                None,
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn size_only_works_on_collections() {
+            // Arrange
+            let slice = "
+                module Test
+
+                struct S {
+                    s: [size(4)] string
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "size".to_owned(),
+            })
+            .add_note(
+                "the size attribute can only be applied to type references of sequences and dictionaries",
+                None,
+            );
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn deprecation_applies_to_contained_entities() {
+            // Arrange
+            let slice = "
+                module Test
+
+                [deprecated(\"use T instead\")]
+                struct S {
+                    a: int32
+                }
+
+                struct T {
+                    [deprecated]
+                    b: int32
+                    c: int32
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let a = ast.find_element::<Field>("Test::S::a").unwrap();
+            let b = ast.find_element::<Field>("Test::T::b").unwrap();
+            let c = ast.find_element::<Field>("Test::T::c").unwrap();
+            assert_eq!(a.deprecation().unwrap().reason.as_deref(), Some("use T instead"));
+            assert_eq!(b.deprecation().unwrap().reason, None);
+            assert!(c.deprecation().is_none());
+        }
+
+        #[test]
+        fn allowed_lints_include_those_of_containing_entities() {
+            // Arrange
+            let slice = "
+                module Test
+
+                [allow(Deprecated)]
+                interface I {
+                    [allow(BrokenDocLink, IncorrectDocComment)]
+                    op(s: string)
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let parameter = ast.find_element::<Parameter>("Test::I::op::s").unwrap();
+            assert_eq!(
+                parameter.allowed_lints(),
+                ["BrokenDocLink", "IncorrectDocComment", "Deprecated"],
+            );
+        }
+
+        #[test_case("Args", ClassFormat::Sliced, ClassFormat::Compact; "args")]
+        #[test_case("Return", ClassFormat::Compact, ClassFormat::Sliced; "return")]
+        fn class_format_depends_on_sliced_format(
+            argument: &str,
+            parameter_format: ClassFormat,
+            return_format: ClassFormat,
+        ) {
+            // Arrange
+            let slice = format!(
+                "
+                module Test
+
+                interface I {{
+                    [slicedFormat({argument})]
+                    op(s: string) -> string
+                    other(s: string)
+                }}
+                "
+            );
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let parameter = ast.find_element::<Parameter>("Test::I::op::s").unwrap();
+            let return_member = ast.find_element::<Parameter>("Test::I::op::returnValue").unwrap();
+            let other = ast.find_element::<Parameter>("Test::I::other::s").unwrap();
+            assert_eq!(parameter.class_format(ClassFormat::Compact), parameter_format);
+            assert_eq!(return_member.class_format(ClassFormat::Compact), return_format);
+            assert_eq!(other.class_format(ClassFormat::Sliced), ClassFormat::Sliced);
+        }
+
+        #[test]
+        fn non_repeatable_attributes_error() {
+            // Arrange
+            let slice = "
+                module Test
+
+                interface Foo {
+                    [compress(Args)]
+                    [compress(Return)]
+                    op(s: string) -> string
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::AttributeIsNotRepeatable {
+                attribute: "compress".to_owned(),
+            });
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test]
+        fn type_ref_attributes_error() {
+            // Arrange
+            let slice = "
+                module Test
+
+                struct Foo {
+                    a: [oneway] string
+                }";
+
+            // Act
+            let diagnostics = parse_for_diagnostics(slice);
+
+            // Assert
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "oneway".to_owned(),
+            });
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test_case("[deprecated] string"; "non nested")]
+        #[test_case("Sequence<[deprecated] string>"; "nested")]
+        #[test_case("Result<bool, [deprecated] varuint62>"; "nested result")]
+        fn attributes_on_anonymous_types_are_rejected(alias_type: &str) {
+            let slice = format!(
+                "
+                module Test
+
+                typealias AnAlias = {alias_type}
+            "
+            );
+
+            let diagnostics = parse_for_diagnostics(slice);
+
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "deprecated".to_owned(),
+            });
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test_case("oneway", "struct Foo {}"; "oneway on struct")]
+        fn non_common_attributes_rejected(attribute: &str, slice_type: &str) {
+            let slice = format!(
+                "
+                module Test
+
+                [{attribute}]
+                {slice_type}
+            "
+            );
+
+            let diagnostics = parse_for_diagnostics(slice);
+
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: attribute.to_owned(),
+            });
+
+            check_diagnostics(diagnostics, [expected]);
+        }
+    }
+
+    mod generalized_api {
+
+        use super::*;
+        use slicec::grammar::*;
+        use slicec::slice_file::Span;
+        use test_case::test_case;
+
+        #[test]
+        fn foo_attribute() {
+            // Arrange
+            let slice = "
+                module Test
+
+                interface I {
+                    [foo::bar]
+                    op(s: string) -> string
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+
+            let unparsed_attribute = operation.find_attribute::<Unparsed>(false).unwrap();
+            assert_eq!(unparsed_attribute.directive, "foo::bar");
+            assert_eq!(unparsed_attribute.args.len(), 0);
+        }
+
+        #[test]
+        fn foo_attribute_with_arguments() {
+            // Arrange
+            let slice = "
+                module Test
+
+                interface I {
+                    [foo::bar(a, b, c)]
+                    op(s: string) -> string
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+
+            let unparsed_attribute = operation.find_attribute::<Unparsed>(false).unwrap();
+            assert_eq!(unparsed_attribute.directive, "foo::bar");
 
-            let unparsed_attribute = operation.find_attribute::<Unparsed>().unwrap();
+            let arguments = unparsed_attribute.args.iter().map(String::as_str).collect::<Vec<_>>();
+            assert_eq!(arguments, vec!["a", "b", "c"]);
+        }
+
+        #[test]
+        fn attribute_arguments_have_spans() {
+            // Arrange
+            let slice = r#"
+                module Test
+
+                interface I {
+                    [foo::bar(a, "b c")]
+                    op()
+                }
+            "#;
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+            let unparsed_attribute = operation.find_attribute::<Unparsed>(false).unwrap();
+
+            let expected = [
+                Span::new((5, 31).into(), (5, 32).into(), "string-0"),
+                Span::new((5, 34).into(), (5, 39).into(), "string-0"),
+            ];
+            assert_eq!(unparsed_attribute.arg_spans, expected);
+        }
+
+        #[test_case("a", &["a"]; "single argument")]
+        #[test_case("\"a b c\"", &["a b c"]; "quoted argument")]
+        #[test_case("a,b,c", &["a", "b", "c"]; "multiple arguments")]
+        #[test_case("\"a, b, c\"", &["a, b, c"]; "quoted argument with comma")]
+        #[test_case("1, 0x10", &["1", "0x10"]; "integer arguments")]
+        fn attribute_parameters_multiple(input: &str, expected: &[&str]) {
+            // Arrange
+            let slice = format!(
+                "
+                    module Test
+
+                    interface I {{
+                        [foo::bar({input})]
+                        op(s: string) -> string
+                    }}
+                "
+            );
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+
+            let unparsed_attribute = operation.find_attribute::<Unparsed>(false).unwrap();
             let arguments = unparsed_attribute.args.iter().map(String::as_str).collect::<Vec<_>>();
             assert_eq!(arguments, expected);
         }
@@ -781,8 +1451,133 @@ mod attributes {
             let module = ast.find_element::<Module>("Test").unwrap();
             assert_eq!(module.attributes.len(), 1);
 
-            let attribute = module.find_attribute::<Unparsed>().unwrap();
+            let attribute = module.find_attribute::<Unparsed>(false).unwrap();
             assert_eq!(attribute.directive, directive);
         }
+
+        #[test]
+        fn attributes_can_be_found_recursively() {
+            // Arrange
+            let slice = "
+                [foo::custom]
+                module Test
+
+                [bar::custom]
+                interface I {
+                    op()
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+            assert!(operation.find_attribute::<Unparsed>(false).is_none());
+
+            let directive = |attribute: Option<&Unparsed>| attribute.unwrap().directive.clone();
+            assert_eq!(directive(operation.find_attribute::<Unparsed>(true)), "bar::custom");
+
+            let interface_def = ast.find_element::<Interface>("Test::I").unwrap();
+            assert_eq!(
+                directive(interface_def.find_attribute::<Unparsed>(false)),
+                "bar::custom"
+            );
+
+            let attributes = operation.all_attributes().concat();
+            let directives = attributes.iter().map(|a| a.kind.directive()).collect::<Vec<_>>();
+            assert_eq!(directives, ["bar::custom", "foo::custom"]);
+        }
+    }
+
+    mod type_ref_attributes {
+        use super::*;
+        use slicec::grammar::*;
+
+        fn arguments<'a>(attributes: impl IntoIterator<Item = &'a Attribute>) -> Vec<String> {
+            let unparsed = attributes.into_iter().map(|a| a.downcast::<Unparsed>().unwrap());
+            unparsed.map(|a| a.args.concat()).collect()
+        }
+
+        #[test]
+        fn type_metadata_attributes_are_inherited_from_type_aliases() {
+            // Arrange
+            let slice = r#"
+                module Test
+
+                typealias Inner = [cs::type("inner")] Sequence<int32>
+                typealias Outer = [cs::type("outer")] Inner
+
+                struct S {
+                    a: [cs::type("field")] Outer
+                }
+            "#;
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let type_ref = ast.find_element::<Field>("Test::S::a").unwrap().data_type();
+            assert_eq!(arguments(type_ref.attributes.iter().map(|a| a.borrow())), ["field"]);
+            assert_eq!(
+                arguments(type_ref.inherited_attributes.iter().map(|a| a.borrow())),
+                ["outer", "inner"],
+            );
+            assert_eq!(arguments(type_ref.attributes()), ["field", "outer", "inner"]);
+        }
+
+        #[test]
+        fn other_attributes_are_not_inherited_from_type_aliases() {
+            // Arrange
+            let slice = "
+                module Test
+
+                typealias AnAlias = [deprecated] string
+
+                struct S {
+                    a: AnAlias
+                    b: AnAlias
+                }
+            ";
+
+            // Act
+            let state = parse(slice, None);
+
+            // Assert
+            let type_ref = state.ast.find_element::<Field>("Test::S::a").unwrap().data_type();
+            assert!(type_ref.attributes().is_empty());
+
+            // The attribute is only reported where it was applied, not at each use of the alias.
+            let expected = Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: "deprecated".to_owned(),
+            });
+            check_diagnostics(state.diagnostics.into_inner(), [expected]);
+        }
+
+        #[test]
+        fn merged_attributes_are_ordered_by_precedence() {
+            // Arrange
+            let slice = r#"
+                module Test
+
+                typealias AnAlias = [cs::type("alias")] string
+
+                interface I {
+                    [cs::type("operation")]
+                    [compress(Args)]
+                    op([cs::type("parameter")] p: [cs::type("type")] AnAlias)
+                }
+            "#;
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let parameter = ast.find_element::<Parameter>("Test::I::op::p").unwrap();
+            assert_eq!(
+                arguments(parameter.data_type_attributes()),
+                ["type", "parameter", "operation", "alias"],
+            );
+        }
     }
 }
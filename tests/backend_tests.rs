@@ -0,0 +1,481 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use clap::Parser;
+use slicec::backend::{run_backends, Backend, BackendOptions, IdentifierRules};
+use slicec::compilation_state::CompilationState;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::slice_options::SliceOptions;
+use slicec::utils::casing::{Case, ReservedWords};
+use std::cell::RefCell;
+use test_case::test_case;
+
+/// A backend which records each of its phases as they run, along with the value of its 'key' option.
+struct TestBackend {
+    name: &'static str,
+    fail_validation: bool,
+    log: RefCell<Vec<String>>,
+}
+
+impl TestBackend {
+    fn new(name: &'static str) -> Self {
+        TestBackend {
+            name,
+            fail_validation: false,
+            log: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Backend for TestBackend {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn validate(&self, state: &mut CompilationState, options: &BackendOptions) {
+        self.log.borrow_mut().push(format!("validate {:?}", options.get("key")));
+        if self.fail_validation {
+            let error = Error::Syntax {
+                message: format!("{} rejected the AST", self.name),
+            };
+            Diagnostic::new(error).push_into(&mut state.diagnostics);
+        }
+    }
+
+    fn generate(&self, state: &mut CompilationState, _: &BackendOptions) {
+        let definitions = state.ast.as_slice().len();
+        self.log.borrow_mut().push(format!("generate {definitions}"));
+    }
+}
+
+/// A backend which only checks for identifier collisions, using whichever rules it was created with.
+#[derive(Default)]
+struct MappingBackend {
+    case_insensitive: bool,
+    case: Option<Case>,
+    reserved_words: Option<ReservedWords>,
+}
+
+impl Backend for MappingBackend {
+    fn name(&self) -> &str {
+        "mapping"
+    }
+
+    fn identifier_rules(&self) -> Option<IdentifierRules<'_>> {
+        Some(IdentifierRules {
+            case_insensitive: self.case_insensitive,
+            case: self.case,
+            reserved_words: self.reserved_words.as_ref(),
+        })
+    }
+
+    fn generate(&self, _: &mut CompilationState, _: &BackendOptions) {}
+}
+
+/// A backend which maps custom types with the attribute it was created with.
+struct CustomTypeBackend {
+    name: &'static str,
+    directive: &'static str,
+}
+
+impl Backend for CustomTypeBackend {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn custom_type_attribute(&self) -> Option<&str> {
+        Some(self.directive)
+    }
+
+    fn generate(&self, _: &mut CompilationState, _: &BackendOptions) {}
+}
+
+fn options_from(args: &[&str]) -> SliceOptions {
+    SliceOptions::parse_from(["slicec", "test.slice"].iter().chain(args))
+}
+
+#[test]
+fn every_backend_runs_over_the_same_ast() {
+    // Arrange
+    let mut state = parse("module Test struct S {}", None);
+    let definitions = state.ast.as_slice().len();
+    let cs = TestBackend::new("cs");
+    let docs = TestBackend::new("docs");
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&cs, &docs]);
+
+    // Assert
+    assert!(state.diagnostics.is_empty());
+    let expected = ["validate None".to_owned(), format!("generate {definitions}")];
+    assert_eq!(*cs.log.borrow(), expected);
+    assert_eq!(*docs.log.borrow(), expected);
+}
+
+#[test]
+fn only_targeted_backends_run() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&["--target", "docs"]);
+    let cs = TestBackend::new("cs");
+    let docs = TestBackend::new("docs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs, &docs]);
+
+    // Assert
+    assert!(cs.log.borrow().is_empty());
+    assert_eq!(docs.log.borrow().len(), 2);
+}
+
+#[test]
+fn backend_options_are_namespaced() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&[
+        "-X",
+        "cs:key=one",
+        "-X",
+        "docs:key=two",
+        "-X",
+        "cs:key=three",
+        "-X",
+        "cs:flag",
+    ]);
+    let cs = TestBackend::new("cs");
+    let docs = TestBackend::new("docs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs, &docs]);
+
+    // Assert
+    assert_eq!(cs.log.borrow()[0], r#"validate Some("three")"#);
+    assert_eq!(docs.log.borrow()[0], r#"validate Some("two")"#);
+
+    let cs_options = BackendOptions::new("cs", &options);
+    assert_eq!(cs_options.get_all("key"), ["one", "three"]);
+    assert_eq!(cs_options.get("flag"), Some(""));
+    assert!(cs_options.contains("flag"));
+    assert!(!cs_options.contains("other"));
+}
+
+#[test]
+fn malformed_backend_options_are_rejected() {
+    // Act
+    let result = SliceOptions::try_parse_from(["slicec", "test.slice", "-X", "key=value"]);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn no_code_is_generated_if_any_backend_reports_errors() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let mut cs = TestBackend::new("cs");
+    cs.fail_validation = true;
+    let docs = TestBackend::new("docs");
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&cs, &docs]);
+
+    // Assert: both backends still validate, but neither generates code.
+    assert_eq!(*cs.log.borrow(), ["validate None"]);
+    assert_eq!(*docs.log.borrow(), ["validate None"]);
+
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "cs rejected the AST".to_owned(),
+    });
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn no_code_is_generated_during_dry_runs() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&["--dry-run"]);
+    let cs = TestBackend::new("cs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs]);
+
+    // Assert
+    assert_eq!(*cs.log.borrow(), ["validate None"]);
+}
+
+#[test]
+fn no_backends_run_in_check_mode() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&["--check"]);
+    let mut cs = TestBackend::new("cs");
+    cs.fail_validation = true;
+
+    // Act
+    run_backends(&mut state, &options, &[&cs]);
+
+    // Assert
+    assert!(cs.log.borrow().is_empty());
+    assert!(state.diagnostics.is_empty());
+}
+
+#[test]
+fn no_backends_run_when_validating_references() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&["--validate-references"]);
+    let cs = TestBackend::new("cs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs]);
+
+    // Assert
+    assert!(cs.log.borrow().is_empty());
+}
+
+#[test]
+fn check_mode_cannot_be_combined_with_dry_runs() {
+    // Act
+    let result = SliceOptions::try_parse_from(["slicec", "test.slice", "--check", "--dry-run"]);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn unknown_backends_are_reported() {
+    // Arrange
+    let mut state = parse("module Test", None);
+    let options = options_from(&["--target", "java", "-X", "java:key", "-X", "py:key"]);
+    let cs = TestBackend::new("cs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs]);
+
+    // Assert
+    assert!(cs.log.borrow().is_empty());
+
+    let expected = [
+        Diagnostic::new(Error::UnknownBackend {
+            name: "java".to_owned(),
+        }),
+        Diagnostic::new(Error::UnknownBackend { name: "py".to_owned() }),
+    ];
+    check_diagnostics(state.diagnostics.into_inner(), expected);
+}
+
+#[test]
+fn identifiers_that_only_differ_in_case_collide_in_case_insensitive_languages() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            foo: int32
+            Foo: int32
+        }
+    ";
+    let mut state = parse(slice, None);
+    let backend = MappingBackend {
+        case_insensitive: true,
+        ..Default::default()
+    };
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&backend]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::GeneratedIdentifierCollision {
+        identifier: "Foo".to_owned(),
+        other_identifier: "foo".to_owned(),
+        backend: "mapping".to_owned(),
+    })
+    .add_note("'foo' was previously defined here", None)
+    .add_note(
+        "these identifiers are generated as 'foo' and 'Foo', which only differ in case",
+        None,
+    );
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn identifiers_only_collide_if_the_backend_provides_rules() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            foo: int32
+            Foo: int32
+        }
+    ";
+    let mut state = parse(slice, None);
+    let backend = TestBackend::new("cs");
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&backend]);
+
+    // Assert
+    assert!(state.diagnostics.is_empty());
+    assert_eq!(backend.log.borrow().len(), 2);
+}
+
+#[test_case("struct S { my_field: int32, myField: int32 }", "myField", "my_field"; "fields")]
+#[test_case("enum E : uint8 { first_value, FirstValue }", "FirstValue", "first_value"; "enumerators")]
+#[test_case("interface I { my_op()\n myOp() }", "myOp", "my_op"; "operations")]
+#[test_case("interface I { op(my_param: int32, myParam: int32) }", "myParam", "my_param"; "parameters")]
+#[test_case("struct my_struct {}\nstruct MyStruct {}", "MyStruct", "my_struct"; "module definitions")]
+fn identifiers_collide_after_case_conversion(definitions: &str, identifier: &str, other_identifier: &str) {
+    // Arrange
+    let slice = format!("module Test\n{definitions}");
+    let mut state = parse(slice, None);
+    let backend = MappingBackend {
+        case: Some(Case::Pascal),
+        ..Default::default()
+    };
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&backend]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::GeneratedIdentifierCollision {
+        identifier: identifier.to_owned(),
+        other_identifier: other_identifier.to_owned(),
+        backend: "mapping".to_owned(),
+    });
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn identifiers_collide_after_escaping() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            type: int32
+            r#type: int32
+        }
+    ";
+    let mut state = parse(slice, None);
+    let backend = MappingBackend {
+        reserved_words: Some(ReservedWords::new(&["type"], |identifier| format!("{identifier}_"))),
+        ..Default::default()
+    };
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&backend]);
+
+    // Assert
+    assert_eq!(state.diagnostics.into_inner().len(), 1);
+}
+
+#[test]
+fn identifiers_in_different_scopes_do_not_collide() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            foo: int32
+        }
+        struct T {
+            Foo: int32
+        }
+        interface I {
+            op(foo: int32) -> (Foo: int32, bar: int32)
+        }
+    ";
+    let mut state = parse(slice, None);
+    let backend = MappingBackend {
+        case_insensitive: true,
+        ..Default::default()
+    };
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&backend]);
+
+    // Assert
+    assert!(state.diagnostics.is_empty());
+}
+
+#[test]
+fn custom_types_must_be_mapped_by_every_backend() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [cs::type(\"System.Guid\")]
+        custom Guid
+
+        [cs::type(\"System.Uri\")]
+        [swift::type(\"URL\")]
+        custom Uri
+
+        custom Timestamp
+    ";
+    let mut state = parse(slice, None);
+    state.files[0].is_source = true;
+    let cs = CustomTypeBackend {
+        name: "cs",
+        directive: "cs::type",
+    };
+    let swift = CustomTypeBackend {
+        name: "swift",
+        directive: "swift::type",
+    };
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&cs, &swift]);
+
+    // Assert
+    let expected = [
+        Diagnostic::new(Error::MissingCustomTypeMappings {
+            identifier: "Guid".to_owned(),
+            backends: "'swift'".to_owned(),
+        })
+        .add_note(
+            "the 'swift' backend maps custom types with the 'swift::type' attribute",
+            None,
+        ),
+        Diagnostic::new(Error::MissingCustomTypeMappings {
+            identifier: "Timestamp".to_owned(),
+            backends: "'cs', 'swift'".to_owned(),
+        })
+        .add_note("the 'cs' backend maps custom types with the 'cs::type' attribute", None)
+        .add_note(
+            "the 'swift' backend maps custom types with the 'swift::type' attribute",
+            None,
+        ),
+    ];
+    check_diagnostics(state.diagnostics.into_inner(), expected);
+}
+
+#[test]
+fn custom_types_are_only_checked_for_selected_backends_with_mappings() {
+    // Arrange
+    let mut state = parse("module Test\ncustom Guid", None);
+    state.files[0].is_source = true;
+    let options = options_from(&["--target", "cs", "--target", "docs"]);
+    let cs = CustomTypeBackend {
+        name: "cs",
+        directive: "cs::type",
+    };
+    let swift = CustomTypeBackend {
+        name: "swift",
+        directive: "swift::type",
+    };
+    let docs = TestBackend::new("docs");
+
+    // Act
+    run_backends(&mut state, &options, &[&cs, &swift, &docs]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::MissingCustomTypeMappings {
+        identifier: "Guid".to_owned(),
+        backends: "'cs'".to_owned(),
+    });
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
@@ -0,0 +1,123 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::diagnostics::{AllowedBy, Baseline, Diagnostic, DiagnosticLevel};
+use slicec::slice_options::SliceOptions;
+
+const SLICE: &str = "
+    module Test
+
+    [deprecated]
+    struct Old {}
+
+    struct S {
+        a: Old
+        b: Old
+    }
+";
+
+/// Compiles the provided slice with the provided options, and returns its updated diagnostics.
+fn diagnostics_for(slice: &str, options: &SliceOptions) -> Vec<Diagnostic> {
+    let state = compile_from_strings(&[slice], Some(options), |_| {}, |_| {});
+    state.into_diagnostics(options)
+}
+
+/// Writes the provided baseline to a temporary file, and returns options which use it.
+fn options_with_baseline(name: &str, baseline: &Baseline) -> SliceOptions {
+    let path = std::env::temp_dir().join(format!("slicec-baseline-{name}-{}.json", std::process::id()));
+    baseline.write(path.to_str().unwrap()).unwrap();
+    SliceOptions {
+        baseline: Some(path.display().to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn baselines_record_warnings_with_their_counts() {
+    // Act
+    let baseline = Baseline::from_diagnostics(&diagnostics_for(SLICE, &SliceOptions::default()));
+
+    // Assert
+    assert_eq!(baseline.warnings.len(), 1);
+    let entry = &baseline.warnings[0];
+    assert_eq!(entry.path.as_deref(), Some("string-0"));
+    assert_eq!(entry.lint, "Deprecated");
+    assert_eq!(entry.message, "'Old' is deprecated");
+    assert_eq!(entry.count, 2);
+    assert_eq!(Baseline::from_json(&baseline.to_json()).unwrap(), baseline);
+}
+
+#[test]
+fn warnings_in_the_baseline_are_allowed() {
+    // Arrange
+    let baseline = Baseline::from_diagnostics(&diagnostics_for(SLICE, &SliceOptions::default()));
+    let options = options_with_baseline("allowed", &baseline);
+
+    // Act
+    let diagnostics = diagnostics_for(SLICE, &options);
+
+    // Assert
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in diagnostics {
+        assert_eq!(diagnostic.level(), DiagnosticLevel::Allowed);
+        assert_eq!(diagnostic.allowed_by(), Some(&AllowedBy::Baseline));
+    }
+}
+
+#[test]
+fn new_warnings_are_reported_as_errors() {
+    // Arrange
+    let baseline = Baseline::from_diagnostics(&diagnostics_for(SLICE, &SliceOptions::default()));
+    let options = options_with_baseline("new", &baseline);
+    let slice = SLICE.replace("b: Old", "b: Old\n        c: Old");
+
+    // Act
+    let diagnostics = diagnostics_for(&slice, &options);
+
+    // Assert
+    let levels = diagnostics.iter().map(Diagnostic::level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            DiagnosticLevel::Allowed,
+            DiagnosticLevel::Allowed,
+            DiagnosticLevel::Error
+        ],
+    );
+    let path = options.baseline.as_deref().unwrap();
+    let note = diagnostics[2].notes().last().unwrap();
+    assert_eq!(
+        note.message,
+        format!("this warning isn't recorded in the baseline '{path}'")
+    );
+}
+
+#[test]
+fn warnings_are_left_as_is_when_updating_the_baseline() {
+    // Arrange
+    let mut options = options_with_baseline("update", &Baseline::default());
+    options.update_baseline = true;
+
+    // Act
+    let diagnostics = diagnostics_for(SLICE, &options);
+
+    // Assert
+    assert!(diagnostics.iter().all(|d| d.level() == DiagnosticLevel::Warning));
+}
+
+#[test]
+fn unreadable_baselines_are_reported() {
+    // Arrange
+    let options = SliceOptions {
+        baseline: Some("does-not-exist.json".to_owned()),
+        ..Default::default()
+    };
+
+    // Act
+    let diagnostics = diagnostics_for(SLICE, &options);
+
+    // Assert
+    let error = diagnostics.last().unwrap();
+    assert_eq!(error.code(), "E001");
+    assert!(error.message().starts_with("unable to read 'does-not-exist.json'"));
+}
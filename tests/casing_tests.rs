@@ -0,0 +1,99 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::utils::casing::*;
+use test_case::test_case;
+
+#[test_case("myIdentifier", &["my", "Identifier"]; "camel case")]
+#[test_case("MyIdentifier", &["My", "Identifier"]; "pascal case")]
+#[test_case("my_identifier", &["my", "identifier"]; "snake case")]
+#[test_case("my-identifier", &["my", "identifier"]; "kebab case")]
+#[test_case("HTTPServer", &["HTTP", "Server"]; "leading acronym")]
+#[test_case("parseHTTP", &["parse", "HTTP"]; "trailing acronym")]
+#[test_case("utf8String", &["utf8", "String"]; "digits")]
+#[test_case("__my__identifier_", &["my", "identifier"]; "repeated separators")]
+#[test_case("", &[]; "empty")]
+fn identifiers_are_split_into_words(identifier: &str, expected: &[&str]) {
+    // Act
+    let words = split_words(identifier);
+
+    // Assert
+    assert_eq!(words, expected);
+}
+
+#[test_case(Case::Pascal, "HttpServerName")]
+#[test_case(Case::Camel, "httpServerName")]
+#[test_case(Case::Snake, "http_server_name")]
+#[test_case(Case::Kebab, "http-server-name")]
+fn identifiers_are_converted(case: Case, expected: &str) {
+    for identifier in [
+        "HTTPServerName",
+        "httpServerName",
+        "http_server_name",
+        "http-server-name",
+    ] {
+        // Act
+        let converted = convert_case(identifier, case);
+
+        // Assert
+        assert_eq!(converted, expected);
+    }
+}
+
+#[test_case(Case::Pascal, "IOStreamIO")]
+#[test_case(Case::Camel, "ioStreamIO")]
+#[test_case(Case::Snake, "io_stream_io")]
+fn acronyms_are_preserved(case: Case, expected: &str) {
+    // Act
+    let converted = convert_case_with_acronyms("io_stream_io", case, &["IO"]);
+
+    // Assert
+    assert_eq!(converted, expected);
+}
+
+#[test]
+fn reserved_words_are_escaped() {
+    // Arrange
+    let reserved_words = ReservedWords::new(&["class", "namespace"], |identifier| format!("@{identifier}"));
+
+    // Act
+    let escaped = reserved_words.escape("class");
+    let case_mismatch = reserved_words.escape("Class");
+    let unreserved = reserved_words.escape("classes");
+
+    // Assert
+    assert_eq!(escaped, "@class");
+    assert_eq!(case_mismatch, "Class");
+    assert_eq!(unreserved, "classes");
+}
+
+#[test]
+fn case_insensitive_reserved_words_are_escaped() {
+    // Arrange
+    let reserved_words = ReservedWords::new_case_insensitive(&["type"], |identifier| format!("{identifier}_"));
+
+    // Act
+    let escaped = reserved_words.escape("TYPE");
+
+    // Assert
+    assert_eq!(escaped, "TYPE_");
+}
+
+#[test]
+fn registry_escapes_with_the_table_for_each_language() {
+    // Arrange
+    let mut registry = ReservedWordsRegistry::default();
+    registry.register(
+        "cs",
+        ReservedWords::new(&["event"], |identifier| format!("@{identifier}")),
+    );
+    registry.register(
+        "rust",
+        ReservedWords::new(&["type"], |identifier| format!("r#{identifier}")),
+    );
+
+    // Act/Assert
+    assert_eq!(registry.escape("cs", "event"), "@event");
+    assert_eq!(registry.escape("cs", "type"), "type");
+    assert_eq!(registry.escape("rust", "type"), "r#type");
+    assert_eq!(registry.escape("python", "type"), "type");
+}
@@ -0,0 +1,184 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::checksums::generate_checksum_manifest;
+use slicec::grammar::*;
+use test_case::test_case;
+
+fn checksum_of(slice: &str, identifier: &str) -> String {
+    let ast = parse_for_ast(slice);
+    ast.find_element::<dyn Entity>(identifier).unwrap().checksum()
+}
+
+#[test]
+fn checksums_are_stable() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            a: int32
+            tag(1) b: string?
+        }
+    ";
+
+    // Act
+    let checksum = checksum_of(slice, "Test::S");
+
+    // Assert
+    assert_eq!(
+        checksum,
+        "fa819625f5235c3fc0c65ab85ed2cf4a3c78a4750d20a8606cfb2695f7dd330a",
+    );
+}
+
+#[test]
+fn checksums_ignore_comments_attributes_and_formatting() {
+    // Arrange
+    let slice1 = "
+        module Test
+        interface I {
+            op(a: int32) -> string
+        }
+    ";
+    let slice2 = "
+        module Test
+
+        /// This is a doc comment.
+        [deprecated]
+        interface I
+        {
+            // This is a comment.
+            [deprecated] op(
+                a:   int32,
+            )   ->   string
+        }
+    ";
+
+    // Act
+    let checksum1 = checksum_of(slice1, "Test::I");
+    let checksum2 = checksum_of(slice2, "Test::I");
+
+    // Assert
+    assert_eq!(checksum1, checksum2);
+}
+
+#[test]
+fn checksums_ignore_type_aliases() {
+    // Arrange
+    let slice1 = "
+        module Test
+        struct S { a: Sequence<int32> }
+    ";
+    let slice2 = "
+        module Test
+        typealias Alias = Sequence<int32>
+        struct S { a: Alias }
+    ";
+
+    // Act
+    let checksum1 = checksum_of(slice1, "Test::S");
+    let checksum2 = checksum_of(slice2, "Test::S");
+
+    // Assert
+    assert_eq!(checksum1, checksum2);
+}
+
+#[test_case("struct S { a: int64 }"; "field type")]
+#[test_case("struct S { b: int32 }"; "field identifier")]
+#[test_case("struct S { a: int32? }"; "field optionality")]
+#[test_case("struct S { tag(1) a: int32? }"; "field tag")]
+#[test_case("struct S { a: int32, b: int32 }"; "added field")]
+#[test_case("compact struct S { a: int32 }"; "compact")]
+#[test_case("module Other struct S { a: int32 }"; "scope")]
+fn checksums_change_with_structure(definition: &str) {
+    // Arrange
+    let original = "module Test struct S { a: int32 }";
+    let modified = if definition.starts_with("module") {
+        definition.to_owned()
+    } else {
+        format!("module Test {definition}")
+    };
+    let modified_identifier = modified.split_whitespace().nth(1).unwrap().to_owned() + "::S";
+
+    // Act
+    let checksum1 = checksum_of(original, "Test::S");
+    let checksum2 = checksum_of(&modified, &modified_identifier);
+
+    // Assert
+    assert_ne!(checksum1, checksum2);
+}
+
+#[test_case("enum E : uint8 { A, B = 2 }"; "enumerator value")]
+#[test_case("unchecked enum E : uint8 { A, B }"; "unchecked")]
+#[test_case("enum E : int16 { A, B }"; "underlying type")]
+#[test_case("enum E : uint8 { A, C }"; "enumerator identifier")]
+fn enum_checksums_change_with_structure(definition: &str) {
+    // Arrange
+    let original = "module Test enum E : uint8 { A, B }";
+    let modified = format!("module Test {definition}");
+
+    // Act
+    let checksum1 = checksum_of(original, "Test::E");
+    let checksum2 = checksum_of(&modified, "Test::E");
+
+    // Assert
+    assert_ne!(checksum1, checksum2);
+}
+
+#[test_case("idempotent op(a: int32) -> string"; "idempotent")]
+#[test_case("op(a: int32, b: int32) -> string"; "added parameter")]
+#[test_case("op(a: int32) -> (r: string, tag(1) s: bool?)"; "return members")]
+#[test_case("op(a: int32) -> string throws E"; "exception specification")]
+#[test_case("op2(a: int32) -> string"; "operation identifier")]
+fn operation_checksums_change_with_structure(operation: &str) {
+    // Arrange
+    let original = "mode = Slice1 module Test exception E {} interface I { op(a: int32) -> string }";
+    let modified = format!("mode = Slice1 module Test exception E {{}} interface I {{ {operation} }}");
+
+    // Act
+    let checksum1 = checksum_of(original, "Test::I");
+    let checksum2 = checksum_of(&modified, "Test::I");
+
+    // Assert
+    assert_ne!(checksum1, checksum2);
+}
+
+#[test]
+fn streamed_parameters_affect_checksums() {
+    // Arrange
+    let slice1 = "module Test interface I { op(a: int32) -> string }";
+    let slice2 = "module Test interface I { op(a: int32) -> stream string }";
+
+    // Act
+    let checksum1 = checksum_of(slice1, "Test::I");
+    let checksum2 = checksum_of(slice2, "Test::I");
+
+    // Assert
+    assert_ne!(checksum1, checksum2);
+}
+
+#[test]
+fn manifest_contains_types_and_interfaces() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {}
+        enum E : uint8 { A }
+        interface I {}
+        custom C
+        typealias T = S
+    ";
+    let ast = parse_for_ast(slice);
+
+    // Act
+    let manifest = generate_checksum_manifest(&ast);
+
+    // Assert
+    let identifiers = manifest.checksums.keys().collect::<Vec<_>>();
+    assert_eq!(identifiers, ["Test::C", "Test::E", "Test::I", "Test::S"]);
+
+    let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+    assert_eq!(manifest.checksums["Test::S"], struct_def.checksum());
+}
@@ -4,6 +4,7 @@ mod container;
 mod inheritance;
 mod mode_compatibility;
 mod tags;
+mod usage;
 
 use crate::test_helpers::*;
 use slicec::grammar::*;
@@ -0,0 +1,114 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::test_helpers::*;
+use slicec::grammar::*;
+
+#[test]
+fn types_transitively_containing_classes_use_classes() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class C {}
+        compact struct Inner {
+            d: Dictionary<string, Sequence<C>>
+        }
+        compact struct Outer {
+            i: Inner
+        }
+        compact struct Plain {
+            i: int32
+            s: Sequence<string>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert!(ast.find_element::<Class>("Test::C").unwrap().uses_classes());
+    assert!(ast.find_element::<Struct>("Test::Inner").unwrap().uses_classes());
+    assert!(ast.find_element::<Struct>("Test::Outer").unwrap().uses_classes());
+    assert!(!ast.find_element::<Struct>("Test::Plain").unwrap().uses_classes());
+}
+
+#[test]
+fn members_that_use_classes_can_be_queried() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class C {}
+        typealias Cs = Sequence<C>
+        compact struct S {
+            a: int32
+            b: AnyClass?
+            c: Cs
+            d: string
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+    let fields = struct_def.fields().into_iter().filter(|field| field.uses_classes());
+    let identifiers = fields.map(|field| field.identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["b", "c"]);
+}
+
+#[test]
+fn exceptions_use_classes_of_their_base_exceptions() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class C {}
+        exception Base {
+            c: C
+        }
+        exception Derived : Base {}
+        exception Other {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert!(ast.find_element::<Exception>("Test::Derived").unwrap().uses_classes());
+    assert!(!ast.find_element::<Exception>("Test::Other").unwrap().uses_classes());
+}
+
+#[test]
+fn class_usage_is_computed_through_cycles() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        compact struct A {
+            b: Sequence<B>
+        }
+        compact struct B {
+            a: Sequence<A>
+            c: C
+        }
+        compact struct D {
+            d: Sequence<D>
+        }
+        class C {}
+    ";
+
+    // Act
+    // These structs form infinite cycles, which are reported during validation, after class usage has been computed.
+    let ast = parse(slice, None).ast;
+
+    // Assert
+    assert!(ast.find_element::<Struct>("Test::A").unwrap().uses_classes());
+    assert!(ast.find_element::<Struct>("Test::B").unwrap().uses_classes());
+    assert!(!ast.find_element::<Struct>("Test::D").unwrap().uses_classes());
+}
@@ -0,0 +1,297 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod codec {
+    use crate::test_helpers::*;
+    use slicec::codec::*;
+    use slicec::grammar::*;
+    use slicec::slice_options::DecodeOptions;
+    use slicec::test_vectors::generate_test_vectors;
+    use test_case::test_case;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn every_test_vector_round_trips() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum E { A, B = 40 }
+            unchecked enum Shape { Circle(radius: float64), Rectangle(width: uint16, height: uint16?) }
+
+            struct S {
+                a: bool?
+                b: Sequence<string?>
+                c: Dictionary<varint62, E?>
+                d: Result<uuid, Shape>
+                tag(1) e: int16?
+            }
+        ";
+        let ast = parse_for_ast(slice);
+        let test_vectors = generate_test_vectors(&ast);
+        assert!(test_vectors.skipped.is_empty());
+
+        for vector in test_vectors.vectors {
+            let data_type = ast.find_element::<dyn Type>(&vector.type_name).unwrap();
+            let encoding = match vector.encoding.as_str() {
+                "Slice1" => Encoding::Slice1,
+                _ => Encoding::Slice2,
+            };
+
+            // Act
+            let value = decode(&from_hex(&vector.bytes), data_type, encoding, &ast).unwrap();
+
+            // Assert
+            assert_eq!(
+                value.to_json(),
+                vector.value,
+                "{} ({:?})",
+                vector.type_name,
+                vector.sample
+            );
+            let bytes = encode(&value, data_type, encoding, &ast).unwrap();
+            assert_eq!(bytes, from_hex(&vector.bytes));
+        }
+    }
+
+    #[test]
+    fn derived_class_instances_round_trip() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class Base { i: int32 }
+            class Derived : Base { tag(3) s: string? }
+            compact struct Holder { base: Base, other: AnyClass? }
+        ";
+        let ast = parse_for_ast(slice);
+        let holder = ast.find_element::<Struct>("Test::Holder").unwrap();
+        let value = Value::Struct(vec![
+            (
+                "base".to_owned(),
+                Value::Instance {
+                    type_name: "Test::Derived".to_owned(),
+                    fields: vec![
+                        ("i".to_owned(), Value::Integer(7)),
+                        ("s".to_owned(), Value::String("hi".to_owned())),
+                    ],
+                },
+            ),
+            ("other".to_owned(), Value::Null),
+        ]);
+
+        // Act
+        let bytes = encode(&value, holder, Encoding::Slice1, &ast).unwrap();
+        let decoded = decode(&bytes, holder, Encoding::Slice1, &ast).unwrap();
+
+        // Assert
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn unknown_tagged_fields_are_skipped() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S { tag(1) a: int32? }
+        ";
+        let ast = parse_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        // A value for the unknown tag 5 (holding 2 bytes), followed by the tag end marker.
+        let bytes = from_hex("1408abcdfc");
+
+        // Act
+        let value = decode(&bytes, struct_def, Encoding::Slice2, &ast).unwrap();
+
+        // Assert
+        assert_eq!(value, Value::Struct(vec![("a".to_owned(), Value::Null)]));
+    }
+
+    #[test]
+    fn unchecked_enums_can_hold_unknown_values() {
+        // Arrange
+        let ast = parse_for_ast("module Test\nunchecked enum E : uint8 { A }");
+        let enum_def = ast.find_element::<Enum>("Test::E").unwrap();
+        let value = Value::Enumerator {
+            identifier: None,
+            value: 9,
+            fields: None,
+        };
+
+        // Act
+        let bytes = encode(&value, enum_def, Encoding::Slice2, &ast).unwrap();
+        let decoded = decode(&bytes, enum_def, Encoding::Slice2, &ast).unwrap();
+
+        // Assert
+        assert_eq!(bytes, [9]);
+        assert_eq!(decoded, value);
+    }
+
+    #[test_case("00", CodecError::EndOfBuffer; "end of buffer")]
+    #[test_case("0000000000", CodecError::InvalidData("1 bytes remained in the buffer after decoding finished".to_owned()); "trailing bytes")]
+    fn invalid_payloads_are_rejected(hex: &str, expected: CodecError) {
+        // Arrange
+        let ast = parse_for_ast("module Test\ncompact struct S { i: int32 }");
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        // Act
+        let error = decode(&from_hex(hex), struct_def, Encoding::Slice2, &ast).unwrap_err();
+
+        // Assert
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn checked_enums_reject_unknown_values() {
+        // Arrange
+        let ast = parse_for_ast("module Test\nenum E : uint8 { A }");
+        let enum_def = ast.find_element::<Enum>("Test::E").unwrap();
+
+        // Act
+        let error = decode(&[9], enum_def, Encoding::Slice2, &ast).unwrap_err();
+
+        // Assert
+        assert_eq!(error.to_string(), "9 is not a valid value for the enum 'Test::E'");
+    }
+
+    #[test_case(Value::Bool(true), "expected a value of type 'S', found bool"; "type mismatch")]
+    #[test_case(Value::Struct(vec![]), "missing a value for the non-optional field 'i'"; "missing field")]
+    #[test_case(
+        Value::Struct(vec![("i".to_owned(), Value::Integer(300))]),
+        "300 is out of range for 'uint8'";
+        "out of range"
+    )]
+    fn invalid_values_are_rejected(value: Value, expected_message: &str) {
+        // Arrange
+        let ast = parse_for_ast("module Test\ncompact struct S { i: uint8 }");
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        // Act
+        let error = encode(&value, struct_def, Encoding::Slice2, &ast).unwrap_err();
+
+        // Assert
+        assert_eq!(error.to_string(), expected_message);
+    }
+
+    #[test_case("01 0a ff", &[0x01, 0x0A, 0xFF]; "with whitespace")]
+    #[test_case("0x010aFF", &[0x01, 0x0A, 0xFF]; "with prefix")]
+    #[test_case("", &[]; "empty")]
+    fn hex_payloads_can_be_parsed(hex: &str, expected: &[u8]) {
+        // Act
+        let bytes = parse_hex(hex).unwrap();
+
+        // Assert
+        assert_eq!(bytes, expected);
+    }
+
+    #[test_case("0g", "'g' is not a hexadecimal digit"; "invalid digit")]
+    #[test_case("010", "hexadecimal payloads must contain an even number of digits"; "odd length")]
+    fn invalid_hex_payloads_are_rejected(hex: &str, expected_message: &str) {
+        // Act
+        let error = parse_hex(hex).unwrap_err();
+
+        // Assert
+        assert_eq!(error.to_string(), expected_message);
+    }
+
+    #[test]
+    fn payloads_are_displayed_field_by_field() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                a: int32?
+                b: Sequence<string>
+                c: Dictionary<uint8, bool>
+                tag(1) d: uint8?
+            }
+        ";
+        let state = parse(slice, None);
+        let options = DecodeOptions {
+            type_name: "::Test::S".to_owned(),
+            payload: "01 05000000 04 08 6869 00 fc".to_owned(),
+            ..Default::default()
+        };
+
+        // Act
+        let value = decode_payload(&options, &state).unwrap();
+
+        // Assert
+        let expected = "\
+{
+    a: 5
+    b: [
+        \"hi\"
+    ]
+    c: {}
+    d: null
+}";
+        assert_eq!(value.to_string(), expected);
+    }
+
+    #[test_case(false, "07000000080c086869fc", "x", "y"; "arguments")]
+    #[test_case(true, "01fc", "r", "s"; "return value")]
+    fn operation_payloads_can_be_decoded(return_value: bool, payload: &str, untagged: &str, tagged: &str) {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                op(x: int32, tag(2) y: string?) -> (r: bool, tag(1) s: int8?)
+            }
+        ";
+        let state = parse(slice, None);
+        let options = DecodeOptions {
+            type_name: "Test::I::op".to_owned(),
+            payload: payload.to_owned(),
+            return_value,
+            ..Default::default()
+        };
+
+        // Act
+        let value = decode_payload(&options, &state).unwrap();
+
+        // Assert
+        let Value::Struct(fields) = value else { panic!("expected a struct, found {value}") };
+        assert_eq!(fields[0].0, untagged);
+        assert_eq!(fields[1].0, tagged);
+        assert_eq!(fields[1].1 == Value::Null, return_value);
+    }
+
+    #[test]
+    fn slice1_payloads_are_decoded_with_the_mode_of_their_file() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            interface I {
+                op(x: int32, tag(2) y: int32?)
+            }
+        ";
+        let state = parse(slice, None);
+        let options = DecodeOptions {
+            type_name: "Test::I::op".to_owned(),
+            payload: "07000000 12 09000000".to_owned(),
+            ..Default::default()
+        };
+
+        // Act
+        let value = decode_payload(&options, &state).unwrap();
+
+        // Assert
+        let expected = vec![("x".to_owned(), Value::Integer(7)), ("y".to_owned(), Value::Integer(9))];
+        assert_eq!(value, Value::Struct(expected));
+    }
+}
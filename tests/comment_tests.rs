@@ -7,6 +7,7 @@ mod comments {
     use crate::test_helpers::*;
     use slicec::diagnostics::{Diagnostic, Error, Lint};
     use slicec::grammar::*;
+    use slicec::rendering::render_doc_comment;
     use test_case::test_case;
 
     #[test]
@@ -41,6 +42,31 @@ mod comments {
         assert_eq!(newline, "\n");
     }
 
+    #[test]
+    fn doc_comments_can_contain_unicode() {
+        // Arrange
+        let slice = "
+            module tests
+
+            /// Ünïcödé, 非推奨, and 😊.
+            interface MyInterface {}
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let interface_def = ast.find_element::<Interface>("tests::MyInterface").unwrap();
+
+        // Columns count characters, so each of these characters only occupies a single column.
+        let overview = interface_def.comment().unwrap().overview.as_ref().unwrap();
+        assert_eq!(overview.span.start, (4, 16).into());
+        assert_eq!(overview.span.end, (4, 37).into());
+
+        let MessageComponent::Text(text) = &overview.value[0] else { panic!() };
+        assert_eq!(text, "Ünïcödé, 非推奨, and 😊.");
+    }
+
     #[test]
     fn multi_line_doc_comment() {
         // Arrange
@@ -301,6 +327,68 @@ mod comments {
         assert_eq!(link_identifier.span.end, (5, 31).into());
     }
 
+    #[test]
+    fn doc_comments_store_the_raw_text_of_each_element() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module tests
+
+            interface TestInterface {
+                /// An  overview with a {@link   TestInterface}.
+                ///   It spans two lines.
+                /// @param testParam: the first line,
+                ///     and an indented second line.
+                /// @returns:   a value
+                /// @throws MyException:  an exception
+                /// @see TestInterface
+                testOp(testParam: string) -> bool throws MyException
+            }
+
+            exception MyException {}
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let operation = ast.find_element::<Operation>("tests::TestInterface::testOp").unwrap();
+        let comment = operation.comment().unwrap();
+        assert_eq!(
+            comment.raw_text,
+            " An  overview with a {@link   TestInterface}.
+   It spans two lines.
+ @param testParam: the first line,
+     and an indented second line.
+ @returns:   a value
+ @throws MyException:  an exception
+ @see TestInterface"
+        );
+
+        let overview = comment.overview.as_ref().unwrap();
+        assert_eq!(
+            overview.raw_text,
+            " An  overview with a {@link   TestInterface}.\n   It spans two lines."
+        );
+        let MessageComponent::Link(link_tag) = &overview.value[1] else { panic!() };
+        assert_eq!(link_tag.raw_text, "@link   TestInterface");
+
+        let param_tag = &comment.params[0];
+        assert_eq!(
+            param_tag.raw_text,
+            "@param testParam: the first line,\n     and an indented second line."
+        );
+        assert_eq!(
+            param_tag.message.raw_text,
+            ": the first line,\n     and an indented second line."
+        );
+
+        assert_eq!(comment.returns[0].raw_text, "@returns:   a value");
+        assert_eq!(comment.returns[0].message.raw_text, ":   a value");
+        assert_eq!(comment.throws[0].raw_text, "@throws MyException:  an exception");
+        assert_eq!(comment.see[0].raw_text, "@see TestInterface");
+    }
+
     #[test_case("/* This is a block comment. */"; "block comment")]
     #[test_case("/*\n* This is a multiline block comment.\n */"; "multi-line block comment")]
     #[test_case("// This is a comment."; "comment")]
@@ -392,6 +480,148 @@ mod comments {
         check_diagnostics(diagnostics, [expected]);
     }
 
+    #[test_case("parm", "param"; "missing character")]
+    #[test_case("sea", "see"; "wrong character")]
+    #[test_case("Throws", "throws"; "wrong case")]
+    fn misspelled_doc_comment_tags_suggest_the_correct_tag(tag: &str, suggestion: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module tests
+
+            interface I {{
+                /// @{tag} foo: a description.
+                op(foo: int32) -> int32
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Lint::MalformedDocComment {
+            message: format!("unknown doc comment tag '{tag}'"),
+        })
+        .add_note(format!("did you mean '@{suggestion}'?"), None);
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn missing_colon_after_param_identifier_is_reported() {
+        // Arrange
+        let slice = "
+            module tests
+
+            interface I {
+                /// @param foo The parameter.
+                op(foo: int32)
+            }
+            ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let span = diagnostics[0].span().unwrap();
+        assert_eq!(span.start, (5, 32).into());
+        assert_eq!(span.end, (5, 35).into());
+
+        let expected = Diagnostic::new(Lint::MalformedDocComment {
+            message: "expected ':' before the tag's description, but found 'The'".to_owned(),
+        })
+        .add_note("block tags must be separated from their descriptions by a ':'", None);
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn unterminated_inline_tags_are_reported() {
+        // Arrange
+        let slice = "
+            module tests
+
+            /// A test struct. Similar to {@link OtherStruct
+            struct TestStruct {}
+            ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let span = diagnostics[0].span().unwrap();
+        assert_eq!(span.start, (4, 43).into());
+        assert_eq!(span.end, (4, 61).into());
+
+        let expected = Diagnostic::new(Lint::MalformedDocComment {
+            message: "missing a closing '}' on an inline doc comment tag".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn duplicate_doc_comment_tags_are_reported() {
+        // Arrange
+        let slice = "
+            module tests
+
+            interface I {
+                /// @param foo: the first description.
+                /// @param foo: the second description.
+                /// @returns: the first description.
+                /// @returns: the second description.
+                op(foo: int32) -> int32
+            }
+            ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let operation = ast.find_element::<Operation>("tests::I::op").unwrap();
+        let comment = operation.comment().unwrap();
+        assert_eq!(comment.params.len(), 2);
+        assert_eq!(comment.returns.len(), 2);
+
+        let span = diagnostics[0].span().unwrap();
+        assert_eq!(span.start, (6, 21).into());
+        assert_eq!(span.end, (6, 31).into());
+        assert_eq!(diagnostics[0].notes()[0].span.as_ref().unwrap().start, (5, 21).into());
+
+        let expected = [
+            Diagnostic::new(Lint::MalformedDocComment {
+                message: "duplicate '@param' tag for 'foo'".to_owned(),
+            })
+            .add_note("'foo' was previously documented here", None),
+            Diagnostic::new(Lint::MalformedDocComment {
+                message: "duplicate '@returns' tag".to_owned(),
+            })
+            .add_note("'@returns' was previously used here", None),
+        ];
+        check_diagnostics(diagnostics, expected);
+    }
+
+    #[test]
+    fn throws_tags_for_different_exceptions_are_not_duplicates() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module tests
+
+            exception E1 {}
+            exception E2 {}
+
+            interface I {
+                /// @throws E1: the first exception.
+                /// @throws E2: the second exception.
+                op() throws (E1, E2)
+            }
+            ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
     #[test]
     fn missing_doc_comment_linked_identifiers() {
         // Arrange
@@ -585,6 +815,7 @@ mod comments {
     }
 
     #[test]
+    #[allow(clippy::useless_format)]
     fn throws_tag_is_rejected_for_operations_that_do_not_throw() {
         // Arrange
         let slice = format!(
@@ -811,4 +1042,117 @@ mod comments {
         });
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test]
+    fn doc_comments_can_be_localized() {
+        // Arrange
+        let slice = "
+            module tests
+
+            interface I {
+                /// Sends a greeting.
+                /// @param name: who to greet.
+                /// @locale fr: Envoie une salutation.
+                /// @param name: qui saluer.
+                /// @locale pt_BR
+                /// Envia uma saudação.
+                op(name: string)
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let operation = ast.find_element::<Operation>("tests::I::op").unwrap();
+        let comment = operation.comment().unwrap();
+        assert_eq!(comment.locales(), ["fr", "pt_BR"]);
+        assert_eq!(comment.params.len(), 1);
+
+        let french = comment.localized("fr");
+        assert_eq!(french.locale(), Some("fr"));
+        assert_eq!(french.params.len(), 1);
+        assert_eq!(french.span.start, (7, 21).into());
+        let MessageComponent::Text(text) = &french.overview.as_ref().unwrap().value[0] else { panic!() };
+        assert_eq!(text, "Envoie une salutation.");
+        let MessageComponent::Text(text) = &french.params[0].message.value[0] else { panic!() };
+        assert_eq!(text, "qui saluer.");
+
+        // Locales are matched case-insensitively, and unknown locales fall back to the original comment.
+        let portuguese = comment.localized("pt_br");
+        assert!(portuguese.params.is_empty());
+        let MessageComponent::Text(text) = &portuguese.overview.as_ref().unwrap().value[0] else { panic!() };
+        assert_eq!(text, "Envia uma saudação.");
+        assert_eq!(comment.localized("de").locale(), None);
+    }
+
+    #[test]
+    fn links_in_localized_doc_comments_are_resolved() {
+        // Arrange
+        let slice = "
+            module tests
+
+            /// See {@link S}.
+            /// @locale fr: Voir {@link S}.
+            /// @see S
+            struct S {}
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let comment = ast.find_element::<Struct>("tests::S").unwrap().comment().unwrap();
+        let french = comment.localized("fr");
+        assert_eq!(render_doc_comment(french), "Voir tests::S.\n\nSee:\n- tests::S");
+        assert_eq!(render_doc_comment(comment), "See tests::S.");
+    }
+
+    #[test]
+    fn localized_doc_comments_are_validated() {
+        // Arrange
+        let slice = "
+            module tests
+
+            interface I {
+                /// @param name: who to greet.
+                /// @locale fr
+                /// @param nom: qui saluer.
+                op(name: string)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Lint::IncorrectDocComment {
+            message: "comment has a 'param' tag for 'nom', but operation 'op' has no parameter with that name"
+                .to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn duplicate_locales_are_reported() {
+        // Arrange
+        let slice = "
+            module tests
+
+            /// A struct.
+            /// @locale fr: Une structure.
+            /// @locale fr: Une autre structure.
+            struct S {}
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Lint::MalformedDocComment {
+            message: "duplicate '@locale' tag for 'fr'".to_owned(),
+        })
+        .add_note("'fr' was previously documented here", None);
+        check_diagnostics(diagnostics, [expected]);
+    }
 }
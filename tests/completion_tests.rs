@@ -0,0 +1,136 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compilation_state::CompilationState;
+use slicec::compile_from_strings;
+use slicec::completion::*;
+use slicec::slice_file::Location;
+use test_case::test_case;
+
+/// Compiles the provided slices, and returns the location of the '$' marker in the first one (which is removed).
+/// Candidates are taken from the AST, so tests which check them place the marker before an identifier in a valid slice.
+fn compile_with_marker(slices: &[&str]) -> (CompilationState, Location) {
+    let offset = slices[0].find('$').expect("missing '$' marker");
+    let first = slices[0].replace('$', "");
+    let slices = std::iter::once(first.as_str())
+        .chain(slices[1..].iter().copied())
+        .collect::<Vec<_>>();
+
+    let state = compile_from_strings(&slices, None, |_| {}, |_| {});
+    let location = state.files[0].position_at(offset).unwrap();
+    (state, location)
+}
+
+fn context_of(slice: &str) -> CompletionContext {
+    let (state, location) = compile_with_marker(&[slice]);
+    completion_context(&state.files[0], location)
+}
+
+fn labels_of(slices: &[&str], kind: CompletionKind) -> Vec<String> {
+    let (state, location) = compile_with_marker(slices);
+    let completions = completions(&state.ast, &state.files[0], location);
+    completions
+        .into_iter()
+        .filter(|completion| completion.kind == kind)
+        .map(|completion| completion.label)
+        .collect()
+}
+
+#[test_case("struct S { a: $ }", CompletionContext::Type; "field type")]
+#[test_case("struct S { a: Seq$ }", CompletionContext::Type; "partially typed field type")]
+#[test_case("struct S { a: Dictionary<int32, $> }", CompletionContext::Type; "generic argument")]
+#[test_case("interface I { op() -> $ }", CompletionContext::Type; "return type")]
+#[test_case("typealias T = $", CompletionContext::Type; "type alias")]
+#[test_case("enum E : $ { A }", CompletionContext::UnderlyingType; "enum underlying type")]
+#[test_case("interface I : J, $ {}", CompletionContext::BaseInterface; "base interface")]
+#[test_case("enum E { A, B = $ }", CompletionContext::ConstantExpression; "enumerator value")]
+#[test_case("const X = $", CompletionContext::ConstantExpression; "constant value")]
+#[test_case("[$] struct S {}", CompletionContext::AttributeDirective; "attribute directive")]
+#[test_case("[deprecated(\"a\"), $] struct S {}", CompletionContext::AttributeDirective; "second attribute")]
+#[test_case("[allow($)] struct S {}", CompletionContext::None; "attribute argument")]
+#[test_case("struct S { $ }", CompletionContext::None; "field identifier")]
+fn contexts_are_determined_from_preceding_tokens(definition: &str, expected: CompletionContext) {
+    // Arrange
+    let slice = format!("module Test\n{definition}\n");
+
+    // Act
+    let context = context_of(&slice);
+
+    // Assert
+    assert_eq!(context, expected);
+}
+
+#[test_case("exception E : $ {}", CompletionContext::BaseException; "base exception")]
+#[test_case("class C : $ {}", CompletionContext::BaseClass; "base class")]
+#[test_case("interface I { op() throws $ }", CompletionContext::ThrownException; "exception specification")]
+#[test_case("interface I { op() throws (A, $) }", CompletionContext::ThrownException; "exception list")]
+fn slice1_contexts_are_determined_from_preceding_tokens(definition: &str, expected: CompletionContext) {
+    // Arrange
+    let slice = format!("mode = Slice1\nmodule Test\n{definition}\n");
+
+    // Act
+    let context = context_of(&slice);
+
+    // Assert
+    assert_eq!(context, expected);
+}
+
+#[test]
+fn types_are_labeled_with_the_shortest_identifier_that_resolves_to_them() {
+    // Arrange
+    let slice1 = "
+        module Foo
+        using Baz = Other::Baz
+        struct S {}
+        compact struct Wrapper {
+            a: $int32
+        }
+    ";
+    let slice2 = "
+        module Other::Baz
+        struct S {}
+        enum E { A }
+    ";
+
+    // Act
+    let structs = labels_of(&[slice1, slice2], CompletionKind::Struct);
+    let enums = labels_of(&[slice1, slice2], CompletionKind::Enum);
+
+    // Assert
+    assert_eq!(structs, ["Baz::S", "S", "Wrapper"]);
+    assert_eq!(enums, ["Baz::E"]);
+}
+
+#[test]
+fn previous_enumerators_and_constants_are_suggested_for_constant_expressions() {
+    // Arrange
+    let slice = "
+        module Test
+        const X = 1
+        enum E : uint8 {
+            A
+            B = $X
+            C
+        }
+    ";
+
+    // Act
+    let enumerators = labels_of(&[slice], CompletionKind::Enumerator);
+    let constants = labels_of(&[slice], CompletionKind::Constant);
+
+    // Assert
+    assert_eq!(enumerators, ["A"]);
+    assert_eq!(constants, ["X"]);
+}
+
+#[test]
+fn attribute_directives_are_suggested() {
+    // Arrange
+    let slice = "module Test\n[$] struct S {}";
+
+    // Act
+    let directives = labels_of(&[slice], CompletionKind::AttributeDirective);
+
+    // Assert
+    assert!(directives.contains(&"deprecated".to_owned()));
+    assert!(directives.contains(&"allow".to_owned()));
+}
@@ -0,0 +1,345 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod constants {
+    use crate::test_helpers::*;
+    use slicec::diagnostics::{Diagnostic, Error};
+    use slicec::grammar::*;
+    use slicec::slice_file::Span;
+    use test_case::test_case;
+
+    #[test_case("1 << 4", 16; "shift left")]
+    #[test_case("256 >> 4", 16; "shift right")]
+    #[test_case("2 + 3 * 4", 14; "multiplication before addition")]
+    #[test_case("(2 + 3) * 4", 20; "parentheses")]
+    #[test_case("10 - 4 - 3", 3; "subtraction is left associative")]
+    #[test_case("0b1100 | 0b0011", 15; "bitwise or")]
+    #[test_case("0b1100 & 0b0110", 4; "bitwise and")]
+    #[test_case("0b1100 ^ 0b0110", 10; "bitwise xor")]
+    #[test_case("~0", -1; "bitwise not")]
+    #[test_case("-(1 << 3)", -8; "negation")]
+    #[test_case("1 + 1 << 2", 8; "addition before shift")]
+    fn expressions_are_evaluated(expression: &str, expected: i128) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            const C = {expression}
+            "
+        );
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let constant = ast.find_element::<Constant>("Test::C").unwrap();
+        assert_eq!(constant.value(), expected);
+    }
+
+    #[test]
+    fn constants_can_reference_other_constants() {
+        // Arrange
+        let slice = "
+            module Test
+
+            const BASE = 100
+            const OFFSET = BASE + 2
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let constant = ast.find_element::<Constant>("Test::OFFSET").unwrap();
+        assert_eq!(constant.value(), 102);
+    }
+
+    #[test]
+    fn expressions_can_be_used_for_enumerator_values() {
+        // Arrange
+        let slice = "
+            module Test
+
+            const BASE = 10
+
+            enum E : uint16 {
+                A = 1 << 4
+                B = BASE + 2
+                C
+                D = A | B
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let values = ["A", "B", "C", "D"].map(|name| {
+            let enumerator = ast.find_element::<Enumerator>(&format!("Test::E::{name}")).unwrap();
+            enumerator.value()
+        });
+        assert_eq!(values, [16, 12, 13, 28]);
+    }
+
+    #[test]
+    fn expressions_can_be_used_for_tags() {
+        // Arrange
+        let slice = "
+            module Test
+
+            const BASE = 10
+
+            struct S {
+                tag(BASE + 1) a: int32?
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let field = ast.find_element::<Field>("Test::S::a").unwrap();
+        assert_eq!(field.tag.as_ref().unwrap().value, 11);
+    }
+
+    #[test]
+    fn expressions_can_be_used_for_compact_ids() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            const BASE = 1 << 8
+
+            class C(BASE + 3) {}
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let class = ast.find_element::<Class>("Test::C").unwrap();
+        assert_eq!(class.compact_id.as_ref().unwrap().value, 259);
+    }
+
+    #[test]
+    fn constants_can_be_referenced_before_they_are_declared() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum E : uint8 {
+                A = B + 1
+            }
+
+            const B = C * 2
+            const C = 4
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let enumerator = ast.find_element::<Enumerator>("Test::E::A").unwrap();
+        assert_eq!(enumerator.value(), 9);
+    }
+
+    #[test]
+    fn constants_can_be_referenced_from_other_files() {
+        // Arrange
+        let slice1 = "
+            module Test
+
+            struct S {
+                tag(BASE) a: int32?
+            }
+        ";
+        let slice2 = "
+            module Test
+
+            const BASE = 3
+        ";
+
+        // Act
+        let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+        // Assert
+        let field = ast.find_element::<Field>("Test::S::a").unwrap();
+        assert_eq!(field.tag.as_ref().unwrap().value, 3);
+    }
+
+    #[test]
+    fn cyclic_constants_are_rejected() {
+        // Arrange
+        let slice = "
+            module Test
+
+            const A = B + 1
+            const B = A
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::CyclicConstantExpression {
+            identifier: "A".to_owned(),
+            cycle: "A -> B -> A".to_owned(),
+        })
+        .set_span(&Span::new((4, 13).into(), (4, 20).into(), "string-0"));
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn references_must_be_constants() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {}
+            const A = S + 1
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::TypeMismatch {
+            expected: "constant".to_owned(),
+            actual: "struct".to_owned(),
+            is_concrete: true,
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("170141183460469231731687303715884105727 + 1"; "addition")]
+    #[test_case("-170141183460469231731687303715884105727 - 2"; "subtraction")]
+    #[test_case("1 << 127"; "shift left")]
+    #[test_case("(1 << 126) * 2"; "multiplication")]
+    fn overflows_are_reported(expression: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            const C = {expression}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ConstantExpressionOverflows);
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("1 << 128"; "too large")]
+    #[test_case("1 >> -1"; "negative")]
+    fn shift_amounts_must_be_in_range(expression: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            const C = {expression}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ConstantExpressionOverflows)
+            .set_span(&Span::new(
+                (4, 23).into(),
+                (4, 23 + expression.len()).into(),
+                "string-0",
+            ))
+            .add_note("shift amounts must be between 0 and 127", None);
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("1 < < 2", "<<"; "shift left")]
+    #[test_case("256 >\t> 2", ">>"; "shift right")]
+    fn shift_operators_cannot_contain_whitespace(expression: &str, operator: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            const C = {expression}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let start = expression.find(' ').unwrap() + 1;
+        let end = expression.rfind(operator.chars().next().unwrap()).unwrap() + 1;
+        let expected = Diagnostic::new(Error::Syntax {
+            message: format!("expected '{operator}', but found whitespace between its chevrons"),
+        })
+        .set_span(&Span::new((4, 23 + start).into(), (4, 23 + end).into(), "string-0"));
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn const_can_be_used_as_an_identifier() {
+        // Arrange
+        let slice = "
+            module Test
+
+            typealias T = string
+            const A = 1
+
+            struct S {
+                const: int32
+            }
+
+            enum E {
+                const
+                B = const
+            }
+
+            interface I {
+                op(const: int32) -> const
+            }
+
+            custom const
+            const C = 2
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        assert!(ast.find_element::<Field>("Test::S::const").is_ok());
+        assert_eq!(ast.find_element::<Enumerator>("Test::E::B").unwrap().value(), 0);
+        assert!(ast.find_element::<Parameter>("Test::I::op::const").is_ok());
+        assert!(ast.find_element::<CustomType>("Test::const").is_ok());
+        assert_eq!(ast.find_element::<Constant>("Test::A").unwrap().value(), 1);
+        assert_eq!(ast.find_element::<Constant>("Test::C").unwrap().value(), 2);
+    }
+
+    #[test_case("const /* comment */ C = 1"; "block comment")]
+    #[test_case("const // comment\n C = 1"; "line comment")]
+    fn const_can_be_followed_by_comments(constant: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+            {constant}
+            "
+        );
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        assert_eq!(ast.find_element::<Constant>("Test::C").unwrap().value(), 1);
+    }
+}
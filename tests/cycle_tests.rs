@@ -159,6 +159,88 @@ mod container {
     }
 }
 
+mod optional {
+    use super::*;
+    use slicec::slice_file::Span;
+
+    #[test]
+    fn cycles_through_optionals_are_allowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Node {
+                next: Node?
+            }
+
+            struct A {
+                b: B
+            }
+
+            struct B {
+                a: A?
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn cycles_through_optional_elements_are_allowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Node {
+                children: Sequence<Node?>
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn cycles_are_rendered_with_a_note_for_each_link() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct A { b: B }
+            struct B { c: C }
+            struct C { a: A, o: A? }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert: the optional field 'o' doesn't cause a cycle, so only 'a' is reported.
+        let expected = Diagnostic::new(Error::InfiniteSizeCycle {
+            type_id: "Test::A".to_owned(),
+            cycle: "Test::A -> Test::B -> Test::C -> Test::A".to_owned(),
+        })
+        .set_span(&Span::new((4, 13).into(), (4, 21).into(), "string-0"))
+        .add_note(
+            "struct 'A' contains a field named 'b' that is of type 'B'",
+            Some(&Span::new((4, 24).into(), (4, 28).into(), "string-0")),
+        )
+        .add_note(
+            "struct 'B' contains a field named 'c' that is of type 'C'",
+            Some(&Span::new((5, 24).into(), (5, 28).into(), "string-0")),
+        )
+        .add_note(
+            "struct 'C' contains a field named 'a' that is of type 'A'",
+            Some(&Span::new((6, 24).into(), (6, 28).into(), "string-0")),
+        )
+        .add_note(
+            "cycles can be broken by making one of these fields optional, or by using a class",
+            None,
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+}
+
 mod builtin {
     use super::*;
     use slicec::slice_file::Span;
@@ -185,6 +267,10 @@ mod builtin {
         .add_note(
             "struct 'Foo' contains a field named 'f' that is of type 'Result<Foo, bool>'",
             Some(&Span::new((5, 17).into(), (5, 37).into(), "string-0")),
+        )
+        .add_note(
+            "cycles can be broken by making one of these fields optional, or by using a class",
+            None,
         );
 
         check_diagnostics(diagnostics, [expected]);
@@ -212,6 +298,10 @@ mod builtin {
         .add_note(
             "struct 'Foo' contains a field named 'f' that is of type 'Sequence<Foo>'",
             Some(&Span::new((5, 17).into(), (5, 33).into(), "string-0")),
+        )
+        .add_note(
+            "cycles can be broken by making one of these fields optional, or by using a class",
+            None,
         );
 
         check_diagnostics(diagnostics, [expected]);
@@ -239,6 +329,10 @@ mod builtin {
         .add_note(
             "struct 'Foo' contains a field named 'f' that is of type 'Dictionary<Foo, bool>'",
             Some(&Span::new((5, 17).into(), (5, 41).into(), "string-0")),
+        )
+        .add_note(
+            "cycles can be broken by making one of these fields optional, or by using a class",
+            None,
         );
 
         check_diagnostics(diagnostics, [expected]);
@@ -345,3 +439,108 @@ mod type_aliases {
         check_diagnostics(diagnostics, expected);
     }
 }
+
+mod boxed {
+    use super::*;
+    use slicec::grammar::*;
+
+    #[test]
+    fn cycles_through_boxed_elements_are_allowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Tree {
+                children: Sequence<[boxed] Tree>
+            }
+
+            struct Graph {
+                neighbors: Dictionary<string, [boxed] Graph>
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let tree = ast.find_element::<Struct>("Test::Tree").unwrap();
+        let Types::Sequence(sequence) = tree.fields()[0].data_type.concrete_type() else {
+            panic!("expected a sequence")
+        };
+        assert!(sequence.element_type.is_boxed());
+        assert_eq!(sequence.element_type.fixed_wire_size(), None);
+    }
+
+    #[test]
+    fn direct_cycles_through_boxed_types_are_disallowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Foo {
+                f: [boxed] Foo
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::InfiniteSizeCycle {
+            type_id: "Test::Foo".to_owned(),
+            cycle: "Test::Foo -> Test::Foo".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn only_structs_and_enums_can_be_boxed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Foo {
+                f: Sequence<[boxed] string>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: "boxed".to_owned(),
+        })
+        .add_note(
+            "the boxed attribute can only be applied to type references of structs and enums",
+            None,
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn boxed_types_are_not_supported_in_slice1_mode() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            compact struct Foo {
+                f: Sequence<[boxed] Foo>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::BoxedTypesNotSupported {
+            kind: "struct".to_owned(),
+        })
+        .add_note(
+            "Slice1 can express recursive data structures with classes instead",
+            None,
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+}
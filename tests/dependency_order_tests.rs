@@ -0,0 +1,169 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::ast::Ast;
+
+fn dependency_order(ast: &Ast) -> Vec<String> {
+    let definitions = ast.definitions_in_dependency_order().into_iter();
+    definitions
+        .map(|definition| definition.parser_scoped_identifier())
+        .collect()
+}
+
+#[test]
+fn definitions_come_after_their_dependencies() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {
+            op(a: A) -> Z
+        }
+
+        struct A {
+            b: B
+        }
+
+        struct B {
+            c: Sequence<C>
+        }
+
+        enum C { X, Y }
+
+        custom Z
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert_eq!(
+        dependency_order(&ast),
+        ["Test::C", "Test::B", "Test::A", "Test::Z", "Test::I"]
+    );
+}
+
+#[test]
+fn type_aliases_come_between_their_types_and_their_users() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct U {
+            t: T
+        }
+
+        typealias T = Dictionary<string, S>
+
+        struct S {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert_eq!(dependency_order(&ast), ["Test::S", "Test::T", "Test::U"]);
+}
+
+#[test]
+fn independent_definitions_are_ordered_by_identifier() {
+    // Arrange
+    let slice1 = "
+        module Test
+        struct B {}
+    ";
+    let slice2 = "
+        module Test
+        struct A {}
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+    // Assert
+    assert_eq!(dependency_order(&ast), ["Test::A", "Test::B"]);
+}
+
+#[test]
+fn class_cycles_are_broken_at_the_first_identifier() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class B {
+            a: A?
+        }
+
+        class A {
+            b: B?
+        }
+
+        class C : B {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert_eq!(dependency_order(&ast), ["Test::A", "Test::B", "Test::C"]);
+}
+
+#[test]
+fn definitions_which_depend_on_a_cycle_come_after_it() {
+    // Arrange
+    let slice1 = "
+        mode = Slice1
+        module Z
+
+        class C1 {
+            c2: C2?
+        }
+
+        class C2 {
+            c1: C1?
+        }
+    ";
+    let slice2 = "
+        mode = Slice1
+        module A
+
+        compact struct S {
+            c: Z::C1?
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+    // Assert
+    assert_eq!(dependency_order(&ast), ["Z::C1", "Z::C2", "A::S"]);
+}
+
+#[test]
+fn struct_cycles_are_broken_at_the_first_identifier() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct A {
+            d: D
+        }
+
+        struct D {
+            e: E?
+        }
+
+        struct E {
+            d: D?
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    assert_eq!(dependency_order(&ast), ["Test::D", "Test::E", "Test::A"]);
+}
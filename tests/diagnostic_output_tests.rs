@@ -5,7 +5,8 @@ mod test_helpers;
 mod output {
     use crate::test_helpers::parse;
     use slicec::diagnostic_emitter::DiagnosticEmitter;
-    use slicec::slice_options::{DiagnosticFormat, SliceOptions};
+    use slicec::slice_options::{ColumnUnit, DiagnosticFormat, SliceOptions};
+    use std::num::NonZeroUsize;
 
     #[test]
     fn output_to_json() {
@@ -178,6 +179,97 @@ error [E010]: invalid enum 'E': enums must contain at least one enumerator
         assert_eq!(expected, String::from_utf8(output).unwrap());
     }
 
+    #[test]
+    fn wide_characters_are_highlighted_correctly() {
+        let slice = "module Foo\n[deprecated(\"非推奨\")] enum E : uint8 {}";
+
+        // Disable ANSI color codes.
+        let options = SliceOptions {
+            disable_color: true,
+            ..Default::default()
+        };
+
+        let state = parse(slice, Some(&options));
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert
+        let expected = "\
+error [E010]: invalid enum 'E': enums must contain at least one enumerator
+ --> string-0:2:21
+  |
+2 | [deprecated(\"非推奨\")] enum E : uint8 {}
+  |                        ------
+  |
+";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn columns_can_be_measured_in_utf16_code_units() {
+        let slice = "module Foo\n[deprecated(\"😊😊\")] enum E : uint8 {}";
+
+        // Set the output format to JSON, and measure columns in UTF-16 code units.
+        let options = SliceOptions {
+            diagnostic_format: DiagnosticFormat::Json,
+            column_unit: ColumnUnit::Utf16,
+            ..Default::default()
+        };
+
+        let state = parse(slice, Some(&options));
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert: each emoji is encoded as 2 UTF-16 code units, so both columns are shifted by 2.
+        let expected = concat!(
+            r#"{"message":"invalid enum 'E': enums must contain at least one enumerator","severity":"error","span":{"start":{"row":2,"col":22},"end":{"row":2,"col":28},"file":"string-0"},"notes":[],"error_code":"E010"}"#,
+            "\n",
+        );
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn tabs_occupy_the_configured_tab_width() {
+        let slice = "module Foo\n\t\tenum E : uint8 {}";
+
+        // Disable ANSI color codes, and set the tab width to 4.
+        let options = SliceOptions {
+            disable_color: true,
+            tab_width: NonZeroUsize::new(4),
+            ..Default::default()
+        };
+
+        let state = parse(slice, Some(&options));
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert
+        let expected = "\
+error [E010]: invalid enum 'E': enums must contain at least one enumerator
+ --> string-0:2:9
+  |
+2 |         enum E : uint8 {}
+  |         ------
+  |
+";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
     #[test]
     fn crlf_line_endings() {
         let slice = "module Foo \r\n   enum\r\n E\r : uint8\r\n{}\r\n\r";
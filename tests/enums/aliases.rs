@@ -0,0 +1,195 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::test_helpers::*;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use slicec::slice_file::Span;
+use test_case::test_case;
+
+#[test]
+fn enumerators_can_alias_previous_enumerators() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E : uint8 {
+            A = 5
+            B = A
+            C
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let a = ast.find_element::<Enumerator>("Test::E::A").unwrap();
+    let b = ast.find_element::<Enumerator>("Test::E::B").unwrap();
+    let c = ast.find_element::<Enumerator>("Test::E::C").unwrap();
+
+    assert!(!a.is_alias());
+    assert!(b.is_alias());
+    assert_eq!(b.value(), 5);
+    assert_eq!(b.aliased_enumerator().unwrap().identifier(), "A");
+
+    // Implicit values continue on from the aliased value.
+    assert!(matches!(c.value, EnumeratorValue::Implicit(6)));
+}
+
+#[test]
+fn aliases_can_alias_other_aliases() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E {
+            A = 3
+            B = A
+            C = B
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let c = ast.find_element::<Enumerator>("Test::E::C").unwrap();
+    assert_eq!(c.value(), 3);
+    assert_eq!(c.aliased_enumerator().unwrap().identifier(), "B");
+}
+
+#[test]
+fn aliases_do_not_trigger_duplicate_value_errors() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E : int32 {
+            A
+            B
+            AlsoA = A
+            AlsoB = B
+        }
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn enumerators_can_alias_later_enumerators() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E {
+            A = B
+            B = 4
+            C
+            D = C + 1
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let a = ast.find_element::<Enumerator>("Test::E::A").unwrap();
+    let d = ast.find_element::<Enumerator>("Test::E::D").unwrap();
+
+    assert!(a.is_alias());
+    assert_eq!(a.value(), 4);
+    assert_eq!(a.aliased_enumerator().unwrap().identifier(), "B");
+    assert!(!d.is_alias());
+    assert_eq!(d.value(), 6);
+}
+
+#[test]
+fn enumerator_values_can_reference_later_enumerators() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E {
+            A = B + 1
+            B = 4
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let a = ast.find_element::<Enumerator>("Test::E::A").unwrap();
+    assert!(!a.is_alias());
+    assert_eq!(a.value(), 5);
+}
+
+#[test]
+fn aliases_cannot_depend_on_their_own_value() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E {
+            A = B
+            B
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::CyclicConstantExpression {
+        identifier: "A".to_owned(),
+        cycle: "A -> B -> A".to_owned(),
+    })
+    .set_span(&Span::new((5, 13).into(), (5, 18).into(), "string-0"));
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test_case("A = A", "A -> A"; "self alias")]
+#[test_case("A = B\n B = A", "A -> B -> A"; "mutual aliases")]
+fn aliases_cannot_form_cycles(enumerators: &str, cycle: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+
+        enum E {{
+            {enumerators}
+        }}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::CyclicConstantExpression {
+        identifier: "A".to_owned(),
+        cycle: cycle.to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn aliases_cannot_refer_to_enumerators_in_other_enums() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E1 { A }
+        enum E2 { B = A }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "A".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
@@ -3,6 +3,7 @@
 use crate::test_helpers::*;
 use slicec::diagnostics::{Diagnostic, Error};
 use slicec::grammar::*;
+use slicec::slice_file::Span;
 use test_case::test_case;
 
 #[test_case("10", "expected one of 'identifier', 'doc comment', '[', or '}', but found '10'"; "numeric identifier")]
@@ -172,6 +173,7 @@ mod associated_fields {
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn associated_fields_are_parsed_correctly() {
         // Arrange
         let slice = "
@@ -356,7 +358,7 @@ mod underlying_type {
     }
 
     #[test]
-    fn implicit_enumerator_values_overflow_cleanly() {
+    fn implicit_enumerator_values_cannot_overflow() {
         // Arrange
         let slice = "
             module Test
@@ -371,21 +373,64 @@ mod underlying_type {
         let diagnostics = parse_for_diagnostics(slice);
 
         // Assert
-        let expected = [
-            Diagnostic::new(Error::EnumeratorValueOutOfBounds {
-                enumerator_identifier: "B".to_owned(),
-                value: i128::MAX,
-                min: -2147483648,
-                max: 2147483647,
-            }),
-            Diagnostic::new(Error::EnumeratorValueOutOfBounds {
-                enumerator_identifier: "C".to_owned(),
-                value: i128::MIN,
-                min: -2147483648,
-                max: 2147483647,
-            }),
-        ];
-        check_diagnostics(diagnostics, expected);
+        let expected = Diagnostic::new(Error::ImplicitEnumeratorValueOverflows {
+            enumerator_identifier: "C".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn out_of_bounds_errors_point_to_explicit_values() {
+        // Arrange
+        let slice = "
+            module Test
+            enum E : uint8 {
+                A = 300
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let span = Span::new((4, 21).into(), (4, 24).into(), "string-0");
+        let note_span = Span::new((3, 22).into(), (3, 27).into(), "string-0");
+        let expected = Diagnostic::new(Error::EnumeratorValueOutOfBounds {
+            enumerator_identifier: "A".to_owned(),
+            value: 300,
+            min: 0,
+            max: 255,
+        })
+        .set_span(&span)
+        .add_note("the enum's underlying type is 'uint8'", Some(&note_span));
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("int8", -129, (i8::MIN as i128, i8::MAX as i128); "int8")]
+    #[test_case("int16", -32769, (i16::MIN as i128, i16::MAX as i128); "int16")]
+    #[test_case("varint62", -2_305_843_009_213_693_953, (-2_305_843_009_213_693_952, 2_305_843_009_213_693_951); "varint62")]
+    fn negative_values_are_checked_against_underlying_type(underlying: &str, value: i128, (min, max): (i128, i128)) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+            enum E : {underlying} {{
+                A = {value}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::EnumeratorValueOutOfBounds {
+            enumerator_identifier: "A".to_owned(),
+            value,
+            min,
+            max,
+        });
+        check_diagnostics(diagnostics, [expected]);
     }
 
     #[test]
@@ -0,0 +1,191 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::test_helpers::*;
+use slicec::diagnostics::{Diagnostic, Error, Lint};
+use slicec::grammar::*;
+use test_case::test_case;
+
+#[test]
+fn flags_enums_are_flags() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [flags]
+        enum E : uint8 {
+            None = 0
+            A = 1
+            B = 2
+            C = 4
+            D = 128
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let enum_def = ast.find_element::<Enum>("Test::E").unwrap();
+    assert!(enum_def.is_flags());
+}
+
+#[test]
+fn enums_are_not_flags_by_default() {
+    // Arrange
+    let slice = "
+        module Test
+
+        enum E : uint8 { A }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let enum_def = ast.find_element::<Enum>("Test::E").unwrap();
+    assert!(!enum_def.is_flags());
+}
+
+#[test_case("int8"; "int8")]
+#[test_case("int32"; "int32")]
+#[test_case("varint62"; "varint62")]
+fn flags_enums_must_have_unsigned_underlying_types(underlying_type: &str) {
+    // Arrange
+    let slice = format!(
+        "
+            module Test
+
+            [flags]
+            enum E : {underlying_type} {{ A = 1 }}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::UnexpectedAttribute {
+        attribute: "flags".to_owned(),
+    })
+    .add_note(
+        "the flags attribute can only be applied to enums with unsigned underlying types",
+        None,
+    );
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn flags_enums_must_have_underlying_types() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [flags]
+        enum E { A }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::UnexpectedAttribute {
+        attribute: "flags".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn flags_attribute_can_only_be_applied_to_enums() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [flags]
+        struct S {}
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::UnexpectedAttribute {
+        attribute: "flags".to_owned(),
+    })
+    .add_note("the flags attribute can only be applied to enums", None);
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn non_power_of_two_values_are_reported() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [flags]
+        enum E : uint16 {
+            A = 1
+            B = 2
+            AD = 9
+            C = 4
+            D = 1000
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = [
+        Diagnostic::new(Lint::NonPowerOfTwoFlag {
+            enumerator_identifier: "AD".to_owned(),
+            value: 9,
+        }),
+        Diagnostic::new(Lint::NonPowerOfTwoFlag {
+            enumerator_identifier: "D".to_owned(),
+            value: 1000,
+        }),
+    ];
+    check_diagnostics(diagnostics, expected);
+}
+
+#[test_case("AB = 3"; "literal")]
+#[test_case("AB = A | B"; "expression")]
+#[test_case("ABC = 7"; "every flag")]
+fn named_combinations_of_flags_are_accepted(combination: &str) {
+    // Arrange
+    let slice = format!(
+        "
+            module Test
+
+            [flags]
+            enum E : uint8 {{
+                A = 1
+                B = 2
+                C = 4
+                {combination}
+            }}
+        "
+    );
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn non_power_of_two_lint_can_be_allowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [flags]
+        [allow(NonPowerOfTwoFlag)]
+        enum E : uint8 {
+            A = 1
+            B = 2
+            X = 12
+        }
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
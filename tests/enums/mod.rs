@@ -1,6 +1,8 @@
 // Copyright (c) ZeroC, Inc.
 
+mod aliases;
 mod container;
+mod flags;
 mod mode_compatibility;
 
 use crate::test_helpers::*;
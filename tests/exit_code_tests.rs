@@ -0,0 +1,86 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compilation_state::{CompilationSummary, ExitCode};
+use slicec::compile_from_strings;
+use slicec::slice_options::SliceOptions;
+use test_case::test_case;
+
+/// Compiles the provided slice with the provided options, then emits its diagnostics and returns the summary.
+fn summarize(slice: &str, options: &SliceOptions) -> CompilationSummary {
+    let state = compile_from_strings(&[slice], Some(options), |_| {}, |_| {});
+    state.emit_diagnostics(options)
+}
+
+const WARNING: &str = "
+    module Test
+    /// @param x: not a parameter.
+    struct S {}
+";
+
+#[test_case("module Test", false, ExitCode::Success; "success")]
+#[test_case(WARNING, false, ExitCode::Success; "warnings")]
+#[test_case(WARNING, true, ExitCode::Warnings; "warnings with warnings exit code")]
+#[test_case("module Test struct", true, ExitCode::Errors; "errors")]
+fn exit_codes_reflect_the_outcome_of_compilation(slice: &str, warnings_exit_code: bool, expected: ExitCode) {
+    // Arrange
+    let options = SliceOptions {
+        warnings_exit_code,
+        ..Default::default()
+    };
+
+    // Act
+    let summary = summarize(slice, &options);
+
+    // Assert
+    assert_eq!(summary.exit_code, expected);
+}
+
+#[test]
+fn summaries_count_warnings_and_errors() {
+    // Act
+    let summary = summarize(WARNING, &SliceOptions::default());
+
+    // Assert
+    assert_eq!((summary.total_warnings, summary.total_errors), (1, 0));
+    assert!(summary.internal_errors.is_empty());
+    assert!(summary.succeeded());
+    assert_eq!(i32::from(summary.exit_code), 0);
+}
+
+#[test]
+fn failing_to_emit_a_report_is_an_internal_error() {
+    // Arrange
+    let options = SliceOptions {
+        baseline: Some("directory-that-does-not-exist/baseline.json".to_owned()),
+        update_baseline: true,
+        ..Default::default()
+    };
+
+    // Act
+    let summary = summarize(WARNING, &options);
+
+    // Assert
+    assert_eq!(summary.exit_code, ExitCode::InternalError);
+    assert_eq!(summary.internal_errors.len(), 1);
+    assert!(summary.internal_errors[0].starts_with("failed to write baseline: "));
+    assert!(!summary.succeeded());
+}
+
+#[test]
+fn updating_the_baseline_without_a_baseline_file_is_an_internal_error() {
+    // Arrange
+    let options = SliceOptions {
+        update_baseline: true,
+        ..Default::default()
+    };
+
+    // Act
+    let summary = summarize(WARNING, &options);
+
+    // Assert
+    assert_eq!(summary.exit_code, ExitCode::InternalError);
+    assert_eq!(
+        summary.internal_errors,
+        ["failed to write baseline: '--update-baseline' requires a baseline file to be specified with '--baseline'"],
+    );
+}
@@ -1,6 +1,7 @@
 // Copyright (c) ZeroC, Inc.
 
-use slicec::diagnostics::{Diagnostic, Diagnostics, Lint};
+use slicec::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
+use slicec::grammar::Interface;
 use slicec::slice_options::SliceOptions;
 use slicec::test_helpers::check_diagnostics;
 use slicec::utils::file_util::resolve_files_from;
@@ -146,3 +147,290 @@ fn compilation_preserves_order() {
     assert_eq!(files[2].relative_path, "tests/files/c.slice");
     assert_eq!(files[3].relative_path, "tests/files/test.slice");
 }
+
+#[test]
+fn missing_files_are_reported() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/missing.slice".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert!(files.is_empty());
+
+    let expected = Diagnostic::new(Error::FileNotFound {
+        path: "tests/files/missing.slice".to_owned(),
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn files_without_the_slice_extension_are_reported() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/io.rs".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert!(files.is_empty());
+
+    let expected = Diagnostic::new(Error::NotASliceFile {
+        path: "tests/files/io.rs".to_owned(),
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn directories_passed_as_sources_are_reported() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert!(files.is_empty());
+
+    let expected = Diagnostic::new(Error::DirectoryPassedAsSource {
+        path: "tests/files".to_owned(),
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn files_with_invalid_utf8_are_reported() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/invalid_utf8.slice".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert!(files.is_empty());
+
+    let expected = Diagnostic::new(Error::InvalidUtf8 {
+        path: "tests/files/invalid_utf8.slice".to_owned(),
+        offset: 15,
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn compilation_continues_past_unreadable_files() {
+    // Arrange
+    let options = SliceOptions {
+        sources: vec![
+            "tests/files/missing.slice".to_owned(),
+            "tests/files/test.slice".to_owned(),
+            "tests/files/invalid_utf8.slice".to_owned(),
+        ],
+        ..Default::default()
+    };
+
+    // Act
+    let state = slicec::compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    assert_eq!(state.files.len(), 1);
+    assert_eq!(state.files[0].relative_path, "tests/files/test.slice");
+    assert!(state.ast.find_element::<Interface>("Test::I").is_ok());
+
+    let expected = [
+        Diagnostic::new(Error::FileNotFound {
+            path: "tests/files/missing.slice".to_owned(),
+        }),
+        Diagnostic::new(Error::InvalidUtf8 {
+            path: "tests/files/invalid_utf8.slice".to_owned(),
+            offset: 15,
+        }),
+    ];
+    check_diagnostics(state.diagnostics.into_inner(), expected);
+}
+
+#[test]
+fn remaining_files_are_validated_after_unreadable_files() {
+    // Arrange
+    let options = SliceOptions {
+        sources: vec!["tests/files/missing.slice".to_owned()],
+        ..Default::default()
+    };
+    let prelude = [("prelude", "module Test\nstruct S { a: int32, a: int32 }")];
+
+    // Act
+    let state = slicec::compile_with_prelude(&prelude, &options, |_| {}, |_| {});
+
+    // Assert
+    let expected = [
+        Diagnostic::new(Error::FileNotFound {
+            path: "tests/files/missing.slice".to_owned(),
+        }),
+        Diagnostic::new(Error::Redefinition {
+            identifier: "a".to_owned(),
+        }),
+    ];
+    check_diagnostics(state.diagnostics.into_inner(), expected);
+}
+
+#[test]
+fn files_larger_than_the_maximum_size_are_reported() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/test.slice".to_owned(), "tests/files/a.slice".to_owned()],
+        max_file_size: Some(10),
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].relative_path, "tests/files/a.slice");
+
+    let expected = Diagnostic::new(Error::FileTooLarge {
+        path: "tests/files/test.slice".to_owned(),
+        size: 83,
+        max: 10,
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn files_within_the_maximum_size_are_read() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/test.slice".to_owned()],
+        max_file_size: Some(83),
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert_eq!(files.len(), 1);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn files_with_duplicate_contents_are_ignored_with_warning() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/test.slice".to_owned()],
+        references: vec!["tests/files/vendored/test.slice".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].relative_path, "tests/files/test.slice");
+
+    let expected = Diagnostic::new(Lint::DuplicateFileContents {
+        path: "tests/files/vendored/test.slice".to_owned(),
+        original_path: "tests/files/test.slice".to_owned(),
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn files_with_duplicate_contents_do_not_cause_redefinitions() {
+    // Arrange
+    let options = SliceOptions {
+        sources: vec!["tests/files/vendored/test.slice".to_owned()],
+        references: vec!["tests/files/test.slice".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let state = slicec::compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    assert!(!state.diagnostics.has_errors());
+    assert_eq!(state.files.len(), 1);
+    assert!(state.ast.find_element::<Interface>("Test::I").is_ok());
+}
+
+#[test]
+fn files_are_reported_with_normalized_paths() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/../files/./test.slice".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].relative_path, "tests/files/test.slice");
+}
+
+#[test]
+fn files_can_be_reported_with_absolute_paths() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/test.slice".to_owned()],
+        absolute_paths: true,
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    let expected = PathBuf::from("tests/files/test.slice").canonicalize().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].relative_path, expected.display().to_string());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinked_files_are_deduplicated() {
+    // Arrange
+    let link = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("symlinked_test.slice");
+    let _ = std::fs::remove_file(&link);
+    let target = PathBuf::from("tests/files/test.slice").canonicalize().unwrap();
+    std::os::unix::fs::symlink(target, &link).unwrap();
+
+    let mut diagnostics = Diagnostics::new();
+    let options = SliceOptions {
+        sources: vec!["tests/files/test.slice".to_owned()],
+        references: vec![link.display().to_string()],
+        ..Default::default()
+    };
+
+    // Act
+    let files = resolve_files_from(&options, &mut diagnostics);
+
+    // Assert
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].relative_path, "tests/files/test.slice");
+    assert!(diagnostics.is_empty());
+}
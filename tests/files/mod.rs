@@ -3,10 +3,11 @@
 mod io;
 
 use slicec::diagnostics::Diagnostics;
-use slicec::slice_file::compute_sha256_hash_of_source_files;
+use slicec::slice_file::{compute_sha256_hash_of_source_files, SliceFile};
 use slicec::slice_options::SliceOptions;
 use slicec::utils::file_util::resolve_files_from;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// This test is used to verify that the `compute_sha256_hash` method for slices of `SliceFile` returns a hash that is
 /// independent of the order of the files in the slice.
@@ -34,3 +35,89 @@ fn fixed_slice_file_hash() {
     // Assert
     assert_eq!(hash1, hash2);
 }
+
+#[test]
+fn file_text_is_shared_instead_of_copied() {
+    // Arrange
+    let raw_text: Arc<str> = Arc::from("module Test");
+
+    // Act
+    let file = SliceFile::new("test.slice".to_owned(), raw_text.clone(), true);
+
+    // Assert
+    assert!(Arc::ptr_eq(&file.raw_text, &raw_text));
+}
+
+mod positions {
+    use slicec::slice_file::{Location, SliceFile, Span};
+    use test_case::test_case;
+
+    fn create_file() -> SliceFile {
+        let raw_text = "module Test\r\n/// Café 😊\n\nstruct S {}".to_owned();
+        SliceFile::new("test.slice".to_owned(), raw_text, true)
+    }
+
+    #[test]
+    fn lines_are_indexed_from_one() {
+        // Arrange
+        let file = create_file();
+
+        // Act/Assert
+        assert_eq!(file.line_count(), 4);
+        assert_eq!(file.line(0), None);
+        assert_eq!(file.line(1), Some("module Test\r"));
+        assert_eq!(file.line(2), Some("/// Café 😊"));
+        assert_eq!(file.line(3), Some(""));
+        assert_eq!(file.line(4), Some("struct S {}"));
+        assert_eq!(file.line(5), None);
+    }
+
+    #[test_case((1, 1), Some(0); "start of file")]
+    #[test_case((1, 12), Some(11); "carriage return")]
+    #[test_case((2, 8), Some(20); "multi-byte character")]
+    #[test_case((2, 9), Some(22); "after multi-byte character")]
+    #[test_case((2, 10), Some(23); "wide character")]
+    #[test_case((2, 11), Some(27); "end of line")]
+    #[test_case((2, 12), None; "past end of line")]
+    #[test_case((4, 12), Some(40); "end of file")]
+    #[test_case((5, 1), None; "past end of file")]
+    fn byte_offsets_of_locations(location: (usize, usize), expected: Option<usize>) {
+        // Arrange
+        let file = create_file();
+
+        // Act
+        let offset = file.byte_offset(location.into());
+
+        // Assert
+        assert_eq!(offset, expected);
+        if let Some(offset) = offset {
+            assert_eq!(file.position_at(offset), Some(location.into()));
+        }
+    }
+
+    #[test_case(25; "inside multi-byte character")]
+    #[test_case(41; "past end of file")]
+    fn positions_of_invalid_offsets(offset: usize) {
+        // Arrange
+        let file = create_file();
+
+        // Act
+        let position = file.position_at(offset);
+
+        // Assert
+        assert_eq!(position, None);
+    }
+
+    #[test]
+    fn snippets_span_multiple_lines() {
+        // Arrange
+        let file = create_file();
+        let span = Span::new(Location { row: 2, col: 5 }, Location { row: 4, col: 7 }, "test.slice");
+
+        // Act
+        let snippet = file.snippet(&span);
+
+        // Assert
+        assert_eq!(snippet, Some("Café 😊\n\nstruct"));
+    }
+}
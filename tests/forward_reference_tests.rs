@@ -0,0 +1,168 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use test_case::test_case;
+
+// Each file references definitions from the files after it, and the last file references the first, so no ordering of
+// the files has every definition before its uses.
+const FILES: [&str; 3] = [
+    "
+    module Test
+
+    typealias First = Second
+    interface Derived : Base {}
+    struct Wrapper {
+        a: First
+    }
+    ",
+    "
+    module Test
+
+    typealias Second = Third
+    interface Base : Root {}
+    ",
+    "
+    module Test
+
+    typealias Third = Sequence<Wrapped>
+    interface Root {}
+    struct Wrapped {}
+    ",
+];
+
+#[test_case([0, 1, 2]; "in order")]
+#[test_case([2, 1, 0]; "in reverse order")]
+#[test_case([1, 0, 2]; "out of order")]
+#[test_case([2, 0, 1]; "rotated")]
+fn definitions_can_be_referenced_from_any_file_in_any_order(order: [usize; 3]) {
+    // Arrange
+    let files = order.map(|i| FILES[i]);
+
+    // Act
+    let ast = parse_multiple_for_ast(&files);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Wrapper::a").unwrap();
+    let Types::Sequence(sequence) = field.data_type().concrete_type() else { panic!() };
+    let Types::Struct(struct_def) = sequence.element_type.concrete_type() else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Test::Wrapped");
+
+    let derived = ast.find_element::<Interface>("Test::Derived").unwrap();
+    let bases = derived.all_base_interfaces();
+    let base_identifiers = bases.iter().map(|i| i.parser_scoped_identifier()).collect::<Vec<_>>();
+    assert_eq!(base_identifiers, ["Test::Base", "Test::Root"]);
+}
+
+#[test]
+fn types_can_be_referenced_before_they_are_defined() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class Derived : Base {
+            next: Derived?
+        }
+        class Base {}
+        exception Error : BaseError {}
+        exception BaseError {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let derived = ast.find_element::<Class>("Test::Derived").unwrap();
+    assert_eq!(derived.base_class().unwrap().parser_scoped_identifier(), "Test::Base");
+    let error = ast.find_element::<Exception>("Test::Error").unwrap();
+    assert_eq!(
+        error.base_exception().unwrap().parser_scoped_identifier(),
+        "Test::BaseError"
+    );
+}
+
+#[test]
+fn references_are_ambiguous_if_a_using_directive_alias_shadows_a_definition() {
+    // Arrange
+    let definitions = "
+        module Foo::Bar
+
+        struct S {}
+    ";
+    let shadowing_definitions = "
+        module Test::Bar
+
+        struct S {}
+    ";
+    let slice = "
+        module Test
+        using Bar = Foo::Bar
+
+        struct Wrapper {
+            s: Bar::S
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[definitions, shadowing_definitions, slice]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::AmbiguousReference {
+        identifier: "Bar::S".to_owned(),
+        candidates: "'Foo::Bar::S', 'Test::Bar::S'".to_owned(),
+    })
+    .add_note("'Foo::Bar::S' is defined here:", None)
+    .add_note("'Test::Bar::S' is defined here:", None)
+    .add_note(
+        "use a globally scoped identifier, like '::Foo::Bar::S', to refer to a specific element",
+        None,
+    );
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn references_are_not_ambiguous_if_both_resolutions_agree() {
+    // Arrange
+    let slice = "
+        module Foo::Bar
+        using Bar = Foo::Bar
+
+        struct S {}
+        struct Wrapper {
+            s: Bar::S
+        }
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn find_nodes_with_scope_returns_shadowed_nodes() {
+    // Arrange
+    let slice1 = "
+        module A
+
+        struct S {}
+    ";
+    let slice2 = "
+        module A::B
+
+        struct S {}
+    ";
+    let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+    // Act
+    let nodes = ast.find_nodes_with_scope("S", "A::B::C");
+
+    // Assert
+    let elements = nodes
+        .into_iter()
+        .map(|node| <&dyn NamedSymbol>::try_from(node).unwrap());
+    let identifiers = elements.map(|e| e.parser_scoped_identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["A::B::S", "A::S"]);
+}
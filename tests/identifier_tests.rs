@@ -5,6 +5,8 @@ mod test_helpers;
 use crate::test_helpers::*;
 use slicec::diagnostics::{Diagnostic, Error};
 use slicec::grammar::{CustomType, Interface, Struct};
+use slicec::slice_file::Span;
+use test_case::test_case;
 
 #[test]
 fn escaped_keywords() {
@@ -79,17 +81,41 @@ fn escaped_scoped_identifiers_containing_keywords() {
     assert!(ast.find_element::<Struct>("Foo::module").is_ok());
 }
 
-#[test]
-fn must_be_ascii_alphanumeric_characters() {
+#[test_case("𒅋"; "non-ascii letter")]
+#[test_case("Café"; "non-ascii suffix")]
+#[test_case("名前"; "wide characters")]
+#[test_case("\\Café"; "escaped")]
+fn must_be_ascii_alphanumeric_characters(identifier: &str) {
     // Arrange
-    let slice = "module 𒅋";
+    let slice = format!("module {identifier}");
 
     // Act
     let diagnostics = parse_for_diagnostics(slice);
 
     // Assert
+    let identifier = identifier.trim_start_matches('\\');
     let expected = Diagnostic::new(Error::Syntax {
-        message: "unknown symbol '𒅋'".to_owned(),
+        message: format!(
+            "invalid identifier '{identifier}': identifiers can only contain ASCII letters, digits, and underscores"
+        ),
     });
     check_diagnostics(diagnostics, [expected]);
 }
+
+#[test]
+fn non_ascii_identifiers_are_reported_with_their_full_span() {
+    // Arrange
+    let slice = "module Tëst_名前 {}";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let span = Span::new((1, 8).into(), (1, 15).into(), "string-0");
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "invalid identifier 'Tëst_名前': identifiers can only contain ASCII letters, digits, and underscores"
+            .to_owned(),
+    })
+    .set_span(&span);
+    check_diagnostics(diagnostics, [expected]);
+}
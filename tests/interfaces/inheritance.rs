@@ -165,3 +165,148 @@ fn inherits_correct_operations() {
     assert_eq!(interface_d_def.all_inherited_operations()[0].identifier(), "opB");
     assert_eq!(interface_d_def.all_inherited_operations()[1].identifier(), "opA");
 }
+
+#[test]
+fn diamond_inheritance_of_the_same_operation_is_allowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface A {
+            op(x: int32) -> string
+        }
+        interface B : A {}
+        interface C : A {}
+        interface D : B, C {}
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn inheriting_operations_with_identical_signatures_is_allowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface B {
+            op(x: int32)
+        }
+        interface C {
+            op(x: int32)
+        }
+        interface D : B, C {}
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn inheriting_conflicting_operations_fails() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface B {
+            op(x: int32)
+        }
+        interface C {
+            op(x: string) -> bool
+        }
+        interface D : B, C {}
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::ConflictingInheritedOperations {
+        interface_identifier: "D".to_owned(),
+        operation_identifier: "op".to_owned(),
+    })
+    .add_note("'Test::B::op' is defined here as 'op(x: int32)'", None)
+    .add_note(
+        "'Test::C::op' is defined here as 'op(x: string) -> (returnValue: bool)'",
+        None,
+    );
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn diamond_bases_are_only_linearized_once() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface A {}
+        interface B : A {}
+        interface C : A {}
+        interface D : B, C {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let interface_d_def = ast.find_element::<Interface>("Test::D").unwrap();
+    let linearization = interface_d_def.linearization();
+    let identifiers = linearization.iter().map(|i| i.identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["D", "B", "C", "A"]);
+
+    let all_bases = interface_d_def.all_base_interfaces();
+    let identifiers = all_bases.iter().map(|i| i.identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["B", "C", "A"]);
+}
+
+#[test]
+fn bases_are_linearized_in_c3_order() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface O {}
+        interface A : O {}
+        interface B : O {}
+        interface C : O {}
+        interface D : O {}
+        interface E : O {}
+        interface K1 : A, B, C {}
+        interface K2 : D, B, E {}
+        interface K3 : D, A {}
+        interface Z : K1, K2, K3 {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let interface_z_def = ast.find_element::<Interface>("Test::Z").unwrap();
+    let linearization = interface_z_def.linearization();
+    let identifiers = linearization.iter().map(|i| i.identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["Z", "K1", "K2", "K3", "D", "A", "B", "C", "E", "O"]);
+}
+
+#[test]
+fn inconsistent_hierarchies_are_linearized_in_encounter_order() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface A {}
+        interface B {}
+        interface C : A, B {}
+        interface D : B, A {}
+        interface E : C, D {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let interface_e_def = ast.find_element::<Interface>("Test::E").unwrap();
+    let linearization = interface_e_def.linearization();
+    let identifiers = linearization.iter().map(|i| i.identifier()).collect::<Vec<_>>();
+    assert_eq!(identifiers, ["E", "C", "D", "A", "B"]);
+}
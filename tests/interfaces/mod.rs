@@ -7,6 +7,7 @@ mod operations;
 use crate::test_helpers::*;
 use slicec::diagnostics::{Diagnostic, Error};
 use slicec::grammar::*;
+use slicec::slice_file::Span;
 
 #[test]
 fn can_have_no_operations() {
@@ -83,10 +84,49 @@ fn cannot_redefine_operations() {
     let diagnostics = parse_for_diagnostics(slice);
 
     // Assert
-    let expected = Diagnostic::new(Error::Redefinition {
-        identifier: "op".to_owned(),
+    let expected = Diagnostic::new(Error::OverloadedOperation {
+        interface_identifier: "I".to_owned(),
+        operation_identifier: "op".to_owned(),
     })
-    .add_note("'op' was previously defined here", None);
+    .add_note("'op' was previously defined here", None)
+    .add_note(
+        "operations are dispatched by name, so each operation must have a unique identifier; consider renaming one of them",
+        None,
+    );
+
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn overloaded_operations_suggest_a_rename() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {
+            getUser(name: string) -> string
+            getUser(user_id: int32) -> string
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::OverloadedOperation {
+        interface_identifier: "I".to_owned(),
+        operation_identifier: "getUser".to_owned(),
+    })
+    .set_span(&Span::new((6, 13).into(), (6, 20).into(), "string-0"))
+    .add_note(
+        "'getUser' was previously defined here",
+        Some(&Span::new((5, 13).into(), (5, 20).into(), "string-0")),
+    )
+    .add_note(
+        "operations are dispatched by name, so each operation must have a unique identifier; consider renaming one of \
+         them, for example to 'getUserWithUserId'",
+        None,
+    );
 
     check_diagnostics(diagnostics, [expected]);
 }
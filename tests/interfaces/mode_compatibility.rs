@@ -3,6 +3,7 @@
 use crate::test_helpers::*;
 use slicec::diagnostics::{Diagnostic, Error};
 use slicec::grammar::CompilationMode;
+use test_case::test_case;
 
 #[test]
 fn parameters_must_be_allowed_within_compilation_mode() {
@@ -33,3 +34,33 @@ fn parameters_must_be_allowed_within_compilation_mode() {
 
     check_diagnostics(diagnostics, [expected]);
 }
+
+/// Interfaces aren't types, so they can't be used as proxies in either mode.
+/// Slice1 uses classes to pass references, while Slice2 uses custom types.
+#[test_case("Slice1"; "Slice1")]
+#[test_case("Slice2"; "Slice2")]
+fn interfaces_cannot_be_used_as_types(mode: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        mode = {mode}
+        module Test
+
+        interface I {{
+            op(proxy: I)
+        }}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::TypeMismatch {
+        expected: "type".to_owned(),
+        actual: "interface".to_owned(),
+        is_concrete: false,
+    });
+
+    check_diagnostics(diagnostics, [expected]);
+}
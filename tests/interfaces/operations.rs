@@ -272,12 +272,31 @@ mod slice2 {
         let expected = Diagnostic::new(Error::ExceptionSpecificationNotSupported);
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test]
+    fn throwing_any_exception_is_not_supported() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                op() throws AnyException
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ExceptionSpecificationNotSupported);
+        check_diagnostics(diagnostics, [expected]);
+    }
 }
 
 mod slice1 {
     use crate::test_helpers::*;
     use slicec::diagnostics::{Diagnostic, Error};
-    use slicec::grammar::{NamedSymbol, Operation};
+    use slicec::grammar::{Field, NamedSymbol, Operation, Throws};
 
     #[test]
     fn operations_can_omit_throws_clause() {
@@ -300,6 +319,7 @@ mod slice1 {
         // Assert
         let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
         assert!(operation.exception_specification.is_empty());
+        assert!(matches!(operation.throws(), Throws::Nothing));
     }
 
     #[test]
@@ -353,6 +373,81 @@ mod slice1 {
         assert_eq!(second_exception.parser_scoped_identifier(), "Test::E2");
     }
 
+    #[test]
+    fn operations_can_throw_any_exception() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            interface I {
+                op() throws AnyException
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+        assert!(operation.exception_specification.is_empty());
+        assert!(operation.throws_any_exception.is_some());
+        assert!(matches!(operation.throws(), Throws::AnyException));
+    }
+
+    #[test]
+    fn any_exception_can_be_used_as_an_identifier() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            exception AnyException {}
+
+            compact struct S {
+                AnyException: int32
+            }
+
+            interface I {
+                op(AnyException: int32) throws (AnyException)
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        assert!(ast.find_element::<Field>("Test::S::AnyException").is_ok());
+        let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+        assert!(operation.throws_any_exception.is_none());
+        let Throws::Specific(exceptions) = operation.throws() else { panic!() };
+        assert_eq!(exceptions[0].parser_scoped_identifier(), "Test::AnyException");
+    }
+
+    #[test]
+    fn any_exception_cannot_be_listed_with_other_exceptions() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            exception E {}
+
+            interface I {
+                op() throws (E, AnyException)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert: outside of `throws AnyException`, it's an ordinary identifier, which doesn't name any exception here.
+        let expected = Diagnostic::new(Error::DoesNotExist {
+            identifier: "AnyException".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
     #[test]
     fn operations_can_only_throw_exceptions() {
         // Arrange
@@ -383,6 +478,7 @@ mod streams {
     use crate::test_helpers::*;
     use slicec::diagnostics::{Diagnostic, Error};
     use slicec::grammar::*;
+    use slicec::slice_file::Span;
 
     #[test]
     fn can_have_streamed_parameter_and_return() {
@@ -451,4 +547,116 @@ mod streams {
         });
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test]
+    fn stream_return_member_must_be_last() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                op() -> (s: stream varuint62, i: int32)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::StreamedMembersMustBeLast {
+            parameter_identifier: "s".to_owned(),
+        })
+        .set_span(&Span::new((5, 26).into(), (5, 45).into(), "string-0"))
+        .add_note(
+            "the last return member is 'i'",
+            Some(&Span::new((5, 47).into(), (5, 55).into(), "string-0")),
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn multiple_streamed_members_point_to_the_last_stream() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                op(s: stream varuint62, s2: stream string)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::MultipleStreamedMembers)
+            .set_span(&Span::new((5, 20).into(), (5, 39).into(), "string-0"))
+            .add_note(
+                "'s2' is already streamed",
+                Some(&Span::new((5, 41).into(), (5, 58).into(), "string-0")),
+            );
+        check_diagnostics(
+            diagnostics,
+            [
+                Diagnostic::new(Error::StreamedMembersMustBeLast {
+                    parameter_identifier: "s".to_owned(),
+                }),
+                expected,
+            ],
+        );
+    }
+
+    #[test]
+    fn streamed_members_cannot_be_tagged() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                op(tag(1) s: stream varuint62?)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::StreamedMembersCannotBeTagged {
+            parameter_identifier: "s".to_owned(),
+        })
+        .add_note(
+            "try removing this tag",
+            Some(&Span::new((5, 24).into(), (5, 25).into(), "string-0")),
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn streams_are_not_supported_in_slice1_mode() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            interface I {
+                op(s: stream int32) -> stream string
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = [
+            Diagnostic::new(Error::StreamedParametersNotSupported)
+                .set_span(&Span::new((6, 20).into(), (6, 35).into(), "string-0"))
+                .add_note("streams can only be used in Slice2 mode", None),
+            Diagnostic::new(Error::StreamedParametersNotSupported).set_span(&Span::new(
+                (6, 39).into(),
+                (6, 53).into(),
+                "string-0",
+            )),
+        ];
+        check_diagnostics(diagnostics, expected);
+    }
 }
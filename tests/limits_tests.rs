@@ -0,0 +1,139 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod limits {
+    use crate::test_helpers::*;
+    use slicec::diagnostics::{Diagnostic, Lint};
+    use slicec::slice_options::SliceOptions;
+
+    #[test]
+    fn limits_are_disabled_by_default() {
+        // Arrange
+        let slice = "
+            module Test
+
+            compact struct S { a: int8, b: int8, c: int8 }
+
+            interface I {
+                op(a: int8, b: int8, c: int8) -> Sequence<Sequence<Sequence<int8>>>
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn compact_struct_field_count_is_limited() {
+        // Arrange
+        let slice = "
+            module Test
+
+            compact struct Small { a: int8, b: int8 }
+            compact struct Large { a: int8, b: int8, c: int8 }
+            struct NotCompact { a: int8, b: int8, c: int8 }
+        ";
+        let options = SliceOptions {
+            max_compact_struct_fields: Some(2),
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected = Diagnostic::new(Lint::CompactStructTooLarge {
+            struct_identifier: "Large".to_owned(),
+            field_count: 3,
+            max: 2,
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn operation_parameter_count_is_limited() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                ok(a: int8) -> (x: int8, y: int8)
+                tooMany(a: int8, b: int8)
+            }
+        ";
+        let options = SliceOptions {
+            max_operation_parameters: Some(1),
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected = Diagnostic::new(Lint::TooManyParameters {
+            operation_identifier: "tooMany".to_owned(),
+            parameter_count: 2,
+            max: 1,
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn type_nesting_depth_is_limited() {
+        // Arrange
+        let slice = "
+            module Test
+
+            typealias Shallow = Sequence<Dictionary<int8, string>>
+            typealias Deep = Sequence<Dictionary<int8, Sequence<string>>>
+
+            struct S {
+                s: Shallow
+                d: Result<bool, Sequence<Sequence<int8>>>
+            }
+        ";
+        let options = SliceOptions {
+            max_type_nesting_depth: Some(2),
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected = [
+            Diagnostic::new(Lint::TypeNestingTooDeep { depth: 3, max: 2 }),
+            Diagnostic::new(Lint::TypeNestingTooDeep { depth: 3, max: 2 }),
+        ];
+        check_diagnostics(diagnostics, expected);
+    }
+
+    #[test]
+    fn limits_can_be_allowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                [allow(TooManyParameters)]
+                op(a: int8, b: int8)
+            }
+        ";
+        let options = SliceOptions {
+            max_operation_parameters: Some(1),
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected: [Diagnostic; 0] = [];
+        check_diagnostics(diagnostics, expected);
+    }
+}
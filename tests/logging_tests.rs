@@ -0,0 +1,95 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::slice_options::{LogFormat, SliceOptions};
+use slicec::{compile_from_options, compile_from_strings, logging};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// A writer which stores everything written to it in a shared buffer, so tests can inspect the logs.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs the provided function with a JSON subscriber installed, and returns the logged events, one per element.
+fn logs_of(function: impl FnOnce(&SliceOptions)) -> Vec<serde_json::Value> {
+    let options = SliceOptions {
+        verbose: true,
+        log_format: LogFormat::Json,
+        ..Default::default()
+    };
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let subscriber = logging::create_subscriber(&options, move || writer.clone());
+    tracing::subscriber::with_default(subscriber, || function(&options));
+
+    let logs = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    logs.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+/// Returns the fields of the first event with the provided message.
+fn find_event<'a>(logs: &'a [serde_json::Value], message: &str) -> &'a serde_json::Value {
+    let event = logs.iter().find(|event| event["fields"]["message"] == message);
+    &event.unwrap_or_else(|| panic!("no '{message}' event was logged in: {logs:#?}"))["fields"]
+}
+
+#[test]
+fn logs_are_discarded_unless_verbose() {
+    assert!(!logging::install(&SliceOptions::default()));
+}
+
+#[test]
+fn failed_type_resolutions_are_logged() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            a: Missing
+        }
+    ";
+
+    // Act
+    let logs = logs_of(|options| {
+        compile_from_strings(&[slice], Some(options), |_| {}, |_| {});
+    });
+
+    // Assert
+    let fields = find_event(&logs, "failed to resolve type reference");
+    assert_eq!(fields["identifier"], "Missing");
+    assert_eq!(fields["scope"], "Test");
+    assert_eq!(fields["error"], "no element with identifier 'Missing' exists");
+}
+
+#[test]
+fn files_that_are_read_are_logged() {
+    // Arrange
+    let path = std::env::temp_dir().join(format!("slicec-logging-{}.slice", std::process::id()));
+    std::fs::write(&path, "module Test").unwrap();
+    let path = path.display().to_string();
+
+    // Act
+    let logs = logs_of(|options| {
+        let options = SliceOptions {
+            sources: vec![path.clone()],
+            ..options.clone()
+        };
+        compile_from_options(&options, |_| {}, |_| {});
+    });
+
+    // Assert
+    assert_eq!(find_event(&logs, "reading file")["path"], path.as_str());
+    let phases = logs
+        .iter()
+        .filter(|event| event["fields"]["message"] == "close" && event["spans"] == serde_json::json!([]))
+        .map(|event| event["span"]["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(phases, ["resolve", "parse", "patch", "validate"]);
+}
@@ -0,0 +1,149 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::lossless::*;
+use slicec::slice_file::SliceFile;
+use test_case::test_case;
+
+fn kinds_and_text_of(node: &SyntaxNode) -> Vec<(SyntaxKind, &str)> {
+    let tokens = node.tokens();
+    tokens
+        .into_iter()
+        .map(|token| (token.kind, token.text.as_str()))
+        .collect()
+}
+
+#[test_case("mode = Slice1\n[[cs::namespace(\"Foo\")]]\nmodule Test\n"; "file prelude")]
+#[test_case("/// A doc comment.\n// A comment.\nstruct S { /* block */ a: int32 }\n"; "comments")]
+#[test_case("#if FOO\n  #define BAR\n#endif\n"; "preprocessor directives")]
+#[test_case("struct S { a: int32; }; ¶\n"; "invalid syntax")]
+#[test_case("interface I { op(a: \"unterminated\n"; "unterminated groups")]
+#[test_case(""; "empty file")]
+fn syntax_trees_are_lossless(slice: &str) {
+    // Act
+    let tree = parse(slice, "test.slice");
+
+    // Assert
+    assert_eq!(tree.to_string(), slice);
+}
+
+#[test]
+fn tokens_are_classified() {
+    // Arrange
+    let slice = "/// Doc\nstruct \\S { a: int32? = 79 } // Comment";
+
+    // Act
+    let tree = parse(slice, "test.slice");
+
+    // Assert
+    let expected = [
+        (SyntaxKind::DocComment, "/// Doc"),
+        (SyntaxKind::Whitespace, "\n"),
+        (SyntaxKind::Keyword, "struct"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Identifier, "\\S"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Symbol, "{"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Identifier, "a"),
+        (SyntaxKind::Symbol, ":"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Keyword, "int32"),
+        (SyntaxKind::Symbol, "?"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Symbol, "="),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::IntegerLiteral, "79"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::Symbol, "}"),
+        (SyntaxKind::Whitespace, " "),
+        (SyntaxKind::LineComment, "// Comment"),
+    ];
+    assert_eq!(kinds_and_text_of(&tree), expected);
+}
+
+#[test]
+fn words_within_attributes_are_not_keywords() {
+    // Arrange
+    let slice = "[cs::attribute(struct)] struct S {}";
+
+    // Act
+    let tree = parse(slice, "test.slice");
+
+    // Assert
+    let keywords = tree
+        .tokens()
+        .into_iter()
+        .filter(|token| token.kind == SyntaxKind::Keyword);
+    assert_eq!(keywords.count(), 1);
+}
+
+#[test]
+fn tokens_are_grouped_by_their_delimiters() {
+    // Arrange
+    let slice = "interface I {\n    op(a: int32)\n}";
+
+    // Act
+    let tree = parse(slice, "test.slice");
+
+    // Assert
+    assert_eq!(tree.kind, NodeKind::File);
+    let SyntaxElement::Node(braces) = &tree.children[4] else { panic!("expected a node") };
+    assert_eq!(braces.kind, NodeKind::Braces);
+    assert_eq!(braces.to_string(), "{\n    op(a: int32)\n}");
+
+    let SyntaxElement::Node(parentheses) = &braces.children[3] else { panic!("expected a node") };
+    assert_eq!(parentheses.kind, NodeKind::Parentheses);
+    assert_eq!(parentheses.significant_tokens().len(), 5);
+
+    let span = parentheses.span().unwrap();
+    assert_eq!((span.start.row, span.start.col), (2, 7));
+    assert_eq!((span.end.row, span.end.col), (2, 17));
+}
+
+#[test]
+fn slice_files_provide_their_syntax_trees() {
+    // Arrange
+    let slice_file = SliceFile::new("test.slice".to_owned(), "module Test".to_owned(), true);
+
+    // Act
+    let tree = slice_file.syntax_tree();
+
+    // Assert
+    assert_eq!(tree.to_string(), "module Test");
+    assert_eq!(tree.tokens()[0].span.file, "test.slice");
+}
+
+#[test]
+fn text_can_be_tokenized_without_compiling() {
+    // Arrange
+    let slice = "module Test\nstruct S { a: \"unterminated";
+
+    // Act
+    let mut tokens = tokenize(slice, "test.slice").filter(|token| !token.kind.is_trivia());
+
+    // Assert
+    let first = tokens.next().unwrap();
+    assert_eq!((first.kind, first.text.as_str()), (SyntaxKind::Keyword, "module"));
+
+    let last = tokens.last().unwrap();
+    assert_eq!(last.kind, SyntaxKind::StringLiteral);
+    assert_eq!(last.text, "\"unterminated");
+    assert_eq!((last.span.start.row, last.span.start.col), (2, 15));
+    assert_eq!((last.span.end.row, last.span.end.col), (2, 28));
+}
+
+#[test_case("$", SyntaxKind::Unknown; "unknown symbols")]
+#[test_case(";", SyntaxKind::Unknown; "semicolons")]
+#[test_case("/* unterminated", SyntaxKind::BlockComment; "unterminated block comments")]
+#[test_case("//// not a doc comment", SyntaxKind::LineComment; "quadruple slashes")]
+#[test_case("\\struct", SyntaxKind::Identifier; "escaped keywords")]
+#[test_case("::", SyntaxKind::Symbol; "multi character symbols")]
+fn tokenizing_never_fails(slice: &str, expected: SyntaxKind) {
+    // Act
+    let tokens = tokenize(slice, "test.slice").collect::<Vec<_>>();
+
+    // Assert
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].kind, expected);
+    assert_eq!(tokens[0].text, slice);
+}
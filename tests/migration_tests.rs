@@ -0,0 +1,143 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compilation_state::CompilationState;
+use slicec::compile_from_strings;
+use slicec::grammar::CompilationMode;
+use slicec::migration::*;
+
+/// Compiles the provided slices, treating each of them as a source file.
+fn compile_sources(slices: &[&str]) -> CompilationState {
+    let mut state = compile_from_strings(slices, None, |_| {}, |_| {});
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+
+    for file in &mut state.files {
+        file.is_source = true;
+    }
+    state
+}
+
+fn blocker_kinds_of(migration: &FileMigration) -> Vec<BlockerKind> {
+    migration.blockers.iter().map(|blocker| blocker.kind).collect()
+}
+
+#[test]
+fn slice1_only_constructs_are_reported() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class C {}
+        exception E {}
+        compact struct S {
+            a: AnyClass?
+        }
+        interface I {
+            op(c: C?) throws E
+        }
+    ";
+    let state = compile_sources(&[slice]);
+
+    // Act
+    let report = MigrationReport::from_files(&state.files);
+
+    // Assert
+    let migration = &report.files[0];
+    let expected = [
+        BlockerKind::ClassDefinition,
+        BlockerKind::ExceptionDefinition,
+        BlockerKind::UnsupportedType,
+        BlockerKind::ExceptionSpecification,
+        BlockerKind::UnsupportedType,
+    ];
+    assert_eq!(blocker_kinds_of(migration), expected);
+    assert_eq!(
+        migration.blockers[2].message,
+        "the type 'AnyClass?' is only supported by Slice1",
+    );
+    assert!(!migration.can_migrate());
+    assert!(migration.rewrites.is_empty());
+}
+
+#[test]
+fn files_without_blockers_are_rewritten_to_slice2() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        compact struct S {
+            a: int32
+        }
+    ";
+    let state = compile_sources(&[slice]);
+
+    // Act
+    let report = MigrationReport::from_files(&state.files);
+
+    // Assert
+    let migration = &report.files[0];
+    assert!(migration.can_migrate());
+    assert_eq!(migration.rewrites.len(), 1);
+
+    let rewritten = migration.apply_rewrites(&state.files[0]);
+    assert_eq!(rewritten, slice.replace("mode = Slice1", "mode = Slice2"));
+    let rewritten_state = compile_sources(&[&rewritten]);
+    assert_eq!(rewritten_state.files[0].compilation_mode(), CompilationMode::Slice2);
+}
+
+#[test]
+fn definitions_used_by_other_slice1_files_are_reported() {
+    // Arrange
+    let slice1 = "
+        mode = Slice1
+        module Test
+
+        compact struct S {
+            a: int32
+        }
+    ";
+    let slice2 = "
+        mode = Slice1
+        module Test
+
+        compact struct Wrapper {
+            s: S
+        }
+    ";
+    let state = compile_sources(&[slice1, slice2]);
+
+    // Act
+    let report = MigrationReport::from_files(&state.files);
+
+    // Assert
+    assert_eq!(blocker_kinds_of(&report.files[0]), [BlockerKind::UsedBySlice1File]);
+    assert_eq!(report.files[0].blockers[0].span.file, "string-1");
+    assert_eq!(
+        report.files[0].blockers[0].message,
+        "'S' is used by 'string-1', which is a Slice1 file",
+    );
+    assert!(report.files[1].can_migrate());
+}
+
+#[test]
+fn only_slice1_source_files_are_reported() {
+    // Arrange
+    let slice1 = "
+        mode = Slice1
+        module Test
+    ";
+    let slice2 = "
+        module Test
+    ";
+    let mut state = compile_from_strings(&[slice1, slice2, slice1], None, |_| {}, |_| {});
+    state.files[1].is_source = true;
+    state.files[2].is_source = true;
+
+    // Act
+    let report = MigrationReport::from_files(&state.files);
+
+    // Assert
+    let paths = report.files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, ["string-2"]);
+}
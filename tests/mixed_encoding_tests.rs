@@ -77,3 +77,83 @@ fn invalid_mixed_compilation_mode_fails() {
     ];
     check_diagnostics(diagnostics, expected);
 }
+
+#[test]
+fn unsupported_types_are_traced_through_fields() {
+    // Arrange
+    let slice2 = "
+        mode = Slice2
+        module Test
+
+        compact struct Inner {
+            data: int32?
+        }
+        compact struct Outer {
+            i: Inner
+        }
+        custom ACustomType
+    ";
+    let slice1 = "
+        mode = Slice1
+        module Test
+        compact struct AStruct {
+            o: Outer
+            c: ACustomType
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[slice1, slice2]);
+
+    // Assert
+    let expected = [
+        Diagnostic::new(Error::UnsupportedType {
+            kind: "Outer".to_owned(),
+            mode: CompilationMode::Slice1,
+        })
+        .add_note("field 'i' isn't supported by Slice1", None)
+        .add_note("field 'data' isn't supported by Slice1", None),
+        Diagnostic::new(Error::UnsupportedType {
+            kind: "ACustomType".to_owned(),
+            mode: CompilationMode::Slice1,
+        })
+        .add_note(
+            "'ACustomType' is defined in a file whose compilation mode is Slice2",
+            None,
+        ),
+    ];
+    check_diagnostics(diagnostics, expected);
+}
+
+#[test]
+fn unsupported_types_are_traced_through_type_aliases() {
+    // Arrange
+    let slice2 = "
+        mode = Slice2
+        module Test
+
+        struct NotCompact {}
+        typealias Inner = NotCompact
+        typealias Outer = Inner
+    ";
+    let slice1 = "
+        mode = Slice1
+        module Test
+        compact struct AStruct {
+            o: Outer
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[slice1, slice2]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::UnsupportedType {
+        kind: "NotCompact".to_owned(),
+        mode: CompilationMode::Slice1,
+    })
+    .add_note("'Outer' is an alias of 'Inner'", None)
+    .add_note("'Inner' is an alias of 'NotCompact'", None)
+    .add_note("structs must be 'compact' to be supported by Slice1", None);
+    check_diagnostics(diagnostics, [expected]);
+}
@@ -0,0 +1,182 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::compile_from_strings;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use test_case::test_case;
+
+const DEFINITIONS: &str = "
+    module Very::Long::Nested
+
+    struct S {}
+    struct Pair<T> {
+        first: T
+        second: T
+    }
+";
+
+const ALIAS: &str = "
+    module Test
+    module Short = Very::Long::Nested
+";
+
+#[test_case("Short::S"; "relative")]
+#[test_case("Test::Short::S"; "scoped")]
+#[test_case("::Test::Short::S"; "global")]
+fn types_can_be_referenced_through_module_aliases(type_string: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test::Inner
+
+        struct Wrapper {{
+            s: {type_string}
+        }}
+        "
+    );
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, &slice, ALIAS]);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Inner::Wrapper::s").unwrap();
+    let Types::Struct(struct_def) = field.data_type().concrete_type() else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Very::Long::Nested::S");
+}
+
+#[test]
+fn module_aliases_apply_to_generic_structs_and_type_aliases() {
+    // Arrange
+    let slice = "
+        module Test
+
+        typealias Alias = Short::S
+        struct Wrapper {
+            p: Short::Pair<Alias>
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, ALIAS, slice]);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Wrapper::p").unwrap();
+    let Types::Struct(pair) = field.data_type().concrete_type() else { panic!() };
+    let first = pair.fields()[0].data_type().concrete_type();
+    let Types::Struct(struct_def) = first else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Very::Long::Nested::S");
+}
+
+#[test]
+fn module_aliases_are_stored_in_their_file() {
+    // Act
+    let state = compile_from_strings(&[DEFINITIONS, ALIAS], None, |_| {}, |_| {});
+
+    // Assert
+    assert!(state.files[0].module_aliases.is_empty());
+    let module_alias = &state.files[1].module_aliases[0];
+    assert_eq!(module_alias.scoped_identifier(), "Test::Short");
+    assert_eq!(module_alias.target_identifier(), "Very::Long::Nested");
+}
+
+#[test]
+fn module_aliases_do_not_apply_outside_their_scope() {
+    // Arrange
+    let slice = "
+        module Other
+
+        struct Wrapper {
+            s: Short::S
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, ALIAS, slice]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Short::S".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn definitions_in_inner_scopes_take_precedence_over_module_aliases() {
+    // Arrange
+    let slice = "
+        module Test::Inner::Short
+
+        struct S {}
+    ";
+    let user = "
+        module Test::Inner
+
+        struct Wrapper {
+            s: Short::S
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, ALIAS, slice, user]);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Inner::Wrapper::s").unwrap();
+    let Types::Struct(struct_def) = field.data_type().concrete_type() else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Test::Inner::Short::S");
+}
+
+#[test_case("Very::Missing", Error::DoesNotExist { identifier: "Very::Missing".to_owned() }; "missing module")]
+#[test_case(
+    "Very::Long::Nested::S",
+    Error::TypeMismatch { expected: "module".to_owned(), actual: "struct".to_owned(), is_concrete: true };
+    "type"
+)]
+fn targets_must_be_modules(target: &str, error: Error) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+        module Short = {target}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, &slice]);
+
+    // Assert
+    check_diagnostics(diagnostics, [Diagnostic::new(error)]);
+}
+
+#[test]
+fn module_aliases_cannot_be_redefined() {
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, ALIAS, ALIAS]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::Redefinition {
+        identifier: "Test::Short".to_owned(),
+    })
+    .add_note("'Test::Short' was previously defined here", None);
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn module_aliases_cannot_reuse_the_identifier_of_a_module() {
+    // Arrange
+    let slice = "
+        module Very
+        module Long = Very::Long::Nested
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, slice]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::Redefinition {
+        identifier: "Very::Long".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
@@ -6,6 +6,8 @@ mod module {
     use crate::test_helpers::*;
     use slicec::diagnostics::{Diagnostic, Error};
     use slicec::grammar::*;
+    use slicec::slice_file::Span;
+    use slicec::slice_options::SliceOptions;
 
     #[test]
     fn can_be_defined() {
@@ -71,4 +73,88 @@ mod module {
         assert!(ast.find_element::<Struct>("Foo::Test1").is_ok());
         assert!(ast.find_element::<Struct>("Foo::Test2").is_ok());
     }
+
+    #[test]
+    fn reopened_nested_modules_share_a_scope() {
+        // Arrange
+        let slice1 = "
+            module Foo::Bar
+            struct S {}
+        ";
+        let slice2 = "
+            module Foo
+            struct T {
+                s: Bar::S
+            }
+        ";
+        let slice3 = "
+            module Foo::Bar
+            struct S {}
+        ";
+
+        // Act
+        let diagnostics = parse_multiple_for_diagnostics(&[slice1, slice2, slice3]);
+
+        // Assert: the reference resolves, and the re-opened module's struct is a redefinition.
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "S".to_owned(),
+        })
+        .set_span(&Span::new((3, 20).into(), (3, 21).into(), "string-2"))
+        .add_note(
+            "'S' was previously defined here",
+            Some(&Span::new((3, 20).into(), (3, 21).into(), "string-0")),
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn cannot_have_a_body() {
+        // Arrange
+        let slice = "
+            module Foo {
+                struct S {}
+            }
+        ";
+
+        // Act
+        let state = parse(slice, None);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Syntax {
+            message: "module declarations cannot have bodies".to_owned(),
+        })
+        .set_span(&Span::new((2, 24).into(), (4, 14).into(), "string-0"))
+        .add_note(
+            "a module declaration applies to every definition after it in the file; remove the braces",
+            None,
+        );
+        assert!(state.ast.find_element::<Struct>("Foo::S").is_ok());
+        let diagnostics = diagnostics_from_compilation_state(state, &SliceOptions::default());
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn files_can_only_have_one_module_declaration() {
+        // Arrange
+        let slice = "
+            module Foo
+            struct S {}
+            module Bar
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Syntax {
+            message: "expected one of 'doc comment', 'struct', 'exception', 'class', 'interface', 'enum', 'custom', \
+                      'typealias', 'const', 'compact', 'unchecked', or '[', but found 'module'"
+                .to_owned(),
+        })
+        .add_note(
+            "each file can only have one module declaration; put definitions in other modules in separate files",
+            None,
+        );
+        check_diagnostics(diagnostics, [expected]);
+    }
 }
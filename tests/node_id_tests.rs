@@ -0,0 +1,112 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::ast::node::{Node, NodeId};
+use slicec::compile_from_strings;
+use slicec::grammar::*;
+
+const SLICE: &str = "
+    module Test
+
+    struct S {
+        a: Sequence<int32>
+    }
+
+    interface I {
+        op(x: bool) -> string
+    }
+";
+
+#[test]
+fn every_node_can_be_found_by_its_id() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+
+    for node in state.ast.as_slice() {
+        // Act
+        let element = <&dyn Element>::from(node);
+        let id = state.ast.id_of(element).unwrap();
+
+        // Assert
+        let found = <&dyn Element>::from(state.ast.get_by_id(id).unwrap());
+        assert!(std::ptr::addr_eq(found, element));
+    }
+}
+
+#[test]
+fn ids_are_unique() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+
+    // Act
+    let mut ids = state
+        .ast
+        .as_slice()
+        .iter()
+        .map(|node| state.ast.id_of(<&dyn Element>::from(node)).unwrap())
+        .collect::<Vec<_>>();
+
+    // Assert
+    let count = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), count);
+}
+
+#[test]
+fn ids_are_stable_across_compilations() {
+    // Arrange
+    let first = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let second = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+
+    // Act
+    let first_id = first
+        .ast
+        .id_of(first.ast.find_element::<Operation>("Test::I::op").unwrap());
+    let second_id = second
+        .ast
+        .id_of(second.ast.find_element::<Operation>("Test::I::op").unwrap());
+
+    // Assert
+    assert!(first_id.is_some());
+    assert_eq!(first_id, second_id);
+}
+
+#[test]
+fn anonymous_types_have_ids() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let field = state.ast.find_element::<Field>("Test::S::a").unwrap();
+    let Types::Sequence(sequence) = field.data_type().concrete_type() else { panic!("expected a sequence") };
+
+    // Act
+    let id = state.ast.id_of(sequence).unwrap();
+
+    // Assert
+    assert!(matches!(state.ast.get_by_id(id), Some(Node::Sequence(_))));
+}
+
+#[test]
+fn ids_can_be_converted_to_and_from_integers() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let struct_def = state.ast.find_element::<Struct>("Test::S").unwrap();
+    let id = state.ast.id_of(struct_def).unwrap();
+
+    // Act
+    let node = state.ast.get_by_id(NodeId::new(id.index()));
+
+    // Assert
+    assert!(matches!(node, Some(Node::Struct(_))));
+}
+
+#[test]
+fn out_of_range_ids_are_not_found() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+
+    // Act
+    let node = state.ast.get_by_id(NodeId::new(state.ast.as_slice().len()));
+
+    // Assert
+    assert!(node.is_none());
+}
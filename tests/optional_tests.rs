@@ -209,10 +209,15 @@ mod optional {
             let diagnostics = parse_for_diagnostics(slice);
 
             // Assert
-            let expected = Diagnostic::new(Error::OptionalsNotSupported {
+            let expected = Diagnostic::new(Error::OptionalElementsNotSupported {
+                collection: "sequence".to_owned(),
                 kind: "bool".to_owned(),
             })
-            .set_span(&Span::new((5, 33).into(), (5, 38).into(), "string-0"));
+            .set_span(&Span::new((5, 24).into(), (5, 39).into(), "string-0"))
+            .add_note(
+                "Slice1 doesn't support optional sequence elements, except for classes, custom types, and 'AnyClass'",
+                Some(&Span::new((5, 33).into(), (5, 38).into(), "string-0")),
+            );
 
             check_diagnostics(diagnostics, [expected]);
         }
@@ -258,10 +263,15 @@ mod optional {
             let diagnostics = parse_for_diagnostics(slice);
 
             // Assert
-            let expected = Diagnostic::new(Error::OptionalsNotSupported {
+            let expected = Diagnostic::new(Error::OptionalElementsNotSupported {
+                collection: "dictionary".to_owned(),
                 kind: "int32".to_owned(),
             })
-            .set_span(&Span::new((5, 43).into(), (5, 49).into(), "string-0"));
+            .set_span(&Span::new((5, 24).into(), (5, 50).into(), "string-0"))
+            .add_note(
+                "Slice1 doesn't support optional dictionary values, except for classes, custom types, and 'AnyClass'",
+                Some(&Span::new((5, 43).into(), (5, 49).into(), "string-0")),
+            );
 
             check_diagnostics(diagnostics, [expected]);
         }
@@ -407,6 +417,7 @@ mod optional {
 
     mod slice2 {
         use super::*;
+        use slicec::utils::code_gen_util::{get_bit_sequence_size, is_element_bit_sequence_encodable};
         use test_case::test_case;
 
         #[test_case("bool")]
@@ -718,5 +729,71 @@ mod optional {
             let member_c = ast.find_element::<Field>("Test::S::c").unwrap();
             assert!(member_c.data_type().is_optional);
         }
+
+        #[test]
+        fn optional_elements_are_encoded_with_bit_sequences() {
+            // Arrange
+            let slice = "
+                module Test
+                struct S {
+                    a: Sequence<bool?>
+                    b: Dictionary<string, int32?>
+                    c: Sequence<bool>
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let element_type_of = |identifier: &str| match ast
+                .find_element::<Field>(identifier)
+                .unwrap()
+                .data_type()
+                .concrete_type()
+            {
+                Types::Sequence(sequence) => &sequence.element_type,
+                Types::Dictionary(dictionary) => &dictionary.value_type,
+                _ => panic!(),
+            };
+            assert!(is_element_bit_sequence_encodable(
+                element_type_of("Test::S::a"),
+                Encoding::Slice2
+            ));
+            assert!(is_element_bit_sequence_encodable(
+                element_type_of("Test::S::b"),
+                Encoding::Slice2
+            ));
+            assert!(!is_element_bit_sequence_encodable(
+                element_type_of("Test::S::c"),
+                Encoding::Slice2
+            ));
+            assert!(!is_element_bit_sequence_encodable(
+                element_type_of("Test::S::a"),
+                Encoding::Slice1
+            ));
+        }
+
+        #[test]
+        fn bit_sequences_only_count_untagged_optional_members() {
+            // Arrange
+            let slice = "
+                module Test
+                struct S {
+                    a: bool?
+                    b: string
+                    c: int32?
+                    tag(1) d: int32?
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let fields = ast.find_element::<Struct>("Test::S").unwrap().fields();
+            assert_eq!(get_bit_sequence_size(&fields, Encoding::Slice2), 2);
+            assert_eq!(get_bit_sequence_size(&fields, Encoding::Slice1), 0);
+        }
     }
 }
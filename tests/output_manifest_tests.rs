@@ -0,0 +1,98 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::grammar::*;
+use slicec::output_manifest::{GeneratedFile, OutputManifest};
+use slicec::slice_options::SliceOptions;
+
+#[test]
+fn generated_files_record_their_sources_and_entities() {
+    // Arrange
+    let slice1 = "
+        module Foo
+        struct S {}
+        struct T {}
+    ";
+    let slice2 = "
+        module Bar
+        interface I {}
+    ";
+    let ast = parse_multiple_for_ast(&[slice1, slice2]);
+    let s = ast.find_element::<Struct>("Foo::S").unwrap();
+    let t = ast.find_element::<Struct>("Foo::T").unwrap();
+    let i = ast.find_element::<Interface>("Bar::I").unwrap();
+
+    let mut manifest = OutputManifest::default();
+
+    // Act
+    manifest.add_file("generated/Foo.cs", b"hello", &[s, t, i]);
+
+    // Assert
+    let expected = GeneratedFile {
+        path: "generated/Foo.cs".to_owned(),
+        sources: vec!["string-0".to_owned(), "string-1".to_owned()],
+        entities: vec!["Foo::S".to_owned(), "Foo::T".to_owned(), "Bar::I".to_owned()],
+        hash: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".to_owned(),
+    };
+    assert_eq!(manifest.files, [expected]);
+}
+
+#[test]
+fn files_missing_from_the_current_manifest_are_stale() {
+    // Arrange
+    let mut previous = OutputManifest::default();
+    previous.add_file("A.cs", b"a", &[]);
+    previous.add_file("B.cs", b"b", &[]);
+    previous.add_file("C.cs", b"c", &[]);
+
+    let mut current = OutputManifest::default();
+    current.add_file("B.cs", b"new contents", &[]);
+
+    // Act
+    let stale_files = current.stale_files(&previous);
+
+    // Assert
+    assert_eq!(stale_files, ["A.cs", "C.cs"]);
+}
+
+#[test]
+fn manifests_round_trip_through_json() {
+    // Arrange
+    let ast = parse_for_ast("module Foo struct S {}");
+    let s = ast.find_element::<Struct>("Foo::S").unwrap();
+
+    let mut manifest = OutputManifest::default();
+    manifest.add_file("Foo.cs", b"contents", &[s]);
+
+    // Act
+    let parsed = OutputManifest::from_json(&manifest.to_json()).unwrap();
+
+    // Assert
+    assert_eq!(parsed, manifest);
+}
+
+#[test]
+fn manifest_is_only_emitted_when_requested() {
+    // Arrange
+    let path = std::env::temp_dir().join(format!("slicec-manifest-{}.json", std::process::id()));
+    let mut manifest = OutputManifest::default();
+    manifest.add_file("Foo.cs", b"contents", &[]);
+
+    let options = SliceOptions {
+        manifest: Some(path.display().to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    manifest.emit(&SliceOptions::default()).unwrap();
+    let existed_before = path.exists();
+    manifest.emit(&options).unwrap();
+
+    // Assert
+    assert!(!existed_before);
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(contents, manifest.to_json());
+}
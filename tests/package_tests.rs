@@ -0,0 +1,255 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::check_diagnostics;
+use slicec::compile_from_options;
+use slicec::diagnostics::{Diagnostic, Diagnostics, Error};
+use slicec::grammar::CompilationMode;
+use slicec::packages::{resolve_packages, PackageManifest, PackageRequest, MANIFEST_FILE_NAME};
+use slicec::slice_options::SliceOptions;
+use std::fs;
+use std::path::{Path, PathBuf};
+use test_case::test_case;
+
+/// Creates an empty directory for a test to write its packages to.
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("slicec-packages-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Writes a package to `directory`, with a manifest listing each of the provided `(path, mode, contents)` files.
+fn write_package(directory: &Path, name: &str, version: &str, files: &[(&str, &str, &str)], dependencies: &str) {
+    fs::create_dir_all(directory).unwrap();
+    let mut listed_files = Vec::new();
+    for (path, mode, contents) in files {
+        fs::write(directory.join(path), contents).unwrap();
+        listed_files.push(format!(r#"{{ "path": "{path}", "mode": "{mode}" }}"#));
+    }
+    let manifest = format!(
+        r#"{{ "name": "{name}", "version": "{version}", "files": [{}], "dependencies": {{ {dependencies} }} }}"#,
+        listed_files.join(", "),
+    );
+    fs::write(directory.join(MANIFEST_FILE_NAME), manifest).unwrap();
+}
+
+fn options(directory: &Path, packages: &[&str]) -> SliceOptions {
+    SliceOptions {
+        packages: packages.iter().map(|package| package.to_string()).collect(),
+        package_paths: vec![directory.display().to_string()],
+        no_default_references: true,
+        ..Default::default()
+    }
+}
+
+fn resolved_packages(options: &SliceOptions) -> Vec<(String, String)> {
+    let mut diagnostics = Diagnostics::new();
+    let packages = resolve_packages(options, &mut diagnostics);
+    let expected: [Diagnostic; 0] = [];
+    check_diagnostics(diagnostics.into_inner(), expected);
+    packages
+        .into_iter()
+        .map(|package| (package.manifest.name, package.manifest.version))
+        .collect()
+}
+
+#[test_case("Greetings", "Greetings", None; "name")]
+#[test_case("Greetings@1.2.0", "Greetings", Some("1.2.0"); "name and version")]
+fn package_requests_are_parsed(request: &str, name: &str, version: Option<&str>) {
+    // Act
+    let request = PackageRequest::parse(request).unwrap();
+
+    // Assert
+    assert_eq!(request.name, name);
+    assert_eq!(request.version.as_deref(), version);
+}
+
+#[test_case(""; "empty")]
+#[test_case("@1.0.0"; "missing name")]
+#[test_case("Greetings@"; "missing version")]
+fn invalid_package_requests_are_rejected(request: &str) {
+    // Act
+    let result = PackageRequest::parse(request);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn package_files_are_compiled_as_references() {
+    // Arrange
+    let directory = test_directory("references");
+    let greeting = "module Greetings\nstruct Greeting { message: string }";
+    write_package(
+        &directory.join("greetings"),
+        "Greetings",
+        "1.0.0",
+        &[("Greeting.slice", "Slice2", greeting)],
+        "",
+    );
+    let source = directory.join("Source.slice");
+    fs::write(&source, "module Test\nstruct S { g: Greetings::Greeting }").unwrap();
+
+    let options = SliceOptions {
+        sources: vec![source.display().to_string()],
+        ..options(&directory, &["Greetings"])
+    };
+
+    // Act
+    let state = compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    let expected: [Diagnostic; 0] = [];
+    check_diagnostics(state.diagnostics.into_inner(), expected);
+    assert_eq!(state.packages.len(), 1);
+    assert_eq!(state.files.len(), 2);
+    assert!(state.files[0].is_source);
+    assert!(!state.files[1].is_source);
+}
+
+#[test_case("Greetings", "1.10.0"; "latest")]
+#[test_case("Greetings@1.9.0", "1.9.0"; "requested")]
+fn package_versions_are_selected(request: &str, expected_version: &str) {
+    // Arrange
+    let directory = test_directory(&format!("versions-{expected_version}"));
+    write_package(&directory.join("greetings-1.9.0"), "Greetings", "1.9.0", &[], "");
+    write_package(&directory.join("greetings-1.10.0"), "Greetings", "1.10.0", &[], "");
+
+    // Act
+    let packages = resolved_packages(&options(&directory, &[request]));
+
+    // Assert
+    assert_eq!(packages, [("Greetings".to_owned(), expected_version.to_owned())]);
+}
+
+#[test]
+fn dependencies_are_resolved_transitively() {
+    // Arrange
+    let directory = test_directory("dependencies");
+    write_package(&directory.join("app"), "App", "1.0.0", &[], r#""Greetings": "1.0.0""#);
+    write_package(
+        &directory.join("greetings"),
+        "Greetings",
+        "1.0.0",
+        &[],
+        r#""Common": "2.0.0""#,
+    );
+    write_package(&directory.join("common"), "Common", "2.0.0", &[], "");
+
+    // Act
+    let packages = resolved_packages(&options(&directory, &["App"]));
+
+    // Assert
+    let names = packages.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["App", "Greetings", "Common"]);
+}
+
+#[test]
+fn missing_packages_are_reported() {
+    // Arrange
+    let directory = test_directory("missing");
+    write_package(&directory.join("greetings"), "Greetings", "1.0.0", &[], "");
+
+    // Act
+    let mut diagnostics = Diagnostics::new();
+    resolve_packages(&options(&directory, &["Greetings@2.0.0", "Other"]), &mut diagnostics);
+
+    // Assert
+    let expected = [
+        Diagnostic::new(Error::PackageNotFound {
+            name: "Greetings".to_owned(),
+            version: Some("2.0.0".to_owned()),
+        }),
+        Diagnostic::new(Error::PackageNotFound {
+            name: "Other".to_owned(),
+            version: None,
+        }),
+    ];
+    check_diagnostics(diagnostics.into_inner(), expected);
+}
+
+#[test]
+fn conflicting_versions_are_reported() {
+    // Arrange
+    let directory = test_directory("conflicts");
+    write_package(&directory.join("app"), "App", "1.0.0", &[], r#""Common": "1.0.0""#);
+    write_package(&directory.join("common-1"), "Common", "1.0.0", &[], "");
+    write_package(&directory.join("common-2"), "Common", "2.0.0", &[], "");
+
+    // Act
+    let mut diagnostics = Diagnostics::new();
+    resolve_packages(&options(&directory, &["Common@2.0.0", "App"]), &mut diagnostics);
+
+    // Assert
+    let expected = Diagnostic::new(Error::ConflictingPackageVersions {
+        name: "Common".to_owned(),
+        resolved: "2.0.0".to_owned(),
+        requested: "1.0.0".to_owned(),
+    });
+    check_diagnostics(diagnostics.into_inner(), [expected]);
+}
+
+#[test_case(&["Common", "App"]; "latest requested first")]
+#[test_case(&["App", "Common"]; "latest requested last")]
+fn versionless_requests_use_the_version_required_by_dependencies(requests: &[&str]) {
+    // Arrange
+    let directory = test_directory(&format!("pinned-{}", requests.join("-")));
+    write_package(&directory.join("app"), "App", "1.0.0", &[], r#""Common": "2.0.0""#);
+    write_package(&directory.join("common-2"), "Common", "2.0.0", &[], "");
+    write_package(&directory.join("common-3"), "Common", "3.0.0", &[], "");
+
+    // Act
+    let packages = resolved_packages(&options(&directory, requests));
+
+    // Assert
+    assert!(packages.contains(&("Common".to_owned(), "2.0.0".to_owned())));
+    assert_eq!(packages.len(), 2);
+}
+
+#[test_case(r#"{ "version": "1.0.0" }"#; "missing name")]
+#[test_case(r#"{ "name": "A@B", "version": "1.0.0" }"#; "invalid name")]
+#[test_case(r#"{ "name": "A", "version": "1.0.0", "files": [{ "path": "A.slice", "mode": "Slice3" }] }"#; "invalid mode")]
+fn invalid_manifests_are_rejected(json: &str) {
+    // Act
+    let result = PackageManifest::from_json(json);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn files_must_use_the_mode_listed_in_their_manifest() {
+    // Arrange
+    let directory = test_directory("modes");
+    let legacy = "module Legacy\nstruct S {}";
+    write_package(
+        &directory.join("legacy"),
+        "Legacy",
+        "1.0.0",
+        &[("Legacy.slice", "Slice1", legacy)],
+        "",
+    );
+    let source = directory.join("Source.slice");
+    fs::write(&source, "module Test").unwrap();
+
+    let options = SliceOptions {
+        sources: vec![source.display().to_string()],
+        absolute_paths: true,
+        ..options(&directory, &["Legacy"])
+    };
+
+    // Act
+    let state = compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    let path = directory.join("legacy").join("Legacy.slice").canonicalize().unwrap();
+    let expected = Diagnostic::new(Error::PackageModeMismatch {
+        path: path.display().to_string(),
+        package: "Legacy".to_owned(),
+        expected: CompilationMode::Slice1,
+        actual: CompilationMode::Slice2,
+    });
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
@@ -0,0 +1,57 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::panic_handler;
+use slicec::slice_options::SliceOptions;
+use std::fs;
+use std::path::Path;
+
+/// Returns the paths of every file under the provided directory.
+fn files_under(directory: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        match path.is_dir() {
+            true => files.extend(files_under(&path)),
+            false => files.push(path.display().to_string()),
+        }
+    }
+    files
+}
+
+#[test]
+fn panics_are_reported_as_internal_compiler_errors_with_a_bundle() {
+    // Arrange
+    let root = std::env::temp_dir().join(format!("slicec-ice-{}", std::process::id()));
+    let bundle = root.join("bundle");
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("Input.slice"), "module Test").unwrap();
+
+    let options = SliceOptions {
+        sources: vec![root.join("Input.slice").display().to_string()],
+        ice_bundle: Some(bundle.display().to_string()),
+        ..Default::default()
+    };
+    panic_handler::install(&options);
+
+    // Act
+    let result = std::panic::catch_unwind(|| {
+        panic_handler::set_phase("testing");
+        panic_handler::set_file("Input.slice");
+        panic_handler::set_entity("Test::S".to_owned());
+        panic!("something went wrong");
+    });
+    let _ = std::panic::take_hook(); // Restore the default panic hook.
+
+    // Assert
+    assert!(result.is_err());
+    let message = fs::read_to_string(bundle.join("ice.txt")).unwrap();
+    assert!(message.starts_with("error: internal compiler error: something went wrong\n"));
+    assert!(message.contains(panic_handler::ISSUE_TRACKER));
+    assert!(message.ends_with("note: the compiler panicked while testing, in 'Input.slice', on 'Test::S'\n"));
+
+    assert!(bundle.join("command_line.txt").exists());
+    let files = files_under(&bundle.join("files"));
+    assert_eq!(files.len(), 1);
+    assert!(files[0].ends_with("Input.slice"));
+    assert_eq!(fs::read_to_string(&files[0]).unwrap(), "module Test");
+}
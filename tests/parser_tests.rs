@@ -4,8 +4,11 @@ mod test_helpers;
 
 use crate::test_helpers::*;
 use slicec::diagnostics::{Diagnostic, Error};
-use slicec::grammar::{attributes, AttributeFunctions, Enumerator, Struct};
+use slicec::grammar::{
+    attributes, Attributable, AttributeFunctions, Constant, Enumerator, EnumeratorValue, Literal, Radix, Struct, Symbol,
+};
 use slicec::slice_file::Span;
+use test_case::test_case;
 
 #[test]
 fn parse_empty_string() {
@@ -35,6 +38,58 @@ fn parse_ideographic_space() {
     assert_parses(slice);
 }
 
+#[test]
+fn leading_byte_order_mark_is_ignored() {
+    // Arrange
+    let slice = "\u{FEFF}module Test\nstruct Foo {}";
+
+    // Act
+    let state = parse(slice, None);
+
+    // Assert
+    assert!(state.diagnostics.is_empty());
+    assert!(!state.files[0].raw_text.starts_with('\u{FEFF}'));
+
+    let struct_def = state.ast.find_element::<Struct>("Test::Foo").unwrap();
+    assert_eq!(struct_def.span().start, (2, 1).into());
+}
+
+#[test]
+fn byte_order_marks_are_only_allowed_at_the_start_of_a_file() {
+    // Arrange
+    let slice = "module Test\n\u{FEFF}struct Foo {}";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "unknown symbol '\u{FEFF}'".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn string_literals_can_contain_unicode() {
+    // Arrange
+    let slice = r#"
+        module Test
+
+        [deprecated("非推奨 😊")] struct Foo {}
+    "#;
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let struct_def = ast.find_element::<Struct>("Test::Foo").unwrap();
+    let deprecated = struct_def.find_attribute::<attributes::Deprecated>(false).unwrap();
+    assert_eq!(deprecated.reason, Some("非推奨 😊".to_owned()));
+
+    // Columns count characters, so each of these wide characters only occupies a single column.
+    assert_eq!(struct_def.span().start, (4, 31).into());
+}
+
 #[test]
 fn string_literals_cannot_contain_newlines() {
     // Arrange
@@ -72,7 +127,7 @@ fn string_literals_support_character_escaping() {
 
     // Assert
     let struct_def = ast.find_element::<Struct>("Test::Foo").unwrap();
-    let deprecated = struct_def.find_attribute::<attributes::Deprecated>().unwrap();
+    let deprecated = struct_def.find_attribute::<attributes::Deprecated>(false).unwrap();
     assert_eq!(deprecated.reason, Some("This is a backslash\"\\\"n.".to_owned()))
 }
 
@@ -95,6 +150,86 @@ fn integer_literals_can_contain_underscores() {
     assert_eq!(enumerator.value(), 17_000_000);
 }
 
+#[test_case("0xFF", 255, Radix::Hexadecimal; "hexadecimal")]
+#[test_case("0b1010", 10, Radix::Binary; "binary")]
+#[test_case("1_000", 1000, Radix::Decimal; "decimal")]
+#[test_case("-0x10", -16, Radix::Hexadecimal; "negative")]
+fn integer_literals_preserve_their_lexeme(lexeme: &str, value: i128, radix: Radix) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+
+        enum Foo : int32 {{
+            A = {lexeme}
+        }}
+        "
+    );
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let enumerator = ast.find_element::<Enumerator>("Test::Foo::A").unwrap();
+    let EnumeratorValue::Explicit(integer) = &enumerator.value else { panic!() };
+    let literal = integer.literal.as_ref().unwrap();
+    assert_eq!(integer.value, value);
+    assert_eq!(integer.lexeme(), Some(lexeme));
+    assert_eq!(literal.value, value);
+    assert_eq!(literal.radix, radix);
+}
+
+#[test]
+fn computed_integers_have_no_literal() {
+    // Arrange
+    let slice = "
+        module Test
+
+        const C = 0x10 << 2
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let constant = ast.find_element::<Constant>("Test::C").unwrap();
+    assert_eq!(constant.value(), 64);
+    assert!(constant.value.literal.is_none());
+}
+
+#[test]
+fn attribute_arguments_preserve_their_literals() {
+    // Arrange
+    let slice = r#"
+        module Test
+
+        [custom::attr("a \"quoted\" value", 0xFF, true, name)]
+        struct S {}
+    "#;
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+    let unparsed = struct_def.attributes()[0].downcast::<attributes::Unparsed>().unwrap();
+    let [Some(Literal::String(string)), Some(Literal::Integer(integer)), Some(Literal::Bool(bool)), None] =
+        unparsed.arg_literals.as_slice()
+    else {
+        panic!("{:?}", unparsed.arg_literals);
+    };
+
+    assert_eq!(string.value, r#"a "quoted" value"#);
+    assert_eq!(string.lexeme, r#""a \"quoted\" value""#);
+    assert_eq!(unparsed.args[0], string.value);
+    assert_eq!(
+        (integer.value, integer.radix, integer.lexeme.as_str()),
+        (255, Radix::Hexadecimal, "0xFF")
+    );
+    assert!(bool.value);
+    assert_eq!(unparsed.args[3], "name");
+}
+
 // Ensure a syntax error in one file doesn't affect how we parse other files; See: github.com/icerpc/slicec/issues/559.
 #[test]
 fn files_are_parsed_independently() {
@@ -110,7 +245,7 @@ fn files_are_parsed_independently() {
     let diagnostics = parse_multiple_for_diagnostics(&[slice1, slice2]);
 
     // Assert
-    let expected_message = "expected one of 'doc comment', 'struct', 'exception', 'class', 'interface', 'enum', 'custom', 'typealias', 'compact', 'unchecked', '[', or '::', but found '-'";
+    let expected_message = "expected one of 'doc comment', 'module', 'struct', 'exception', 'class', 'interface', 'enum', 'custom', 'typealias', 'const', 'compact', 'unchecked', 'using', '[', or '{', but found '-'";
     let expected = [
         Diagnostic::new(Error::Syntax {
             message: expected_message.to_owned(),
@@ -121,3 +256,171 @@ fn files_are_parsed_independently() {
     ];
     check_diagnostics(diagnostics, expected);
 }
+
+#[test_case("class C : uint8 {}", "class", "primitive"; "class base")]
+#[test_case("exception E : Sequence<bool> {}", "exception", "sequence"; "exception base")]
+#[test_case("interface I : Dictionary<bool, bool> {}", "interface", "dictionary"; "interface base")]
+#[test_case("interface I { op() throws string }", "exception", "primitive"; "exception specification")]
+#[test_case("enum E : Result<bool, bool> { A }", "primitive", "result type"; "enum underlying type")]
+#[test_case("typealias A = uint8\nclass C : A {}", "class", "primitive"; "aliased class base")]
+#[test_case("typealias A = Sequence<bool>\nexception E : A {}", "exception", "sequence"; "aliased exception base")]
+#[test_case("typealias A = bool\ninterface I : A {}", "interface", "primitive"; "aliased interface base")]
+#[test_case("typealias A = Result<bool, bool>\nenum E : A { X }", "primitive", "result type"; "aliased enum underlying type")]
+fn base_and_underlying_types_must_have_the_expected_kind(definition: &str, expected: &str, actual: &str) {
+    // Arrange
+    let slice = format!("mode = Slice1\nmodule Test\n{definition}");
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::TypeMismatch {
+        expected: expected.to_owned(),
+        actual: actual.to_owned(),
+        is_concrete: true,
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test_case("#if (A && ("; "unterminated preprocessor expression")]
+#[test_case("module Test\nstruct S { a: Sequence<"; "unterminated type")]
+#[test_case("module Test\n/// {@link\nstruct S {}"; "unterminated link")]
+#[test_case("\u{feff}\u{2028}[[\"\\"; "unusual characters")]
+fn parsing_arbitrary_input_never_panics(slice: &str) {
+    // Act/Assert
+    slicec::fuzzing::preprocess(slice);
+    slicec::fuzzing::lex(slice);
+    slicec::fuzzing::parse(slice);
+}
+
+#[test_case("interface I : A, B, {}"; "interface bases")]
+#[test_case("struct S { a: Sequence<bool,> }"; "sequence")]
+#[test_case("struct S { a: Dictionary<bool, string,> }"; "dictionary")]
+#[test_case("struct S { a: Result<bool, string,> }"; "result")]
+#[test_case("[cs::attribute(\"a\", \"b\",)]\nstruct S {}"; "attribute arguments")]
+#[test_case("struct S {\n a: bool,\n b: bool,\n}"; "fields")]
+#[test_case("struct S {\n a: bool\n b: bool\n}"; "newline separated fields")]
+fn trailing_commas_are_allowed(definition: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+        interface A {{}}
+        interface B {{}}
+        {definition}
+        "
+    );
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test_case("interface I : A B {}", 25; "interface bases")]
+#[test_case("interface I { op() throws (E F) }", 38; "exception specification")]
+#[test_case("struct S { a: Dictionary<bool string> }", 39; "dictionary")]
+#[test_case("struct S { a: Result<bool string> }", 35; "result")]
+#[test_case("[foo(\"a\" \"b\")]\nstruct S {}", 18; "attribute arguments")]
+fn missing_commas_are_reported(definition: &str, column: usize) {
+    // Arrange
+    let slice = format!(
+        "
+        mode = Slice1
+        module Test
+        interface A {{}}
+        exception E {{}}
+        exception F {{}}
+        {definition}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let span = Span::new((7, column).into(), (7, column).into(), "string-0");
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "missing ',' between list elements".to_owned(),
+    })
+    .set_span(&span)
+    .add_note("elements of this list must be separated by a ','", None);
+
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn missing_commas_do_not_stop_parsing() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            a: Dictionary<bool string>
+            b: Result<bool string>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = || {
+        Diagnostic::new(Error::Syntax {
+            message: "missing ',' between list elements".to_owned(),
+        })
+        .add_note("elements of this list must be separated by a ','", None)
+    };
+    check_diagnostics(diagnostics, [expected(), expected()]);
+}
+
+#[test]
+fn repeated_commas_are_reported() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            a: Dictionary<bool,, string>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let span = Span::new((4, 31).into(), (4, 33).into(), "string-0");
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "list elements must be separated by a single ','".to_owned(),
+    })
+    .set_span(&span);
+
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test_case("struct S { a: stream int32 }", "stream", (2, 23); "stream on field")]
+#[test_case("struct S { idempotent a: int32 }", "idempotent", (2, 20); "idempotent on field")]
+#[test_case("idempotent interface I {}", "idempotent", (2, 9); "idempotent on interface")]
+#[test_case("struct S { a: tag(1) int32? }", "tag", (2, 23); "tag after identifier")]
+#[test_case("interface I { op() -> stream tag(1) int32? }", "tag", (2, 38); "tag after stream")]
+fn misplaced_modifiers_have_a_note_on_where_they_can_be_used(definition: &str, modifier: &str, start: (usize, usize)) {
+    // Arrange
+    let slice = format!("module Test\n        {definition}");
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let [diagnostic] = diagnostics.as_slice() else { panic!("expected a single diagnostic: {diagnostics:?}") };
+    assert!(diagnostic.message().ends_with(&format!("but found '{modifier}'")));
+    assert_eq!(diagnostic.span().unwrap().start, start.into());
+
+    let expected_note = match modifier {
+        "stream" => {
+            "'stream' can only be applied to the type of an operation's last parameter or return type, as in \
+             'op(data: stream uint8)'"
+        }
+        "idempotent" => "'idempotent' can only be applied to operations, as in 'idempotent op()'",
+        _ => {
+            "tags can only be applied to fields, parameters, and return types, and must come before the member's \
+             name, as in 'tag(1) a: int32?'"
+        }
+    };
+    assert_eq!(diagnostic.notes()[0].message, expected_note);
+}
@@ -0,0 +1,80 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod prelude {
+    use crate::test_helpers::*;
+    use slicec::compile_with_prelude;
+    use slicec::diagnostics::{Diagnostic, Error};
+    use slicec::grammar::*;
+    use slicec::slice_options::SliceOptions;
+
+    const WELL_KNOWN_TYPES: &str = "
+        module WellKnownTypes
+
+        custom Duration
+    ";
+
+    #[test]
+    fn source_files_can_reference_prelude_definitions() {
+        // Arrange
+        let options = SliceOptions {
+            sources: vec!["tests/files/prelude.slice".to_owned()],
+            ..Default::default()
+        };
+
+        // Act
+        let state = compile_with_prelude(&[("WellKnownTypes.slice", WELL_KNOWN_TYPES)], &options, |_| {}, |_| {});
+
+        // Assert
+        assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+        let field = state.ast.find_element::<Field>("Test::S::timeout").unwrap();
+        let Types::CustomType(custom_type) = field.data_type.concrete_type() else { panic!() };
+        assert_eq!(custom_type.parser_scoped_identifier(), "WellKnownTypes::Duration");
+    }
+
+    #[test]
+    fn prelude_files_are_not_source_files() {
+        // Arrange
+        let options = SliceOptions {
+            sources: vec!["tests/files/prelude.slice".to_owned()],
+            ..Default::default()
+        };
+
+        // Act
+        let state = compile_with_prelude(&[("WellKnownTypes.slice", WELL_KNOWN_TYPES)], &options, |_| {}, |_| {});
+
+        // Assert
+        let prelude_file = state
+            .files
+            .iter()
+            .find(|f| f.relative_path == "WellKnownTypes.slice")
+            .unwrap();
+        let source_file = state
+            .files
+            .iter()
+            .find(|f| f.relative_path == "tests/files/prelude.slice")
+            .unwrap();
+        assert!(!prelude_file.is_source);
+        assert!(source_file.is_source);
+    }
+
+    #[test]
+    fn prelude_definitions_are_required_for_resolution() {
+        // Arrange
+        let options = SliceOptions {
+            sources: vec!["tests/files/prelude.slice".to_owned()],
+            ..Default::default()
+        };
+
+        // Act
+        let state = compile_with_prelude(&[], &options, |_| {}, |_| {});
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected = Diagnostic::new(Error::DoesNotExist {
+            identifier: "WellKnownTypes::Duration".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+}
@@ -75,6 +75,22 @@ fn identifiers_must_start_with_a_letter(identifier: &str) {
     check_diagnostics(diagnostics, [expected]);
 }
 
+#[test]
+fn identifiers_must_be_ascii() {
+    // Arrange
+    let slice = "#define café";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::Syntax {
+        message: "invalid identifier 'café': identifiers can only contain ASCII letters, digits, and underscores"
+            .to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
 #[test]
 fn preprocessor_define_symbol() {
     // Arrange
@@ -0,0 +1,122 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::test_helpers::*;
+use clap::Parser;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use slicec::slice_options::SliceOptions;
+use test_case::test_case;
+
+fn options_with_aliases(aliases: &[&str]) -> SliceOptions {
+    SliceOptions {
+        primitive_aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn aliases_resolve_to_their_primitives() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            a: f32
+            b: Sequence<i64>
+        }
+    ";
+    let options = options_with_aliases(&["f32=float32", "i64=int64"]);
+
+    // Act
+    let state = parse(slice, Some(&options));
+
+    // Assert
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+    let fields = state.ast.find_element::<Struct>("Test::S").unwrap().fields();
+    assert!(matches!(
+        fields[0].data_type().concrete_type(),
+        Types::Primitive(Primitive::Float32)
+    ));
+    let Types::Sequence(sequence) = fields[1].data_type().concrete_type() else { panic!() };
+    assert!(matches!(
+        sequence.element_type.concrete_type(),
+        Types::Primitive(Primitive::Int64)
+    ));
+}
+
+#[test]
+fn aliases_are_not_available_by_default() {
+    // Arrange
+    let slice = "
+        module Test
+
+        typealias T = f32
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "f32".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn definitions_shadow_aliases() {
+    // Arrange
+    let slice = "
+        module Test
+
+        custom f32
+
+        struct S {
+            a: f32
+        }
+    ";
+    let options = options_with_aliases(&["f32=float32"]);
+
+    // Act
+    let state = parse(slice, Some(&options));
+
+    // Assert
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+    let field = state.ast.find_element::<Field>("Test::S::a").unwrap();
+    assert!(matches!(field.data_type().concrete_type(), Types::CustomType(_)));
+}
+
+#[test_case("f32"; "missing primitive")]
+#[test_case("f32=float"; "unknown primitive")]
+#[test_case("1f=float32"; "invalid alias")]
+#[test_case("struct=float32"; "keyword alias")]
+#[test_case("const=float32"; "contextual keyword alias")]
+#[test_case("AnyClass=float32"; "built-in class alias")]
+fn malformed_aliases_are_rejected(alias: &str) {
+    // Act
+    let result = SliceOptions::try_parse_from(["slicec", "test.slice", "--primitive-alias", alias]);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test_case("uuid"; "built-in type")]
+#[test_case("timestamp"; "another built-in type")]
+#[test_case("f32"; "duplicate alias")]
+fn aliases_that_are_already_defined_are_rejected(alias: &str) {
+    // Arrange
+    let slice = "module Test";
+    let option = format!("{alias}=float64");
+    let options = options_with_aliases(&["f32=float32", &option]);
+
+    // Act
+    let state = parse(slice, Some(&options));
+    let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+    // Assert
+    let expected = Diagnostic::new(Error::InvalidPrimitiveAlias {
+        alias: option.clone(),
+        message: format!("'{alias}' is already the name of a type or another alias"),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
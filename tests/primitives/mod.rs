@@ -1,5 +1,6 @@
 // Copyright (c) ZeroC, Inc.
 
+mod aliases;
 mod mode_compatibility;
 
 use crate::test_helpers::*;
@@ -22,6 +23,9 @@ use test_case::test_case;
 #[test_case("float32", Primitive::Float32, "Slice2"; "float32")]
 #[test_case("float64", Primitive::Float64, "Slice2"; "float64")]
 #[test_case("string", Primitive::String, "Slice2"; "string")]
+#[test_case("uuid", Primitive::Uuid, "Slice2"; "uuid")]
+#[test_case("timestamp", Primitive::Timestamp, "Slice2"; "timestamp")]
+#[test_case("duration", Primitive::Duration, "Slice2"; "duration")]
 #[test_case("AnyClass", Primitive::AnyClass, "Slice1"; "AnyClass")]
 fn type_parses(slice_component: &str, expected: Primitive, mode: &str) {
     // Arrange
@@ -49,3 +53,37 @@ fn type_parses(slice_component: &str, expected: Primitive, mode: &str) {
         panic!("type alias was unpatched");
     }
 }
+
+#[test_case(Primitive::Uuid, Some(16); "uuid")]
+#[test_case(Primitive::Timestamp, Some(8); "timestamp")]
+#[test_case(Primitive::Duration, Some(8); "duration")]
+fn fixed_wire_size_is_correct(primitive: Primitive, expected: Option<u32>) {
+    // Act
+    let wire_size = primitive.fixed_wire_size();
+
+    // Assert
+    assert_eq!(wire_size, expected);
+}
+
+#[test_case("uuid"; "uuid")]
+#[test_case("timestamp"; "timestamp")]
+#[test_case("duration"; "duration")]
+fn built_in_type_names_can_be_used_as_identifiers(name: &str) {
+    // Arrange
+    let slice = format!(
+        "
+            module Test
+
+            struct S {{
+                {name}: {name}
+            }}
+        "
+    );
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>(&format!("Test::S::{name}")).unwrap();
+    assert_eq!(field.data_type.type_string(), name);
+}
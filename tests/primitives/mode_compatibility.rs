@@ -8,7 +8,7 @@ mod slice1 {
     use test_case::test_case;
 
     /// Verifies that if Slice1 is used with unsupported types (int8, uint16, uint32, varint32,
-    /// varuint32, uint64, varint62, and varuint62) that the compiler will produce the relevant not
+    /// varuint32, uint64, varint62, varuint62, uuid, timestamp, and duration) that the compiler will produce the relevant not
     /// supported errors.
     #[test_case("int8"; "int8")]
     #[test_case("uint16"; "uint16")]
@@ -18,6 +18,9 @@ mod slice1 {
     #[test_case("uint64"; "uint64")]
     #[test_case("varint62"; "varint62")]
     #[test_case("varuint62"; "varuint62")]
+    #[test_case("uuid"; "uuid")]
+    #[test_case("timestamp"; "timestamp")]
+    #[test_case("duration"; "duration")]
     fn unsupported_types_fail(value: &str) {
         // Test setup
         let slice = &format!(
@@ -106,8 +109,8 @@ mod slice2 {
     }
 
     /// Verifies that valid Slice2 types (bool, int8, uint8, int16, uint16, int32, uint32,
-    /// varint32, varuint32, int64, uint64, varint62, varuint62, float32, float64, and string) will
-    /// not produce any compiler errors.
+    /// varint32, varuint32, int64, uint64, varint62, varuint62, float32, float64, string, uuid,
+    /// timestamp, and duration) will not produce any compiler errors.
     #[test_case("bool"; "bool")]
     #[test_case("int8"; "int8")]
     #[test_case("uint8"; "uint8")]
@@ -124,6 +127,9 @@ mod slice2 {
     #[test_case("float32"; "float32")]
     #[test_case("float64"; "float64")]
     #[test_case("string"; "string")]
+    #[test_case("uuid"; "uuid")]
+    #[test_case("timestamp"; "timestamp")]
+    #[test_case("duration"; "duration")]
     fn supported_types_succeed(value: &str) {
         // Arrange
         let slice = format!(
@@ -4,6 +4,7 @@ mod test_helpers;
 
 mod redefinition {
     use crate::test_helpers::*;
+    use slicec::compile_from_strings;
     use slicec::diagnostics::{Diagnostic, Error};
     use slicec::slice_file::Span;
 
@@ -147,8 +148,9 @@ mod redefinition {
             })
             .set_span(&Span::new((11, 23).into(), (11, 24).into(), "string-0")),
             // The operation
-            Diagnostic::new(Error::Redefinition {
-                identifier: "b".to_owned(),
+            Diagnostic::new(Error::OverloadedOperation {
+                interface_identifier: "A".to_owned(),
+                operation_identifier: "b".to_owned(),
             })
             .set_span(&Span::new((15, 17).into(), (15, 18).into(), "string-0")),
             // The parameter
@@ -203,4 +205,91 @@ mod redefinition {
 
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test]
+    fn many_files_can_reopen_the_same_module() {
+        // Arrange
+        let slices = (0..50)
+            .map(|i| format!("module Foo::Bar\nstruct S{i} {{}}\ncustom C{i}"))
+            .collect::<Vec<_>>();
+        let slices = slices.iter().map(String::as_str).collect::<Vec<_>>();
+
+        // Act
+        let ast = parse_multiple_for_ast(&slices);
+
+        // Assert
+        for i in 0..50 {
+            assert_eq!(ast.find_definitions(&format!("Foo::Bar::S{i}")).len(), 1);
+            assert_eq!(ast.find_definitions(&format!("::Foo::Bar::C{i}")).len(), 1);
+        }
+        assert!(ast.find_definitions("Foo::Bar").is_empty());
+    }
+
+    #[test]
+    fn redefinitions_across_many_files_are_reported_against_the_first_definition() {
+        // Arrange
+        let slices = (0..10).map(|_| "module Foo\nstruct S {}").collect::<Vec<_>>();
+
+        // Act
+        let diagnostics = parse_multiple_for_diagnostics(&slices);
+
+        // Assert
+        let expected: [Diagnostic; 9] = std::array::from_fn(|i| {
+            Diagnostic::new(Error::Redefinition {
+                identifier: "S".to_owned(),
+            })
+            .set_span(&Span::new((2, 8).into(), (2, 9).into(), &format!("string-{}", i + 1)))
+            .add_note(
+                "'S' was previously defined here",
+                Some(&Span::new((2, 8).into(), (2, 9).into(), "string-0")),
+            )
+        });
+        check_diagnostics(diagnostics, expected);
+    }
+
+    #[test]
+    fn definition_index_contains_every_definition() {
+        // Arrange
+        let slice1 = "
+            module Foo
+            struct S {}
+        ";
+        let slice2 = "
+            module Foo
+            custom S
+        ";
+
+        // Act
+        let state = compile_from_strings(&[slice1, slice2], None, |_| {}, |_| {});
+
+        // Assert
+        let definitions = state.ast.find_definitions("Foo::S");
+        let spans = definitions.iter().map(|d| &d.raw_identifier().span).collect::<Vec<_>>();
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].kind(), "struct");
+        assert_eq!(definitions[1].kind(), "custom type");
+        assert_eq!(spans[0].file, "string-0");
+        assert_eq!(spans[1].file, "string-1");
+    }
+
+    #[test]
+    fn members_can_share_identifiers_with_module_scoped_definitions() {
+        // Arrange
+        let slice1 = "
+            module Foo
+            struct S {
+                a: int32
+            }
+        ";
+        let slice2 = "
+            module Foo::S
+            struct a {}
+        ";
+
+        // Act
+        let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+        // Assert
+        assert_eq!(ast.find_definitions("Foo::S::a").len(), 2);
+    }
 }
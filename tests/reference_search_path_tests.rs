@@ -0,0 +1,107 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::check_diagnostics;
+use slicec::compile_from_options;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::slice_options::SliceOptions;
+use slicec::utils::file_util::{reference_search_path, PROJECT_SEARCH_PATH_FILE, SLICE_PATH_VARIABLE};
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates an empty directory for a test to write its files to.
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("slicec-search-path-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory.canonicalize().unwrap()
+}
+
+// The search path depends on the process' environment and working directory, so the tests which modify them are
+// combined into a single test, to keep them from running in parallel.
+#[test]
+fn default_reference_directories_are_searched_after_references() {
+    // Arrange
+    let directory = test_directory("defaults");
+    for subdirectory in ["explicit", "project", "environment"] {
+        fs::create_dir_all(directory.join(subdirectory)).unwrap();
+    }
+    fs::write(
+        directory.join(PROJECT_SEARCH_PATH_FILE),
+        "# Project references\nproject\n\nmissing\nexplicit\n",
+    )
+    .unwrap();
+    let environment = std::env::join_paths([directory.join("environment"), directory.join("missing")]).unwrap();
+    std::env::set_var(SLICE_PATH_VARIABLE, environment);
+    std::env::set_current_dir(&directory).unwrap();
+
+    let options = SliceOptions {
+        references: vec!["explicit".to_owned()],
+        ..Default::default()
+    };
+    let no_default_options = SliceOptions {
+        no_default_references: true,
+        ..options.clone()
+    };
+
+    // Act
+    let search_path = reference_search_path(&options);
+    let no_default_search_path = reference_search_path(&no_default_options);
+
+    // Assert
+    let environment = directory.join("environment").display().to_string();
+    assert_eq!(search_path, ["explicit", "project", &environment]);
+    assert_eq!(no_default_search_path, ["explicit"]);
+}
+
+#[test]
+fn missing_definitions_list_the_reference_search_path() {
+    // Arrange
+    let directory = test_directory("note");
+    let source = directory.join("Source.slice");
+    fs::write(&source, "module Test\nstruct S { m: Missing }").unwrap();
+    let reference = directory.join("vendor").display().to_string();
+    fs::create_dir_all(&reference).unwrap();
+
+    let options = SliceOptions {
+        sources: vec![source.display().to_string()],
+        references: vec![reference.clone()],
+        no_default_references: true,
+        ..Default::default()
+    };
+
+    // Act
+    let state = compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Missing".to_owned(),
+    })
+    .add_note(format!("reference files were searched for in: '{reference}'"), None);
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn missing_definitions_note_when_no_references_were_searched() {
+    // Arrange
+    let directory = test_directory("empty");
+    let source = directory.join("Source.slice");
+    fs::write(&source, "module Test\nstruct S { m: Missing }").unwrap();
+
+    let options = SliceOptions {
+        sources: vec![source.display().to_string()],
+        no_default_references: true,
+        ..Default::default()
+    };
+
+    // Act
+    let state = compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Missing".to_owned(),
+    })
+    .add_note("no reference files were searched; they can be provided with '-R'", None);
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
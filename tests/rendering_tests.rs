@@ -0,0 +1,130 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::grammar::*;
+use slicec::render_signature;
+use slicec::rendering::render_hover;
+use slicec::test_helpers::parse_for_ast;
+use test_case::test_case;
+
+#[test_case("Test::S", "struct Test::S"; "struct")]
+#[test_case("Test::S::a", "[deprecated] tag(1) a: Test::T?"; "field")]
+#[test_case("Test::E", "unchecked enum Test::E : uint8"; "enum")]
+#[test_case("Test::E::B", "B = 5"; "enumerator")]
+#[test_case("Test::T", "typealias Test::T = Sequence<Test::E>"; "type alias")]
+#[test_case("Test::I", "interface Test::I : Test::Base"; "interface")]
+#[test_case("Test::I::op", "idempotent op(a: int32, b: stream bool) -> Test::S"; "operation")]
+#[test_case("Test::I::tupleOp", "tupleOp() -> (x: Test::T, tag(1) y: string?)"; "operation with return tuple")]
+#[test_case("Test::I::op::b", "b: stream bool"; "parameter")]
+#[test_case("Test::X", "[custom::attr(arg1, arg2)] const Test::X = 3"; "constant")]
+fn signatures_are_rendered(identifier: &str, expected: &str) {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            [deprecated] tag(1) a: T?
+        }
+        unchecked enum E : uint8 { A, B = 5 }
+        typealias T = Sequence<E>
+        interface Base {}
+        interface I : Base {
+            idempotent op(a: int32, b: stream bool) -> S
+            tupleOp() -> (x: T, tag(1) y: string?)
+        }
+        [custom::attr(arg1, arg2)]
+        const X = 3
+    ";
+    let ast = parse_for_ast(slice);
+
+    // Act
+    let entity = ast.find_element::<dyn Entity>(identifier).unwrap();
+    let signature = render_signature(entity);
+
+    // Assert
+    assert_eq!(signature, expected);
+}
+
+#[test_case("deprecated(\"use 'other' instead\")"; "deprecated")]
+#[test_case("compress(Args, Return)"; "compress")]
+#[test_case("slicedFormat(Return)"; "sliced format")]
+#[test_case("allow(Deprecated)"; "allow")]
+fn parsed_attributes_are_rendered_with_their_arguments(attribute: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+        interface I {{
+            [{attribute}]
+            op(a: int32) -> string
+        }}
+        "
+    );
+    let ast = parse_for_ast(slice);
+
+    // Act
+    let operation = ast.find_element::<dyn Entity>("Test::I::op").unwrap();
+    let signature = render_signature(operation);
+
+    // Assert
+    assert_eq!(signature, format!("[{attribute}] op(a: int32) -> string"));
+}
+
+#[test]
+fn unparsed_attribute_arguments_are_rendered_as_they_were_written() {
+    // Arrange
+    let slice = r#"
+        module Test
+        [custom::attr(0xFF, "a \"b\"", false, name)]
+        struct S {}
+    "#;
+    let ast = parse_for_ast(slice);
+
+    // Act
+    let struct_def = ast.find_element::<dyn Entity>("Test::S").unwrap();
+    let signature = render_signature(struct_def);
+
+    // Assert
+    assert_eq!(
+        signature,
+        r#"[custom::attr(0xFF, "a \"b\"", false, name)] struct Test::S"#
+    );
+}
+
+#[test]
+fn hover_includes_the_formatted_doc_comment() {
+    // Arrange
+    let slice = "
+        module Test
+        interface I {
+            /// Does something with {@link S}.
+            /// @param a: the first parameter.
+            /// @returns: a boolean.
+            /// @see S
+            op(a: int32) -> bool
+        }
+        struct S {}
+    ";
+    let ast = parse_for_ast(slice);
+    let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+    let parameter = ast.find_element::<Parameter>("Test::I::op::a").unwrap();
+
+    // Act
+    let operation_hover = render_hover(operation);
+    let parameter_hover = render_hover(parameter);
+
+    // Assert
+    let expected = "\
+op(a: int32) -> bool
+
+Does something with Test::S.
+
+Parameters:
+- a: the first parameter.
+
+Returns:
+- a boolean.
+
+See:
+- Test::S";
+    assert_eq!(operation_hover, expected);
+    assert_eq!(parameter_hover, "a: int32\n\nthe first parameter.");
+}
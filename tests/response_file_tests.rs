@@ -0,0 +1,110 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::response_files::{expand, split};
+use std::fs;
+use std::path::PathBuf;
+use test_case::test_case;
+
+/// Creates an empty directory for a test to write its response files to.
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("slicec-rsp-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test_case("a.slice  b.slice\n\tc.slice", &["a.slice", "b.slice", "c.slice"]; "whitespace")]
+#[test_case(r#""My Files/a.slice" -D"A B""#, &["My Files/a.slice", "-DA B"]; "double quotes")]
+#[test_case(r#""say \"hi\" \\""#, &[r#"say "hi" \"#]; "escapes in double quotes")]
+#[test_case(r"'C:\Slice Files\a.slice'", &[r"C:\Slice Files\a.slice"]; "single quotes")]
+#[test_case(r"My\ Files/a.slice \'", &["My Files/a.slice", "'"]; "escapes")]
+#[test_case("# sources\na.slice # trailing comment\n#b.slice\nc.slice", &["a.slice", "c.slice"]; "comments")]
+#[test_case("a#b.slice", &["a#b.slice"]; "hash within argument")]
+#[test_case("", &[]; "empty")]
+fn response_files_are_split_into_arguments(contents: &str, expected: &[&str]) {
+    // Act
+    let split_args = split(contents).unwrap();
+
+    // Assert
+    assert_eq!(split_args, expected);
+}
+
+#[test_case("'a.slice"; "single quote")]
+#[test_case("\"a.slice"; "double quote")]
+fn unterminated_quotes_are_rejected(contents: &str) {
+    // Act
+    let result = split(contents);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn response_files_are_expanded_in_place() {
+    // Arrange
+    let directory = test_directory("expand");
+    let outer = directory.join("outer.rsp");
+    let inner = directory.join("inner.rsp");
+    fs::write(&inner, "b.slice c.slice").unwrap();
+    fs::write(&outer, format!("a.slice '@{}' -R vendor", inner.display())).unwrap();
+
+    // Act
+    let expanded = expand(args(&["slicec", &format!("@{}", outer.display()), "--dry-run", "@"])).unwrap();
+
+    // Assert
+    let expected = [
+        "slicec",
+        "a.slice",
+        "b.slice",
+        "c.slice",
+        "-R",
+        "vendor",
+        "--dry-run",
+        "@",
+    ];
+    assert_eq!(expanded, expected);
+}
+
+#[test]
+fn response_files_can_be_used_more_than_once() {
+    // Arrange
+    let directory = test_directory("repeat");
+    let file = directory.join("args.rsp");
+    fs::write(&file, "a.slice").unwrap();
+    let arg = format!("@{}", file.display());
+
+    // Act
+    let expanded = expand(args(&[&arg, &arg])).unwrap();
+
+    // Assert
+    assert_eq!(expanded, ["a.slice", "a.slice"]);
+}
+
+#[test]
+fn recursive_response_files_are_rejected() {
+    // Arrange
+    let directory = test_directory("recursive");
+    let first = directory.join("first.rsp");
+    let second = directory.join("second.rsp");
+    fs::write(&first, format!("a.slice '@{}'", second.display())).unwrap();
+    fs::write(&second, format!("'@{}'", first.display())).unwrap();
+
+    // Act
+    let error = expand(args(&[&format!("@{}", first.display())])).unwrap_err();
+
+    // Assert
+    assert_eq!(error, format!("response file '{}' references itself", first.display()));
+}
+
+#[test]
+fn missing_response_files_are_rejected() {
+    // Act
+    let error = expand(args(&["@does-not-exist.rsp"])).unwrap_err();
+
+    // Assert
+    assert!(error.starts_with("unable to read response file 'does-not-exist.rsp'"));
+}
@@ -206,4 +206,174 @@ mod scope_resolution {
         });
         check_diagnostics(diagnostics, [expected]);
     }
+
+    mod shadowing {
+        use super::*;
+        use slicec::diagnostics::Lint;
+        use test_case::test_case;
+
+        const PARENT: &str = "
+            module A
+
+            struct S {}
+        ";
+
+        #[test]
+        fn shadowing_an_outer_definition_is_reported() {
+            // Arrange
+            let slice = "
+                module A::B
+
+                struct S {}
+                struct C {
+                    s: S
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_multiple_for_diagnostics(&[PARENT, slice]);
+
+            // Assert
+            let expected = Diagnostic::new(Lint::ShadowedReference {
+                identifier: "S".to_owned(),
+                chosen: "A::B::S".to_owned(),
+                shadowed: "A::S".to_owned(),
+            })
+            .add_note("'A::B::S' is defined here:", None)
+            .add_note("'A::S' is defined here:", None)
+            .add_note(
+                "use '::A::B::S' to keep referring to 'A::B::S', or '::A::S' to refer to 'A::S'",
+                None,
+            );
+            check_diagnostics(diagnostics, [expected]);
+        }
+
+        #[test_case("::A::B::S"; "globally scoped")]
+        #[test_case("B::S"; "partially scoped")]
+        fn scoped_references_are_not_reported(type_string: &str) {
+            // Arrange
+            let slice = format!(
+                "
+                module A::B
+
+                struct S {{}}
+                struct C {{
+                    s: {type_string}
+                }}
+                "
+            );
+
+            // Act
+            let diagnostics = parse_multiple_for_diagnostics(&[PARENT, &slice]);
+
+            // Assert
+            let expected: [Diagnostic; 0] = [];
+            check_diagnostics(diagnostics, expected);
+        }
+
+        #[test]
+        fn shadowing_can_be_allowed() {
+            // Arrange
+            let slice = "
+                module A::B
+
+                struct S {}
+                [allow(ShadowedReference)]
+                struct C {
+                    s: S
+                }
+            ";
+
+            // Act
+            let diagnostics = parse_multiple_for_diagnostics(&[PARENT, slice]);
+
+            // Assert
+            let expected: [Diagnostic; 0] = [];
+            check_diagnostics(diagnostics, expected);
+        }
+    }
+
+    mod navigation {
+        use super::*;
+
+        fn identifiers(entities: Vec<&dyn Entity>) -> Vec<String> {
+            entities.iter().map(|e| e.parser_scoped_identifier()).collect()
+        }
+
+        #[test]
+        fn ancestors_are_ordered_from_innermost_to_outermost() {
+            // Arrange
+            let slice = "
+                module A::B
+
+                interface I {
+                    op(p: int32)
+                }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let parameter = ast.find_element::<Parameter>("A::B::I::op::p").unwrap();
+            assert_eq!(identifiers(parameter.ancestors()), ["A::B::I::op", "A::B::I"]);
+            assert_eq!(parameter.enclosing_module().unwrap().nested_module_identifier(), "A::B");
+
+            let interface_def = ast.find_element::<Interface>("A::B::I").unwrap();
+            assert!(interface_def.parent_entity().is_none());
+            assert!(interface_def.ancestors().is_empty());
+        }
+
+        #[test]
+        fn fields_and_enumerators_have_parents() {
+            // Arrange
+            let slice = "
+                module A
+
+                struct S {
+                    f: int32
+                }
+                enum E : uint8 { X }
+            ";
+
+            // Act
+            let ast = parse_for_ast(slice);
+
+            // Assert
+            let field = ast.find_element::<Field>("A::S::f").unwrap();
+            let enumerator = ast.find_element::<Enumerator>("A::E::X").unwrap();
+            assert_eq!(identifiers(field.ancestors()), ["A::S"]);
+            assert_eq!(identifiers(enumerator.ancestors()), ["A::E"]);
+        }
+
+        #[test]
+        fn entities_in_scope_only_includes_direct_children() {
+            // Arrange
+            let slice1 = "
+                module A
+
+                struct S {
+                    f: int32
+                }
+                interface I {
+                    op1()
+                    op2(p: int32)
+                }
+            ";
+            let slice2 = "
+                module A::B
+
+                struct T {}
+            ";
+
+            // Act
+            let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+            // Assert
+            assert_eq!(identifiers(ast.entities_in_scope("A")), ["A::S", "A::I"]);
+            assert_eq!(identifiers(ast.entities_in_scope("::A::I")), ["A::I::op1", "A::I::op2"]);
+            assert_eq!(identifiers(ast.entities_in_scope("A::B")), ["A::B::T"]);
+            assert!(ast.entities_in_scope("C").is_empty());
+        }
+    }
 }
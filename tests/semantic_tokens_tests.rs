@@ -0,0 +1,170 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::semantic_tokens::{semantic_tokens, SemanticTokenKind};
+
+/// Compiles the provided slice and returns the text, kind, and whether it's a definition, of each of its tokens.
+fn classify(slice: &str) -> Vec<(String, SemanticTokenKind, bool)> {
+    let state = compile_from_strings(&[slice], None, |_| {}, |_| {});
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+
+    let slice_file = &state.files[0];
+    semantic_tokens(slice_file)
+        .into_iter()
+        .map(|token| {
+            let text = slice_file.snippet(&token.span).unwrap().to_owned();
+            (text, token.kind, token.is_definition)
+        })
+        .collect()
+}
+
+fn token(text: &str, kind: SemanticTokenKind, is_definition: bool) -> (String, SemanticTokenKind, bool) {
+    (text.to_owned(), kind, is_definition)
+}
+
+#[test]
+fn definitions_and_keywords_are_classified() {
+    // Arrange
+    let slice = "
+        module Foo::Bar
+        enum E : uint8 { A }
+        interface I {
+            op(p: int32) -> (x: bool, y: E)
+        }
+    ";
+
+    // Act
+    let tokens = classify(slice);
+
+    // Assert
+    use SemanticTokenKind::*;
+    let expected = [
+        token("module", Keyword, false),
+        token("Foo", Module, true),
+        token("Bar", Module, true),
+        token("enum", Keyword, false),
+        token("E", Enum, true),
+        token("uint8", Keyword, false),
+        token("A", Enumerator, true),
+        token("interface", Keyword, false),
+        token("I", Interface, true),
+        token("op", Operation, true),
+        token("p", Parameter, true),
+        token("int32", Keyword, false),
+        token("x", Parameter, true),
+        token("bool", Keyword, false),
+        token("y", Parameter, true),
+        token("E", Enum, false),
+    ];
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn references_are_classified_by_what_they_resolve_to() {
+    // Arrange
+    let slice = "
+        module Foo
+        struct S {}
+        custom C
+        typealias T = S
+        compact struct Wrapper {
+            a: Foo::S
+            b: [cs::type(\"int\")] C?
+            c: Sequence<T>
+        }
+    ";
+
+    // Act
+    let tokens = classify(slice);
+
+    // Assert
+    use SemanticTokenKind::*;
+    let references = tokens
+        .into_iter()
+        .filter(|t| !t.2 && t.1 != Keyword)
+        .collect::<Vec<_>>();
+    let expected = [
+        token("S", Struct, false),
+        token("Foo", Module, false),
+        token("S", Struct, false),
+        token("C", CustomType, false),
+        token("T", TypeAlias, false),
+    ];
+    assert_eq!(references, expected);
+}
+
+#[test]
+fn slice1_references_are_classified() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+        exception Base {}
+        exception E : Base {}
+        class A {}
+        class B : A {}
+        interface I {
+            op() throws E
+        }
+        interface J : I {}
+    ";
+
+    // Act
+    let tokens = classify(slice);
+
+    // Assert
+    use SemanticTokenKind::*;
+    let references = tokens
+        .into_iter()
+        .filter(|t| !t.2 && t.1 != Keyword)
+        .collect::<Vec<_>>();
+    let expected = [
+        token("Base", Exception, false),
+        token("A", Class, false),
+        token("E", Exception, false),
+        token("I", Interface, false),
+    ];
+    assert_eq!(references, expected);
+}
+
+#[test]
+fn nameless_definitions_are_skipped() {
+    // Arrange
+    let slice = "
+        module Test
+        interface I {
+            op() -> int32
+        }
+        struct S {
+            a: (b: bool, c: bool)
+        }
+    ";
+
+    // Act
+    let tokens = classify(slice);
+
+    // Assert
+    let texts = tokens.iter().map(|t| t.0.as_str()).collect::<Vec<_>>();
+    let expected = [
+        "module",
+        "Test",
+        "interface",
+        "I",
+        "op",
+        "int32",
+        "struct",
+        "S",
+        "a",
+        "b",
+        "bool",
+        "c",
+        "bool",
+    ];
+    assert_eq!(texts, expected);
+}
+
+#[test]
+fn lsp_token_types() {
+    assert_eq!(SemanticTokenKind::Module.lsp_token_type(), "namespace");
+    assert_eq!(SemanticTokenKind::Enumerator.lsp_token_type(), "enumMember");
+}
@@ -0,0 +1,118 @@
+// Copyright (c) ZeroC, Inc.
+
+use clap::Parser;
+use slicec::slice_options::{RunMode, SliceOptions, SliceOptionsBuilder};
+use test_case::test_case;
+
+#[test]
+fn built_options_match_parsed_options() {
+    // Arrange
+    let args = [
+        "slicec",
+        "a.slice",
+        "b.slice",
+        "-R",
+        "vendor",
+        "-D",
+        "DEBUG",
+        "-A",
+        "Deprecated",
+        "--target",
+        "cs",
+        "-X",
+        "cs:namespace=Foo",
+        "--dry-run",
+        "-O",
+        "generated",
+    ];
+
+    // Act
+    let built = SliceOptions::builder()
+        .sources(["a.slice", "b.slice"])
+        .reference("vendor")
+        .define("DEBUG")
+        .allow("Deprecated")
+        .target("cs")
+        .backend_option("cs:namespace=Foo")
+        .mode(RunMode::DryRun)
+        .output_dir("generated")
+        .build()
+        .unwrap();
+
+    // Assert
+    let parsed = SliceOptions::parse_from(args);
+    assert_eq!(format!("{built:?}"), format!("{parsed:?}"));
+}
+
+#[test_case(RunMode::Generate, false, false; "generate")]
+#[test_case(RunMode::DryRun, true, false; "dry run")]
+#[test_case(RunMode::Check, false, true; "check")]
+fn mode_sets_the_corresponding_flags(mode: RunMode, dry_run: bool, check: bool) {
+    // Act
+    let options = SliceOptions::builder()
+        .source("a.slice")
+        .mode(RunMode::Check)
+        .mode(mode)
+        .build()
+        .unwrap();
+
+    // Assert
+    assert_eq!(options.dry_run, dry_run);
+    assert_eq!(options.check, check);
+}
+
+#[test]
+fn sources_are_required() {
+    // Act
+    let result = SliceOptions::builder().reference("vendor").build();
+
+    // Assert
+    assert_eq!(result.unwrap_err(), "at least one source file must be provided");
+}
+
+#[test]
+fn sources_are_not_required_when_validating_references() {
+    // Act
+    let result = SliceOptions::builder()
+        .reference("vendor")
+        .validate_references(true)
+        .build();
+
+    // Assert
+    assert!(result.unwrap().validate_references);
+}
+
+#[test]
+fn lints_are_matched_case_insensitively() {
+    // Act
+    let result = SliceOptions::builder().source("a.slice").allow("deprecated").build();
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test_case(SliceOptions::builder().allow("NotALint"); "unknown lint")]
+#[test_case(SliceOptions::builder().backend_option("key=value"); "malformed backend option")]
+#[test_case(SliceOptions::builder().primitive_alias("f32"); "malformed primitive alias")]
+#[test_case(SliceOptions::builder().primitive_alias("module=float32"); "keyword primitive alias")]
+fn invalid_options_are_rejected(builder: SliceOptionsBuilder) {
+    // Act
+    let result = builder.source("a.slice").build();
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn baselines_can_be_updated() {
+    // Act
+    let options = SliceOptions::builder()
+        .source("a.slice")
+        .baseline("baseline.json", true)
+        .build()
+        .unwrap();
+
+    // Assert
+    assert_eq!(options.baseline.as_deref(), Some("baseline.json"));
+    assert!(options.update_baseline);
+}
@@ -0,0 +1,399 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::test_helpers::*;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+
+#[test]
+fn generic_structs_are_instantiated() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Pair<T, U> {
+            first: T
+            second: U?
+        }
+
+        struct S {
+            p: Pair<int32, string>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::S::p").unwrap();
+    let Types::Struct(instantiation) = field.data_type.concrete_type() else { panic!("expected a struct") };
+    assert_eq!(instantiation.parser_scoped_identifier(), "Test::PairInt32String");
+    assert!(!instantiation.is_generic());
+
+    let fields = instantiation.fields();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].identifier(), "first");
+    assert!(matches!(
+        fields[0].data_type.concrete_type(),
+        Types::Primitive(Primitive::Int32)
+    ));
+    assert!(!fields[0].data_type.is_optional);
+    assert!(matches!(
+        fields[1].data_type.concrete_type(),
+        Types::Primitive(Primitive::String)
+    ));
+    assert!(fields[1].data_type.is_optional);
+}
+
+#[test]
+fn type_parameters_are_patched_to_placeholders() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Box<T> {
+            value: Sequence<T>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let generic = ast.find_element::<Struct>("Test::Box").unwrap();
+    assert!(generic.is_generic());
+    assert_eq!(generic.type_parameters[0].value, "T");
+
+    let Types::Sequence(sequence) = generic.fields()[0].data_type.concrete_type() else {
+        panic!("expected a sequence")
+    };
+    let Types::CustomType(placeholder) = sequence.element_type.concrete_type() else {
+        panic!("expected a custom type")
+    };
+    assert_eq!(placeholder.identifier(), "T");
+}
+
+#[test]
+fn identical_instantiations_are_shared() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Box<T> {
+            value: T
+        }
+
+        typealias Number = int32
+
+        struct S {
+            a: Box<int32>
+            b: Box<Number>
+            c: Box<int32>?
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let instantiation_of = |identifier: &str| {
+        let field = ast.find_element::<Field>(identifier).unwrap();
+        match field.data_type.concrete_type() {
+            Types::Struct(struct_def) => struct_def.parser_scoped_identifier(),
+            _ => panic!("expected a struct"),
+        }
+    };
+    assert_eq!(instantiation_of("Test::S::a"), "Test::BoxInt32");
+    assert_eq!(instantiation_of("Test::S::b"), "Test::BoxNumber");
+    assert_eq!(instantiation_of("Test::S::c"), "Test::BoxInt32");
+    assert!(ast.find_element::<Field>("Test::S::c").unwrap().data_type.is_optional);
+}
+
+#[test]
+fn generic_structs_can_be_nested() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Box<T> {
+            value: T
+        }
+
+        struct S {
+            b: Box<Box<bool>>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::S::b").unwrap();
+    let Types::Struct(outer) = field.data_type.concrete_type() else { panic!("expected a struct") };
+    assert_eq!(outer.identifier(), "BoxBoxBool");
+
+    let Types::Struct(inner) = outer.fields()[0].data_type.concrete_type() else { panic!("expected a struct") };
+    assert_eq!(inner.identifier(), "BoxBool");
+    assert!(matches!(
+        inner.fields()[0].data_type.concrete_type(),
+        Types::Primitive(Primitive::Bool),
+    ));
+}
+
+#[test]
+fn wrong_number_of_type_arguments_is_disallowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Pair<T, U> {
+            first: T
+            second: U
+        }
+
+        struct S {
+            p: Pair<int32>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::WrongNumberOfTypeArguments {
+        identifier: "Pair".to_owned(),
+        expected: 2,
+        actual: 1,
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn generic_structs_must_have_type_arguments() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Box<T> {
+            value: T
+        }
+
+        struct S {
+            b: Box
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::WrongNumberOfTypeArguments {
+        identifier: "Box".to_owned(),
+        expected: 1,
+        actual: 0,
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn non_generic_structs_cannot_have_type_arguments() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Point {}
+
+        struct S {
+            p: Point<int32>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::WrongNumberOfTypeArguments {
+        identifier: "Point".to_owned(),
+        expected: 0,
+        actual: 1,
+    })
+    .add_note("'Point' isn't a generic struct", None);
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn primitives_cannot_have_type_arguments() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            s: string<bool>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::WrongNumberOfTypeArguments {
+        identifier: "string".to_owned(),
+        expected: 0,
+        actual: 1,
+    })
+    .add_note("'string' isn't a generic struct", None);
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn recursive_instantiations_are_disallowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Nested<T> {
+            inner: Nested<Sequence<T>>?
+        }
+
+        struct S {
+            n: Nested<int32>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::GenericInstantiationTooDeep {
+        identifier: "Nested".to_owned(),
+        max_depth: 16,
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn self_referential_instantiations_are_allowed() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Node<T> {
+            value: T
+            next: Node<T>?
+        }
+
+        struct S {
+            n: Node<int32>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::S::n").unwrap();
+    let Types::Struct(instantiation) = field.data_type.concrete_type() else { panic!("expected a struct") };
+    let Types::Struct(next) = instantiation.fields()[1].data_type.concrete_type() else { panic!("expected a struct") };
+    assert_eq!(
+        next.parser_scoped_identifier(),
+        instantiation.parser_scoped_identifier()
+    );
+}
+
+#[test]
+fn instantiations_with_the_same_name_are_given_unique_names() {
+    // Arrange
+    let slice1 = "
+        module A
+        struct Point {}
+    ";
+    let slice2 = "
+        module B
+        struct Point {}
+    ";
+    let slice3 = "
+        module Test
+
+        struct Pair<T> {
+            value: T
+        }
+
+        struct S {
+            a: Pair<A::Point>
+            b: Pair<B::Point>
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[slice1, slice2, slice3]);
+
+    // Assert
+    let instantiation_of = |identifier: &str| {
+        let field = ast.find_element::<Field>(identifier).unwrap();
+        let Types::Struct(struct_def) = field.data_type.concrete_type() else { panic!("expected a struct") };
+        let Types::Struct(point) = struct_def.fields()[0].data_type.concrete_type() else {
+            panic!("expected a struct")
+        };
+        (struct_def.parser_scoped_identifier(), point.parser_scoped_identifier())
+    };
+    let a = ("Test::PairPoint".to_owned(), "A::Point".to_owned());
+    let b = ("Test::PairPoint2".to_owned(), "B::Point".to_owned());
+    assert_eq!(instantiation_of("Test::S::a"), a);
+    assert_eq!(instantiation_of("Test::S::b"), b);
+}
+
+#[test]
+fn instantiations_do_not_redefine_existing_definitions() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Pair<T> {
+            value: T
+        }
+
+        struct PairInt32 {}
+
+        struct S {
+            p: Pair<int32>
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::S::p").unwrap();
+    let Types::Struct(instantiation) = field.data_type.concrete_type() else { panic!("expected a struct") };
+    assert_eq!(instantiation.parser_scoped_identifier(), "Test::PairInt322");
+    assert!(ast
+        .find_element::<Struct>("Test::PairInt32")
+        .unwrap()
+        .fields()
+        .is_empty());
+}
+
+#[test]
+fn missing_type_arguments_are_reported_once() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Pair<T> {
+            first: T
+            second: T
+        }
+
+        struct S {
+            p: Pair<Missing>
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Missing".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
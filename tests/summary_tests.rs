@@ -0,0 +1,155 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::summary::*;
+use std::collections::BTreeMap;
+
+/// Compiles the provided slices, treating each of them as a source file, and returns a summary of them.
+fn summarize(slices: &[&str]) -> Summary {
+    let mut state = compile_from_strings(slices, None, |_| {}, |_| {});
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+
+    for file in &mut state.files {
+        file.is_source = true;
+    }
+    Summary::from_files(&state.files)
+}
+
+fn counts(entries: &[(&str, usize)]) -> BTreeMap<String, usize> {
+    entries.iter().map(|&(kind, count)| (kind.to_owned(), count)).collect()
+}
+
+#[test]
+fn entities_are_counted_per_file_and_per_mode() {
+    // Arrange
+    let slice1 = "
+        mode = Slice1
+        module Test
+        class C {
+            a: int32
+        }
+    ";
+    let slice2 = "
+        module Test
+        interface I {
+            op(a: (b: bool, c: bool)) -> string
+        }
+        enum E : uint8 { A, B }
+    ";
+
+    // Act
+    let summary = summarize(&[slice1, slice2]);
+
+    // Assert
+    let expected_slice1 = counts(&[("class", 1), ("field", 1)]);
+    let expected_slice2 = counts(&[
+        ("enum", 1),
+        ("enumerator", 2),
+        ("interface", 1),
+        ("operation", 1),
+        ("parameter", 2),
+    ]);
+    assert_eq!(summary.files.len(), 2);
+    assert_eq!(summary.files[0].mode, "Slice1");
+    assert_eq!(summary.files[0].entities, expected_slice1);
+    assert_eq!(summary.files[1].mode, "Slice2");
+    assert_eq!(summary.files[1].entities, expected_slice2);
+
+    assert_eq!(summary.modes["Slice1"], expected_slice1);
+    assert_eq!(summary.modes["Slice2"], expected_slice2);
+    assert_eq!(summary.totals.values().sum::<usize>(), 9);
+}
+
+#[test]
+fn reference_files_are_not_summarized() {
+    // Arrange
+    let mut state = compile_from_strings(&["module Test\nstruct S {}"], None, |_| {}, |_| {});
+    state.files[0].is_source = false;
+
+    // Act
+    let summary = Summary::from_files(&state.files);
+
+    // Assert
+    assert!(summary.files.is_empty());
+    assert!(summary.totals.is_empty());
+}
+
+#[test]
+fn largest_types_are_sorted_by_size() {
+    // Arrange
+    let slice = "
+        module Test
+        struct Small {
+            a: int32
+        }
+        struct Large {
+            a: int32
+            b: int32
+            c: int32
+        }
+        enum E { A, B }
+    ";
+
+    // Act
+    let summary = summarize(&[slice]);
+
+    // Assert
+    let largest_types = summary
+        .largest_types
+        .iter()
+        .map(|t| (t.identifier.as_str(), t.kind, t.members))
+        .collect::<Vec<_>>();
+    let expected = [
+        ("Test::Large", "struct", 3),
+        ("Test::E", "enum", 2),
+        ("Test::Small", "struct", 1),
+    ];
+    assert_eq!(largest_types, expected);
+}
+
+#[test]
+fn deepest_inheritance_chains_are_listed() {
+    // Arrange
+    let slice = "
+        module Test
+        interface A {}
+        interface B : A {}
+        interface C {}
+        interface D : C, B {}
+    ";
+
+    // Act
+    let summary = summarize(&[slice]);
+
+    // Assert
+    let chains = summary
+        .deepest_inheritance
+        .iter()
+        .map(|chain| (chain.identifier.as_str(), chain.bases.clone()))
+        .collect::<Vec<_>>();
+    let expected = [
+        ("Test::D", vec!["Test::B".to_owned(), "Test::A".to_owned()]),
+        ("Test::B", vec!["Test::A".to_owned()]),
+    ];
+    assert_eq!(chains, expected);
+}
+
+#[test]
+fn report_lists_every_section() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+        class A {}
+        class B : A {}
+    ";
+
+    // Act
+    let report = summarize(&[slice]).to_report();
+
+    // Assert
+    assert!(report.contains("class                                              2           2"));
+    assert!(report.contains("string-0                                      Slice1           2"));
+    assert!(report.contains("Largest Types"));
+    assert!(report.contains("Test::B -> Test::A"));
+}
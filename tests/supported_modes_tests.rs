@@ -0,0 +1,175 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::grammar::*;
+use test_case::test_case;
+
+const SLICE1_DEFINITIONS: &str = "
+    mode = Slice1
+    module Test
+
+    compact struct P {
+        x: int32
+    }
+
+    class C {
+        p: P
+    }
+
+    interface I {
+        op(c: C) -> int32
+        other(p: P)
+    }
+";
+
+fn supported_modes_of(entity: &dyn Entity) -> Vec<Encoding> {
+    entity.supported_modes()[..].to_vec()
+}
+
+fn reasons_of(entity: &dyn Entity) -> Vec<(Encoding, String)> {
+    let restrictions = entity.explain_supported_modes();
+    restrictions.into_iter().map(|r| (r.encoding, r.reason)).collect()
+}
+
+#[test_case("Test::P", &[Encoding::Slice1, Encoding::Slice2]; "struct")]
+#[test_case("Test::P::x", &[Encoding::Slice1, Encoding::Slice2]; "field")]
+#[test_case("Test::C", &[Encoding::Slice1]; "class")]
+#[test_case("Test::C::p", &[Encoding::Slice1]; "class field")]
+#[test_case("Test::I", &[Encoding::Slice1, Encoding::Slice2]; "interface")]
+#[test_case("Test::I::op", &[Encoding::Slice1]; "operation")]
+#[test_case("Test::I::op::c", &[Encoding::Slice1]; "parameter")]
+#[test_case("Test::I::other", &[Encoding::Slice1, Encoding::Slice2]; "unrestricted operation")]
+fn every_entity_reports_its_supported_modes(identifier: &str, expected: &[Encoding]) {
+    // Arrange
+    let ast = parse_for_ast(SLICE1_DEFINITIONS);
+
+    // Act
+    let entity = ast.find_element::<dyn Entity>(identifier).unwrap();
+
+    // Assert
+    assert_eq!(supported_modes_of(entity), expected);
+}
+
+#[test]
+fn unrestricted_entities_have_no_explanations() {
+    // Arrange
+    let ast = parse_for_ast(SLICE1_DEFINITIONS);
+
+    // Act
+    let entity = ast.find_element::<dyn Entity>("Test::I::other").unwrap();
+
+    // Assert
+    assert!(entity.explain_supported_modes().is_empty());
+}
+
+#[test]
+fn restrictions_explain_which_member_caused_them() {
+    // Arrange
+    let ast = parse_for_ast(SLICE1_DEFINITIONS);
+    let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+    let parameter = ast.find_element::<Parameter>("Test::I::op::c").unwrap();
+
+    // Act
+    let operation_restrictions = operation.explain_supported_modes();
+    let parameter_restrictions = parameter.explain_supported_modes();
+
+    // Assert
+    let expected = vec![(Encoding::Slice2, "parameter 'c' isn't supported by Slice2".to_owned())];
+    assert_eq!(reasons_of(operation), expected);
+    assert_eq!(operation_restrictions[0].span.as_ref(), Some(parameter.span()));
+
+    let expected = vec![(Encoding::Slice2, "its type 'C' isn't supported by Slice2".to_owned())];
+    assert_eq!(reasons_of(parameter), expected);
+    assert_eq!(
+        parameter_restrictions[0].span.as_ref(),
+        Some(parameter.data_type().span())
+    );
+
+    let class_def = ast.find_element::<dyn Entity>("Test::C").unwrap();
+    let expected = vec![(Encoding::Slice2, "classes are only supported by Slice1".to_owned())];
+    assert_eq!(reasons_of(class_def), expected);
+}
+
+#[test]
+fn restrictions_explain_every_cause() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            x: int32
+            y: int8
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let struct_def = ast.find_element::<dyn Entity>("Test::S").unwrap();
+    let expected = vec![
+        (
+            Encoding::Slice1,
+            "only compact structs are supported by Slice1".to_owned(),
+        ),
+        (Encoding::Slice1, "field 'y' isn't supported by Slice1".to_owned()),
+    ];
+    assert_eq!(reasons_of(struct_def), expected);
+
+    let field = ast.find_element::<dyn Entity>("Test::S::x").unwrap();
+    let expected = vec![(Encoding::Slice1, "struct 'S' isn't supported by Slice1".to_owned())];
+    assert_eq!(reasons_of(field), expected);
+}
+
+#[test]
+fn untagged_optionals_are_explained() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {
+            op(a: string?, tag(1) b: string?)
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let a = ast.find_element::<dyn Entity>("Test::I::op::a").unwrap();
+    let expected = vec![(
+        Encoding::Slice1,
+        "optional types are only supported by Slice1 if they're tagged".to_owned(),
+    )];
+    assert_eq!(reasons_of(a), expected);
+
+    let b = ast.find_element::<dyn Entity>("Test::I::op::b").unwrap();
+    let expected = vec![(Encoding::Slice1, "interface 'I' isn't supported by Slice1".to_owned())];
+    assert_eq!(reasons_of(b), expected);
+}
+
+#[test]
+fn compilation_modes_are_explained_if_nothing_else_restricts_an_entity() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {}
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let interface_def = ast.find_element::<dyn Entity>("Test::I").unwrap();
+    assert_eq!(supported_modes_of(interface_def), [Encoding::Slice2]);
+    assert_eq!(
+        reasons_of(interface_def),
+        [(
+            Encoding::Slice1,
+            "it's defined in a file whose compilation mode is Slice2".to_owned()
+        )],
+    );
+}
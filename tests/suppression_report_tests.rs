@@ -0,0 +1,105 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::slice_options::SliceOptions;
+use slicec::suppression_report::*;
+
+/// Compiles the provided slice with the provided options, and returns a report of the diagnostics it suppressed.
+fn report_for(slice: &str, options: SliceOptions) -> SuppressionReport {
+    let state = compile_from_strings(&[slice], Some(&options), |_| {}, |_| {});
+    SuppressionReport::from_diagnostics(&state.into_diagnostics(&options))
+}
+
+#[test]
+fn suppressions_are_counted_per_lint_and_per_location() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [deprecated]
+        struct Old {}
+
+        [allow(Deprecated)]
+        struct S {
+            a: Old
+            b: Sequence<Old>
+        }
+
+        /// {@link Missing}
+        [allow(BrokenDocLink)]
+        struct T {}
+    ";
+
+    // Act
+    let report = report_for(slice, SliceOptions::default());
+
+    // Assert
+    assert_eq!(report.lints["Deprecated"], 2);
+    assert_eq!(report.lints["BrokenDocLink"], 1);
+    assert_eq!(report.suppressions.len(), 3);
+
+    let suppression = &report.suppressions[0];
+    assert_eq!(suppression.path.as_deref(), Some("string-0"));
+    assert_eq!((suppression.line, suppression.column), (Some(9), Some(16)));
+    assert_eq!(suppression.lint, "Deprecated");
+    assert_eq!(suppression.allowed_by, "attribute on 'Test::S'");
+    assert_eq!(suppression.count, 1);
+    assert_eq!(report.suppressions[2].allowed_by, "attribute on 'Test::T'");
+}
+
+#[test]
+fn suppressions_record_the_most_specific_allow() {
+    // Arrange
+    let slice = "
+        [[allow(Deprecated)]]
+        module Test
+
+        [deprecated]
+        struct Old {}
+
+        [allow(Deprecated)]
+        struct S {
+            a: Old
+        }
+
+        struct U {
+            a: Old
+        }
+    ";
+    let options = SliceOptions {
+        allowed_lints: vec!["Deprecated".to_owned()],
+        ..Default::default()
+    };
+
+    // Act
+    let report = report_for(slice, options);
+
+    // Assert
+    let allowed_by = report.suppressions.iter().map(|s| s.allowed_by.as_str());
+    assert_eq!(
+        allowed_by.collect::<Vec<_>>(),
+        ["attribute on 'Test::S'", "file attribute"],
+    );
+}
+
+#[test]
+fn reported_diagnostics_are_not_included() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [deprecated]
+        struct Old {}
+
+        struct S {
+            a: Old
+        }
+    ";
+
+    // Act
+    let report = report_for(slice, SliceOptions::default());
+
+    // Assert
+    assert!(report.lints.is_empty());
+    assert!(report.suppressions.is_empty());
+}
@@ -0,0 +1,71 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::symbol_tree::{Symbol, SymbolKind};
+
+/// Returns a compact representation of the provided symbols: each symbol's kind and name, followed by its children.
+fn outline_of(symbols: &[Symbol]) -> String {
+    let outlines = symbols
+        .iter()
+        .map(|symbol| match symbol.children.as_slice() {
+            [] => format!("{:?} {}", symbol.kind, symbol.name),
+            children => format!("{:?} {} [{}]", symbol.kind, symbol.name, outline_of(children)),
+        })
+        .collect::<Vec<_>>();
+    outlines.join(", ")
+}
+
+#[test]
+fn definitions_are_nested_under_their_module() {
+    // Arrange
+    let slice = "
+        module Foo::Bar
+        struct S {
+            a: int32
+            b: (c: bool, d: bool)
+        }
+        enum E { A(x: int8), B }
+        interface I {
+            op(p: string) -> (r1: bool, r2: bool)
+            opNameless() -> int32
+        }
+        custom C
+        typealias T = S
+    ";
+    let state = compile_from_strings(&[slice], None, |_| {}, |_| {});
+    assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+
+    // Act
+    let symbols = state.files[0].symbol_tree();
+
+    // Assert
+    let expected = "Module Foo::Bar [\
+        Struct S [Field a, Field b], \
+        Enum E [Enumerator A [Field x], Enumerator B], \
+        Interface I [Operation op [Parameter p, ReturnMember r1, ReturnMember r2], Operation opNameless], \
+        CustomType C, \
+        TypeAlias T\
+    ]";
+    assert_eq!(outline_of(&symbols), expected);
+}
+
+#[test]
+fn symbols_have_spans_for_their_definitions_and_identifiers() {
+    // Arrange
+    let slice = "module Test\nstruct S {\n    a: int32\n}\n";
+    let state = compile_from_strings(&[slice], None, |_| {}, |_| {});
+    let slice_file = &state.files[0];
+
+    // Act
+    let symbols = slice_file.symbol_tree();
+
+    // Assert
+    let struct_symbol = &symbols[0].children[0];
+    assert_eq!(struct_symbol.kind, SymbolKind::Struct);
+    assert_eq!(slice_file.snippet(&struct_symbol.identifier_span), Some("S"));
+    assert_eq!(slice_file.snippet(&struct_symbol.span), Some("struct S"));
+
+    let field_symbol = &struct_symbol.children[0];
+    assert_eq!(slice_file.snippet(&field_symbol.identifier_span), Some("a"));
+    assert_eq!(slice_file.snippet(&field_symbol.span), Some("a: int32"));
+}
@@ -235,8 +235,84 @@ mod tags {
 
         // Assert
         let expected = Diagnostic::new(Error::Syntax {
-            message: "expected one of 'integer literal' or '-', but found 'test string'".to_owned(),
+            message: "expected one of 'identifier', 'integer literal', '(', '::', '-', or '~', but found 'test string'"
+                .to_owned(),
         });
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test_case("bool", TagFormat::F1)]
+    #[test_case("int16", TagFormat::F2)]
+    #[test_case("float64", TagFormat::F8)]
+    #[test_case("string", TagFormat::OptimizedVSize)]
+    #[test_case("Sequence<uint8>", TagFormat::OptimizedVSize)]
+    #[test_case("Sequence<int32>", TagFormat::VSize)]
+    #[test_case("Sequence<string>", TagFormat::FSize)]
+    #[test_case("Fixed", TagFormat::VSize)]
+    #[test_case("E", TagFormat::Size)]
+    fn tagged_members_have_slice1_tag_formats(type_string: &str, expected: TagFormat) {
+        // Arrange
+        let slice = format!(
+            "
+            mode = Slice1
+            module Test
+
+            compact struct Fixed {{
+                a: int32
+            }}
+            enum E {{ A }}
+
+            interface I {{
+                op(tag(1) p: {type_string}?)
+            }}
+            "
+        );
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let parameter = ast.find_element::<Parameter>("Test::I::op::p").unwrap();
+        assert_eq!(parameter.tag_format(), Some(expected));
+    }
+
+    #[test]
+    fn tagged_members_are_size_prefixed_in_slice2() {
+        // Arrange
+        let slice = "
+            module Test
+            struct S {
+                tag(1) a: int32?
+                tag(2) b: Sequence<string>?
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let a = ast.find_element::<Field>("Test::S::a").unwrap();
+        let b = ast.find_element::<Field>("Test::S::b").unwrap();
+        assert_eq!(a.tag_format(), Some(TagFormat::VSize));
+        assert_eq!(b.tag_format(), Some(TagFormat::VSize));
+    }
+
+    #[test]
+    fn untagged_members_have_no_tag_format() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+            compact struct S {
+                a: int32
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let field = ast.find_element::<Field>("Test::S::a").unwrap();
+        assert_eq!(field.tag_format(), None);
+    }
 }
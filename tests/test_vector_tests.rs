@@ -0,0 +1,292 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod test_vectors {
+    use crate::test_helpers::*;
+    use serde_json::json;
+    use slicec::codec::decode;
+    use slicec::grammar::*;
+    use slicec::test_vectors::*;
+    use test_case::test_case;
+
+    fn find_vector<'a>(test_vectors: &'a TestVectors, type_name: &str, sample: SampleKind) -> &'a TestVector {
+        test_vectors
+            .vectors
+            .iter()
+            .find(|v| v.type_name == type_name && v.sample == sample)
+            .unwrap_or_else(|| panic!("no '{sample:?}' vector was generated for '{type_name}'"))
+    }
+
+    #[test_case(SampleKind::Zero, "0000000000"; "zero")]
+    #[test_case(SampleKind::Minimum, "0000008000"; "minimum")]
+    #[test_case(SampleKind::Maximum, "ffffff7fffffffffffffffff"; "maximum")]
+    fn compact_struct_vectors(sample: SampleKind, expected_bytes: &str) {
+        // Arrange
+        let slice = "
+            module Test
+
+            compact struct Point { x: int32, y: varuint62 }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let vector = find_vector(&test_vectors, "Test::Point", sample);
+        assert_eq!(vector.encoding, "Slice2");
+        assert_eq!(vector.bytes, expected_bytes);
+    }
+
+    #[test]
+    fn optional_and_tagged_fields_are_only_set_in_populated_samples() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                a: bool?
+                b: string
+                tag(1) c: uint8?
+            }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let zero = find_vector(&test_vectors, "Test::S", SampleKind::Zero);
+        assert_eq!(zero.bytes, "0000fc");
+        assert_eq!(zero.value, json!({ "a": null, "b": "", "c": null }));
+
+        let maximum = find_vector(&test_vectors, "Test::S", SampleKind::Maximum);
+        assert_eq!(maximum.bytes, "010124536c69636520e29c930404fffc");
+        assert_eq!(maximum.value, json!({ "a": true, "b": "Slice \u{2713}", "c": 255 }));
+    }
+
+    #[test_case("enum E : uint8 { A = 1, B = 5 }", "05"; "with underlying type")]
+    #[test_case("enum E { A, B = 40 }", "a100"; "without underlying type")]
+    fn enum_vectors(enum_def: &str, expected_bytes: &str) {
+        // Arrange
+        let slice = format!("module Test\n{enum_def}");
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let vector = find_vector(&test_vectors, "Test::E", SampleKind::Maximum);
+        assert_eq!(vector.bytes, expected_bytes);
+        assert_eq!(vector.value, json!("B"));
+    }
+
+    #[test]
+    fn recursive_types_produce_finite_samples() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Node { next: Node? }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let vector = find_vector(&test_vectors, "Test::Node", SampleKind::Maximum);
+        assert_eq!(vector.bytes, "010100fcfcfc");
+    }
+
+    #[test]
+    fn class_instances_are_encoded_as_slices() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class Base { i: int32 }
+            class Derived : Base { s: string }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let vector = find_vector(&test_vectors, "Test::Derived", SampleKind::Zero);
+        assert_eq!(vector.encoding, "Slice1");
+        assert_eq!(vector.bytes, "01010f3a3a546573743a3a44657269766564002000000000");
+        assert_eq!(vector.value, json!({ "i": 0, "s": "" }));
+    }
+
+    #[test]
+    fn slice1_tagged_fields_are_encoded_with_their_format() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class C { tag(2) s: string? }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        let vector = find_vector(&test_vectors, "Test::C", SampleKind::Maximum);
+        assert_eq!(vector.bytes, "0125093a3a546573743a3a431509536c69636520e29c93ff");
+    }
+
+    #[test]
+    fn types_containing_custom_types_are_skipped() {
+        // Arrange
+        let slice = "
+            module Test
+
+            custom C
+            struct S { c: C }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let test_vectors = generate_test_vectors(&ast);
+
+        // Assert
+        assert!(test_vectors.vectors.is_empty());
+        assert_eq!(test_vectors.skipped.len(), 1);
+        assert_eq!(test_vectors.skipped[0].type_name, "Test::S");
+        assert_eq!(
+            test_vectors.skipped[0].reason,
+            "the custom type 'Test::C' cannot be encoded by the compiler",
+        );
+    }
+
+    #[test]
+    fn test_vectors_can_be_serialized_to_json() {
+        // Arrange
+        let slice = "
+            module Test
+
+            compact struct S { b: bool }
+        ";
+        let ast = parse_for_ast(slice);
+
+        // Act
+        let json = generate_test_vectors(&ast).to_json();
+
+        // Assert
+        let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            document["vectors"][0],
+            json!({ "type": "Test::S", "encoding": "Slice2", "sample": "zero", "value": { "b": false }, "bytes": "00" }),
+        );
+    }
+
+    const RANDOM_SLICE: &str = "
+        module Test
+
+        enum E : int16 { A = -5, B, C = 300 }
+        unchecked enum Shape { Circle(radius: float32), Rectangle(width: uint16, height: uint16?) }
+
+        struct Node { next: Node? }
+        struct S {
+            a: bool?
+            b: Sequence<string?>
+            c: Dictionary<varint62, E?>
+            d: Result<uuid, Shape>
+            e: float64
+            f: Node
+            tag(1) g: int16?
+        }
+    ";
+
+    #[test]
+    fn random_values_are_reproducible() {
+        // Arrange
+        let ast = parse_for_ast(RANDOM_SLICE);
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        // Act
+        let first = ValueGenerator::random(42).generate(struct_def).unwrap();
+        let second = ValueGenerator::random(42).generate(struct_def).unwrap();
+        let other = ValueGenerator::random(43).generate(struct_def).unwrap();
+
+        // Assert
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn random_values_round_trip() {
+        // Arrange
+        let ast = parse_for_ast(RANDOM_SLICE);
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        for seed in 0..200 {
+            // Act
+            let mut generator = ValueGenerator::random(seed);
+            let (value, bytes) = generator.generate_encoded(struct_def, Encoding::Slice2, &ast).unwrap();
+
+            // Assert
+            let decoded = decode(&bytes, struct_def, Encoding::Slice2, &ast).unwrap();
+            assert_eq!(decoded, value, "seed: {seed}");
+        }
+    }
+
+    #[test]
+    fn random_slice1_values_round_trip() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class Base { i: int32, tag(1) s: string? }
+            class Derived : Base { b: Base?, tag(2) f: float32? }
+        ";
+        let ast = parse_for_ast(slice);
+        let class_def = ast.find_element::<Class>("Test::Derived").unwrap();
+
+        for seed in 0..200 {
+            // Act
+            let mut generator = ValueGenerator::random(seed);
+            let (value, bytes) = generator.generate_encoded(class_def, Encoding::Slice1, &ast).unwrap();
+
+            // Assert
+            let decoded = decode(&bytes, class_def, Encoding::Slice1, &ast).unwrap();
+            assert_eq!(decoded, value, "seed: {seed}");
+        }
+    }
+
+    #[test]
+    fn boundary_values_can_be_generated_for_any_type() {
+        // Arrange
+        let ast = parse_for_ast("module Test\ntypealias Bytes = Sequence<uint8>");
+        let alias = ast.find_element::<TypeAlias>("Test::Bytes").unwrap();
+        let mut generator = ValueGenerator::boundary(SampleKind::Maximum);
+
+        // Act
+        let (value, bytes) = generator.generate_encoded(alias, Encoding::Slice2, &ast).unwrap();
+
+        // Assert
+        assert_eq!(value.to_json(), json!([255]));
+        assert_eq!(bytes, [0x04, 0xFF]);
+    }
+
+    #[test]
+    fn fuzz_corpora_start_with_boundary_values() {
+        // Arrange
+        let ast = parse_for_ast("module Test\ncompact struct S { b: bool }");
+        let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+
+        // Act
+        let corpus = generate_fuzz_corpus(struct_def, Encoding::Slice2, &ast, 7, 10).unwrap();
+
+        // Assert
+        // 'S' only has 2 possible encodings, and duplicate payloads are removed.
+        assert_eq!(corpus, vec![vec![0x00], vec![0x01]]);
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use clap::Parser;
+use slicec::backend::{run_backends, Backend, BackendOptions};
+use slicec::compilation_state::CompilationState;
+use slicec::compile_from_strings;
+use slicec::slice_options::{SliceOptions, TimingsFormat};
+use std::time::Duration;
+use test_case::test_case;
+
+struct NoopBackend;
+
+impl Backend for NoopBackend {
+    fn name(&self) -> &str {
+        "noop"
+    }
+
+    fn generate(&self, _: &mut CompilationState, _: &BackendOptions) {}
+}
+
+fn phases_of(state: &CompilationState) -> Vec<&str> {
+    state
+        .timings
+        .phases
+        .iter()
+        .map(|timing| timing.phase.as_str())
+        .collect()
+}
+
+#[test]
+fn every_phase_of_compilation_is_timed() {
+    // Arrange
+    let slice1 = "module Foo";
+    let slice2 = "module Bar";
+
+    // Act
+    let state = compile_from_strings(&[slice1, slice2], None, |_| {}, |_| {});
+
+    // Assert
+    assert_eq!(phases_of(&state), ["preprocess", "lex", "parse", "patch", "validate"]);
+
+    let paths = state.timings.files.iter().map(|timing| timing.path.as_str());
+    assert_eq!(paths.collect::<Vec<_>>(), ["string-0", "string-1"]);
+}
+
+#[test]
+fn lexing_is_only_measured_if_timings_were_requested() {
+    // Arrange
+    let options = SliceOptions::parse_from(["slicec", "--timings", "test.slice"]);
+
+    // Act
+    let untimed_state = parse("module Test", None);
+    let timed_state = parse("module Test", Some(&options));
+
+    // Assert
+    assert_eq!(untimed_state.timings.files[0].lex, Duration::ZERO);
+    assert_ne!(timed_state.timings.files[0].lex, Duration::ZERO);
+}
+
+#[test]
+fn backend_phases_are_timed() {
+    // Arrange
+    let mut state = parse("module Test", None);
+
+    // Act
+    run_backends(&mut state, &SliceOptions::default(), &[&NoopBackend]);
+
+    // Assert
+    let phases = phases_of(&state);
+    assert_eq!(phases[phases.len() - 2..], ["validate (noop)", "generate (noop)"]);
+}
+
+#[test]
+fn timings_can_be_reported_as_json() {
+    // Arrange
+    let state = parse("module Test", None);
+
+    // Act
+    let json = state.timings.to_json();
+
+    // Assert
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["phases"][0]["phase"], "preprocess");
+    assert!(value["phases"][0]["milliseconds"].is_f64());
+    assert_eq!(value["files"][0]["path"], "string-0");
+    assert!(value["files"][0]["parse_milliseconds"].is_f64());
+}
+
+#[test]
+fn timings_can_be_reported_as_a_table() {
+    // Arrange
+    let state = parse("module Test", None);
+
+    // Act
+    let report = state.timings.to_report();
+
+    // Assert
+    let first_columns = report.lines().filter_map(|line| line.split_whitespace().next());
+    let expected = [
+        "Phase",
+        "preprocess",
+        "lex",
+        "parse",
+        "patch",
+        "validate",
+        "total",
+        "File",
+        "string-0",
+    ];
+    assert_eq!(first_columns.collect::<Vec<_>>(), expected);
+}
+
+#[test_case(&[], None; "not specified")]
+#[test_case(&["--timings"], Some(TimingsFormat::Human); "default format")]
+#[test_case(&["--timings=json"], Some(TimingsFormat::Json); "json format")]
+fn timings_option_is_parsed(args: &[&str], expected: Option<TimingsFormat>) {
+    // Act
+    let options = SliceOptions::parse_from(["slicec"].iter().chain(args).chain(&["test.slice"]));
+
+    // Assert
+    assert_eq!(options.timings, expected);
+}
+
+#[test]
+fn timings_can_be_written_as_trace_events() {
+    // Arrange
+    let state = parse("module Test", None);
+
+    // Act
+    let trace = state.timings.to_trace_json();
+
+    // Assert
+    let value: serde_json::Value = serde_json::from_str(&trace).unwrap();
+    let events = value["traceEvents"].as_array().unwrap();
+    let names = events
+        .iter()
+        .map(|e| (e["cat"].as_str().unwrap(), e["name"].as_str().unwrap()));
+    let expected = [
+        ("phase", "patch"),
+        ("phase", "validate"),
+        ("file", "string-0"),
+        ("file", "preprocess"),
+        ("file", "lex"),
+        ("file", "parse"),
+    ];
+    assert_eq!(names.collect::<Vec<_>>(), expected);
+
+    for event in events {
+        assert_eq!(event["ph"], "X");
+        assert!(event["ts"].is_f64() && event["dur"].is_f64());
+    }
+}
+
+#[test]
+fn trace_events_are_in_chronological_order() {
+    // Arrange
+    let state = parse("module Test", None);
+
+    // Act
+    let phases = &state.timings.phases;
+    let file = &state.timings.files[0];
+
+    // Assert
+    let patch_start = phases.iter().find(|p| p.phase == "patch").unwrap().start.unwrap();
+    let validate_start = phases.iter().find(|p| p.phase == "validate").unwrap().start.unwrap();
+    assert!(file.start <= patch_start);
+    assert!(patch_start <= validate_start);
+}
+
+#[test]
+fn trace_is_written_to_the_specified_file() {
+    // Arrange
+    let path = std::env::temp_dir().join("slicec-trace-test.json");
+    let path_string = path.to_str().unwrap();
+    let options = SliceOptions::parse_from(["slicec", "--trace", path_string, "test.slice"]);
+    let state = parse("module Test", Some(&options));
+
+    // Act
+    state.timings.emit(&options).unwrap();
+
+    // Assert
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, state.timings.to_trace_json());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,199 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::compile_from_strings;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+
+fn tuple_struct_of(type_ref: &TypeRef) -> &Struct {
+    let Types::Struct(struct_def) = type_ref.concrete_type() else { panic!("expected a struct") };
+    struct_def
+}
+
+#[test]
+fn field_tuples_are_desugared_into_compact_structs() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct Point {
+            pos: (x: int32, y: int32)
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Point::pos").unwrap();
+    let tuple_struct = tuple_struct_of(field.data_type());
+    assert_eq!(tuple_struct.parser_scoped_identifier(), "Test::PointPos");
+    assert!(tuple_struct.is_compact);
+    assert!(tuple_struct.is_synthesized());
+    assert!(!ast.find_element::<Struct>("Test::Point").unwrap().is_synthesized());
+
+    let field_identifiers = tuple_struct
+        .fields()
+        .iter()
+        .map(|f| f.parser_scoped_identifier())
+        .collect::<Vec<_>>();
+    assert_eq!(field_identifiers, ["Test::PointPos::x", "Test::PointPos::y"]);
+    assert!(ast.find_element::<Field>("Test::PointPos::x").is_ok());
+}
+
+#[test]
+fn parameter_tuples_are_desugared_into_compact_structs() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {
+            op(p: (x: int32, y: string)) -> (r: (z: bool), s: string)
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let parameter = ast.find_element::<Parameter>("Test::I::op::p").unwrap();
+    let return_member = ast.find_element::<Parameter>("Test::I::op::r").unwrap();
+    assert_eq!(tuple_struct_of(parameter.data_type()).identifier(), "IOpP");
+    assert_eq!(tuple_struct_of(return_member.data_type()).identifier(), "IOpR");
+}
+
+#[test]
+fn tuples_can_be_optional_and_have_attributes() {
+    // Arrange
+    let slice = r#"
+        module Test
+
+        struct S {
+            t: [cs::attribute("a")] (a: int32)?
+        }
+    "#;
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let data_type = ast.find_element::<Field>("Test::S::t").unwrap().data_type();
+    assert!(data_type.is_optional);
+    assert_eq!(data_type.attributes().len(), 1);
+    assert!(tuple_struct_of(data_type).attributes().is_empty());
+}
+
+#[test]
+fn synthesized_structs_are_definitions_of_their_file() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            t: (a: int32)
+        }
+    ";
+
+    // Act
+    let state = compile_from_strings(&[slice], None, |_| {}, |_| {});
+
+    // Assert
+    let definitions = state.files[0].contents.iter().map(|d| d.borrow().identifier());
+    assert_eq!(definitions.collect::<Vec<_>>(), ["S", "ST"]);
+}
+
+#[test]
+fn tuple_fields_are_validated() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            t: (tag(1) a: int32?)
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::CompactTypeCannotContainTaggedFields { kind: "struct" });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn synthesized_names_do_not_conflict_with_other_definitions() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            t: (a: int32)
+        }
+        struct ST {
+            b: string
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let tuple_struct = tuple_struct_of(ast.find_element::<Field>("Test::S::t").unwrap().data_type());
+    assert_eq!(tuple_struct.parser_scoped_identifier(), "Test::ST2");
+    assert!(ast.find_element::<Field>("Test::ST2::a").is_ok());
+    assert!(!ast.find_element::<Struct>("Test::ST").unwrap().is_synthesized());
+    assert!(ast.find_element::<Field>("Test::ST::b").is_ok());
+}
+
+#[test]
+fn synthesized_names_do_not_conflict_with_definitions_in_other_files() {
+    // Arrange
+    let slice1 = "
+        module Test
+
+        struct S {
+            t: (a: int32)
+        }
+    ";
+    let slice2 = "
+        module Test
+
+        struct ST {}
+        struct ST2 {}
+        struct Uses {
+            st: ST
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[slice1, slice2]);
+
+    // Assert
+    let tuple_struct = tuple_struct_of(ast.find_element::<Field>("Test::S::t").unwrap().data_type());
+    assert_eq!(tuple_struct.parser_scoped_identifier(), "Test::ST3");
+    let used_struct = ast.find_element::<Field>("Test::Uses::st").unwrap().data_type();
+    assert!(!tuple_struct_of(used_struct).is_synthesized());
+}
+
+#[test]
+fn nameless_return_types_are_synthesized() {
+    // Arrange
+    let slice = "
+        module Test
+
+        interface I {
+            op(p: int32) -> string
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+    assert!(operation.return_members()[0].is_synthesized());
+    assert!(!operation.parameters()[0].is_synthesized());
+}
@@ -0,0 +1,153 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::compile_from_strings;
+use slicec::slice_options::SliceOptions;
+use slicec::type_registry::*;
+
+/// Returns a registry of the types defined in the provided slice, treating it as a source file.
+fn registry_of(slice: &str) -> TypeRegistry {
+    let mut state = parse(slice, None);
+    state.files[0].is_source = true;
+    TypeRegistry::from_files(&state.files)
+}
+
+#[test]
+fn interfaces_and_their_operations_are_registered() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Foo::Bar
+
+        exception E {}
+
+        interface Base {}
+        interface I : Base {
+            idempotent op(a: int32, b: string) -> (x: bool, y: bool) throws E
+        }
+    ";
+
+    // Act
+    let registry = registry_of(slice);
+
+    // Assert
+    let expected_operation = OperationEntry {
+        identifier: "op".to_owned(),
+        is_idempotent: true,
+        parameters: vec!["a".to_owned(), "b".to_owned()],
+        return_members: vec!["x".to_owned(), "y".to_owned()],
+        exceptions: vec!["::Foo::Bar::E".to_owned()],
+    };
+    let expected = [
+        InterfaceEntry {
+            type_id: "::Foo::Bar::Base".to_owned(),
+            bases: vec![],
+            operations: vec![],
+        },
+        InterfaceEntry {
+            type_id: "::Foo::Bar::I".to_owned(),
+            bases: vec!["::Foo::Bar::Base".to_owned()],
+            operations: vec![expected_operation],
+        },
+    ];
+    assert_eq!(registry.interfaces, expected);
+}
+
+#[test]
+fn classes_and_exceptions_are_registered() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        class A(5) {}
+        class B : A {}
+        exception E {}
+        exception F : E {}
+    ";
+
+    // Act
+    let registry = registry_of(slice);
+
+    // Assert
+    let expected_classes = [
+        ClassEntry {
+            type_id: "::Test::A".to_owned(),
+            compact_id: Some(5),
+            base: None,
+        },
+        ClassEntry {
+            type_id: "::Test::B".to_owned(),
+            compact_id: None,
+            base: Some("::Test::A".to_owned()),
+        },
+    ];
+    let expected_exceptions = [
+        ExceptionEntry {
+            type_id: "::Test::E".to_owned(),
+            base: None,
+        },
+        ExceptionEntry {
+            type_id: "::Test::F".to_owned(),
+            base: Some("::Test::E".to_owned()),
+        },
+    ];
+    assert_eq!(registry.classes, expected_classes);
+    assert_eq!(registry.exceptions, expected_exceptions);
+}
+
+#[test]
+fn reference_files_are_not_registered() {
+    // Arrange
+    let source = "
+        module Test
+        interface I {}
+    ";
+    let reference = "
+        module Test
+        interface R {}
+    ";
+
+    // Act
+    let state = compile_from_strings(&[source, reference], None, |_| {}, |_| {});
+    let mut files = state.files;
+    files[0].is_source = true;
+    let registry = TypeRegistry::from_files(&files);
+
+    // Assert
+    let type_ids = registry
+        .interfaces
+        .iter()
+        .map(|i| i.type_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(type_ids, ["::Test::I"]);
+}
+
+#[test]
+fn registries_can_be_round_tripped_through_json() {
+    // Arrange
+    let slice = "
+        module Test
+        interface I {
+            op(a: int32)
+        }
+    ";
+    let registry = registry_of(slice);
+
+    // Act
+    let parsed = TypeRegistry::from_json(&registry.to_json()).unwrap();
+
+    // Assert
+    assert_eq!(parsed, registry);
+}
+
+#[test]
+fn registries_are_only_emitted_if_requested() {
+    // Arrange
+    let registry = TypeRegistry::default();
+
+    // Act/Assert
+    registry.emit(&SliceOptions::default()).unwrap();
+}
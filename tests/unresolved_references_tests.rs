@@ -0,0 +1,111 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::parse;
+use slicec::unresolved_references::*;
+
+#[test]
+fn missing_types_are_reported_with_the_scopes_that_were_searched() {
+    // Arrange
+    let slice = "
+        module Foo::Bar
+
+        struct S {
+            a: Missing
+        }
+    ";
+
+    // Act
+    let state = parse(slice, None);
+
+    // Assert
+    assert_eq!(state.unresolved_references.len(), 1);
+    let reference = &state.unresolved_references[0];
+    assert_eq!(reference.identifier, "Missing");
+    assert_eq!(reference.expected, "type");
+    assert_eq!(reference.found, None);
+    assert_eq!(reference.candidates, ["Foo::Bar::Missing", "Foo::Missing", "Missing"]);
+    assert_eq!((reference.span.start.row, reference.span.start.col), (5, 16));
+}
+
+#[test]
+fn references_of_the_wrong_kind_are_reported_with_what_was_found() {
+    // Arrange
+    let slice = "
+        mode = Slice1
+        module Test
+
+        struct S {}
+
+        class C : S {}
+    ";
+
+    // Act
+    let state = parse(slice, None);
+
+    // Assert
+    assert_eq!(state.unresolved_references.len(), 1);
+    let reference = &state.unresolved_references[0];
+    assert_eq!(reference.identifier, "S");
+    assert_eq!(reference.expected, "class");
+    assert_eq!(reference.found.as_deref(), Some("struct"));
+}
+
+#[test]
+fn resolved_references_are_not_reported() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {}
+
+        struct T {
+            s: S
+            b: bool
+        }
+    ";
+
+    // Act
+    let state = parse(slice, None);
+
+    // Assert
+    assert!(state.unresolved_references.is_empty());
+}
+
+#[test]
+fn globally_scoped_identifiers_are_only_searched_for_at_global_scope() {
+    // Act
+    let candidates = UnresolvedReference::candidates_for("::Foo::Missing", "Foo::Bar");
+
+    // Assert
+    assert_eq!(candidates, ["Foo::Missing"]);
+}
+
+#[test]
+fn report_is_sorted_by_location() {
+    // Arrange
+    let slice = "
+        module Test
+
+        struct S {
+            b: MissingB
+            a: MissingA
+        }
+    ";
+    let state = parse(slice, None);
+
+    // Act
+    let report = UnresolvedReferencesReport::from_references(&state.unresolved_references);
+
+    // Assert
+    let identifiers = report
+        .references
+        .iter()
+        .map(|r| r.identifier.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(identifiers, ["MissingB", "MissingA"]);
+    assert!(report
+        .to_json()
+        .starts_with(r#"{"references":[{"identifier":"MissingB""#));
+}
@@ -0,0 +1,104 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::upgrade::upgrade;
+use test_case::test_case;
+
+#[test]
+fn old_syntax_is_upgraded() {
+    // Arrange
+    let slice = "
+encoding = 2;
+
+// A module block; with a comment.
+module Foo
+{
+    module Bar
+    {
+        /// A struct.
+        [cs::type(\"long;\")]
+        compact struct S
+        {
+            a: long;
+            b: sequence<double>;
+            c: dictionary<varulong, short>, d: int;
+        };
+    };
+};
+";
+
+    // Act
+    let upgrade = upgrade(slice);
+
+    // Assert
+    let expected = "
+mode = Slice2
+
+// A module block; with a comment.
+module Foo::Bar
+        /// A struct.
+        [cs::type(\"long;\")]
+        compact struct S
+        {
+            a: int64
+            b: Sequence<float64>
+            c: Dictionary<varuint62, int16>, d: int32
+        }
+";
+    assert_eq!(upgrade.text, expected);
+    assert!(upgrade.issues.is_empty());
+    assert_parses(&upgrade.text);
+}
+
+#[test]
+fn changes_are_reported_with_their_locations() {
+    // Arrange
+    let slice = "module Test;\n\ntypealias T = long;\n";
+
+    // Act
+    let upgrade = upgrade(slice);
+
+    // Assert
+    let changes = upgrade
+        .changes
+        .iter()
+        .map(|c| (c.location.row, c.location.col, c.description.as_str()))
+        .collect::<Vec<_>>();
+    let expected = [
+        (1, 12, "removed the semicolon"),
+        (3, 15, "renamed 'long' to 'int64'"),
+        (3, 19, "removed the semicolon"),
+    ];
+    assert_eq!(changes, expected);
+}
+
+#[test_case("struct S { float: int32 }"; "identifiers named after old keywords")]
+#[test_case("struct S { a: \\long }"; "escaped identifiers")]
+#[test_case("enum E { encoding = 1 }"; "enumerators named encoding")]
+fn current_syntax_is_left_unchanged(definition: &str) {
+    // Arrange
+    let slice = format!("module Test\n{definition}\n");
+
+    // Act
+    let upgrade = upgrade(&slice);
+
+    // Assert
+    assert_eq!(upgrade.text, slice);
+    assert!(upgrade.is_up_to_date());
+}
+
+#[test]
+fn multiple_module_blocks_are_reported() {
+    // Arrange
+    let slice = "module A {}\nmodule B {}\n";
+
+    // Act
+    let upgrade = upgrade(slice);
+
+    // Assert
+    assert_eq!(upgrade.text, slice);
+    assert_eq!(upgrade.issues.len(), 1);
+    assert_eq!(upgrade.issues[0].location.row, 2);
+}
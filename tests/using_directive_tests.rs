@@ -0,0 +1,185 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::compile_from_strings;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use test_case::test_case;
+
+const DEFINITIONS: &str = "
+    module Foo::Bar::Baz
+
+    struct S {}
+";
+
+#[test_case("using Baz = Foo::Bar::Baz", "Baz::S"; "explicit alias")]
+#[test_case("using Foo::Bar::Baz", "Baz::S"; "implicit alias")]
+#[test_case("using ::Foo::Bar::Baz", "Baz::S"; "global target")]
+#[test_case("using Short = Foo::Bar", "Short::Baz::S"; "partial module")]
+#[test_case("using T = Foo::Bar::Baz::S", "T"; "type alias")]
+#[test_case("using /* c */ Baz = Foo::Bar::Baz", "Baz::S"; "block comment")]
+#[test_case("using // c\n Foo::Bar::Baz", "Baz::S"; "line comment")]
+fn using_directives_shorten_type_references(using_directive: &str, type_string: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+        {using_directive}
+
+        struct Wrapper {{
+            s: {type_string}
+        }}
+        "
+    );
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, &slice]);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Wrapper::s").unwrap();
+    let Types::Struct(struct_def) = field.data_type().concrete_type() else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Foo::Bar::Baz::S");
+}
+
+#[test]
+fn using_directives_apply_to_type_alias_underlying_types() {
+    // Arrange
+    let slice = "
+        module Test
+        using Baz = Foo::Bar::Baz
+
+        typealias Alias = Sequence<Baz::S>
+        struct Wrapper {
+            s: Alias
+        }
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, slice]);
+
+    // Assert
+    let field = ast.find_element::<Field>("Test::Wrapper::s").unwrap();
+    let Types::Sequence(sequence) = field.data_type().concrete_type() else { panic!() };
+    let Types::Struct(struct_def) = sequence.element_type.concrete_type() else { panic!() };
+    assert_eq!(struct_def.parser_scoped_identifier(), "Foo::Bar::Baz::S");
+}
+
+#[test]
+fn using_directives_are_stored_in_their_file() {
+    // Arrange
+    let slice = "
+        module Test
+        using Baz = Foo::Bar::Baz
+    ";
+
+    // Act
+    let state = compile_from_strings(&[DEFINITIONS, slice], None, |_| {}, |_| {});
+
+    // Assert
+    assert!(state.files[0].using_directives.is_empty());
+    let using_directive = &state.files[1].using_directives[0];
+    assert_eq!(using_directive.alias.value, "Baz");
+    assert_eq!(using_directive.target_identifier(), "Foo::Bar::Baz");
+}
+
+#[test]
+fn using_directives_only_apply_to_their_own_file() {
+    // Arrange
+    let slice1 = "
+        module Test
+        using Baz = Foo::Bar::Baz
+    ";
+    let slice2 = "
+        module Test
+
+        struct Wrapper {
+            s: Baz::S
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, slice1, slice2]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Baz::S".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn aliases_cannot_be_redefined() {
+    // Arrange
+    let slice = "
+        module Test
+        using Baz = Foo::Bar::Baz
+        using Foo::Bar::Baz
+    ";
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, slice]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::Redefinition {
+        identifier: "Baz".to_owned(),
+    })
+    .add_note("'Baz' was previously defined here", None);
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test_case("Foo::Missing"; "missing module")]
+#[test_case("Foo::Bar::Baz::T"; "missing type")]
+#[test_case("Foo::Ba"; "partial segment")]
+fn targets_must_exist(target: &str) {
+    // Arrange
+    let slice = format!(
+        "
+        module Test
+        using Alias = {target}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_multiple_for_diagnostics(&[DEFINITIONS, &slice]);
+
+    // Assert
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: target.to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn using_can_be_used_as_an_identifier() {
+    // Arrange
+    let slice = "
+        module Test
+        using ::Foo::Bar
+
+        struct S {
+            using: int32
+        }
+
+        enum E {
+            using
+            B
+        }
+
+        interface I {
+            op(using: Bar::Baz::S) -> using
+        }
+
+        custom using
+    ";
+
+    // Act
+    let ast = parse_multiple_for_ast(&[DEFINITIONS, slice]);
+
+    // Assert
+    assert!(ast.find_element::<Field>("Test::S::using").is_ok());
+    assert!(ast.find_element::<Enumerator>("Test::E::using").is_ok());
+    assert!(ast.find_element::<Parameter>("Test::I::op::using").is_ok());
+    assert!(ast.find_element::<CustomType>("Test::using").is_ok());
+}
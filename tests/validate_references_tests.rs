@@ -0,0 +1,54 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::check_diagnostics;
+use clap::Parser;
+use slicec::compile_from_options;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::slice_options::SliceOptions;
+use std::fs;
+
+#[test]
+fn references_can_be_validated_without_sources() {
+    // Arrange
+    let directory = std::env::temp_dir().join(format!("slicec-validate-references-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("Shared.slice"), "module Shared\nstruct S { m: Missing }").unwrap();
+    let reference = directory.display().to_string();
+
+    let options = SliceOptions::builder()
+        .reference(&reference)
+        .validate_references(true)
+        .build()
+        .unwrap();
+    let options = SliceOptions {
+        no_default_references: true,
+        ..options
+    };
+
+    // Act
+    let state = compile_from_options(&options, |_| {}, |_| {});
+
+    // Assert
+    assert_eq!(state.files.len(), 1);
+    assert!(!state.files[0].is_source);
+
+    let expected = Diagnostic::new(Error::DoesNotExist {
+        identifier: "Missing".to_owned(),
+    })
+    .add_note(format!("reference files were searched for in: '{reference}'"), None);
+    check_diagnostics(state.diagnostics.into_inner(), [expected]);
+}
+
+#[test]
+fn sources_are_only_optional_when_validating_references() {
+    // Act
+    let with_flag = SliceOptions::try_parse_from(["slicec", "-R", "vendor", "--validate-references"]);
+    let without_flag = SliceOptions::try_parse_from(["slicec", "-R", "vendor"]);
+
+    // Assert
+    assert!(with_flag.is_ok());
+    assert!(without_flag.is_err());
+}
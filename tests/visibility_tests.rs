@@ -0,0 +1,209 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use crate::test_helpers::*;
+use slicec::diagnostics::{Diagnostic, Error};
+use slicec::grammar::*;
+use slicec::slice_file::Span;
+use test_case::test_case;
+
+#[test]
+fn definitions_are_public_by_default() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            a: int32
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let struct_def = ast.find_element::<Struct>("Test::S").unwrap();
+    let field = ast.find_element::<Field>("Test::S::a").unwrap();
+    assert_eq!(struct_def.visibility(), Visibility::Public);
+    assert_eq!(field.visibility(), Visibility::Public);
+}
+
+#[test]
+fn members_share_the_visibility_of_their_parent() {
+    // Arrange
+    let slice = "
+        module Test
+        [internal]
+        interface I {
+            op(a: int32)
+        }
+    ";
+
+    // Act
+    let ast = parse_for_ast(slice);
+
+    // Assert
+    let interface_def = ast.find_element::<Interface>("Test::I").unwrap();
+    let operation = ast.find_element::<Operation>("Test::I::op").unwrap();
+    let parameter = &operation.parameters()[0];
+    assert_eq!(interface_def.visibility(), Visibility::Internal);
+    assert_eq!(operation.visibility(), Visibility::Internal);
+    assert_eq!(parameter.visibility(), Visibility::Internal);
+}
+
+#[test]
+fn internal_attribute_cannot_be_applied_to_members() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            [internal] a: int32
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::UnexpectedAttribute {
+        attribute: "internal".to_owned(),
+    })
+    .add_note(
+        "the internal attribute can only be applied to definitions; their members share their visibility",
+        None,
+    );
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn public_types_cannot_expose_internal_types() {
+    // Arrange
+    let slice = "
+        module Test
+
+        [internal]
+        struct Secret {}
+
+        struct S {
+            a: Secret
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::InternalTypeExposed {
+        kind: "struct",
+        identifier: "S".to_owned(),
+        type_identifier: "Secret".to_owned(),
+    })
+    .set_span(&Span::new((8, 16).into(), (8, 22).into(), "string-0"))
+    .add_note(
+        "'Secret' was marked internal here",
+        Some(&Span::new((4, 10).into(), (4, 18).into(), "string-0")),
+    );
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test_case("a: Sequence<Secret>"; "sequence element")]
+#[test_case("a: Dictionary<int32, Secret>"; "dictionary value")]
+#[test_case("a: Result<bool, Secret>"; "result failure")]
+#[test_case("a: (b: bool, c: Secret)"; "tuple element")]
+fn internal_types_nested_in_anonymous_types_are_reported(field: &str) {
+    // Arrange
+    let slice = format!(
+        "
+            module Test
+            [internal] enum Secret {{ A }}
+            struct S {{
+                {field}
+            }}
+        "
+    );
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = Diagnostic::new(Error::InternalTypeExposed {
+        kind: "struct",
+        identifier: "S".to_owned(),
+        type_identifier: "Secret".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn operations_of_public_interfaces_cannot_expose_internal_types() {
+    // Arrange
+    let slice = "
+        module Test
+        [internal] custom Secret
+        interface I {
+            op(a: Secret) -> Secret
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert
+    let expected = || {
+        Diagnostic::new(Error::InternalTypeExposed {
+            kind: "interface",
+            identifier: "I".to_owned(),
+            type_identifier: "Secret".to_owned(),
+        })
+    };
+    check_diagnostics(diagnostics, [expected(), expected()]);
+}
+
+#[test]
+fn public_type_aliases_cannot_alias_internal_types() {
+    // Arrange
+    let slice = "
+        module Test
+        [internal] struct Secret {}
+        typealias Alias = Secret
+        struct S {
+            a: Alias
+        }
+    ";
+
+    // Act
+    let diagnostics = parse_for_diagnostics(slice);
+
+    // Assert: only the alias is reported, since `Alias` itself is public.
+    let expected = Diagnostic::new(Error::InternalTypeExposed {
+        kind: "type alias",
+        identifier: "Alias".to_owned(),
+        type_identifier: "Secret".to_owned(),
+    });
+    check_diagnostics(diagnostics, [expected]);
+}
+
+#[test]
+fn internal_types_can_use_internal_types() {
+    // Arrange
+    let slice = "
+        module Test
+        [internal] struct Secret {}
+        [internal] struct S {
+            a: Secret
+        }
+        [internal] interface I {
+            op() -> Sequence<Secret>
+        }
+    ";
+
+    // Act/Assert
+    assert_parses(slice);
+}
+
+#[test]
+fn visibility_keywords() {
+    assert_eq!(Visibility::Public.keyword(), "public");
+    assert_eq!(Visibility::Internal.keyword(), "internal");
+    assert_eq!(Visibility::Internal.to_string(), "internal");
+}
@@ -0,0 +1,302 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::compile_from_strings;
+use slicec::grammar::*;
+use slicec::slice_file::SliceFile;
+use slicec::visitor::{
+    visit_definitions, visit_module_subtree, visit_types, walk, walk_entity, EntityVisitor, VisitControl, Visitor,
+};
+
+const SLICE: &str = "
+    module Test
+
+    struct S {
+        a: int32
+        b: string
+    }
+
+    interface I {
+        op(x: bool)
+    }
+
+    enum E : uint8 { A }
+    const C = 5
+";
+
+/// Records the identifier of every element it visits, and returns `control` after visiting `target`.
+#[derive(Default)]
+struct Recorder {
+    visited: Vec<String>,
+    target: &'static str,
+    control: VisitControl,
+}
+
+impl Recorder {
+    fn record(&mut self, element: &dyn NamedSymbol) -> VisitControl {
+        self.visited.push(element.identifier().to_owned());
+        match element.identifier() == self.target {
+            true => self.control,
+            false => VisitControl::Continue,
+        }
+    }
+}
+
+impl Visitor for Recorder {
+    fn visit_file(&mut self, _: &SliceFile) -> VisitControl {
+        self.visited.push("file".to_owned());
+        VisitControl::Continue
+    }
+
+    fn visit_module(&mut self, module_def: &Module) -> VisitControl {
+        self.record(module_def)
+    }
+
+    fn visit_struct(&mut self, struct_def: &Struct) -> VisitControl {
+        self.record(struct_def)
+    }
+
+    fn visit_interface(&mut self, interface_def: &Interface) -> VisitControl {
+        self.record(interface_def)
+    }
+
+    fn visit_enum(&mut self, enum_def: &Enum) -> VisitControl {
+        self.record(enum_def)
+    }
+
+    fn visit_operation(&mut self, operation: &Operation) -> VisitControl {
+        self.record(operation)
+    }
+
+    fn visit_constant(&mut self, constant: &Constant) -> VisitControl {
+        self.record(constant)
+    }
+
+    fn visit_field(&mut self, field: &Field) -> VisitControl {
+        self.record(field)
+    }
+
+    fn visit_parameter(&mut self, parameter: &Parameter) -> VisitControl {
+        self.record(parameter)
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) -> VisitControl {
+        self.record(enumerator)
+    }
+}
+
+fn visit(target: &'static str, control: VisitControl) -> (Vec<String>, VisitControl) {
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = Recorder {
+        target,
+        control,
+        ..Default::default()
+    };
+    let result = state.files[0].visit_with(&mut recorder);
+    (recorder.visited, result)
+}
+
+#[test]
+fn everything_is_visited_by_default() {
+    // Act
+    let (visited, result) = visit("S", VisitControl::Continue);
+
+    // Assert
+    let expected = ["file", "Test", "S", "a", "b", "I", "op", "x", "E", "A", "C"];
+    assert_eq!(visited, expected);
+    assert_eq!(result, VisitControl::Continue);
+}
+
+#[test]
+fn skipping_children_skips_only_the_element_contents() {
+    // Act
+    let (visited, result) = visit("S", VisitControl::SkipChildren);
+
+    // Assert
+    let expected = ["file", "Test", "S", "I", "op", "x", "E", "A", "C"];
+    assert_eq!(visited, expected);
+    assert_eq!(result, VisitControl::Continue);
+}
+
+#[test]
+fn stopping_ends_visitation() {
+    // Act
+    let (visited, result) = visit("op", VisitControl::Stop);
+
+    // Assert
+    let expected = ["file", "Test", "S", "a", "b", "I", "op"];
+    assert_eq!(visited, expected);
+    assert_eq!(result, VisitControl::Stop);
+}
+
+#[test]
+fn visit_types_only_visits_type_definitions() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = Recorder::default();
+
+    // Act
+    visit_types(&state.files, &mut recorder);
+
+    // Assert
+    assert_eq!(recorder.visited, ["S", "a", "b", "E", "A"]);
+}
+
+#[test]
+fn visit_definitions_only_visits_matching_definitions() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = Recorder::default();
+
+    // Act
+    visit_definitions(
+        &state.files,
+        |entity| entity.parser_scoped_identifier() == "Test::I",
+        &mut recorder,
+    );
+
+    // Assert
+    assert_eq!(recorder.visited, ["I", "op", "x"]);
+}
+
+#[test]
+fn visit_module_subtree_only_visits_nested_modules() {
+    // Arrange
+    let slices = [
+        "module Test\nstruct A {}",
+        "module Test::Inner\nstruct B {}",
+        "module Testing\nstruct C {}",
+        "module Other\nstruct D {}",
+    ];
+    let state = compile_from_strings(&slices, None, |_| {}, |_| {});
+    let mut recorder = Recorder::default();
+
+    // Act
+    visit_module_subtree(&state.files, "Test", &mut recorder);
+
+    // Assert
+    assert_eq!(recorder.visited, ["file", "Test", "A", "file", "Inner", "B"]);
+}
+
+/// Records each entity it enters and leaves, along with the identifiers of its parents.
+#[derive(Default)]
+struct EntityRecorder {
+    events: Vec<String>,
+    skip: &'static str,
+}
+
+impl EntityRecorder {
+    fn record(&mut self, event: &str, entity: &dyn Entity, parents: &[&dyn Entity]) {
+        let parents = parents.iter().map(|parent| parent.identifier()).collect::<Vec<_>>();
+        let event = format!("{event} {} [{}]", entity.identifier(), parents.join(", "));
+        self.events.push(event);
+    }
+}
+
+impl EntityVisitor for EntityRecorder {
+    fn enter(&mut self, entity: &dyn Entity, parents: &[&dyn Entity]) -> VisitControl {
+        self.record("enter", entity, parents);
+        match entity.identifier() == self.skip {
+            true => VisitControl::SkipChildren,
+            false => VisitControl::Continue,
+        }
+    }
+
+    fn leave(&mut self, entity: &dyn Entity, parents: &[&dyn Entity]) {
+        self.record("leave", entity, parents);
+    }
+}
+
+#[test]
+fn entities_are_walked_with_their_parents() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = EntityRecorder::default();
+    let interface_def = state.ast.find_element::<Interface>("Test::I").unwrap();
+
+    // Act
+    walk_entity(interface_def, &mut recorder);
+
+    // Assert
+    let expected = [
+        "enter I []",
+        "enter op [I]",
+        "enter x [I, op]",
+        "leave x [I, op]",
+        "leave op [I]",
+        "leave I []",
+    ];
+    assert_eq!(recorder.events, expected);
+}
+
+#[test]
+fn parents_start_with_the_ancestors_of_the_walked_entity() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = EntityRecorder::default();
+    let operation = state.ast.find_element::<Operation>("Test::I::op").unwrap();
+
+    // Act
+    walk_entity(operation, &mut recorder);
+
+    // Assert
+    let expected = ["enter op [I]", "enter x [I, op]", "leave x [I, op]", "leave op [I]"];
+    assert_eq!(recorder.events, expected);
+}
+
+#[test]
+fn skipped_entities_are_still_left() {
+    // Arrange
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut recorder = EntityRecorder {
+        skip: "S",
+        ..Default::default()
+    };
+
+    // Act
+    walk(&state.files, &mut recorder);
+
+    // Assert
+    assert_eq!(recorder.events[..2], ["enter S []", "leave S []"]);
+    assert_eq!(recorder.events.len(), 2 + 6 + 4 + 2);
+}
+
+#[test]
+fn post_order_walks_can_compute_aggregates() {
+    // Arrange
+    /// Computes the number of entities contained by each entity, and the deepest nesting of any entity.
+    #[derive(Default)]
+    struct Sizes {
+        stack: Vec<usize>,
+        sizes: Vec<(String, usize)>,
+        max_depth: usize,
+    }
+
+    impl EntityVisitor for Sizes {
+        fn enter(&mut self, _: &dyn Entity, parents: &[&dyn Entity]) -> VisitControl {
+            self.max_depth = self.max_depth.max(parents.len());
+            self.stack.push(0);
+            VisitControl::Continue
+        }
+
+        fn leave(&mut self, entity: &dyn Entity, _: &[&dyn Entity]) {
+            let size = self.stack.pop().unwrap();
+            if let Some(parent_size) = self.stack.last_mut() {
+                *parent_size += size + 1;
+            }
+            self.sizes.push((entity.identifier().to_owned(), size));
+        }
+    }
+
+    let state = compile_from_strings(&[SLICE], None, |_| {}, |_| {});
+    let mut sizes = Sizes::default();
+
+    // Act
+    let result = walk(&state.files, &mut sizes);
+
+    // Assert
+    let entity_sizes = sizes.sizes.iter().map(|(name, size)| format!("{name} {size}"));
+    let expected = ["a 0", "b 0", "S 2", "x 0", "op 1", "I 2", "A 0", "E 1", "C 0"];
+    assert_eq!(entity_sizes.collect::<Vec<_>>(), expected);
+    assert_eq!(sizes.max_depth, 2);
+    assert_eq!(result, VisitControl::Continue);
+}
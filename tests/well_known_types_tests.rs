@@ -0,0 +1,151 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod well_known_types {
+    use crate::test_helpers::*;
+    use slicec::ast::Ast;
+    use slicec::diagnostics::{Diagnostic, Error};
+    use slicec::grammar::attributes::Unparsed;
+    use slicec::grammar::*;
+    use slicec::slice_options::SliceOptions;
+    use slicec::well_known_types::FILE_NAME;
+    use test_case::test_case;
+
+    #[test_case("Duration")]
+    #[test_case("Timestamp")]
+    #[test_case("Uuid")]
+    #[test_case("Uri")]
+    fn can_be_used_when_enabled(type_name: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            struct S {{
+                value: WellKnownTypes::{type_name}
+            }}
+            "
+        );
+        let options = SliceOptions {
+            well_known_types: true,
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+
+        // Assert
+        assert!(!state.diagnostics.has_errors(), "{:?}", state.diagnostics);
+        let custom_type = state
+            .ast
+            .find_element::<CustomType>(&format!("WellKnownTypes::{type_name}"));
+        assert!(custom_type.is_ok());
+    }
+
+    #[test_case("Duration", "System.TimeSpan")]
+    #[test_case("Timestamp", "System.DateTime")]
+    #[test_case("Uuid", "System.Guid")]
+    #[test_case("Uri", "System.Uri")]
+    fn are_mapped_to_csharp_types_by_default(type_name: &str, expected: &str) {
+        // Arrange
+        let options = SliceOptions {
+            well_known_types: true,
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse("module Test", Some(&options));
+
+        // Assert
+        let custom_type = state
+            .ast
+            .find_element::<CustomType>(&format!("WellKnownTypes::{type_name}"))
+            .unwrap();
+        let attributes = custom_type.attributes();
+        assert_eq!(attributes.len(), 1);
+        let unparsed = attributes[0].downcast::<Unparsed>().unwrap();
+        assert_eq!(unparsed.directive, "cs::type");
+        assert_eq!(unparsed.args, vec![expected.to_owned()]);
+    }
+
+    #[test]
+    fn are_not_defined_in_any_file() {
+        // Arrange
+        let options = SliceOptions {
+            well_known_types: true,
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse("module Test", Some(&options));
+
+        // Assert
+        assert_eq!(state.files.len(), 1);
+        let custom_type = state.ast.find_element::<CustomType>("WellKnownTypes::Uri").unwrap();
+        assert_eq!(custom_type.span().file, FILE_NAME);
+    }
+
+    #[test]
+    fn can_be_mapped_by_backends() {
+        // Arrange
+        let mut ast = Ast::create();
+        ast.add_well_known_types();
+
+        // Act
+        let mapped_well_known_type = ast.add_well_known_type_mapping("Uri", "java::type", "java.net.URI");
+        let mapped_unknown_type = ast.add_well_known_type_mapping("Foo", "java::type", "java.net.URI");
+
+        // Assert
+        assert!(mapped_well_known_type);
+        assert!(!mapped_unknown_type);
+        let custom_type = ast.find_element::<CustomType>("WellKnownTypes::Uri").unwrap();
+        let directives = custom_type.attributes().into_iter().map(|a| a.kind.directive());
+        assert_eq!(directives.collect::<Vec<_>>(), vec!["cs::type", "java::type"]);
+    }
+
+    #[test]
+    fn cannot_be_redefined() {
+        // Arrange
+        let slice = "
+            module WellKnownTypes
+
+            custom Uri
+        ";
+        let options = SliceOptions {
+            well_known_types: true,
+            ..Default::default()
+        };
+
+        // Act
+        let state = parse(slice, Some(&options));
+        let diagnostics = diagnostics_from_compilation_state(state, &options);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "Uri".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn are_not_available_by_default() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                value: WellKnownTypes::Duration
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::DoesNotExist {
+            identifier: "WellKnownTypes::Duration".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+}
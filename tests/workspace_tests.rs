@@ -0,0 +1,90 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::grammar::Interface;
+use slicec::slice_options::SliceOptions;
+use slicec::workspace::Workspace;
+use std::sync::Arc;
+
+fn options_with_sources(sources: &[&str]) -> SliceOptions {
+    SliceOptions {
+        sources: sources.iter().map(|&source| source.to_owned()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn units_are_compiled_independently() {
+    // Arrange
+    let mut workspace = Workspace::new();
+    workspace.add_unit("first", options_with_sources(&["tests/files/test.slice"]));
+    workspace.add_unit("second", options_with_sources(&["tests/files/a.slice"]));
+
+    // Act
+    let results = workspace.compile_all(|_| {}, |_| {});
+
+    // Assert
+    assert_eq!(results.len(), 2);
+    let (first_name, first_state) = &results[0];
+    let (second_name, second_state) = &results[1];
+    assert_eq!((first_name.as_str(), second_name.as_str()), ("first", "second"));
+
+    assert!(first_state.ast.find_element::<Interface>("Test::I").is_ok());
+    assert!(second_state.ast.find_element::<Interface>("Test::I").is_err());
+}
+
+#[test]
+fn shared_references_are_read_once_and_shared_between_units() {
+    // Arrange
+    let mut workspace = Workspace::new();
+    workspace.add_shared_reference("tests/files/test.slice");
+    workspace.add_unit("first", options_with_sources(&["tests/files/a.slice"]));
+    workspace.add_unit("second", options_with_sources(&["tests/files/b.slice"]));
+
+    // Act
+    let results = workspace.compile_all(|_| {}, |_| {});
+
+    // Assert
+    let reference_text = |index: usize| {
+        let state = &results[index].1;
+        let file = state.files.iter().find(|f| f.relative_path == "tests/files/test.slice");
+        file.unwrap().raw_text.clone()
+    };
+    assert!(Arc::ptr_eq(&reference_text(0), &reference_text(1)));
+    assert!(results[1].1.ast.find_element::<Interface>("Test::I").is_ok());
+    assert_eq!(workspace.file_cache().len(), 3);
+}
+
+#[test]
+fn invalidated_files_are_read_again() {
+    // Arrange
+    let mut workspace = Workspace::new();
+    workspace.add_unit("unit", options_with_sources(&["tests/files/test.slice"]));
+    let first_state = workspace.compile_unit("unit", |_| {}, |_| {}).unwrap();
+
+    // Act
+    let was_cached = workspace.file_cache().invalidate("tests/files/test.slice");
+    let second_state = workspace.compile_unit("unit", |_| {}, |_| {}).unwrap();
+
+    // Assert
+    assert!(was_cached);
+    assert!(!Arc::ptr_eq(
+        &first_state.files[0].raw_text,
+        &second_state.files[0].raw_text
+    ));
+    assert_eq!(first_state.files[0].raw_text, second_state.files[0].raw_text);
+}
+
+#[test]
+fn adding_a_unit_with_an_existing_name_replaces_it() {
+    // Arrange
+    let mut workspace = Workspace::new();
+    workspace.add_unit("unit", options_with_sources(&["tests/files/a.slice"]));
+
+    // Act
+    workspace.add_unit("unit", options_with_sources(&["tests/files/b.slice"]));
+
+    // Assert
+    assert_eq!(workspace.units().len(), 1);
+    assert_eq!(workspace.units()[0].options.sources, ["tests/files/b.slice"]);
+    assert!(workspace.compile_unit("missing", |_| {}, |_| {}).is_none());
+}